@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::models::calendar::date_calculation_rules::{DateCalculationRulesModel, DateRulePurpose, DateShiftRule};
+use crate::repository::create_batch::CreateBatch;
+
+use super::heapless;
+
+/// Builder of `DateCalculationRulesModel` test data
+///
+/// Defaults to an active country wide date shift rule moving to the next business
+/// day, effective from 2024-01-01 without expiry. The country is required.
+pub struct DateCalculationRulesFixture {
+    model: DateCalculationRulesModel,
+}
+
+impl DateCalculationRulesFixture {
+    pub fn builder(country_id: Uuid) -> Self {
+        Self {
+            model: DateCalculationRulesModel {
+                id: Uuid::new_v4(),
+                country_id,
+                country_subdivision_id: None,
+                rule_name: heapless("rule_name", "Test Rule"),
+                rule_purpose: DateRulePurpose::DateShift,
+                default_shift_rule: DateShiftRule::NextBusinessDay,
+                weekend_days_id: None,
+                priority: 1,
+                is_active: true,
+                effective_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                expiry_date: None,
+            },
+        }
+    }
+
+    pub fn rule_name(mut self, rule_name: &str) -> Self {
+        self.model.rule_name = heapless("rule_name", rule_name);
+        self
+    }
+
+    pub fn country_subdivision_id(mut self, country_subdivision_id: Uuid) -> Self {
+        self.model.country_subdivision_id = Some(country_subdivision_id);
+        self
+    }
+
+    pub fn rule_purpose(mut self, rule_purpose: DateRulePurpose) -> Self {
+        self.model.rule_purpose = rule_purpose;
+        self
+    }
+
+    pub fn default_shift_rule(mut self, default_shift_rule: DateShiftRule) -> Self {
+        self.model.default_shift_rule = default_shift_rule;
+        self
+    }
+
+    pub fn weekend_days_id(mut self, weekend_days_id: Uuid) -> Self {
+        self.model.weekend_days_id = Some(weekend_days_id);
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.model.priority = priority;
+        self
+    }
+
+    pub fn effective(mut self, effective_date: NaiveDate, expiry_date: Option<NaiveDate>) -> Self {
+        self.model.effective_date = effective_date;
+        self.model.expiry_date = expiry_date;
+        self
+    }
+
+    /// Change any other field of the model
+    pub fn with(mut self, change: impl FnOnce(&mut DateCalculationRulesModel)) -> Self {
+        change(&mut self.model);
+        self
+    }
+
+    /// The rule, checked to not expire before it takes effect
+    pub fn build(self) -> DateCalculationRulesModel {
+        let model = self.model;
+        if let Some(expiry_date) = model.expiry_date {
+            assert!(
+                model.effective_date <= expiry_date,
+                "Rule expiry_date {expiry_date} is before effective_date {}",
+                model.effective_date
+            );
+        }
+        model
+    }
+
+    /// Build the rule and create it through `repo`
+    pub async fn persist<R>(
+        self,
+        repo: &R,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DateCalculationRulesModel, Box<dyn Error + Send + Sync>>
+    where
+        R: CreateBatch<Postgres, DateCalculationRulesModel> + ?Sized,
+    {
+        super::persist(self.build(), repo, audit_log_id).await
+    }
+}
@@ -0,0 +1,80 @@
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::models::person::location::{LocationModel, LocationType};
+use crate::repository::create_batch::CreateBatch;
+
+use super::heapless;
+
+/// Builder of `LocationModel` test data
+///
+/// Defaults to a residential location without coordinates. The locality is
+/// required, there is no sensible default for it.
+pub struct LocationFixture {
+    model: LocationModel,
+}
+
+impl LocationFixture {
+    pub fn builder(locality_id: Uuid) -> Self {
+        Self {
+            model: LocationModel {
+                id: Uuid::new_v4(),
+                street_line1: heapless("street_line1", "1 Test Street"),
+                street_line2: None,
+                street_line3: None,
+                street_line4: None,
+                locality_id,
+                postal_code: None,
+                latitude: None,
+                longitude: None,
+                accuracy_meters: None,
+                location_type: LocationType::Residential,
+                antecedent_hash: 0,
+                antecedent_audit_log_id: Uuid::nil(),
+                hash: 0,
+                audit_log_id: None,
+            },
+        }
+    }
+
+    pub fn street_line1(mut self, street_line1: &str) -> Self {
+        self.model.street_line1 = heapless("street_line1", street_line1);
+        self
+    }
+
+    pub fn postal_code(mut self, postal_code: &str) -> Self {
+        self.model.postal_code = Some(heapless("postal_code", postal_code));
+        self
+    }
+
+    pub fn location_type(mut self, location_type: LocationType) -> Self {
+        self.model.location_type = location_type;
+        self
+    }
+
+    /// Change any other field of the model
+    pub fn with(mut self, change: impl FnOnce(&mut LocationModel)) -> Self {
+        change(&mut self.model);
+        self
+    }
+
+    /// The location, checked to have both coordinates or none
+    pub fn build(self) -> LocationModel {
+        let model = self.model;
+        assert_eq!(
+            model.latitude.is_some(),
+            model.longitude.is_some(),
+            "Location has only one of latitude and longitude"
+        );
+        model
+    }
+
+    /// Build the location and create it through `repo`
+    pub async fn persist<R>(self, repo: &R, audit_log_id: Option<Uuid>) -> Result<LocationModel, Box<dyn Error + Send + Sync>>
+    where
+        R: CreateBatch<Postgres, LocationModel> + ?Sized,
+    {
+        super::persist(self.build(), repo, audit_log_id).await
+    }
+}
@@ -0,0 +1,51 @@
+//! Test data builders for the models
+//!
+//! Each fixture starts from defaults that pass the model's validations, lets a test
+//! set the fields it cares about and checks the result in `build`. Fixtures of
+//! persisted models also have `persist`, which creates the built model through any
+//! `CreateBatch` repository.
+//!
+//! Available to this crate's tests and, with the `test-utils` feature, to other crates.
+
+pub mod date_calculation_rules;
+pub mod location;
+pub mod person;
+pub mod product;
+pub mod reason;
+
+pub use date_calculation_rules::DateCalculationRulesFixture;
+pub use location::LocationFixture;
+pub use person::PersonFixture;
+pub use product::ProductFixture;
+pub use reason::ReasonFixture;
+
+use heapless::String as HeaplessString;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::models::identifiable::Identifiable;
+use crate::repository::create_batch::CreateBatch;
+
+/// Convert `value` for `field`, panicking with the field name when it does not fit
+pub(crate) fn heapless<const N: usize>(field: &str, value: &str) -> HeaplessString<N> {
+    HeaplessString::try_from(value)
+        .unwrap_or_else(|_| panic!("{field}: {value:?} is longer than {N} bytes"))
+}
+
+/// Create `model` through `repo` and return the persisted model
+pub(crate) async fn persist<T, R>(
+    model: T,
+    repo: &R,
+    audit_log_id: Option<Uuid>,
+) -> Result<T, Box<dyn Error + Send + Sync>>
+where
+    T: Identifiable + Send,
+    R: CreateBatch<Postgres, T> + ?Sized,
+{
+    repo.create_batch(vec![model], audit_log_id)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Repository returned no persisted model".into())
+}
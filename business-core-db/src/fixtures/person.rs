@@ -0,0 +1,141 @@
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::models::person::common_enums::{PersonStatus, RiskRating};
+use crate::models::person::person::{IdentityType, PersonModel, PersonType};
+use crate::repository::create_batch::CreateBatch;
+
+use super::heapless;
+
+/// Builder of `PersonModel` test data
+///
+/// Defaults to an active, low risk natural person with a national id.
+pub struct PersonFixture {
+    model: PersonModel,
+}
+
+impl PersonFixture {
+    pub fn builder() -> Self {
+        Self {
+            model: PersonModel::builder()
+                .display_name("Test Person")
+                .identity(IdentityType::NationalId, "TEST123456")
+                .build()
+                .expect("Default test person is valid"),
+        }
+    }
+
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.model.id = id;
+        self
+    }
+
+    pub fn display_name(mut self, display_name: &str) -> Self {
+        self.model.display_name = heapless("display_name", display_name);
+        self
+    }
+
+    pub fn person_type(mut self, person_type: PersonType) -> Self {
+        self.model.person_type = person_type;
+        self
+    }
+
+    pub fn risk_rating(mut self, risk_rating: RiskRating) -> Self {
+        self.model.risk_rating = risk_rating;
+        self
+    }
+
+    pub fn status(mut self, status: PersonStatus) -> Self {
+        self.model.status = status;
+        self
+    }
+
+    pub fn external_identifier(mut self, external_identifier: &str) -> Self {
+        self.model.external_identifier = Some(heapless("external_identifier", external_identifier));
+        self
+    }
+
+    pub fn identity(mut self, id_type: IdentityType, id_number: &str) -> Self {
+        self.model.id_type = id_type;
+        self.model.id_number = heapless("id_number", id_number);
+        self
+    }
+
+    pub fn organization_person_id(mut self, organization_person_id: Uuid) -> Self {
+        self.model.organization_person_id = Some(organization_person_id);
+        self
+    }
+
+    pub fn department(mut self, department: &str) -> Self {
+        self.model.department = Some(heapless("department", department));
+        self
+    }
+
+    pub fn location_id(mut self, location_id: Uuid) -> Self {
+        self.model.location_id = Some(location_id);
+        self
+    }
+
+    pub fn duplicate_of_person_id(mut self, duplicate_of_person_id: Uuid) -> Self {
+        self.model.duplicate_of_person_id = Some(duplicate_of_person_id);
+        self
+    }
+
+    /// Change any other field of the model
+    pub fn with(mut self, change: impl FnOnce(&mut PersonModel)) -> Self {
+        change(&mut self.model);
+        self
+    }
+
+    /// The person, checked to not reference itself as organization or duplicate
+    pub fn build(self) -> PersonModel {
+        let model = self.model;
+        assert_ne!(model.organization_person_id, Some(model.id), "Person is its own organization");
+        assert_ne!(model.duplicate_of_person_id, Some(model.id), "Person is a duplicate of itself");
+        model
+    }
+
+    /// Build the person and create it through `repo`
+    pub async fn persist<R>(self, repo: &R, audit_log_id: Option<Uuid>) -> Result<PersonModel, Box<dyn Error + Send + Sync>>
+    where
+        R: CreateBatch<Postgres, PersonModel> + ?Sized,
+    {
+        super::persist(self.build(), repo, audit_log_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PersonFixture;
+    use crate::models::person::common_enums::RiskRating;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_person_fixture() {
+        let person = PersonFixture::builder()
+            .display_name("Jane Doe")
+            .risk_rating(RiskRating::High)
+            .with(|person| person.entity_reference_count = 2)
+            .build();
+
+        assert_eq!(person.display_name.as_str(), "Jane Doe");
+        assert_eq!(person.risk_rating, RiskRating::High);
+        assert_eq!(person.entity_reference_count, 2);
+        assert_eq!(person.hash, 0);
+        assert!(person.audit_log_id.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "display_name")]
+    fn test_person_fixture_rejects_long_name() {
+        PersonFixture::builder().display_name(&"x".repeat(101));
+    }
+
+    #[test]
+    #[should_panic(expected = "own organization")]
+    fn test_person_fixture_rejects_self_reference() {
+        let id = Uuid::new_v4();
+        PersonFixture::builder().id(id).organization_person_id(id).build();
+    }
+}
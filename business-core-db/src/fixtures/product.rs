@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::models::product::product::{ProductModel, ProductType};
+use crate::models::product::product_rules::{PostingFrequency, ProductAccrualFrequency, ProductRules};
+
+use super::heapless;
+
+/// Builder of `ProductModel` test data
+///
+/// Defaults to an active XAF savings (CASA) product valid from 2024-01-01, posting
+/// interest monthly on the daily balance, without fees. There is no product
+/// repository, so the fixture has no `persist`.
+pub struct ProductFixture {
+    model: ProductModel,
+}
+
+impl ProductFixture {
+    pub fn builder() -> Self {
+        Self {
+            model: ProductModel {
+                id: Uuid::new_v4(),
+                name_l1: heapless("name_l1", "Savings"),
+                name_l2: heapless("name_l2", ""),
+                name_l3: heapless("name_l3", ""),
+                description: heapless("description", ""),
+                is_active: true,
+                valid_from: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                valid_to: None,
+                product_type: ProductType::CASA,
+                currency: heapless("currency", "XAF"),
+                rules: ProductRules {
+                    minimum_balance: Decimal::from(100),
+                    maximum_balance: None,
+                    daily_transaction_limit: None,
+                    monthly_transaction_limit: None,
+                    overdraft_allowed: false,
+                    overdraft_limit: None,
+                    interest_calculation_method: heapless("interest_calculation_method", "DAILY_BALANCE"),
+                    interest_posting_frequency: PostingFrequency::Monthly,
+                    dormancy_threshold_days: 365,
+                    minimum_opening_balance: Decimal::ZERO,
+                    closure_fee: Decimal::ZERO,
+                    maintenance_fee: None,
+                    maintenance_fee_frequency: None,
+                    default_dormancy_days: None,
+                    default_overdraft_limit: None,
+                    per_transaction_limit: None,
+                    overdraft_interest_rate: None,
+                    accrual_frequency: ProductAccrualFrequency::Daily,
+                },
+            },
+        }
+    }
+
+    pub fn name(mut self, name_l1: &str) -> Self {
+        self.model.name_l1 = heapless("name_l1", name_l1);
+        self
+    }
+
+    pub fn product_type(mut self, product_type: ProductType) -> Self {
+        self.model.product_type = product_type;
+        self
+    }
+
+    pub fn currency(mut self, currency: &str) -> Self {
+        self.model.currency = heapless("currency", currency);
+        self
+    }
+
+    pub fn valid_from(mut self, valid_from: NaiveDate) -> Self {
+        self.model.valid_from = valid_from;
+        self
+    }
+
+    pub fn maximum_balance(mut self, maximum_balance: Decimal) -> Self {
+        self.model.rules.maximum_balance = Some(maximum_balance);
+        self
+    }
+
+    pub fn interest_posting_frequency(mut self, frequency: PostingFrequency) -> Self {
+        self.model.rules.interest_posting_frequency = frequency;
+        self
+    }
+
+    pub fn maintenance_fee(mut self, fee: Decimal, frequency: &str) -> Self {
+        self.model.rules.maintenance_fee = Some(fee);
+        self.model.rules.maintenance_fee_frequency = Some(heapless("maintenance_fee_frequency", frequency));
+        self
+    }
+
+    /// Change any other field of the model
+    pub fn with(mut self, change: impl FnOnce(&mut ProductModel)) -> Self {
+        change(&mut self.model);
+        self
+    }
+
+    /// The product, with its validity window checked
+    ///
+    /// The currency is not validated here so tests can build products with invalid
+    /// codes; `ProductModel::validate_currency` checks it.
+    pub fn build(self) -> ProductModel {
+        let model = self.model;
+        if let Some(valid_to) = model.valid_to {
+            assert!(model.valid_from <= valid_to, "Product valid_to {valid_to} is before valid_from {}", model.valid_from);
+        }
+        model
+    }
+}
@@ -0,0 +1,111 @@
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::models::reason_and_purpose::reason::{
+    ReasonCategory, ReasonContext, ReasonModel, ReasonSeverity,
+};
+use crate::repository::create_batch::CreateBatch;
+
+use super::heapless;
+
+/// Builder of `ReasonModel` test data
+///
+/// Defaults to an active, medium severity compliance reason for transactions with
+/// English content and a random code, so several fixtures never share a code.
+pub struct ReasonFixture {
+    model: ReasonModel,
+}
+
+impl ReasonFixture {
+    pub fn builder() -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            model: ReasonModel {
+                id,
+                code: heapless("code", &format!("TEST_{}", id.simple())),
+                category: ReasonCategory::Compliance,
+                context: ReasonContext::Transaction,
+                l1_content: Some(heapless("l1_content", "Test Reason")),
+                l2_content: None,
+                l3_content: None,
+                l1_language_code: Some(heapless("l1_language_code", "eng")),
+                l2_language_code: None,
+                l3_language_code: None,
+                requires_details: false,
+                is_active: true,
+                severity: Some(ReasonSeverity::Medium),
+                display_order: 0,
+                compliance_metadata: None,
+            },
+        }
+    }
+
+    pub fn code(mut self, code: &str) -> Self {
+        self.model.code = heapless("code", code);
+        self
+    }
+
+    pub fn content(mut self, l1_content: &str) -> Self {
+        self.model.l1_content = Some(heapless("l1_content", l1_content));
+        self
+    }
+
+    pub fn category(mut self, category: ReasonCategory) -> Self {
+        self.model.category = category;
+        self
+    }
+
+    pub fn context(mut self, context: ReasonContext) -> Self {
+        self.model.context = context;
+        self
+    }
+
+    pub fn requires_details(mut self, requires_details: bool) -> Self {
+        self.model.requires_details = requires_details;
+        self
+    }
+
+    pub fn is_active(mut self, is_active: bool) -> Self {
+        self.model.is_active = is_active;
+        self
+    }
+
+    pub fn display_order(mut self, display_order: i32) -> Self {
+        self.model.display_order = display_order;
+        self
+    }
+
+    pub fn compliance_metadata(mut self, compliance_metadata_id: Uuid) -> Self {
+        self.model.compliance_metadata = Some(compliance_metadata_id);
+        self
+    }
+
+    /// Change any other field of the model
+    pub fn with(mut self, change: impl FnOnce(&mut ReasonModel)) -> Self {
+        change(&mut self.model);
+        self
+    }
+
+    /// The reason, checked to have a code and a language code for each content
+    pub fn build(self) -> ReasonModel {
+        let model = self.model;
+        assert!(!model.code.is_empty(), "Reason code is empty");
+        for (content, language_code) in [
+            (&model.l1_content, &model.l1_language_code),
+            (&model.l2_content, &model.l2_language_code),
+            (&model.l3_content, &model.l3_language_code),
+        ] {
+            assert!(content.is_none() || language_code.is_some(), "Reason content without language code");
+        }
+        model
+    }
+
+    /// Build the reason and create it through `repo`
+    pub async fn persist<R>(self, repo: &R, audit_log_id: Option<Uuid>) -> Result<ReasonModel, Box<dyn Error + Send + Sync>>
+    where
+        R: CreateBatch<Postgres, ReasonModel> + ?Sized,
+    {
+        super::persist(self.build(), repo, audit_log_id).await
+    }
+}
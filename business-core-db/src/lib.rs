@@ -11,6 +11,9 @@ pub mod models;
 pub mod repository;
 pub mod utils;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fixtures;
+
 #[async_trait]
 pub trait DatabaseExecutor: Send + Sync {
     async fn begin(&self) -> Result<Transaction<'_, Postgres>, sqlx::Error>;
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use uuid::Uuid;
+
+use super::audit_log::AuditLogModel;
+
+/// The audit log a unit of work writes under
+///
+/// Created once per request from its audit_log row, then handed to every write of
+/// the request instead of the bare audit log id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditContext {
+    pub audit_log_id: Uuid,
+    pub updated_by_person_id: Uuid,
+    /// `updated_at` of the audit log
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditContext {
+    /// The audit log row backing the context
+    pub fn audit_log(&self) -> AuditLogModel {
+        AuditLogModel {
+            id: self.audit_log_id,
+            updated_at: self.created_at,
+            updated_by_person_id: self.updated_by_person_id,
+        }
+    }
+}
+
+impl From<&AuditLogModel> for AuditContext {
+    fn from(audit_log: &AuditLogModel) -> Self {
+        Self {
+            audit_log_id: audit_log.id,
+            updated_by_person_id: audit_log.updated_by_person_id,
+            created_at: audit_log.updated_at,
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_AUDIT_CONTEXT: AuditContext;
+}
+
+/// Run `fut` with `ctx` as the current audit context
+///
+/// Code awaited inside `fut`, on the same task, reads it with `current_audit_context`.
+/// Spawned tasks do not inherit it.
+pub async fn with_audit_context<F: Future>(ctx: AuditContext, fut: F) -> F::Output {
+    CURRENT_AUDIT_CONTEXT.scope(ctx, fut).await
+}
+
+/// The audit context set by the enclosing `with_audit_context`, if any
+pub fn current_audit_context() -> Option<AuditContext> {
+    CURRENT_AUDIT_CONTEXT.try_with(|ctx| *ctx).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{current_audit_context, with_audit_context, AuditContext};
+    use crate::models::audit::audit_log::AuditLogModel;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn context() -> AuditContext {
+        AuditContext::from(&AuditLogModel {
+            id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+            updated_by_person_id: Uuid::new_v4(),
+        })
+    }
+
+    async fn nested() -> Option<Uuid> {
+        current_audit_context().map(|ctx| ctx.audit_log_id)
+    }
+
+    #[tokio::test]
+    async fn test_with_audit_context() {
+        assert!(current_audit_context().is_none());
+
+        let outer = context();
+        let inner = context();
+        let seen = with_audit_context(outer, async {
+            let before = nested().await;
+            let shadowed = with_audit_context(inner, nested()).await;
+            (before, shadowed, nested().await)
+        })
+        .await;
+        assert_eq!(seen, (Some(outer.audit_log_id), Some(inner.audit_log_id), Some(outer.audit_log_id)));
+
+        assert!(current_audit_context().is_none());
+    }
+
+    #[test]
+    fn test_round_trip_through_audit_log() {
+        let ctx = context();
+        assert_eq!(AuditContext::from(&ctx.audit_log()), ctx);
+    }
+}
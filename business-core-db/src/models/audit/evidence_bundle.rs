@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::models::audit::audit_log::AuditLogModel;
+use crate::models::audit::entity_type::EntityType;
+use crate::models::audit_chained::{verify_links, AuditChained, LinkVerification};
+
+/// Audit trail of one entity, exported for verification outside the database
+///
+/// `audit_rows` are the versions of the entity oldest first, `audit_logs` the audit
+/// logs they reference and `links` the outcome of the chain checks at export time.
+/// `digest` is the hex BLAKE3 digest of the CBOR serialization of the bundle with
+/// `digest` empty, so any change to the serialized bundle is detected by
+/// `verify_bundle` without access to the database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceBundle<T> {
+    pub entity_type: EntityType,
+    pub entity_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub audit_rows: Vec<T>,
+    pub audit_logs: Vec<AuditLogModel>,
+    pub links: Vec<LinkVerification>,
+    pub digest: String,
+}
+
+impl<T: AuditChained + Serialize + Clone> EvidenceBundle<T> {
+    /// Build a bundle from versions ordered oldest first, checking the chain and sealing it
+    pub fn new(
+        entity_type: EntityType,
+        entity_id: Uuid,
+        audit_rows: Vec<T>,
+        audit_logs: Vec<AuditLogModel>,
+    ) -> Result<Self, String> {
+        let links = verify_links(&audit_rows);
+        let mut bundle = Self {
+            entity_type,
+            entity_id,
+            exported_at: Utc::now(),
+            audit_rows,
+            audit_logs,
+            links,
+            digest: String::new(),
+        };
+        bundle.digest = bundle.compute_digest()?;
+        Ok(bundle)
+    }
+
+    fn compute_digest(&self) -> Result<String, String> {
+        let mut unsealed = self.clone();
+        unsealed.digest = String::new();
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(&unsealed, &mut cbor)
+            .map_err(|e| format!("Failed to serialize evidence bundle: {e}"))?;
+        Ok(blake3::hash(&cbor).to_hex().to_string())
+    }
+}
+
+/// Outcome of `verify_bundle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleVerification {
+    /// The bundle content hashes to its digest
+    pub digest_matches: bool,
+    /// Versions failing the chain checks, by index in `audit_rows`
+    pub failed_links: Vec<(usize, LinkVerification)>,
+    /// Audit logs referenced by a version but absent from `audit_logs`
+    pub missing_audit_logs: Vec<Uuid>,
+}
+
+impl BundleVerification {
+    pub fn is_valid(&self) -> bool {
+        self.digest_matches && self.failed_links.is_empty() && self.missing_audit_logs.is_empty()
+    }
+}
+
+/// Verify a bundle offline
+///
+/// The chain checks are run again on `audit_rows` rather than read from `links`, and
+/// the digest is recomputed from the content, so a bundle edited after export fails
+/// on the digest and on the checks of the versions that were changed.
+pub fn verify_bundle<T: AuditChained + Serialize + Clone>(
+    bundle: &EvidenceBundle<T>,
+) -> Result<BundleVerification, String> {
+    let digest_matches = bundle.compute_digest()? == bundle.digest;
+    let failed_links = verify_links(&bundle.audit_rows)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, link)| !link.is_valid())
+        .collect();
+
+    let known: HashSet<Uuid> = bundle.audit_logs.iter().map(|audit_log| audit_log.id).collect();
+    let mut missing_audit_logs = Vec::new();
+    for audit_log_id in bundle.audit_rows.iter().filter_map(AuditChained::audit_log_id) {
+        if !known.contains(&audit_log_id) && !missing_audit_logs.contains(&audit_log_id) {
+            missing_audit_logs.push(audit_log_id);
+        }
+    }
+
+    Ok(BundleVerification {
+        digest_matches,
+        failed_links,
+        missing_audit_logs,
+    })
+}
@@ -1,8 +1,14 @@
 pub mod audit_log;
 pub use audit_log::*;
 
+pub mod audit_context;
+pub use audit_context::*;
+
 pub mod audit_link;
 pub use audit_link::*;
 
 pub mod entity_type;
-pub use entity_type::*;
\ No newline at end of file
+pub use entity_type::*;
+
+pub mod evidence_bundle;
+pub use evidence_bundle::*;
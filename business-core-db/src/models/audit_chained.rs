@@ -0,0 +1,545 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::utils::hash_as_i64;
+
+/// Trait for auditable entities whose versions form a hash chain
+///
+/// Each version stores the hash of itself computed with `hash` set to 0, and links to
+/// the previous version through `antecedent_hash` and `antecedent_audit_log_id`.
+pub trait AuditChained {
+    fn hash(&self) -> i64;
+
+    fn set_hash(&mut self, hash: i64);
+
+    fn antecedent_hash(&self) -> i64;
+
+    fn antecedent_audit_log_id(&self) -> Uuid;
+
+    fn audit_log_id(&self) -> Option<Uuid>;
+
+    /// Whether the personal data of the version was scrubbed after it was hashed
+    ///
+    /// A redacted version keeps its stored hash, verification only checks its links.
+    fn is_redacted(&self) -> bool {
+        false
+    }
+
+    /// Hashes of the version under earlier layouts of the model, computed with `hash` set to 0
+    ///
+    /// A version stored before a migration changed the serialized fields verifies
+    /// against one of these.
+    fn legacy_hashes(&self) -> Vec<i64> {
+        Vec::new()
+    }
+
+    /// Whether `other` holds the same data, ignoring the audit bookkeeping
+    ///
+    /// Compares the serialized forms without the `AUDIT_FIELDS`, so two versions of an
+    /// entity are equal when an update between them changed nothing. A value that
+    /// cannot be serialized equals nothing.
+    fn content_equals(&self, other: &Self) -> bool
+    where
+        Self: Serialize + Sized,
+    {
+        match (content_of(self), content_of(other)) {
+            (Some(this), Some(other)) => this == other,
+            _ => false,
+        }
+    }
+}
+
+/// Implement `AuditChained` for a model with the usual `hash`, `antecedent_hash`,
+/// `antecedent_audit_log_id` and `audit_log_id: Option<Uuid>` fields
+///
+/// `legacy_hashes = path` names a `fn(&Model) -> Vec<i64>` for `AuditChained::legacy_hashes`.
+macro_rules! impl_audit_chained {
+    ($model:ty) => {
+        $crate::models::audit_chained::impl_audit_chained!(@impl $model, {});
+    };
+    ($model:ty, legacy_hashes = $legacy_hashes:path) => {
+        $crate::models::audit_chained::impl_audit_chained!(@impl $model, {
+            fn legacy_hashes(&self) -> Vec<i64> {
+                $legacy_hashes(self)
+            }
+        });
+    };
+    (@impl $model:ty, { $($extra:tt)* }) => {
+        impl $crate::models::audit_chained::AuditChained for $model {
+            fn hash(&self) -> i64 {
+                self.hash
+            }
+
+            fn set_hash(&mut self, hash: i64) {
+                self.hash = hash;
+            }
+
+            fn antecedent_hash(&self) -> i64 {
+                self.antecedent_hash
+            }
+
+            fn antecedent_audit_log_id(&self) -> uuid::Uuid {
+                self.antecedent_audit_log_id
+            }
+
+            fn audit_log_id(&self) -> Option<uuid::Uuid> {
+                self.audit_log_id
+            }
+
+            $($extra)*
+        }
+    };
+}
+pub(crate) use impl_audit_chained;
+
+/// A stored version of an entity with the `redacted` flag of its audit row
+///
+/// Serializes as `item`, so the version hashes like the bare model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditVersion<T> {
+    pub item: T,
+    pub redacted: bool,
+}
+
+impl<T: AuditChained> AuditChained for AuditVersion<T> {
+    fn hash(&self) -> i64 {
+        self.item.hash()
+    }
+
+    fn set_hash(&mut self, hash: i64) {
+        self.item.set_hash(hash);
+    }
+
+    fn antecedent_hash(&self) -> i64 {
+        self.item.antecedent_hash()
+    }
+
+    fn antecedent_audit_log_id(&self) -> Uuid {
+        self.item.antecedent_audit_log_id()
+    }
+
+    fn audit_log_id(&self) -> Option<Uuid> {
+        self.item.audit_log_id()
+    }
+
+    fn is_redacted(&self) -> bool {
+        self.redacted
+    }
+
+    fn legacy_hashes(&self) -> Vec<i64> {
+        self.item.legacy_hashes()
+    }
+}
+
+impl<T: Serialize> Serialize for AuditVersion<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.item.serialize(serializer)
+    }
+}
+
+/// Serialized names of the fields `AuditChained` maintains
+pub const AUDIT_FIELDS: [&str; 4] = ["hash", "audit_log_id", "antecedent_hash", "antecedent_audit_log_id"];
+
+/// Serialized form of `item` without the `AUDIT_FIELDS`
+fn content_of<T: Serialize>(item: &T) -> Option<serde_json::Value> {
+    let mut value = serde_json::to_value(item).ok()?;
+    if let Some(fields) = value.as_object_mut() {
+        for field in AUDIT_FIELDS {
+            fields.remove(field);
+        }
+    }
+    Some(value)
+}
+
+/// Why a chain failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// The version at `index` does not hash to its stored hash
+    HashMismatch { index: usize, stored: i64, computed: i64 },
+    /// The version at `index` does not link to the version before it
+    BrokenLink { index: usize },
+    /// The version at `index` could not be serialized for hashing
+    Hashing { index: usize, message: String },
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainError::HashMismatch { index, stored, computed } => {
+                write!(f, "Version {index}: stored hash {stored} does not match computed hash {computed}")
+            }
+            ChainError::BrokenLink { index } => {
+                write!(f, "Version {index} does not link to the previous version")
+            }
+            ChainError::Hashing { index, message } => write!(f, "Version {index}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Verify consecutive versions of one entity, oldest first
+///
+/// Every version must hash to its stored hash, under the current layout or one of its
+/// `legacy_hashes`, unless it is redacted, and every version
+/// after the first must carry the hash and audit log id of the version before it as its
+/// antecedent. The
+/// first version's antecedent is not checked, so a chain can be verified from any
+/// version on.
+pub fn verify_chain<T: AuditChained + Serialize + Clone>(rows: &[T]) -> Result<(), ChainError> {
+    for (index, link) in verify_links(rows).into_iter().enumerate() {
+        if !link.hash_matches {
+            let row = &rows[index];
+            return Err(match computed_hash(row) {
+                Ok(computed) => ChainError::HashMismatch {
+                    index,
+                    stored: row.hash(),
+                    computed,
+                },
+                Err(message) => ChainError::Hashing { index, message },
+            });
+        }
+        if !link.links_to_previous {
+            return Err(ChainError::BrokenLink { index });
+        }
+    }
+    Ok(())
+}
+
+/// Hash of `row` computed with its `hash` set to 0
+fn computed_hash<T: AuditChained + Serialize + Clone>(row: &T) -> Result<i64, String> {
+    let mut for_hashing = row.clone();
+    for_hashing.set_hash(0);
+    hash_as_i64(&for_hashing)
+}
+
+/// Last archived version of an entity, recorded when its older versions leave the audit table
+///
+/// After archival the oldest remaining version links to a version that is no longer
+/// stored. The marker keeps that version's audit log id and hash so the remaining
+/// versions still verify from their first link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedAntecedent {
+    pub audit_log_id: Uuid,
+    pub hash: i64,
+}
+
+/// Verify consecutive versions of one entity, oldest first, including the first link
+///
+/// Runs `verify_chain`, and also checks the antecedent of the first version: without
+/// `archived` it must be empty, the chain is complete. With `archived` it must be the
+/// archived version.
+pub fn verify_chain_from<T: AuditChained + Serialize + Clone>(
+    rows: &[T],
+    archived: Option<&ArchivedAntecedent>,
+) -> Result<(), ChainError> {
+    if let Some(first) = rows.first() {
+        let (hash, audit_log_id) = archived.map_or((0, Uuid::nil()), |archived| (archived.hash, archived.audit_log_id));
+        if first.antecedent_hash() != hash || first.antecedent_audit_log_id() != audit_log_id {
+            return Err(ChainError::BrokenLink { index: 0 });
+        }
+    }
+    verify_chain(rows)
+}
+
+/// Outcome of the checks of one version of a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkVerification {
+    pub audit_log_id: Option<Uuid>,
+    /// The version hashes to its stored hash, under the current layout or a legacy one.
+    /// Always set for a redacted version
+    pub hash_matches: bool,
+    /// The hash was not checked, see `AuditChained::is_redacted`
+    #[serde(default)]
+    pub redacted: bool,
+    /// The version carries the hash and audit log id of the version before it
+    pub links_to_previous: bool,
+}
+
+impl LinkVerification {
+    pub fn is_valid(&self) -> bool {
+        self.hash_matches && self.links_to_previous
+    }
+}
+
+/// Check every version of a chain, oldest first, without stopping at the first failure
+///
+/// Reports the checks `verify_chain` stops at, per version. A version that
+/// cannot be serialized counts as a hash mismatch.
+pub fn verify_links<T: AuditChained + Serialize + Clone>(rows: &[T]) -> Vec<LinkVerification> {
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let redacted = row.is_redacted();
+            let hash_matches = redacted
+                || computed_hash(row).is_ok_and(|computed| computed == row.hash())
+                || row.legacy_hashes().contains(&row.hash());
+            let links_to_previous = match index.checked_sub(1).map(|previous| &rows[previous]) {
+                Some(previous) => {
+                    row.antecedent_hash() == previous.hash()
+                        && Some(row.antecedent_audit_log_id()) == previous.audit_log_id()
+                }
+                None => true,
+            };
+            LinkVerification {
+                audit_log_id: row.audit_log_id(),
+                hash_matches,
+                redacted,
+                links_to_previous,
+            }
+        })
+        .collect()
+}
+
+/// Order the versions of one entity along their chain, oldest first
+///
+/// Audit log ids are random, so the chain is walked from the version without
+/// antecedent through the antecedent audit log ids. When older versions were archived,
+/// the walk starts from the first version whose antecedent is not among `rows`.
+/// Versions the walk does not reach are appended in their original order, where
+/// verification reports them.
+pub fn order_chain<T: AuditChained>(rows: Vec<T>) -> Vec<T> {
+    let mut by_antecedent: HashMap<Uuid, usize> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        by_antecedent.entry(row.antecedent_audit_log_id()).or_insert(index);
+    }
+
+    let mut order = Vec::with_capacity(rows.len());
+    let mut placed = vec![false; rows.len()];
+    let mut current = Uuid::nil();
+    if !by_antecedent.contains_key(&current) {
+        let audit_log_ids: HashSet<Uuid> = rows.iter().filter_map(|row| row.audit_log_id()).collect();
+        if let Some(start) = rows
+            .iter()
+            .map(|row| row.antecedent_audit_log_id())
+            .find(|antecedent| !audit_log_ids.contains(antecedent))
+        {
+            current = start;
+        }
+    }
+    while let Some(&index) = by_antecedent.get(&current) {
+        if placed[index] {
+            break;
+        }
+        placed[index] = true;
+        order.push(index);
+        match rows[index].audit_log_id() {
+            Some(audit_log_id) => current = audit_log_id,
+            None => break,
+        }
+    }
+    order.extend((0..rows.len()).filter(|index| !placed[*index]));
+
+    let mut rows: Vec<Option<T>> = rows.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|index| rows[index].take()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        order_chain, verify_chain, verify_chain_from, verify_links, ArchivedAntecedent, AuditChained, AuditVersion, ChainError,
+    };
+    use crate::models::audit::entity_type::EntityType;
+    use crate::models::person::location::{LocationModel, LocationType};
+    use crate::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
+    use crate::utils::hash_as_i64;
+    use chrono::Utc;
+    use heapless::String as HeaplessString;
+    use serde::Serialize;
+    use uuid::Uuid;
+
+    fn seal<T: AuditChained + Serialize>(mut item: T) -> T {
+        item.set_hash(0);
+        let hash = hash_as_i64(&item).unwrap();
+        item.set_hash(hash);
+        item
+    }
+
+    fn reason_reference_chain() -> Vec<ReasonReferenceModel> {
+        let created = seal(ReasonReferenceModel {
+            id: Uuid::new_v4(),
+            reason_id: Uuid::new_v4(),
+            entity_id: Uuid::new_v4(),
+            additional_details: None,
+            details: None,
+            entity_type: EntityType::Person,
+            created_at: Utc::now(),
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: Some(Uuid::new_v4()),
+        });
+        let mut updated = created.clone();
+        updated.additional_details = Some(HeaplessString::try_from("Updated").unwrap());
+        updated.antecedent_hash = created.hash;
+        updated.antecedent_audit_log_id = created.audit_log_id.unwrap();
+        updated.audit_log_id = Some(Uuid::new_v4());
+        vec![created, seal(updated)]
+    }
+
+    fn location_chain() -> Vec<LocationModel> {
+        let created = seal(LocationModel {
+            id: Uuid::new_v4(),
+            street_line1: HeaplessString::try_from("1 Main Street").unwrap(),
+            street_line2: None,
+            street_line3: None,
+            street_line4: None,
+            locality_id: Uuid::new_v4(),
+            postal_code: None,
+            latitude: None,
+            longitude: None,
+            accuracy_meters: None,
+            location_type: LocationType::Branch,
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: Some(Uuid::new_v4()),
+        });
+        let mut updated = created.clone();
+        updated.street_line1 = HeaplessString::try_from("2 Main Street").unwrap();
+        updated.antecedent_hash = created.hash;
+        updated.antecedent_audit_log_id = created.audit_log_id.unwrap();
+        updated.audit_log_id = Some(Uuid::new_v4());
+        vec![created, seal(updated)]
+    }
+
+    #[test]
+    fn test_verify_chain_accepts_valid_chains() {
+        assert_eq!(verify_chain(&reason_reference_chain()), Ok(()));
+        assert_eq!(verify_chain(&location_chain()), Ok(()));
+        assert_eq!(verify_chain::<LocationModel>(&[]), Ok(()));
+        // A chain can be verified from any version on
+        assert_eq!(verify_chain(&location_chain()[1..]), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_chain_detects_tampering() {
+        let mut chain = reason_reference_chain();
+        chain[0].additional_details = Some(HeaplessString::try_from("Tampered").unwrap());
+        assert!(matches!(verify_chain(&chain), Err(ChainError::HashMismatch { index: 0, .. })));
+
+        let mut chain = location_chain();
+        chain[1].street_line2 = Some(HeaplessString::try_from("Tampered").unwrap());
+        assert!(matches!(verify_chain(&chain), Err(ChainError::HashMismatch { index: 1, .. })));
+    }
+
+    #[test]
+    fn test_verify_chain_detects_broken_links() {
+        // Each version is consistent on its own, but the second skips the first
+        let mut chain = location_chain();
+        chain[1].antecedent_audit_log_id = Uuid::new_v4();
+        chain[1] = seal(chain[1].clone());
+        assert_eq!(verify_chain(&chain), Err(ChainError::BrokenLink { index: 1 }));
+
+        let mut chain = reason_reference_chain();
+        chain.swap(0, 1);
+        assert_eq!(verify_chain(&chain), Err(ChainError::BrokenLink { index: 1 }));
+    }
+
+    #[test]
+    fn test_verify_links_reports_every_version() {
+        let chain = location_chain();
+        assert!(verify_links(&chain).iter().all(|link| link.is_valid()));
+
+        let mut chain = location_chain();
+        chain[0].street_line2 = Some(HeaplessString::try_from("Tampered").unwrap());
+        let links = verify_links(&chain);
+        assert!(!links[0].hash_matches);
+        // The second version still hashes correctly and links to the stored hash
+        assert!(links[1].is_valid());
+    }
+
+    #[test]
+    fn test_verify_chain_accepts_redacted_versions() {
+        let mut chain = location_chain();
+        chain[0].street_line1 = HeaplessString::try_from("Redacted").unwrap();
+        assert!(matches!(verify_chain(&chain), Err(ChainError::HashMismatch { index: 0, .. })));
+
+        let versions: Vec<_> = chain
+            .iter()
+            .enumerate()
+            .map(|(index, item)| AuditVersion {
+                item: item.clone(),
+                redacted: index == 0,
+            })
+            .collect();
+        assert_eq!(verify_chain_from(&versions, None), Ok(()));
+        let links = verify_links(&versions);
+        assert!(links[0].is_valid() && links[0].redacted);
+        assert!(!links[1].redacted);
+
+        // Only the hash of a redacted version is skipped, its links are still checked
+        let mut unlinked = versions.clone();
+        unlinked[0].item.antecedent_audit_log_id = Uuid::new_v4();
+        assert_eq!(verify_chain_from(&unlinked, None), Err(ChainError::BrokenLink { index: 0 }));
+        let mut tampered = versions;
+        tampered[1].item.street_line2 = Some(HeaplessString::try_from("Tampered").unwrap());
+        assert!(matches!(verify_chain(&tampered), Err(ChainError::HashMismatch { index: 1, .. })));
+    }
+
+    #[test]
+    fn test_order_chain_follows_antecedents() {
+        let chain = reason_reference_chain();
+        let ids: Vec<_> = chain.iter().map(|row| row.audit_log_id).collect();
+
+        let reversed: Vec<_> = chain.into_iter().rev().collect();
+        let ordered = order_chain(reversed);
+        assert_eq!(ordered.iter().map(|row| row.audit_log_id).collect::<Vec<_>>(), ids);
+        assert_eq!(verify_chain(&ordered), Ok(()));
+
+        // A version outside the chain goes last
+        let mut chain = location_chain();
+        chain[1].antecedent_audit_log_id = Uuid::new_v4();
+        let stray = chain[1].audit_log_id;
+        let ordered = order_chain(chain.into_iter().rev().collect());
+        assert_eq!(ordered[1].audit_log_id, stray);
+    }
+
+    #[test]
+    fn test_verify_chain_from_archived_antecedent() {
+        let mut chain = location_chain();
+        let mut third = chain[1].clone();
+        third.street_line1 = HeaplessString::try_from("3 Main Street").unwrap();
+        third.antecedent_hash = chain[1].hash;
+        third.antecedent_audit_log_id = chain[1].audit_log_id.unwrap();
+        third.audit_log_id = Some(Uuid::new_v4());
+        chain.push(seal(third));
+
+        assert_eq!(verify_chain_from(&chain, None), Ok(()));
+
+        // The first version was archived
+        let archived = ArchivedAntecedent {
+            audit_log_id: chain[0].audit_log_id.unwrap(),
+            hash: chain[0].hash,
+        };
+        let remaining = order_chain(chain[1..].iter().rev().cloned().collect());
+        assert_eq!(remaining[0].audit_log_id, chain[1].audit_log_id);
+        assert_eq!(verify_chain_from(&remaining, Some(&archived)), Ok(()));
+
+        // Without the marker the missing version is a broken link
+        assert_eq!(verify_chain_from(&remaining, None), Err(ChainError::BrokenLink { index: 0 }));
+        let wrong = ArchivedAntecedent { hash: archived.hash + 1, ..archived };
+        assert_eq!(verify_chain_from(&remaining, Some(&wrong)), Err(ChainError::BrokenLink { index: 0 }));
+    }
+
+    #[test]
+    fn test_content_equals_ignores_audit_fields() {
+        let chain = location_chain();
+        let mut restamped = chain[0].clone();
+        restamped.antecedent_hash = chain[1].hash;
+        restamped.antecedent_audit_log_id = Uuid::new_v4();
+        restamped.audit_log_id = Some(Uuid::new_v4());
+        restamped = seal(restamped);
+        assert_ne!(restamped.hash, chain[0].hash);
+        assert!(restamped.content_equals(&chain[0]));
+
+        // Consecutive versions differ in a business field
+        assert!(!chain[1].content_equals(&chain[0]));
+        let chain = reason_reference_chain();
+        assert!(!chain[1].content_equals(&chain[0]));
+        let mut reverted = chain[1].clone();
+        reverted.additional_details = chain[0].additional_details.clone();
+        assert!(reverted.content_equals(&chain[0]));
+    }
+}
@@ -8,6 +8,8 @@ use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{IndexAware, Identifiable, Index};
 use postgres_index_cache::HasPrimaryKey as HasPrimaryKeyCache;
 
+use super::calendar_weekday::CalendarWeekday;
+
 /// Business Day Model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -16,7 +18,7 @@ pub struct BusinessDayModel {
     pub country_id: Option<Uuid>,
     pub country_subdivision_id: Option<Uuid>,
     pub date: NaiveDate,
-    pub weekday: Weekday,
+    pub weekday: CalendarWeekday,
     pub is_business_day: bool,
     pub is_weekend: bool,
     pub weekend_day_01: Option<Uuid>,
@@ -25,35 +27,9 @@ pub struct BusinessDayModel {
     pub day_scope: DayScope,
 }
 
-/// Weekday enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "weekday", rename_all = "PascalCase")]
-pub enum Weekday {
-    Monday,
-    Tuesday,
-    Wednesday,
-    Thursday,
-    Friday,
-    Saturday,
-    Sunday,
-}
-
-impl FromStr for Weekday {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Monday" => Ok(Weekday::Monday),
-            "Tuesday" => Ok(Weekday::Tuesday),
-            "Wednesday" => Ok(Weekday::Wednesday),
-            "Thursday" => Ok(Weekday::Thursday),
-            "Friday" => Ok(Weekday::Friday),
-            "Saturday" => Ok(Weekday::Saturday),
-            "Sunday" => Ok(Weekday::Sunday),
-            _ => Err(()),
-        }
-    }
-}
+/// Superseded by `CalendarWeekday`
+#[deprecated(note = "use CalendarWeekday")]
+pub type Weekday = CalendarWeekday;
 
 /// Day Scope enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
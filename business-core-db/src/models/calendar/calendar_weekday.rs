@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::weekend_days::WeekendDaysModel;
+
+/// Day of the week shared by all calendar models
+///
+/// Maps to the `weekday` database enum and converts to and from `chrono::Weekday`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "weekday", rename_all = "PascalCase")]
+pub enum CalendarWeekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl CalendarWeekday {
+    /// All days, Monday first
+    pub const ALL: [CalendarWeekday; 7] = [
+        CalendarWeekday::Monday,
+        CalendarWeekday::Tuesday,
+        CalendarWeekday::Wednesday,
+        CalendarWeekday::Thursday,
+        CalendarWeekday::Friday,
+        CalendarWeekday::Saturday,
+        CalendarWeekday::Sunday,
+    ];
+
+    /// Whether this day is one of the weekend days of the given configuration
+    pub fn is_in_weekend(&self, weekend_days: &WeekendDaysModel) -> bool {
+        weekend_days.weekend_days().contains(self)
+    }
+}
+
+impl FromStr for CalendarWeekday {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Monday" => Ok(CalendarWeekday::Monday),
+            "Tuesday" => Ok(CalendarWeekday::Tuesday),
+            "Wednesday" => Ok(CalendarWeekday::Wednesday),
+            "Thursday" => Ok(CalendarWeekday::Thursday),
+            "Friday" => Ok(CalendarWeekday::Friday),
+            "Saturday" => Ok(CalendarWeekday::Saturday),
+            "Sunday" => Ok(CalendarWeekday::Sunday),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<chrono::Weekday> for CalendarWeekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => CalendarWeekday::Monday,
+            chrono::Weekday::Tue => CalendarWeekday::Tuesday,
+            chrono::Weekday::Wed => CalendarWeekday::Wednesday,
+            chrono::Weekday::Thu => CalendarWeekday::Thursday,
+            chrono::Weekday::Fri => CalendarWeekday::Friday,
+            chrono::Weekday::Sat => CalendarWeekday::Saturday,
+            chrono::Weekday::Sun => CalendarWeekday::Sunday,
+        }
+    }
+}
+
+impl From<CalendarWeekday> for chrono::Weekday {
+    fn from(weekday: CalendarWeekday) -> Self {
+        match weekday {
+            CalendarWeekday::Monday => chrono::Weekday::Mon,
+            CalendarWeekday::Tuesday => chrono::Weekday::Tue,
+            CalendarWeekday::Wednesday => chrono::Weekday::Wed,
+            CalendarWeekday::Thursday => chrono::Weekday::Thu,
+            CalendarWeekday::Friday => chrono::Weekday::Fri,
+            CalendarWeekday::Saturday => chrono::Weekday::Sat,
+            CalendarWeekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::str::FromStr;
 use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{IndexAware, Identifiable, Index};
+use crate::models::effective_dated::EffectiveDated;
 use postgres_index_cache::HasPrimaryKey as HasPrimaryKeyCache;
 
 /// Date Calculation Rules Model
@@ -84,6 +85,16 @@ impl Identifiable for DateCalculationRulesModel {
     }
 }
 
+impl EffectiveDated for DateCalculationRulesModel {
+    fn effective_from(&self) -> Option<NaiveDate> {
+        Some(self.effective_date)
+    }
+
+    fn effective_until(&self) -> Option<NaiveDate> {
+        self.expiry_date
+    }
+}
+
 impl IndexAware for DateCalculationRulesModel {
     type IndexType = DateCalculationRulesIdxModel;
 
@@ -1,7 +1,13 @@
+pub mod calendar_weekday;
 pub mod weekend_days;
 pub mod business_day;
 pub mod date_calculation_rules;
 
-pub use weekend_days::{WeekendDaysModel, WeekendDaysIdxModel, Weekday};
-pub use business_day::{BusinessDayModel, BusinessDayIdxModel, DayScope, Weekday as BusinessWeekday};
+pub use calendar_weekday::CalendarWeekday;
+pub use weekend_days::{WeekendDaysError, WeekendDaysModel, WeekendDaysIdxModel};
+pub use business_day::{BusinessDayModel, BusinessDayIdxModel, DayScope};
+#[allow(deprecated)]
+pub use weekend_days::Weekday;
+#[allow(deprecated)]
+pub use business_day::Weekday as BusinessWeekday;
 pub use date_calculation_rules::{DateCalculationRulesModel, DateCalculationRulesIdxModel, DateRulePurpose, DateShiftRule};
\ No newline at end of file
@@ -1,58 +1,160 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Utc};
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{IndexAware, Identifiable, Index};
+use crate::models::effective_dated::EffectiveDated;
 use postgres_index_cache::HasPrimaryKey as HasPrimaryKeyCache;
 
+use super::calendar_weekday::CalendarWeekday;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
 pub struct WeekendDaysModel {
     pub id: Uuid,
     pub country_id: Option<Uuid>,
     pub country_subdivision_id: Option<Uuid>,
-    pub weekend_day_01: Option<Weekday>,
-    pub weekend_day_02: Option<Weekday>,
-    pub weekend_day_03: Option<Weekday>,
-    pub weekend_day_04: Option<Weekday>,
-    pub weekend_day_05: Option<Weekday>,
-    pub weekend_day_06: Option<Weekday>,
-    pub weekend_day_07: Option<Weekday>,
+    pub weekend_day_01: Option<CalendarWeekday>,
+    pub weekend_day_02: Option<CalendarWeekday>,
+    pub weekend_day_03: Option<CalendarWeekday>,
+    pub weekend_day_04: Option<CalendarWeekday>,
+    pub weekend_day_05: Option<CalendarWeekday>,
+    pub weekend_day_06: Option<CalendarWeekday>,
+    pub weekend_day_07: Option<CalendarWeekday>,
     pub effective_date: NaiveDate,
     pub expiry_date: Option<NaiveDate>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "weekday", rename_all = "PascalCase")]
-pub enum Weekday {
-    Monday,
-    Tuesday,
-    Wednesday,
-    Thursday,
-    Friday,
-    Saturday,
-    Sunday,
+/// Why a `WeekendDaysModel` is rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeekendDaysError {
+    /// `day` fills more than one slot
+    DuplicateDay { day: CalendarWeekday },
+    /// Slot `slot`, numbered from 1, is filled after an empty slot
+    SlotGap { slot: usize },
+    /// Neither `country_id` nor `country_subdivision_id` is set
+    MissingScope,
 }
 
-impl FromStr for Weekday {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Monday" => Ok(Weekday::Monday),
-            "Tuesday" => Ok(Weekday::Tuesday),
-            "Wednesday" => Ok(Weekday::Wednesday),
-            "Thursday" => Ok(Weekday::Thursday),
-            "Friday" => Ok(Weekday::Friday),
-            "Saturday" => Ok(Weekday::Saturday),
-            "Sunday" => Ok(Weekday::Sunday),
-            _ => Err(()),
+impl std::fmt::Display for WeekendDaysError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeekendDaysError::DuplicateDay { day } => write!(f, "{day:?} is configured more than once"),
+            WeekendDaysError::SlotGap { slot } => write!(f, "weekend_day_{slot:02} is filled after an empty slot"),
+            WeekendDaysError::MissingScope => write!(f, "country_id or country_subdivision_id is required"),
         }
     }
 }
 
+impl std::error::Error for WeekendDaysError {}
+
+/// Superseded by `CalendarWeekday`
+#[deprecated(note = "use CalendarWeekday")]
+pub type Weekday = CalendarWeekday;
+
+impl WeekendDaysModel {
+    /// Weekend days packed from slot 1, in the given order, effective today
+    ///
+    /// Rejects a day given twice. The scope is left unset for the caller to fill.
+    pub fn from_days(days: &[CalendarWeekday]) -> Result<Self, WeekendDaysError> {
+        let mut model = WeekendDaysModel {
+            id: Uuid::new_v4(),
+            country_id: None,
+            country_subdivision_id: None,
+            weekend_day_01: None,
+            weekend_day_02: None,
+            weekend_day_03: None,
+            weekend_day_04: None,
+            weekend_day_05: None,
+            weekend_day_06: None,
+            weekend_day_07: None,
+            effective_date: Utc::now().date_naive(),
+            expiry_date: None,
+        };
+        for (i, day) in days.iter().enumerate() {
+            if days[..i].contains(day) {
+                return Err(WeekendDaysError::DuplicateDay { day: *day });
+            }
+        }
+        model.set_weekend_days(days);
+        Ok(model)
+    }
+
+    /// The weekend days, checked to fill the slots contiguously from slot 1 without duplicates
+    pub fn normalized(&self) -> Result<Vec<CalendarWeekday>, WeekendDaysError> {
+        let slots = [
+            self.weekend_day_01,
+            self.weekend_day_02,
+            self.weekend_day_03,
+            self.weekend_day_04,
+            self.weekend_day_05,
+            self.weekend_day_06,
+            self.weekend_day_07,
+        ];
+        let mut days: Vec<CalendarWeekday> = Vec::with_capacity(7);
+        for (i, slot) in slots.into_iter().enumerate() {
+            let Some(day) = slot else { continue };
+            if days.len() < i {
+                return Err(WeekendDaysError::SlotGap { slot: i + 1 });
+            }
+            if days.contains(&day) {
+                return Err(WeekendDaysError::DuplicateDay { day });
+            }
+            days.push(day);
+        }
+        Ok(days)
+    }
+
+    /// Check the slots with `normalized` and that a country or subdivision is set
+    pub fn validate(&self) -> Result<(), WeekendDaysError> {
+        self.normalized()?;
+        if self.country_id.is_none() && self.country_subdivision_id.is_none() {
+            return Err(WeekendDaysError::MissingScope);
+        }
+        Ok(())
+    }
+
+    /// Weekend days configured in the seven slots, in slot order
+    pub fn weekend_days(&self) -> Vec<CalendarWeekday> {
+        [
+            self.weekend_day_01,
+            self.weekend_day_02,
+            self.weekend_day_03,
+            self.weekend_day_04,
+            self.weekend_day_05,
+            self.weekend_day_06,
+            self.weekend_day_07,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Fill the slots with the given days, dropping duplicates and clearing unused slots
+    pub fn set_weekend_days(&mut self, days: &[CalendarWeekday]) {
+        let mut distinct: Vec<CalendarWeekday> = Vec::with_capacity(7);
+        for day in days {
+            if !distinct.contains(day) {
+                distinct.push(*day);
+            }
+        }
+        let mut slots = distinct.into_iter();
+        self.weekend_day_01 = slots.next();
+        self.weekend_day_02 = slots.next();
+        self.weekend_day_03 = slots.next();
+        self.weekend_day_04 = slots.next();
+        self.weekend_day_05 = slots.next();
+        self.weekend_day_06 = slots.next();
+        self.weekend_day_07 = slots.next();
+    }
+
+    /// Whether the given date falls on a configured weekend day
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        CalendarWeekday::from(date.weekday()).is_in_weekend(self)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeekendDaysIdxModel {
     pub id: Uuid,
@@ -66,6 +168,16 @@ impl Identifiable for WeekendDaysModel {
     }
 }
 
+impl EffectiveDated for WeekendDaysModel {
+    fn effective_from(&self) -> Option<NaiveDate> {
+        Some(self.effective_date)
+    }
+
+    fn effective_until(&self) -> Option<NaiveDate> {
+        self.expiry_date
+    }
+}
+
 impl IndexAware for WeekendDaysModel {
     type IndexType = WeekendDaysIdxModel;
 
@@ -111,4 +223,58 @@ impl HasPrimaryKeyCache for WeekendDaysModel {
     }
 }
 
-pub type WeekendDaysIdxModelCache = IdxModelCache<WeekendDaysIdxModel>;
\ No newline at end of file
+pub type WeekendDaysIdxModelCache = IdxModelCache<WeekendDaysIdxModel>;
+
+#[cfg(test)]
+mod tests {
+    use super::{WeekendDaysError, WeekendDaysModel};
+    use crate::models::calendar::calendar_weekday::CalendarWeekday;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_from_days_packs_slots() {
+        let mut model = WeekendDaysModel::from_days(&[CalendarWeekday::Friday, CalendarWeekday::Saturday]).unwrap();
+        assert_eq!(model.weekend_day_01, Some(CalendarWeekday::Friday));
+        assert_eq!(model.weekend_day_02, Some(CalendarWeekday::Saturday));
+        assert!(model.weekend_day_03.is_none());
+        assert_eq!(
+            model.normalized().unwrap(),
+            vec![CalendarWeekday::Friday, CalendarWeekday::Saturday]
+        );
+
+        // Valid once scoped
+        assert_eq!(model.validate(), Err(WeekendDaysError::MissingScope));
+        model.country_id = Some(Uuid::new_v4());
+        assert_eq!(model.validate(), Ok(()));
+
+        assert!(WeekendDaysModel::from_days(&[]).unwrap().normalized().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_days_rejected() {
+        assert_eq!(
+            WeekendDaysModel::from_days(&[CalendarWeekday::Sunday, CalendarWeekday::Monday, CalendarWeekday::Sunday])
+                .unwrap_err(),
+            WeekendDaysError::DuplicateDay { day: CalendarWeekday::Sunday }
+        );
+
+        let mut model = WeekendDaysModel::from_days(&[CalendarWeekday::Sunday]).unwrap();
+        model.weekend_day_02 = Some(CalendarWeekday::Sunday);
+        assert_eq!(
+            model.normalized(),
+            Err(WeekendDaysError::DuplicateDay { day: CalendarWeekday::Sunday })
+        );
+    }
+
+    #[test]
+    fn test_gap_in_slots_rejected() {
+        let mut model = WeekendDaysModel::from_days(&[CalendarWeekday::Saturday]).unwrap();
+        model.weekend_day_03 = Some(CalendarWeekday::Sunday);
+        assert_eq!(model.normalized(), Err(WeekendDaysError::SlotGap { slot: 3 }));
+
+        model.weekend_day_01 = None;
+        model.weekend_day_03 = None;
+        model.weekend_day_07 = Some(CalendarWeekday::Sunday);
+        assert_eq!(model.normalized(), Err(WeekendDaysError::SlotGap { slot: 7 }));
+    }
+}
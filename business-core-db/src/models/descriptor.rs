@@ -0,0 +1,298 @@
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+/// Schema-style description of a model, for generating API documentation and client types
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelDescriptor {
+    pub name: &'static str,
+    pub fields: Vec<FieldDescriptor>,
+}
+
+/// Description of a single model field
+///
+/// `type_name` is the JSON-facing type (`string`, `uuid`, `date`, ...). `enum_values`
+/// lists the allowed strings for enum fields, exactly as serde writes them.
+/// Nested structs carry their own descriptor in `nested`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDescriptor {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub optional: bool,
+    pub max_length: Option<usize>,
+    pub enum_values: Option<Vec<String>>,
+    pub nested: Option<ModelDescriptor>,
+}
+
+impl ModelDescriptor {
+    pub fn new(name: &'static str, fields: Vec<FieldDescriptor>) -> Self {
+        Self { name, fields }
+    }
+
+    /// Looks up a field by name
+    pub fn field(&self, name: &str) -> Option<&FieldDescriptor> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl FieldDescriptor {
+    pub fn new(name: &'static str, type_name: &'static str) -> Self {
+        Self {
+            name,
+            type_name,
+            optional: false,
+            max_length: None,
+            enum_values: None,
+            nested: None,
+        }
+    }
+
+    /// A bounded string field, as backed by `heapless::String<N>`
+    pub fn string(name: &'static str, max_length: usize) -> Self {
+        Self {
+            max_length: Some(max_length),
+            ..Self::new(name, "string")
+        }
+    }
+
+    /// An enum field; the allowed values are the serde strings of the variants of `T`
+    ///
+    /// Fails when a variant does not serialize to a plain string.
+    pub fn enumeration<T: IntoEnumIterator + Serialize>(name: &'static str) -> Result<Self, String> {
+        Ok(Self {
+            enum_values: Some(enum_values::<T>()?),
+            ..Self::new(name, "enum")
+        })
+    }
+
+    /// A nested struct field
+    pub fn object(name: &'static str, descriptor: ModelDescriptor) -> Self {
+        Self {
+            nested: Some(descriptor),
+            ..Self::new(name, "object")
+        }
+    }
+
+    /// Marks the field as optional (serialized as `null` when absent)
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+}
+
+/// Models that can describe their serialized shape
+pub trait Describe {
+    fn describe() -> Result<ModelDescriptor, String>;
+}
+
+fn enum_values<T: IntoEnumIterator + Serialize>() -> Result<Vec<String>, String> {
+    T::iter()
+        .map(|variant| match serde_json::to_value(&variant) {
+            Ok(serde_json::Value::String(value)) => Ok(value),
+            Ok(other) => Err(format!("Enum variant serializes to {other} instead of a string")),
+            Err(e) => Err(format!("Enum variant cannot be serialized: {e}")),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Describe, FieldDescriptor, ModelDescriptor};
+    use crate::models::person::common_enums::{PersonStatus, RiskRating};
+    use crate::models::person::person::{IdentityType, PersonModel, PersonType};
+    use crate::models::product::product::{ProductModel, ProductType};
+    use crate::models::product::product_rules::{PostingFrequency, ProductAccrualFrequency};
+    use crate::models::reason_and_purpose::{ReasonCategory, ReasonContext, ReasonModel, ReasonSeverity};
+    use crate::fixtures::ProductFixture;
+    use heapless::String as HeaplessString;
+    use serde::Serialize;
+    use strum::IntoEnumIterator;
+    use uuid::Uuid;
+
+    fn test_person() -> PersonModel {
+        PersonModel {
+            id: Uuid::new_v4(),
+            person_type: PersonType::Natural,
+            risk_rating: RiskRating::Low,
+            status: PersonStatus::Active,
+            display_name: HeaplessString::try_from("Descriptor Person").unwrap(),
+            external_identifier: None,
+            id_type: IdentityType::NationalId,
+            id_number: HeaplessString::try_from("ID-1").unwrap(),
+            entity_reference_count: 0,
+            organization_person_id: None,
+            messaging_info1: None,
+            messaging_info2: None,
+            messaging_info3: None,
+            messaging_info4: None,
+            messaging_info5: None,
+            department: None,
+            location_id: None,
+            duplicate_of_person_id: None,
+            last_activity_log: None,
+            last_compliance_status: None,
+            last_document: None,
+            last_portfolio: None,
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: None,
+        }
+    }
+
+    fn test_reason() -> ReasonModel {
+        ReasonModel {
+            id: Uuid::new_v4(),
+            code: HeaplessString::try_from("DESCRIPTOR").unwrap(),
+            category: ReasonCategory::Other,
+            context: ReasonContext::General,
+            l1_content: None,
+            l2_content: None,
+            l3_content: None,
+            l1_language_code: None,
+            l2_language_code: None,
+            l3_language_code: None,
+            requires_details: false,
+            is_active: true,
+            severity: None,
+            display_order: 0,
+            compliance_metadata: None,
+        }
+    }
+
+    fn test_product() -> ProductModel {
+        ProductFixture::builder().build()
+    }
+
+    /// Serializes `model` once per variant, with `set` applying the variant, and
+    /// collects the string written for `field`
+    fn serialized_values<M: Serialize + Clone, V: IntoEnumIterator>(
+        model: &M,
+        set: impl Fn(&mut M, V),
+        field: &[&str],
+    ) -> Vec<String> {
+        V::iter()
+            .map(|variant| {
+                let mut model = model.clone();
+                set(&mut model, variant);
+                let mut value = serde_json::to_value(&model).unwrap();
+                for name in field {
+                    value = value[*name].take();
+                }
+                value.as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    fn enum_values<'a>(descriptor: &'a ModelDescriptor, path: &[&str]) -> &'a [String] {
+        let (last, parents) = path.split_last().unwrap();
+        let mut descriptor = descriptor;
+        for parent in parents {
+            descriptor = descriptor.field(parent).unwrap().nested.as_ref().unwrap();
+        }
+        descriptor.field(last).unwrap().enum_values.as_deref().unwrap()
+    }
+
+    #[test]
+    fn test_descriptor_fields_match_serialized_keys() {
+        fn keys<M: Serialize>(model: &M) -> Vec<String> {
+            let mut keys: Vec<String> = serde_json::to_value(model)
+                .unwrap()
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+            keys.sort();
+            keys
+        }
+        fn names(descriptor: &ModelDescriptor) -> Vec<String> {
+            let mut names: Vec<String> = descriptor.fields.iter().map(|f| f.name.to_string()).collect();
+            names.sort();
+            names
+        }
+
+        assert_eq!(names(&PersonModel::describe().unwrap()), keys(&test_person()));
+        assert_eq!(names(&ReasonModel::describe().unwrap()), keys(&test_reason()));
+        assert_eq!(names(&ProductModel::describe().unwrap()), keys(&test_product()));
+        let product = ProductModel::describe().unwrap();
+        let rules = product.field("rules").unwrap().nested.as_ref().unwrap();
+        assert_eq!(names(rules), keys(&test_product().rules));
+    }
+
+    #[test]
+    fn test_descriptor_enum_values_match_serialized_strings() {
+        let person = PersonModel::describe().unwrap();
+        let model = test_person();
+        assert_eq!(
+            enum_values(&person, &["person_type"]),
+            serialized_values(&model, |m, v: PersonType| m.person_type = v, &["person_type"])
+        );
+        assert_eq!(
+            enum_values(&person, &["risk_rating"]),
+            serialized_values(&model, |m, v: RiskRating| m.risk_rating = v, &["risk_rating"])
+        );
+        assert_eq!(
+            enum_values(&person, &["status"]),
+            serialized_values(&model, |m, v: PersonStatus| m.status = v, &["status"])
+        );
+        assert_eq!(
+            enum_values(&person, &["id_type"]),
+            serialized_values(&model, |m, v: IdentityType| m.id_type = v, &["id_type"])
+        );
+
+        let reason = ReasonModel::describe().unwrap();
+        let model = test_reason();
+        assert_eq!(
+            enum_values(&reason, &["category"]),
+            serialized_values(&model, |m, v: ReasonCategory| m.category = v, &["category"])
+        );
+        assert_eq!(
+            enum_values(&reason, &["context"]),
+            serialized_values(&model, |m, v: ReasonContext| m.context = v, &["context"])
+        );
+        assert_eq!(
+            enum_values(&reason, &["severity"]),
+            serialized_values(&model, |m, v: ReasonSeverity| m.severity = Some(v), &["severity"])
+        );
+        assert!(reason.field("severity").unwrap().optional);
+
+        let product = ProductModel::describe().unwrap();
+        let model = test_product();
+        assert_eq!(
+            enum_values(&product, &["product_type"]),
+            serialized_values(&model, |m, v: ProductType| m.product_type = v, &["product_type"])
+        );
+        assert_eq!(
+            enum_values(&product, &["rules", "interest_posting_frequency"]),
+            serialized_values(
+                &model,
+                |m, v: PostingFrequency| m.rules.interest_posting_frequency = v,
+                &["rules", "interest_posting_frequency"],
+            )
+        );
+        assert_eq!(
+            enum_values(&product, &["rules", "accrual_frequency"]),
+            serialized_values(
+                &model,
+                |m, v: ProductAccrualFrequency| m.rules.accrual_frequency = v,
+                &["rules", "accrual_frequency"],
+            )
+        );
+    }
+
+    #[test]
+    fn test_enumeration_rejects_non_string_variants() {
+        #[derive(Serialize, strum::EnumIter)]
+        enum Shape {
+            Plain,
+            Sized(u8),
+        }
+
+        let error = FieldDescriptor::enumeration::<Shape>("shape").unwrap_err();
+        assert!(error.contains("instead of a string"), "{error}");
+        assert_eq!(
+            FieldDescriptor::enumeration::<RiskRating>("risk_rating").unwrap().enum_values,
+            Some(vec!["Low".to_string(), "Medium".to_string(), "High".to_string(), "Blacklisted".to_string()])
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+
+use crate::utils::Clock;
+
+/// Model applying over a range of dates
+///
+/// The range starts on `effective_from`, inclusive, and ends on `effective_until`,
+/// exclusive: a model expiring on a date no longer applies on that date. `None`
+/// leaves the range open on that side.
+pub trait EffectiveDated {
+    /// First date the model applies on
+    fn effective_from(&self) -> Option<NaiveDate>;
+
+    /// First date the model no longer applies on
+    fn effective_until(&self) -> Option<NaiveDate>;
+
+    /// Whether `date` falls inside the model's range
+    fn is_effective_on(&self, date: NaiveDate) -> bool {
+        self.effective_from().is_none_or(|from| from <= date)
+            && self.effective_until().is_none_or(|until| date < until)
+    }
+
+    /// Whether the model applies on the current date of `clock`
+    fn is_effective_today(&self, clock: &dyn Clock) -> bool {
+        self.is_effective_on(clock.today())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EffectiveDated;
+    use crate::fixtures::{DateCalculationRulesFixture, ProductFixture};
+    use crate::utils::{Clock, FixedClock};
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_product_boundaries() {
+        let product = ProductFixture::builder()
+            .with(|p| {
+                p.valid_from = date(2024, 3, 1);
+                p.valid_to = Some(date(2024, 6, 1));
+            })
+            .build();
+
+        assert!(!product.is_effective_on(date(2024, 2, 29)));
+        assert!(product.is_effective_on(date(2024, 3, 1)));
+        assert!(product.is_effective_on(date(2024, 5, 31)));
+        assert!(!product.is_effective_on(date(2024, 6, 1)));
+    }
+
+    #[test]
+    fn test_product_open_ended() {
+        let product = ProductFixture::builder()
+            .with(|p| p.valid_from = date(2024, 3, 1))
+            .build();
+
+        assert!(!product.is_effective_on(date(2024, 2, 29)));
+        assert!(product.is_effective_on(date(2024, 3, 1)));
+        assert!(product.is_effective_on(date(2999, 12, 31)));
+    }
+
+    #[test]
+    fn test_date_calculation_rules_boundaries() {
+        let rule = DateCalculationRulesFixture::builder(Uuid::new_v4())
+            .effective(date(2024, 1, 1), Some(date(2024, 12, 31)))
+            .build();
+
+        assert!(!rule.is_effective_on(date(2023, 12, 31)));
+        assert!(rule.is_effective_on(date(2024, 1, 1)));
+        assert!(rule.is_effective_on(date(2024, 12, 30)));
+        assert!(!rule.is_effective_on(date(2024, 12, 31)));
+    }
+
+    #[test]
+    fn test_date_calculation_rules_open_ended() {
+        let rule = DateCalculationRulesFixture::builder(Uuid::new_v4())
+            .effective(date(2024, 1, 1), None)
+            .build();
+
+        assert!(!rule.is_effective_on(date(2023, 12, 31)));
+        assert!(rule.is_effective_on(date(2024, 1, 1)));
+        assert!(rule.is_effective_on(date(2999, 12, 31)));
+    }
+
+    #[test]
+    fn test_same_day_range_is_empty() {
+        let rule = DateCalculationRulesFixture::builder(Uuid::new_v4())
+            .effective(date(2024, 1, 1), Some(date(2024, 1, 1)))
+            .build();
+
+        assert!(!rule.is_effective_on(date(2024, 1, 1)));
+    }
+
+    #[test]
+    fn test_effective_today_on_leap_day() {
+        let leap_day = FixedClock::new(date(2024, 2, 29));
+        assert_eq!(leap_day.today(), date(2024, 2, 29));
+
+        let ending_on_leap_day = ProductFixture::builder()
+            .with(|p| {
+                p.valid_from = date(2024, 1, 1);
+                p.valid_to = Some(date(2024, 2, 29));
+            })
+            .build();
+        let starting_on_leap_day = ProductFixture::builder()
+            .with(|p| {
+                p.valid_from = date(2024, 2, 29);
+                p.valid_to = Some(date(2024, 3, 1));
+            })
+            .build();
+
+        assert!(!ending_on_leap_day.is_effective_today(&leap_day));
+        assert!(starting_on_leap_day.is_effective_today(&leap_day));
+        assert!(!starting_on_leap_day.is_effective_today(&FixedClock::new(date(2024, 3, 1))));
+    }
+
+    #[test]
+    fn test_effective_today_across_year_end() {
+        let new_year_eve = FixedClock::new(date(2024, 12, 31));
+        let new_year = FixedClock::new(date(2025, 1, 1));
+        let rule_2024 = DateCalculationRulesFixture::builder(Uuid::new_v4())
+            .effective(date(2024, 1, 1), Some(date(2025, 1, 1)))
+            .build();
+        let rule_2025 = DateCalculationRulesFixture::builder(Uuid::new_v4())
+            .effective(date(2025, 1, 1), None)
+            .build();
+
+        assert!(rule_2024.is_effective_today(&new_year_eve));
+        assert!(!rule_2025.is_effective_today(&new_year_eve));
+        assert!(!rule_2024.is_effective_today(&new_year));
+        assert!(rule_2025.is_effective_today(&new_year));
+    }
+}
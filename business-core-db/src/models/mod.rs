@@ -1,4 +1,7 @@
+pub mod audit_chained;
 pub mod auditable;
+pub mod descriptor;
+pub mod effective_dated;
 pub mod identifiable;
 pub mod index;
 pub mod index_aware;
@@ -13,7 +16,12 @@ pub mod product;
 // pub mod person;
 
 // Re-exports
+pub use audit_chained::{
+    order_chain, verify_chain, verify_chain_from, verify_links, ArchivedAntecedent, AuditChained, AuditVersion, ChainError, LinkVerification, AUDIT_FIELDS,
+};
 pub use auditable::*;
+pub use descriptor::{Describe, FieldDescriptor, ModelDescriptor};
+pub use effective_dated::EffectiveDated;
 pub use identifiable::*;
 pub use index::*;
 pub use index_aware::*;
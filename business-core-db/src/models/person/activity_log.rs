@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use heapless::String as HeaplessString;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 
@@ -18,6 +20,9 @@ pub struct ActivityLogModel {
     /// Summary of the activity
     pub activity_summary: Option<HeaplessString<250>>,
     
+    /// Date the activity took place
+    pub activity_date: DateTime<Utc>,
+    
     /// First predecessor reference (nullable)
     pub predecessor_1: Option<Uuid>,
     
@@ -56,4 +61,6 @@ impl Auditable for ActivityLogModel {
     fn get_audit_log_id(&self) -> Option<Uuid> {
         self.audit_log_id
     }
-}
\ No newline at end of file
+}
+
+impl_audit_chained!(ActivityLogModel);
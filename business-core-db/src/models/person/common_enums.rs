@@ -1,7 +1,8 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+/// Ordered from lowest to highest risk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "risk_rating", rename_all = "PascalCase")]
 pub enum RiskRating {
     Low,
@@ -62,7 +63,7 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "person_status", rename_all = "PascalCase")]
 pub enum PersonStatus {
     Active,
@@ -72,6 +73,36 @@ pub enum PersonStatus {
     Blacklisted,
 }
 
+impl PersonStatus {
+    /// Whether a person in this status may be moved to `next`
+    ///
+    /// Keeping the status is always allowed. Deceased and Dissolved are final. A
+    /// blacklisted person is never made Active directly: reactivation goes back
+    /// through PendingVerification.
+    pub fn can_transition_to(&self, next: PersonStatus) -> bool {
+        use PersonStatus::*;
+        *self == next
+            || match self {
+                Active => matches!(next, PendingVerification | Blacklisted | Deceased | Dissolved),
+                PendingVerification => matches!(next, Active | Blacklisted | Deceased | Dissolved),
+                Blacklisted => matches!(next, PendingVerification | Deceased | Dissolved),
+                Deceased | Dissolved => false,
+            }
+    }
+}
+
+impl std::fmt::Display for PersonStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersonStatus::Active => write!(f, "Active"),
+            PersonStatus::PendingVerification => write!(f, "PendingVerification"),
+            PersonStatus::Deceased => write!(f, "Deceased"),
+            PersonStatus::Dissolved => write!(f, "Dissolved"),
+            PersonStatus::Blacklisted => write!(f, "Blacklisted"),
+        }
+    }
+}
+
 impl FromStr for PersonStatus {
     type Err = ();
 
@@ -116,4 +147,56 @@ where
             "Invalid PersonStatus: {value_str}"
         ))),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PersonStatus, RiskRating};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_risk_rating_order() {
+        let ratings: Vec<RiskRating> = RiskRating::iter().collect();
+        assert!(ratings.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(ratings.iter().max(), Some(&RiskRating::Blacklisted));
+    }
+
+    #[test]
+    fn test_person_status_legal_transitions() {
+        use PersonStatus::*;
+        let legal = [
+            (Active, PendingVerification),
+            (Active, Blacklisted),
+            (Active, Deceased),
+            (Active, Dissolved),
+            (PendingVerification, Active),
+            (PendingVerification, Blacklisted),
+            (PendingVerification, Deceased),
+            (PendingVerification, Dissolved),
+            (Blacklisted, PendingVerification),
+            (Blacklisted, Deceased),
+            (Blacklisted, Dissolved),
+        ];
+        for (from, to) in legal {
+            assert!(from.can_transition_to(to), "{from} -> {to} must be allowed");
+        }
+        for status in PersonStatus::iter() {
+            assert!(status.can_transition_to(status));
+        }
+
+        // Every other move is refused
+        let refused = PersonStatus::iter()
+            .flat_map(|from| PersonStatus::iter().map(move |to| (from, to)))
+            .filter(|(from, to)| from != to && !legal.contains(&(*from, *to)))
+            .filter(|(from, to)| from.can_transition_to(*to))
+            .collect::<Vec<_>>();
+        assert!(refused.is_empty(), "unexpected transitions allowed: {refused:?}");
+    }
+
+    #[test]
+    fn test_person_status_illegal_transitions() {
+        assert!(!PersonStatus::Blacklisted.can_transition_to(PersonStatus::Active));
+        assert!(!PersonStatus::Deceased.can_transition_to(PersonStatus::Active));
+        assert!(!PersonStatus::Dissolved.can_transition_to(PersonStatus::PendingVerification));
+    }
+}
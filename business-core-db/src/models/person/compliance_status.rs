@@ -3,6 +3,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sqlx::FromRow;
 use std::str::FromStr;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 
@@ -131,4 +132,6 @@ impl Auditable for ComplianceStatusModel {
     fn get_audit_log_id(&self) -> Option<Uuid> {
         self.audit_log_id
     }
-}
\ No newline at end of file
+}
+
+impl_audit_chained!(ComplianceStatusModel);
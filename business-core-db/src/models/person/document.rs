@@ -3,13 +3,16 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sqlx::FromRow;
 use std::str::FromStr;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
+use crate::utils::hash_as_i64;
 
 /// # Documentation
-/// Database model for Customer documents
+/// Database model for documents
 /// 
-/// Tracks document information for a person including:
+/// Tracks document information for an owner (a person, a product or an entity
+/// reference) including:
 /// - Document type (e.g., passport, driver's license)
 /// - Document storage path
 /// - Document verification status
@@ -19,8 +22,15 @@ use crate::models::identifiable::Identifiable;
 pub struct DocumentModel {
     pub id: Uuid,
     
-    /// Reference to the person who owns this document
-    pub person_id: Uuid,
+    /// Reference to the entity that owns this document
+    pub owner_id: Uuid,
+    
+    /// Kind of entity `owner_id` refers to
+    #[serde(
+        serialize_with = "serialize_document_owner_type",
+        deserialize_with = "deserialize_document_owner_type"
+    )]
+    pub owner_type: DocumentOwnerType,
     
     /// Type of document (e.g., "Passport", "ID Card", "Driver License")
     pub document_type: HeaplessString<50>,
@@ -63,6 +73,29 @@ pub struct DocumentModel {
     pub audit_log_id: Option<Uuid>,
 }
 
+impl DocumentModel {
+    /// Returns the non-null predecessor references in slot order
+    pub fn predecessors(&self) -> Vec<Uuid> {
+        [self.predecessor_1, self.predecessor_2, self.predecessor_3]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// # Documentation
+/// Node of a document history tree
+///
+/// Each node holds a document and the history nodes of the documents it superseded,
+/// following `predecessor_1`, `predecessor_2` and `predecessor_3` in that order.
+#[derive(Debug, Clone)]
+pub struct DocumentHistoryNode {
+    pub document: DocumentModel,
+
+    /// History of the superseded documents, in predecessor slot order
+    pub predecessors: Vec<DocumentHistoryNode>,
+}
+
 impl Identifiable for DocumentModel {
     fn get_id(&self) -> Uuid {
         self.id
@@ -75,6 +108,108 @@ impl Auditable for DocumentModel {
     }
 }
 
+impl_audit_chained!(DocumentModel, legacy_hashes = document_legacy_hashes);
+
+/// Serialized layout of a person's document before migration 027, which renamed
+/// `person_id` to `owner_id` and added `owner_type`
+#[derive(Serialize)]
+struct PersonDocumentLayout<'a> {
+    id: Uuid,
+    person_id: Uuid,
+    document_type: &'a HeaplessString<50>,
+    document_path: &'a Option<HeaplessString<500>>,
+    #[serde(serialize_with = "serialize_document_status")]
+    status: DocumentStatus,
+    predecessor_1: Option<Uuid>,
+    predecessor_2: Option<Uuid>,
+    predecessor_3: Option<Uuid>,
+    antecedent_hash: i64,
+    antecedent_audit_log_id: Uuid,
+    hash: i64,
+    audit_log_id: Option<Uuid>,
+}
+
+/// Hash of a person's document under the pre-027 layout, documents stored before
+/// migration 027 keep it
+fn document_legacy_hashes(document: &DocumentModel) -> Vec<i64> {
+    if document.owner_type != DocumentOwnerType::Person {
+        return Vec::new();
+    }
+    let layout = PersonDocumentLayout {
+        id: document.id,
+        person_id: document.owner_id,
+        document_type: &document.document_type,
+        document_path: &document.document_path,
+        status: document.status,
+        predecessor_1: document.predecessor_1,
+        predecessor_2: document.predecessor_2,
+        predecessor_3: document.predecessor_3,
+        antecedent_hash: document.antecedent_hash,
+        antecedent_audit_log_id: document.antecedent_audit_log_id,
+        hash: 0,
+        audit_log_id: document.audit_log_id,
+    };
+    hash_as_i64(&layout).into_iter().collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "document_owner_type", rename_all = "PascalCase")]
+pub enum DocumentOwnerType {
+    Person,
+    Product,
+    EntityReference,
+}
+
+impl std::fmt::Display for DocumentOwnerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentOwnerType::Person => write!(f, "Person"),
+            DocumentOwnerType::Product => write!(f, "Product"),
+            DocumentOwnerType::EntityReference => write!(f, "EntityReference"),
+        }
+    }
+}
+
+impl FromStr for DocumentOwnerType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Person" => Ok(DocumentOwnerType::Person),
+            "Product" => Ok(DocumentOwnerType::Product),
+            "EntityReference" => Ok(DocumentOwnerType::EntityReference),
+            _ => Err(()),
+        }
+    }
+}
+
+fn serialize_document_owner_type<S>(value: &DocumentOwnerType, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let value_str = match value {
+        DocumentOwnerType::Person => "Person",
+        DocumentOwnerType::Product => "Product",
+        DocumentOwnerType::EntityReference => "EntityReference",
+    };
+    serializer.serialize_str(value_str)
+}
+
+fn deserialize_document_owner_type<'de, D>(deserializer: D) -> Result<DocumentOwnerType, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value_str = String::deserialize(deserializer)?;
+    match value_str.as_str() {
+        "Person" => Ok(DocumentOwnerType::Person),
+        "Product" => Ok(DocumentOwnerType::Product),
+        "EntityReference" => Ok(DocumentOwnerType::EntityReference),
+        _ => Err(serde::de::Error::custom(format!(
+            "Invalid DocumentOwnerType: {value_str}"
+        ))),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "document_status", rename_all = "PascalCase")]
 pub enum DocumentStatus {
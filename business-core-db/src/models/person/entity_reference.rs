@@ -4,7 +4,9 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
+use crate::models::effective_dated::EffectiveDated;
 use crate::models::identifiable::Identifiable;
 use crate::models::{Index, IndexAware};
 
@@ -105,6 +107,9 @@ pub struct EntityReferenceModel {
 
     /// # Documentation
     /// - Type of entity relationship
+    ///
+    /// # Finder Method (use index)
+    /// - find_by_person_and_role, through entity_role_hash
     #[serde(serialize_with = "serialize_person_entity_type", deserialize_with = "deserialize_person_entity_type")]
     pub entity_role: RelationshipRole,
 
@@ -166,6 +171,21 @@ impl Auditable for EntityReferenceModel {
     }
 }
 
+/// Day granularity of `start_date` and `end_date`
+///
+/// A relationship ending during a day is no longer effective on that day.
+impl EffectiveDated for EntityReferenceModel {
+    fn effective_from(&self) -> Option<chrono::NaiveDate> {
+        self.start_date.map(|start| start.date_naive())
+    }
+
+    fn effective_until(&self) -> Option<chrono::NaiveDate> {
+        self.end_date.map(|end| end.date_naive())
+    }
+}
+
+impl_audit_chained!(EntityReferenceModel);
+
 // Serialization functions for RelationshipRole
 pub fn serialize_person_entity_type<S>(entity_role: &RelationshipRole, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -258,11 +278,21 @@ where
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct EntityReferenceIdxModel {
     pub id: Uuid,
     pub person_id: Uuid,
     pub reference_external_id_hash: i64,
+    /// `hash_as_i64` of the role, see `entity_role_hash`
+    pub entity_role_hash: i64,
+}
+
+/// Index hash of a role
+///
+/// The role serializes as its variant name, the migration filling entity_role_hash
+/// of existing rows relies on it.
+pub fn entity_role_hash(role: RelationshipRole) -> i64 {
+    crate::utils::hash_as_i64(&role).unwrap_or(0)
 }
 
 impl HasPrimaryKey for EntityReferenceIdxModel {
@@ -282,6 +312,7 @@ impl IndexAware for EntityReferenceModel {
             id: self.id,
             person_id: self.person_id,
             reference_external_id_hash,
+            entity_role_hash: entity_role_hash(self.entity_role),
         }
     }
 }
@@ -298,6 +329,7 @@ impl Indexable for EntityReferenceIdxModel {
     fn i64_keys(&self) -> HashMap<String, Option<i64>> {
         let mut keys = HashMap::new();
         keys.insert("reference_external_id_hash".to_string(), Some(self.reference_external_id_hash));
+        keys.insert("entity_role_hash".to_string(), Some(self.entity_role_hash));
         keys
     }
 
@@ -5,7 +5,7 @@ use uuid::Uuid;
 use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{IndexAware, Identifiable, Index};
-use crate::utils::hash_as_i64;
+use crate::utils::{hash_as_i64, normalize_code};
 
 /// # Documentation
 /// - Locality structure with locality code and multi-language names
@@ -31,6 +31,37 @@ pub struct LocalityIdxModel {
     pub code_hash: i64,
 }
 
+impl LocalityModel {
+    /// New locality with a fresh id and its code normalized, see `normalize_code`
+    pub fn new(country_subdivision_id: Uuid, code: &str, name_l1: &str) -> Result<Self, String> {
+        let mut item = Self {
+            id: Uuid::new_v4(),
+            country_subdivision_id,
+            code: HeaplessString::new(),
+            name_l1: HeaplessString::try_from(name_l1)
+                .map_err(|_| format!("Locality name is longer than 50 characters: {name_l1}"))?,
+            name_l2: None,
+            name_l3: None,
+        };
+        item.set_code(code)?;
+        Ok(item)
+    }
+
+    /// Set `code` to the normal form of `code`
+    pub fn set_code(&mut self, code: &str) -> Result<(), String> {
+        let code = normalize_code(code);
+        self.code = HeaplessString::try_from(code.as_str())
+            .map_err(|_| format!("Locality code is longer than 50 characters: {code}"))?;
+        Ok(())
+    }
+
+    /// Rewrite `code` in its normal form
+    pub fn normalize(&mut self) -> Result<(), String> {
+        let code = self.code.clone();
+        self.set_code(&code)
+    }
+}
+
 impl HasPrimaryKey for LocalityIdxModel {
     fn primary_key(&self) -> Uuid {
         self.id
@@ -47,8 +78,8 @@ impl IndexAware for LocalityModel {
     type IndexType = LocalityIdxModel;
     
     fn to_index(&self) -> Self::IndexType {
-        // Calculate hash for code field using the centralized hash_as_i64 function
-        let code_hash = hash_as_i64(&self.code.as_str()).unwrap();
+        // Hash the normalized code, so the index matches whatever case the code was given in
+        let code_hash = hash_as_i64(&normalize_code(&self.code).as_str()).unwrap();
         
         LocalityIdxModel {
             id: self.id,
@@ -3,6 +3,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 use std::collections::HashMap;
@@ -75,6 +76,8 @@ impl Auditable for LocationModel {
     }
 }
 
+impl_audit_chained!(LocationModel);
+
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct LocationIdxModel {
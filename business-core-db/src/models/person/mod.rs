@@ -4,6 +4,7 @@ pub mod locality;
 pub mod location;
 #[allow(clippy::module_inception)]
 pub mod person;
+pub mod person_builder;
 pub mod entity_reference;
 pub mod common_enums;
 pub mod activity_log;
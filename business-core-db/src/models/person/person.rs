@@ -3,15 +3,18 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sqlx::FromRow;
 use std::str::FromStr;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{Index, IndexAware};
 use crate::models::person::common_enums::{RiskRating, PersonStatus};
+use crate::utils::HashVersion;
+use crate::models::descriptor::{Describe, FieldDescriptor, ModelDescriptor};
 
 /// Database model for identity type enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "identity_type", rename_all = "PascalCase")]
 pub enum IdentityType {
     NationalId,
@@ -55,7 +58,7 @@ impl FromStr for IdentityType {
 }
 
 /// Database model for person type enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "person_type", rename_all = "PascalCase")]
 pub enum PersonType {
     Natural,
@@ -154,6 +157,42 @@ pub struct PersonModel {
     pub audit_log_id: Option<Uuid>,
 }
 
+impl Describe for PersonModel {
+    fn describe() -> Result<ModelDescriptor, String> {
+        Ok(ModelDescriptor::new(
+            "PersonModel",
+            vec![
+                FieldDescriptor::new("id", "uuid"),
+                FieldDescriptor::enumeration::<PersonType>("person_type")?,
+                FieldDescriptor::enumeration::<RiskRating>("risk_rating")?,
+                FieldDescriptor::enumeration::<PersonStatus>("status")?,
+                FieldDescriptor::string("display_name", 100),
+                FieldDescriptor::string("external_identifier", 50).optional(),
+                FieldDescriptor::enumeration::<IdentityType>("id_type")?,
+                FieldDescriptor::string("id_number", 50),
+                FieldDescriptor::new("entity_reference_count", "integer"),
+                FieldDescriptor::new("organization_person_id", "uuid").optional(),
+                FieldDescriptor::string("messaging_info1", 50).optional(),
+                FieldDescriptor::string("messaging_info2", 50).optional(),
+                FieldDescriptor::string("messaging_info3", 50).optional(),
+                FieldDescriptor::string("messaging_info4", 50).optional(),
+                FieldDescriptor::string("messaging_info5", 50).optional(),
+                FieldDescriptor::string("department", 50).optional(),
+                FieldDescriptor::new("location_id", "uuid").optional(),
+                FieldDescriptor::new("duplicate_of_person_id", "uuid").optional(),
+                FieldDescriptor::new("last_activity_log", "uuid").optional(),
+                FieldDescriptor::new("last_compliance_status", "uuid").optional(),
+                FieldDescriptor::new("last_document", "uuid").optional(),
+                FieldDescriptor::new("last_portfolio", "uuid").optional(),
+                FieldDescriptor::new("antecedent_hash", "integer"),
+                FieldDescriptor::new("antecedent_audit_log_id", "uuid"),
+                FieldDescriptor::new("hash", "integer"),
+                FieldDescriptor::new("audit_log_id", "uuid").optional(),
+            ],
+        ))
+    }
+}
+
 impl Identifiable for PersonModel {
     fn get_id(&self) -> Uuid {
         self.id
@@ -166,14 +205,23 @@ impl Auditable for PersonModel {
     }
 }
 
+impl_audit_chained!(PersonModel);
+
 /// Index model for Person
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct PersonIdxModel {
     pub id: Uuid,
     pub external_identifier_hash: Option<i64>,
     pub organization_person_id: Option<Uuid>,
     pub duplicate_of_person_id: Option<Uuid>,
     pub id_number_hash: Option<i64>,
+    pub location_id: Option<Uuid>,
+    /// Hash version of `external_identifier_hash` and `id_number_hash`
+    ///
+    /// Defaulted so cache notification payloads from rows written before the
+    /// column existed still deserialize.
+    #[serde(default)]
+    pub hash_version: HashVersion,
 }
 
 impl HasPrimaryKey for PersonIdxModel {
@@ -186,11 +234,18 @@ impl IndexAware for PersonModel {
     type IndexType = PersonIdxModel;
     
     fn to_index(&self) -> Self::IndexType {
+        self.to_index_with_hash_version(HashVersion::default())
+    }
+}
+
+impl PersonModel {
+    /// Build the index model with the hash columns computed by `hash_version`
+    pub fn to_index_with_hash_version(&self, hash_version: HashVersion) -> PersonIdxModel {
         let external_identifier_hash = self.external_identifier.as_ref().and_then(|ext_id| {
-            crate::utils::hash_as_i64(&ext_id.as_str()).ok()
+            hash_version.hash(&ext_id.as_str()).ok()
         });
 
-        let id_number_hash = crate::utils::hash_as_i64(&self.id_number.as_str()).ok();
+        let id_number_hash = hash_version.hash(&self.id_number.as_str()).ok();
 
         PersonIdxModel {
             id: self.id,
@@ -198,6 +253,8 @@ impl IndexAware for PersonModel {
             organization_person_id: self.organization_person_id,
             duplicate_of_person_id: self.duplicate_of_person_id,
             id_number_hash,
+            location_id: self.location_id,
+            hash_version,
         }
     }
 }
@@ -234,6 +291,7 @@ impl Indexable for PersonIdxModel {
             "duplicate_of_person_id".to_string(),
             self.duplicate_of_person_id,
         );
+        keys.insert("location_id".to_string(), self.location_id);
         keys
     }
 }
@@ -304,4 +362,29 @@ where
         "Unknown" => Ok(PersonType::Unknown),
         _ => Err(serde::de::Error::custom(format!("Unknown person type: {s}"))),
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::PersonIdxModel;
+    use crate::utils::HashVersion;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_person_idx_payload_tolerates_unknown_and_missing_fields() {
+        let id = Uuid::new_v4();
+        let payload = serde_json::json!({
+            "id": id,
+            "external_identifier_hash": 42,
+            "id_number_hash": null,
+            "column_added_later": "ignored"
+        });
+
+        let idx: PersonIdxModel = serde_json::from_value(payload).unwrap();
+
+        assert_eq!(idx.id, id);
+        assert_eq!(idx.external_identifier_hash, Some(42));
+        assert_eq!(idx.organization_person_id, None);
+        assert_eq!(idx.duplicate_of_person_id, None);
+        assert_eq!(idx.hash_version, HashVersion::V1);
+    }
+}
@@ -0,0 +1,327 @@
+use heapless::String as HeaplessString;
+use uuid::Uuid;
+
+use crate::models::person::common_enums::{PersonStatus, RiskRating};
+use crate::models::person::person::{IdentityType, PersonModel, PersonType};
+
+/// Why `PersonBuilder::build` rejected a person
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PersonBuilderError {
+    /// `value` does not fit the `max` bytes of `field`
+    TooLong { field: &'static str, max: usize, value: String },
+    /// A required field was never set
+    Missing { field: &'static str },
+    /// The person references itself through `field`
+    SelfReference { field: &'static str },
+}
+
+impl std::fmt::Display for PersonBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersonBuilderError::TooLong { field, max, value } => {
+                write!(f, "{field}: {value:?} is longer than {max} bytes")
+            }
+            PersonBuilderError::Missing { field } => write!(f, "{field} is required"),
+            PersonBuilderError::SelfReference { field } => write!(f, "{field} references the person itself"),
+        }
+    }
+}
+
+impl std::error::Error for PersonBuilderError {}
+
+/// Builder of a new `PersonModel`
+///
+/// Starts from an active, low risk natural person of unknown identity, with no
+/// optional fields and no audit history: nil antecedent, hash 0, no audit log.
+/// Only `display_name` is required. Text setters check the field's length; the
+/// first failure is reported by `build`.
+#[derive(Debug, Clone)]
+pub struct PersonBuilder {
+    model: PersonModel,
+    display_name_set: bool,
+    error: Option<PersonBuilderError>,
+}
+
+impl PersonModel {
+    /// Builder of a new person
+    pub fn builder() -> PersonBuilder {
+        PersonBuilder {
+            model: PersonModel {
+                id: Uuid::new_v4(),
+                person_type: PersonType::Natural,
+                risk_rating: RiskRating::Low,
+                status: PersonStatus::Active,
+                display_name: HeaplessString::new(),
+                external_identifier: None,
+                id_type: IdentityType::Unknown,
+                id_number: HeaplessString::new(),
+                entity_reference_count: 0,
+                organization_person_id: None,
+                messaging_info1: None,
+                messaging_info2: None,
+                messaging_info3: None,
+                messaging_info4: None,
+                messaging_info5: None,
+                department: None,
+                location_id: None,
+                duplicate_of_person_id: None,
+                last_activity_log: None,
+                last_compliance_status: None,
+                last_document: None,
+                last_portfolio: None,
+                antecedent_hash: 0,
+                antecedent_audit_log_id: Uuid::nil(),
+                hash: 0,
+                audit_log_id: None,
+            },
+            display_name_set: false,
+            error: None,
+        }
+    }
+}
+
+impl PersonBuilder {
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.model.id = id;
+        self
+    }
+
+    pub fn person_type(mut self, person_type: PersonType) -> Self {
+        self.model.person_type = person_type;
+        self
+    }
+
+    pub fn risk_rating(mut self, risk_rating: RiskRating) -> Self {
+        self.model.risk_rating = risk_rating;
+        self
+    }
+
+    pub fn status(mut self, status: PersonStatus) -> Self {
+        self.model.status = status;
+        self
+    }
+
+    pub fn display_name(mut self, display_name: &str) -> Self {
+        if let Some(value) = self.text("display_name", display_name) {
+            self.model.display_name = value;
+            self.display_name_set = true;
+        }
+        self
+    }
+
+    pub fn external_identifier(mut self, external_identifier: &str) -> Self {
+        if let Some(value) = self.text("external_identifier", external_identifier) {
+            self.model.external_identifier = Some(value);
+        }
+        self
+    }
+
+    pub fn identity(mut self, id_type: IdentityType, id_number: &str) -> Self {
+        if let Some(value) = self.text("id_number", id_number) {
+            self.model.id_type = id_type;
+            self.model.id_number = value;
+        }
+        self
+    }
+
+    pub fn organization_person_id(mut self, organization_person_id: Uuid) -> Self {
+        self.model.organization_person_id = Some(organization_person_id);
+        self
+    }
+
+    /// Set the messaging methods, encoded as `type:value`, in slot order
+    ///
+    /// Up to 5 methods; more are reported by `build`.
+    pub fn messaging_info(mut self, messaging_info: &[&str]) -> Self {
+        if messaging_info.len() > 5 {
+            self.fail(PersonBuilderError::TooLong {
+                field: "messaging_info",
+                max: 5,
+                value: messaging_info.join(", "),
+            });
+            return self;
+        }
+        let mut slots: [Option<HeaplessString<50>>; 5] = Default::default();
+        for (slot, info) in slots.iter_mut().zip(messaging_info) {
+            match self.text("messaging_info", info) {
+                Some(value) => *slot = Some(value),
+                None => return self,
+            }
+        }
+        let [info1, info2, info3, info4, info5] = slots;
+        self.model.messaging_info1 = info1;
+        self.model.messaging_info2 = info2;
+        self.model.messaging_info3 = info3;
+        self.model.messaging_info4 = info4;
+        self.model.messaging_info5 = info5;
+        self
+    }
+
+    pub fn department(mut self, department: &str) -> Self {
+        if let Some(value) = self.text("department", department) {
+            self.model.department = Some(value);
+        }
+        self
+    }
+
+    pub fn location_id(mut self, location_id: Uuid) -> Self {
+        self.model.location_id = Some(location_id);
+        self
+    }
+
+    pub fn duplicate_of_person_id(mut self, duplicate_of_person_id: Uuid) -> Self {
+        self.model.duplicate_of_person_id = Some(duplicate_of_person_id);
+        self
+    }
+
+    /// The person, or the first error met while building it
+    pub fn build(self) -> Result<PersonModel, PersonBuilderError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if !self.display_name_set {
+            return Err(PersonBuilderError::Missing { field: "display_name" });
+        }
+        let model = self.model;
+        if model.organization_person_id == Some(model.id) {
+            return Err(PersonBuilderError::SelfReference { field: "organization_person_id" });
+        }
+        if model.duplicate_of_person_id == Some(model.id) {
+            return Err(PersonBuilderError::SelfReference { field: "duplicate_of_person_id" });
+        }
+        Ok(model)
+    }
+
+    /// Convert `value` for `field`, recording the error when it does not fit
+    fn text<const N: usize>(&mut self, field: &'static str, value: &str) -> Option<HeaplessString<N>> {
+        match HeaplessString::try_from(value) {
+            Ok(text) => Some(text),
+            Err(_) => {
+                self.fail(PersonBuilderError::TooLong {
+                    field,
+                    max: N,
+                    value: value.to_string(),
+                });
+                None
+            }
+        }
+    }
+
+    fn fail(&mut self, error: PersonBuilderError) {
+        self.error.get_or_insert(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PersonBuilderError;
+    use crate::models::person::common_enums::{PersonStatus, RiskRating};
+    use crate::models::person::person::{IdentityType, PersonModel, PersonType};
+    use crate::utils::hash_as_i64;
+    use heapless::String as HeaplessString;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_builder_matches_manual_construction() {
+        let id = Uuid::new_v4();
+        let organization_person_id = Uuid::new_v4();
+        let location_id = Uuid::new_v4();
+        let manual = PersonModel {
+            id,
+            person_type: PersonType::Legal,
+            risk_rating: RiskRating::Medium,
+            status: PersonStatus::Active,
+            display_name: HeaplessString::try_from("Acme Ltd").unwrap(),
+            external_identifier: Some(HeaplessString::try_from("EXT-1").unwrap()),
+            id_type: IdentityType::CompanyRegistration,
+            id_number: HeaplessString::try_from("RC-2024-001").unwrap(),
+            entity_reference_count: 0,
+            organization_person_id: Some(organization_person_id),
+            messaging_info1: Some(HeaplessString::try_from("email:info@acme.test").unwrap()),
+            messaging_info2: Some(HeaplessString::try_from("phone:+237600000000").unwrap()),
+            messaging_info3: None,
+            messaging_info4: None,
+            messaging_info5: None,
+            department: Some(HeaplessString::try_from("Treasury").unwrap()),
+            location_id: Some(location_id),
+            duplicate_of_person_id: None,
+            last_activity_log: None,
+            last_compliance_status: None,
+            last_document: None,
+            last_portfolio: None,
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: None,
+        };
+
+        let built = PersonModel::builder()
+            .id(id)
+            .person_type(PersonType::Legal)
+            .risk_rating(RiskRating::Medium)
+            .display_name("Acme Ltd")
+            .external_identifier("EXT-1")
+            .identity(IdentityType::CompanyRegistration, "RC-2024-001")
+            .organization_person_id(organization_person_id)
+            .messaging_info(&["email:info@acme.test", "phone:+237600000000"])
+            .department("Treasury")
+            .location_id(location_id)
+            .build()
+            .unwrap();
+
+        assert_eq!(hash_as_i64(&built).unwrap(), hash_as_i64(&manual).unwrap());
+        assert_eq!(serde_json::to_value(&built).unwrap(), serde_json::to_value(&manual).unwrap());
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let person = PersonModel::builder().display_name("Jane Doe").build().unwrap();
+
+        assert_eq!(person.person_type, PersonType::Natural);
+        assert_eq!(person.risk_rating, RiskRating::Low);
+        assert_eq!(person.status, PersonStatus::Active);
+        assert_eq!(person.id_type, IdentityType::Unknown);
+        assert!(person.id_number.is_empty());
+        assert!(person.messaging_info1.is_none());
+        assert_eq!(person.antecedent_audit_log_id, Uuid::nil());
+        assert_eq!(person.antecedent_hash, 0);
+        assert_eq!(person.hash, 0);
+        assert!(person.audit_log_id.is_none());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_fields() {
+        let error = PersonModel::builder()
+            .display_name(&"x".repeat(101))
+            .department(&"y".repeat(51))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            PersonBuilderError::TooLong {
+                field: "display_name",
+                max: 100,
+                value: "x".repeat(101),
+            }
+        );
+
+        let error = PersonModel::builder()
+            .display_name("Jane Doe")
+            .messaging_info(&["a:1", "b:2", "c:3", "d:4", "e:5", "f:6"])
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, PersonBuilderError::TooLong { field: "messaging_info", max: 5, .. }));
+
+        let error = PersonModel::builder().build().unwrap_err();
+        assert_eq!(error, PersonBuilderError::Missing { field: "display_name" });
+
+        let id = Uuid::new_v4();
+        let error = PersonModel::builder()
+            .id(id)
+            .display_name("Jane Doe")
+            .duplicate_of_person_id(id)
+            .build()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "duplicate_of_person_id references the person itself");
+    }
+}
@@ -2,6 +2,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 
@@ -79,4 +80,6 @@ impl Auditable for PortfolioModel {
     fn get_audit_log_id(&self) -> Option<Uuid> {
         self.audit_log_id
     }
-}
\ No newline at end of file
+}
+
+impl_audit_chained!(PortfolioModel);
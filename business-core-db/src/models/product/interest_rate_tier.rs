@@ -1,10 +1,260 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use super::product::ProductModel;
+use crate::utils::{rescale_decimal, MONETARY_SCALE, RATE_SCALE};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InterestRateTierModel {
     pub minimum_balance: Decimal,
     pub maximum_balance: Option<Decimal>,
     pub interest_rate: Decimal,
     pub tier_name: heapless::String<100>,
-}
\ No newline at end of file
+}
+
+impl InterestRateTierModel {
+    /// Rescale the band limits to `MONETARY_SCALE` and the rate to `RATE_SCALE` decimal places
+    pub fn normalize_decimals(&mut self) {
+        self.minimum_balance = rescale_decimal(self.minimum_balance, MONETARY_SCALE);
+        self.maximum_balance = self
+            .maximum_balance
+            .map(|maximum| rescale_decimal(maximum, MONETARY_SCALE));
+        self.interest_rate = rescale_decimal(self.interest_rate, RATE_SCALE);
+    }
+}
+
+/// Tier whose band contains `balance`, for products applying one rate to the whole balance
+///
+/// Bands are inclusive on both ends. When bands overlap the tier with the highest
+/// `minimum_balance` wins.
+pub fn resolve_tier(balance: Decimal, tiers: &[InterestRateTierModel]) -> Option<&InterestRateTierModel> {
+    tiers
+        .iter()
+        .filter(|tier| {
+            tier.minimum_balance <= balance
+                && tier.maximum_balance.is_none_or(|maximum| balance <= maximum)
+        })
+        .max_by_key(|tier| tier.minimum_balance)
+}
+
+/// Several tiers whose bands contain the same balance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmbiguousTiers {
+    pub balance: Decimal,
+    /// Names of the matching tiers, in the order given
+    pub tier_names: Vec<String>,
+}
+
+impl std::fmt::Display for AmbiguousTiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Balance {} falls in overlapping tiers {}", self.balance, self.tier_names.join(", "))
+    }
+}
+
+impl std::error::Error for AmbiguousTiers {}
+
+/// The single tier whose band contains `balance`
+///
+/// Unlike `resolve_tier`, bands are half open, `minimum_balance <= balance < maximum_balance`,
+/// so adjacent tiers sharing a boundary do not overlap, and a missing `maximum_balance`
+/// leaves the band unbounded. A balance matching more than one tier fails with
+/// `AmbiguousTiers` rather than picking one.
+pub fn find_applicable_tier(
+    balance: Decimal,
+    tiers: &[InterestRateTierModel],
+) -> Result<Option<&InterestRateTierModel>, AmbiguousTiers> {
+    let matching: Vec<&InterestRateTierModel> = tiers
+        .iter()
+        .filter(|tier| {
+            tier.minimum_balance <= balance
+                && tier.maximum_balance.is_none_or(|maximum| balance < maximum)
+        })
+        .collect();
+    match matching.as_slice() {
+        [] => Ok(None),
+        [tier] => Ok(Some(tier)),
+        _ => Err(AmbiguousTiers {
+            balance,
+            tier_names: matching.iter().map(|tier| tier.tier_name.to_string()).collect(),
+        }),
+    }
+}
+
+/// Effective rate of `balance` when every band earns its own rate
+///
+/// The balance, capped at the product's `maximum_balance`, is split across the tier
+/// bands and the result is the average of the tier rates weighted by the amount in
+/// each band. Amounts not covered by any band, in a gap between bands or above the
+/// top tier's `maximum_balance`, earn nothing and dilute the rate. Overlapping bands
+/// count the overlap once, for the tier with the lower `minimum_balance`.
+/// A balance of zero or less yields zero.
+pub fn blended_rate(product: &ProductModel, balance: Decimal, tiers: &[InterestRateTierModel]) -> Decimal {
+    let balance = match product.rules.maximum_balance {
+        Some(maximum) => balance.min(maximum),
+        None => balance,
+    };
+    if balance <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    let mut sorted: Vec<&InterestRateTierModel> = tiers.iter().collect();
+    sorted.sort_by_key(|tier| tier.minimum_balance);
+
+    let mut covered_to = Decimal::ZERO;
+    let mut weighted = Decimal::ZERO;
+    for tier in sorted {
+        let lower = tier.minimum_balance.max(covered_to);
+        let upper = tier.maximum_balance.map_or(balance, |maximum| maximum.min(balance));
+        if upper > lower {
+            weighted += (upper - lower) * tier.interest_rate;
+            covered_to = upper;
+        }
+    }
+
+    weighted / balance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blended_rate, find_applicable_tier, resolve_tier, AmbiguousTiers, InterestRateTierModel};
+    use crate::fixtures::ProductFixture;
+    use crate::models::product::product::ProductModel;
+    use chrono::NaiveDate;
+    use heapless::String as HeaplessString;
+    use rust_decimal::Decimal;
+
+    fn tier(minimum: Decimal, maximum: Option<Decimal>, rate: Decimal) -> InterestRateTierModel {
+        InterestRateTierModel {
+            minimum_balance: minimum,
+            maximum_balance: maximum,
+            interest_rate: rate,
+            tier_name: HeaplessString::try_from("Tier").unwrap(),
+        }
+    }
+
+    fn test_product(maximum_balance: Option<Decimal>) -> ProductModel {
+        ProductFixture::builder()
+            .valid_from(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap())
+            .with(|product| {
+                product.rules.minimum_balance = Decimal::ZERO;
+                product.rules.maximum_balance = maximum_balance;
+            })
+            .build()
+    }
+
+    fn three_tiers() -> Vec<InterestRateTierModel> {
+        vec![
+            tier(Decimal::ZERO, Some(Decimal::from(1000)), Decimal::new(1, 2)),
+            tier(Decimal::from(1000), Some(Decimal::from(5000)), Decimal::new(2, 2)),
+            tier(Decimal::from(5000), None, Decimal::new(3, 2)),
+        ]
+    }
+
+    #[test]
+    fn test_blended_rate_compared_to_flat_tier() {
+        let product = test_product(None);
+        let tiers = three_tiers();
+
+        // 1000 at 1%, 4000 at 2%, 5000 at 3%: 240 on 10000
+        assert_eq!(blended_rate(&product, Decimal::from(10000), &tiers), Decimal::new(24, 3));
+        assert_eq!(resolve_tier(Decimal::from(10000), &tiers).unwrap().interest_rate, Decimal::new(3, 2));
+
+        // Inside the first band both approaches agree
+        assert_eq!(blended_rate(&product, Decimal::from(500), &tiers), Decimal::new(1, 2));
+        assert_eq!(resolve_tier(Decimal::from(500), &tiers).unwrap().interest_rate, Decimal::new(1, 2));
+
+        // 1000 at 1%, 1000 at 2%: 30 on 2000
+        assert_eq!(blended_rate(&product, Decimal::from(2000), &tiers), Decimal::new(15, 3));
+        assert_eq!(resolve_tier(Decimal::from(2000), &tiers).unwrap().interest_rate, Decimal::new(2, 2));
+
+        assert_eq!(blended_rate(&product, Decimal::ZERO, &tiers), Decimal::ZERO);
+        assert_eq!(blended_rate(&product, Decimal::from(-50), &tiers), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_blended_rate_above_top_tier_and_gaps() {
+        let product = test_product(None);
+
+        // 1000 at 1%, the 1000 above the top band earns nothing
+        let capped = vec![tier(Decimal::ZERO, Some(Decimal::from(1000)), Decimal::new(1, 2))];
+        assert_eq!(blended_rate(&product, Decimal::from(2000), &capped), Decimal::new(5, 3));
+        assert!(resolve_tier(Decimal::from(2000), &capped).is_none());
+
+        // 1000 at 1%, the 1000 to 2000 gap earns nothing, 2000 at 4%: 90 on 4000
+        let gapped = vec![
+            tier(Decimal::from(2000), None, Decimal::new(4, 2)),
+            tier(Decimal::ZERO, Some(Decimal::from(1000)), Decimal::new(1, 2)),
+        ];
+        assert_eq!(blended_rate(&product, Decimal::from(4000), &gapped), Decimal::new(225, 4));
+        assert!(resolve_tier(Decimal::from(1500), &gapped).is_none());
+    }
+
+    #[test]
+    fn test_blended_rate_capped_at_product_maximum_balance() {
+        let product = test_product(Some(Decimal::from(2000)));
+
+        assert_eq!(blended_rate(&product, Decimal::from(10000), &three_tiers()), Decimal::new(15, 3));
+    }
+
+    #[test]
+    fn test_find_applicable_tier_boundaries() {
+        let tiers = three_tiers();
+        let rate = |balance: i64| {
+            find_applicable_tier(Decimal::from(balance), &tiers)
+                .unwrap()
+                .map(|tier| tier.interest_rate)
+        };
+
+        assert_eq!(rate(0), Some(Decimal::new(1, 2)));
+        assert_eq!(rate(999), Some(Decimal::new(1, 2)));
+        // A shared boundary belongs to the upper tier only
+        assert_eq!(rate(1000), Some(Decimal::new(2, 2)));
+        assert_eq!(rate(5000), Some(Decimal::new(3, 2)));
+        assert_eq!(rate(-1), None);
+        assert_eq!(
+            find_applicable_tier(Decimal::new(99999, 2), &tiers).unwrap().unwrap().interest_rate,
+            Decimal::new(1, 2)
+        );
+    }
+
+    #[test]
+    fn test_find_applicable_tier_unbounded_top_tier() {
+        let tiers = three_tiers();
+        let top = find_applicable_tier(Decimal::from(1_000_000_000), &tiers).unwrap().unwrap();
+        assert_eq!(top.interest_rate, Decimal::new(3, 2));
+
+        // A bounded top tier leaves higher balances without a tier
+        let capped = vec![tier(Decimal::ZERO, Some(Decimal::from(1000)), Decimal::new(1, 2))];
+        assert!(find_applicable_tier(Decimal::from(1000), &capped).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_find_applicable_tier_overlapping_bands() {
+        let tiers = vec![
+            tier(Decimal::ZERO, Some(Decimal::from(2000)), Decimal::new(1, 2)),
+            tier(Decimal::from(1000), None, Decimal::new(2, 2)),
+        ];
+
+        assert!(find_applicable_tier(Decimal::from(500), &tiers).unwrap().is_some());
+        assert_eq!(
+            find_applicable_tier(Decimal::from(1500), &tiers).unwrap_err(),
+            AmbiguousTiers {
+                balance: Decimal::from(1500),
+                tier_names: vec!["Tier".to_string(), "Tier".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalize_decimals_of_tier() {
+        let mut short = tier(Decimal::from(1000), None, Decimal::new(2, 2));
+        let mut padded = tier(Decimal::new(100000, 2), None, Decimal::new(200, 4));
+        short.normalize_decimals();
+        padded.normalize_decimals();
+        assert_eq!(short.minimum_balance.scale(), 4);
+        assert_eq!(
+            crate::utils::hash_as_i64(&short).unwrap(),
+            crate::utils::hash_as_i64(&padded).unwrap()
+        );
+    }
+}
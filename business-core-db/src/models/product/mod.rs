@@ -1,5 +1,8 @@
 #[allow(clippy::module_inception)]
 pub mod product;
+pub mod product_builder;
 pub mod product_rules;
+pub mod posting_schedule;
 pub mod gl_mapping;
-pub mod interest_rate_tier;
\ No newline at end of file
+pub mod interest_rate_tier;
+pub mod money;
\ No newline at end of file
@@ -0,0 +1,250 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::product::{validate_currency, ProductModel};
+use crate::utils::MONETARY_SCALE;
+
+/// An ISO 4217 currency code
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency(heapless::String<3>);
+
+impl Currency {
+    /// Refuses anything but three upper case letters, see `validate_currency`
+    pub fn new(code: &str) -> Result<Self, String> {
+        validate_currency(code)?;
+        let code = heapless::String::try_from(code).map_err(|_| format!("Invalid currency code: {code:?}"))?;
+        Ok(Self(code))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = String;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        Self::new(&code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> Self {
+        currency.0.as_str().to_string()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a computed amount is brought back to a fixed number of decimal places
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Midpoints to the even neighbour, for interest: no bias over many postings
+    Bankers,
+    /// Midpoints away from zero, for fees
+    HalfUp,
+}
+
+impl Rounding {
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Rounding::Bankers => RoundingStrategy::MidpointNearestEven,
+            Rounding::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        }
+    }
+}
+
+/// Error of a `Money` operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The operands are in different currencies
+    CurrencyMismatch { left: Currency, right: Currency },
+    /// The result does not fit a `Decimal`
+    Overflow,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::CurrencyMismatch { left, right } => write!(f, "Cannot combine {left} with {right}"),
+            MoneyError::Overflow => write!(f, "Amount overflow"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// An amount in a currency
+///
+/// Amounts of different currencies are never combined: `checked_add` and
+/// `checked_sub` fail with `MoneyError::CurrencyMismatch`. The amount keeps the scale
+/// of the computation that produced it until `round` brings it to `MONETARY_SCALE`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    /// No amount in `currency`
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(Decimal::ZERO, currency)
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        let amount = self.amount.checked_add(other.amount).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        let amount = self.amount.checked_sub(other.amount).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    /// The amount multiplied by `factor`, e.g. a rate, unrounded
+    pub fn checked_mul(&self, factor: Decimal) -> Result<Money, MoneyError> {
+        let amount = self.amount.checked_mul(factor).ok_or(MoneyError::Overflow)?;
+        Ok(Money::new(amount, self.currency.clone()))
+    }
+
+    /// The amount with exactly `MONETARY_SCALE` decimal places
+    pub fn round(&self, rounding: Rounding) -> Money {
+        self.round_dp(MONETARY_SCALE, rounding)
+    }
+
+    /// The amount with exactly `scale` decimal places
+    pub fn round_dp(&self, scale: u32, rounding: Rounding) -> Money {
+        let mut amount = self.amount.round_dp_with_strategy(scale, rounding.strategy());
+        amount.rescale(scale);
+        Money::new(amount, self.currency.clone())
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+impl ProductModel {
+    /// `amount`, e.g. one of the amounts of `rules`, in the currency of the product
+    ///
+    /// Fails when the product currency is not a valid code.
+    pub fn money(&self, amount: Decimal) -> Result<Money, String> {
+        Ok(Money::new(amount, Currency::new(self.currency.as_str())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, Money, MoneyError, Rounding};
+    use crate::fixtures::ProductFixture;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn xaf(amount: &str) -> Money {
+        Money::new(Decimal::from_str(amount).unwrap(), Currency::new("XAF").unwrap())
+    }
+
+    fn eur(amount: &str) -> Money {
+        Money::new(Decimal::from_str(amount).unwrap(), Currency::new("EUR").unwrap())
+    }
+
+    /// Amounts from -2 to 2 in steps of 0.00001, so every rounding digit and midpoint occurs
+    fn amounts() -> impl Iterator<Item = Decimal> {
+        (-200_000i64..=200_000).map(|units| Decimal::new(units, 5))
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        assert_eq!(xaf("1").checked_add(&xaf("2.5")), Ok(xaf("3.5")));
+        assert_eq!(xaf("1").checked_sub(&xaf("2.5")), Ok(xaf("-1.5")));
+        let mismatch = MoneyError::CurrencyMismatch {
+            left: Currency::new("XAF").unwrap(),
+            right: Currency::new("EUR").unwrap(),
+        };
+        assert_eq!(xaf("1").checked_add(&eur("1")), Err(mismatch.clone()));
+        assert_eq!(xaf("1").checked_sub(&eur("1")), Err(mismatch));
+        assert_eq!(Money::new(Decimal::MAX, Currency::new("XAF").unwrap()).checked_add(&xaf("1")), Err(MoneyError::Overflow));
+        assert!(Currency::new("xaf").is_err());
+    }
+
+    #[test]
+    fn test_rounding_midpoints() {
+        assert_eq!(xaf("0.00025").round(Rounding::Bankers), xaf("0.0002"));
+        assert_eq!(xaf("0.00035").round(Rounding::Bankers), xaf("0.0004"));
+        assert_eq!(xaf("0.00025").round(Rounding::HalfUp), xaf("0.0003"));
+        assert_eq!(xaf("-0.00025").round(Rounding::HalfUp), xaf("-0.0003"));
+        assert_eq!(xaf("1.5").round(Rounding::HalfUp).amount.scale(), 4);
+    }
+
+    #[test]
+    fn test_rounding_invariants() {
+        let step = Decimal::new(1, 4);
+        let half_step = Decimal::new(5, 5);
+        for amount in amounts() {
+            let money = xaf(&amount.to_string());
+            for rounding in [Rounding::Bankers, Rounding::HalfUp] {
+                let rounded = money.round(rounding);
+                // Scale fixed, within half a step, idempotent, symmetric around zero
+                assert_eq!(rounded.amount.scale(), 4);
+                assert!((rounded.amount - amount).abs() <= half_step, "{amount} {rounding:?}");
+                assert_eq!(rounded.round(rounding), rounded);
+                assert_eq!(xaf(&(-amount).to_string()).round(rounding).amount, -rounded.amount);
+                assert_eq!(rounded.currency, money.currency);
+            }
+
+            // The strategies only differ on midpoints
+            let bankers = money.round(Rounding::Bankers).amount;
+            let half_up = money.round(Rounding::HalfUp).amount;
+            if bankers != half_up {
+                assert_eq!((amount - bankers).abs(), half_step);
+                assert_eq!((half_up - bankers).abs(), step);
+                assert!(half_up.abs() > bankers.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_then_sub_round_trips() {
+        let base = xaf("1234.5678");
+        for amount in amounts().step_by(97) {
+            let other = xaf(&amount.to_string());
+            assert_eq!(base.checked_add(&other).unwrap().checked_sub(&other).unwrap(), base);
+        }
+    }
+
+    #[test]
+    fn test_money_of_product() {
+        let product = ProductFixture::builder().currency("EUR").build();
+        let fee = product.money(product.rules.closure_fee).unwrap();
+        assert_eq!(fee.currency.as_str(), "EUR");
+        assert_eq!(fee.amount, product.rules.closure_fee);
+
+        let invalid = ProductFixture::builder().currency("eu").build();
+        assert!(invalid.money(Decimal::ONE).is_err());
+    }
+}
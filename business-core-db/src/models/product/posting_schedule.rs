@@ -0,0 +1,239 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use super::product::ProductModel;
+use super::product_rules::PostingFrequency;
+use crate::models::calendar::{DateShiftRule, WeekendDaysModel};
+
+/// Longest run of non-business days a shift will walk over before giving up
+const MAX_SHIFT_DAYS: u64 = 31;
+
+/// Source of business day information for date calculations
+///
+/// `WeekendDaysModel` only knows the weekend. The `BusinessDayCalendar` of the Postgres
+/// crate also follows the holidays and banking days of the business_day table.
+pub trait BusinessDayProvider {
+    fn is_business_day(&self, date: NaiveDate) -> bool;
+}
+
+impl BusinessDayProvider for WeekendDaysModel {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date)
+    }
+}
+
+/// Interest posting dates of a product between `from` and `to`, inclusive
+///
+/// Dates are produced from the product's `interest_posting_frequency`:
+/// - `Daily`: every business day in the window. Non-business days are skipped
+///   rather than shifted, so no day posts twice.
+/// - `Weekly`: the last day of each 7-day period starting at `from`, shifted per
+///   `shift_rule`.
+/// - `Monthly`, `Quarterly`, `Annually`: the end of each calendar month, quarter or
+///   year. A `NextBusinessDay` shift never leaves the period: a period end that
+///   would roll into the next month posts on the last business day of its month instead.
+///
+/// Only periods ending inside the window produce a date; the shifted date may
+/// fall after `to`. The result is sorted and free of duplicates.
+pub fn posting_dates(
+    product: &ProductModel,
+    from: NaiveDate,
+    to: NaiveDate,
+    calendar: &impl BusinessDayProvider,
+    shift_rule: DateShiftRule,
+) -> Vec<NaiveDate> {
+    if to < from {
+        return Vec::new();
+    }
+
+    let mut dates: Vec<NaiveDate> = match product.rules.interest_posting_frequency {
+        PostingFrequency::Daily => from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .filter(|date| calendar.is_business_day(*date))
+            .collect(),
+        PostingFrequency::Weekly => from
+            .iter_weeks()
+            .filter_map(|start| start.checked_add_days(Days::new(6)))
+            .take_while(|date| *date <= to)
+            .filter_map(|date| shift(date, calendar, shift_rule))
+            .collect(),
+        PostingFrequency::Monthly => period_ends(from, to, 1)
+            .filter_map(|date| shift_within_month(date, calendar, shift_rule))
+            .collect(),
+        PostingFrequency::Quarterly => period_ends(from, to, 3)
+            .filter_map(|date| shift_within_month(date, calendar, shift_rule))
+            .collect(),
+        PostingFrequency::Annually => period_ends(from, to, 12)
+            .filter_map(|date| shift_within_month(date, calendar, shift_rule))
+            .collect(),
+    };
+
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Calendar month ends inside `[from, to]` whose month is a multiple of `months`
+fn period_ends(from: NaiveDate, to: NaiveDate, months: u32) -> impl Iterator<Item = NaiveDate> {
+    let mut month_start = from.with_day(1);
+    std::iter::from_fn(move || {
+        let start = month_start?;
+        let next_start = start.checked_add_months(Months::new(1))?;
+        month_start = Some(next_start);
+        next_start.pred_opt()
+    })
+    .take_while(move |end| *end <= to)
+    .filter(move |end| *end >= from && end.month() % months == 0)
+}
+
+/// Move `date` onto a business day per `shift_rule`
+///
+/// Business days and `NoShift` leave the date as is. `None` when no business day
+/// is found within `MAX_SHIFT_DAYS`.
+pub fn shift(date: NaiveDate, calendar: &impl BusinessDayProvider, shift_rule: DateShiftRule) -> Option<NaiveDate> {
+    let mut candidate = date;
+    for _ in 0..=MAX_SHIFT_DAYS {
+        if shift_rule == DateShiftRule::NoShift || calendar.is_business_day(candidate) {
+            return Some(candidate);
+        }
+        candidate = match shift_rule {
+            DateShiftRule::PreviousBusinessDay => candidate.pred_opt()?,
+            _ => candidate.succ_opt()?,
+        };
+    }
+    None
+}
+
+/// Shift a period end, falling back to the previous business day when the
+/// shifted date would land in another month
+fn shift_within_month(
+    date: NaiveDate,
+    calendar: &impl BusinessDayProvider,
+    shift_rule: DateShiftRule,
+) -> Option<NaiveDate> {
+    match shift(date, calendar, shift_rule) {
+        Some(shifted) if shifted.month() == date.month() => Some(shifted),
+        _ => shift(date, calendar, DateShiftRule::PreviousBusinessDay),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{posting_dates, BusinessDayProvider};
+    use crate::models::calendar::DateShiftRule;
+    use crate::fixtures::ProductFixture;
+    use crate::models::product::product::ProductModel;
+    use crate::models::product::product_rules::PostingFrequency;
+    use chrono::{Datelike, NaiveDate, Weekday};
+    use rust_decimal::Decimal;
+
+    /// Saturday/Sunday weekend plus a fixed holiday list
+    struct TestCalendar {
+        holidays: Vec<NaiveDate>,
+    }
+
+    impl BusinessDayProvider for TestCalendar {
+        fn is_business_day(&self, date: NaiveDate) -> bool {
+            !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains(&date)
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Q1 2024: New Year's Day and Good Friday are holidays, March 31 is a Sunday
+    fn q1_calendar() -> TestCalendar {
+        TestCalendar {
+            holidays: vec![date(2024, 1, 1), date(2024, 3, 29)],
+        }
+    }
+
+    fn test_product(frequency: PostingFrequency) -> ProductModel {
+        ProductFixture::builder()
+            .valid_from(date(2020, 1, 1))
+            .interest_posting_frequency(frequency)
+            .with(|product| product.rules.minimum_balance = Decimal::ZERO)
+            .build()
+    }
+
+    #[test]
+    fn test_monthly_posts_on_last_business_day_of_month() {
+        let product = test_product(PostingFrequency::Monthly);
+        let calendar = q1_calendar();
+
+        // March 31 is a Sunday and March 29 a holiday: the following business day
+        // is in April, so the posting moves back to Thursday March 28
+        for shift_rule in [DateShiftRule::NextBusinessDay, DateShiftRule::PreviousBusinessDay] {
+            assert_eq!(
+                posting_dates(&product, date(2024, 1, 1), date(2024, 3, 31), &calendar, shift_rule),
+                vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 28)]
+            );
+        }
+
+        assert_eq!(
+            posting_dates(&product, date(2024, 1, 1), date(2024, 3, 31), &calendar, DateShiftRule::NoShift),
+            vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31)]
+        );
+    }
+
+    #[test]
+    fn test_quarterly_and_annually() {
+        let calendar = q1_calendar();
+
+        let quarterly = test_product(PostingFrequency::Quarterly);
+        assert_eq!(
+            posting_dates(&quarterly, date(2024, 1, 1), date(2024, 3, 31), &calendar, DateShiftRule::NextBusinessDay),
+            vec![date(2024, 3, 28)]
+        );
+        assert!(posting_dates(&quarterly, date(2024, 1, 1), date(2024, 3, 30), &calendar, DateShiftRule::NextBusinessDay).is_empty());
+
+        // December 31, 2023 is a Sunday
+        let annually = test_product(PostingFrequency::Annually);
+        assert_eq!(
+            posting_dates(&annually, date(2023, 1, 1), date(2024, 3, 31), &calendar, DateShiftRule::NextBusinessDay),
+            vec![date(2023, 12, 29)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_shifts_to_business_day() {
+        let product = test_product(PostingFrequency::Weekly);
+        let calendar = q1_calendar();
+
+        // Periods start on Monday January 1, so each ends on a Sunday
+        assert_eq!(
+            posting_dates(&product, date(2024, 1, 1), date(2024, 1, 21), &calendar, DateShiftRule::NextBusinessDay),
+            vec![date(2024, 1, 8), date(2024, 1, 15), date(2024, 1, 22)]
+        );
+        assert_eq!(
+            posting_dates(&product, date(2024, 1, 1), date(2024, 1, 21), &calendar, DateShiftRule::PreviousBusinessDay),
+            vec![date(2024, 1, 5), date(2024, 1, 12), date(2024, 1, 19)]
+        );
+    }
+
+    #[test]
+    fn test_daily_skips_non_business_days() {
+        let product = test_product(PostingFrequency::Daily);
+        let calendar = q1_calendar();
+
+        assert_eq!(
+            posting_dates(&product, date(2024, 3, 27), date(2024, 4, 1), &calendar, DateShiftRule::NextBusinessDay),
+            vec![date(2024, 3, 27), date(2024, 3, 28), date(2024, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        let product = test_product(PostingFrequency::Monthly);
+        let calendar = q1_calendar();
+
+        // A window that starts or ends exactly on a month end includes it
+        assert_eq!(
+            posting_dates(&product, date(2024, 1, 31), date(2024, 2, 29), &calendar, DateShiftRule::NextBusinessDay),
+            vec![date(2024, 1, 31), date(2024, 2, 29)]
+        );
+        assert!(posting_dates(&product, date(2024, 2, 1), date(2024, 2, 28), &calendar, DateShiftRule::NextBusinessDay).is_empty());
+        assert!(posting_dates(&product, date(2024, 3, 31), date(2024, 1, 1), &calendar, DateShiftRule::NextBusinessDay).is_empty());
+    }
+}
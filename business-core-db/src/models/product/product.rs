@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::product_rules::ProductRules;
+use crate::models::descriptor::{Describe, FieldDescriptor, ModelDescriptor};
+use crate::models::effective_dated::EffectiveDated;
 
 /// Represents a banking product in the database.
 /// # Audit
@@ -17,11 +19,263 @@ pub struct ProductModel {
     pub valid_from: NaiveDate,
     pub valid_to: Option<NaiveDate>,
     pub product_type: ProductType,
+    /// ISO 4217 code of the product's monetary amounts
+    pub currency: heapless::String<3>,
     pub rules: ProductRules,
 }
 
+impl ProductModel {
+    /// Whether the product is active and `date` falls inside its validity window
+    ///
+    /// `valid_to` is exclusive: a product deactivated effective a date is no longer
+    /// offered on that date.
+    pub fn is_active_on(&self, date: NaiveDate) -> bool {
+        self.is_active && self.is_effective_on(date)
+    }
+
+    /// Deactivate the product effective the given date
+    ///
+    /// Refuses an effective date before `valid_from`.
+    pub fn deactivate(&mut self, effective: NaiveDate) -> Result<(), String> {
+        if effective < self.valid_from {
+            return Err(format!(
+                "Product {}: effective date {effective} is before valid_from {}",
+                self.id, self.valid_from
+            ));
+        }
+        self.is_active = false;
+        self.valid_to = Some(effective);
+        Ok(())
+    }
+
+    /// Reactivate the product and reopen its validity window
+    pub fn activate(&mut self) {
+        self.is_active = true;
+        self.valid_to = None;
+    }
+
+    /// End the product at `valid_from` and return its successor starting that date
+    ///
+    /// The successor is a copy of the product under a new id, with `overrides` applied,
+    /// active and valid from `valid_from` to the product's former `valid_to`. Since
+    /// `valid_to` is exclusive, the product's last day becomes `valid_from - 1`, so the
+    /// two windows neither overlap nor leave a gap. Refuses a `valid_from` not after the
+    /// product's own, or after a `valid_to` the product already had. Both products are
+    /// to be saved in one transaction.
+    pub fn clone_for_period(
+        &mut self,
+        valid_from: NaiveDate,
+        overrides: ProductOverrides,
+    ) -> Result<ProductModel, String> {
+        if valid_from <= self.valid_from {
+            return Err(format!(
+                "Product {}: new period start {valid_from} is not after valid_from {}",
+                self.id, self.valid_from
+            ));
+        }
+        if let Some(valid_to) = self.valid_to {
+            if valid_to < valid_from {
+                return Err(format!(
+                    "Product {}: already ends on {valid_to}, before the new period start {valid_from}",
+                    self.id
+                ));
+            }
+        }
+
+        let mut successor = ProductModel {
+            id: Uuid::new_v4(),
+            is_active: true,
+            valid_from,
+            valid_to: self.valid_to,
+            ..self.clone()
+        };
+        overrides.apply(&mut successor);
+        successor.validate()?;
+
+        self.valid_to = Some(valid_from);
+        Ok(successor)
+    }
+
+    /// Bring the decimal fields to a fixed scale, see `ProductRules::normalize_decimals`
+    ///
+    /// Call before hashing, so products differing only by trailing zeros hash alike.
+    pub fn normalize_decimals(&mut self) {
+        self.rules.normalize_decimals();
+    }
+
+    /// Check that `currency` is an ISO 4217 code
+    pub fn validate_currency(&self) -> Result<(), String> {
+        validate_currency(self.currency.as_str())
+    }
+
+    /// Check the product is consistent
+    ///
+    /// The name is set, the currency is an ISO 4217 code, the validity window is
+    /// not reversed, the maximum balance is not below the minimum, overdraft terms
+    /// only come with an allowed overdraft and a maintenance fee comes with its
+    /// frequency. Then the settings are checked against the product type, see
+    /// `validate_casa` and `validate_loan`.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name_l1.is_empty() {
+            return Err(format!("Product {}: name_l1 is empty", self.id));
+        }
+        self.validate_currency()?;
+        if let Some(valid_to) = self.valid_to {
+            if valid_to < self.valid_from {
+                return Err(format!(
+                    "Product {}: valid_to {valid_to} is before valid_from {}",
+                    self.id, self.valid_from
+                ));
+            }
+        }
+        let rules = &self.rules;
+        if let Some(maximum_balance) = rules.maximum_balance {
+            if maximum_balance < rules.minimum_balance {
+                return Err(format!(
+                    "Product {}: maximum_balance {maximum_balance} is below minimum_balance {}",
+                    self.id, rules.minimum_balance
+                ));
+            }
+        }
+        let overdraft_terms_set = rules.overdraft_limit.is_some()
+            || rules.default_overdraft_limit.is_some()
+            || rules.overdraft_interest_rate.is_some();
+        if !rules.overdraft_allowed && overdraft_terms_set {
+            return Err(format!("Product {}: overdraft terms set but overdraft is not allowed", self.id));
+        }
+        if rules.maintenance_fee.is_some() != rules.maintenance_fee_frequency.is_some() {
+            return Err(format!(
+                "Product {}: maintenance_fee and maintenance_fee_frequency must be set together",
+                self.id
+            ));
+        }
+        match self.product_type {
+            ProductType::CASA => self.validate_casa(),
+            ProductType::LOAN => self.validate_loan(),
+        }
+    }
+
+    /// Settings of a current or savings account
+    ///
+    /// A debit balance only comes from an overdraft, so the minimum balance is not
+    /// negative; the overdraft is configured by `overdraft_limit`.
+    fn validate_casa(&self) -> Result<(), String> {
+        if self.rules.minimum_balance.is_sign_negative() {
+            return Err(format!(
+                "Product {}: CASA minimum_balance {} is negative, use an overdraft",
+                self.id, self.rules.minimum_balance
+            ));
+        }
+        Ok(())
+    }
+
+    /// Settings of a loan
+    ///
+    /// A loan has an interest calculation method. Overdraft, maintenance fee, dormancy
+    /// and opening deposit settings only apply to deposit accounts and are refused.
+    fn validate_loan(&self) -> Result<(), String> {
+        let rules = &self.rules;
+        if rules.interest_calculation_method.is_empty() {
+            return Err(format!("Product {}: LOAN requires interest_calculation_method", self.id));
+        }
+        let casa_only = [
+            ("overdraft_allowed", rules.overdraft_allowed),
+            ("overdraft_limit", rules.overdraft_limit.is_some()),
+            ("default_overdraft_limit", rules.default_overdraft_limit.is_some()),
+            ("overdraft_interest_rate", rules.overdraft_interest_rate.is_some()),
+            ("maintenance_fee", rules.maintenance_fee.is_some()),
+            ("default_dormancy_days", rules.default_dormancy_days.is_some()),
+            ("minimum_opening_balance", !rules.minimum_opening_balance.is_zero()),
+        ];
+        if let Some((field, _)) = casa_only.iter().find(|(_, set)| *set) {
+            return Err(format!("Product {}: {field} only applies to CASA products", self.id));
+        }
+        Ok(())
+    }
+}
+
+/// Fields replaced in the successor made by `ProductModel::clone_for_period`
+///
+/// `None` keeps the value of the source product.
+#[derive(Debug, Clone, Default)]
+pub struct ProductOverrides {
+    pub name_l1: Option<heapless::String<100>>,
+    pub name_l2: Option<heapless::String<100>>,
+    pub name_l3: Option<heapless::String<100>>,
+    pub description: Option<heapless::String<255>>,
+    pub product_type: Option<ProductType>,
+    pub currency: Option<heapless::String<3>>,
+    pub rules: Option<ProductRules>,
+}
+
+impl ProductOverrides {
+    fn apply(self, product: &mut ProductModel) {
+        if let Some(name_l1) = self.name_l1 {
+            product.name_l1 = name_l1;
+        }
+        if let Some(name_l2) = self.name_l2 {
+            product.name_l2 = name_l2;
+        }
+        if let Some(name_l3) = self.name_l3 {
+            product.name_l3 = name_l3;
+        }
+        if let Some(description) = self.description {
+            product.description = description;
+        }
+        if let Some(product_type) = self.product_type {
+            product.product_type = product_type;
+        }
+        if let Some(currency) = self.currency {
+            product.currency = currency;
+        }
+        if let Some(rules) = self.rules {
+            product.rules = rules;
+        }
+    }
+}
+
+/// Check that `currency` is an ISO 4217 code: exactly 3 uppercase ASCII letters
+pub fn validate_currency(currency: &str) -> Result<(), String> {
+    if currency.len() == 3 && currency.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(format!("Invalid currency code: {currency:?}"))
+    }
+}
+
+impl EffectiveDated for ProductModel {
+    fn effective_from(&self) -> Option<NaiveDate> {
+        Some(self.valid_from)
+    }
+
+    fn effective_until(&self) -> Option<NaiveDate> {
+        self.valid_to
+    }
+}
+
+impl Describe for ProductModel {
+    fn describe() -> Result<ModelDescriptor, String> {
+        Ok(ModelDescriptor::new(
+            "ProductModel",
+            vec![
+                FieldDescriptor::new("id", "uuid"),
+                FieldDescriptor::string("name_l1", 100),
+                FieldDescriptor::string("name_l2", 100),
+                FieldDescriptor::string("name_l3", 100),
+                FieldDescriptor::string("description", 255),
+                FieldDescriptor::new("is_active", "boolean"),
+                FieldDescriptor::new("valid_from", "date"),
+                FieldDescriptor::new("valid_to", "date").optional(),
+                FieldDescriptor::enumeration::<ProductType>("product_type")?,
+                FieldDescriptor::string("currency", 3),
+                FieldDescriptor::object("rules", ProductRules::describe()?),
+            ],
+        ))
+    }
+}
+
 /// The type of banking product.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, strum::EnumIter)]
 pub enum ProductType {
     CASA,
     LOAN,
@@ -48,3 +302,159 @@ impl std::str::FromStr for ProductType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_currency, ProductModel, ProductOverrides};
+    use crate::fixtures::ProductFixture;
+    use crate::utils::hash_as_i64;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn test_product(currency: &str) -> ProductModel {
+        ProductFixture::builder().currency(currency).build()
+    }
+
+    #[test]
+    fn test_validate_currency() {
+        for valid in ["XAF", "EUR", "USD"] {
+            assert!(validate_currency(valid).is_ok(), "{valid}");
+        }
+        for invalid in ["", "EU", "EURO", "eur", "Eur", "E1R", "ÉUR"] {
+            assert!(validate_currency(invalid).is_err(), "{invalid}");
+        }
+        assert!(test_product("XAF").validate_currency().is_ok());
+        assert!(test_product("xa").validate_currency().is_err());
+    }
+
+    #[test]
+    fn test_currency_round_trip() {
+        let product = test_product("EUR");
+        let json = serde_json::to_value(&product).unwrap();
+        assert_eq!(json["currency"], "EUR");
+
+        let decoded: ProductModel = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.currency.as_str(), "EUR");
+    }
+
+    #[test]
+    fn test_normalize_decimals_makes_hash_stable() {
+        let mut product = test_product("XAF");
+        product.rules.minimum_balance = Decimal::new(15, 1);
+        product.rules.closure_fee = Decimal::new(2, 0);
+        product.rules.overdraft_interest_rate = Some(Decimal::new(125, 4));
+
+        // Same values with trailing zeros
+        let mut padded = product.clone();
+        padded.rules.minimum_balance = Decimal::new(150, 2);
+        padded.rules.closure_fee = Decimal::new(20000, 4);
+        padded.rules.overdraft_interest_rate = Some(Decimal::new(12500, 6));
+        assert_ne!(hash_as_i64(&product).unwrap(), hash_as_i64(&padded).unwrap());
+
+        product.normalize_decimals();
+        padded.normalize_decimals();
+        assert_eq!(hash_as_i64(&product).unwrap(), hash_as_i64(&padded).unwrap());
+        assert_eq!(product.rules.minimum_balance.scale(), 4);
+        assert_eq!(product.rules.overdraft_interest_rate.unwrap().scale(), 6);
+
+        // Normalizing again changes nothing
+        let hash = hash_as_i64(&product).unwrap();
+        product.normalize_decimals();
+        assert_eq!(hash_as_i64(&product).unwrap(), hash);
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_is_active_on_window_boundaries() {
+        let mut product = ProductFixture::builder().valid_from(date(2024, 3, 1)).build();
+        assert!(!product.is_active_on(date(2024, 2, 29)));
+        assert!(product.is_active_on(date(2024, 3, 1)));
+        assert!(product.is_active_on(date(2099, 12, 31)));
+
+        // valid_to is exclusive
+        product.valid_to = Some(date(2024, 12, 31));
+        assert!(product.is_active_on(date(2024, 12, 30)));
+        assert!(!product.is_active_on(date(2024, 12, 31)));
+
+        // An inactive product is not offered inside its window
+        product.is_active = false;
+        assert!(!product.is_active_on(date(2024, 3, 1)));
+    }
+
+    #[test]
+    fn test_deactivate_and_activate() {
+        let mut product = ProductFixture::builder().valid_from(date(2024, 3, 1)).build();
+
+        let error = product.deactivate(date(2024, 2, 29)).unwrap_err();
+        assert!(error.contains("is before valid_from"), "{error}");
+        assert!(product.is_active);
+        assert_eq!(product.valid_to, None);
+
+        // Deactivating effective the first day leaves an empty window
+        let mut same_day = product.clone();
+        same_day.deactivate(date(2024, 3, 1)).unwrap();
+        assert!(!same_day.is_active_on(date(2024, 3, 1)));
+
+        product.deactivate(date(2024, 7, 1)).unwrap();
+        assert!(!product.is_active);
+        assert_eq!(product.valid_to, Some(date(2024, 7, 1)));
+        assert!(!product.is_active_on(date(2024, 6, 30)));
+
+        product.activate();
+        assert!(product.is_active);
+        assert_eq!(product.valid_to, None);
+        assert!(product.is_active_on(date(2024, 7, 1)));
+        assert!(!product.is_active_on(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn test_clone_for_period() {
+        let mut source = test_product("XAF");
+        let mut rules = source.rules.clone();
+        rules.closure_fee = Decimal::new(5000, 0);
+        let overrides = ProductOverrides {
+            name_l1: Some(heapless::String::try_from("Savings 2025").unwrap()),
+            rules: Some(rules),
+            ..Default::default()
+        };
+
+        let clone = source.clone_for_period(date(2025, 1, 1), overrides).unwrap();
+
+        assert_ne!(clone.id, source.id);
+        assert_eq!(clone.name_l1.as_str(), "Savings 2025");
+        assert_eq!(clone.rules.closure_fee, Decimal::new(5000, 0));
+        assert_eq!(clone.description, source.description);
+        assert_eq!(clone.currency, source.currency);
+        assert_eq!((clone.valid_from, clone.valid_to), (date(2025, 1, 1), None));
+
+        assert_eq!(source.valid_to, Some(date(2025, 1, 1)));
+        assert!(source.is_active_on(date(2024, 12, 31)));
+        assert!(!source.is_active_on(date(2025, 1, 1)));
+        assert!(clone.is_active_on(date(2025, 1, 1)));
+        assert!(!clone.is_active_on(date(2024, 12, 31)));
+    }
+
+    #[test]
+    fn test_clone_for_period_rejects_bad_start() {
+        let mut source = test_product("XAF");
+        source.valid_to = Some(date(2024, 6, 1));
+        let before = source.clone();
+
+        // Not after the source start
+        assert!(source.clone_for_period(date(2024, 1, 1), ProductOverrides::default()).is_err());
+        // Source already over before the new start
+        assert!(source.clone_for_period(date(2024, 7, 1), ProductOverrides::default()).is_err());
+        assert_eq!(source.valid_to, before.valid_to);
+
+        // An invalid override leaves the source untouched
+        let overrides = ProductOverrides {
+            currency: Some(heapless::String::try_from("eur").unwrap()),
+            ..Default::default()
+        };
+        assert!(source.clone_for_period(date(2024, 3, 1), overrides).is_err());
+        assert_eq!(source.valid_to, before.valid_to);
+    }
+}
@@ -0,0 +1,319 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use super::product::{ProductModel, ProductType};
+use super::product_rules::{PostingFrequency, ProductAccrualFrequency, ProductRules};
+
+/// Builder of a new `ProductModel`
+///
+/// Starts from an active savings (CASA) product valid from today, posting interest
+/// monthly on the daily balance, without overdraft, limits or fees. Text setters
+/// check the field's length; `build` reports the first failure, then runs
+/// `ProductModel::validate`.
+#[derive(Debug, Clone)]
+pub struct ProductBuilder {
+    model: ProductModel,
+    error: Option<String>,
+}
+
+impl ProductModel {
+    /// Builder of a new product named `name_l1` with amounts in `currency`
+    pub fn builder(name_l1: &str, currency: &str) -> ProductBuilder {
+        let mut builder = ProductBuilder {
+            model: ProductModel {
+                id: Uuid::new_v4(),
+                name_l1: heapless::String::new(),
+                name_l2: heapless::String::new(),
+                name_l3: heapless::String::new(),
+                description: heapless::String::new(),
+                is_active: true,
+                valid_from: Utc::now().date_naive(),
+                valid_to: None,
+                product_type: ProductType::CASA,
+                currency: heapless::String::new(),
+                rules: ProductRules {
+                    minimum_balance: Decimal::ZERO,
+                    maximum_balance: None,
+                    daily_transaction_limit: None,
+                    monthly_transaction_limit: None,
+                    overdraft_allowed: false,
+                    overdraft_limit: None,
+                    interest_calculation_method: heapless::String::try_from("DAILY_BALANCE").unwrap(),
+                    interest_posting_frequency: PostingFrequency::Monthly,
+                    dormancy_threshold_days: 365,
+                    minimum_opening_balance: Decimal::ZERO,
+                    closure_fee: Decimal::ZERO,
+                    maintenance_fee: None,
+                    maintenance_fee_frequency: None,
+                    default_dormancy_days: None,
+                    default_overdraft_limit: None,
+                    per_transaction_limit: None,
+                    overdraft_interest_rate: None,
+                    accrual_frequency: ProductAccrualFrequency::Daily,
+                },
+            },
+            error: None,
+        };
+        if let Some(name) = builder.text("name_l1", name_l1) {
+            builder.model.name_l1 = name;
+        }
+        if let Some(currency) = builder.text("currency", currency) {
+            builder.model.currency = currency;
+        }
+        builder
+    }
+}
+
+impl ProductBuilder {
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.model.id = id;
+        self
+    }
+
+    /// Names in the second and third languages
+    pub fn translations(mut self, name_l2: &str, name_l3: &str) -> Self {
+        if let Some(name) = self.text("name_l2", name_l2) {
+            self.model.name_l2 = name;
+        }
+        if let Some(name) = self.text("name_l3", name_l3) {
+            self.model.name_l3 = name;
+        }
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        if let Some(description) = self.text("description", description) {
+            self.model.description = description;
+        }
+        self
+    }
+
+    pub fn product_type(mut self, product_type: ProductType) -> Self {
+        self.model.product_type = product_type;
+        self
+    }
+
+    pub fn inactive(mut self) -> Self {
+        self.model.is_active = false;
+        self
+    }
+
+    /// Validity window, `valid_to` exclusive
+    pub fn valid(mut self, valid_from: NaiveDate, valid_to: Option<NaiveDate>) -> Self {
+        self.model.valid_from = valid_from;
+        self.model.valid_to = valid_to;
+        self
+    }
+
+    pub fn balances(mut self, minimum_balance: Decimal, maximum_balance: Option<Decimal>) -> Self {
+        self.model.rules.minimum_balance = minimum_balance;
+        self.model.rules.maximum_balance = maximum_balance;
+        self
+    }
+
+    pub fn minimum_opening_balance(mut self, minimum_opening_balance: Decimal) -> Self {
+        self.model.rules.minimum_opening_balance = minimum_opening_balance;
+        self
+    }
+
+    pub fn transaction_limits(
+        mut self,
+        per_transaction: Option<Decimal>,
+        daily: Option<Decimal>,
+        monthly: Option<Decimal>,
+    ) -> Self {
+        self.model.rules.per_transaction_limit = per_transaction;
+        self.model.rules.daily_transaction_limit = daily;
+        self.model.rules.monthly_transaction_limit = monthly;
+        self
+    }
+
+    /// Allow an overdraft up to `limit` at `interest_rate`
+    pub fn overdraft(mut self, limit: Decimal, interest_rate: Option<Decimal>) -> Self {
+        self.model.rules.overdraft_allowed = true;
+        self.model.rules.overdraft_limit = Some(limit);
+        self.model.rules.overdraft_interest_rate = interest_rate;
+        self
+    }
+
+    pub fn interest(
+        mut self,
+        calculation_method: &str,
+        posting_frequency: PostingFrequency,
+        accrual_frequency: ProductAccrualFrequency,
+    ) -> Self {
+        if let Some(method) = self.text("interest_calculation_method", calculation_method) {
+            self.model.rules.interest_calculation_method = method;
+        }
+        self.model.rules.interest_posting_frequency = posting_frequency;
+        self.model.rules.accrual_frequency = accrual_frequency;
+        self
+    }
+
+    pub fn dormancy_threshold_days(mut self, days: i32) -> Self {
+        self.model.rules.dormancy_threshold_days = days;
+        self
+    }
+
+    pub fn closure_fee(mut self, fee: Decimal) -> Self {
+        self.model.rules.closure_fee = fee;
+        self
+    }
+
+    pub fn maintenance_fee(mut self, fee: Decimal, frequency: &str) -> Self {
+        if let Some(frequency) = self.text("maintenance_fee_frequency", frequency) {
+            self.model.rules.maintenance_fee = Some(fee);
+            self.model.rules.maintenance_fee_frequency = Some(frequency);
+        }
+        self
+    }
+
+    /// The product, or the first error met while building or validating it
+    pub fn build(self) -> Result<ProductModel, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.model.validate()?;
+        Ok(self.model)
+    }
+
+    /// Convert `value` for `field`, recording the error when it does not fit
+    fn text<const N: usize>(&mut self, field: &str, value: &str) -> Option<heapless::String<N>> {
+        match heapless::String::try_from(value) {
+            Ok(text) => Some(text),
+            Err(_) => {
+                self.error
+                    .get_or_insert_with(|| format!("{field}: {value:?} is longer than {N} bytes"));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::product::product::{ProductModel, ProductType};
+    use crate::models::product::product_rules::PostingFrequency;
+    use chrono::{NaiveDate, Utc};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_minimal_build() {
+        let product = ProductModel::builder("Savings", "XAF").build().unwrap();
+
+        assert_eq!(product.name_l1.as_str(), "Savings");
+        assert_eq!(product.currency.as_str(), "XAF");
+        assert_eq!(product.product_type, ProductType::CASA);
+        assert!(product.is_active);
+        assert_eq!(product.valid_from, Utc::now().date_naive());
+        assert!(product.valid_to.is_none());
+        assert_eq!(product.rules.interest_posting_frequency, PostingFrequency::Monthly);
+        assert!(!product.rules.overdraft_allowed);
+        assert!(product.rules.maintenance_fee.is_none());
+        assert!(product.rules.maintenance_fee_frequency.is_none());
+        assert!(product.is_active_on(Utc::now().date_naive()));
+    }
+
+    #[test]
+    fn test_build_with_terms() {
+        let valid_from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let product = ProductModel::builder("Current", "EUR")
+            .product_type(ProductType::CASA)
+            .valid(valid_from, None)
+            .balances(Decimal::from(100), Some(Decimal::from(1_000_000)))
+            .overdraft(Decimal::from(500), Some(Decimal::new(125, 3)))
+            .maintenance_fee(Decimal::from(5), "MONTHLY")
+            .build()
+            .unwrap();
+
+        assert_eq!(product.valid_from, valid_from);
+        assert_eq!(product.rules.overdraft_limit, Some(Decimal::from(500)));
+        assert_eq!(product.rules.maintenance_fee_frequency.as_deref(), Some("MONTHLY"));
+    }
+
+    #[test]
+    fn test_build_fails_validation() {
+        let error = ProductModel::builder("Savings", "xaf").build().unwrap_err();
+        assert!(error.contains("Invalid currency code"), "{error}");
+
+        let error = ProductModel::builder("", "XAF").build().unwrap_err();
+        assert!(error.contains("name_l1 is empty"), "{error}");
+
+        let error = ProductModel::builder("Savings", "XAF")
+            .valid(
+                NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 1),
+            )
+            .build()
+            .unwrap_err();
+        assert!(error.contains("is before valid_from"), "{error}");
+
+        let error = ProductModel::builder("Savings", "XAF")
+            .balances(Decimal::from(100), Some(Decimal::from(10)))
+            .build()
+            .unwrap_err();
+        assert!(error.contains("is below minimum_balance"), "{error}");
+
+        let error = ProductModel::builder("Savings", "XAF")
+            .description(&"x".repeat(256))
+            .build()
+            .unwrap_err();
+        assert!(error.starts_with("description:"), "{error}");
+    }
+
+    #[test]
+    fn test_validate_overdraft_and_fee_pairs() {
+        let mut product = ProductModel::builder("Savings", "XAF").build().unwrap();
+        product.rules.overdraft_limit = Some(Decimal::from(100));
+        assert!(product.validate().unwrap_err().contains("overdraft is not allowed"));
+
+        product.rules.overdraft_limit = None;
+        product.rules.default_overdraft_limit = Some(Decimal::from(100));
+        assert!(product.validate().unwrap_err().contains("overdraft is not allowed"));
+
+        product.rules.default_overdraft_limit = None;
+        product.rules.maintenance_fee = Some(Decimal::from(5));
+        assert!(product.validate().unwrap_err().contains("must be set together"));
+    }
+
+    #[test]
+    fn test_validate_by_product_type() {
+        let loan = ProductModel::builder("Mortgage", "XAF")
+            .product_type(ProductType::LOAN)
+            .balances(Decimal::ZERO, Some(Decimal::from(50_000_000)))
+            .build()
+            .unwrap();
+        assert_eq!(loan.product_type, ProductType::LOAN);
+
+        let casa = ProductModel::builder("Current", "XAF")
+            .overdraft(Decimal::from(500), Some(Decimal::new(125, 3)))
+            .maintenance_fee(Decimal::from(5), "MONTHLY")
+            .build()
+            .unwrap();
+
+        // The current account settings relabelled as a loan
+        let mut misconfigured = casa.clone();
+        misconfigured.product_type = ProductType::LOAN;
+        let error = misconfigured.validate().unwrap_err();
+        assert!(error.contains("overdraft_allowed only applies to CASA products"), "{error}");
+
+        misconfigured.rules.overdraft_allowed = false;
+        misconfigured.rules.overdraft_limit = None;
+        misconfigured.rules.overdraft_interest_rate = None;
+        let error = misconfigured.validate().unwrap_err();
+        assert!(error.contains("maintenance_fee only applies to CASA products"), "{error}");
+
+        let mut without_method = loan.clone();
+        without_method.rules.interest_calculation_method.clear();
+        let error = without_method.validate().unwrap_err();
+        assert!(error.contains("LOAN requires interest_calculation_method"), "{error}");
+
+        // And a loan's debit balance relabelled as a current account
+        let mut debit_casa = loan;
+        debit_casa.product_type = ProductType::CASA;
+        debit_casa.rules.minimum_balance = Decimal::from(-1_000);
+        let error = debit_casa.validate().unwrap_err();
+        assert!(error.contains("is negative, use an overdraft"), "{error}");
+    }
+}
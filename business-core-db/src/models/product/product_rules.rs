@@ -1,8 +1,11 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::models::descriptor::{Describe, FieldDescriptor, ModelDescriptor};
+use crate::utils::{rescale_decimal, MONETARY_SCALE, RATE_SCALE};
+
 /// Frequency for interest posting
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, strum::EnumIter)]
 pub enum PostingFrequency {
     Daily,
     Weekly,
@@ -12,7 +15,7 @@ pub enum PostingFrequency {
 }
 
 /// Frequency for interest accrual
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, strum::EnumIter)]
 pub enum ProductAccrualFrequency {
     Daily,
     BusinessDaysOnly,
@@ -39,4 +42,52 @@ pub struct ProductRules {
     pub per_transaction_limit: Option<Decimal>,
     pub overdraft_interest_rate: Option<Decimal>,
     pub accrual_frequency: ProductAccrualFrequency,
-}
\ No newline at end of file
+}
+
+impl ProductRules {
+    /// Rescale the amounts to `MONETARY_SCALE` and the rates to `RATE_SCALE` decimal places
+    pub fn normalize_decimals(&mut self) {
+        let amount = |value: Decimal| rescale_decimal(value, MONETARY_SCALE);
+        self.minimum_balance = amount(self.minimum_balance);
+        self.maximum_balance = self.maximum_balance.map(amount);
+        self.daily_transaction_limit = self.daily_transaction_limit.map(amount);
+        self.monthly_transaction_limit = self.monthly_transaction_limit.map(amount);
+        self.overdraft_limit = self.overdraft_limit.map(amount);
+        self.minimum_opening_balance = amount(self.minimum_opening_balance);
+        self.closure_fee = amount(self.closure_fee);
+        self.maintenance_fee = self.maintenance_fee.map(amount);
+        self.default_overdraft_limit = self.default_overdraft_limit.map(amount);
+        self.per_transaction_limit = self.per_transaction_limit.map(amount);
+        self.overdraft_interest_rate = self
+            .overdraft_interest_rate
+            .map(|rate| rescale_decimal(rate, RATE_SCALE));
+    }
+}
+
+impl Describe for ProductRules {
+    fn describe() -> Result<ModelDescriptor, String> {
+        Ok(ModelDescriptor::new(
+            "ProductRules",
+            vec![
+                FieldDescriptor::new("minimum_balance", "decimal"),
+                FieldDescriptor::new("maximum_balance", "decimal").optional(),
+                FieldDescriptor::new("daily_transaction_limit", "decimal").optional(),
+                FieldDescriptor::new("monthly_transaction_limit", "decimal").optional(),
+                FieldDescriptor::new("overdraft_allowed", "boolean"),
+                FieldDescriptor::new("overdraft_limit", "decimal").optional(),
+                FieldDescriptor::string("interest_calculation_method", 50),
+                FieldDescriptor::enumeration::<PostingFrequency>("interest_posting_frequency")?,
+                FieldDescriptor::new("dormancy_threshold_days", "integer"),
+                FieldDescriptor::new("minimum_opening_balance", "decimal"),
+                FieldDescriptor::new("closure_fee", "decimal"),
+                FieldDescriptor::new("maintenance_fee", "decimal").optional(),
+                FieldDescriptor::string("maintenance_fee_frequency", 50).optional(),
+                FieldDescriptor::new("default_dormancy_days", "integer").optional(),
+                FieldDescriptor::new("default_overdraft_limit", "decimal").optional(),
+                FieldDescriptor::new("per_transaction_limit", "decimal").optional(),
+                FieldDescriptor::new("overdraft_interest_rate", "decimal").optional(),
+                FieldDescriptor::enumeration::<ProductAccrualFrequency>("accrual_frequency")?,
+            ],
+        ))
+    }
+}
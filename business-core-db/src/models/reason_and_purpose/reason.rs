@@ -6,9 +6,10 @@ use std::collections::HashMap;
 use crate::{HasPrimaryKey, IdxModelCache, Indexable};
 use crate::models::{IndexAware, Identifiable, Index};
 use crate::utils::hash_as_i64;
+use crate::models::descriptor::{Describe, FieldDescriptor, ModelDescriptor};
 
 /// Database model for ReasonCategory enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "reason_category", rename_all = "PascalCase")]
 pub enum ReasonCategory {
     // Loan related
@@ -144,7 +145,7 @@ impl std::str::FromStr for ReasonCategory {
 }
 
 /// Database model for ReasonContext enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "reason_context", rename_all = "PascalCase")]
 pub enum ReasonContext {
     Account,
@@ -194,7 +195,7 @@ impl std::str::FromStr for ReasonContext {
 }
 
 /// Database model for ReasonSeverity enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, strum::EnumIter)]
 #[sqlx(type_name = "reason_severity", rename_all = "PascalCase")]
 pub enum ReasonSeverity {
     Critical,
@@ -287,6 +288,31 @@ pub struct ReasonModel {
     pub compliance_metadata: Option<Uuid>,
 }
 
+impl Describe for ReasonModel {
+    fn describe() -> Result<ModelDescriptor, String> {
+        Ok(ModelDescriptor::new(
+            "ReasonModel",
+            vec![
+                FieldDescriptor::new("id", "uuid"),
+                FieldDescriptor::string("code", 50),
+                FieldDescriptor::enumeration::<ReasonCategory>("category")?,
+                FieldDescriptor::enumeration::<ReasonContext>("context")?,
+                FieldDescriptor::string("l1_content", 100).optional(),
+                FieldDescriptor::string("l2_content", 100).optional(),
+                FieldDescriptor::string("l3_content", 100).optional(),
+                FieldDescriptor::string("l1_language_code", 3).optional(),
+                FieldDescriptor::string("l2_language_code", 3).optional(),
+                FieldDescriptor::string("l3_language_code", 3).optional(),
+                FieldDescriptor::new("requires_details", "boolean"),
+                FieldDescriptor::new("is_active", "boolean"),
+                FieldDescriptor::enumeration::<ReasonSeverity>("severity")?.optional(),
+                FieldDescriptor::new("display_order", "integer"),
+                FieldDescriptor::new("compliance_metadata", "uuid").optional(),
+            ],
+        ))
+    }
+}
+
 // Custom serialization functions for database compatibility
 fn serialize_reason_category<S>(category: &ReasonCategory, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -444,4 +470,28 @@ impl Indexable for ReasonIdxModel {
     }
 }
 
-pub type ReasonIdxModelCache = IdxModelCache<ReasonIdxModel>;
\ No newline at end of file
+pub type ReasonIdxModelCache = IdxModelCache<ReasonIdxModel>;
+
+#[cfg(test)]
+mod tests {
+    use super::ReasonIdxModel;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_reason_idx_payload_tolerates_unknown_and_missing_fields() {
+        let id = Uuid::new_v4();
+        let payload = serde_json::json!({
+            "id": id,
+            "code_hash": 1,
+            "category_hash": 2,
+            "context_hash": 3,
+            "column_added_later": 4
+        });
+
+        let idx: ReasonIdxModel = serde_json::from_value(payload).unwrap();
+
+        assert_eq!(idx.id, id);
+        assert_eq!(idx.code_hash, 1);
+        assert_eq!(idx.compliance_metadata, None);
+    }
+}
@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use heapless::String as HeaplessString;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use crate::models::audit_chained::impl_audit_chained;
 use crate::models::auditable::Auditable;
 use crate::models::identifiable::Identifiable;
 use crate::models::audit::entity_type::EntityType;
@@ -25,10 +27,16 @@ pub struct ReasonReferenceModel {
     /// Additional contextual details about this reason application
     pub additional_details: Option<HeaplessString<200>>,
 
+    /// Free-text details, mandatory when the reason has `requires_details` set
+    pub details: Option<HeaplessString<500>>,
+
     /// The type of entity being referenced
     #[serde(serialize_with = "serialize_entity_type", deserialize_with = "deserialize_entity_type")]
     pub entity_type: EntityType,
 
+    /// When the reason was applied, kept by updates
+    pub created_at: DateTime<Utc>,
+
     /// Hash from the previous audit record for chain verification (0 for initial create)
     pub antecedent_hash: i64,
     
@@ -60,6 +68,8 @@ impl Auditable for ReasonReferenceModel {
     }
 }
 
+impl_audit_chained!(ReasonReferenceModel);
+
 fn serialize_entity_type<S>(entity_type: &EntityType, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use sqlx::Database;
+use uuid::Uuid;
+
+/// Generic repository trait for counting entities by one of their index keys
+///
+/// Answers "how many persons per organization" style questions without loading models.
+/// The key names are those of `Indexable::uuid_keys` and `Indexable::i64_keys` of the
+/// entity's index model.
+///
+/// # Type Parameters
+/// * `DB` - The database type (must implement sqlx::Database)
+///
+/// # Example
+/// ```ignore
+/// impl CountByKey<Postgres> for PersonRepositoryImpl {
+///     async fn count_by_uuid_key(&self, key_name: &str, value: Uuid) -> Result<usize, Box<dyn Error + Send + Sync>> {
+///         // Implementation
+///     }
+///     // ...
+/// }
+///
+/// // Usage:
+/// let members = repo.count_by_uuid_key("organization_person_id", organization_id).await?;
+/// ```
+#[async_trait]
+pub trait CountByKey<DB: Database>: Send + Sync {
+    /// Count the entities whose uuid key `key_name` equals `value`
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of matching entities
+    /// * `Err` - An error if `key_name` is not a uuid key or the count could not be executed
+    async fn count_by_uuid_key(&self, key_name: &str, value: Uuid) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Count the entities whose i64 key `key_name` equals `value`
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of matching entities
+    /// * `Err` - An error if `key_name` is not an i64 key or the count could not be executed
+    async fn count_by_i64_key(&self, key_name: &str, value: i64) -> Result<usize, Box<dyn std::error::Error + Send + Sync>>;
+}
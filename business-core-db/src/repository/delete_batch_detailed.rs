@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use sqlx::Database;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Outcome of a detailed batch delete
+///
+/// Partitions the requested IDs into those that were actually deleted and those
+/// that did not exist in the data store.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteOutcome {
+    /// IDs of the entities that were deleted
+    pub deleted: Vec<Uuid>,
+    /// Requested IDs for which no entity was found
+    pub not_found: Vec<Uuid>,
+}
+
+impl DeleteOutcome {
+    /// Build an outcome from the requested IDs and the IDs returned by the delete statement
+    ///
+    /// `not_found` keeps the order of `requested`, an ID requested twice is reported once.
+    pub fn from_returned(requested: &[Uuid], deleted: Vec<Uuid>) -> Self {
+        let mut seen: HashSet<Uuid> = deleted.iter().copied().collect();
+        let not_found = requested
+            .iter()
+            .filter(|id| seen.insert(**id))
+            .copied()
+            .collect();
+        Self { deleted, not_found }
+    }
+}
+
+/// Generic repository trait for deleting multiple entities in a batch, reporting which IDs were deleted
+///
+/// Same semantics as `DeleteBatch`, but instead of a count the caller gets a `DeleteOutcome`
+/// telling exactly which of the requested IDs were deleted and which did not exist.
+/// Implementations should rely on `DELETE ... WHERE id = ANY($1) RETURNING id` and only
+/// evict the returned IDs from their caches.
+///
+/// # Type Parameters
+/// * `DB` - The database type (must implement sqlx::Database)
+///
+/// # Example
+/// ```ignore
+/// impl DeleteBatchDetailed<Postgres> for PersonRepositoryImpl {
+///     async fn delete_batch_detailed(&self, ids: &[Uuid], audit_log_id: Option<Uuid>) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
+///         // Implementation
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait DeleteBatchDetailed<DB: Database>: Send + Sync {
+    /// Delete multiple items by their IDs in a single transaction
+    ///
+    /// # Arguments
+    /// * `ids` - A slice of UUIDs of the entities to delete
+    /// * `audit_log_id` - The optional UUID of the audit log for tracking this operation
+    ///
+    /// # Returns
+    /// * `Ok(DeleteOutcome)` - The deleted and not found IDs
+    /// * `Err` - An error if the transaction could not be executed
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeleteOutcome;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_from_returned_reports_each_missing_id_once() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let requested = [ids[0], ids[1], ids[2], ids[1], ids[3], ids[0]];
+
+        let outcome = DeleteOutcome::from_returned(&requested, vec![ids[2], ids[0]]);
+
+        assert_eq!(outcome.deleted, vec![ids[2], ids[0]]);
+        assert_eq!(outcome.not_found, vec![ids[1], ids[3]]);
+    }
+}
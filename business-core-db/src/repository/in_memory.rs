@@ -0,0 +1,373 @@
+//! In-memory repositories for tests that do not need Postgres
+//!
+//! `InMemoryRepository` implements the generic repository traits over a map guarded by
+//! a mutex, so code written against `CreateBatch`, `LoadBatch`, `UpdateBatch` and the
+//! other traits can be tested without a database. Audited models created with
+//! `InMemoryRepository::audited` get their audit columns maintained the way the
+//! Postgres repositories do: the audit log id is stamped, the hash is computed, and an
+//! update that changes nothing keeps the stored version.
+//!
+//! The backend does not reproduce what Postgres enforces or what a repository checks
+//! with SQL: foreign keys, unique codes, status transitions, index caches and the
+//! audit tables. Tests of those stay on the Postgres test context.
+//!
+//! Available to this crate's tests and, with the `in-memory` feature, to other crates.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use sqlx::Postgres;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::models::audit::audit_log::AuditLogModel;
+use crate::models::audit_chained::AuditChained;
+use crate::models::identifiable::Identifiable;
+use crate::models::person::entity_reference::EntityReferenceModel;
+use crate::models::person::person::PersonModel;
+use crate::models::reason_and_purpose::reason::ReasonModel;
+use crate::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
+use crate::repository::create_batch::CreateBatch;
+use crate::repository::delete_batch::DeleteBatch;
+use crate::repository::exist_by_ids::ExistByIds;
+use crate::repository::load::Load;
+use crate::repository::load_batch::LoadBatch;
+use crate::repository::update_batch::UpdateBatch;
+use crate::utils::hash_as_i64;
+
+/// Audit columns an in-memory repository writes on create and update
+pub trait AuditColumns: AuditChained {
+    fn set_audit_columns(&mut self, antecedent_hash: i64, antecedent_audit_log_id: Uuid, audit_log_id: Uuid);
+}
+
+impl AuditColumns for PersonModel {
+    fn set_audit_columns(&mut self, antecedent_hash: i64, antecedent_audit_log_id: Uuid, audit_log_id: Uuid) {
+        self.antecedent_hash = antecedent_hash;
+        self.antecedent_audit_log_id = antecedent_audit_log_id;
+        self.audit_log_id = Some(audit_log_id);
+    }
+}
+
+impl AuditColumns for EntityReferenceModel {
+    fn set_audit_columns(&mut self, antecedent_hash: i64, antecedent_audit_log_id: Uuid, audit_log_id: Uuid) {
+        self.antecedent_hash = antecedent_hash;
+        self.antecedent_audit_log_id = antecedent_audit_log_id;
+        self.audit_log_id = Some(audit_log_id);
+    }
+}
+
+impl AuditColumns for ReasonReferenceModel {
+    fn set_audit_columns(&mut self, antecedent_hash: i64, antecedent_audit_log_id: Uuid, audit_log_id: Uuid) {
+        self.antecedent_hash = antecedent_hash;
+        self.antecedent_audit_log_id = antecedent_audit_log_id;
+        self.audit_log_id = Some(audit_log_id);
+    }
+}
+
+/// How a write updates the audit columns of an item
+type Stamp<T> = fn(&mut T, Option<Uuid>, bool) -> Result<bool, Box<dyn Error + Send + Sync>>;
+
+/// Stamp an audited item, returning false for an update that changes nothing
+fn stamp_audited<T: AuditColumns + Serialize + Clone>(
+    item: &mut T,
+    audit_log_id: Option<Uuid>,
+    is_update: bool,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
+    let audit_log_id = audit_log_id.ok_or("audit_log_id is required for audited models")?;
+    let (antecedent_hash, antecedent_audit_log_id) = if is_update {
+        let mut for_hashing = item.clone();
+        for_hashing.set_hash(0);
+        if hash_as_i64(&for_hashing)? == item.hash() {
+            return Ok(false);
+        }
+        let previous_audit_log_id = item
+            .audit_log_id()
+            .ok_or("Entity must have audit_log_id for update")?;
+        (item.hash(), previous_audit_log_id)
+    } else {
+        (item.antecedent_hash(), item.antecedent_audit_log_id())
+    };
+    item.set_audit_columns(antecedent_hash, antecedent_audit_log_id, audit_log_id);
+    item.set_hash(0);
+    let hash = hash_as_i64(&*item)?;
+    item.set_hash(hash);
+    Ok(true)
+}
+
+/// Repository keeping its models in memory
+pub struct InMemoryRepository<T> {
+    items: Mutex<HashMap<Uuid, T>>,
+    stamp: Option<Stamp<T>>,
+}
+
+impl<T> Default for InMemoryRepository<T> {
+    fn default() -> Self {
+        Self {
+            items: Mutex::new(HashMap::new()),
+            stamp: None,
+        }
+    }
+}
+
+impl<T: Identifiable + Clone + Send + Sync> InMemoryRepository<T> {
+    /// Repository storing the models as given
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Repository maintaining the audit columns of the models it writes
+    pub fn audited() -> Self
+    where
+        T: AuditColumns + Serialize,
+    {
+        Self {
+            items: Mutex::new(HashMap::new()),
+            stamp: Some(stamp_audited::<T>),
+        }
+    }
+
+    /// Number of stored models
+    pub fn len(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> CreateBatch<Postgres, T> for InMemoryRepository<T> {
+    async fn create_batch(
+        &self,
+        mut items: Vec<T>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>> {
+        if let Some(stamp) = self.stamp {
+            for item in &mut items {
+                stamp(item, audit_log_id, false)?;
+            }
+        }
+        let mut stored = self.items.lock().unwrap();
+        if let Some(item) = items.iter().find(|item| stored.contains_key(&item.get_id())) {
+            return Err(format!("{} already exists", item.get_id()).into());
+        }
+        for item in &items {
+            stored.insert(item.get_id(), item.clone());
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> LoadBatch<Postgres, T> for InMemoryRepository<T> {
+    async fn load_batch(&self, ids: &[Uuid]) -> Result<Vec<Option<T>>, Box<dyn Error + Send + Sync>> {
+        let stored = self.items.lock().unwrap();
+        Ok(ids.iter().map(|id| stored.get(id).cloned()).collect())
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> Load<Postgres, T> for InMemoryRepository<T> {
+    async fn load(&self, id: Uuid) -> Result<T, Box<dyn Error + Send + Sync>> {
+        self.items
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| format!("{id} not found").into())
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> UpdateBatch<Postgres, T> for InMemoryRepository<T> {
+    async fn update_batch(
+        &self,
+        mut items: Vec<T>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>> {
+        let mut changed = vec![true; items.len()];
+        if let Some(stamp) = self.stamp {
+            for (item, changed) in items.iter_mut().zip(&mut changed) {
+                *changed = stamp(item, audit_log_id, true)?;
+            }
+        }
+        let mut stored = self.items.lock().unwrap();
+        if let Some(item) = items.iter().find(|item| !stored.contains_key(&item.get_id())) {
+            return Err(format!("{} not found", item.get_id()).into());
+        }
+        for (item, changed) in items.iter().zip(changed) {
+            if changed {
+                stored.insert(item.get_id(), item.clone());
+            }
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> DeleteBatch<Postgres> for InMemoryRepository<T> {
+    async fn delete_batch(
+        &self,
+        ids: &[Uuid],
+        _audit_log_id: Option<Uuid>,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut stored = self.items.lock().unwrap();
+        Ok(ids.iter().filter(|id| stored.remove(id).is_some()).count())
+    }
+}
+
+#[async_trait]
+impl<T: Identifiable + Clone + Send + Sync> ExistByIds<Postgres> for InMemoryRepository<T> {
+    async fn exist_by_ids(&self, ids: &[Uuid]) -> Result<Vec<(Uuid, bool)>, Box<dyn Error + Send + Sync>> {
+        let stored = self.items.lock().unwrap();
+        Ok(ids.iter().map(|id| (*id, stored.contains_key(id))).collect())
+    }
+}
+
+/// In-memory counterpart of the Postgres test context
+///
+/// The repositories are shared like the ones of a session, so services built from them
+/// and the test see the same models.
+pub struct MockContext {
+    pub audit_log_repository: Arc<InMemoryRepository<AuditLogModel>>,
+    pub person_repository: Arc<InMemoryRepository<PersonModel>>,
+    pub entity_reference_repository: Arc<InMemoryRepository<EntityReferenceModel>>,
+    pub reason_repository: Arc<InMemoryRepository<ReasonModel>>,
+    pub reason_reference_repository: Arc<InMemoryRepository<ReasonReferenceModel>>,
+}
+
+/// Build a context of empty in-memory repositories
+pub fn setup_mock_context() -> MockContext {
+    MockContext {
+        audit_log_repository: Arc::new(InMemoryRepository::new()),
+        person_repository: Arc::new(InMemoryRepository::audited()),
+        entity_reference_repository: Arc::new(InMemoryRepository::audited()),
+        reason_repository: Arc::new(InMemoryRepository::new()),
+        reason_reference_repository: Arc::new(InMemoryRepository::audited()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::setup_mock_context;
+    use crate::fixtures::PersonFixture;
+    use crate::models::audit::audit_log::AuditLogModel;
+    use crate::models::audit_chained::verify_chain;
+    use crate::models::person::person::PersonType;
+    use crate::repository::create_batch::CreateBatch;
+    use crate::repository::load_batch::LoadBatch;
+    use crate::repository::update_batch::UpdateBatch;
+    use chrono::Utc;
+    use heapless::String as HeaplessString;
+    use uuid::Uuid;
+
+    fn audit_log() -> AuditLogModel {
+        AuditLogModel {
+            id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+            updated_by_person_id: Uuid::new_v4(),
+        }
+    }
+
+    // Ported from person_repository::create_batch::tests::test_create_batch
+    #[tokio::test]
+    async fn test_create_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_mock_context();
+        let audit_log = ctx.audit_log_repository.create_batch(vec![audit_log()], None).await?.remove(0);
+
+        let persons = (0..5)
+            .map(|i| {
+                PersonFixture::builder()
+                    .display_name(&format!("Person {i}"))
+                    .person_type(PersonType::Natural)
+                    .build()
+            })
+            .collect();
+        let saved_persons = ctx.person_repository.create_batch(persons, Some(audit_log.id)).await?;
+
+        assert_eq!(saved_persons.len(), 5);
+        for (i, saved_person) in saved_persons.iter().enumerate() {
+            assert_eq!(saved_person.display_name.as_str(), format!("Person {i}"));
+            assert_eq!(saved_person.person_type, PersonType::Natural);
+            assert_eq!(saved_person.audit_log_id, Some(audit_log.id));
+            assert_ne!(saved_person.hash, 0);
+        }
+
+        // Creating the same person twice fails
+        let duplicate = saved_persons[0].clone();
+        assert!(ctx.person_repository.create_batch(vec![duplicate], Some(audit_log.id)).await.is_err());
+        assert_eq!(ctx.person_repository.len(), 5);
+
+        Ok(())
+    }
+
+    // Ported from person_repository::load_batch::tests
+    #[tokio::test]
+    async fn test_load_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_mock_context();
+        let audit_log = audit_log();
+
+        let saved = ctx
+            .person_repository
+            .create_batch(vec![PersonFixture::builder().display_name("Loaded Person").build()], Some(audit_log.id))
+            .await?;
+        let missing = Uuid::new_v4();
+
+        let loaded = ctx.person_repository.load_batch(&[saved[0].id, missing]).await?;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].as_ref().unwrap().display_name.as_str(), "Loaded Person");
+        assert!(loaded[1].is_none());
+
+        Ok(())
+    }
+
+    // Ported from person_repository::update_batch::tests::test_update_batch
+    #[tokio::test]
+    async fn test_update_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_mock_context();
+        let audit_log = audit_log();
+
+        let persons = (0..3)
+            .map(|i| PersonFixture::builder().display_name(&format!("Original Person {i}")).build())
+            .collect();
+        let saved = ctx.person_repository.create_batch(persons, Some(audit_log.id)).await?;
+
+        let update_audit_log = self::audit_log();
+        let updated_persons = saved
+            .iter()
+            .cloned()
+            .map(|mut person| {
+                person.display_name = HeaplessString::try_from("Updated Person").unwrap();
+                person
+            })
+            .collect();
+        let updated = ctx
+            .person_repository
+            .update_batch(updated_persons, Some(update_audit_log.id))
+            .await?;
+
+        assert_eq!(updated.len(), 3);
+        for (before, after) in saved.iter().zip(&updated) {
+            assert_eq!(after.display_name.as_str(), "Updated Person");
+            assert_eq!(after.audit_log_id, Some(update_audit_log.id));
+            assert_eq!(verify_chain(&[before.clone(), after.clone()]), Ok(()));
+        }
+        let loaded = ctx.person_repository.load_batch(&[updated[0].id]).await?;
+        assert_eq!(loaded[0].as_ref().unwrap().display_name.as_str(), "Updated Person");
+
+        // An update that changes nothing keeps the stored version
+        let unchanged = ctx
+            .person_repository
+            .update_batch(vec![updated[0].clone()], Some(self::audit_log().id))
+            .await?;
+        assert_eq!(unchanged[0].audit_log_id, Some(update_audit_log.id));
+
+        // Updating a person that was never created fails
+        let unknown = PersonFixture::builder().build();
+        assert!(ctx.person_repository.update_batch(vec![unknown], Some(audit_log.id)).await.is_err());
+
+        Ok(())
+    }
+}
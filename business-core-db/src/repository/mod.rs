@@ -1,13 +1,18 @@
 pub mod pagination;
 pub mod exist_by_ids;
+pub mod count_by_key;
 pub mod find_by_id;
 pub mod find_by_ids;
 pub mod load;
 pub mod load_audits;
 pub mod load_batch;
 pub mod create_batch;
+pub mod try_create_batch;
 pub mod update_batch;
 pub mod delete_batch;
+pub mod delete_batch_detailed;
+#[cfg(any(test, feature = "in-memory"))]
+pub mod in_memory;
 
 // Repository modules will be added here as needed
 // For example:
@@ -17,13 +22,16 @@ pub mod delete_batch;
 // Re-exports
 pub use pagination::*;
 pub use exist_by_ids::*;
+pub use count_by_key::*;
 pub use find_by_id::*;
 pub use find_by_ids::*;
 pub use load::*;
 pub use load_audits::*;
 pub use load_batch::*;
 pub use create_batch::*;
+pub use try_create_batch::*;
 pub use update_batch::*;
 pub use delete_batch::*;
+pub use delete_batch_detailed::*;
 // pub use audit::*;
 // pub use person::*;
\ No newline at end of file
@@ -143,4 +143,23 @@ impl<T> Page<T> {
     pub fn is_last_page(&self) -> bool {
         !self.has_more()
     }
-}
\ No newline at end of file
+}
+/// Direction of the sort key of an ordered finder
+///
+/// Each ordered finder documents its sort key and tie-breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    /// SQL keyword of the direction
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "ASC",
+            SortOrder::Descending => "DESC",
+        }
+    }
+}
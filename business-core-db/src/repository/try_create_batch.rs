@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use sqlx::Database;
+use uuid::Uuid;
+
+use crate::models::identifiable::Identifiable;
+
+/// What became of one item of a `try_create_batch`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemOutcome<T> {
+    /// The item was created, as persisted
+    Created(T),
+    /// An entity with the same key already exists, nothing was written for the item
+    SkippedDuplicate,
+    /// The item was rejected, nothing was written for it
+    Failed(String),
+}
+
+/// Outcome of a `try_create_batch`, one entry per item in the order of the batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult<T> {
+    pub outcomes: Vec<ItemOutcome<T>>,
+}
+
+impl<T> BatchResult<T> {
+    /// The created items
+    pub fn created(&self) -> impl Iterator<Item = &T> {
+        self.outcomes.iter().filter_map(|outcome| match outcome {
+            ItemOutcome::Created(item) => Some(item),
+            _ => None,
+        })
+    }
+
+    /// Number of items skipped as duplicates
+    pub fn skipped_count(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome, ItemOutcome::SkippedDuplicate))
+            .count()
+    }
+
+    /// Position in the batch and reason of each failed item
+    pub fn failures(&self) -> Vec<(usize, &str)> {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, outcome)| match outcome {
+                ItemOutcome::Failed(reason) => Some((index, reason.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether every item was created
+    pub fn is_complete(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, ItemOutcome::Created(_)))
+    }
+}
+
+/// Generic repository trait for creating a batch where items may fail on their own
+///
+/// Same writes as `CreateBatch`, but an item hitting a recoverable error (a duplicate
+/// key, a constraint or validation error) is reported in the `BatchResult` and the
+/// batch goes on with the next item. The created items stay in the session
+/// transaction, the caller commits or rolls back depending on the result. Errors that
+/// leave the session unusable, such as a lost connection or a timeout, still fail the
+/// whole call.
+///
+/// # Type Parameters
+/// * `DB` - The database type (must implement sqlx::Database)
+/// * `T` - The entity type (must implement Identifiable)
+#[async_trait]
+pub trait TryCreateBatch<DB: Database, T: Identifiable>: Send + Sync {
+    /// Create the items one by one, reporting the outcome of each
+    ///
+    /// # Arguments
+    /// * `items` - A vector of entities to create
+    /// * `audit_log_id` - The optional UUID of the audit log for tracking this operation
+    ///
+    /// # Returns
+    /// * `Ok(BatchResult<T>)` - The outcome of every item
+    /// * `Err` - An error that stopped the batch
+    async fn try_create_batch(
+        &self,
+        items: Vec<T>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<BatchResult<T>, Box<dyn std::error::Error + Send + Sync>>;
+}
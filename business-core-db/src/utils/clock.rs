@@ -0,0 +1,38 @@
+use chrono::{NaiveDate, Utc};
+use std::fmt::Debug;
+
+/// Source of the current date
+///
+/// Date-sensitive logic asks the clock for "today" instead of reading the system time,
+/// so tests can pin the date with a `FixedClock`. The repository factories hand their
+/// clock to the repositories and services they build.
+pub trait Clock: Debug + Send + Sync {
+    /// Current date in UTC
+    fn today(&self) -> NaiveDate;
+}
+
+/// The system time, the clock of the factories unless one is given
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        Utc::now().date_naive()
+    }
+}
+
+/// A clock stopped on one date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(NaiveDate);
+
+impl FixedClock {
+    pub fn new(date: NaiveDate) -> Self {
+        Self(date)
+    }
+}
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
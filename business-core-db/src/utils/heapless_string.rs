@@ -0,0 +1,113 @@
+use heapless::String as HeaplessString;
+use serde::{Deserialize, Deserializer};
+
+/// Copy `value` into a `HeaplessString<N>`, cutting it at the last character boundary
+/// that fits
+///
+/// `N` is a capacity in bytes, so a multibyte character that would straddle the limit
+/// is dropped whole. Returns the string and whether anything was cut.
+pub fn truncate_to_heapless<const N: usize>(value: &str) -> (HeaplessString<N>, bool) {
+    let mut end = value.len().min(N);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let mut truncated = HeaplessString::new();
+    // Cannot fail, `end` is at most `N` bytes
+    let _ = truncated.push_str(&value[..end]);
+    (truncated, end < value.len())
+}
+
+/// Deserialize a `HeaplessString<N>`, truncating over-length input instead of failing
+///
+/// Opt-in with `#[serde(deserialize_with = "deserialize_truncating_heapless")]` on fields
+/// fed from external data, so one over-length field does not reject the whole record.
+/// Each truncation is reported as a `tracing` warning.
+pub fn deserialize_truncating_heapless<'de, D, const N: usize>(
+    deserializer: D,
+) -> Result<HeaplessString<N>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    let (truncated, was_truncated) = truncate_to_heapless::<N>(&value);
+    if was_truncated {
+        tracing::warn!(
+            capacity = N,
+            length = value.len(),
+            "Truncated over-length string during deserialization"
+        );
+    }
+    Ok(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize_truncating_heapless, truncate_to_heapless};
+    use heapless::String as HeaplessString;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Import {
+        #[serde(deserialize_with = "deserialize_truncating_heapless")]
+        name: HeaplessString<5>,
+        code: HeaplessString<3>,
+    }
+
+    #[test]
+    fn test_exact_length_is_kept() {
+        let (value, truncated) = truncate_to_heapless::<5>("abcde");
+        assert_eq!(value.as_str(), "abcde");
+        assert!(!truncated);
+
+        let import: Import = serde_json::from_str(r#"{"name": "abcde", "code": "XAF"}"#).unwrap();
+        assert_eq!(import.name.as_str(), "abcde");
+    }
+
+    #[test]
+    fn test_over_length_multibyte_is_truncated() {
+        // "é" is 2 bytes: "abcé" is 5 bytes, "abcéf" 6
+        let (value, truncated) = truncate_to_heapless::<5>("abcéf");
+        assert_eq!(value.as_str(), "abcé");
+        assert!(truncated);
+
+        // The record is accepted, the strict field still rejects over-length input
+        let import: Import = serde_json::from_str(r#"{"name": "abcdéfg", "code": "XAF"}"#).unwrap();
+        assert_eq!(import.name.as_str(), "abcd");
+        assert!(serde_json::from_str::<Import>(r#"{"name": "abc", "code": "XAFX"}"#).is_err());
+    }
+
+    #[test]
+    fn test_truncation_stays_on_char_boundary() {
+        // Every cut point of a string mixing 1 to 4 byte characters yields valid UTF-8
+        // no longer than the capacity and a prefix of the input
+        fn check<const N: usize>(input: &str) {
+            let (value, truncated) = truncate_to_heapless::<N>(input);
+            assert!(value.len() <= N);
+            assert!(input.starts_with(value.as_str()));
+            assert_eq!(truncated, value.len() < input.len());
+            // Only a partial character is dropped
+            assert!(!truncated || N - value.len() < 4);
+        }
+
+        let input = "aé€😀b€é😀";
+        check::<0>(input);
+        check::<1>(input);
+        check::<2>(input);
+        check::<3>(input);
+        check::<4>(input);
+        check::<5>(input);
+        check::<6>(input);
+        check::<7>(input);
+        check::<8>(input);
+        check::<9>(input);
+        check::<10>(input);
+        check::<11>(input);
+        check::<20>(input);
+        check::<30>(input);
+
+        let (value, truncated) = truncate_to_heapless::<3>("😀");
+        assert_eq!(value.as_str(), "");
+        assert!(truncated);
+    }
+}
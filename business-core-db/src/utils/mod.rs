@@ -1,7 +1,38 @@
-use serde::Serialize;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
 use std::hash::Hasher;
 use twox_hash::XxHash64;
 
+pub mod clock;
+pub mod heapless_string;
+
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use heapless_string::{deserialize_truncating_heapless, truncate_to_heapless};
+
+/// Normal form of a reference data code: surrounding whitespace removed, upper case
+///
+/// Subdivision and locality codes are stored, hashed and compared in this form, so
+/// "ca", "CA" and " CA" are the same code.
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+/// Scale of monetary amounts (balances, limits, fees) once normalized
+pub const MONETARY_SCALE: u32 = 4;
+
+/// Scale of interest rates once normalized
+pub const RATE_SCALE: u32 = 6;
+
+/// `value` with exactly `scale` decimal places, rounded half away from zero
+///
+/// `Decimal` keeps the scale it was built with, so 1.5 and 1.50 serialize, and hash,
+/// differently. Rescaling both to the same scale makes them identical.
+pub fn rescale_decimal(value: Decimal, scale: u32) -> Decimal {
+    let mut rescaled = value.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+    rescaled.rescale(scale);
+    rescaled
+}
+
 /// Hashes serializable data into an i64 using CBOR serialization and XxHash64.
 ///
 /// This provides a stable hash across different runs and systems by:
@@ -14,4 +45,98 @@ pub fn hash_as_i64<T: Serialize>(data: &T) -> Result<i64, String> {
         .map_err(|e| format!("Failed to serialize data for hashing: {e}"))?;
     hasher.write(&cbor);
     Ok(hasher.finish() as i64)
-}
\ No newline at end of file
+}
+
+/// Hashes serializable data into an i64 using CBOR serialization and a 128-bit BLAKE3 digest.
+///
+/// The first 16 bytes of the BLAKE3 output are read as a little-endian u128 and folded
+/// into 64 bits by XOR-ing its high and low halves. Index columns stay BIGINT, so the
+/// collision bound is still that of a 64-bit value; the gain over `hash_as_i64` is the
+/// stronger mixing of the underlying hash.
+///
+/// Two different values can therefore still share a hash. A lookup by hash returns
+/// candidates only, callers must compare each one with the stored value before using it.
+pub fn hash_as_i64_v2<T: Serialize>(data: &T) -> Result<i64, String> {
+    let mut cbor = Vec::new();
+    ciborium::ser::into_writer(data, &mut cbor)
+        .map_err(|e| format!("Failed to serialize data for hashing: {e}"))?;
+    let digest = blake3::hash(&cbor);
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest.as_bytes()[..16]);
+    let wide = u128::from_le_bytes(bytes);
+    Ok(((wide as u64) ^ ((wide >> 64) as u64)) as i64)
+}
+
+/// Version of the hash function behind an index hash column
+///
+/// Stored per index row as SMALLINT (`1` for `V1`, `2` for `V2`) so rows hashed with
+/// different versions can coexist while an index is migrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, sqlx::Type)]
+#[repr(i16)]
+pub enum HashVersion {
+    /// `hash_as_i64`
+    #[default]
+    V1 = 1,
+    /// `hash_as_i64_v2`
+    V2 = 2,
+}
+
+impl HashVersion {
+    /// Hash `data` with this version
+    pub fn hash<T: Serialize>(&self, data: &T) -> Result<i64, String> {
+        match self {
+            HashVersion::V1 => hash_as_i64(data),
+            HashVersion::V2 => hash_as_i64_v2(data),
+        }
+    }
+
+    /// The version finders fall back to while rows of both versions exist
+    pub fn fallback(&self) -> HashVersion {
+        match self {
+            HashVersion::V1 => HashVersion::V2,
+            HashVersion::V2 => HashVersion::V1,
+        }
+    }
+}
+
+impl TryFrom<i16> for HashVersion {
+    type Error = String;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(HashVersion::V1),
+            2 => Ok(HashVersion::V2),
+            _ => Err(format!("Unknown hash version {value}")),
+        }
+    }
+}
+
+// Serialized as the stored SMALLINT, cache notifications carry the raw column value
+impl Serialize for HashVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i16(*self as i16)
+    }
+}
+
+impl<'de> Deserialize<'de> for HashVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = i16::deserialize(deserializer)?;
+        HashVersion::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_as_i64, hash_as_i64_v2, HashVersion};
+
+    // Stored index hashes depend on these values, a change here needs a new HashVersion
+    #[test]
+    fn test_hash_versions_golden() {
+        assert_eq!(hash_as_i64(&"EMP-0001").unwrap(), 4118262451101348953);
+        assert_eq!(hash_as_i64(&"ID-123456789").unwrap(), -9134679210177641988);
+        assert_eq!(hash_as_i64_v2(&"EMP-0001").unwrap(), 8707776836479578080);
+        assert_eq!(hash_as_i64_v2(&"ID-123456789").unwrap(), 3354632525961642543);
+        assert_eq!(HashVersion::V1.hash(&"EMP-0001").unwrap(), hash_as_i64(&"EMP-0001").unwrap());
+        assert_eq!(HashVersion::V2.hash(&"EMP-0001").unwrap(), hash_as_i64_v2(&"EMP-0001").unwrap());
+    }
+}
@@ -0,0 +1,2 @@
+-- Cleanup script for Health Heartbeat tables
+DROP TABLE IF EXISTS health_heartbeat CASCADE;
\ No newline at end of file
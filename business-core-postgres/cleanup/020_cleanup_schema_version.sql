@@ -0,0 +1,2 @@
+-- Cleanup script for Schema Version tables
+DROP TABLE IF EXISTS schema_version CASCADE;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Cleanup: Scoped Subdivision and Locality Codes
+-- Description: Removes all artifacts created by 021_person_scoped_location_codes.sql
+
+DROP INDEX IF EXISTS locality_subdivision_code_key;
+DROP INDEX IF EXISTS country_subdivision_country_code_key;
\ No newline at end of file
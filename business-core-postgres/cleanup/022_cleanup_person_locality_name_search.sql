@@ -0,0 +1,7 @@
+-- Cleanup: Locality Name Search
+-- Description: Removes all artifacts created by 022_person_locality_name_search.sql
+-- The pg_trgm extension is left installed, other schemas may use it.
+
+DROP INDEX IF EXISTS locality_name_l3_trgm_idx;
+DROP INDEX IF EXISTS locality_name_l2_trgm_idx;
+DROP INDEX IF EXISTS locality_name_l1_trgm_idx;
@@ -0,0 +1,4 @@
+-- Cleanup: Audit Archive Marker
+-- Description: Removes all artifacts created by 023_audit_archive_marker.sql
+
+DROP TABLE IF EXISTS audit_archive_marker CASCADE;
@@ -0,0 +1,5 @@
+-- Cleanup: Entity Reference Role Index
+-- Description: Removes all artifacts created by 024_person_entity_reference_role_idx.sql
+
+ALTER TABLE IF EXISTS entity_reference_idx DROP COLUMN IF EXISTS entity_role_hash;
+DROP FUNCTION IF EXISTS entity_role_hash(person_entity_type);
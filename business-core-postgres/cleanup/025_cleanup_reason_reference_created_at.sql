@@ -0,0 +1,6 @@
+-- Cleanup: Reason Reference Creation Time
+-- Description: Removes all artifacts created by 025_reason_reference_created_at.sql
+
+DROP INDEX IF EXISTS idx_reason_reference_reason_id_created_at;
+ALTER TABLE IF EXISTS reason_reference_audit DROP COLUMN IF EXISTS created_at;
+ALTER TABLE IF EXISTS reason_reference DROP COLUMN IF EXISTS created_at;
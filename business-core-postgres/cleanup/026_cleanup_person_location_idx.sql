@@ -0,0 +1,4 @@
+-- Cleanup: Person Location Index
+-- Description: Removes all artifacts created by 026_person_location_idx.sql
+
+ALTER TABLE IF EXISTS person_idx DROP COLUMN IF EXISTS location_id;
@@ -0,0 +1,7 @@
+-- Cleanup: Document Owner
+-- Description: Removes all artifacts created by 027_document_owner.sql
+
+DROP INDEX IF EXISTS idx_person_document_owner;
+ALTER TABLE IF EXISTS person_document_audit DROP COLUMN IF EXISTS owner_type;
+ALTER TABLE IF EXISTS person_document DROP COLUMN IF EXISTS owner_type;
+DROP TYPE IF EXISTS document_owner_type CASCADE;
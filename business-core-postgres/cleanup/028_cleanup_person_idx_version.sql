@@ -0,0 +1,4 @@
+-- Cleanup: Person Index Version
+-- Description: Removes all artifacts created by 028_person_idx_version.sql
+
+ALTER TABLE IF EXISTS person_idx DROP COLUMN IF EXISTS version;
@@ -0,0 +1,6 @@
+-- Cleanup: Person Activity Date
+-- Description: Removes all artifacts created by 029_person_activity_log_date.sql
+
+DROP INDEX IF EXISTS idx_person_activity_log_person_date;
+ALTER TABLE IF EXISTS person_activity_log_audit DROP COLUMN IF EXISTS activity_date;
+ALTER TABLE IF EXISTS person_activity_log DROP COLUMN IF EXISTS activity_date;
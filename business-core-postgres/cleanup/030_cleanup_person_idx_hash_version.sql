@@ -0,0 +1,4 @@
+-- Cleanup: Person Index Hash Version
+-- Description: Removes all artifacts created by 030_person_idx_hash_version.sql
+
+ALTER TABLE IF EXISTS person_idx DROP COLUMN IF EXISTS hash_version;
@@ -0,0 +1,4 @@
+-- Cleanup: Portfolio Membership Index
+-- Description: Removes all artifacts created by 031_person_portfolio_person_idx.sql
+
+DROP INDEX IF EXISTS idx_portfolio_person_id;
@@ -0,0 +1,5 @@
+-- Cleanup: Redacted Person Audit Snapshots
+-- Description: Removes all artifacts created by 032_person_audit_redacted.sql
+
+ALTER TABLE IF EXISTS entity_reference_audit DROP COLUMN IF EXISTS redacted;
+ALTER TABLE IF EXISTS person_audit DROP COLUMN IF EXISTS redacted;
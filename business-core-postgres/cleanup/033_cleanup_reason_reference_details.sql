@@ -0,0 +1,5 @@
+-- Cleanup: Reason Reference Details
+-- Description: Removes all artifacts created by 033_reason_reference_details.sql
+
+ALTER TABLE IF EXISTS reason_reference_audit DROP COLUMN IF EXISTS details;
+ALTER TABLE IF EXISTS reason_reference DROP COLUMN IF EXISTS details;
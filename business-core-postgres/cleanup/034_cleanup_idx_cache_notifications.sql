@@ -0,0 +1,4 @@
+-- Cleanup: Index Cache Notifications
+-- Description: Removes all artifacts created by 034_idx_cache_notifications.sql
+
+DROP FUNCTION IF EXISTS notify_idx_cache_change() CASCADE;
@@ -0,0 +1,13 @@
+-- Cleanup: Index Versions
+-- Description: Removes all artifacts created by 035_idx_version.sql
+
+DROP FUNCTION IF EXISTS advance_idx_version() CASCADE;
+
+ALTER TABLE IF EXISTS country_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS country_subdivision_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS locality_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS location_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS entity_reference_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS risk_summary_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS compliance_metadata_idx DROP COLUMN IF EXISTS version;
+ALTER TABLE IF EXISTS reason_idx DROP COLUMN IF EXISTS version;
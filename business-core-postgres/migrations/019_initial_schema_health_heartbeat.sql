@@ -0,0 +1,16 @@
+-- Migration: Health Heartbeat Schema
+-- Description: Creates the table written by the health check notification roundtrip probe.
+
+-- Heartbeat Table
+-- Rows only live for the duration of a probe: each one is inserted and deleted in one transaction.
+CREATE TABLE IF NOT EXISTS health_heartbeat (
+    id UUID PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create trigger for health_heartbeat table so probes go through the cache notification path
+DROP TRIGGER IF EXISTS health_heartbeat_notify ON health_heartbeat;
+CREATE TRIGGER health_heartbeat_notify
+    AFTER INSERT OR UPDATE OR DELETE ON health_heartbeat
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_cache_change();
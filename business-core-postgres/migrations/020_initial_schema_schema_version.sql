@@ -0,0 +1,12 @@
+-- Migration: Schema Version Schema
+-- Description: Records which migrations the database went through, checked by check_schema_version.
+
+-- Schema Version Table
+-- One row per applied migration level. A migration that changes the schema the
+-- repositories rely on inserts its own number here and bumps db_init::SCHEMA_VERSION.
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO schema_version (version) VALUES (20) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,16 @@
+-- Migration: Scoped Subdivision and Locality Codes
+-- Description: Subdivision codes are unique per country and locality codes per subdivision,
+-- compared without surrounding whitespace and case. The repositories store codes normalized
+-- and reject duplicates before inserting; these indexes are the backstop.
+
+ALTER TABLE country_subdivision DROP CONSTRAINT IF EXISTS country_subdivision_code_key;
+ALTER TABLE country_subdivision_idx DROP CONSTRAINT IF EXISTS country_subdivision_idx_code_hash_key;
+CREATE UNIQUE INDEX IF NOT EXISTS country_subdivision_country_code_key
+    ON country_subdivision (country_id, UPPER(BTRIM(code)));
+
+ALTER TABLE locality DROP CONSTRAINT IF EXISTS locality_code_key;
+ALTER TABLE locality_idx DROP CONSTRAINT IF EXISTS locality_idx_code_hash_key;
+CREATE UNIQUE INDEX IF NOT EXISTS locality_subdivision_code_key
+    ON locality (country_subdivision_id, UPPER(BTRIM(code)));
+
+INSERT INTO schema_version (version) VALUES (21) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,11 @@
+-- Migration: Locality Name Search
+-- Description: Trigram indexes on the locality names, so the ILIKE searches of
+-- LocalityRepositoryImpl::search_by_name do not scan the table.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS locality_name_l1_trgm_idx ON locality USING GIN (name_l1 gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS locality_name_l2_trgm_idx ON locality USING GIN (name_l2 gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS locality_name_l3_trgm_idx ON locality USING GIN (name_l3 gin_trgm_ops);
+
+INSERT INTO schema_version (version) VALUES (22) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,17 @@
+-- Migration: Audit Archive Marker
+-- Description: Records the last archived version of each entity, so the audit
+-- chain of the versions left by AuditRetentionService::archive_before still verifies.
+
+-- Audit Archive Marker Table
+-- One row per audit table and entity. audit_log_id and hash are those of the newest
+-- archived version, the antecedent of the oldest version still in the audit table.
+CREATE TABLE IF NOT EXISTS audit_archive_marker (
+    table_name VARCHAR(64) NOT NULL,
+    entity_id UUID NOT NULL,
+    audit_log_id UUID NOT NULL,
+    hash BIGINT NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (table_name, entity_id)
+);
+
+INSERT INTO schema_version (version) VALUES (23) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,40 @@
+-- Migration: Entity Reference Role Index
+-- Description: Adds the role to entity_reference_idx, so the references of a person with
+-- a given role are found from the index cache.
+
+-- hash_as_i64 of each role, the CBOR encoding of the variant name hashed with XxHash64.
+-- Only used to fill the rows written before this migration, the repository computes the
+-- hash of new rows itself.
+CREATE OR REPLACE FUNCTION entity_role_hash(entity_role person_entity_type) RETURNS BIGINT AS $$
+    SELECT CASE entity_role
+        WHEN 'Customer' THEN 1644315960491035992
+        WHEN 'Employee' THEN 4286150739788185167
+        WHEN 'Shareholder' THEN -3030472844736545534
+        WHEN 'Director' THEN 523835312251192679
+        WHEN 'UltimateBeneficialOwner' THEN 8217372815726727751
+        WHEN 'Agent' THEN -5989710027576616257
+        WHEN 'Vendor' THEN 3506182120819639895
+        WHEN 'Partner' THEN 2965415720612417896
+        WHEN 'RegulatoryContact' THEN -1167527434527046916
+        WHEN 'EmergencyContact' THEN 8780434006588453740
+        WHEN 'SystemAdmin' THEN -8620194833232925628
+        WHEN 'Guarantor' THEN -7908122939865900112
+        WHEN 'LegalGuardian' THEN -2668320905914035906
+        WHEN 'PowerOfAttorney' THEN 6136908863296277354
+        WHEN 'Beneficiary' THEN 3756494415570101430
+        WHEN 'AuthorizedSignatory' THEN -909872228056648280
+        WHEN 'ControllingPerson' THEN -6763471497169635210
+        WHEN 'Delegate' THEN 1955109781811023192
+        WHEN 'Administrator' THEN 1851741532200992615
+        WHEN 'Other' THEN 634335863035501897
+    END
+$$ LANGUAGE SQL IMMUTABLE;
+
+ALTER TABLE entity_reference_idx ADD COLUMN IF NOT EXISTS entity_role_hash BIGINT NOT NULL DEFAULT 0;
+
+UPDATE entity_reference_idx i
+SET entity_role_hash = entity_role_hash(e.entity_role)
+FROM entity_reference e
+WHERE e.id = i.id;
+
+INSERT INTO schema_version (version) VALUES (24) ON CONFLICT (version) DO NOTHING;
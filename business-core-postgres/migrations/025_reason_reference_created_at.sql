@@ -0,0 +1,16 @@
+-- Migration: Reason Reference Creation Time
+-- Description: Records when a reason was applied, for reason usage statistics.
+-- Note: rows written before this migration get the migration time, and their stored
+-- hashes were computed without the column.
+
+ALTER TABLE reason_reference
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE reason_reference_audit
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+-- Usage statistics group the references by reason
+CREATE INDEX IF NOT EXISTS idx_reason_reference_reason_id_created_at
+    ON reason_reference(reason_id, created_at);
+
+INSERT INTO schema_version (version) VALUES (25) ON CONFLICT (version) DO NOTHING;
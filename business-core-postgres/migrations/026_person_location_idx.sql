@@ -0,0 +1,12 @@
+-- Migration: Person Location Index
+-- Description: Adds the location to person_idx, so the persons at a location are found
+-- from the index cache.
+
+ALTER TABLE person_idx ADD COLUMN IF NOT EXISTS location_id UUID;
+
+UPDATE person_idx i
+SET location_id = p.location_id
+FROM person p
+WHERE p.id = i.id;
+
+INSERT INTO schema_version (version) VALUES (26) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,44 @@
+-- Migration: Document Owner
+-- Description: Documents belong to a person, a product or an entity reference. The
+-- person_id column becomes owner_id, and owner_type tells what it refers to; the
+-- existing rows are backfilled as Person. Their stored hashes were computed with
+-- person_id.
+
+DO $$
+BEGIN
+    CREATE TYPE document_owner_type AS ENUM ('Person', 'Product', 'EntityReference');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'person_document' AND column_name = 'person_id'
+    ) THEN
+        ALTER TABLE person_document RENAME COLUMN person_id TO owner_id;
+    END IF;
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'person_document_audit' AND column_name = 'person_id'
+    ) THEN
+        ALTER TABLE person_document_audit RENAME COLUMN person_id TO owner_id;
+    END IF;
+END
+$$;
+
+ALTER TABLE person_document
+    ADD COLUMN IF NOT EXISTS owner_type document_owner_type NOT NULL DEFAULT 'Person';
+ALTER TABLE person_document ALTER COLUMN owner_type DROP DEFAULT;
+
+ALTER TABLE person_document_audit
+    ADD COLUMN IF NOT EXISTS owner_type document_owner_type NOT NULL DEFAULT 'Person';
+ALTER TABLE person_document_audit ALTER COLUMN owner_type DROP DEFAULT;
+
+-- Documents are looked up by owner
+CREATE INDEX IF NOT EXISTS idx_person_document_owner
+    ON person_document(owner_type, owner_id);
+
+INSERT INTO schema_version (version) VALUES (27) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,17 @@
+-- Migration: Person Index Version
+-- Description: Adds a version to person_idx, 1 on insert and advanced by every update.
+-- The cache notifications carry it, so a replayed notification older than the cached
+-- entry is dropped. Existing rows start at the length of their audit chain.
+
+ALTER TABLE person_idx ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
+
+UPDATE person_idx i
+SET version = a.versions
+FROM (
+    SELECT id, COUNT(*) AS versions
+    FROM person_audit
+    GROUP BY id
+) a
+WHERE a.id = i.id;
+
+INSERT INTO schema_version (version) VALUES (28) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,24 @@
+-- Migration: Person Activity Date
+-- Description: Records when an activity took place, for the recent activity of a person.
+-- Existing rows get the time of the audit log that wrote them.
+
+ALTER TABLE person_activity_log ADD COLUMN IF NOT EXISTS activity_date TIMESTAMPTZ;
+UPDATE person_activity_log l
+SET activity_date = a.updated_at
+FROM audit_log a
+WHERE a.id = l.audit_log_id AND l.activity_date IS NULL;
+UPDATE person_activity_log SET activity_date = NOW() WHERE activity_date IS NULL;
+ALTER TABLE person_activity_log ALTER COLUMN activity_date SET NOT NULL;
+
+ALTER TABLE person_activity_log_audit ADD COLUMN IF NOT EXISTS activity_date TIMESTAMPTZ;
+UPDATE person_activity_log_audit l
+SET activity_date = a.updated_at
+FROM audit_log a
+WHERE a.id = l.audit_log_id AND l.activity_date IS NULL;
+ALTER TABLE person_activity_log_audit ALTER COLUMN activity_date SET NOT NULL;
+
+-- Recent activity of a person, newest first
+CREATE INDEX IF NOT EXISTS idx_person_activity_log_person_date
+    ON person_activity_log(person_id, activity_date DESC);
+
+INSERT INTO schema_version (version) VALUES (29) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,7 @@
+-- Migration: Person Index Hash Version
+-- Description: Records the hash function of the hash columns of person_idx
+-- (1: hash_as_i64, 2: hash_as_i64_v2). Existing rows were hashed with version 1.
+
+ALTER TABLE person_idx ADD COLUMN IF NOT EXISTS hash_version SMALLINT NOT NULL DEFAULT 1;
+
+INSERT INTO schema_version (version) VALUES (30) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,7 @@
+-- Migration: Portfolio Membership Index
+-- Description: Indexes portfolio by person_id for the portfolio membership queries.
+
+CREATE INDEX IF NOT EXISTS idx_portfolio_person_id
+    ON portfolio(person_id);
+
+INSERT INTO schema_version (version) VALUES (31) ON CONFLICT (version) DO NOTHING;
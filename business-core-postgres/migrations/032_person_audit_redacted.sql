@@ -0,0 +1,10 @@
+-- Migration: Redacted Person Audit Snapshots
+-- Description: Marks the person and entity reference audit rows whose personal data was
+-- scrubbed. The stored hash is kept, chain verification checks the links but not the
+-- content of redacted rows.
+
+ALTER TABLE person_audit ADD COLUMN IF NOT EXISTS redacted BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE entity_reference_audit ADD COLUMN IF NOT EXISTS redacted BOOLEAN NOT NULL DEFAULT FALSE;
+
+INSERT INTO schema_version (version) VALUES (32) ON CONFLICT (version) DO NOTHING;
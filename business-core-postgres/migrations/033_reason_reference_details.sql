@@ -0,0 +1,10 @@
+-- Migration: Reason Reference Details
+-- Description: Stores the free text details required by reasons with requires_details.
+-- Note: existing rows have no details, and their stored hashes were computed without
+-- the column.
+
+ALTER TABLE reason_reference ADD COLUMN IF NOT EXISTS details TEXT;
+
+ALTER TABLE reason_reference_audit ADD COLUMN IF NOT EXISTS details TEXT;
+
+INSERT INTO schema_version (version) VALUES (33) ON CONFLICT (version) DO NOTHING;
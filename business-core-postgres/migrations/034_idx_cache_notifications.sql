@@ -0,0 +1,80 @@
+-- Migration: Index Cache Notifications
+-- Description: Publishes the changes of the person and reason and purpose idx tables on
+-- the idx_cache_change channel, as {"table", "op", "rows"} with the changed rows as JSON.
+-- Read by IdxNotificationListener, which hands them to the coalescer of each cache. The
+-- health_heartbeat rows written by the health check roundtrip probe go the same way.
+--
+-- The triggers fire once per statement and read the changed rows from its transition
+-- table, so a batch write sends one notification instead of one per row. A payload is
+-- limited to 8000 bytes by NOTIFY: the rows of a large statement are split over as few
+-- notifications as fit under the limit. A single row too large for a notification fails
+-- the statement, as it did with a trigger per row.
+
+CREATE OR REPLACE FUNCTION notify_idx_cache_change() RETURNS trigger AS $$
+DECLARE
+    max_payload CONSTANT INTEGER := 7900;
+    prefix TEXT := '{"table":' || to_json(TG_TABLE_NAME::text) || ',"op":' || to_json(TG_OP) || ',"rows":[';
+    batch TEXT := '';
+    changed TEXT;
+BEGIN
+    FOR changed IN EXECUTE format(
+        'SELECT row_to_json(changed)::text FROM %I AS changed',
+        CASE WHEN TG_OP = 'DELETE' THEN 'old_rows' ELSE 'new_rows' END
+    ) LOOP
+        IF batch <> '' AND octet_length(prefix) + octet_length(batch) + octet_length(changed) + 3 > max_payload THEN
+            PERFORM pg_notify('idx_cache_change', prefix || batch || ']}');
+            batch := '';
+        END IF;
+        IF batch = '' THEN
+            batch := changed;
+        ELSE
+            batch := batch || ',' || changed;
+        END IF;
+    END LOOP;
+    IF batch <> '' THEN
+        PERFORM pg_notify('idx_cache_change', prefix || batch || ']}');
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- A trigger with transition tables fires on a single event, hence one per operation.
+-- The triggers of migrations 002 to 009 and 019 on these tables, notifying through
+-- notify_cache_change, are dropped: nothing reads that channel for them any more. The
+-- calendar tables keep theirs, of migrations 011 to 013.
+DO $$
+DECLARE
+    idx_table TEXT;
+BEGIN
+    FOREACH idx_table IN ARRAY ARRAY[
+        'country_idx', 'country_subdivision_idx', 'locality_idx', 'location_idx', 'person_idx',
+        'entity_reference_idx', 'risk_summary_idx', 'compliance_metadata_idx', 'reason_idx',
+        'health_heartbeat'
+    ] LOOP
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', idx_table || '_notify', idx_table);
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', idx_table || '_cache_change_insert', idx_table);
+        EXECUTE format(
+            'CREATE TRIGGER %I AFTER INSERT ON %I REFERENCING NEW TABLE AS new_rows '
+            'FOR EACH STATEMENT EXECUTE FUNCTION notify_idx_cache_change()',
+            idx_table || '_cache_change_insert',
+            idx_table
+        );
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', idx_table || '_cache_change_update', idx_table);
+        EXECUTE format(
+            'CREATE TRIGGER %I AFTER UPDATE ON %I REFERENCING NEW TABLE AS new_rows '
+            'FOR EACH STATEMENT EXECUTE FUNCTION notify_idx_cache_change()',
+            idx_table || '_cache_change_update',
+            idx_table
+        );
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', idx_table || '_cache_change_delete', idx_table);
+        EXECUTE format(
+            'CREATE TRIGGER %I AFTER DELETE ON %I REFERENCING OLD TABLE AS old_rows '
+            'FOR EACH STATEMENT EXECUTE FUNCTION notify_idx_cache_change()',
+            idx_table || '_cache_change_delete',
+            idx_table
+        );
+    END LOOP;
+END;
+$$;
+
+INSERT INTO schema_version (version) VALUES (34) ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,46 @@
+-- Migration: Index Versions
+-- Description: Adds a version to the remaining person and reason and purpose idx tables,
+-- and advances the version of every idx row on update in a trigger, person_idx included.
+-- The notifications of migration 034 carry it, so a replayed notification older than the
+-- cached entry is dropped, see CacheVersions. Existing rows start at 1.
+
+DO $$
+DECLARE
+    idx_table TEXT;
+BEGIN
+    FOREACH idx_table IN ARRAY ARRAY[
+        'country_idx', 'country_subdivision_idx', 'locality_idx', 'location_idx',
+        'entity_reference_idx', 'risk_summary_idx', 'compliance_metadata_idx', 'reason_idx'
+    ] LOOP
+        EXECUTE format('ALTER TABLE %I ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1', idx_table);
+    END LOOP;
+END;
+$$;
+
+CREATE OR REPLACE FUNCTION advance_idx_version() RETURNS trigger AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    idx_table TEXT;
+BEGIN
+    FOREACH idx_table IN ARRAY ARRAY[
+        'country_idx', 'country_subdivision_idx', 'locality_idx', 'location_idx', 'person_idx',
+        'entity_reference_idx', 'risk_summary_idx', 'compliance_metadata_idx', 'reason_idx'
+    ] LOOP
+        EXECUTE format('DROP TRIGGER IF EXISTS %I ON %I', idx_table || '_version', idx_table);
+        EXECUTE format(
+            'CREATE TRIGGER %I BEFORE UPDATE ON %I '
+            'FOR EACH ROW EXECUTE FUNCTION advance_idx_version()',
+            idx_table || '_version',
+            idx_table
+        );
+    END LOOP;
+END;
+$$;
+
+INSERT INTO schema_version (version) VALUES (35) ON CONFLICT (version) DO NOTHING;
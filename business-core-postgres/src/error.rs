@@ -0,0 +1,159 @@
+use business_core_db::repository::try_create_batch::ItemOutcome;
+use sqlx::error::ErrorKind;
+use sqlx::postgres::PgDatabaseError;
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Typed repository error for database constraint violations, rejected batches and
+/// operation timeouts
+///
+/// Produced by `map_db_error`, `OperationTimeout` and the batch validations. Repositories still return
+/// `Box<dyn Error + Send + Sync>`, callers recover the typed variant with
+/// `downcast_ref::<RepositoryError>()`.
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    #[error("{entity}: foreign key violation on {constraint} (referenced table: {referenced_table})")]
+    ForeignKeyViolation {
+        entity: String,
+        constraint: String,
+        referenced_table: String,
+    },
+
+    #[error("{entity}: unique violation on {constraint}")]
+    UniqueViolation { entity: String, constraint: String },
+
+    #[error("{entity}: check violation on {constraint}")]
+    CheckViolation { entity: String, constraint: String },
+
+    #[error("reason: duplicate code {0}")]
+    DuplicateCode(String),
+
+    #[error("reason {id}: l{level}_{present} is set without l{level}_{missing}")]
+    UnpairedLanguageColumn {
+        id: Uuid,
+        level: u8,
+        present: &'static str,
+        missing: &'static str,
+    },
+
+    #[error("{entity}: code {code} is already used by {existing_id}")]
+    CodeTaken { entity: String, code: String, existing_id: Uuid },
+
+    #[error("{entity}: details are required by reason codes {}", codes.join(", "))]
+    MissingReasonDetails { entity: String, codes: Vec<String> },
+
+    #[error("{entity} {id}: status cannot change from {from} to {to}")]
+    InvalidTransition { entity: String, id: Uuid, from: String, to: String },
+
+    #[error("{entity}: search text must have at least {min_chars} characters")]
+    SearchTooShort { entity: String, min_chars: usize },
+
+    #[error("{entity}: {key} is not an i64 index key")]
+    UnknownIndexKey { entity: String, key: String },
+
+    #[error("person {person_id}: entity_reference_count {count} cannot take a delta of {delta}")]
+    EntityReferenceCountUnderflow { person_id: Uuid, count: i32, delta: i32 },
+
+    #[error("{entity} {id}: index row does not match the main row")]
+    IndexDiverged { entity: String, id: Uuid },
+
+    #[error("{entity}: transaction lock not acquired within {timeout:?}")]
+    LockAcquisitionTimeout { entity: String, timeout: Duration },
+
+    #[error("{entity}: query did not complete within {timeout:?}")]
+    QueryTimeout { entity: String, timeout: Duration },
+
+    #[error("{entity}: {source}")]
+    Database {
+        entity: String,
+        #[source]
+        source: sqlx::Error,
+    },
+}
+
+/// Map a sqlx error to a `RepositoryError` for the given entity
+///
+/// Constraint violations are recognized from the Postgres error code. The referenced
+/// table of a foreign key violation is taken from the error detail
+/// (`Key (...)=(...) is not present in table "..."`), falling back to the table the
+/// error was raised on. Any other error is wrapped in `RepositoryError::Database`.
+pub fn map_db_error(entity: &str, e: sqlx::Error) -> RepositoryError {
+    let entity = entity.to_string();
+    let (kind, constraint, referenced_table) = match e.as_database_error() {
+        Some(db_error) => {
+            let pg_error = db_error.try_downcast_ref::<PgDatabaseError>();
+            let constraint = db_error.constraint().unwrap_or_default().to_string();
+            let referenced_table = pg_error
+                .and_then(|pg_error| pg_error.detail().and_then(table_from_detail))
+                .or_else(|| db_error.table().map(str::to_string))
+                .unwrap_or_default();
+            (db_error.kind(), constraint, referenced_table)
+        }
+        None => return RepositoryError::Database { entity, source: e },
+    };
+
+    match kind {
+        ErrorKind::ForeignKeyViolation => RepositoryError::ForeignKeyViolation {
+            entity,
+            constraint,
+            referenced_table,
+        },
+        ErrorKind::UniqueViolation => RepositoryError::UniqueViolation { entity, constraint },
+        ErrorKind::CheckViolation => RepositoryError::CheckViolation { entity, constraint },
+        _ => RepositoryError::Database { entity, source: e },
+    }
+}
+
+/// Outcome of an item of a `try_create_batch` whose write failed with `error`
+///
+/// A unique violation skips the item as a duplicate. Other constraint violations and
+/// the rejections of the batch validations fail the item. `None` for any other error,
+/// which stops the batch.
+pub fn recoverable_outcome<T>(error: &(dyn std::error::Error + Send + Sync + 'static)) -> Option<ItemOutcome<T>> {
+    match error.downcast_ref::<RepositoryError>()? {
+        RepositoryError::UniqueViolation { .. } => Some(ItemOutcome::SkippedDuplicate),
+        RepositoryError::LockAcquisitionTimeout { .. }
+        | RepositoryError::QueryTimeout { .. }
+        | RepositoryError::Database { .. } => None,
+        rejected => Some(ItemOutcome::Failed(rejected.to_string())),
+    }
+}
+
+fn table_from_detail(detail: &str) -> Option<String> {
+    let start = detail.find("table \"")? + "table \"".len();
+    let end = detail[start..].find('"')? + start;
+    Some(detail[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{map_db_error, RepositoryError};
+    use crate::test_helper::setup_test_context;
+
+    #[tokio::test]
+    async fn test_map_db_error_check_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let executor = &ctx.audit_repos().audit_log_repository.executor;
+
+        let mut tx = executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        sqlx::query("CREATE TEMP TABLE map_db_error_check (amount INT CONSTRAINT amount_positive CHECK (amount > 0))")
+            .execute(&mut **transaction)
+            .await?;
+        let result = sqlx::query("INSERT INTO map_db_error_check (amount) VALUES (-1)")
+            .execute(&mut **transaction)
+            .await;
+
+        let error = map_db_error("map_db_error_check", result.unwrap_err());
+        match error {
+            RepositoryError::CheckViolation { entity, constraint } => {
+                assert_eq!(entity, "map_db_error_check");
+                assert_eq!(constraint, "amount_positive");
+            }
+            other => panic!("Expected CheckViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
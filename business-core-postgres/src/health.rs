@@ -0,0 +1,352 @@
+//! Readiness probe for the postgres layer
+//!
+//! `health_check` runs a set of cheap checks against a pool and reports each one
+//! with its outcome and latency. It never fails as a whole: a check that cannot run
+//! is reported as failed with the reason in `detail`.
+
+use sqlx::postgres::PgListener;
+use sqlx::{PgExecutor, PgPool};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::repository::idx_notification_listener::{ListenerActivity, IDX_CACHE_CHANNEL};
+
+/// Trigger function of migration 034, publishing the idx changes on `IDX_CACHE_CHANNEL`
+const NOTIFY_FUNCTION: &str = "notify_idx_cache_change";
+
+/// `pg_trigger.tgtype` bits of the insert, delete and update events, one trigger each
+const NOTIFY_EVENTS: i32 = 4 | 8 | 16;
+
+/// Notification the `listener` check sends on a quiet channel, of a table without handler
+const LISTENER_HEARTBEAT: &str = r#"{"table":"health_heartbeat","op":"INSERT","row":{}}"#;
+
+/// What `health_check` verifies
+#[derive(Debug, Clone)]
+pub struct HealthOptions {
+    /// Tables that must exist
+    pub expected_tables: Vec<String>,
+    /// Tables that must carry the cache notification triggers of every event
+    pub notifying_tables: Vec<String>,
+    /// Activity of the `IdxNotificationListener` that must be receiving the idx cache
+    /// notifications, `None` skips the check
+    pub listener: Option<ListenerActivity>,
+    /// Age of the last notification up to which the listener counts as receiving
+    /// without being probed
+    pub max_notification_age: Duration,
+    /// Insert and delete a heartbeat row and wait for its cache notification
+    pub probe_notification_roundtrip: bool,
+    /// How long the roundtrip probe waits for the notification
+    pub notification_timeout: Duration,
+}
+
+impl Default for HealthOptions {
+    fn default() -> Self {
+        Self {
+            expected_tables: vec!["person".to_string(), "audit_log".to_string()],
+            notifying_tables: vec!["person_idx".to_string()],
+            listener: None,
+            max_notification_age: Duration::from_secs(60),
+            probe_notification_roundtrip: false,
+            notification_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub passed: bool,
+    pub latency: Duration,
+    /// Reason of a failure
+    pub detail: Option<String>,
+}
+
+/// Outcome of all checks, in the order they ran
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Whether every check passed
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// The check with the given name, if it ran
+    pub fn check(&self, name: &str) -> Option<&HealthCheck> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+}
+
+/// Check that the database behind `pool` is ready to serve the repositories
+///
+/// Checks, in order:
+/// - `connection`: a connection can be acquired and answers a query
+/// - `tables`: every table of `expected_tables` exists
+/// - `triggers`: every table of `notifying_tables` has the cache notification triggers
+///   of inserts, updates and deletes
+/// - `listener`: only with `listener`, the listener received a notification within
+///   `max_notification_age`, or receives one sent on its channel within
+///   `notification_timeout`
+/// - `notification_roundtrip`: only with `probe_notification_roundtrip`, a heartbeat row
+///   is inserted and deleted and its notification arrives on `IDX_CACHE_CHANNEL` within
+///   `notification_timeout`
+///
+/// The checks after `connection` are skipped when no connection could be acquired.
+pub async fn health_check(pool: &PgPool, options: &HealthOptions) -> HealthReport {
+    let mut checks = vec![timed("connection", check_connection(pool)).await];
+    if !checks[0].passed {
+        return HealthReport { checks };
+    }
+
+    checks.push(timed("tables", check_tables(pool, &options.expected_tables)).await);
+    checks.push(timed("triggers", check_triggers(pool, &options.notifying_tables)).await);
+    if let Some(activity) = &options.listener {
+        checks.push(timed("listener", check_listener(pool, activity, options)).await);
+    }
+    if options.probe_notification_roundtrip {
+        checks.push(
+            timed(
+                "notification_roundtrip",
+                probe_notification_roundtrip(pool, options.notification_timeout),
+            )
+            .await,
+        );
+    }
+
+    HealthReport { checks }
+}
+
+async fn timed(name: &'static str, check: impl Future<Output = Result<(), String>>) -> HealthCheck {
+    let started = Instant::now();
+    let result = check.await;
+    HealthCheck {
+        name,
+        passed: result.is_ok(),
+        latency: started.elapsed(),
+        detail: result.err(),
+    }
+}
+
+async fn check_connection(pool: &PgPool) -> Result<(), String> {
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    sqlx::query("SELECT 1")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn check_tables(pool: &PgPool, tables: &[String]) -> Result<(), String> {
+    let missing: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM unnest($1::text[]) AS name WHERE to_regclass(name) IS NULL",
+    )
+    .bind(tables)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Missing tables: {}", missing.join(", ")))
+    }
+}
+
+async fn check_triggers<'e>(executor: impl PgExecutor<'e>, tables: &[String]) -> Result<(), String> {
+    let missing: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT name FROM unnest($1::text[]) AS name
+        WHERE (
+            SELECT COALESCE(bit_or(pg_trigger.tgtype::integer), 0) & $3
+            FROM pg_trigger
+            JOIN pg_proc ON pg_proc.oid = pg_trigger.tgfoid
+            WHERE pg_trigger.tgrelid = to_regclass(name)
+            AND pg_proc.proname = $2
+            AND NOT pg_trigger.tgisinternal
+        ) <> $3
+        "#,
+    )
+    .bind(tables)
+    .bind(NOTIFY_FUNCTION)
+    .bind(NOTIFY_EVENTS)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Missing cache notification trigger on: {}", missing.join(", ")))
+    }
+}
+
+async fn check_listener(pool: &PgPool, activity: &ListenerActivity, options: &HealthOptions) -> Result<(), String> {
+    let last_notification = activity.last_notification();
+    if last_notification.is_some_and(|last| last.elapsed() <= options.max_notification_age) {
+        return Ok(());
+    }
+
+    // A quiet channel, send a notification and wait for the listener to receive it
+    let sent = Instant::now();
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(IDX_CACHE_CHANNEL)
+        .bind(LISTENER_HEARTBEAT)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    match tokio::time::timeout(options.notification_timeout, activity.received_since(sent)).await {
+        Ok(()) => Ok(()),
+        Err(_) => Err(match last_notification {
+            Some(last) => format!(
+                "No notification received on {IDX_CACHE_CHANNEL} for {:?}",
+                last.elapsed()
+            ),
+            None => format!("No notification ever received on {IDX_CACHE_CHANNEL}"),
+        }),
+    }
+}
+
+async fn probe_notification_roundtrip(pool: &PgPool, timeout: Duration) -> Result<(), String> {
+    let mut listener = PgListener::connect_with(pool).await.map_err(|e| e.to_string())?;
+    listener.listen(IDX_CACHE_CHANNEL).await.map_err(|e| e.to_string())?;
+
+    let heartbeat_id = Uuid::new_v4();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("INSERT INTO health_heartbeat (id) VALUES ($1)")
+        .bind(heartbeat_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM health_heartbeat WHERE id = $1")
+        .bind(heartbeat_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    let heartbeat_id = heartbeat_id.to_string();
+    let wait = async {
+        loop {
+            let notification = listener.recv().await.map_err(|e| e.to_string())?;
+            if notification.payload().contains(&heartbeat_id) {
+                return Ok(());
+            }
+        }
+    };
+    match tokio::time::timeout(timeout, wait).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("No cache notification for the heartbeat within {timeout:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_triggers, health_check, HealthOptions};
+    use crate::repository::idx_notification_listener::ListenerActivity;
+    use crate::test_helper::{setup_test_context, setup_test_context_and_listen};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_health_check_passes() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The larger pool of this context leaves room for the probe's listener and
+        // heartbeat transaction next to the test session
+        let ctx = setup_test_context_and_listen().await?;
+
+        let options = HealthOptions {
+            probe_notification_roundtrip: true,
+            ..HealthOptions::default()
+        };
+        let report = health_check(ctx.pool(), &options).await;
+
+        assert!(report.is_healthy(), "{report:?}");
+        let names: Vec<&str> = report.checks.iter().map(|check| check.name).collect();
+        assert_eq!(names, vec!["connection", "tables", "triggers", "notification_roundtrip"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_with_listener() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context_and_listen().await?;
+
+        // Nothing was received yet, the check probes the channel and the listener of
+        // the context receives the heartbeat
+        let options = HealthOptions {
+            listener: Some(ctx.listener_activity()),
+            max_notification_age: Duration::ZERO,
+            ..HealthOptions::default()
+        };
+        let report = health_check(ctx.pool(), &options).await;
+        assert!(report.check("listener").is_some_and(|check| check.passed), "{report:?}");
+        assert!(ctx.listener_activity().last_notification().is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_a_listener_receiving_nothing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+
+        // The activity of a listener that is not running
+        let activity = ListenerActivity::default();
+        let options = HealthOptions {
+            listener: Some(activity.clone()),
+            notification_timeout: Duration::from_millis(100),
+            ..HealthOptions::default()
+        };
+        let report = health_check(ctx.pool(), &options).await;
+        let listener = report.check("listener").unwrap();
+        assert!(!listener.passed);
+        assert_eq!(
+            listener.detail.as_deref(),
+            Some("No notification ever received on idx_cache_change")
+        );
+
+        // A recent notification passes without probing the channel
+        activity.record();
+        let report = health_check(ctx.pool(), &options).await;
+        assert!(report.check("listener").is_some_and(|check| check.passed), "{report:?}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_missing_triggers_and_tables() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+
+        let options = HealthOptions {
+            expected_tables: vec!["person".to_string(), "no_such_table".to_string()],
+            ..HealthOptions::default()
+        };
+        let report = health_check(ctx.pool(), &options).await;
+
+        assert!(!report.is_healthy());
+        assert!(report.check("connection").is_some_and(|check| check.passed));
+        assert!(report.check("triggers").is_some_and(|check| check.passed), "{report:?}");
+
+        let tables = report.check("tables").unwrap();
+        assert!(!tables.passed);
+        assert_eq!(tables.detail.as_deref(), Some("Missing tables: no_such_table"));
+
+        // The schema is shared between tests, so the trigger is dropped in the session
+        // and checked there, the rollback restores it. The deletes of person_idx would
+        // no longer reach the caches.
+        let person_repo = &ctx.person_repos().person_repository;
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        sqlx::query("DROP TRIGGER person_idx_cache_change_delete ON person_idx")
+            .execute(&mut **transaction)
+            .await?;
+        let notifying_tables = vec!["person_idx".to_string(), "audit_log".to_string()];
+        assert_eq!(
+            check_triggers(&mut **transaction, &notifying_tables).await,
+            Err("Missing cache notification trigger on: person_idx, audit_log".to_string())
+        );
+
+        Ok(())
+    }
+}
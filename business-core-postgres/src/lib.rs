@@ -1,4 +1,8 @@
+pub mod error;
+pub mod health;
+pub mod pool_monitor;
 pub mod repository;
+pub mod service;
 pub mod utils;
 
 pub use repository::audit::audit_log_repository::AuditLogRepositoryImpl;
@@ -0,0 +1,301 @@
+//! Connection pool metrics and acquisition-failure diagnostics
+//!
+//! `PoolMonitor` wraps a pool and counts the acquisitions made through it, the ones
+//! that had to wait for a free connection and the ones that timed out. Connections
+//! acquired through the monitor are held by a `MonitoredConnection`, tagged by the
+//! caller, so an acquire timeout can name the session holding a connection longest.
+//! sqlx exposes no acquire metrics, so the time spent waiting for a connection is
+//! measured by the monitor as well and reported by `PoolMonitor::stats`.
+
+use parking_lot::Mutex;
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// State of a pool at one point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSnapshot {
+    /// Connections currently in use, monitored or not
+    pub in_use: u32,
+    /// Upper bound of the pool
+    pub max_connections: u32,
+    /// Acquisitions made through the monitor
+    pub acquisitions: u64,
+    /// Acquisitions that found no idle connection in a full pool
+    pub waits: u64,
+    /// Acquisitions that timed out
+    pub timeouts: u64,
+    /// The monitored connection held longest, with its tag
+    pub longest_hold: Option<(Duration, Option<String>)>,
+}
+
+impl std::fmt::Display for PoolSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} in use", self.in_use, self.max_connections)?;
+        match &self.longest_hold {
+            Some((held, Some(tag))) => write!(f, ", longest hold {:.1}s by session tagged '{tag}'", held.as_secs_f64()),
+            Some((held, None)) => write!(f, ", longest hold {:.1}s by an untagged session", held.as_secs_f64()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Why `PoolMonitor::acquire` failed
+#[derive(Debug, Error)]
+pub enum PoolMonitorError {
+    #[error("pool exhausted: {0}")]
+    Exhausted(PoolSnapshot),
+
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// A connection held through the monitor
+struct Hold {
+    tag: Option<String>,
+    since: Instant,
+}
+
+/// Pressure on a pool, for diagnosing lock and acquire timeouts at runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Open connections, active or idle
+    pub size: u32,
+    /// Connections currently in use, monitored or not
+    pub active: u32,
+    /// Open connections waiting in the pool
+    pub idle: u32,
+    /// Upper bound of the pool
+    pub max_connections: u32,
+    /// Acquisitions made through the monitor
+    pub acquisitions: u64,
+    /// Acquisitions that found no idle connection in a full pool
+    pub waits: u64,
+    /// Acquisitions that timed out
+    pub timeouts: u64,
+    /// Time spent in acquisitions through the monitor, timed out ones included
+    pub total_acquire_wait: Duration,
+    /// Longest single acquisition
+    pub max_acquire_wait: Duration,
+}
+
+impl PoolStats {
+    /// Mean time an acquisition took, `None` before the first one
+    pub fn mean_acquire_wait(&self) -> Option<Duration> {
+        let acquisitions = u32::try_from(self.acquisitions).ok().filter(|count| *count > 0)?;
+        Some(self.total_acquire_wait / acquisitions)
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    acquisitions: u64,
+    waits: u64,
+    timeouts: u64,
+    total_acquire_wait: Duration,
+    max_acquire_wait: Duration,
+    next_hold: u64,
+    holds: HashMap<u64, Hold>,
+}
+
+/// Pool wrapper recording acquisitions, waits, timeouts and held connections
+///
+/// Only acquisitions through `acquire` are counted and tagged. Connections taken from
+/// the pool directly, e.g. by a unit of work, still show in `in_use`.
+#[derive(Clone)]
+pub struct PoolMonitor {
+    pool: PgPool,
+    stats: Arc<Mutex<Counters>>,
+}
+
+impl PoolMonitor {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            stats: Arc::new(Mutex::new(Counters::default())),
+        }
+    }
+
+    /// The monitored pool
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Acquire a connection, tagged with the caller's name for diagnostics
+    ///
+    /// When the pool's acquire timeout passes, fails with
+    /// `PoolMonitorError::Exhausted` carrying the snapshot at that moment.
+    pub async fn acquire(&self, tag: Option<&str>) -> Result<MonitoredConnection, PoolMonitorError> {
+        let must_wait = self.pool.num_idle() == 0 && self.pool.size() >= self.max_connections();
+        {
+            let mut stats = self.stats.lock();
+            stats.acquisitions += 1;
+            if must_wait {
+                stats.waits += 1;
+            }
+        }
+
+        let started = Instant::now();
+        let acquired = self.pool.acquire().await;
+        self.record_acquire_wait(started.elapsed());
+        let connection = match acquired {
+            Ok(connection) => connection,
+            Err(sqlx::Error::PoolTimedOut) => {
+                self.stats.lock().timeouts += 1;
+                return Err(PoolMonitorError::Exhausted(self.snapshot()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut stats = self.stats.lock();
+        let hold = stats.next_hold;
+        stats.next_hold += 1;
+        stats.holds.insert(
+            hold,
+            Hold {
+                tag: tag.map(str::to_string),
+                since: Instant::now(),
+            },
+        );
+        Ok(MonitoredConnection {
+            connection,
+            stats: self.stats.clone(),
+            hold,
+        })
+    }
+
+    /// Current state of the pool
+    pub fn snapshot(&self) -> PoolSnapshot {
+        let stats = self.stats.lock();
+        let longest_hold = stats
+            .holds
+            .values()
+            .min_by_key(|hold| hold.since)
+            .map(|hold| (hold.since.elapsed(), hold.tag.clone()));
+        PoolSnapshot {
+            in_use: self.pool.size().saturating_sub(self.pool.num_idle() as u32),
+            max_connections: self.max_connections(),
+            acquisitions: stats.acquisitions,
+            waits: stats.waits,
+            timeouts: stats.timeouts,
+            longest_hold,
+        }
+    }
+
+    /// Connection counts of the pool and the acquire waits seen by the monitor
+    pub fn stats(&self) -> PoolStats {
+        let stats = self.stats.lock();
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        PoolStats {
+            size,
+            active: size.saturating_sub(idle),
+            idle,
+            max_connections: self.max_connections(),
+            acquisitions: stats.acquisitions,
+            waits: stats.waits,
+            timeouts: stats.timeouts,
+            total_acquire_wait: stats.total_acquire_wait,
+            max_acquire_wait: stats.max_acquire_wait,
+        }
+    }
+
+    fn record_acquire_wait(&self, wait: Duration) {
+        let mut stats = self.stats.lock();
+        stats.total_acquire_wait += wait;
+        stats.max_acquire_wait = stats.max_acquire_wait.max(wait);
+    }
+
+    fn max_connections(&self) -> u32 {
+        self.pool.options().get_max_connections()
+    }
+}
+
+/// Connection acquired through `PoolMonitor::acquire`
+///
+/// Dereferences to the pooled connection. Dropping it returns the connection to the
+/// pool and ends the hold.
+pub struct MonitoredConnection {
+    connection: PoolConnection<Postgres>,
+    stats: Arc<Mutex<Counters>>,
+    hold: u64,
+}
+
+impl Deref for MonitoredConnection {
+    type Target = PoolConnection<Postgres>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+impl DerefMut for MonitoredConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.connection
+    }
+}
+
+impl Drop for MonitoredConnection {
+    fn drop(&mut self) {
+        self.stats.lock().holds.remove(&self.hold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoolMonitor, PoolMonitorError};
+    use crate::test_helper::database_url;
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_exhausted_pool_error_names_longest_hold() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .acquire_timeout(Duration::from_millis(300))
+            .connect(&database_url())
+            .await?;
+        let monitor = PoolMonitor::new(pool);
+
+        let mut import = monitor.acquire(Some("bulk-import")).await?;
+        sqlx::query("SELECT 1").execute(&mut **import).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let report = monitor.acquire(None).await?;
+
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.in_use, 2);
+        assert_eq!(snapshot.max_connections, 2);
+        assert_eq!(snapshot.acquisitions, 2);
+
+        let error = monitor.acquire(Some("late")).await.err().expect("The pool is saturated");
+        let PoolMonitorError::Exhausted(snapshot) = &error else {
+            panic!("Unexpected error: {error}");
+        };
+        assert_eq!(snapshot.in_use, 2);
+        assert_eq!(snapshot.acquisitions, 3);
+        assert_eq!(snapshot.waits, 1);
+        assert_eq!(snapshot.timeouts, 1);
+        let (held, tag) = snapshot.longest_hold.clone().expect("Two connections are held");
+        assert!(held >= Duration::from_millis(100));
+        assert_eq!(tag.as_deref(), Some("bulk-import"));
+
+        let message = error.to_string();
+        assert!(message.starts_with("pool exhausted: 2/2 in use, longest hold "), "{message}");
+        assert!(message.ends_with("s by session tagged 'bulk-import'"), "{message}");
+
+        // Releasing the tagged connection ends its hold
+        drop(import);
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.longest_hold.map(|(_, tag)| tag), Some(None));
+        let again = monitor.acquire(Some("retry")).await?;
+        drop((again, report));
+        assert_eq!(monitor.snapshot().longest_hold, None);
+
+        monitor.pool().close().await;
+        Ok(())
+    }
+}
@@ -16,7 +16,11 @@ impl AuditLinkRepositoryImpl {
         .bind(audit_link.entity_id)
         .bind(audit_link.entity_type);
 
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo
+            .operation_timeout
+            .lock("audit_link", &repo.executor.tx)
+            .await
+            .map_err(|e| sqlx::Error::Configuration(e.into()))?;
         if let Some(transaction) = tx.as_mut() {
             query.execute(&mut **transaction).await?;
         } else {
@@ -12,7 +12,11 @@ impl AuditLinkRepositoryImpl {
         // First, get the total count of audit links for this audit log
         let count_query = r#"SELECT COUNT(*) as count FROM audit_link WHERE audit_log_id = $1"#;
         let total: i64 = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo
+                .operation_timeout
+                .lock("audit_link", &repo.executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query_scalar(count_query)
                     .bind(audit_log_id)
@@ -37,7 +41,11 @@ impl AuditLinkRepositoryImpl {
         .bind(page.offset as i64);
 
         let items = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo
+                .operation_timeout
+                .lock("audit_link", &repo.executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -0,0 +1,188 @@
+use business_core_db::models::audit::{AuditLinkModel, EntityType};
+use sqlx::PgConnection;
+use std::collections::HashMap;
+use std::error::Error;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+
+use super::repo_impl::AuditLinkRepositoryImpl;
+
+/// Typed error of the audit link insertion
+///
+/// Raised when an entity is linked under one audit log with a second entity type,
+/// within the batch or against an already stored link.
+#[derive(Debug, Error)]
+pub enum AuditLinkError {
+    #[error("Entity {entity_id} is linked to audit log {audit_log_id} as {existing:?}, cannot link it as {conflicting:?}")]
+    ConflictingEntityType {
+        audit_log_id: Uuid,
+        entity_id: Uuid,
+        existing: EntityType,
+        conflicting: EntityType,
+    },
+}
+
+impl AuditLinkRepositoryImpl {
+    /// Insert audit links on an already locked connection
+    ///
+    /// Shared by the batch operations of all auditable repositories. A link that is
+    /// repeated, in `links` or already stored, is inserted once. Linking an entity
+    /// under one audit log with two different entity types yields
+    /// `AuditLinkError::ConflictingEntityType`.
+    pub(crate) async fn insert_in_connection(
+        conn: &mut PgConnection,
+        links: &[AuditLinkModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut distinct: HashMap<(Uuid, Uuid), EntityType> = HashMap::with_capacity(links.len());
+        let mut ordered = Vec::with_capacity(links.len());
+        for link in links {
+            match distinct.get(&(link.audit_log_id, link.entity_id)) {
+                Some(existing) if *existing != link.entity_type => {
+                    return Err(AuditLinkError::ConflictingEntityType {
+                        audit_log_id: link.audit_log_id,
+                        entity_id: link.entity_id,
+                        existing: *existing,
+                        conflicting: link.entity_type,
+                    }
+                    .into());
+                }
+                Some(_) => {}
+                None => {
+                    distinct.insert((link.audit_log_id, link.entity_id), link.entity_type);
+                    ordered.push(link);
+                }
+            }
+        }
+
+        for link in ordered {
+            let inserted: Option<EntityType> = sqlx::query_scalar(
+                r#"
+                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (audit_log_id, entity_id) DO NOTHING
+                RETURNING entity_type
+                "#,
+            )
+            .bind(link.audit_log_id)
+            .bind(link.entity_id)
+            .bind(link.entity_type)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| map_db_error("audit_link", e))?;
+            if inserted.is_some() {
+                continue;
+            }
+
+            // Already linked, only the stored entity type has to agree
+            let stored: EntityType = sqlx::query_scalar(
+                "SELECT entity_type FROM audit_link WHERE audit_log_id = $1 AND entity_id = $2",
+            )
+            .bind(link.audit_log_id)
+            .bind(link.entity_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| map_db_error("audit_link", e))?;
+            if stored != link.entity_type {
+                return Err(AuditLinkError::ConflictingEntityType {
+                    audit_log_id: link.audit_log_id,
+                    entity_id: link.entity_id,
+                    existing: stored,
+                    conflicting: link.entity_type,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditLinkError;
+    use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::{AuditLinkModel, EntityType};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::pagination::PageRequest;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_insert_in_connection_dedupes_links() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let audit_link_repo = &ctx.audit_repos().audit_link_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let link = AuditLinkModel {
+            audit_log_id: audit_log.id,
+            entity_id: Uuid::new_v4(),
+            entity_type: EntityType::Person,
+        };
+
+        {
+            let mut tx = audit_link_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[link.clone(), link.clone()]).await?;
+            // Already stored links are accepted again
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[link.clone()]).await?;
+        }
+
+        let page = audit_link_repo.find_by_audit_log_id(audit_log.id, PageRequest::new(10, 0)).await?;
+        assert_eq!(page.total, 1);
+
+        let conflicting = AuditLinkModel {
+            entity_type: EntityType::Location,
+            ..link.clone()
+        };
+        let mut tx = audit_link_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        for links in [vec![conflicting.clone()], vec![link.clone(), conflicting.clone()]] {
+            let error = AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &links)
+                .await
+                .unwrap_err();
+            match error.downcast_ref::<AuditLinkError>() {
+                Some(AuditLinkError::ConflictingEntityType { existing, conflicting, .. }) => {
+                    assert_eq!(*existing, EntityType::Person);
+                    assert_eq!(*conflicting, EntityType::Location);
+                }
+                None => panic!("Expected ConflictingEntityType, got {error}"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_accepts_stored_link() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let audit_link_repo = &ctx.audit_repos().audit_link_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Linked Person");
+
+        // The link is recorded ahead of the write it belongs to
+        audit_link_repo
+            .create(&AuditLinkModel {
+                audit_log_id: audit_log.id,
+                entity_id: person.id,
+                entity_type: EntityType::Person,
+            })
+            .await?;
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        assert_eq!(saved.len(), 1);
+
+        let page = audit_link_repo.find_by_audit_log_id(audit_log.id, PageRequest::new(10, 0)).await?;
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].entity_id, saved[0].id);
+
+        Ok(())
+    }
+}
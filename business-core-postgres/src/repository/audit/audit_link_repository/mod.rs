@@ -1,4 +1,6 @@
 pub mod create;
 pub mod find_by_audit_log_id;
+pub mod insert_in_connection;
 pub mod repo_impl;
-pub use repo_impl::*;
\ No newline at end of file
+pub use insert_in_connection::AuditLinkError;
+pub use repo_impl::*;
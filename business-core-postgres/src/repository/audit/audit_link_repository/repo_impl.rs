@@ -2,14 +2,20 @@ use business_core_db::models::audit::AuditLinkModel;
 use business_core_db::repository::pagination::{Page, PageRequest};
 use postgres_unit_of_work::Executor;
 use uuid::Uuid;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct AuditLinkRepositoryImpl {
     pub(crate) executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub(crate) operation_timeout: OperationTimeout,
 }
 
 impl AuditLinkRepositoryImpl {
     pub fn new(executor: Executor) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 
     pub async fn create(
@@ -18,7 +18,7 @@ impl AuditLogRepositoryImpl {
         .bind(audit_log.updated_by_person_id);
 
         // Execute query using the new executor structure
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("audit_log", &repo.executor.tx).await?;
         if let Some(transaction) = tx.as_mut() {
             query.execute(&mut **transaction).await?;
         } else {
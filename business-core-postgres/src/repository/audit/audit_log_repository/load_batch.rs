@@ -1,9 +1,11 @@
 use business_core_db::models::audit::AuditLogModel;
 use postgres_unit_of_work::Executor;
+use crate::repository::operation_timeout::OperationTimeout;
 use uuid::Uuid;
 
 pub async fn load_batch_impl(
     executor: &Executor,
+    operation_timeout: &OperationTimeout,
     ids: &[Uuid],
 ) -> Result<Vec<Option<AuditLogModel>>, Box<dyn std::error::Error + Send + Sync>> {
     if ids.is_empty() {
@@ -20,7 +22,7 @@ pub async fn load_batch_impl(
     .bind(ids);
 
     // Execute query using the new executor structure
-    let mut tx = executor.tx.lock().await;
+    let mut tx = operation_timeout.lock("audit_log", &executor.tx).await?;
     let rows = if let Some(transaction) = tx.as_mut() {
         query.fetch_all(&mut **transaction).await?
     } else {
@@ -6,14 +6,20 @@ use business_core_db::{
 use sqlx::Postgres;
 use uuid::Uuid;
 use postgres_unit_of_work::Executor;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct AuditLogRepositoryImpl {
     pub(crate) executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub(crate) operation_timeout: OperationTimeout,
 }
 
 impl AuditLogRepositoryImpl {
     pub fn new(executor: Executor) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 
     pub async fn create(&self, audit_log: &AuditLogModel) -> Result<AuditLogModel, Box<dyn std::error::Error + Send + Sync>> {
@@ -32,10 +38,11 @@ impl Load<Postgres, AuditLogModel> for AuditLogRepositoryImpl {
 
 #[async_trait]
 impl LoadBatch<Postgres, AuditLogModel> for AuditLogRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "audit_log", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
     ) -> Result<Vec<Option<AuditLogModel>>, Box<dyn std::error::Error + Send + Sync>> {
-        super::load_batch::load_batch_impl(&self.executor, ids).await
+        super::load_batch::load_batch_impl(&self.executor, &self.operation_timeout, ids).await
     }
 }
\ No newline at end of file
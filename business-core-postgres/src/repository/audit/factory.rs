@@ -1,5 +1,6 @@
 use std::sync::Arc;
 use postgres_unit_of_work::UnitOfWorkSession;
+use crate::repository::operation_timeout::OperationTimeout;
 use super::{
     audit_link_repository::AuditLinkRepositoryImpl,
     audit_log_repository::AuditLogRepositoryImpl,
@@ -13,22 +14,35 @@ use super::{
 #[derive(Default)]
 pub struct AuditRepoFactory {
     // Currently no caches needed for audit module
+    operation_timeout: OperationTimeout,
 }
 
 impl AuditRepoFactory {
     /// Create a new AuditRepoFactory singleton
     pub fn new() -> Arc<Self> {
-        Arc::new(Self {})
+        Self::new_with_operation_timeout(OperationTimeout::default())
+    }
+
+    /// Create a new AuditRepoFactory singleton whose repositories bound their wait for
+    /// the transaction lock with `operation_timeout`
+    pub fn new_with_operation_timeout(operation_timeout: OperationTimeout) -> Arc<Self> {
+        Arc::new(Self { operation_timeout })
     }
 
     /// Build an AuditLogRepository with the given executor
     pub fn build_audit_log_repo(&self, session: &impl UnitOfWorkSession) -> Arc<AuditLogRepositoryImpl> {
-        Arc::new(AuditLogRepositoryImpl::new(session.executor().clone()))
+        Arc::new(AuditLogRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..AuditLogRepositoryImpl::new(session.executor().clone())
+        })
     }
 
     /// Build an AuditLinkRepository with the given executor
     pub fn build_audit_link_repo(&self, session: &impl UnitOfWorkSession) -> Arc<AuditLinkRepositoryImpl> {
-        Arc::new(AuditLinkRepositoryImpl::new(session.executor().clone()))
+        Arc::new(AuditLinkRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..AuditLinkRepositoryImpl::new(session.executor().clone())
+        })
     }
 
     /// Build all audit repositories with the given executor
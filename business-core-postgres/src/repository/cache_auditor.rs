@@ -0,0 +1,356 @@
+use business_core_db::{HasPrimaryKey, IdxModelCache, Indexable};
+use parking_lot::{Mutex, RwLock as ParkingRwLock};
+use serde::Serialize;
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::error::Error;
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::repository::cache_versions::CacheVersions;
+use crate::utils::TryFromRow;
+
+/// How a spawned `CacheAuditor` samples the cache
+#[derive(Debug, Clone, Copy)]
+pub struct CacheAuditConfig {
+    /// Time between two verifications
+    pub interval: Duration,
+    /// Number of cache entries compared per verification
+    pub sample_size: usize,
+    /// Overwrite the diverged entries from SQL
+    pub repair: bool,
+}
+
+impl Default for CacheAuditConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(300),
+            sample_size: 100,
+            repair: false,
+        }
+    }
+}
+
+/// Ids of the entries held by an index cache, which `IdxModelCache` does not list
+///
+/// A bounded cache lists them through its `CacheCapacity`, a cache fed by a listener
+/// through its `NotificationCoalescer`.
+pub trait CachedIds: Send + Sync {
+    /// Ids of the entries the cache holds
+    fn cached_ids(&self) -> Vec<Uuid>;
+
+    /// Stop listing `ids`, removed from the cache
+    fn forget_ids(&self, ids: &[Uuid]);
+}
+
+/// A cached entry differing from its idx row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheMismatch {
+    pub id: Uuid,
+    /// Fields of the index model whose cached value differs from the row
+    pub fields: Vec<String>,
+}
+
+/// Outcome of one `CacheAuditor::verify`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheAuditReport {
+    /// Number of cache entries compared
+    pub checked: usize,
+    /// Entries differing from their row
+    pub mismatches: Vec<CacheMismatch>,
+    /// Entries whose row no longer exists
+    pub orphaned: Vec<Uuid>,
+    /// Mismatched and orphaned entries overwritten from SQL
+    pub repaired: usize,
+}
+
+impl CacheAuditReport {
+    /// Whether every compared entry matched its row
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty() && self.orphaned.is_empty()
+    }
+}
+
+/// Detects index cache entries diverging silently from their idx rows
+///
+/// A verification samples ids of the cache, reads their idx rows and compares each
+/// entry, field by field, with its row. An entry evicted between the sampling and the
+/// comparison is skipped. An entry whose notification is still in flight shows up as a
+/// mismatch; repairing it applies the row early.
+///
+/// With `with_versions`, a repair goes through `CacheVersions::admit` with the `version`
+/// column of the row, so it never overwrites an entry written from a newer version.
+pub struct CacheAuditor<T> {
+    /// Entity name, the idx table is `{entity}_idx`
+    entity: &'static str,
+    cache: Arc<ParkingRwLock<IdxModelCache<T>>>,
+    ids: Arc<dyn CachedIds>,
+    versions: Option<Arc<CacheVersions>>,
+    pool: PgPool,
+    last_report: Mutex<Option<CacheAuditReport>>,
+}
+
+impl<T> CacheAuditor<T>
+where
+    T: HasPrimaryKey + Indexable + Clone + Serialize + TryFromRow<PgRow> + Send + Sync + 'static,
+{
+    /// Auditor sampling the entries of `cache` among the ids listed by `ids`
+    pub fn new(
+        entity: &'static str,
+        cache: Arc<ParkingRwLock<IdxModelCache<T>>>,
+        ids: Arc<dyn CachedIds>,
+        pool: PgPool,
+    ) -> Self {
+        Self {
+            entity,
+            cache,
+            ids,
+            versions: None,
+            pool,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Check the repairs against the versions written to the cache
+    pub fn with_versions(mut self, versions: Arc<CacheVersions>) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+
+    /// Report of the latest verification, `None` before the first one
+    pub fn last_report(&self) -> Option<CacheAuditReport> {
+        self.last_report.lock().clone()
+    }
+
+    /// Compare `sample_size` random cache entries with their idx rows, on a connection
+    /// of the pool
+    ///
+    /// With `repair`, the mismatched entries are replaced by their rows and the orphaned
+    /// ones are removed.
+    pub async fn verify(&self, sample_size: usize, repair: bool) -> Result<CacheAuditReport, Box<dyn Error + Send + Sync>> {
+        let mut conn = self.pool.acquire().await?;
+        self.verify_on(&mut conn, sample_size, repair).await
+    }
+
+    /// `verify` on `conn`, e.g. the transaction of a session
+    pub async fn verify_on(
+        &self,
+        conn: &mut PgConnection,
+        sample_size: usize,
+        repair: bool,
+    ) -> Result<CacheAuditReport, Box<dyn Error + Send + Sync>> {
+        let sample = self.sample(sample_size);
+        let query = format!("SELECT * FROM {}_idx WHERE id = ANY($1)", self.entity);
+        let rows = sqlx::query(&query)
+            .bind(&sample)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| map_db_error(self.entity, e))?;
+        let mut stored = HashMap::with_capacity(rows.len());
+        for row in &rows {
+            let version = match self.versions {
+                Some(_) => row.try_get::<i64, _>("version")? as u64,
+                None => CacheVersions::INITIAL,
+            };
+            let item = T::try_from_row(row)?;
+            stored.insert(item.primary_key(), (item, version));
+        }
+
+        let mut report = CacheAuditReport::default();
+        let mut diverged = Vec::new();
+        {
+            let cache = self.cache.read();
+            for id in sample {
+                let Some(cached) = cache.get_by_primary(&id) else {
+                    continue;
+                };
+                report.checked += 1;
+                match stored.remove(&id) {
+                    Some((row, version)) => {
+                        let fields = differing_fields(&cached, &row)?;
+                        if !fields.is_empty() {
+                            report.mismatches.push(CacheMismatch { id, fields });
+                            diverged.push((id, Some(row), version));
+                        }
+                    }
+                    None => {
+                        report.orphaned.push(id);
+                        diverged.push((id, None, CacheVersions::DELETED));
+                    }
+                }
+            }
+        }
+
+        if repair && !diverged.is_empty() {
+            let mut removed = Vec::new();
+            {
+                let mut cache = self.cache.write();
+                for (id, row, version) in diverged {
+                    if !self.versions.as_ref().is_none_or(|versions| versions.admit(id, version)) {
+                        continue;
+                    }
+                    if cache.contains_primary(&id) {
+                        cache.remove(&id);
+                    }
+                    match row {
+                        Some(row) => cache.add(row),
+                        None => removed.push(id),
+                    }
+                    report.repaired += 1;
+                }
+            }
+            self.ids.forget_ids(&removed);
+        }
+
+        #[cfg(feature = "tracing")]
+        if !report.is_consistent() {
+            tracing::warn!(
+                entity = self.entity,
+                mismatches = report.mismatches.len(),
+                orphaned = report.orphaned.len(),
+                repaired = report.repaired,
+                "Index cache diverged from SQL"
+            );
+        }
+        *self.last_report.lock() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Up to `sample_size` ids of the cache, picked at random
+    fn sample(&self, sample_size: usize) -> Vec<Uuid> {
+        let mut ids = self.ids.cached_ids();
+        if ids.len() > sample_size {
+            let order = RandomState::new();
+            ids.sort_by_cached_key(|id| order.hash_one(id));
+            ids.truncate(sample_size);
+        }
+        ids
+    }
+
+    /// Call `verify` every `config.interval` until the returned task is aborted
+    ///
+    /// A failed verification is skipped, the next tick tries again.
+    pub fn spawn(self: &Arc<Self>, config: CacheAuditConfig) -> JoinHandle<()> {
+        let auditor = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.interval);
+            loop {
+                interval.tick().await;
+                if let Err(_error) = auditor.verify(config.sample_size, config.repair).await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(entity = auditor.entity, error = %_error, "Index cache audit failed");
+                }
+            }
+        })
+    }
+}
+
+/// Names of the fields of `cached` and `stored` with different values
+fn differing_fields<T: Serialize>(cached: &T, stored: &T) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    let as_fields = |item: &T| -> Result<HashMap<String, serde_json::Value>, Box<dyn Error + Send + Sync>> {
+        match serde_json::to_value(item)? {
+            serde_json::Value::Object(fields) => Ok(fields.into_iter().collect()),
+            other => Err(format!("Index model serialized to {other} instead of an object").into()),
+        }
+    };
+    let (cached, stored) = (as_fields(cached)?, as_fields(stored)?);
+    let mut fields: Vec<String> = stored
+        .iter()
+        .filter(|(field, value)| cached.get(*field) != Some(*value))
+        .map(|(field, _)| field.clone())
+        .collect();
+    fields.sort();
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CacheAuditor;
+    use crate::repository::cache_capacity::CacheCapacity;
+    use crate::repository::cache_versions::CacheVersions;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonIdxModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_verify_detects_and_repairs_corrupted_entry() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let persons = person_repo
+            .create_batch(
+                vec![create_test_person("Audited Person"), create_test_person("Evicted Person")],
+                Some(audit_log.id),
+            )
+            .await?;
+        let stored = persons[0].to_index_with_hash_version(person_repo.hash_version);
+        let evicted_id = persons[1].id;
+
+        // The session does not commit, so its rows only reach the shared cache by hand.
+        // The cache also holds an entry without a row, and the capacity still lists an
+        // entry the cache no longer holds.
+        let shared_cache = person_repo.person_idx_shared_cache.clone();
+        let corrupted = PersonIdxModel {
+            location_id: Some(Uuid::new_v4()),
+            ..stored.clone()
+        };
+        let orphan = PersonIdxModel {
+            id: Uuid::new_v4(),
+            ..stored.clone()
+        };
+        shared_cache.write().add(corrupted);
+        shared_cache.write().add(orphan.clone());
+        let capacity = Arc::new(CacheCapacity::with_capacity_limit(10));
+        capacity.touch(&[stored.id, orphan.id, evicted_id]);
+        let versions = Arc::new(CacheVersions::new());
+
+        let auditor = CacheAuditor::new("person", shared_cache.clone(), capacity.clone(), ctx.pool().as_ref().clone())
+            .with_versions(versions.clone());
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+
+        let report = auditor.verify_on(&mut **transaction, 10, false).await?;
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].id, stored.id);
+        assert_eq!(report.mismatches[0].fields, vec!["location_id".to_string()]);
+        assert_eq!(report.orphaned, vec![orphan.id]);
+        assert_eq!(report.repaired, 0);
+        assert_ne!(shared_cache.read().get_by_primary(&stored.id), Some(stored.clone()));
+
+        // An entry written from a newer version than the row read is left alone
+        versions.admit(stored.id, 2);
+        let report = auditor.verify_on(&mut **transaction, 10, true).await?;
+        assert_eq!(report.repaired, 1);
+        assert_ne!(shared_cache.read().get_by_primary(&stored.id), Some(stored.clone()));
+        assert!(!shared_cache.read().contains_primary(&orphan.id));
+        assert!(!capacity.contains(&orphan.id));
+        assert_eq!(versions.version(orphan.id), Some(CacheVersions::DELETED));
+
+        versions.forget(&[stored.id]);
+        let report = auditor.verify_on(&mut **transaction, 10, true).await?;
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.repaired, 1);
+        assert_eq!(shared_cache.read().get_by_primary(&stored.id), Some(stored.clone()));
+        assert_eq!(versions.version(stored.id), Some(CacheVersions::INITIAL));
+        assert_eq!(auditor.last_report(), Some(report));
+
+        // Nothing left to repair
+        let report = auditor.verify_on(&mut **transaction, 10, false).await?;
+        assert!(report.is_consistent());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,557 @@
+use business_core_db::{HasPrimaryKey, IdxModelCache, Indexable};
+use parking_lot::{Mutex, RwLock as ParkingRwLock};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::repository::cache_auditor::CachedIds;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_versions::CacheVersions;
+
+/// Value of an index key, as looked up by a finder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyValue {
+    I64(i64),
+    Uuid(Uuid),
+}
+
+impl From<i64> for KeyValue {
+    fn from(value: i64) -> Self {
+        KeyValue::I64(value)
+    }
+}
+
+impl From<Uuid> for KeyValue {
+    fn from(value: Uuid) -> Self {
+        KeyValue::Uuid(value)
+    }
+}
+
+/// The keys of `item` with a value
+pub fn key_values<T: Indexable>(item: &T) -> Vec<(String, KeyValue)> {
+    let i64_keys = item
+        .i64_keys()
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, KeyValue::I64(value?))));
+    let uuid_keys = item
+        .uuid_keys()
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, KeyValue::Uuid(value?))));
+    i64_keys.chain(uuid_keys).collect()
+}
+
+/// Least recently used bound on the entries of an index cache
+///
+/// For idx tables too large to cache whole: the hot entries stay, the cold ones are
+/// dropped and read again from the idx table on a miss. Caches built without a
+/// capacity, such as the small reference tables, stay unbounded.
+///
+/// `IdxModelCache` has no capacity of its own. The repositories built with a capacity
+/// record the ids they add to or read from the cache in their `CacheBound`, which
+/// touches them here once the session commits and removes the least recently used
+/// entries that no longer fit from the cache, and with them from its secondary key maps.
+///
+/// A bounded cache cannot tell a missing entry from a missing row, so its finders go
+/// to SQL, except for the key values known to be complete: all rows of the value were
+/// loaded, and none was evicted or inserted by another session since.
+///
+/// Shared by all repositories a factory builds on the same cache, and by the coalescer
+/// of its notifications.
+#[derive(Debug)]
+pub struct CacheCapacity {
+    limit: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    tick: u64,
+    last_used: HashMap<Uuid, u64>,
+    by_last_used: BTreeMap<u64, Uuid>,
+    /// Key values whose rows are all in the cache
+    complete: HashSet<(String, KeyValue)>,
+    /// Advanced by every change that can make a key value incomplete
+    epoch: u64,
+}
+
+impl CacheCapacity {
+    /// Keep at most `limit` entries
+    pub fn with_capacity_limit(limit: usize) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Capacity of a cache used under `policy` and keeping at most `limit` entries
+    ///
+    /// `None` when `limit` is `None` or the policy leaves the cache alone.
+    pub fn for_policy(policy: CachePolicy, limit: Option<usize>) -> Option<Arc<Self>> {
+        limit
+            .filter(|_| policy.maintains_cache())
+            .map(|limit| Arc::new(Self::with_capacity_limit(limit)))
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Number of entries currently tracked
+    pub fn len(&self) -> usize {
+        self.state.lock().last_used.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Ids of the tracked entries, least recently used first
+    pub fn ids(&self) -> Vec<Uuid> {
+        self.state.lock().by_last_used.values().copied().collect()
+    }
+
+    /// Whether `id` is tracked, i.e. its entry is in the cache
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.state.lock().last_used.contains_key(id)
+    }
+
+    /// Mark `ids` as most recently used, in order, and return the least recently used
+    /// ids that no longer fit
+    ///
+    /// The returned ids are forgotten; the caller removes them from the cache.
+    pub fn touch(&self, ids: &[Uuid]) -> Vec<Uuid> {
+        let mut state = self.state.lock();
+        for &id in ids {
+            state.tick += 1;
+            let tick = state.tick;
+            if let Some(previous) = state.last_used.insert(id, tick) {
+                state.by_last_used.remove(&previous);
+            }
+            state.by_last_used.insert(tick, id);
+        }
+
+        let mut evicted = Vec::new();
+        while state.last_used.len() > self.limit {
+            let Some((_, id)) = state.by_last_used.pop_first() else {
+                break;
+            };
+            state.last_used.remove(&id);
+            evicted.push(id);
+        }
+        evicted
+    }
+
+    /// `touch` the entries of `ids`, then remove the evicted ones from `cache`
+    ///
+    /// The key values of the evicted entries are no longer complete.
+    pub fn touch_in<T>(&self, cache: &ParkingRwLock<IdxModelCache<T>>, ids: &[Uuid]) -> Vec<Uuid>
+    where
+        T: HasPrimaryKey + Indexable + Clone + Send + Sync + 'static,
+    {
+        let evicted = self.touch(ids);
+        if evicted.is_empty() {
+            return evicted;
+        }
+        let mut cache = cache.write();
+        for id in &evicted {
+            if let Some(entry) = cache.get_by_primary(id) {
+                self.invalidate_keys(key_values(&entry));
+            }
+            cache.remove(id);
+        }
+        evicted
+    }
+
+    /// Stop tracking every entry, for a cache cleared as a whole
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.last_used.clear();
+        state.by_last_used.clear();
+        state.complete.clear();
+        state.epoch += 1;
+    }
+
+    /// Stop tracking `ids`, for entries removed from the cache for another reason
+    pub fn forget(&self, ids: &[Uuid]) {
+        let mut state = self.state.lock();
+        for id in ids {
+            if let Some(tick) = state.last_used.remove(id) {
+                state.by_last_used.remove(&tick);
+            }
+        }
+    }
+
+    /// Whether all rows of `key` = `value` are in the cache
+    pub fn is_complete(&self, key: &str, value: KeyValue) -> bool {
+        self.state.lock().complete.contains(&(key.to_string(), value))
+    }
+
+    /// Current epoch, to pass to `mark_complete` for the rows read from now on
+    pub fn epoch(&self) -> u64 {
+        self.state.lock().epoch
+    }
+
+    /// Record that all rows of `key` = `value`, read at `epoch`, are in the cache
+    ///
+    /// Ignored when a key value was invalidated since `epoch`, the rows read may miss it.
+    pub fn mark_complete(&self, key: &str, value: KeyValue, epoch: u64) {
+        let mut state = self.state.lock();
+        if state.epoch == epoch {
+            state.complete.insert((key.to_string(), value));
+        }
+    }
+
+    /// Record that some rows of the key values `keys` may be missing from the cache
+    pub fn invalidate_keys(&self, keys: impl IntoIterator<Item = (String, KeyValue)>) {
+        let mut state = self.state.lock();
+        for key in keys {
+            state.complete.remove(&key);
+        }
+        state.epoch += 1;
+    }
+
+    /// Record that any key value may be missing rows, e.g. after entries were evicted
+    /// without their keys
+    pub fn invalidate_all_keys(&self) {
+        let mut state = self.state.lock();
+        state.complete.clear();
+        state.epoch += 1;
+    }
+}
+
+impl CachedIds for CacheCapacity {
+    fn cached_ids(&self) -> Vec<Uuid> {
+        self.ids()
+    }
+
+    fn forget_ids(&self, ids: &[Uuid]) {
+        self.forget(ids)
+    }
+}
+
+/// Uses of a bounded cache by the repository of one session
+///
+/// The ids the repository adds to or reads from its cache, and the key values it reads
+/// completely from SQL, are held until the session ends: `on_commit` applies them to
+/// the shared `CacheCapacity`, `on_rollback` drops them along with the entries the
+/// rollback discards, so the LRU tracker never counts an entry the cache does not hold.
+#[derive(Debug)]
+pub struct CacheBound {
+    capacity: Arc<CacheCapacity>,
+    pending: Mutex<PendingUse>,
+}
+
+#[derive(Debug, Default)]
+struct PendingUse {
+    used: Vec<Uuid>,
+    removed: Vec<Uuid>,
+    complete: Vec<(String, KeyValue, u64)>,
+}
+
+impl CacheBound {
+    pub fn new(capacity: Arc<CacheCapacity>) -> Self {
+        Self {
+            capacity,
+            pending: Mutex::new(PendingUse::default()),
+        }
+    }
+
+    pub fn capacity(&self) -> &Arc<CacheCapacity> {
+        &self.capacity
+    }
+
+    /// Record the use of the entries of `ids`
+    pub fn record_use(&self, ids: &[Uuid]) {
+        self.pending.lock().used.extend_from_slice(ids);
+    }
+
+    /// Record the removal of the entries of `ids`, e.g. of deleted rows
+    pub fn record_removal(&self, ids: &[Uuid]) {
+        self.pending.lock().removed.extend_from_slice(ids);
+    }
+
+    /// Epoch of the shared capacity, read before the rows passed to `record_complete`
+    pub fn epoch(&self) -> u64 {
+        self.capacity.epoch()
+    }
+
+    /// Record that all rows of `key` = `value`, read from SQL at `epoch`, were added
+    /// to the cache
+    pub fn record_complete(&self, key: &str, value: KeyValue, epoch: u64) {
+        self.pending.lock().complete.push((key.to_string(), value, epoch));
+    }
+
+    /// Whether the finders of `key` = `value` can be answered from the cache
+    pub fn is_complete(&self, key: &str, value: KeyValue) -> bool {
+        self.capacity.is_complete(key, value)
+    }
+
+    /// Apply the recorded uses to the shared `cache`, once the session committed
+    ///
+    /// The evicted entries are forgotten by `versions`.
+    pub fn on_commit<T>(&self, cache: &ParkingRwLock<IdxModelCache<T>>, versions: &CacheVersions)
+    where
+        T: HasPrimaryKey + Indexable + Clone + Send + Sync + 'static,
+    {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        for (key, value, epoch) in pending.complete {
+            self.capacity.mark_complete(&key, value, epoch);
+        }
+        let evicted = self.capacity.touch_in(cache, &pending.used);
+        versions.forget(&evicted);
+        self.capacity.forget(&pending.removed);
+    }
+
+    /// Drop the recorded uses, the entries they name are discarded with the session
+    pub fn on_rollback(&self) {
+        *self.pending.lock() = PendingUse::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheBound, CacheCapacity, KeyValue};
+    use crate::repository::find_by_i64_key::FindByI64Key;
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonIdxModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::exist_by_ids::ExistByIds;
+    use business_core_db::IdxModelCache;
+    use business_core_db::repository::pagination::PageRequest;
+    use parking_lot::RwLock as ParkingRwLock;
+    use postgres_unit_of_work::TransactionAware;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_touch_evicts_least_recently_used() {
+        let capacity = CacheCapacity::with_capacity_limit(3);
+        let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+
+        assert!(capacity.touch(&ids[..3]).is_empty());
+        // Reading the first id makes the second the least recently used
+        assert!(capacity.touch(&ids[..1]).is_empty());
+        assert_eq!(capacity.touch(&ids[3..]), vec![ids[1], ids[2]]);
+        assert_eq!(capacity.len(), 3);
+    }
+
+    #[test]
+    fn test_forget() {
+        let capacity = CacheCapacity::with_capacity_limit(2);
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        capacity.touch(&ids[..2]);
+        capacity.forget(&ids[..1]);
+        assert!(capacity.touch(&ids[2..]).is_empty());
+        assert_eq!(capacity.len(), 2);
+    }
+
+    #[test]
+    fn test_key_read_before_an_invalidation_is_not_complete() {
+        let capacity = CacheCapacity::with_capacity_limit(2);
+        let key = KeyValue::Uuid(Uuid::new_v4());
+
+        let epoch = capacity.epoch();
+        capacity.invalidate_keys(vec![("organization_person_id".to_string(), KeyValue::Uuid(Uuid::new_v4()))]);
+        capacity.mark_complete("organization_person_id", key, epoch);
+        assert!(!capacity.is_complete("organization_person_id", key));
+
+        capacity.mark_complete("organization_person_id", key, capacity.epoch());
+        assert!(capacity.is_complete("organization_person_id", key));
+        capacity.invalidate_all_keys();
+        assert!(!capacity.is_complete("organization_person_id", key));
+    }
+
+    /// Ids of the cached members of an organization, from the secondary key map
+    fn members_of(cache: &IdxModelCache<PersonIdxModel>, organization_id: Uuid) -> HashSet<Uuid> {
+        cache
+            .get_by_uuid_index("organization_person_id", &organization_id)
+            .iter()
+            .map(|idx| idx.id)
+            .collect()
+    }
+
+    fn bounded_person_repo(executor: postgres_unit_of_work::Executor, limit: usize) -> PersonRepositoryImpl {
+        PersonRepositoryImpl {
+            cache_bound: Some(CacheBound::new(Arc::new(CacheCapacity::with_capacity_limit(limit)))),
+            ..PersonRepositoryImpl::new(
+                executor,
+                Arc::new(ParkingRwLock::new(IdxModelCache::new(vec![]).unwrap())),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capacity_two_evicts_the_least_recently_used_entry() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = bounded_person_repo(ctx.person_repos().person_repository.executor.clone(), 2);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // The organization goes through the unbounded repository, only its members count
+        let organization = create_test_person("lru-organization");
+        let organization_id = organization.id;
+        ctx.person_repos()
+            .person_repository
+            .create_batch(vec![organization], Some(audit_log.id))
+            .await?;
+        let member = |name: &str| {
+            let mut person = create_test_person(name);
+            person.organization_person_id = Some(organization_id);
+            person
+        };
+        let members = vec![member("lru-0"), member("lru-1"), member("lru-2")];
+        let ids: Vec<Uuid> = members.iter().map(|person| person.id).collect();
+        let mut members = members.into_iter();
+
+        person_repo.create_batch(members.by_ref().take(2).collect(), Some(audit_log.id)).await?;
+        person_repo.on_commit().await?;
+        // Reading the first entry leaves the second least recently used
+        assert_eq!(person_repo.exist_by_ids(&ids[..1]).await?, vec![(ids[0], true)]);
+        person_repo.on_commit().await?;
+        person_repo.create_batch(members.collect(), Some(audit_log.id)).await?;
+        person_repo.on_commit().await?;
+
+        {
+            let cache = person_repo.person_idx_shared_cache.read();
+            assert!(cache.contains_primary(&ids[0]));
+            assert!(!cache.contains_primary(&ids[1]));
+            assert!(cache.contains_primary(&ids[2]));
+            // The secondary key map dropped the evicted entry with it
+            assert_eq!(members_of(&cache, organization_id), HashSet::from([ids[0], ids[2]]));
+        }
+
+        // The evicted entry is read back from the database on a miss, evicting the next one
+        assert_eq!(person_repo.exist_by_ids(&ids[1..2]).await?, vec![(ids[1], true)]);
+        person_repo.on_commit().await?;
+        let cache = person_repo.person_idx_shared_cache.read();
+        assert!(cache.contains_primary(&ids[1]));
+        assert!(!cache.contains_primary(&ids[0]));
+        assert_eq!(members_of(&cache, organization_id), HashSet::from([ids[1], ids[2]]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bounded_person_cache_falls_back_to_sql() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = bounded_person_repo(ctx.person_repos().person_repository.executor.clone(), 3);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let organization = create_test_person("bounded-cache-organization");
+        let organization_id = organization.id;
+        person_repo.create_batch(vec![organization], Some(audit_log.id)).await?;
+
+        let members: Vec<_> = (0..5)
+            .map(|i| {
+                let mut person = create_test_person(&format!("bounded-cache-{i}"));
+                person.organization_person_id = Some(organization_id);
+                person
+            })
+            .collect();
+        let ids: Vec<Uuid> = members.iter().map(|person| person.id).collect();
+        person_repo.create_batch(members, Some(audit_log.id)).await?;
+        // The session never commits, apply its writes to the shared cache by hand
+        person_repo.on_commit().await?;
+
+        // Only the 3 most recently written persons remain, the organization went first
+        {
+            let cache = person_repo.person_idx_shared_cache.read();
+            assert!(!cache.contains_primary(&organization_id));
+            let cached: Vec<bool> = ids.iter().map(|id| cache.contains_primary(id)).collect();
+            assert_eq!(cached, vec![false, false, true, true, true]);
+            // Evicted entries are gone from the secondary key maps too
+            assert_eq!(cache.get_by_uuid_index("organization_person_id", &organization_id).len(), 3);
+        }
+
+        // The finder goes to SQL and still returns all 5
+        let organization_key = KeyValue::Uuid(organization_id);
+        assert!(!person_repo.serves_key_from_cache("organization_person_id", organization_key));
+        let page = person_repo
+            .find_by_organization_person_id(organization_id, PageRequest::new(10, 0))
+            .await?;
+        assert_eq!(page.total, 5);
+        assert!(ids.iter().all(|id| page.items.iter().any(|idx| idx.id == *id)));
+
+        // The 5 rows do not fit, so the organization does not become complete
+        person_repo.on_commit().await?;
+        let cache = person_repo.person_idx_shared_cache.read();
+        assert_eq!(ids.iter().filter(|id| cache.contains_primary(id)).count(), 3);
+        assert!(!person_repo.serves_key_from_cache("organization_person_id", organization_key));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bounded_person_cache_serves_complete_keys() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = bounded_person_repo(ctx.person_repos().person_repository.executor.clone(), 3);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let organization = create_test_person("complete-key-organization");
+        let organization_id = organization.id;
+        let member = |name: &str| {
+            let mut person = create_test_person(name);
+            person.organization_person_id = Some(organization_id);
+            person
+        };
+        person_repo
+            .create_batch(vec![organization, member("complete-key-0"), member("complete-key-1")], Some(audit_log.id))
+            .await?;
+        person_repo.on_commit().await?;
+
+        // A first lookup reads the organization from SQL, then the cache holds all its rows
+        let organization_key = KeyValue::Uuid(organization_id);
+        assert!(!person_repo.serves_key_from_cache("organization_person_id", organization_key));
+        let page = person_repo
+            .find_by_organization_person_id(organization_id, PageRequest::new(10, 0))
+            .await?;
+        assert_eq!(page.total, 2);
+        person_repo.on_commit().await?;
+        assert!(person_repo.serves_key_from_cache("organization_person_id", organization_key));
+
+        // A write the session rolls back is never counted
+        let bound = person_repo.cache_bound.as_ref().ok_or("The repository must be bounded")?;
+        let discarded = create_test_person("complete-key-discarded");
+        let discarded_id = discarded.id;
+        person_repo.create_batch(vec![discarded], Some(audit_log.id)).await?;
+        person_repo.on_rollback().await?;
+        assert_eq!(bound.capacity().len(), 3);
+        assert!(!bound.capacity().contains(&discarded_id));
+        assert!(person_repo.serves_key_from_cache("organization_person_id", organization_key));
+
+        // A third member evicts the organization entry, the key stays complete
+        person_repo.create_batch(vec![member("complete-key-2")], Some(audit_log.id)).await?;
+        person_repo.on_commit().await?;
+        assert!(!person_repo.person_idx_shared_cache.read().contains_primary(&organization_id));
+        assert!(person_repo.serves_key_from_cache("organization_person_id", organization_key));
+        let page = person_repo
+            .find_by_organization_person_id(organization_id, PageRequest::new(10, 0))
+            .await?;
+        assert_eq!(page.total, 3);
+
+        // Evicting a member does not, the finder goes back to SQL
+        person_repo
+            .create_batch(vec![create_test_person("complete-key-outsider")], Some(audit_log.id))
+            .await?;
+        person_repo.on_commit().await?;
+        assert!(!person_repo.serves_key_from_cache("organization_person_id", organization_key));
+        let page = person_repo
+            .find_by_organization_person_id(organization_id, PageRequest::new(10, 0))
+            .await?;
+        assert_eq!(page.total, 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+use parking_lot::RwLock;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::repository::idx_notification_listener::IdxNotificationHandler;
+
+/// Health of the notification feed that keeps the index caches in sync
+///
+/// A handle owned by the `IdxNotificationListener`, shared by the factories with the
+/// repositories they build. `IdxNotificationListener::supervise` marks it degraded when
+/// the listener stops, and healthy once it listens again and the caches were reloaded.
+/// While degraded, the caches may miss changes made by other sessions. Clones share the
+/// same state.
+///
+/// `report` adds the state of each cache whose handler was registered with the listener.
+#[derive(Clone, Default)]
+pub struct CacheHealth {
+    degraded: Arc<RwLock<Option<Degradation>>>,
+    handlers: Arc<RwLock<Vec<Arc<dyn IdxNotificationHandler>>>>,
+}
+
+impl fmt::Debug for CacheHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheHealth")
+            .field("degraded", &self.degraded)
+            .field("caches", &self.handlers.read().len())
+            .finish()
+    }
+}
+
+/// State of one index cache, see `CacheHealth::report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheStatus {
+    /// Idx table of the cache
+    pub table: String,
+    /// Number of entries, as of the notifications applied
+    pub entries: usize,
+    /// When a notification was last applied to the cache, `None` before the first one
+    pub last_applied: Option<Instant>,
+}
+
+/// Readiness of the index caches, see `CacheHealth::report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheHealthReport {
+    /// Whether the listener receives the notifications
+    pub listener_connected: bool,
+    /// How long the caches have been possibly stale, `None` while connected
+    pub staleness: Option<Duration>,
+    /// Error that disconnected the listener, `None` while connected
+    pub last_error: Option<String>,
+    /// State of each cache, ordered by table
+    pub caches: Vec<CacheStatus>,
+}
+
+#[derive(Debug)]
+struct Degradation {
+    since: Instant,
+    last_error: String,
+}
+
+impl CacheHealth {
+    /// Record that notifications stopped arriving because of `error`
+    ///
+    /// Staleness is counted from the first call, later calls only replace the error.
+    pub fn mark_degraded(&self, error: impl Into<String>) {
+        let mut degraded = self.degraded.write();
+        match degraded.as_mut() {
+            Some(degradation) => degradation.last_error = error.into(),
+            None => {
+                *degraded = Some(Degradation {
+                    since: Instant::now(),
+                    last_error: error.into(),
+                })
+            }
+        }
+    }
+
+    /// Record that notifications arrive again
+    pub fn mark_healthy(&self) {
+        *self.degraded.write() = None;
+    }
+
+    /// Whether the caches may be missing changes
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.read().is_some()
+    }
+
+    /// How long the caches have been possibly stale, `None` when healthy
+    pub fn staleness(&self) -> Option<Duration> {
+        self.degraded.read().as_ref().map(|degradation| degradation.since.elapsed())
+    }
+
+    /// Error that degraded the caches, `None` when healthy
+    pub fn last_error(&self) -> Option<String> {
+        self.degraded.read().as_ref().map(|degradation| degradation.last_error.clone())
+    }
+
+    /// Report the state of the cache fed by `handler`
+    pub fn track(&self, handler: Arc<dyn IdxNotificationHandler>) {
+        self.handlers.write().push(handler);
+    }
+
+    /// State of the listener and of every tracked cache
+    pub fn report(&self) -> CacheHealthReport {
+        let mut caches: Vec<CacheStatus> = self
+            .handlers
+            .read()
+            .iter()
+            .filter_map(|handler| handler.cache_status())
+            .collect();
+        caches.sort_by(|a, b| a.table.cmp(&b.table));
+        CacheHealthReport {
+            listener_connected: !self.is_degraded(),
+            staleness: self.staleness(),
+            last_error: self.last_error(),
+            caches,
+        }
+    }
+}
+
+/// How fresh the answer of a `find_by_*_hash` finder must be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Freshness {
+    /// Answer as the cache policy says, even from a possibly stale cache
+    #[default]
+    CacheOk,
+    /// Go to SQL while the `CacheHealth` of the repository is degraded, otherwise as `CacheOk`
+    RequireFresh,
+    /// Answer from the cache only, never go to SQL
+    CacheOnly,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CacheHealth;
+    use crate::repository::idx_notification_listener::IdxNotificationListener;
+    use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+    use crate::test_helper::setup_test_context_and_listen;
+    use business_core_db::models::person::locality::LocalityIdxModel;
+    use business_core_db::IdxModelCache;
+    use parking_lot::RwLock as ParkingRwLock;
+    use serde_json::json;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_degraded_until_marked_healthy() {
+        let health = CacheHealth::default();
+        assert!(!health.is_degraded());
+        assert_eq!(health.staleness(), None);
+        assert_eq!(health.last_error(), None);
+
+        // Clones share the state, as the factories hand the handle to their repositories
+        let repo_health = health.clone();
+        health.mark_degraded("connection reset");
+        let staleness = repo_health.staleness().unwrap();
+        health.mark_degraded("connection refused");
+        assert!(repo_health.is_degraded());
+        assert_eq!(repo_health.last_error().as_deref(), Some("connection refused"));
+        assert!(repo_health.staleness().unwrap() >= staleness);
+
+        health.mark_healthy();
+        assert!(!repo_health.is_degraded());
+        assert_eq!(repo_health.staleness(), None);
+        assert_eq!(repo_health.last_error(), None);
+    }
+
+    #[test]
+    fn test_report_counts_the_entries_of_the_applied_notifications() {
+        let cache = Arc::new(ParkingRwLock::new(IdxModelCache::<LocalityIdxModel>::new(vec![]).unwrap()));
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(Arc::new(NotificationCoalescer::new(
+            "locality_idx",
+            cache,
+            CoalescingConfig::default(),
+        )));
+        let health = listener.cache_health();
+
+        let report = health.report();
+        assert!(!report.listener_connected, "Disconnected until the listener listens");
+        assert_eq!(report.caches.len(), 1);
+        assert_eq!((report.caches[0].entries, report.caches[0].last_applied), (0, None));
+
+        let subdivision = Uuid::new_v4();
+        for _ in 0..2 {
+            let row = json!({ "id": Uuid::new_v4(), "country_subdivision_id": subdivision, "code_hash": 1 });
+            let payload = json!({ "table": "locality_idx", "op": "INSERT", "row": row });
+            listener.dispatch(&payload.to_string()).unwrap();
+        }
+        listener.flush();
+
+        let status = &health.report().caches[0];
+        assert_eq!(status.table, "locality_idx");
+        assert_eq!(status.entries, 2);
+        assert!(status.last_applied.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_report_shows_a_stopped_listener_disconnected() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut ctx = setup_test_context_and_listen().await?;
+        let health = ctx.person_repos().person_repository.cache_health.clone();
+
+        let report = health.report();
+        assert!(report.listener_connected, "{report:?}");
+        assert_eq!(report.staleness, None);
+        let tables: Vec<&str> = report.caches.iter().map(|status| status.table.as_str()).collect();
+        assert!(tables.contains(&"person_idx") && tables.contains(&"reason_idx"), "{tables:?}");
+
+        ctx.stop_listeners().await;
+
+        let report = health.report();
+        assert!(!report.listener_connected);
+        assert!(report.staleness.is_some());
+        assert_eq!(report.last_error.as_deref(), Some("idx cache notification listener stopped"));
+
+        Ok(())
+    }
+}
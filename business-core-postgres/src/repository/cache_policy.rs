@@ -0,0 +1,206 @@
+/// Controls how a repository uses its index cache
+///
+/// The policy is chosen per repository when building the repository factory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Finders are served from the cache, writes maintain it and the cache
+    /// is kept in sync through database notifications
+    #[default]
+    Enabled,
+    /// The cache is never read nor written, finders and existence checks go straight to SQL
+    Disabled,
+    /// Finders go to SQL and add the loaded index models to the cache, existence checks
+    /// use the cache and fall back to SQL on a miss. Writes maintain the cache but no
+    /// notification handler is registered
+    ReadThrough,
+}
+
+impl CachePolicy {
+    /// Whether finders can be answered from the cache alone
+    pub fn serves_from_cache(&self) -> bool {
+        matches!(self, CachePolicy::Enabled)
+    }
+
+    /// Whether the repository keeps the cache up to date on reads and writes
+    pub fn maintains_cache(&self) -> bool {
+        !matches!(self, CachePolicy::Disabled)
+    }
+
+    /// Whether the factory registers a notification handler for the cache
+    pub fn registers_notifications(&self) -> bool {
+        matches!(self, CachePolicy::Enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachePolicy;
+    use crate::repository::person::{CountryRepositoryImpl, EntityReferenceRepositoryImpl, PersonRepositoryImpl};
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_country, create_test_entity_reference, create_test_person,
+    };
+    use crate::repository::reason_and_purpose::ReasonRepositoryImpl;
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::country::CountryIdxModel;
+    use business_core_db::models::IndexAware;
+    use business_core_db::repository::count_by_key::CountByKey;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::exist_by_ids::ExistByIds;
+    use business_core_db::utils::hash_as_i64;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_disabled_policy_does_not_touch_cache() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let executor = ctx.person_repos().person_repository.executor.clone();
+
+        let shared_cache = Arc::new(ParkingRwLock::new(
+            business_core_db::IdxModelCache::new(vec![]).unwrap()
+        ));
+        let person_repo = PersonRepositoryImpl::new_with_cache_policy(
+            executor,
+            shared_cache.clone(),
+            CachePolicy::Disabled,
+        );
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let mut person = create_test_person("cache-disabled");
+        person.external_identifier = Some(heapless::String::try_from("CACHE-DISABLED-1").unwrap());
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let person_id = saved[0].id;
+
+        // Nothing was written to the cache
+        assert!(!person_repo.person_idx_cache.read().await.contains_primary(&person_id));
+
+        // Reads go to SQL
+        let exists = person_repo.exist_by_ids(&[person_id]).await?;
+        assert_eq!(exists, vec![(person_id, true)]);
+        let hash = hash_as_i64(&"CACHE-DISABLED-1").unwrap();
+        let found = person_repo.find_by_external_identifier_hash(hash).await?;
+        assert!(found.iter().any(|idx| idx.id == person_id));
+
+        // ... and still leave the cache untouched
+        assert!(!person_repo.person_idx_cache.read().await.contains_primary(&person_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_through_policy_populates_cache_lazily() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        // Create the reason through a repository whose cache is disabled
+        let writer = ReasonRepositoryImpl::new_with_cache_policy(
+            reason_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+        let saved = writer.create_batch(vec![create_test_reason("READ_THROUGH_TEST", "Read through")], None).await?;
+        let reason_id = saved[0].id;
+
+        let reader = ReasonRepositoryImpl::new_with_cache_policy(
+            reason_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::ReadThrough,
+        );
+        assert!(!reader.reason_idx_cache.read().await.contains_primary(&reason_id));
+
+        let code_hash = hash_as_i64(&"READ_THROUGH_TEST").unwrap();
+        let found = reader.find_by_code_hash(code_hash).await?;
+        assert!(found.iter().any(|idx| idx.id == reason_id));
+
+        // The entry was loaded into the cache on read
+        assert!(reader.reason_idx_cache.read().await.contains_primary(&reason_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_disabled_policy_applies_to_country_and_entity_reference() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+        let executor = person_repo.executor.clone();
+
+        let country_repo = CountryRepositoryImpl::new_with_cache_policy(
+            executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+        let saved = country_repo.create_batch(vec![create_test_country("QD", "Uncached Country")], None).await?;
+        let country_idx = saved[0].to_index();
+        assert!(!country_repo.country_idx_cache.read().await.contains_primary(&country_idx.id));
+        assert_eq!(country_repo.exist_by_ids(&[country_idx.id]).await?, vec![(country_idx.id, true)]);
+        let found = country_repo.find_by_iso2_hash(country_idx.iso2_hash).await?;
+        assert!(found.iter().any(|idx| idx.id == country_idx.id));
+        assert!(!country_repo.country_idx_cache.read().await.contains_primary(&country_idx.id));
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Uncached References");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let uncached_repo = EntityReferenceRepositoryImpl::new_with_cache_policy(
+            executor,
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            entity_reference_repo.clock.clone(),
+            CachePolicy::Disabled,
+        );
+        let reference = create_test_entity_reference(person_id, "UNCACHED-REF-1");
+        let reference_id = reference.id;
+        uncached_repo.create_batch(vec![reference], Some(audit_log.id)).await?;
+
+        assert!(!uncached_repo.entity_reference_idx_cache.read().await.contains_primary(&reference_id));
+        assert_eq!(uncached_repo.find_ids_by_person_id(person_id).await?, vec![reference_id]);
+        assert_eq!(uncached_repo.count_by_uuid_key("person_id", person_id).await?, 1);
+        assert_eq!(uncached_repo.exist_by_ids(&[reference_id]).await?, vec![(reference_id, true)]);
+        assert!(!uncached_repo.entity_reference_idx_cache.read().await.contains_primary(&reference_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_through_replaces_stale_entries() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let executor = ctx.person_repos().country_repository.executor.clone();
+
+        let country_repo = CountryRepositoryImpl::new_with_cache_policy(
+            executor,
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::ReadThrough,
+        );
+        let saved = country_repo.create_batch(vec![create_test_country("QT", "Read Through Country")], None).await?;
+        let country_idx = saved[0].to_index();
+
+        // An entry missed by the notifications, e.g. after a write of another node
+        let stale = CountryIdxModel {
+            iso2_hash: hash_as_i64(&"QS").unwrap(),
+            ..country_idx.clone()
+        };
+        {
+            let cache = country_repo.country_idx_cache.read().await;
+            cache.remove(&country_idx.id);
+            cache.add(stale);
+        }
+
+        let found = country_repo.find_by_iso2_hash(country_idx.iso2_hash).await?;
+        assert!(found.iter().any(|idx| idx.id == country_idx.id));
+        let cached = country_repo
+            .country_idx_cache
+            .read()
+            .await
+            .get_by_primary(&country_idx.id)
+            .ok_or("The entry must stay cached")?;
+        assert_eq!(cached.iso2_hash, country_idx.iso2_hash);
+
+        Ok(())
+    }
+}
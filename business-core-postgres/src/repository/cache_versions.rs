@@ -0,0 +1,256 @@
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Latest version written to an index cache, per entry
+///
+/// The `version` column of an index row is 1 on insert and advanced by every update,
+/// and the cache notifications carry it in their payload. Local writes of a repository
+/// and replayed notifications reach the cache in no particular order, so each write is
+/// first passed to `admit`: a version lower than the one already written means the
+/// write is stale and must be dropped. An equal version is admitted, it is the same
+/// row written twice. Shared by the factory with the repositories of one idx table and
+/// with the coalescer of its notifications.
+///
+/// The versions follow the entries of the cache: an entry evicted by a `CacheCapacity`
+/// is passed to `forget`, and the `DELETED` version of a deleted entry is a tombstone
+/// kept for `tombstone_ttl`, long enough for the notifications in flight to arrive.
+#[derive(Debug)]
+pub struct CacheVersions {
+    state: Mutex<VersionState>,
+    tombstone_ttl: Duration,
+}
+
+#[derive(Debug, Default)]
+struct VersionState {
+    versions: HashMap<Uuid, u64>,
+    /// Deleted entries, oldest first
+    tombstones: VecDeque<(Instant, Uuid)>,
+}
+
+impl Default for CacheVersions {
+    fn default() -> Self {
+        Self::with_tombstone_ttl(Duration::from_secs(300))
+    }
+}
+
+impl CacheVersions {
+    /// Version of an index row on insert
+    pub const INITIAL: u64 = 1;
+    /// Version admitted for a deleted entry, so no replayed write brings it back
+    pub const DELETED: u64 = u64::MAX;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Versions dropping the tombstone of a deleted entry after `tombstone_ttl`
+    pub fn with_tombstone_ttl(tombstone_ttl: Duration) -> Self {
+        Self {
+            state: Mutex::new(VersionState::default()),
+            tombstone_ttl,
+        }
+    }
+
+    /// Record `version` for `id` and tell whether the write may go to the cache
+    ///
+    /// Returns `false`, and keeps the recorded version, when a higher version was
+    /// already admitted.
+    pub fn admit(&self, id: Uuid, version: u64) -> bool {
+        let mut state = self.state.lock();
+        self.expire_tombstones(&mut state);
+        let previous = state.versions.get(&id).copied();
+        if previous.is_some_and(|previous| version < previous) {
+            return false;
+        }
+        state.versions.insert(id, version);
+        if version == Self::DELETED && previous != Some(Self::DELETED) {
+            state.tombstones.push_back((Instant::now(), id));
+        }
+        true
+    }
+
+    /// Stop tracking `ids`, for entries evicted from the cache
+    ///
+    /// A notification of an evicted entry is admitted again, as for an entry never
+    /// written. Tombstones are kept until they expire.
+    pub fn forget(&self, ids: &[Uuid]) {
+        let mut state = self.state.lock();
+        for id in ids {
+            if state.versions.get(id) != Some(&Self::DELETED) {
+                state.versions.remove(id);
+            }
+        }
+    }
+
+    /// Highest version admitted for `id`
+    pub fn version(&self, id: Uuid) -> Option<u64> {
+        self.state.lock().versions.get(&id).copied()
+    }
+
+    /// Number of entries with an admitted version, tombstones included
+    pub fn len(&self) -> usize {
+        self.state.lock().versions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().versions.is_empty()
+    }
+
+    /// Drop the tombstones older than `tombstone_ttl`
+    fn expire_tombstones(&self, state: &mut VersionState) {
+        while let Some((since, id)) = state.tombstones.front().copied() {
+            if since.elapsed() < self.tombstone_ttl {
+                break;
+            }
+            state.tombstones.pop_front();
+            if state.versions.get(&id) == Some(&Self::DELETED) {
+                state.versions.remove(&id);
+            }
+        }
+    }
+}
+
+/// Versions admitted by the repository of one session
+///
+/// The writes of the session are admitted against the versions it admitted itself and
+/// the ones recorded in the shared `CacheVersions`, but only `on_commit` records them
+/// there, along with the cache changes the commit applies. `on_rollback` drops them, so
+/// a rolled back delete leaves no `DELETED` tombstone rejecting the later writes of the
+/// row.
+#[derive(Debug)]
+pub struct PendingVersions {
+    versions: Arc<CacheVersions>,
+    pending: Mutex<HashMap<Uuid, u64>>,
+}
+
+impl PendingVersions {
+    pub fn new(versions: Arc<CacheVersions>) -> Self {
+        Self {
+            versions,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Versions shared with the other sessions and the coalescer of the notifications
+    pub fn shared(&self) -> &Arc<CacheVersions> {
+        &self.versions
+    }
+
+    /// Hold `version` for `id` until the session ends and tell whether the write may go
+    /// to the cache
+    ///
+    /// Returns `false` when a higher version was admitted by the session or recorded.
+    pub fn admit(&self, id: Uuid, version: u64) -> bool {
+        let mut pending = self.pending.lock();
+        let previous = pending.get(&id).copied().or_else(|| self.versions.version(id));
+        if previous.is_some_and(|previous| version < previous) {
+            return false;
+        }
+        pending.insert(id, version);
+        true
+    }
+
+    /// Highest version admitted for `id`, by the session or recorded
+    pub fn version(&self, id: Uuid) -> Option<u64> {
+        self.pending.lock().get(&id).copied().or_else(|| self.versions.version(id))
+    }
+
+    /// Record the versions held in the shared versions
+    pub fn on_commit(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        for (id, version) in pending {
+            self.versions.admit(id, version);
+        }
+    }
+
+    /// Drop the versions held
+    pub fn on_rollback(&self) {
+        self.pending.lock().clear();
+    }
+}
+
+/// Version carried by a cache notification, `None` for rows without a `version` column
+///
+/// `payload` is the index row as sent by the notification trigger.
+pub fn version_from_payload(payload: &serde_json::Value) -> Option<u64> {
+    payload.get("version")?.as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{version_from_payload, CacheVersions, PendingVersions};
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_admit_rejects_lower_versions() {
+        let versions = CacheVersions::new();
+        let id = Uuid::new_v4();
+
+        assert!(versions.admit(id, CacheVersions::INITIAL));
+        assert!(versions.admit(id, 3));
+        // Replay of the same row
+        assert!(versions.admit(id, 3));
+        assert!(!versions.admit(id, 2));
+        assert_eq!(versions.version(id), Some(3));
+
+        assert!(versions.admit(id, CacheVersions::DELETED));
+        assert!(!versions.admit(id, 4));
+        assert_eq!(versions.len(), 1);
+    }
+
+    #[test]
+    fn test_tombstones_expire_and_evicted_entries_are_forgotten() {
+        let versions = CacheVersions::with_tombstone_ttl(Duration::ZERO);
+        let (deleted, evicted) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(versions.admit(deleted, CacheVersions::DELETED));
+        assert_eq!(versions.version(deleted), Some(CacheVersions::DELETED));
+        // The next admission expires the tombstone
+        assert!(versions.admit(evicted, 3));
+        assert_eq!(versions.version(deleted), None);
+
+        versions.forget(&[evicted]);
+        assert_eq!(versions.version(evicted), None);
+        assert!(versions.is_empty());
+
+        // Within its time to live, a tombstone survives `forget`
+        let versions = CacheVersions::new();
+        assert!(versions.admit(deleted, CacheVersions::DELETED));
+        versions.forget(&[deleted]);
+        assert!(!versions.admit(deleted, CacheVersions::INITIAL));
+    }
+
+    #[test]
+    fn test_pending_versions_are_recorded_on_commit() {
+        let versions = Arc::new(CacheVersions::new());
+        let (committed, rolled_back) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(versions.admit(rolled_back, CacheVersions::INITIAL));
+
+        let pending = PendingVersions::new(versions.clone());
+        assert!(pending.admit(committed, 2));
+        assert!(!pending.admit(committed, CacheVersions::INITIAL));
+        assert_eq!(pending.version(committed), Some(2));
+        assert_eq!(versions.version(committed), None);
+        pending.on_commit();
+        assert_eq!(versions.version(committed), Some(2));
+
+        // A rolled back delete leaves no tombstone
+        assert!(pending.admit(rolled_back, CacheVersions::DELETED));
+        pending.on_rollback();
+        assert_eq!(pending.version(rolled_back), Some(CacheVersions::INITIAL));
+        assert!(pending.admit(rolled_back, 2));
+    }
+
+    #[test]
+    fn test_version_from_payload() {
+        let id = Uuid::new_v4();
+        assert_eq!(version_from_payload(&json!({ "id": id, "version": 4 })), Some(4));
+        assert_eq!(version_from_payload(&json!({ "id": id })), None);
+    }
+}
@@ -0,0 +1,219 @@
+use business_core_db::models::calendar::business_day::BusinessDayModel;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::utils::TryFromRow;
+
+use super::repo_impl::BusinessDayRepositoryImpl;
+
+impl BusinessDayRepositoryImpl {
+    /// Business days of a country, and optionally one of its subdivisions, between two dates
+    ///
+    /// Country rows are the ones without a subdivision. When `country_subdivision_id` is
+    /// given its rows are included too and win over the country row of the same date.
+    /// The result is ordered by date, one entry per date.
+    ///
+    /// The index cache knows every row of the country and subdivision. When all of them
+    /// are in the main cache the range is answered from it, otherwise one range query
+    /// is issued and its rows are added to the main cache.
+    pub async fn find_by_country_and_range(
+        &self,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<BusinessDayModel>, Box<dyn Error + Send + Sync>> {
+        if to < from {
+            return Ok(Vec::new());
+        }
+
+        let candidates = match self.load_covered_from_cache(country_id, country_subdivision_id).await {
+            Some(items) => items
+                .into_iter()
+                .filter(|item| item.date >= from && item.date <= to)
+                .collect(),
+            None => {
+                let rows = {
+                    let mut tx = self.operation_timeout.lock("calendar_business_day", &self.executor.tx).await?;
+                    let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+                    sqlx::query(
+                        r#"
+                        SELECT * FROM calendar_business_day
+                        WHERE date BETWEEN $3 AND $4
+                        AND ((country_id = $1 AND country_subdivision_id IS NULL)
+                            OR country_subdivision_id = $2)
+                        "#,
+                    )
+                    .bind(country_id)
+                    .bind(country_subdivision_id)
+                    .bind(from)
+                    .bind(to)
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("calendar_business_day", e))?
+                };
+
+                let mut items = Vec::with_capacity(rows.len());
+                for row in rows {
+                    items.push(BusinessDayModel::try_from_row(&row)?);
+                }
+
+                let main_cache = self.business_day_cache.read().await;
+                for item in &items {
+                    main_cache.insert(item.clone());
+                }
+                items
+            }
+        };
+
+        Ok(merge_by_date(candidates))
+    }
+
+    /// All rows of the country and subdivision, if every one of them is in the main cache
+    async fn load_covered_from_cache(
+        &self,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+    ) -> Option<Vec<BusinessDayModel>> {
+        let mut ids: Vec<Uuid> = {
+            let idx_cache = self.business_day_idx_cache.read().await;
+            let mut ids: Vec<Uuid> = idx_cache
+                .get_by_uuid_index("country_id", &country_id)
+                .into_iter()
+                .filter(|idx| idx.country_subdivision_id.is_none())
+                .map(|idx| idx.id)
+                .collect();
+            if let Some(country_subdivision_id) = country_subdivision_id {
+                ids.extend(
+                    idx_cache
+                        .get_by_uuid_index("country_subdivision_id", &country_subdivision_id)
+                        .into_iter()
+                        .map(|idx| idx.id),
+                );
+            }
+            ids
+        };
+        ids.sort();
+        ids.dedup();
+
+        let main_cache = self.business_day_cache.read().await;
+        ids.iter().map(|id| main_cache.get(id)).collect()
+    }
+}
+
+/// Order by date keeping one row per date, a subdivision row over a country row
+fn merge_by_date(items: Vec<BusinessDayModel>) -> Vec<BusinessDayModel> {
+    let mut by_date: BTreeMap<NaiveDate, BusinessDayModel> = BTreeMap::new();
+    for item in items {
+        match by_date.get(&item.date) {
+            Some(existing) if existing.country_subdivision_id.is_some() => {}
+            _ => {
+                by_date.insert(item.date, item);
+            }
+        }
+    }
+    by_date.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+    use super::super::test_utils::test_utils::create_test_business_day;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_find_by_country_and_range_across_month_boundary() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let business_day_repo = &ctx.calendar_repos().business_day_repository;
+
+        let country_id = Uuid::new_v4();
+        let mut items = Vec::new();
+        for day in [date(2024, 1, 30), date(2024, 1, 31), date(2024, 2, 1), date(2024, 2, 2)] {
+            let mut item = create_test_business_day(Some(country_id), None);
+            item.date = day;
+            items.push(item);
+        }
+        let mut other_country = create_test_business_day(Some(Uuid::new_v4()), None);
+        other_country.date = date(2024, 1, 31);
+        items.push(other_country);
+        business_day_repo.create_batch(items, None).await?;
+
+        // Served from the main cache, create_batch added every row to it
+        let found = business_day_repo
+            .find_by_country_and_range(country_id, None, date(2024, 1, 31), date(2024, 2, 1))
+            .await?;
+        let dates: Vec<NaiveDate> = found.iter().map(|item| item.date).collect();
+        assert_eq!(dates, vec![date(2024, 1, 31), date(2024, 2, 1)]);
+
+        // Evicting one row forces the range query
+        {
+            let main_cache = business_day_repo.business_day_cache.read().await;
+            main_cache.remove(&found[0].id);
+        }
+        let from_sql = business_day_repo
+            .find_by_country_and_range(country_id, None, date(2024, 1, 31), date(2024, 2, 1))
+            .await?;
+        let ids: Vec<Uuid> = from_sql.iter().map(|item| item.id).collect();
+        assert_eq!(ids, found.iter().map(|item| item.id).collect::<Vec<_>>());
+        assert!(business_day_repo.business_day_cache.read().await.contains(&found[0].id));
+
+        assert!(business_day_repo
+            .find_by_country_and_range(country_id, None, date(2024, 2, 1), date(2024, 1, 31))
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_country_and_range_subdivision_wins() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let business_day_repo = &ctx.calendar_repos().business_day_repository;
+
+        let country_id = Uuid::new_v4();
+        let subdivision_id = Uuid::new_v4();
+
+        let mut national = create_test_business_day(Some(country_id), None);
+        national.date = date(2024, 3, 1);
+        let mut regional_holiday = create_test_business_day(Some(country_id), Some(subdivision_id));
+        regional_holiday.date = date(2024, 3, 1);
+        regional_holiday.is_business_day = false;
+        let regional_holiday_id = regional_holiday.id;
+        let mut national_only = create_test_business_day(Some(country_id), None);
+        national_only.date = date(2024, 3, 4);
+        let national_only_id = national_only.id;
+        business_day_repo
+            .create_batch(vec![national, regional_holiday, national_only], None)
+            .await?;
+
+        for evict in [false, true] {
+            if evict {
+                let main_cache = business_day_repo.business_day_cache.read().await;
+                main_cache.remove(&national_only_id);
+            }
+            let found = business_day_repo
+                .find_by_country_and_range(country_id, Some(subdivision_id), date(2024, 3, 1), date(2024, 3, 31))
+                .await?;
+            let ids: Vec<Uuid> = found.iter().map(|item| item.id).collect();
+            assert_eq!(ids, vec![regional_holiday_id, national_only_id]);
+        }
+
+        // Without the subdivision only country rows are returned
+        let found = business_day_repo
+            .find_by_country_and_range(country_id, None, date(2024, 3, 1), date(2024, 3, 31))
+            .await?;
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|item| item.country_subdivision_id.is_none()));
+
+        Ok(())
+    }
+}
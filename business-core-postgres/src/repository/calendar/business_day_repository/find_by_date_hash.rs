@@ -2,6 +2,7 @@ use std::error::Error;
 
 use business_core_db::models::calendar::business_day::BusinessDayIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::BusinessDayRepositoryImpl;
 
 impl BusinessDayRepositoryImpl {
@@ -9,9 +10,7 @@ impl BusinessDayRepositoryImpl {
         &self,
         date_hash: i64,
     ) -> Result<Vec<BusinessDayIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.business_day_idx_cache.read().await;
-        let items = cache.get_by_i64_index("date_hash", &date_hash);
-        Ok(items)
+        self.find_by_i64_key("date_hash", date_hash).await
     }
 }
 
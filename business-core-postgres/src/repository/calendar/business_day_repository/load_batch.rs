@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 #[async_trait]
 impl LoadBatch<sqlx::Postgres, BusinessDayModel> for BusinessDayRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "business_day", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
@@ -50,7 +51,7 @@ impl BusinessDayRepositoryImpl {
         // Load missing items from database
         let query = r#"SELECT * FROM calendar_business_day WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("calendar_business_day", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(&missing_ids).fetch_all(&mut **transaction).await?
         };
@@ -74,6 +75,11 @@ impl BusinessDayRepositoryImpl {
         
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<BusinessDayModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[cfg(test)]
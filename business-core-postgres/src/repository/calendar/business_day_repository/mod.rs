@@ -7,6 +7,7 @@ pub mod update_batch;
 pub mod find_by_country_id;
 pub mod find_by_country_subdivision_id;
 pub mod find_by_date_hash;
+pub mod find_by_country_and_range;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -7,11 +7,18 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct BusinessDayRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub business_day_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<BusinessDayIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub business_day_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<BusinessDayIdxModel>>>,
     pub business_day_cache: Arc<RwLock<TransactionAwareMainModelCache<BusinessDayModel>>>,
 }
 
@@ -23,6 +30,8 @@ impl BusinessDayRepositoryImpl {
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            business_day_idx_shared_cache: business_day_idx_cache.clone(),
             business_day_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 business_day_idx_cache,
             ))),
@@ -34,10 +43,14 @@ impl BusinessDayRepositoryImpl {
 
     pub async fn load_all_business_day_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<BusinessDayIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM calendar_business_day_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("calendar_business_day", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -95,4 +108,39 @@ impl TryFromRow<PgRow> for BusinessDayIdxModel {
             date_hash: row.get("date_hash"),
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for BusinessDayRepositoryImpl {
+    type Idx = BusinessDayIdxModel;
+
+    const ENTITY: &'static str = "business_day";
+    const I64_KEYS: &'static [&'static str] = &["date_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<BusinessDayIdxModel>> {
+        &self.business_day_idx_cache
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for BusinessDayRepositoryImpl {
+    type Idx = BusinessDayIdxModel;
+
+    const ENTITY: &'static str = "business_day";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<BusinessDayIdxModel>>> {
+        &self.business_day_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<BusinessDayIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_business_day_idx(&self.executor, &self.operation_timeout).await?)
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 pub mod test_utils {
-    use business_core_db::models::calendar::business_day::{BusinessDayModel, Weekday, DayScope};
+    use business_core_db::models::calendar::{business_day::{BusinessDayModel, DayScope}, calendar_weekday::CalendarWeekday};
     use chrono::NaiveDate;
     use uuid::Uuid;
     use heapless::String as HeaplessString;
@@ -14,7 +14,7 @@ pub mod test_utils {
             country_id,
             country_subdivision_id,
             date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
-            weekday: Weekday::Monday,
+            weekday: CalendarWeekday::Monday,
             is_business_day: true,
             is_weekend: false,
             weekend_day_01: None,
@@ -32,7 +32,7 @@ pub mod test_utils {
             country_id: None,
             country_subdivision_id: None,
             date,
-            weekday: Weekday::Monday,
+            weekday: CalendarWeekday::Monday,
             is_business_day: true,
             is_weekend: false,
             weekend_day_01: None,
@@ -54,7 +54,7 @@ pub mod test_utils {
             country_id,
             country_subdivision_id: None,
             date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
-            weekday: Weekday::Monday,
+            weekday: CalendarWeekday::Monday,
             is_business_day: false,
             is_weekend: false,
             weekend_day_01: None,
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 #[async_trait]
 impl UpdateBatch<sqlx::Postgres, BusinessDayModel> for BusinessDayRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "business_day", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<BusinessDayModel>,
@@ -30,7 +31,7 @@ impl BusinessDayRepositoryImpl {
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("calendar_business_day", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for item in items {
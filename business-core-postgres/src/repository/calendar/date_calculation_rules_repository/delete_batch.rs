@@ -1,16 +1,31 @@
 use business_core_db::repository::delete_batch::DeleteBatch;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
 use super::repo_impl::DateCalculationRulesRepositoryImpl;
 use async_trait::async_trait;
+use sqlx::Row;
 use std::error::Error;
 use uuid::Uuid;
 
 #[async_trait]
 impl DeleteBatch<sqlx::Postgres> for DateCalculationRulesRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "date_calculation_rules", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         _audit_info: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<sqlx::Postgres> for DateCalculationRulesRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        _audit_info: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids).await
     }
 }
@@ -19,41 +34,41 @@ impl DateCalculationRulesRepositoryImpl {
     pub(super) async fn delete_batch_impl(
         repo: &DateCalculationRulesRepositoryImpl,
         ids: &[Uuid],
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         // Delete from index table first
         let delete_idx_query = r#"DELETE FROM calendar_date_calculation_rules_idx WHERE id = ANY($1)"#;
-        let delete_query = r#"DELETE FROM calendar_date_calculation_rules WHERE id = ANY($1)"#;
+        let delete_query = r#"DELETE FROM calendar_date_calculation_rules WHERE id = ANY($1) RETURNING id"#;
 
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("calendar_date_calculation_rules", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
-        
+
         sqlx::query(delete_idx_query)
             .bind(ids)
             .execute(&mut **transaction)
             .await?;
-        let result = sqlx::query(delete_query)
+        let rows = sqlx::query(delete_query)
             .bind(ids)
-            .execute(&mut **transaction)
+            .fetch_all(&mut **transaction)
             .await?;
-        let rows_affected = result.rows_affected() as usize;
-        
+        let deleted: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+
         drop(tx); // Release transaction lock
-        
-        // Update BOTH caches after releasing transaction lock
+
+        // Update BOTH caches after releasing transaction lock, only for deleted ids
         {
             let idx_cache = repo.date_calculation_rules_idx_cache.read().await;
             let main_cache = repo.date_calculation_rules_cache.read().await;
-            
-            for id in ids {
+
+            for id in &deleted {
                 idx_cache.remove(id);
                 main_cache.remove(id);
             }
         }
-        
-        Ok(rows_affected)
+
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+
+use business_core_db::models::calendar::date_calculation_rules::{DateCalculationRulesModel, DateRulePurpose};
+use business_core_db::repository::load_batch::LoadBatch;
+use uuid::Uuid;
+
+use super::repo_impl::DateCalculationRulesRepositoryImpl;
+
+impl DateCalculationRulesRepositoryImpl {
+    /// Rules of a country serving `purpose`, country-wide and subdivision ones alike
+    ///
+    /// The purpose is not indexed: the country's rules are found through the index
+    /// cache, loaded, and filtered on `rule_purpose`.
+    pub async fn find_by_purpose(
+        &self,
+        purpose: DateRulePurpose,
+        country_id: Uuid,
+    ) -> Result<Vec<DateCalculationRulesModel>, Box<dyn Error + Send + Sync>> {
+        let ids: Vec<Uuid> = self
+            .find_by_country_id(country_id)
+            .await?
+            .into_iter()
+            .map(|idx| idx.id)
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let items = self
+            .load_batch(&ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .filter(|item| item.rule_purpose == purpose)
+            .collect();
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::calendar::date_calculation_rules::DateRulePurpose;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+    use super::super::test_utils::test_utils::create_test_date_calculation_rule;
+
+    #[tokio::test]
+    async fn test_find_by_purpose() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let date_calculation_rules_repo = &ctx.calendar_repos().date_calculation_rules_repository;
+
+        let country_id = Uuid::new_v4();
+        let subdivision_id = Uuid::new_v4();
+        let mut maturity = create_test_date_calculation_rule(country_id, None, "Maturity");
+        maturity.rule_purpose = DateRulePurpose::MaturityCalculation;
+        let items = vec![
+            create_test_date_calculation_rule(country_id, None, "CountryShift"),
+            create_test_date_calculation_rule(country_id, Some(subdivision_id), "SubdivisionShift"),
+            maturity,
+            create_test_date_calculation_rule(Uuid::new_v4(), None, "OtherCountry"),
+        ];
+        let saved = date_calculation_rules_repo.create_batch(items, None).await?;
+
+        let mut found = date_calculation_rules_repo
+            .find_by_purpose(DateRulePurpose::DateShift, country_id)
+            .await?;
+        found.sort_by_key(|rule| rule.country_subdivision_id.is_some());
+        let found_ids: Vec<Uuid> = found.iter().map(|rule| rule.id).collect();
+        assert_eq!(found_ids, vec![saved[0].id, saved[1].id]);
+
+        let found = date_calculation_rules_repo
+            .find_by_purpose(DateRulePurpose::MaturityCalculation, country_id)
+            .await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[2].id);
+
+        let found = date_calculation_rules_repo
+            .find_by_purpose(DateRulePurpose::PaymentDue, country_id)
+            .await?;
+        assert!(found.is_empty());
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use std::error::Error;
 
 use business_core_db::models::calendar::date_calculation_rules::DateCalculationRulesIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::DateCalculationRulesRepositoryImpl;
 
 impl DateCalculationRulesRepositoryImpl {
@@ -9,8 +10,6 @@ impl DateCalculationRulesRepositoryImpl {
         &self,
         rule_name_hash: i64,
     ) -> Result<Vec<DateCalculationRulesIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.date_calculation_rules_idx_cache.read().await;
-        let items = cache.get_by_i64_index("rule_name_hash", &rule_name_hash);
-        Ok(items)
+        self.find_by_i64_key("rule_name_hash", rule_name_hash).await
     }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use std::error::Error;
+
+use business_core_db::models::calendar::date_calculation_rules::DateCalculationRulesModel;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::utils::TryFromRow;
+
+use super::repo_impl::DateCalculationRulesRepositoryImpl;
+
+impl DateCalculationRulesRepositoryImpl {
+    /// Rules using a weekend days definition, to see what editing it affects
+    ///
+    /// `weekend_days_id` is not indexed, so the main table is queried; the rules
+    /// found are added to the main cache.
+    pub async fn find_by_weekend_days_id(
+        &self,
+        weekend_days_id: Uuid,
+    ) -> Result<Vec<DateCalculationRulesModel>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.operation_timeout.lock("calendar_date_calculation_rules", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("SELECT * FROM calendar_date_calculation_rules WHERE weekend_days_id = $1 ORDER BY id")
+                .bind(weekend_days_id)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("calendar_date_calculation_rules", e))?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(DateCalculationRulesModel::try_from_row(&row)?);
+        }
+
+        let main_cache = self.date_calculation_rules_cache.read().await;
+        for item in &items {
+            main_cache.insert(item.clone());
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+    use super::super::test_utils::test_utils::create_test_date_calculation_rule;
+
+    #[tokio::test]
+    async fn test_find_by_weekend_days_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let date_calculation_rules_repo = &ctx.calendar_repos().date_calculation_rules_repository;
+
+        let country_id = Uuid::new_v4();
+        let weekend_days_id = Uuid::new_v4();
+        let mut rule1 = create_test_date_calculation_rule(country_id, None, "Rule1");
+        rule1.weekend_days_id = Some(weekend_days_id);
+        let mut rule2 = create_test_date_calculation_rule(country_id, Some(Uuid::new_v4()), "Rule2");
+        rule2.weekend_days_id = Some(weekend_days_id);
+        let mut other = create_test_date_calculation_rule(country_id, None, "Other");
+        other.weekend_days_id = Some(Uuid::new_v4());
+        let unlinked = create_test_date_calculation_rule(country_id, None, "Unlinked");
+
+        let saved = date_calculation_rules_repo
+            .create_batch(vec![rule1, rule2, other, unlinked], None)
+            .await?;
+
+        let found = date_calculation_rules_repo.find_by_weekend_days_id(weekend_days_id).await?;
+        let mut found_ids: Vec<Uuid> = found.iter().map(|rule| rule.id).collect();
+        let mut expected = vec![saved[0].id, saved[1].id];
+        found_ids.sort();
+        expected.sort();
+        assert_eq!(found_ids, expected);
+        assert!(found.iter().all(|rule| rule.weekend_days_id == Some(weekend_days_id)));
+
+        let found = date_calculation_rules_repo.find_by_weekend_days_id(Uuid::new_v4()).await?;
+        assert!(found.is_empty());
+
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 #[async_trait]
 impl LoadBatch<sqlx::Postgres, DateCalculationRulesModel> for DateCalculationRulesRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "date_calculation_rules", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
@@ -50,7 +51,7 @@ impl DateCalculationRulesRepositoryImpl {
         // Load missing items from database
         let query = r#"SELECT * FROM calendar_date_calculation_rules WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("calendar_date_calculation_rules", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(&missing_ids).fetch_all(&mut **transaction).await?
         };
@@ -74,4 +75,9 @@ impl DateCalculationRulesRepositoryImpl {
         
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<DateCalculationRulesModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
\ No newline at end of file
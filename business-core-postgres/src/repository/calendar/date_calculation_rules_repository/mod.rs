@@ -7,6 +7,8 @@ mod exist_by_ids;
 mod find_by_country_id;
 mod find_by_country_subdivision_id;
 mod find_by_rule_name_hash;
+mod find_by_purpose;
+mod find_by_weekend_days_id;
 mod test_utils;
 pub use repo_impl::DateCalculationRulesRepositoryImpl;
 
@@ -116,6 +118,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_delete_batch_detailed_with_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use business_core_db::repository::delete_batch_detailed::DeleteBatchDetailed;
+
+        let ctx = setup_test_context().await?;
+        let date_calculation_rules_repo = &ctx.calendar_repos().date_calculation_rules_repository;
+
+        let country_id = Uuid::new_v4();
+        let items = vec![
+            create_test_date_calculation_rule(country_id, None, "Rule1"),
+            create_test_date_calculation_rule(country_id, None, "Rule2"),
+        ];
+        let saved = date_calculation_rules_repo.create_batch(items, None).await?;
+
+        let missing_id = Uuid::new_v4();
+        let ids = vec![saved[0].id, missing_id, saved[1].id];
+
+        let outcome = date_calculation_rules_repo.delete_batch_detailed(&ids, None).await?;
+        assert_eq!(outcome.deleted.len(), 2);
+        assert!(outcome.deleted.contains(&saved[0].id));
+        assert!(outcome.deleted.contains(&saved[1].id));
+        assert_eq!(outcome.not_found, vec![missing_id]);
+
+        let main_cache = date_calculation_rules_repo.date_calculation_rules_cache.read().await;
+        for item in &saved {
+            assert!(!main_cache.contains(&item.id), "Entity should be removed from main cache");
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_find_by_country_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
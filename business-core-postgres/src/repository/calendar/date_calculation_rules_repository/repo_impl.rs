@@ -7,11 +7,18 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct DateCalculationRulesRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub date_calculation_rules_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<DateCalculationRulesIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub date_calculation_rules_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<DateCalculationRulesIdxModel>>>,
     pub date_calculation_rules_cache: Arc<RwLock<TransactionAwareMainModelCache<DateCalculationRulesModel>>>,
 }
 
@@ -23,6 +30,8 @@ impl DateCalculationRulesRepositoryImpl {
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            date_calculation_rules_idx_shared_cache: date_calculation_rules_idx_cache.clone(),
             date_calculation_rules_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 date_calculation_rules_idx_cache,
             ))),
@@ -34,10 +43,14 @@ impl DateCalculationRulesRepositoryImpl {
 
     pub async fn load_all_date_calculation_rules_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<DateCalculationRulesIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM calendar_date_calculation_rules_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("calendar_date_calculation_rules", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -95,4 +108,39 @@ impl TryFromRow<PgRow> for DateCalculationRulesIdxModel {
             rule_name_hash: row.get("rule_name_hash"),
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for DateCalculationRulesRepositoryImpl {
+    type Idx = DateCalculationRulesIdxModel;
+
+    const ENTITY: &'static str = "date_calculation_rules";
+    const I64_KEYS: &'static [&'static str] = &["rule_name_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<DateCalculationRulesIdxModel>> {
+        &self.date_calculation_rules_idx_cache
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for DateCalculationRulesRepositoryImpl {
+    type Idx = DateCalculationRulesIdxModel;
+
+    const ENTITY: &'static str = "date_calculation_rules";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<DateCalculationRulesIdxModel>>> {
+        &self.date_calculation_rules_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<DateCalculationRulesIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_date_calculation_rules_idx(&self.executor, &self.operation_timeout).await?)
+    }
+}
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 #[async_trait]
 impl UpdateBatch<sqlx::Postgres, DateCalculationRulesModel> for DateCalculationRulesRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "date_calculation_rules", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<DateCalculationRulesModel>,
@@ -30,7 +31,7 @@ impl DateCalculationRulesRepositoryImpl {
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("calendar_date_calculation_rules", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for item in items {
@@ -6,6 +6,8 @@ use std::time::Duration;
 use business_core_db::models::calendar::weekend_days::{WeekendDaysIdxModel, WeekendDaysModel};
 use business_core_db::models::calendar::business_day::{BusinessDayIdxModel, BusinessDayModel};
 use business_core_db::models::calendar::date_calculation_rules::{DateCalculationRulesIdxModel, DateCalculationRulesModel};
+use business_core_db::utils::{Clock, SystemClock};
+use crate::repository::operation_timeout::OperationTimeout;
 use super::{WeekendDaysRepositoryImpl, BusinessDayRepositoryImpl, DateCalculationRulesRepositoryImpl};
 
 /// Factory for creating calendar module repositories with main cache
@@ -16,6 +18,8 @@ pub struct CalendarRepoFactory {
     business_day_cache: Arc<ParkingRwLock<MainModelCache<BusinessDayModel>>>,
     date_calculation_rules_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<DateCalculationRulesIdxModel>>>,
     date_calculation_rules_cache: Arc<ParkingRwLock<MainModelCache<DateCalculationRulesModel>>>,
+    clock: Arc<dyn Clock>,
+    operation_timeout: OperationTimeout,
 }
 
 impl CalendarRepoFactory {
@@ -23,6 +27,22 @@ impl CalendarRepoFactory {
     ///
     /// Optionally register cache handlers with a notification listener
     pub fn new(listener: Option<&mut CacheNotificationListener>) -> Arc<Self> {
+        Self::new_with_clock(listener, Arc::new(SystemClock))
+    }
+
+    /// Create a new CalendarRepoFactory singleton whose repositories and services take
+    /// the current date from `clock`
+    pub fn new_with_clock(listener: Option<&mut CacheNotificationListener>, clock: Arc<dyn Clock>) -> Arc<Self> {
+        Self::new_with_operation_timeout(listener, clock, OperationTimeout::default())
+    }
+
+    /// Create a new CalendarRepoFactory singleton whose repositories bound their wait for
+    /// the transaction lock with `operation_timeout`
+    pub fn new_with_operation_timeout(
+        listener: Option<&mut CacheNotificationListener>,
+        clock: Arc<dyn Clock>,
+        operation_timeout: OperationTimeout,
+    ) -> Arc<Self> {
         // Initialize index cache
         let weekend_days_idx_cache = Arc::new(ParkingRwLock::new(
             business_core_db::IdxModelCache::new(vec![]).unwrap()
@@ -123,38 +143,54 @@ impl CalendarRepoFactory {
             business_day_cache,
             date_calculation_rules_idx_cache,
             date_calculation_rules_cache,
+            clock,
+            operation_timeout,
         })
     }
 
+    /// Clock the repositories and services of this factory take the current date from
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
     /// Build a WeekendDaysRepository with the given executor
     pub fn build_weekend_days_repo(&self, session: &impl UnitOfWorkSession) -> Arc<WeekendDaysRepositoryImpl> {
-        let repo = Arc::new(WeekendDaysRepositoryImpl::new(
-            session.executor().clone(),
-            self.weekend_days_idx_cache.clone(),
-            self.weekend_days_cache.clone(),
-        ));
+        let repo = Arc::new(WeekendDaysRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..WeekendDaysRepositoryImpl::new(
+                session.executor().clone(),
+                self.weekend_days_idx_cache.clone(),
+                self.weekend_days_cache.clone(),
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a BusinessDayRepository with the given executor
     pub fn build_business_day_repo(&self, session: &impl UnitOfWorkSession) -> Arc<BusinessDayRepositoryImpl> {
-        let repo = Arc::new(BusinessDayRepositoryImpl::new(
-            session.executor().clone(),
-            self.business_day_idx_cache.clone(),
-            self.business_day_cache.clone(),
-        ));
+        let repo = Arc::new(BusinessDayRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..BusinessDayRepositoryImpl::new(
+                session.executor().clone(),
+                self.business_day_idx_cache.clone(),
+                self.business_day_cache.clone(),
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a DateCalculationRulesRepository with the given executor
     pub fn build_date_calculation_rules_repo(&self, session: &impl UnitOfWorkSession) -> Arc<DateCalculationRulesRepositoryImpl> {
-        let repo = Arc::new(DateCalculationRulesRepositoryImpl::new(
-            session.executor().clone(),
-            self.date_calculation_rules_idx_cache.clone(),
-            self.date_calculation_rules_cache.clone(),
-        ));
+        let repo = Arc::new(DateCalculationRulesRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..DateCalculationRulesRepositoryImpl::new(
+                session.executor().clone(),
+                self.date_calculation_rules_idx_cache.clone(),
+                self.date_calculation_rules_cache.clone(),
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
@@ -165,6 +201,7 @@ impl CalendarRepoFactory {
             weekend_days_repository: self.build_weekend_days_repo(session),
             business_day_repository: self.build_business_day_repo(session),
             date_calculation_rules_repository: self.build_date_calculation_rules_repo(session),
+            clock: self.clock.clone(),
         }
     }
 }
@@ -174,4 +211,6 @@ pub struct CalendarRepositories {
     pub weekend_days_repository: Arc<WeekendDaysRepositoryImpl>,
     pub business_day_repository: Arc<BusinessDayRepositoryImpl>,
     pub date_calculation_rules_repository: Arc<DateCalculationRulesRepositoryImpl>,
+    /// Clock of the factory, for the services built on these repositories
+    pub clock: Arc<dyn Clock>,
 }
\ No newline at end of file
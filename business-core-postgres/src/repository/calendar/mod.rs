@@ -1,9 +1,11 @@
 pub mod factory;
+pub mod repo_builder;
 pub mod weekend_days_repository;
 pub mod business_day_repository;
 pub mod date_calculation_rules_repository;
 
 pub use factory::{CalendarRepoFactory, CalendarRepositories};
+pub use repo_builder::{CalendarRepoBuilder, SelectedCalendarRepositories};
 pub use weekend_days_repository::WeekendDaysRepositoryImpl;
 pub use business_day_repository::BusinessDayRepositoryImpl;
 pub use date_calculation_rules_repository::DateCalculationRulesRepositoryImpl;
\ No newline at end of file
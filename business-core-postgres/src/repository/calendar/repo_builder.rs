@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+use postgres_unit_of_work::UnitOfWorkSession;
+use crate::repository::repo_selection::{RepoSelection, Selected, Unselected};
+use super::factory::CalendarRepoFactory;
+use super::{WeekendDaysRepositoryImpl, BusinessDayRepositoryImpl, DateCalculationRulesRepositoryImpl};
+
+/// Builder constructing only the selected calendar module repositories
+///
+/// Obtained from `CalendarRepoFactory::builder`. Each `with_*` call selects one repository;
+/// `build` returns a `SelectedCalendarRepositories` whose unselected fields are `()`.
+pub struct CalendarRepoBuilder<'a, WeekendDays = Unselected, BusinessDay = Unselected, DateCalculationRules = Unselected> {
+    factory: &'a CalendarRepoFactory,
+    selection: PhantomData<(WeekendDays, BusinessDay, DateCalculationRules)>,
+}
+
+impl CalendarRepoFactory {
+    /// Start a builder that constructs only the selected repositories
+    pub fn builder(&self) -> CalendarRepoBuilder<'_> {
+        CalendarRepoBuilder {
+            factory: self,
+            selection: PhantomData,
+        }
+    }
+}
+
+impl<'a, WeekendDays: RepoSelection, BusinessDay: RepoSelection, DateCalculationRules: RepoSelection> CalendarRepoBuilder<'a, WeekendDays, BusinessDay, DateCalculationRules> {
+    /// Select the WeekendDaysRepository
+    pub fn with_weekend_days(self) -> CalendarRepoBuilder<'a, Selected, BusinessDay, DateCalculationRules> {
+        CalendarRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the BusinessDayRepository
+    pub fn with_business_day(self) -> CalendarRepoBuilder<'a, WeekendDays, Selected, DateCalculationRules> {
+        CalendarRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the DateCalculationRulesRepository
+    pub fn with_date_calculation_rules(self) -> CalendarRepoBuilder<'a, WeekendDays, BusinessDay, Selected> {
+        CalendarRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Build the selected repositories with the given executor
+    pub fn build(self, session: &impl UnitOfWorkSession) -> SelectedCalendarRepositories<WeekendDays, BusinessDay, DateCalculationRules> {
+        SelectedCalendarRepositories {
+            weekend_days_repository: WeekendDays::build(|| self.factory.build_weekend_days_repo(session)),
+            business_day_repository: BusinessDay::build(|| self.factory.build_business_day_repo(session)),
+            date_calculation_rules_repository: DateCalculationRules::build(|| self.factory.build_date_calculation_rules_repo(session)),
+        }
+    }
+}
+
+/// Container for the calendar module repositories selected on a `CalendarRepoBuilder`
+pub struct SelectedCalendarRepositories<WeekendDays: RepoSelection, BusinessDay: RepoSelection, DateCalculationRules: RepoSelection> {
+    pub weekend_days_repository: WeekendDays::Repo<WeekendDaysRepositoryImpl>,
+    pub business_day_repository: BusinessDay::Repo<BusinessDayRepositoryImpl>,
+    pub date_calculation_rules_repository: DateCalculationRules::Repo<DateCalculationRulesRepositoryImpl>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::calendar::CalendarRepoFactory;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use postgres_unit_of_work::{PostgresUnitOfWork, UnitOfWork};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_builder_builds_selected_repositories() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+
+        let uow = PostgresUnitOfWork::new(ctx.pool().clone());
+        let session = uow.begin().await?;
+        let factory = CalendarRepoFactory::new(None);
+        let repos = factory.builder().with_business_day().build(&session);
+
+        let loaded = repos.business_day_repository.load_batch(&[Uuid::new_v4()]).await?;
+        assert!(loaded[0].is_none());
+
+        // Unselected repositories are `()`, using them does not compile
+        let _: () = repos.weekend_days_repository;
+        let _: () = repos.date_calculation_rules_repository;
+
+        Ok(())
+    }
+}
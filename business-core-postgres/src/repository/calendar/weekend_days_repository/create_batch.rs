@@ -16,13 +16,16 @@ impl WeekendDaysRepositoryImpl {
         if items.is_empty() {
             return Ok(Vec::new());
         }
+        for item in &items {
+            item.validate()?;
+        }
 
         let mut saved_items = Vec::new();
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("calendar_weekend_days", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -89,6 +92,7 @@ impl WeekendDaysRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, WeekendDaysModel> for WeekendDaysRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "weekend_days", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<WeekendDaysModel>,
@@ -113,7 +117,7 @@ mod tests {
 
         let mut items = Vec::new();
         for _ in 0..3 {
-            let item = create_test_weekend_days(None, None);
+            let item = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
             items.push(item);
         }
 
@@ -130,6 +134,44 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_create_batch_rejects_invalid_weekend_days() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+        use business_core_db::models::calendar::weekend_days::WeekendDaysError;
+
+        let ctx = setup_test_context().await?;
+        let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
+
+        let mut duplicate = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
+        duplicate.weekend_day_03 = Some(CalendarWeekday::Sunday);
+        let mut gap = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
+        gap.weekend_day_02 = None;
+        gap.weekend_day_04 = Some(CalendarWeekday::Friday);
+        let unscoped = create_test_weekend_days(None, None);
+
+        for (item, expected) in [
+            (duplicate, WeekendDaysError::DuplicateDay { day: CalendarWeekday::Sunday }),
+            (gap, WeekendDaysError::SlotGap { slot: 4 }),
+            (unscoped, WeekendDaysError::MissingScope),
+        ] {
+            let id = item.id;
+            let valid = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
+            let valid_id = valid.id;
+            let error = weekend_days_repo
+                .create_batch(vec![valid, item], None)
+                .await
+                .expect_err("Invalid weekend days must be rejected");
+            assert_eq!(error.downcast_ref::<WeekendDaysError>(), Some(&expected));
+
+            // Nothing of the batch was written
+            let cache = weekend_days_repo.weekend_days_cache.read().await;
+            assert!(!cache.contains(&id));
+            assert!(!cache.contains(&valid_id));
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_batch_empty() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
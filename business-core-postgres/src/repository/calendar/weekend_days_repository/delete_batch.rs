@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use business_core_db::repository::delete_batch::DeleteBatch;
-use sqlx::Postgres;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
+use sqlx::{Postgres, Row};
 use std::error::Error;
 use uuid::Uuid;
 
@@ -10,22 +11,22 @@ impl WeekendDaysRepositoryImpl {
     pub(super) async fn delete_batch_impl(
         repo: &WeekendDaysRepositoryImpl,
         ids: &[Uuid],
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         // Delete from index table first
         let delete_idx_query = r#"DELETE FROM calendar_weekend_days_idx WHERE id = ANY($1)"#;
-        let delete_query = r#"DELETE FROM calendar_weekend_days WHERE id = ANY($1)"#;
+        let delete_query = r#"DELETE FROM calendar_weekend_days WHERE id = ANY($1) RETURNING id"#;
 
-        let rows_affected = {
-            let mut tx = repo.executor.tx.lock().await;
+        let deleted: Vec<Uuid> = {
+            let mut tx = repo.operation_timeout.lock("calendar_weekend_days", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             sqlx::query(delete_idx_query).bind(ids).execute(&mut **transaction).await?;
-            let result = sqlx::query(delete_query).bind(ids).execute(&mut **transaction).await?;
-            result.rows_affected() as usize
+            let rows = sqlx::query(delete_query).bind(ids).fetch_all(&mut **transaction).await?;
+            rows.iter().map(|row| row.get("id")).collect()
         }; // Transaction lock released here
         
         // Update BOTH caches after releasing transaction lock
@@ -33,23 +34,36 @@ impl WeekendDaysRepositoryImpl {
             let idx_cache = repo.weekend_days_idx_cache.read().await;
             let main_cache = repo.weekend_days_cache.read().await;
             
-            for id in ids {
+            for id in &deleted {
                 idx_cache.remove(id);
                 main_cache.remove(id);
             }
         }
         
-        Ok(rows_affected)
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for WeekendDaysRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "weekend_days", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         _audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for WeekendDaysRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        _audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids).await
     }
 }
@@ -69,7 +83,7 @@ mod tests {
 
         let mut items = Vec::new();
         for _ in 0..3 {
-            let item = create_test_weekend_days(None, None);
+            let item = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
             items.push(item);
         }
 
@@ -40,8 +40,8 @@ mod tests {
         let ctx = setup_test_context().await?;
         let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
 
-        let item1 = create_test_weekend_days(None, None);
-        let item2 = create_test_weekend_days(None, None);
+        let item1 = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
+        let item2 = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
 
         let saved_items = weekend_days_repo.create_batch(vec![item1.clone(), item2.clone()], None).await?;
 
@@ -31,7 +31,7 @@ mod tests {
         let country_id = Uuid::new_v4();
         let item1 = create_test_weekend_days(Some(country_id), None);
         let item2 = create_test_weekend_days(Some(country_id), None);
-        let item3 = create_test_weekend_days(None, None);
+        let item3 = create_test_weekend_days(None, Some(Uuid::new_v4()));
         
         let _saved = weekend_days_repo.create_batch(vec![item1, item2, item3], None).await?;
 
@@ -31,7 +31,7 @@ mod tests {
         let subdivision_id = Uuid::new_v4();
         let item1 = create_test_weekend_days(None, Some(subdivision_id));
         let item2 = create_test_weekend_days(None, Some(subdivision_id));
-        let item3 = create_test_weekend_days(None, None);
+        let item3 = create_test_weekend_days(Some(Uuid::new_v4()), None);
         
         let _saved = weekend_days_repo.create_batch(vec![item1, item2, item3], None).await?;
 
@@ -42,7 +42,7 @@ impl WeekendDaysRepositoryImpl {
         // Load missing items from database
         let query = r#"SELECT * FROM calendar_weekend_days WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("calendar_weekend_days", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(&missing_ids).fetch_all(&mut **transaction).await?
         };
@@ -66,10 +66,16 @@ impl WeekendDaysRepositoryImpl {
         
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<WeekendDaysModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, WeekendDaysModel> for WeekendDaysRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "weekend_days", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
@@ -84,6 +90,10 @@ mod tests {
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::repository::load_batch::LoadBatch;
     use super::super::test_utils::test_utils::create_test_weekend_days;
+    use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+    use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+    use crate::utils::TryFromRow;
+    use sqlx::Row;
 
     #[tokio::test]
     async fn test_load_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -92,7 +102,7 @@ mod tests {
 
         let mut items = Vec::new();
         for _ in 0..3 {
-            let item = create_test_weekend_days(None, None);
+            let item = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
             items.push(item);
         }
 
@@ -114,7 +124,7 @@ mod tests {
         let ctx = setup_test_context().await?;
         let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
 
-        let items = vec![create_test_weekend_days(None, None)];
+        let items = vec![create_test_weekend_days(Some(uuid::Uuid::new_v4()), None)];
         let saved = weekend_days_repo.create_batch(items, None).await?;
         let ids: Vec<_> = saved.iter().map(|i| i.id).collect();
 
@@ -128,4 +138,58 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_calendar_weekday_round_trip() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
+
+        {
+            let mut tx = weekend_days_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            for day in CalendarWeekday::ALL {
+                let row = sqlx::query("SELECT $1::weekday AS day")
+                    .bind(day)
+                    .fetch_one(&mut **transaction)
+                    .await?;
+                let decoded: CalendarWeekday = row.get("day");
+                assert_eq!(decoded, day);
+                assert_eq!(CalendarWeekday::from(chrono::Weekday::from(day)), day);
+            }
+        }
+
+        let mut item = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
+        item.set_weekend_days(&CalendarWeekday::ALL);
+        let saved = weekend_days_repo.create_batch(vec![item], None).await?;
+
+        // Read back from the table, the cache would return the model as written
+        let mut tx = weekend_days_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let row = sqlx::query("SELECT * FROM calendar_weekend_days WHERE id = $1")
+            .bind(saved[0].id)
+            .fetch_one(&mut **transaction)
+            .await?;
+        let loaded = WeekendDaysModel::try_from_row(&row)?;
+        assert_eq!(loaded.weekend_days(), CalendarWeekday::ALL.to_vec());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
+
+        let saved = weekend_days_repo
+            .create_batch(vec![create_test_weekend_days(Some(uuid::Uuid::new_v4()), None)], None)
+            .await?;
+
+        let loaded = weekend_days_repo.load(saved[0].id).await?;
+        assert_eq!(loaded.map(|item| item.id), Some(saved[0].id));
+
+        assert!(weekend_days_repo.load(uuid::Uuid::new_v4()).await?.is_none());
+
+        Ok(())
+    }
+}
+
@@ -8,10 +8,16 @@ use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct WeekendDaysRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub weekend_days_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<WeekendDaysIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub weekend_days_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<WeekendDaysIdxModel>>>,
     pub weekend_days_cache: Arc<RwLock<TransactionAwareMainModelCache<WeekendDaysModel>>>,
 }
 
@@ -23,6 +29,8 @@ impl WeekendDaysRepositoryImpl {
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            weekend_days_idx_shared_cache: weekend_days_idx_cache.clone(),
             weekend_days_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 weekend_days_idx_cache,
             ))),
@@ -34,10 +42,14 @@ impl WeekendDaysRepositoryImpl {
 
     pub async fn load_all_weekend_days_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<WeekendDaysIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM calendar_weekend_days_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("calendar_weekend_days", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -95,4 +107,19 @@ impl TryFromRow<PgRow> for WeekendDaysIdxModel {
             country_subdivision_id: row.get("country_subdivision_id"),
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl RefreshIdxCache for WeekendDaysRepositoryImpl {
+    type Idx = WeekendDaysIdxModel;
+
+    const ENTITY: &'static str = "weekend_days";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<WeekendDaysIdxModel>>> {
+        &self.weekend_days_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<WeekendDaysIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_weekend_days_idx(&self.executor, &self.operation_timeout).await?)
+    }
+}
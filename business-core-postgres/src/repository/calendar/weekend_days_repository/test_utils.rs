@@ -1,6 +1,6 @@
 #[cfg(test)]
 pub mod test_utils {
-    use business_core_db::models::calendar::weekend_days::{WeekendDaysModel, Weekday};
+    use business_core_db::models::calendar::{calendar_weekday::CalendarWeekday, weekend_days::WeekendDaysModel};
     use chrono::NaiveDate;
     use uuid::Uuid;
 
@@ -12,8 +12,8 @@ pub mod test_utils {
             id: Uuid::new_v4(),
             country_id,
             country_subdivision_id,
-            weekend_day_01: Some(Weekday::Saturday),
-            weekend_day_02: Some(Weekday::Sunday),
+            weekend_day_01: Some(CalendarWeekday::Saturday),
+            weekend_day_02: Some(CalendarWeekday::Sunday),
             weekend_day_03: None,
             weekend_day_04: None,
             weekend_day_05: None,
@@ -16,13 +16,16 @@ impl WeekendDaysRepositoryImpl {
         if items.is_empty() {
             return Ok(Vec::new());
         }
+        for item in &items {
+            item.validate()?;
+        }
 
         let mut updated_items = Vec::new();
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("calendar_weekend_days", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -77,6 +80,7 @@ impl WeekendDaysRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, WeekendDaysModel> for WeekendDaysRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "weekend_days", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<WeekendDaysModel>,
@@ -102,7 +106,7 @@ mod tests {
 
         let mut items = Vec::new();
         for _ in 0..3 {
-            let item = create_test_weekend_days(None, None);
+            let item = create_test_weekend_days(Some(uuid::Uuid::new_v4()), None);
             items.push(item);
         }
 
@@ -146,4 +150,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_update_batch_rejects_unscoped_weekend_days() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use business_core_db::models::calendar::weekend_days::WeekendDaysError;
+
+        let ctx = setup_test_context().await?;
+        let weekend_days_repo = &ctx.calendar_repos().weekend_days_repository;
+
+        let country_id = uuid::Uuid::new_v4();
+        let mut saved = weekend_days_repo
+            .create_batch(vec![create_test_weekend_days(Some(country_id), None)], None)
+            .await?;
+        let mut item = saved.remove(0);
+        item.country_id = None;
+
+        let error = weekend_days_repo
+            .update_batch(vec![item.clone()], None)
+            .await
+            .expect_err("Unscoped weekend days must be rejected");
+        assert_eq!(error.downcast_ref::<WeekendDaysError>(), Some(&WeekendDaysError::MissingScope));
+
+        let loaded = weekend_days_repo.load_batch(&[item.id]).await?;
+        assert_eq!(loaded[0].as_ref().and_then(|item| item.country_id), Some(country_id));
+
+        Ok(())
+    }
 }
\ No newline at end of file
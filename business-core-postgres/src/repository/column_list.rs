@@ -0,0 +1,160 @@
+use sqlx::postgres::PgArguments;
+use sqlx::query::Query;
+use sqlx::Postgres;
+
+/// A query being built, as returned by `sqlx::query`
+pub type PgQuery<'q> = Query<'q, Postgres, PgArguments>;
+
+/// Columns a model is written to and how its values are bound to them
+///
+/// The INSERT and UPDATE statements of a table are generated from the list, so a new
+/// column is added in one place: to `columns` and, at the same position, to `bind`.
+/// The first column is the primary key.
+pub struct ColumnList<T> {
+    pub columns: &'static [&'static str],
+    bind: for<'q> fn(PgQuery<'q>, &'q T) -> PgQuery<'q>,
+}
+
+impl<T> ColumnList<T> {
+    pub const fn new(columns: &'static [&'static str], bind: for<'q> fn(PgQuery<'q>, &'q T) -> PgQuery<'q>) -> Self {
+        Self { columns, bind }
+    }
+
+    /// Bind the values of `item` to `query`, in the order of `columns`
+    pub fn bind<'q>(&self, query: PgQuery<'q>, item: &'q T) -> PgQuery<'q> {
+        (self.bind)(query, item)
+    }
+
+    /// `INSERT INTO table (columns) VALUES ($1, ..)`
+    pub fn insert_sql(&self, table: &str) -> String {
+        let placeholders: Vec<String> = (1..=self.columns.len()).map(|n| format!("${n}")).collect();
+        format!(
+            "INSERT INTO {table} ({}) VALUES ({})",
+            self.columns.join(", "),
+            placeholders.join(", ")
+        )
+    }
+
+    /// `insert_sql` overwriting every column but the primary key when the row exists
+    pub fn upsert_sql(&self, table: &str) -> String {
+        let assignments: Vec<String> = self
+            .columns
+            .iter()
+            .skip(1)
+            .map(|column| format!("{column} = EXCLUDED.{column}"))
+            .collect();
+        format!(
+            "{} ON CONFLICT ({}) DO UPDATE SET {}",
+            self.insert_sql(table),
+            self.columns[0],
+            assignments.join(", ")
+        )
+    }
+
+    /// `UPDATE table SET column = $n, .. WHERE id = $1`, with `AND guard = $m` per guard
+    ///
+    /// Every column but the primary key is set. The guard values are bound after the
+    /// columns, in the order of `guards`.
+    pub fn update_sql(&self, table: &str, guards: &[&str]) -> String {
+        let assignments: Vec<String> = self
+            .columns
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, column)| format!("{column} = ${}", index + 1))
+            .collect();
+        let mut conditions = vec![format!("{} = $1", self.columns[0])];
+        conditions.extend(
+            guards
+                .iter()
+                .enumerate()
+                .map(|(index, guard)| format!("{guard} = ${}", self.columns.len() + index + 1)),
+        );
+        format!(
+            "UPDATE {table} SET {} WHERE {}",
+            assignments.join(", "),
+            conditions.join(" AND ")
+        )
+    }
+}
+
+/// Write statements of an audited table and of its `_audit` and `_idx` tables
+///
+/// Built once per model from its column lists.
+pub struct AuditedTableSql {
+    pub insert: String,
+    pub insert_audit: String,
+    /// Guarded by the previous `hash` and `audit_log_id`, bound after the columns
+    pub update: String,
+    pub insert_idx: String,
+    pub update_idx: String,
+    /// Insert or overwrite of the idx row, for rebuilding it from the main row
+    pub upsert_idx: String,
+}
+
+impl AuditedTableSql {
+    pub fn new<T, I>(table: &str, columns: &ColumnList<T>, idx_columns: &ColumnList<I>) -> Self {
+        let idx_table = format!("{table}_idx");
+        Self {
+            insert: columns.insert_sql(table),
+            insert_audit: columns.insert_sql(&format!("{table}_audit")),
+            update: columns.update_sql(table, &["hash", "audit_log_id"]),
+            insert_idx: idx_columns.insert_sql(&idx_table),
+            update_idx: idx_columns.update_sql(&idx_table, &[]),
+            upsert_idx: idx_columns.upsert_sql(&idx_table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditedTableSql, ColumnList, PgQuery};
+
+    struct Row {
+        id: i64,
+        name: String,
+        hash: i64,
+        audit_log_id: i64,
+    }
+
+    fn bind_row<'q>(query: PgQuery<'q>, item: &'q Row) -> PgQuery<'q> {
+        query.bind(item.id).bind(item.name.as_str()).bind(item.hash).bind(item.audit_log_id)
+    }
+
+    fn bind_row_idx<'q>(query: PgQuery<'q>, item: &'q Row) -> PgQuery<'q> {
+        query.bind(item.id).bind(item.hash)
+    }
+
+    const ROW_COLUMNS: ColumnList<Row> = ColumnList::new(&["id", "name", "hash", "audit_log_id"], bind_row);
+    const ROW_IDX_COLUMNS: ColumnList<Row> = ColumnList::new(&["id", "name_hash"], bind_row_idx);
+
+    #[test]
+    fn test_generated_sql() {
+        let sql = AuditedTableSql::new("row", &ROW_COLUMNS, &ROW_IDX_COLUMNS);
+
+        assert_eq!(sql.insert, "INSERT INTO row (id, name, hash, audit_log_id) VALUES ($1, $2, $3, $4)");
+        assert_eq!(
+            sql.insert_audit,
+            "INSERT INTO row_audit (id, name, hash, audit_log_id) VALUES ($1, $2, $3, $4)"
+        );
+        assert_eq!(
+            sql.update,
+            "UPDATE row SET name = $2, hash = $3, audit_log_id = $4 WHERE id = $1 AND hash = $5 AND audit_log_id = $6"
+        );
+        assert_eq!(sql.insert_idx, "INSERT INTO row_idx (id, name_hash) VALUES ($1, $2)");
+        assert_eq!(sql.update_idx, "UPDATE row_idx SET name_hash = $2 WHERE id = $1");
+        assert_eq!(
+            sql.upsert_idx,
+            "INSERT INTO row_idx (id, name_hash) VALUES ($1, $2) ON CONFLICT (id) DO UPDATE SET name_hash = EXCLUDED.name_hash"
+        );
+
+        let row = Row {
+            id: 1,
+            name: "name".to_string(),
+            hash: 2,
+            audit_log_id: 3,
+        };
+        let _insert = ROW_COLUMNS.bind(sqlx::query(&sql.insert), &row);
+        let _update_idx = ROW_IDX_COLUMNS.bind(sqlx::query(&sql.update_idx), &row);
+    }
+}
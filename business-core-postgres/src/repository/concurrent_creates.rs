@@ -0,0 +1,277 @@
+use business_core_db::models::identifiable::Identifiable;
+use business_core_db::repository::create_batch::CreateBatch;
+use futures::future::{try_join_all, BoxFuture};
+use sqlx::Postgres;
+use std::error::Error;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+/// Independent `create_batch` calls of one unit of work, run concurrently
+///
+/// # Concurrency model
+///
+/// All repositories built from a session share its executor, and with it one sqlx
+/// transaction on one connection behind a lock. A transaction cannot be shared across
+/// connections, and spreading the creates over separate transactions would need two
+/// phase commit (`PREPARE TRANSACTION`), which Postgres only allows when
+/// `max_prepared_transactions` is raised and which leaves prepared transactions to
+/// recover after a crash. The creates therefore stay on the session transaction.
+///
+/// A create has two phases. Its preparation, the future given to `create_with`, builds
+/// the items: loading, validating, reading documents. The preparations of all creates
+/// run concurrently. Its write is the `create_batch` call, and the writes run one at a
+/// time in the order the creates were added, each once its own preparation is done. A
+/// write therefore overlaps the preparations still running, and the statements reach
+/// the session in a fixed order: a create may reference rows written by an earlier
+/// create of the same run.
+///
+/// # Atomicity
+///
+/// `run` returns the first error and drops the creates still pending. Nothing is
+/// committed by `run`: as with any failed write, the caller rolls back the unit of
+/// work, which discards the writes of the creates that succeeded.
+pub struct ConcurrentCreates<'a> {
+    creates: Vec<BoxFuture<'a, Result<(), Box<dyn Error + Send + Sync>>>>,
+    /// Position of the create whose write may run
+    turn: Arc<watch::Sender<usize>>,
+}
+
+impl Default for ConcurrentCreates<'_> {
+    fn default() -> Self {
+        Self {
+            creates: Vec::new(),
+            turn: Arc::new(watch::Sender::new(0)),
+        }
+    }
+}
+
+impl<'a> ConcurrentCreates<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a create of `items` through `repo`
+    pub fn create<T, R>(self, repo: &'a R, items: Vec<T>, audit_log_id: Option<Uuid>) -> Self
+    where
+        T: Identifiable + Send + 'a,
+        R: CreateBatch<Postgres, T> + ?Sized,
+    {
+        self.create_with(repo, async move { Ok(items) }, audit_log_id)
+    }
+
+    /// Add a create through `repo` of the items returned by `prepare`
+    ///
+    /// `prepare` runs concurrently with the other creates, the write waits for the
+    /// writes of the creates added before.
+    pub fn create_with<T, R, F>(mut self, repo: &'a R, prepare: F, audit_log_id: Option<Uuid>) -> Self
+    where
+        T: Identifiable + Send + 'a,
+        R: CreateBatch<Postgres, T> + ?Sized,
+        F: Future<Output = Result<Vec<T>, Box<dyn Error + Send + Sync>>> + Send + 'a,
+    {
+        let position = self.creates.len();
+        let mut turn = self.turn.subscribe();
+        let next_turn = Arc::clone(&self.turn);
+        self.creates.push(Box::pin(async move {
+            let items = prepare.await?;
+            turn.wait_for(|turn| *turn == position).await?;
+            repo.create_batch(items, audit_log_id).await?;
+            next_turn.send_replace(position + 1);
+            Ok(())
+        }));
+        self
+    }
+
+    /// Number of creates added
+    pub fn len(&self) -> usize {
+        self.creates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty()
+    }
+
+    /// Run all creates, returning the first error
+    pub async fn run(self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        try_join_all(self.creates).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConcurrentCreates;
+    use crate::error::RepositoryError;
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_country, create_test_country_subdivision, create_test_locality,
+        create_test_person,
+    };
+    use crate::test_helper::setup_test_context;
+    use async_trait::async_trait;
+    use business_core_db::models::identifiable::Identifiable;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use sqlx::Postgres;
+    use std::error::Error;
+    use std::time::Duration;
+    use tokio::sync::Notify;
+    use uuid::Uuid;
+
+    /// Signals once the statements of its repository's create have run
+    struct WrittenSignal<'a, R: ?Sized> {
+        inner: &'a R,
+        written: &'a Notify,
+    }
+
+    #[async_trait]
+    impl<T, R> CreateBatch<Postgres, T> for WrittenSignal<'_, R>
+    where
+        T: Identifiable + Send + 'static,
+        R: CreateBatch<Postgres, T> + ?Sized,
+    {
+        async fn create_batch(
+            &self,
+            items: Vec<T>,
+            audit_log_id: Option<Uuid>,
+        ) -> Result<Vec<T>, Box<dyn Error + Send + Sync>> {
+            let saved = self.inner.create_batch(items, audit_log_id).await?;
+            self.written.notify_one();
+            Ok(saved)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_overlaps_pending_preparations() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let country_repo = &ctx.person_repos().country_repository;
+        let subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let written = Notify::new();
+        let country_signal = WrittenSignal { inner: country_repo.as_ref(), written: &written };
+        let country = create_test_country("QC", "Concurrent Country");
+        let subdivision = create_test_country_subdivision(country.id, "QC-1", "Concurrent Subdivision");
+        let person = create_test_person("Concurrent Person");
+        let (country_id, subdivision_id, person_id) = (country.id, subdivision.id, person.id);
+
+        // The person preparation only finishes once the country is in the database, it
+        // never would if the writes waited for all preparations
+        let prepare_person = async {
+            written.notified().await;
+            let countries = country_repo.load_batch(&[country_id]).await?;
+            assert!(countries[0].is_some(), "The country write must precede the end of the preparation");
+            Ok::<_, Box<dyn Error + Send + Sync>>(vec![person])
+        };
+
+        let creates = ConcurrentCreates::new()
+            .create(&country_signal, vec![country], None)
+            .create_with(person_repo.as_ref(), prepare_person, Some(audit_log.id))
+            .create(subdivision_repo.as_ref(), vec![subdivision], None);
+        assert_eq!(creates.len(), 3);
+        tokio::time::timeout(Duration::from_secs(10), creates.run())
+            .await
+            .map_err(|_| "The country write must run while the person is prepared")??;
+
+        assert!(person_repo.load_batch(&[person_id]).await?[0].is_some());
+        assert!(subdivision_repo.load_batch(&[subdivision_id]).await?[0].is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writes_follow_the_order_of_the_creates() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        let country = create_test_country("QO", "Ordered Country");
+        let subdivision = create_test_country_subdivision(country.id, "QO-1", "Ordered Subdivision");
+        let locality = create_test_locality(subdivision.id, "QO-1-1", "Ordered Locality");
+        let locality_id = locality.id;
+
+        // The locality is ready first and the country last, each references the one before
+        let locality_prepared = Notify::new();
+        let prepare_country = async {
+            locality_prepared.notified().await;
+            Ok::<_, Box<dyn Error + Send + Sync>>(vec![country])
+        };
+        let prepare_locality = async {
+            locality_prepared.notify_one();
+            Ok::<_, Box<dyn Error + Send + Sync>>(vec![locality])
+        };
+
+        ConcurrentCreates::new()
+            .create_with(country_repo.as_ref(), prepare_country, None)
+            .create(subdivision_repo.as_ref(), vec![subdivision], None)
+            .create_with(locality_repo.as_ref(), prepare_locality, None)
+            .run()
+            .await?;
+
+        assert!(locality_repo.load_batch(&[locality_id]).await?[0].is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_create_rolls_back_the_run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let country_repo = &ctx.person_repos().country_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // The audit log of the last person is never persisted
+        let missing_audit_log = create_test_audit_log();
+        let person = create_test_person("Rolled Back Person");
+        let orphan = create_test_person("Person Without Audit Log");
+        let country = create_test_country("QR", "Rolled Back Country");
+        let (country_id, person_id) = (country.id, person.id);
+
+        let error = ConcurrentCreates::new()
+            .create(country_repo.as_ref(), vec![country], None)
+            .create(person_repo.as_ref(), vec![person], Some(audit_log.id))
+            .create(person_repo.as_ref(), vec![orphan], Some(missing_audit_log.id))
+            .run()
+            .await
+            .expect_err("A failed create must fail the run");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::ForeignKeyViolation { entity, referenced_table, .. }) => {
+                assert_eq!(entity, "person");
+                assert_eq!(referenced_table, "audit_log");
+            }
+            other => panic!("Expected ForeignKeyViolation, got {other:?}"),
+        }
+
+        // The country and the first person were written before the failure, roll the
+        // aborted transaction back as the unit of work would
+        {
+            let mut tx = country_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            assert!(sqlx::query("SELECT 1").execute(&mut **transaction).await.is_err());
+            tx.take().ok_or("Transaction has been consumed")?.rollback().await?;
+        }
+
+        // Nothing of the run reached the database
+        let countries: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM country WHERE id = $1")
+            .bind(country_id)
+            .fetch_one(ctx.pool().as_ref())
+            .await?;
+        let persons: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM person WHERE id = $1")
+            .bind(person_id)
+            .fetch_one(ctx.pool().as_ref())
+            .await?;
+        assert_eq!((countries, persons), (0, 0));
+
+        Ok(())
+    }
+}
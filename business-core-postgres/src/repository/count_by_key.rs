@@ -0,0 +1,39 @@
+use postgres_unit_of_work::Executor;
+use sqlx::Postgres;
+use std::error::Error;
+
+use crate::error::RepositoryError;
+use crate::repository::operation_timeout::OperationTimeout;
+
+/// Fail with `RepositoryError::UnknownIndexKey` unless `key_name` is one of `keys`
+pub(crate) fn check_index_key(entity: &str, keys: &[&str], key_name: &str) -> Result<(), RepositoryError> {
+    if keys.contains(&key_name) {
+        Ok(())
+    } else {
+        Err(RepositoryError::UnknownIndexKey {
+            entity: entity.to_string(),
+            key: key_name.to_string(),
+        })
+    }
+}
+
+/// Count the rows of an idx table where `column` equals `value`
+///
+/// `table` and `column` go into the query text, so `column` must have been checked
+/// with `check_index_key` first.
+pub(crate) async fn count_idx_rows<T>(
+    executor: &Executor,
+    operation_timeout: &OperationTimeout,
+    table: &str,
+    column: &str,
+    value: T,
+) -> Result<usize, Box<dyn Error + Send + Sync>>
+where
+    T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Send + 'static,
+{
+    let query = format!("SELECT count(*) FROM {table} WHERE {column} = $1");
+    let mut tx = operation_timeout.lock(table, &executor.tx).await?;
+    let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+    let count: i64 = sqlx::query_scalar(&query).bind(value).fetch_one(&mut **transaction).await?;
+    Ok(count as usize)
+}
@@ -3,9 +3,28 @@
 //! This module provides functions to initialize and cleanup the PostgreSQL
 //! database schema by executing SQL migration and cleanup files.
 
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use std::fs;
 use std::path::Path;
+use thiserror::Error;
+
+/// Schema version the repositories of this crate are written against
+///
+/// Recorded in the schema_version table by the migration of the same number.
+pub const SCHEMA_VERSION: i32 = 35;
+
+/// Why `check_schema_version` refused the database
+#[derive(Debug, Error)]
+pub enum SchemaMismatch {
+    #[error("Schema version {expected} is not recorded, the database is not migrated")]
+    Missing { expected: i32 },
+
+    #[error("Schema version {expected} is not recorded, the database is at version {found}")]
+    Unexpected { expected: i32, found: i32 },
+
+    #[error("Cannot read the schema version: {0}")]
+    Database(#[from] sqlx::Error),
+}
 
 /// Initialize the database by executing migration files in ascending order
 ///
@@ -49,6 +68,51 @@ pub async fn cleanup_database(pool: &PgPool) -> Result<(), sqlx::Error> {
     execute_sql_files_in_order(pool, &cleanup_dir, false).await
 }
 
+/// Check that the database went through the migrations of `SCHEMA_VERSION`
+///
+/// Meant to be called once at startup, before building the repository factories, so
+/// a database missing migrations is refused up front instead of failing later on a
+/// missing column.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use sqlx::PgPool;
+/// use business_core_postgres::repository::db_init::check_schema_version;
+///
+/// # async fn example(pool: &PgPool) -> Result<(), Box<dyn std::error::Error>> {
+/// check_schema_version(pool).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn check_schema_version(pool: &PgPool) -> Result<(), SchemaMismatch> {
+    let mut conn = pool.acquire().await?;
+    check_schema_version_in_connection(&mut conn).await
+}
+
+/// `check_schema_version` on an already acquired connection
+pub async fn check_schema_version_in_connection(conn: &mut PgConnection) -> Result<(), SchemaMismatch> {
+    let table_exists: bool = sqlx::query_scalar("SELECT to_regclass('schema_version') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    if !table_exists {
+        return Err(SchemaMismatch::Missing { expected: SCHEMA_VERSION });
+    }
+
+    let (recorded, latest): (bool, Option<i32>) = sqlx::query_as(
+        "SELECT bool_or(version = $1) IS TRUE, MAX(version) FROM schema_version",
+    )
+    .bind(SCHEMA_VERSION)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    match (recorded, latest) {
+        (true, _) => Ok(()),
+        (false, Some(found)) => Err(SchemaMismatch::Unexpected { expected: SCHEMA_VERSION, found }),
+        (false, None) => Err(SchemaMismatch::Missing { expected: SCHEMA_VERSION }),
+    }
+}
+
 /// Execute SQL files from a directory in the specified order
 ///
 /// # Arguments
@@ -115,4 +179,30 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_check_schema_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = crate::test_helper::setup_test_context().await?;
+
+        check_schema_version(ctx.pool()).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_schema_version_without_version_row() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = crate::test_helper::setup_test_context().await?;
+
+        // The schema is shared between tests, the rows are only removed inside this transaction
+        let mut tx = ctx.pool().begin().await?;
+        sqlx::query("DELETE FROM schema_version")
+            .execute(&mut *tx)
+            .await?;
+        let result = check_schema_version_in_connection(&mut tx).await;
+        tx.rollback().await?;
+
+        assert!(matches!(result, Err(SchemaMismatch::Missing { expected }) if expected == SCHEMA_VERSION));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use sqlx::PgConnection;
+use std::error::Error;
+
+/// Open the savepoint a dry run writes under
+///
+/// Everything executed on `conn` until `end_dry_run` runs against the database,
+/// so SQL-level constraints are checked, and is then discarded.
+pub(crate) async fn begin_dry_run(conn: &mut PgConnection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    sqlx::query("SAVEPOINT repository_dry_run").execute(&mut *conn).await?;
+    Ok(())
+}
+
+/// Roll back to the dry run savepoint and release it
+///
+/// Also recovers a transaction left aborted by a failed statement of the dry run.
+pub(crate) async fn end_dry_run(conn: &mut PgConnection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    sqlx::query("ROLLBACK TO SAVEPOINT repository_dry_run").execute(&mut *conn).await?;
+    sqlx::query("RELEASE SAVEPOINT repository_dry_run").execute(&mut *conn).await?;
+    Ok(())
+}
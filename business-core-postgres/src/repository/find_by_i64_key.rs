@@ -0,0 +1,439 @@
+use async_trait::async_trait;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::{HasPrimaryKey, Indexable};
+use postgres_index_cache::TransactionAwareIdxModelCache;
+use postgres_unit_of_work::Executor;
+use sqlx::postgres::PgRow;
+use sqlx::Postgres;
+use std::collections::HashSet;
+use std::error::Error;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::{map_db_error, RepositoryError};
+use crate::repository::cache_capacity::{CacheBound, KeyValue};
+use crate::repository::cache_health::{CacheHealth, Freshness};
+use crate::repository::operation_timeout::OperationTimeout;
+use crate::utils::TryFromRow;
+
+/// Lookup of the index models of a repository by one of their i64 keys
+///
+/// The `find_by_*_hash` finders delegate to `find_by_i64_key`, which checks the key
+/// name, then answers from the index cache or, when the repository does not serve
+/// finders from its cache, from its idx table. `find_by_i64_key_with_freshness` lets
+/// the caller trade the cache for SQL while the notification feed is degraded.
+///
+/// The keys are hashes: `find_matching_by_i64_key` loads the candidates and keeps
+/// those holding the hashed value, so callers never see a collision.
+///
+/// The reads of the idx table behind the cache policies are shared here as well, so a
+/// cached repository without i64 keys implements the trait with empty `I64_KEYS`.
+#[async_trait]
+pub trait FindByI64Key: Send + Sync {
+    type Idx: HasPrimaryKey + Indexable + Clone + TryFromRow<PgRow> + Send + Sync + 'static;
+
+    /// Entity name used in errors, the idx table is `{ENTITY}_idx`
+    const ENTITY: &'static str;
+
+    /// Keys of `Indexable::i64_keys`, each stored in the idx table column of the same name
+    const I64_KEYS: &'static [&'static str];
+
+    fn executor(&self) -> &Executor;
+
+    /// Bounds on the wait for the transaction lock of `executor`
+    fn operation_timeout(&self) -> &OperationTimeout;
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<Self::Idx>>;
+
+    /// Whether finders can be answered from the cache alone
+    fn serves_from_cache(&self) -> bool {
+        true
+    }
+
+    /// Whether the finders of `key` = `value` can be answered from the cache alone
+    ///
+    /// A bounded cache answers the key values whose rows are all cached, see
+    /// `CacheCapacity`.
+    fn serves_key_from_cache(&self, key: &str, value: KeyValue) -> bool {
+        self.serves_from_cache() || self.cache_bound().is_some_and(|bound| bound.is_complete(key, value))
+    }
+
+    /// Whether index models read from the idx table are added to the cache
+    fn maintains_cache(&self) -> bool {
+        true
+    }
+
+    /// Health of the notifications keeping the cache in sync, `None` when not tracked
+    fn cache_health(&self) -> Option<&CacheHealth> {
+        None
+    }
+
+    /// Bound on the cache of this repository, `None` when unbounded
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        None
+    }
+
+    /// Called with the ids of the index models just added to or read from the cache
+    fn record_cache_use(&self, ids: &[Uuid]) {
+        if let Some(bound) = self.cache_bound() {
+            bound.record_use(ids);
+        }
+    }
+
+    /// Index models whose `column` equals `value`, read from the idx table
+    ///
+    /// `column` is put in the query as is, callers pass a known column name. The
+    /// models are added to the cache when the repository maintains it, and a bounded
+    /// cache then holds all rows of `column` = `value`.
+    async fn find_idx_by_column<V>(&self, column: &str, value: V) -> Result<Vec<Self::Idx>, Box<dyn Error + Send + Sync>>
+    where
+        V: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Into<KeyValue> + Copy + Send + 'static,
+    {
+        let query = format!("SELECT * FROM {}_idx WHERE {column} = $1", Self::ENTITY);
+        let epoch = self.cache_bound().map(CacheBound::epoch);
+        let rows = {
+            let mut tx = self.operation_timeout().lock(Self::ENTITY, &self.executor().tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(&query)
+                .bind(value)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error(Self::ENTITY, e))?
+        };
+        let items = rows.iter().map(Self::Idx::try_from_row).collect::<Result<Vec<_>, _>>()?;
+        self.populate_cache(&items).await;
+        if let (true, Some(bound), Some(epoch)) = (self.maintains_cache(), self.cache_bound(), epoch) {
+            bound.record_complete(column, value.into(), epoch);
+        }
+        Ok(items)
+    }
+
+    /// Index models of `ids` read from the idx table, added to the cache as by `find_idx_by_column`
+    async fn load_idx_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Self::Idx>, Box<dyn Error + Send + Sync>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = format!("SELECT * FROM {}_idx WHERE id = ANY($1)", Self::ENTITY);
+        let rows = {
+            let mut tx = self.operation_timeout().lock(Self::ENTITY, &self.executor().tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(&query)
+                .bind(ids)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error(Self::ENTITY, e))?
+        };
+        let items = rows.iter().map(Self::Idx::try_from_row).collect::<Result<Vec<_>, _>>()?;
+        self.populate_cache(&items).await;
+        Ok(items)
+    }
+
+    /// Put index models read from the idx table in the cache, when it is maintained
+    ///
+    /// A cached entry is replaced by the row: the entry of a repository not kept in sync
+    /// through notifications may be older than the row just read.
+    async fn populate_cache(&self, items: &[Self::Idx]) {
+        if !self.maintains_cache() {
+            return;
+        }
+        {
+            let cache = self.idx_cache().read().await;
+            for item in items {
+                let id = item.primary_key();
+                if cache.contains_primary(&id) {
+                    cache.remove(&id);
+                }
+                cache.add(item.clone());
+            }
+        }
+        let ids: Vec<Uuid> = items.iter().map(HasPrimaryKey::primary_key).collect();
+        self.record_cache_use(&ids);
+    }
+
+    /// Whether each of `ids` has an idx row
+    ///
+    /// A maintained cache answers first, a miss is read from the idx table unless the
+    /// repository serves finders from its cache. Without a maintained cache, all ids are
+    /// read from the idx table.
+    async fn exist_idx_by_ids(&self, ids: &[Uuid]) -> Result<Vec<(Uuid, bool)>, Box<dyn Error + Send + Sync>> {
+        let mut result = Vec::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        if self.maintains_cache() {
+            {
+                let cache = self.idx_cache().read().await;
+                for &id in ids {
+                    let cached = cache.contains_primary(&id);
+                    if !cached && !self.serves_from_cache() {
+                        missing.push(id);
+                    }
+                    result.push((id, cached));
+                }
+            }
+            let hits: Vec<Uuid> = result.iter().filter(|(_, cached)| *cached).map(|(id, _)| *id).collect();
+            self.record_cache_use(&hits);
+        } else {
+            missing.extend_from_slice(ids);
+            result.extend(ids.iter().map(|&id| (id, false)));
+        }
+
+        if !missing.is_empty() {
+            let found: HashSet<Uuid> = self
+                .load_idx_by_ids(&missing)
+                .await?
+                .iter()
+                .map(HasPrimaryKey::primary_key)
+                .collect();
+            for (id, exists) in result.iter_mut() {
+                if found.contains(id) {
+                    *exists = true;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Index models whose i64 key `key_name` equals `value`
+    ///
+    /// A `key_name` outside `I64_KEYS` fails with `RepositoryError::UnknownIndexKey`.
+    async fn find_by_i64_key(
+        &self,
+        key_name: &str,
+        value: i64,
+    ) -> Result<Vec<Self::Idx>, Box<dyn Error + Send + Sync>> {
+        self.find_by_i64_key_with_freshness(key_name, value, Freshness::CacheOk).await
+    }
+
+    /// Index models whose i64 key `key_name` equals `value`, as fresh as `freshness` asks
+    ///
+    /// `Freshness::RequireFresh` reads the idx table while `cache_health` is degraded,
+    /// `Freshness::CacheOnly` reads the cache even when the repository does not serve
+    /// finders from it.
+    async fn find_by_i64_key_with_freshness(
+        &self,
+        key_name: &str,
+        value: i64,
+        freshness: Freshness,
+    ) -> Result<Vec<Self::Idx>, Box<dyn Error + Send + Sync>> {
+        if !Self::I64_KEYS.contains(&key_name) {
+            return Err(RepositoryError::UnknownIndexKey {
+                entity: Self::ENTITY.to_string(),
+                key: key_name.to_string(),
+            }
+            .into());
+        }
+        let from_cache = self.serves_key_from_cache(key_name, KeyValue::I64(value));
+        let from_sql = match freshness {
+            Freshness::CacheOk => !from_cache,
+            Freshness::RequireFresh => !from_cache || self.cache_health().is_some_and(CacheHealth::is_degraded),
+            Freshness::CacheOnly => false,
+        };
+        if from_sql {
+            return self.find_idx_by_column(key_name, value).await;
+        }
+        let cache = self.idx_cache().read().await;
+        Ok(cache.get_by_i64_index(key_name, &value))
+    }
+
+    /// Models whose i64 key `key_name` equals `value` and that hold the hashed value
+    ///
+    /// `candidate` narrows the index models found, e.g. to a parent or a hash version,
+    /// before their models are loaded. `matches` compares a loaded model with the value
+    /// that was hashed, so a model only sharing the hash is left out.
+    async fn find_matching_by_i64_key<M, C, F>(
+        &self,
+        key_name: &str,
+        value: i64,
+        candidate: C,
+        matches: F,
+    ) -> Result<Vec<M>, Box<dyn Error + Send + Sync>>
+    where
+        Self: LoadBatch<Postgres, M>,
+        M: Send + 'static,
+        C: Fn(&Self::Idx) -> bool + Send + Sync,
+        F: Fn(&M) -> bool + Send + Sync,
+    {
+        let ids: Vec<Uuid> = self
+            .find_by_i64_key(key_name, value)
+            .await?
+            .iter()
+            .filter(|idx| candidate(idx))
+            .map(HasPrimaryKey::primary_key)
+            .collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .load_batch(&ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .filter(|model| matches(model))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FindByI64Key;
+    use crate::error::RepositoryError;
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason;
+    use crate::repository::reason_and_purpose::ReasonRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonModel;
+    use business_core_db::models::IndexAware;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use business_core_db::Indexable;
+    use heapless::String as HeaplessString;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    fn key_names<T: FindByI64Key>() -> HashSet<String> {
+        T::I64_KEYS.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_i64_keys_match_indexable() {
+        let person_idx = create_test_person("Keys").to_index();
+        assert_eq!(
+            key_names::<PersonRepositoryImpl>(),
+            person_idx.i64_keys().into_keys().collect::<HashSet<_>>()
+        );
+        let reason_idx = create_test_reason("KEYS", "Keys").to_index();
+        assert_eq!(
+            key_names::<ReasonRepositoryImpl>(),
+            reason_idx.i64_keys().into_keys().collect::<HashSet<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delegated_finders_match_find_by_i64_key() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let executor = ctx.person_repos().person_repository.executor.clone();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        for policy in [CachePolicy::Enabled, CachePolicy::Disabled, CachePolicy::ReadThrough] {
+            let person_repo = PersonRepositoryImpl::new_with_cache_policy(
+                executor.clone(),
+                Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+                policy,
+            );
+            let reason_repo = ReasonRepositoryImpl::new_with_cache_policy(
+                executor.clone(),
+                Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+                policy,
+            );
+
+            let external_identifier = format!("I64-KEY-{policy:?}");
+            let mut person = create_test_person("I64 Key Person");
+            person.external_identifier = Some(HeaplessString::try_from(external_identifier.as_str()).unwrap());
+            let person_id = person_repo.create_batch(vec![person], Some(audit_log.id)).await?[0].id;
+
+            let code = format!("I64_KEY_{policy:?}").to_uppercase();
+            let reason_id = reason_repo
+                .create_batch(vec![create_test_reason(&code, "I64 key")], None)
+                .await?[0]
+                .id;
+
+            let hash = hash_as_i64(&external_identifier.as_str())?;
+            let by_finder = person_repo.find_by_external_identifier_hash(hash).await?;
+            let by_key = person_repo.find_by_i64_key("external_identifier_hash", hash).await?;
+            assert_eq!(by_finder.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![person_id]);
+            assert_eq!(by_key.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![person_id]);
+
+            let hash = hash_as_i64(&code.as_str())?;
+            let by_finder = reason_repo.find_by_code_hash(hash).await?;
+            let by_key = reason_repo.find_by_i64_key("code_hash", hash).await?;
+            assert_eq!(by_finder.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![reason_id]);
+            assert_eq!(by_key.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![reason_id]);
+
+            // Nothing matches an unused hash either way
+            assert!(reason_repo.find_by_code_hash(0).await?.is_empty());
+            assert!(reason_repo.find_by_i64_key("code_hash", 0).await?.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_matching_leaves_out_collisions() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let executor = ctx.person_repos().person_repository.executor.clone();
+        let sql_repo = PersonRepositoryImpl::new_with_cache_policy(
+            executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let external_identifier = "COLLIDING-KEY";
+        let mut person = create_test_person("Key Holder");
+        person.external_identifier = Some(HeaplessString::try_from(external_identifier).unwrap());
+        let person_id = sql_repo.create_batch(vec![person], Some(audit_log.id)).await?[0].id;
+        let other_id = sql_repo
+            .create_batch(vec![create_test_person("Colliding Person")], Some(audit_log.id))
+            .await?[0]
+            .id;
+
+        // Give the other person the same hash, as a collision would
+        let hash = sql_repo.hash_version.hash(&external_identifier)?;
+        {
+            let mut tx = executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET external_identifier_hash = $1 WHERE id = $2")
+                .bind(hash)
+                .bind(other_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        assert_eq!(sql_repo.find_by_i64_key("external_identifier_hash", hash).await?.len(), 2);
+
+        let matching: Vec<PersonModel> = sql_repo
+            .find_matching_by_i64_key(
+                "external_identifier_hash",
+                hash,
+                |_| true,
+                |person: &PersonModel| person.external_identifier.as_deref() == Some(external_identifier),
+            )
+            .await?;
+        assert_eq!(matching.iter().map(|person| person.id).collect::<Vec<_>>(), vec![person_id]);
+
+        // Candidates left out are not loaded
+        let matching: Vec<PersonModel> = sql_repo
+            .find_matching_by_i64_key("external_identifier_hash", hash, |idx| idx.id != person_id, |_| true)
+            .await?;
+        assert_eq!(matching.iter().map(|person| person.id).collect::<Vec<_>>(), vec![other_id]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_is_rejected() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        // Also guards the SQL fallback, which puts the key name in the query
+        let error = person_repo
+            .find_by_i64_key("id; DROP TABLE person_idx", 1)
+            .await
+            .expect_err("Only i64 keys can be looked up");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::UnknownIndexKey { entity, key }) => {
+                assert_eq!(entity, "person");
+                assert_eq!(key, "id; DROP TABLE person_idx");
+            }
+            other => panic!("Expected UnknownIndexKey, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,558 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::repository::cache_health::{CacheHealth, CacheStatus};
+
+/// Channel `notify_idx_cache_change` publishes the idx row changes on, see migration 034
+pub const IDX_CACHE_CHANNEL: &str = "idx_cache_change";
+
+/// Statement that changed an idx row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum IdxOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// A changed idx row, as notified on `IDX_CACHE_CHANNEL`
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdxNotification {
+    /// Idx table of the changed row
+    pub table: String,
+    pub op: IdxOperation,
+    /// The row after the change, or before it for a delete, as `row_to_json` writes it
+    pub row: serde_json::Value,
+}
+
+/// Payload of a notification on `IDX_CACHE_CHANNEL`
+///
+/// The statement triggers of migration 034 send the rows changed by a statement in
+/// `rows`; a payload with a single `row` is still accepted.
+#[derive(Debug, Deserialize)]
+struct IdxPayload {
+    table: String,
+    op: IdxOperation,
+    #[serde(default)]
+    row: Option<serde_json::Value>,
+    #[serde(default)]
+    rows: Vec<serde_json::Value>,
+}
+
+impl IdxNotification {
+    /// The changed rows of a notification payload, in the order of the payload
+    pub fn parse_payload(payload: &str) -> Result<Vec<Self>, Box<dyn Error + Send + Sync>> {
+        let IdxPayload { table, op, row, rows } = serde_json::from_str(payload)?;
+        Ok(row
+            .into_iter()
+            .chain(rows)
+            .map(|row| Self {
+                table: table.clone(),
+                op,
+                row,
+            })
+            .collect())
+    }
+
+    /// Id of the changed row
+    pub fn id(&self) -> Result<Uuid, Box<dyn Error + Send + Sync>> {
+        let id = self
+            .row
+            .get("id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| format!("Notification on {} without an id", self.table))?;
+        Ok(Uuid::parse_str(id)?)
+    }
+}
+
+/// Applies the notifications of one idx table to its cache
+///
+/// A handler may buffer the notifications, the listener calls `flush_if_due` on every
+/// tick of its flush interval.
+#[async_trait]
+pub trait IdxNotificationHandler: Send + Sync {
+    /// Idx table the handler receives the notifications of
+    fn table(&self) -> &str;
+
+    fn handle(&self, notification: IdxNotification) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+    /// Apply the buffered notifications that waited long enough
+    fn flush_if_due(&self) {}
+
+    /// Apply all buffered notifications
+    fn flush(&self) {}
+
+    /// State of the cache, reported by `CacheHealth::report`
+    fn cache_status(&self) -> Option<CacheStatus> {
+        None
+    }
+
+    /// Rebuild the cache from the idx table, after notifications may have been missed
+    async fn reload(&self, _pool: &PgPool) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+/// When an `IdxNotificationListener` last received a notification
+///
+/// Clones share the same state, `health_check` reads it to tell whether the listener
+/// still receives.
+#[derive(Debug, Clone)]
+pub struct ListenerActivity {
+    last_notification: Arc<watch::Sender<Option<Instant>>>,
+}
+
+impl Default for ListenerActivity {
+    fn default() -> Self {
+        Self {
+            last_notification: Arc::new(watch::Sender::new(None)),
+        }
+    }
+}
+
+impl ListenerActivity {
+    /// Record a notification received now
+    pub fn record(&self) {
+        self.last_notification.send_replace(Some(Instant::now()));
+    }
+
+    /// When the last notification was received, `None` before the first one
+    pub fn last_notification(&self) -> Option<Instant> {
+        *self.last_notification.borrow()
+    }
+
+    /// Wait until a notification is received at or after `instant`
+    pub async fn received_since(&self, instant: Instant) {
+        let mut last_notification = self.last_notification.subscribe();
+        // The sender lives as long as `self`, so the wait only ends with a notification
+        let _ = last_notification
+            .wait_for(|last| last.is_some_and(|last| last >= instant))
+            .await;
+    }
+}
+
+/// How `IdxNotificationListener::supervise` reconnects
+#[derive(Debug, Clone, Copy)]
+pub struct SupervisorConfig {
+    /// Wait before the first reconnection attempt
+    pub initial_backoff: Duration,
+    /// Longest wait between two attempts, the wait doubles from `initial_backoff`
+    pub max_backoff: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Reads `IDX_CACHE_CHANNEL` and hands each notification to the handlers of its table
+///
+/// The factories register their handlers, one `NotificationCoalescer` per cached idx
+/// table, and share its `CacheHealth` with their repositories, before `supervise` is
+/// spawned. The health is degraded until the listener first listens, and whenever its
+/// connection is lost.
+pub struct IdxNotificationListener {
+    handlers: HashMap<String, Vec<Arc<dyn IdxNotificationHandler>>>,
+    flush_interval: Duration,
+    cache_health: CacheHealth,
+    activity: ListenerActivity,
+}
+
+impl Default for IdxNotificationListener {
+    fn default() -> Self {
+        let cache_health = CacheHealth::default();
+        cache_health.mark_degraded("idx cache notification listener not started");
+        Self {
+            handlers: HashMap::new(),
+            flush_interval: Duration::from_millis(10),
+            cache_health,
+            activity: ListenerActivity::default(),
+        }
+    }
+}
+
+impl IdxNotificationListener {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Listener calling `flush_if_due` on its handlers every `flush_interval`
+    pub fn with_flush_interval(flush_interval: Duration) -> Self {
+        Self {
+            flush_interval,
+            ..Self::default()
+        }
+    }
+
+    /// Health of the caches fed by this listener, shared by the factories registering
+    /// their handlers
+    pub fn cache_health(&self) -> CacheHealth {
+        self.cache_health.clone()
+    }
+
+    /// When this listener last received a notification, for `health_check`
+    pub fn activity(&self) -> ListenerActivity {
+        self.activity.clone()
+    }
+
+    pub fn register_handler(&mut self, handler: Arc<dyn IdxNotificationHandler>) {
+        self.cache_health.track(handler.clone());
+        self.handlers
+            .entry(handler.table().to_string())
+            .or_default()
+            .push(handler);
+    }
+
+    /// Tables with a registered handler
+    pub fn tables(&self) -> Vec<&str> {
+        let mut tables: Vec<&str> = self.handlers.keys().map(String::as_str).collect();
+        tables.sort();
+        tables
+    }
+
+    /// Hand each row of the notification `payload` to the handlers of its table
+    ///
+    /// Notifications of tables without a handler are ignored.
+    pub fn dispatch(&self, payload: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for notification in IdxNotification::parse_payload(payload)? {
+            let Some(handlers) = self.handlers.get(&notification.table) else {
+                return Ok(());
+            };
+            for handler in handlers {
+                handler.handle(notification.clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `flush_if_due` on every handler
+    pub fn flush_if_due(&self) {
+        self.handlers.values().flatten().for_each(|handler| handler.flush_if_due());
+    }
+
+    /// Call `flush` on every handler
+    pub fn flush(&self) {
+        self.handlers.values().flatten().for_each(|handler| handler.flush());
+    }
+
+    /// Reload the cache of every handler from its idx table
+    pub async fn reload(&self, pool: &PgPool) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for handler in self.handlers.values().flatten() {
+            handler.reload(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Listen on `IDX_CACHE_CHANNEL` until the connection fails
+    ///
+    /// The cache health is healthy while listening and degraded once the connection
+    /// fails or the future is dropped. A notification the handlers reject is skipped.
+    /// Use `supervise` to reconnect.
+    pub async fn listen(&self, pool: &PgPool) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let _stopped = StoppedGuard(self.cache_health.clone());
+        let error = match self.connect(pool).await {
+            Ok(listener) => {
+                self.cache_health.mark_healthy();
+                self.receive(listener).await
+            }
+            Err(error) => error,
+        };
+        self.cache_health.mark_degraded(error.to_string());
+        Err(error)
+    }
+
+    /// Listen on `IDX_CACHE_CHANNEL`, reconnecting until the returned task is aborted
+    ///
+    /// The cache health is degraded as soon as the connection fails, and once the task is
+    /// aborted. Each reconnection waits for the backoff of `config`, then reloads the
+    /// caches from their idx tables, as notifications were missed meanwhile, before
+    /// marking the health healthy again.
+    pub fn supervise(self: &Arc<Self>, pool: PgPool, config: SupervisorConfig) -> JoinHandle<()> {
+        let listener = Arc::clone(self);
+        tokio::spawn(async move {
+            let _stopped = StoppedGuard(listener.cache_health.clone());
+            let mut backoff = config.initial_backoff;
+            loop {
+                let error = match listener.connect(&pool).await {
+                    // Listening before reloading, a change committed during the reload is
+                    // notified; its version orders it against the reloaded row
+                    Ok(connection) => match listener.reload(&pool).await {
+                        Ok(()) => {
+                            listener.cache_health.mark_healthy();
+                            backoff = config.initial_backoff;
+                            listener.receive(connection).await.to_string()
+                        }
+                        Err(error) => error.to_string(),
+                    },
+                    Err(error) => error.to_string(),
+                };
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %error, backoff = ?backoff, "Idx cache notification listener lost, reconnecting");
+                listener.cache_health.mark_degraded(error);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(config.max_backoff);
+            }
+        })
+    }
+
+    async fn connect(&self, pool: &PgPool) -> Result<PgListener, Box<dyn Error + Send + Sync>> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(IDX_CACHE_CHANNEL).await?;
+        Ok(listener)
+    }
+
+    /// Dispatch the notifications of `listener` until its connection is lost, returning
+    /// the error that ended it
+    ///
+    /// `try_recv` is used over `recv`, which would reconnect silently and drop the
+    /// notifications sent while disconnected.
+    async fn receive(&self, mut listener: PgListener) -> Box<dyn Error + Send + Sync> {
+        let mut flush_tick = tokio::time::interval(self.flush_interval);
+        loop {
+            tokio::select! {
+                notification = listener.try_recv() => {
+                    let notification = match notification {
+                        Ok(Some(notification)) => notification,
+                        Ok(None) => return "Connection of the idx cache notification listener lost".into(),
+                        Err(error) => return error.into(),
+                    };
+                    self.activity.record();
+                    if let Err(_error) = self.dispatch(notification.payload()) {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_error, "Skipped an idx cache notification");
+                    }
+                }
+                _ = flush_tick.tick() => self.flush_if_due(),
+            }
+        }
+    }
+}
+
+/// Degrades the cache health when the listening task ends, aborted included, keeping
+/// the error of a connection already lost
+struct StoppedGuard(CacheHealth);
+
+impl Drop for StoppedGuard {
+    fn drop(&mut self) {
+        if !self.0.is_degraded() {
+            self.0.mark_degraded("idx cache notification listener stopped");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdxNotificationListener, ListenerActivity, SupervisorConfig, IDX_CACHE_CHANNEL};
+    use crate::repository::cache_health::{CacheHealth, CacheStatus};
+    use crate::repository::cache_versions::CacheVersions;
+    use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+    use crate::test_helper::{setup_test_context, setup_test_context_and_listen};
+    use business_core_db::models::person::locality::LocalityIdxModel;
+    use business_core_db::IdxModelCache;
+    use parking_lot::RwLock as ParkingRwLock;
+    use serde_json::json;
+    use sqlx::postgres::PgListener;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    async fn wait_for_degraded(health: &CacheHealth, degraded: bool) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while health.is_degraded() != degraded {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("The listener health must change");
+    }
+
+    #[test]
+    fn test_dispatch_coalesces_into_the_cache_of_the_table() {
+        let cache = Arc::new(ParkingRwLock::new(IdxModelCache::<LocalityIdxModel>::new(vec![]).unwrap()));
+        let coalescer = Arc::new(NotificationCoalescer::new(
+            "locality_idx",
+            cache.clone(),
+            CoalescingConfig {
+                max_delay: Duration::from_secs(60),
+                max_events: 100,
+            },
+        ));
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(coalescer.clone());
+        assert_eq!(listener.tables(), vec!["locality_idx"]);
+
+        let (kept, deleted, subdivision) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let row = |id: Uuid, code_hash: i64| json!({ "id": id, "country_subdivision_id": subdivision, "code_hash": code_hash });
+        for (op, row) in [
+            ("INSERT", row(kept, 1)),
+            ("INSERT", row(deleted, 2)),
+            ("UPDATE", row(kept, 3)),
+            ("DELETE", row(deleted, 2)),
+        ] {
+            let payload = json!({ "table": "locality_idx", "op": op, "row": row }).to_string();
+            listener.dispatch(&payload).unwrap();
+        }
+        // The rows of a statement arrive in one notification
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let batch = json!({ "table": "locality_idx", "op": "INSERT", "rows": [row(first, 4), row(second, 5)] });
+        listener.dispatch(&batch.to_string()).unwrap();
+        // Another table's notification is not for this cache
+        let other = json!({ "table": "country_idx", "op": "INSERT", "row": { "id": Uuid::new_v4(), "iso2_hash": 1 } });
+        listener.dispatch(&other.to_string()).unwrap();
+        assert!(listener.dispatch("not a notification").is_err());
+
+        // Buffered until flushed, then applied under one lock
+        assert!(cache.read().get_by_primary(&kept).is_none());
+        listener.flush();
+        assert_eq!(coalescer.counters().flushes(), 1);
+        assert_eq!(coalescer.counters().deduped(), 2);
+        assert_eq!(cache.read().get_by_primary(&kept).map(|idx| idx.code_hash), Some(3));
+        assert!(!cache.read().contains_primary(&deleted));
+        assert_eq!(cache.read().get_by_primary(&first).map(|idx| idx.code_hash), Some(4));
+        assert_eq!(cache.read().get_by_primary(&second).map(|idx| idx.code_hash), Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_statement_notifies_its_rows_in_few_notifications() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The larger pool of this context leaves room for the notification connection
+        // and the writing transaction next to the test session
+        let ctx = setup_test_context_and_listen().await?;
+        let pool = ctx.pool().as_ref();
+        let cache = Arc::new(ParkingRwLock::new(IdxModelCache::<LocalityIdxModel>::new(vec![]).unwrap()));
+        let coalescer = Arc::new(NotificationCoalescer::new(
+            "batch_notify_idx",
+            cache.clone(),
+            CoalescingConfig {
+                max_delay: Duration::from_secs(60),
+                max_events: 1000,
+            },
+        ));
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(coalescer.clone());
+
+        let mut notifications = PgListener::connect_with(pool).await?;
+        notifications.listen(IDX_CACHE_CHANNEL).await?;
+
+        // A table of this session shaped like locality_idx, with the insert trigger of
+        // migration 034, written by one statement
+        let mut tx = pool.begin().await?;
+        sqlx::query("CREATE TEMP TABLE batch_notify_idx (LIKE locality_idx INCLUDING DEFAULTS) ON COMMIT DROP")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "CREATE TRIGGER batch_notify_idx_cache_change_insert AFTER INSERT ON batch_notify_idx \
+             REFERENCING NEW TABLE AS new_rows FOR EACH STATEMENT EXECUTE FUNCTION notify_idx_cache_change()",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO batch_notify_idx (id, country_subdivision_id, code_hash) \
+             SELECT gen_random_uuid(), $1, n FROM generate_series(1, 100) AS n",
+        )
+        .bind(Uuid::new_v4())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        let mut received = 0;
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while coalescer.counters().buffered() < 100 {
+                let notification = notifications.recv().await?;
+                if notification.payload().contains("batch_notify_idx") {
+                    received += 1;
+                    listener.dispatch(notification.payload())?;
+                }
+            }
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+        })
+        .await
+        .map_err(|_| "The 100 rows must be notified")??;
+
+        listener.flush();
+        assert_eq!(coalescer.cache_status().entries, 100);
+        assert!(received < 10, "{received} notifications for one statement");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_supervise_reloads_the_caches_after_a_lost_connection() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let cache = Arc::new(ParkingRwLock::new(IdxModelCache::<LocalityIdxModel>::new(vec![]).unwrap()));
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(Arc::new(
+            NotificationCoalescer::new("locality_idx", cache.clone(), CoalescingConfig::default())
+                .with_versions(Arc::new(CacheVersions::new())),
+        ));
+        let listener = Arc::new(listener);
+        let health = listener.cache_health();
+        assert!(health.is_degraded(), "Degraded until the listener first listens");
+
+        // An entry without a row, dropped by each reload
+        let orphan = || LocalityIdxModel {
+            id: Uuid::new_v4(),
+            country_subdivision_id: Uuid::new_v4(),
+            code_hash: 0,
+        };
+        let first_orphan = orphan();
+        cache.write().add(first_orphan.clone());
+
+        let config = SupervisorConfig {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_millis(500),
+        };
+        let handle = listener.supervise(ctx.pool().as_ref().clone(), config);
+        wait_for_degraded(&health, false).await;
+        assert!(!cache.read().contains_primary(&first_orphan.id));
+
+        // Dropping the listening connection degrades the health until the supervisor
+        // reconnected and reloaded the cache
+        let second_orphan = orphan();
+        cache.write().add(second_orphan.clone());
+        sqlx::query(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+             WHERE query ILIKE 'LISTEN%idx_cache_change%' AND pid <> pg_backend_pid()",
+        )
+        .execute(ctx.pool().as_ref())
+        .await?;
+        wait_for_degraded(&health, true).await;
+        assert!(health.last_error().is_some());
+        wait_for_degraded(&health, false).await;
+        assert!(!cache.read().contains_primary(&second_orphan.id));
+
+        handle.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_activity_wakes_the_waits_for_a_later_notification() {
+        let activity = ListenerActivity::default();
+        assert_eq!(activity.last_notification(), None);
+
+        let since = std::time::Instant::now();
+        let waiting = tokio::spawn({
+            let activity = activity.clone();
+            async move { activity.received_since(since).await }
+        });
+        // Clones share the state, as the listener shares it with `health_check`
+        activity.clone().record();
+        tokio::time::timeout(Duration::from_secs(10), waiting)
+            .await
+            .expect("A notification after `since` ends the wait")
+            .unwrap();
+        assert!(activity.last_notification().is_some_and(|last| last >= since));
+    }
+}
@@ -1,5 +1,36 @@
 pub mod audit;
+pub mod cache_auditor;
+pub mod cache_capacity;
+pub mod cache_health;
+pub mod cache_policy;
+pub mod cache_versions;
+pub mod column_list;
+pub mod concurrent_creates;
+pub(crate) mod count_by_key;
 pub mod db_init;
+pub mod find_by_i64_key;
+pub mod idx_notification_listener;
+pub mod notification_coalescer;
+pub mod operation_timeout;
+pub mod refresh_idx_cache;
+pub mod repo_selection;
+pub(crate) mod dry_run;
 pub mod person;
 pub mod reason_and_purpose;
-pub mod calendar;
\ No newline at end of file
+pub mod calendar;
+
+pub use cache_auditor::{CacheAuditConfig, CacheAuditReport, CacheAuditor, CacheMismatch, CachedIds};
+pub use cache_capacity::{CacheBound, CacheCapacity, KeyValue};
+pub use cache_health::{CacheHealth, CacheHealthReport, CacheStatus, Freshness};
+pub use cache_policy::CachePolicy;
+pub use cache_versions::{version_from_payload, CacheVersions, PendingVersions};
+pub use column_list::{AuditedTableSql, ColumnList};
+pub use concurrent_creates::ConcurrentCreates;
+pub use find_by_i64_key::FindByI64Key;
+pub use idx_notification_listener::{
+    IdxNotification, IdxNotificationHandler, IdxNotificationListener, IdxOperation, ListenerActivity,
+    SupervisorConfig,
+};
+pub use notification_coalescer::{CacheEvent, CoalescingConfig, NotificationCoalescer};
+pub use operation_timeout::OperationTimeout;
+pub use refresh_idx_cache::RefreshIdxCache;
@@ -0,0 +1,716 @@
+use crate::repository::cache_auditor::CachedIds;
+use crate::repository::cache_capacity::{key_values, CacheCapacity};
+use crate::repository::cache_health::CacheStatus;
+use crate::repository::cache_versions::{version_from_payload, CacheVersions};
+use crate::repository::idx_notification_listener::{IdxNotification, IdxNotificationHandler, IdxOperation};
+use crate::error::map_db_error;
+use crate::utils::TryFromRow;
+use async_trait::async_trait;
+use business_core_db::{HasPrimaryKey, IdxModelCache, Indexable};
+use parking_lot::{Mutex, RwLock as ParkingRwLock};
+use serde::de::DeserializeOwned;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// A change of one index cache entry, as carried by a cache notification
+#[derive(Debug, Clone)]
+pub enum CacheEvent<T> {
+    /// The entry was inserted or updated
+    Upsert(T),
+    /// The entry was deleted
+    Delete(Uuid),
+}
+
+impl<T: HasPrimaryKey> CacheEvent<T> {
+    pub fn id(&self) -> Uuid {
+        match self {
+            CacheEvent::Upsert(item) => item.primary_key(),
+            CacheEvent::Delete(id) => *id,
+        }
+    }
+}
+
+/// When a coalescer applies its buffered events
+#[derive(Debug, Clone, Copy)]
+pub struct CoalescingConfig {
+    /// Longest time an event stays buffered
+    pub max_delay: Duration,
+    /// Number of received events that triggers a flush
+    pub max_events: usize,
+}
+
+impl Default for CoalescingConfig {
+    fn default() -> Self {
+        Self {
+            max_delay: Duration::from_millis(50),
+            max_events: 1_000,
+        }
+    }
+}
+
+/// Counters of a coalescer, cumulative since its creation
+#[derive(Debug, Default)]
+pub struct CoalescingCounters {
+    buffered: AtomicU64,
+    deduped: AtomicU64,
+    applied: AtomicU64,
+    stale: AtomicU64,
+    flushes: AtomicU64,
+}
+
+impl CoalescingCounters {
+    /// Events received
+    pub fn buffered(&self) -> u64 {
+        self.buffered.load(Ordering::Relaxed)
+    }
+
+    /// Events replaced by a later event for the same id before being applied
+    pub fn deduped(&self) -> u64 {
+        self.deduped.load(Ordering::Relaxed)
+    }
+
+    /// Events applied to the cache
+    pub fn applied(&self) -> u64 {
+        self.applied.load(Ordering::Relaxed)
+    }
+
+    /// Events dropped because a newer version of the entry was already written
+    pub fn stale(&self) -> u64 {
+        self.stale.load(Ordering::Relaxed)
+    }
+
+    /// Flushes that applied at least one event, one cache write lock each
+    pub fn flushes(&self) -> u64 {
+        self.flushes.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+struct Pending<T> {
+    events: HashMap<Uuid, (CacheEvent<T>, Option<u64>)>,
+    received: usize,
+    since: Option<Instant>,
+}
+
+/// Buffers the notifications of one index cache and applies them in batches
+///
+/// Events are deduplicated by id, the latest one wins, so a delete replaces the
+/// upserts received before it in the same window. A flush applies all buffered
+/// events under a single acquisition of the cache write lock, instead of one per
+/// notification, so a bulk import does not keep readers waiting on the lock.
+///
+/// A flush happens when `max_events` events were received, on `flush_if_due` once
+/// the oldest buffered event is `max_delay` old, and on `flush`.
+///
+/// With `with_versions`, events pushed by `push_versioned` are ordered by version
+/// instead: the highest version wins the deduplication, and a flush drops the events
+/// older than the version already written to the cache, see `CacheVersions`.
+///
+/// With `with_capacity`, the cache is bounded: an upsert only updates an entry already
+/// cached, the row of an absent one is left to SQL and its key values are no longer
+/// complete, see `CacheCapacity`.
+///
+/// The factories register one coalescer per cached idx table with the
+/// `IdxNotificationListener`, whose flush tick calls `flush_if_due`.
+pub struct NotificationCoalescer<T> {
+    /// Idx table whose notifications are coalesced
+    table: &'static str,
+    cache: Arc<ParkingRwLock<IdxModelCache<T>>>,
+    config: CoalescingConfig,
+    versions: Option<Arc<CacheVersions>>,
+    capacity: Option<Arc<CacheCapacity>>,
+    pending: Mutex<Pending<T>>,
+    counters: CoalescingCounters,
+    /// Ids of the entries, as of the last reload and the events applied since
+    ids: Mutex<HashSet<Uuid>>,
+    last_applied: Mutex<Option<Instant>>,
+}
+
+impl<T> NotificationCoalescer<T>
+where
+    T: HasPrimaryKey + Indexable + Clone + Send + Sync + 'static,
+{
+    pub fn new(table: &'static str, cache: Arc<ParkingRwLock<IdxModelCache<T>>>, config: CoalescingConfig) -> Self {
+        Self {
+            table,
+            cache,
+            config,
+            versions: None,
+            capacity: None,
+            pending: Mutex::new(Pending {
+                events: HashMap::new(),
+                received: 0,
+                since: None,
+            }),
+            counters: CoalescingCounters::default(),
+            ids: Mutex::new(HashSet::new()),
+            last_applied: Mutex::new(None),
+        }
+    }
+
+    /// Check the versions of the events against `versions` before applying them
+    pub fn with_versions(mut self, versions: Arc<CacheVersions>) -> Self {
+        self.versions = Some(versions);
+        self
+    }
+
+    /// Apply the events to a cache bounded by `capacity`, `None` leaves it unbounded
+    pub fn with_capacity(mut self, capacity: Option<Arc<CacheCapacity>>) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn counters(&self) -> &CoalescingCounters {
+        &self.counters
+    }
+
+    /// Buffer an event, flushing when `max_events` events were received
+    pub fn push(&self, event: CacheEvent<T>) {
+        self.buffer(event, None);
+    }
+
+    /// Buffer an event carrying the version of its row, as read by `version_from_payload`
+    ///
+    /// A delete is ordered after every version of the row.
+    pub fn push_versioned(&self, event: CacheEvent<T>, version: u64) {
+        let version = match event {
+            CacheEvent::Upsert(_) => version,
+            CacheEvent::Delete(_) => CacheVersions::DELETED,
+        };
+        self.buffer(event, Some(version));
+    }
+
+    fn buffer(&self, event: CacheEvent<T>, version: Option<u64>) {
+        self.counters.buffered.fetch_add(1, Ordering::Relaxed);
+        let full = {
+            let mut pending = self.pending.lock();
+            let id = event.id();
+            let superseded = match (pending.events.get(&id), version) {
+                (Some((_, Some(buffered))), Some(version)) => version < *buffered,
+                _ => false,
+            };
+            if pending.events.contains_key(&id) {
+                self.counters.deduped.fetch_add(1, Ordering::Relaxed);
+            }
+            if !superseded {
+                pending.events.insert(id, (event, version));
+            }
+            pending.received += 1;
+            pending.since.get_or_insert_with(Instant::now);
+            pending.received >= self.config.max_events
+        };
+        if full {
+            self.flush();
+        }
+    }
+
+    /// Flush when the oldest buffered event has waited `max_delay`
+    pub fn flush_if_due(&self) {
+        let due = self
+            .pending
+            .lock()
+            .since
+            .is_some_and(|since| since.elapsed() >= self.config.max_delay);
+        if due {
+            self.flush();
+        }
+    }
+
+    /// Apply all buffered events under one cache write lock
+    pub fn flush(&self) {
+        let events = {
+            let mut pending = self.pending.lock();
+            pending.received = 0;
+            pending.since = None;
+            std::mem::take(&mut pending.events)
+        };
+        if events.is_empty() {
+            return;
+        }
+
+        let mut applied = 0;
+        let mut stale = 0;
+        {
+            // Versions are admitted under the cache write lock, so no other flush
+            // interleaves between the check and the write
+            let mut cache = self.cache.write();
+            let mut ids = self.ids.lock();
+            for (id, (event, version)) in events {
+                let admitted = match (&self.versions, version) {
+                    (Some(versions), Some(version)) => versions.admit(id, version),
+                    _ => true,
+                };
+                if !admitted {
+                    stale += 1;
+                    continue;
+                }
+                if let (Some(capacity), CacheEvent::Upsert(item)) = (&self.capacity, &event) {
+                    if !cache.contains_primary(&id) {
+                        capacity.invalidate_keys(key_values(item));
+                        if let Some(versions) = &self.versions {
+                            versions.forget(&[id]);
+                        }
+                        applied += 1;
+                        continue;
+                    }
+                }
+                cache.remove(&id);
+                match event {
+                    CacheEvent::Upsert(item) => {
+                        cache.add(item);
+                        ids.insert(id);
+                    }
+                    CacheEvent::Delete(_) => {
+                        ids.remove(&id);
+                        if let Some(capacity) = &self.capacity {
+                            capacity.forget(&[id]);
+                        }
+                    }
+                }
+                applied += 1;
+            }
+        }
+        if applied > 0 {
+            *self.last_applied.lock() = Some(Instant::now());
+        }
+        self.counters.applied.fetch_add(applied, Ordering::Relaxed);
+        self.counters.stale.fetch_add(stale, Ordering::Relaxed);
+        if applied > 0 {
+            self.counters.flushes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of entries and time of the last applied event
+    ///
+    /// The entries of a bounded cache are the ones tracked by its capacity, the
+    /// evictions do not go through the coalescer.
+    pub fn cache_status(&self) -> CacheStatus {
+        let entries = match &self.capacity {
+            Some(capacity) => capacity.len(),
+            None => self.ids.lock().len(),
+        };
+        CacheStatus {
+            table: self.table.to_string(),
+            entries,
+            last_applied: *self.last_applied.lock(),
+        }
+    }
+
+    /// Call `flush_if_due` every `max_delay` until the returned task is aborted
+    pub fn spawn_flush_timer(self: &Arc<Self>) -> JoinHandle<()> {
+        let coalescer = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(coalescer.config.max_delay);
+            loop {
+                interval.tick().await;
+                coalescer.flush_if_due();
+            }
+        })
+    }
+}
+
+/// The entries of a bounded cache are listed by its capacity
+impl<T> CachedIds for NotificationCoalescer<T>
+where
+    T: HasPrimaryKey + Indexable + Clone + Send + Sync + 'static,
+{
+    fn cached_ids(&self) -> Vec<Uuid> {
+        match &self.capacity {
+            Some(capacity) => capacity.ids(),
+            None => self.ids.lock().iter().copied().collect(),
+        }
+    }
+
+    fn forget_ids(&self, ids: &[Uuid]) {
+        match &self.capacity {
+            Some(capacity) => capacity.forget(ids),
+            None => {
+                let mut cached = self.ids.lock();
+                for id in ids {
+                    cached.remove(id);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<T> IdxNotificationHandler for NotificationCoalescer<T>
+where
+    T: HasPrimaryKey + Indexable + Clone + DeserializeOwned + TryFromRow<PgRow> + Send + Sync + 'static,
+{
+    fn table(&self) -> &str {
+        self.table
+    }
+
+    /// Buffer the notification, with the version of its row when the coalescer checks
+    /// versions
+    ///
+    /// A row without a `version` column is at `CacheVersions::INITIAL`.
+    fn handle(&self, notification: IdxNotification) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let event = match notification.op {
+            IdxOperation::Delete => CacheEvent::Delete(notification.id()?),
+            IdxOperation::Insert | IdxOperation::Update => CacheEvent::Upsert(serde_json::from_value(notification.row.clone())?),
+        };
+        match &self.versions {
+            Some(_) => {
+                let version = version_from_payload(&notification.row).unwrap_or(CacheVersions::INITIAL);
+                self.push_versioned(event, version);
+            }
+            None => self.push(event),
+        }
+        Ok(())
+    }
+
+    fn flush_if_due(&self) {
+        NotificationCoalescer::flush_if_due(self)
+    }
+
+    fn flush(&self) {
+        NotificationCoalescer::flush(self)
+    }
+
+    fn cache_status(&self) -> Option<CacheStatus> {
+        Some(NotificationCoalescer::cache_status(self))
+    }
+
+    /// Replace the cache by the rows of the idx table
+    ///
+    /// With versions, a row older than the version already written keeps the cached
+    /// entry, or its absence for a deleted one. A bounded cache only reloads the
+    /// entries it holds, and no key value stays complete: rows inserted while the
+    /// listener was away were never added.
+    async fn reload(&self, pool: &PgPool) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rows = sqlx::query(&format!("SELECT * FROM {}", self.table))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| map_db_error(self.table, e))?;
+        let mut stored = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let version = match self.versions {
+                Some(_) => Some(row.try_get::<i64, _>("version")? as u64),
+                None => None,
+            };
+            stored.push((T::try_from_row(row)?, version));
+        }
+
+        let mut cache = self.cache.write();
+        let mut reloaded = IdxModelCache::new(vec![])
+            .map_err(|e| format!("{}: index cache rebuild failed: {e:?}", self.table))?;
+        let mut ids = HashSet::with_capacity(stored.len());
+        for (item, version) in stored {
+            let id = item.primary_key();
+            if self.capacity.is_some() && !cache.contains_primary(&id) {
+                continue;
+            }
+            let admitted = match (&self.versions, version) {
+                (Some(versions), Some(version)) => versions.admit(id, version),
+                _ => true,
+            };
+            if admitted {
+                reloaded.add(item);
+            } else if let Some(cached) = cache.get_by_primary(&id) {
+                reloaded.add(cached);
+            } else {
+                continue;
+            }
+            ids.insert(id);
+        }
+        if let Some(capacity) = &self.capacity {
+            let gone: Vec<Uuid> = capacity.ids().into_iter().filter(|id| !ids.contains(id)).collect();
+            capacity.forget(&gone);
+            capacity.invalidate_all_keys();
+        }
+        *cache = reloaded;
+        *self.ids.lock() = ids;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheEvent, CoalescingConfig, NotificationCoalescer};
+    use crate::repository::cache_capacity::{CacheCapacity, KeyValue};
+    use crate::repository::cache_versions::CacheVersions;
+    use crate::repository::idx_notification_listener::IdxNotificationListener;
+    use business_core_db::models::person::locality::LocalityIdxModel;
+    use business_core_db::IdxModelCache;
+    use parking_lot::RwLock as ParkingRwLock;
+    use rand::seq::SliceRandom;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn locality_idx(id: Uuid, country_subdivision_id: Uuid) -> LocalityIdxModel {
+        LocalityIdxModel {
+            id,
+            country_subdivision_id,
+            code_hash: 0,
+        }
+    }
+
+    fn coalescer(config: CoalescingConfig) -> NotificationCoalescer<LocalityIdxModel> {
+        let cache = Arc::new(ParkingRwLock::new(IdxModelCache::new(vec![]).unwrap()));
+        NotificationCoalescer::new("locality_idx", cache, config)
+    }
+
+    #[test]
+    fn test_burst_is_applied_under_one_lock() {
+        let coalescer = coalescer(CoalescingConfig {
+            max_delay: Duration::from_secs(60),
+            max_events: 100,
+        });
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let (before, after) = (Uuid::new_v4(), Uuid::new_v4());
+        // Present before the burst, deleted by it
+        coalescer.cache.write().add(locality_idx(ids[3], before));
+
+        // 0: inserted then updated, 1: inserted then deleted,
+        // 2: deleted then inserted again, 3: deleted
+        let burst = vec![
+            CacheEvent::Upsert(locality_idx(ids[0], before)),
+            CacheEvent::Upsert(locality_idx(ids[1], before)),
+            CacheEvent::Delete(ids[2]),
+            CacheEvent::Upsert(locality_idx(ids[0], after)),
+            CacheEvent::Delete(ids[1]),
+            CacheEvent::Upsert(locality_idx(ids[2], after)),
+            CacheEvent::Delete(ids[3]),
+        ];
+        for event in burst {
+            coalescer.push(event);
+        }
+        assert_eq!(coalescer.counters().flushes(), 0);
+
+        coalescer.flush();
+
+        let counters = coalescer.counters();
+        assert_eq!(counters.buffered(), 7);
+        assert_eq!(counters.deduped(), 3);
+        assert_eq!(counters.applied(), 4);
+        assert_eq!(counters.flushes(), 1);
+
+        let cache = coalescer.cache.read();
+        assert!(cache.contains_primary(&ids[0]));
+        assert!(!cache.contains_primary(&ids[1]));
+        assert!(cache.contains_primary(&ids[2]));
+        assert!(!cache.contains_primary(&ids[3]));
+        assert_eq!(cache.get_by_uuid_index("country_subdivision_id", &after).len(), 2);
+        assert!(cache.get_by_uuid_index("country_subdivision_id", &before).is_empty());
+    }
+
+    #[test]
+    fn test_max_events_triggers_flush() {
+        let coalescer = coalescer(CoalescingConfig {
+            max_delay: Duration::from_secs(60),
+            max_events: 3,
+        });
+        let id = Uuid::new_v4();
+
+        coalescer.push(CacheEvent::Upsert(locality_idx(id, Uuid::new_v4())));
+        coalescer.push(CacheEvent::Upsert(locality_idx(id, Uuid::new_v4())));
+        assert_eq!(coalescer.counters().flushes(), 0);
+        coalescer.push(CacheEvent::Delete(id));
+
+        assert_eq!(coalescer.counters().flushes(), 1);
+        assert_eq!(coalescer.counters().applied(), 1);
+        assert!(!coalescer.cache.read().contains_primary(&id));
+
+        // Nothing left to apply
+        coalescer.flush();
+        assert_eq!(coalescer.counters().flushes(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_flush_timer_applies_after_max_delay() {
+        let coalescer = Arc::new(coalescer(CoalescingConfig {
+            max_delay: Duration::from_millis(10),
+            max_events: 100,
+        }));
+        let timer = coalescer.spawn_flush_timer();
+        let id = Uuid::new_v4();
+
+        coalescer.push(CacheEvent::Upsert(locality_idx(id, Uuid::new_v4())));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        timer.abort();
+
+        assert_eq!(coalescer.counters().flushes(), 1);
+        assert!(coalescer.cache.read().contains_primary(&id));
+    }
+
+    #[test]
+    fn test_stale_notification_is_dropped() {
+        let versions = Arc::new(CacheVersions::new());
+        let coalescer = coalescer(CoalescingConfig::default()).with_versions(versions.clone());
+        let id = Uuid::new_v4();
+        let (older, newer) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // Local write of version 2, then the delayed notification of version 1
+        assert!(versions.admit(id, 2));
+        coalescer.cache.write().add(locality_idx(id, newer));
+        coalescer.push_versioned(CacheEvent::Upsert(locality_idx(id, older)), 1);
+        coalescer.flush();
+
+        assert_eq!(coalescer.counters().stale(), 1);
+        assert_eq!(coalescer.counters().flushes(), 0);
+        assert!(coalescer.cache.read().get_by_uuid_index("country_subdivision_id", &newer).iter().any(|idx| idx.id == id));
+
+        // Without a version the notification overwrites the newer write
+        coalescer.push(CacheEvent::Upsert(locality_idx(id, older)));
+        coalescer.flush();
+        assert!(coalescer.cache.read().get_by_uuid_index("country_subdivision_id", &older).iter().any(|idx| idx.id == id));
+
+        // A delete wins over any version
+        coalescer.push_versioned(CacheEvent::Delete(id), 2);
+        coalescer.push_versioned(CacheEvent::Upsert(locality_idx(id, newer)), 3);
+        coalescer.flush();
+        assert!(!coalescer.cache.read().contains_primary(&id));
+    }
+
+    #[test]
+    fn test_bounded_cache_only_updates_cached_entries() {
+        let capacity = Arc::new(CacheCapacity::with_capacity_limit(10));
+        let coalescer = coalescer(CoalescingConfig::default()).with_capacity(Some(capacity.clone()));
+        let (cached, uncached) = (Uuid::new_v4(), Uuid::new_v4());
+        let (before, after) = (Uuid::new_v4(), Uuid::new_v4());
+        coalescer.cache.write().add(locality_idx(cached, before));
+        capacity.touch(&[cached]);
+        capacity.mark_complete("country_subdivision_id", KeyValue::Uuid(after), capacity.epoch());
+
+        coalescer.push(CacheEvent::Upsert(locality_idx(cached, after)));
+        coalescer.push(CacheEvent::Upsert(locality_idx(uncached, after)));
+        coalescer.flush();
+
+        // The row of an uncached entry is left to SQL, its key value is no longer complete
+        {
+            let cache = coalescer.cache.read();
+            assert_eq!(cache.get_by_uuid_index("country_subdivision_id", &after).len(), 1);
+            assert!(!cache.contains_primary(&uncached));
+        }
+        assert!(!capacity.is_complete("country_subdivision_id", KeyValue::Uuid(after)));
+        assert_eq!(coalescer.cache_status().entries, 1);
+
+        coalescer.push(CacheEvent::Delete(cached));
+        coalescer.flush();
+        assert!(!capacity.contains(&cached));
+        assert_eq!(coalescer.cache_status().entries, 0);
+    }
+
+    #[test]
+    fn test_interleaved_local_writes_and_notifications_end_on_latest_version() {
+        const IDS: usize = 8;
+        const VERSIONS: u64 = 50;
+
+        let versions = Arc::new(CacheVersions::new());
+        let coalescer = Arc::new(
+            coalescer(CoalescingConfig {
+                max_delay: Duration::from_secs(60),
+                max_events: 7,
+            })
+            .with_versions(versions.clone()),
+        );
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(coalescer.clone());
+        let listener = Arc::new(listener);
+        let ids: Vec<Uuid> = (0..IDS).map(|_| Uuid::new_v4()).collect();
+        // The last id is deleted after its last version
+        let deleted = ids[IDS - 1];
+        // Content written by each version of each entry
+        let contents: Arc<HashMap<(Uuid, u64), Uuid>> = Arc::new(
+            ids.iter()
+                .flat_map(|id| (1..=VERSIONS).map(move |version| ((*id, version), Uuid::new_v4())))
+                .collect(),
+        );
+
+        // The repository applying its writes in version order
+        let local = {
+            let (coalescer, versions, ids, contents) =
+                (coalescer.clone(), versions.clone(), ids.clone(), contents.clone());
+            std::thread::spawn(move || {
+                for version in 1..=VERSIONS {
+                    for id in &ids {
+                        let mut cache = coalescer.cache.write();
+                        if versions.admit(*id, version) {
+                            cache.remove(id);
+                            cache.add(locality_idx(*id, contents[&(*id, version)]));
+                        }
+                    }
+                }
+                let mut cache = coalescer.cache.write();
+                if versions.admit(deleted, CacheVersions::DELETED) {
+                    cache.remove(&deleted);
+                }
+            })
+        };
+
+        // The notifications of the same writes, as sent by the trigger, dispatched out of
+        // order by two threads
+        let payloads: Vec<String> = {
+            let mut replay: Vec<(Uuid, u64)> = contents.keys().copied().collect();
+            replay.shuffle(&mut rand::thread_rng());
+            let mut payloads: Vec<String> = replay
+                .into_iter()
+                .map(|(id, version)| {
+                    let op = if version == CacheVersions::INITIAL { "INSERT" } else { "UPDATE" };
+                    json!({
+                        "table": "locality_idx",
+                        "op": op,
+                        "row": {
+                            "id": id,
+                            "country_subdivision_id": contents[&(id, version)],
+                            "code_hash": 0,
+                            "version": version,
+                        },
+                    })
+                    .to_string()
+                })
+                .collect();
+            let delete = json!({
+                "table": "locality_idx",
+                "op": "DELETE",
+                "row": { "id": deleted, "country_subdivision_id": contents[&(deleted, VERSIONS)], "code_hash": 0, "version": VERSIONS },
+            });
+            payloads.insert(payloads.len() / 2, delete.to_string());
+            payloads
+        };
+        let notifications: Vec<_> = payloads
+            .chunks(payloads.len() / 2 + 1)
+            .map(|chunk| {
+                let (listener, chunk) = (listener.clone(), chunk.to_vec());
+                std::thread::spawn(move || {
+                    for payload in chunk {
+                        listener.dispatch(&payload).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        local.join().unwrap();
+        for notifications in notifications {
+            notifications.join().unwrap();
+        }
+        listener.flush();
+
+        let cache = coalescer.cache.read();
+        for id in &ids[..IDS - 1] {
+            assert_eq!(versions.version(*id), Some(VERSIONS));
+            let latest = contents[&(*id, VERSIONS)];
+            let found: Vec<Uuid> = cache
+                .get_by_uuid_index("country_subdivision_id", &latest)
+                .iter()
+                .map(|idx| idx.id)
+                .collect();
+            assert_eq!(found, vec![*id]);
+        }
+        assert!(!cache.contains_primary(&deleted));
+        assert_eq!(versions.version(deleted), Some(CacheVersions::DELETED));
+    }
+}
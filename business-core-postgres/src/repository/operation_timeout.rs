@@ -0,0 +1,173 @@
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::error::RepositoryError;
+
+/// Default bound of both the lock acquisition and the SQL execution
+pub const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bounds on how long a repository operation waits
+///
+/// Chosen per repository factory. The wait for the session's transaction lock and the
+/// SQL run while holding it are bounded separately, so a timeout tells whether another
+/// caller of the session or the database was holding things up. Independent of the
+/// Postgres `statement_timeout`, which bounds single statements server side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationTimeout {
+    /// Bound on acquiring the session's transaction lock
+    pub lock: Duration,
+    /// Bound on the SQL executed while the lock is held
+    pub query: Duration,
+}
+
+impl Default for OperationTimeout {
+    fn default() -> Self {
+        Self {
+            lock: DEFAULT_OPERATION_TIMEOUT,
+            query: DEFAULT_OPERATION_TIMEOUT,
+        }
+    }
+}
+
+impl OperationTimeout {
+    /// Acquire `mutex`, failing with `RepositoryError::LockAcquisitionTimeout` after `lock`
+    pub async fn lock<'a, T>(
+        &self,
+        entity: &str,
+        mutex: &'a Mutex<T>,
+    ) -> Result<MutexGuard<'a, T>, RepositoryError> {
+        tokio::time::timeout(self.lock, mutex.lock())
+            .await
+            .map_err(|_| RepositoryError::LockAcquisitionTimeout {
+                entity: entity.to_string(),
+                timeout: self.lock,
+            })
+    }
+
+    /// Run `operation`, failing with `RepositoryError::QueryTimeout` after `query`
+    ///
+    /// The operation is dropped on timeout. Its statements may have run partially, the
+    /// caller is expected to roll the session back.
+    pub async fn query<F: Future>(&self, entity: &str, operation: F) -> Result<F::Output, RepositoryError> {
+        tokio::time::timeout(self.query, operation)
+            .await
+            .map_err(|_| RepositoryError::QueryTimeout {
+                entity: entity.to_string(),
+                timeout: self.query,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OperationTimeout;
+    use crate::error::RepositoryError;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::{CountryRepositoryImpl, PersonRepositoryImpl};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_lock_timeout_under_contention() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // Another task holds the session's transaction lock
+        let tx = person_repo.executor.tx.clone();
+        let (locked_tx, locked_rx) = tokio::sync::oneshot::channel();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+        let holder = tokio::spawn(async move {
+            let _guard = tx.lock().await;
+            let _ = locked_tx.send(());
+            let _ = release_rx.await;
+        });
+        locked_rx.await?;
+
+        let timeout = OperationTimeout {
+            lock: Duration::from_millis(50),
+            query: Duration::from_secs(5),
+        };
+        let error = timeout.lock("person", &person_repo.executor.tx).await.unwrap_err();
+        assert!(matches!(
+            error,
+            RepositoryError::LockAcquisitionTimeout { ref entity, timeout } if entity == "person" && timeout == Duration::from_millis(50)
+        ));
+
+        // A repository built with the bound reports it through its batch operations
+        let bounded_repo = PersonRepositoryImpl {
+            operation_timeout: timeout,
+            ..PersonRepositoryImpl::new_with_hash_version(
+                person_repo.executor.clone(),
+                person_repo.person_idx_shared_cache.clone(),
+                person_repo.cache_policy,
+                person_repo.hash_version,
+            )
+        };
+        let error = bounded_repo
+            .create_batch(vec![create_test_person("Blocked Person")], Some(audit_log.id))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::LockAcquisitionTimeout { .. })
+        ));
+
+        // As do the finders of the other repositories reading SQL under the lock
+        let country_repo = &ctx.person_repos().country_repository;
+        let bounded_country_repo = CountryRepositoryImpl {
+            operation_timeout: timeout,
+            ..CountryRepositoryImpl::new_with_cache_policy(
+                country_repo.executor.clone(),
+                country_repo.country_idx_shared_cache.clone(),
+                CachePolicy::Disabled,
+            )
+        };
+        let error = bounded_country_repo
+            .find_by_iso2_hash(hash_as_i64(&"QB").unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::LockAcquisitionTimeout { ref entity, .. }) if entity == "country"
+        ));
+
+        release_tx.send(()).ok();
+        holder.await?;
+
+        // Once released, the repository writes again
+        let saved = person_repo
+            .create_batch(vec![create_test_person("After Contention")], Some(audit_log.id))
+            .await?;
+        assert_eq!(saved.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let executor = &ctx.audit_repos().audit_log_repository.executor;
+
+        let timeout = OperationTimeout {
+            lock: Duration::from_secs(5),
+            query: Duration::from_millis(50),
+        };
+        let mut tx = timeout.lock("audit_log", &executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let error = timeout
+            .query("audit_log", sqlx::query("SELECT pg_sleep(1)").execute(&mut **transaction))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, RepositoryError::QueryTimeout { .. }));
+
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::ActivityLogRepositoryImpl;
 
 impl ActivityLogRepositoryImpl {
@@ -23,7 +24,7 @@ impl ActivityLogRepositoryImpl {
         }
 
         let mut saved_items = Vec::new();
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("activity_log", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -43,8 +44,8 @@ impl ActivityLogRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_activity_log_audit
-                (id, person_id, activity_summary, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                (id, person_id, activity_summary, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id, activity_date)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 "#,
             )
             .bind(entity.id)
@@ -56,14 +57,15 @@ impl ActivityLogRepositoryImpl {
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
             .bind(entity.hash)
-            .bind(entity.audit_log_id);
+            .bind(entity.audit_log_id)
+            .bind(entity.activity_date);
 
             // 5. Build entity insert query
             let entity_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_activity_log
-                (id, person_id, activity_summary, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                (id, person_id, activity_summary, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id, activity_date)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 "#,
             )
             .bind(entity.id)
@@ -75,7 +77,8 @@ impl ActivityLogRepositoryImpl {
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
             .bind(entity.hash)
-            .bind(entity.audit_log_id);
+            .bind(entity.audit_log_id)
+            .bind(entity.activity_date);
 
             // 6. Create audit link to track the entity modification in the transaction
             let audit_link = AuditLinkModel {
@@ -83,20 +86,11 @@ impl ActivityLogRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::ActivityLog,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
 
             // 7. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             entity_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
             saved_items.push(entity);
         }
@@ -107,6 +101,7 @@ impl ActivityLogRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, ActivityLogModel> for ActivityLogRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "activity_log", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<ActivityLogModel>,
@@ -0,0 +1,91 @@
+use business_core_db::models::person::activity_log::ActivityLogModel;
+use chrono::{DateTime, Utc};
+use crate::utils::TryFromRow;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::ActivityLogRepositoryImpl;
+
+impl ActivityLogRepositoryImpl {
+    /// List the activities of a person dated at or after `since`, newest first, at most `limit`
+    pub async fn find_recent(
+        &self,
+        person_id: Uuid,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ActivityLogModel>, Box<dyn Error + Send + Sync>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query = r#"
+            SELECT * FROM person_activity_log
+            WHERE person_id = $1 AND activity_date >= $2
+            ORDER BY activity_date DESC, id
+            LIMIT $3
+        "#;
+        let rows = {
+            let mut tx = self.operation_timeout.lock("activity_log", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(query)
+                .bind(person_id)
+                .bind(since)
+                .bind(i64::try_from(limit)?)
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(ActivityLogModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::activity_log_repository::test_utils::create_test_activity_log_with_summary;
+    use crate::repository::person::person_repository::test_utils::create_test_person;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_find_recent_applies_since_and_limit() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let activity_log_repo = &ctx.person_repos().activity_log_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Recent Activity Person", PersonType::Natural);
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // Activities 1, 2, 3, 4 and 10 days ago
+        let now = Utc::now();
+        for days_ago in [1, 2, 3, 4, 10] {
+            let mut entry = create_test_activity_log_with_summary(person_id, &format!("{days_ago} days ago"));
+            entry.activity_date = now - Duration::days(days_ago);
+            activity_log_repo.log_activity(entry, audit_log.id).await?;
+        }
+
+        let since = now - Duration::days(5);
+
+        let recent = activity_log_repo.find_recent(person_id, since, 10).await?;
+        assert_eq!(recent.len(), 4);
+        assert!(recent.iter().all(|entry| entry.activity_date >= since));
+        assert!(recent.windows(2).all(|pair| pair[0].activity_date >= pair[1].activity_date));
+
+        let limited = activity_log_repo.find_recent(person_id, since, 2).await?;
+        assert_eq!(limited.len(), 2);
+        assert_eq!(limited[0].activity_summary.as_deref(), Some("1 days ago"));
+        assert_eq!(limited[1].activity_summary.as_deref(), Some("2 days ago"));
+
+        Ok(())
+    }
+}
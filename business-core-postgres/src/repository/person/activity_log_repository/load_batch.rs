@@ -19,7 +19,7 @@ impl ActivityLogRepositoryImpl {
         
         let query = r#"SELECT * FROM person_activity_log WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("activity_log", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
         };
@@ -36,10 +36,16 @@ impl ActivityLogRepositoryImpl {
         }
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<ActivityLogModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, ActivityLogModel> for ActivityLogRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "activity_log", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
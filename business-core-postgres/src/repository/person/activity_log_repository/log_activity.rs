@@ -0,0 +1,55 @@
+use business_core_db::models::person::activity_log::ActivityLogModel;
+use business_core_db::repository::create_batch::CreateBatch;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::ActivityLogRepositoryImpl;
+
+impl ActivityLogRepositoryImpl {
+    /// Append a single activity, see `create_batch`
+    pub async fn log_activity(
+        &self,
+        entry: ActivityLogModel,
+        audit_log_id: Uuid,
+    ) -> Result<ActivityLogModel, Box<dyn Error + Send + Sync>> {
+        let saved = self.create_batch(vec![entry], Some(audit_log_id)).await?;
+        saved.into_iter().next().ok_or_else(|| "Activity log was not created".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::activity_log_repository::test_utils::create_test_activity_log_with_summary;
+    use crate::repository::person::person_repository::test_utils::create_test_person;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+
+    #[tokio::test]
+    async fn test_log_activity() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let activity_log_repo = &ctx.person_repos().activity_log_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Activity Person", PersonType::Natural);
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let entry = create_test_activity_log_with_summary(person_id, "Logged in");
+        let saved = activity_log_repo.log_activity(entry, audit_log.id).await?;
+
+        assert_eq!(saved.audit_log_id, Some(audit_log.id));
+        assert_ne!(saved.hash, 0);
+
+        let loaded = activity_log_repo.load_batch(&[saved.id]).await?;
+        let loaded = loaded[0].as_ref().ok_or("Activity log not found")?;
+        assert_eq!(loaded.activity_summary.as_deref(), Some("Logged in"));
+
+        Ok(())
+    }
+}
@@ -5,6 +5,8 @@ pub mod load_audits;
 pub mod update_batch;
 pub mod delete_batch;
 pub mod exist_by_ids;
+pub mod log_activity;
+pub mod find_recent;
 #[cfg(test)]
 pub mod test_utils;
 
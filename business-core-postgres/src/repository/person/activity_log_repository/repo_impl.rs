@@ -4,14 +4,20 @@ use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
 use async_trait::async_trait;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct ActivityLogRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
 }
 
 impl ActivityLogRepositoryImpl {
     pub fn new(executor: Executor) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 }
 
@@ -21,6 +27,7 @@ impl TryFromRow<PgRow> for ActivityLogModel {
             id: row.get("id"),
             person_id: row.get("person_id"),
             activity_summary: get_optional_heapless_string(row, "activity_summary")?,
+            activity_date: row.get("activity_date"),
             predecessor_1: row.try_get("predecessor_1").ok(),
             predecessor_2: row.try_get("predecessor_2").ok(),
             predecessor_3: row.try_get("predecessor_3").ok(),
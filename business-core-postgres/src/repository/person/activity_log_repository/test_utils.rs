@@ -1,4 +1,5 @@
 use business_core_db::models::person::activity_log::ActivityLogModel;
+use chrono::Utc;
 use heapless::String as HeaplessString;
 use uuid::Uuid;
 
@@ -7,6 +8,7 @@ pub fn create_test_activity_log(person_id: Uuid) -> ActivityLogModel {
         id: Uuid::new_v4(),
         person_id,
         activity_summary: Some(HeaplessString::try_from("Test activity summary").unwrap()),
+        activity_date: Utc::now(),
         predecessor_1: None,
         predecessor_2: None,
         predecessor_3: None,
@@ -25,6 +27,7 @@ pub fn create_test_activity_log_with_summary(
         id: Uuid::new_v4(),
         person_id,
         activity_summary: Some(HeaplessString::try_from(summary).unwrap()),
+        activity_date: Utc::now(),
         predecessor_1: None,
         predecessor_2: None,
         predecessor_3: None,
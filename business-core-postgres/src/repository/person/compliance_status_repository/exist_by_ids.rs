@@ -18,7 +18,7 @@ impl ComplianceStatusRepositoryImpl {
         
         let query = r#"SELECT id FROM person_compliance_status WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("compliance_status", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
         };
@@ -18,7 +18,7 @@ impl ComplianceStatusRepositoryImpl {
         // First, get the total count of audit records for this entity
         let count_query = r#"SELECT COUNT(*) as count FROM person_compliance_status_audit WHERE id = $1"#;
         let total: i64 = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("compliance_status", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query_scalar(count_query)
                     .bind(id)
@@ -38,7 +38,7 @@ impl ComplianceStatusRepositoryImpl {
         "#;
         
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("compliance_status", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query(query)
                     .bind(id)
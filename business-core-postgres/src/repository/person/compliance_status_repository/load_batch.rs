@@ -19,7 +19,7 @@ impl ComplianceStatusRepositoryImpl {
         
         let query = r#"SELECT * FROM person_compliance_status WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("compliance_status", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
         };
@@ -36,10 +36,16 @@ impl ComplianceStatusRepositoryImpl {
         }
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<ComplianceStatusModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, ComplianceStatusModel> for ComplianceStatusRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "compliance_status", count = ids.len())))]
     async fn load_batch(
         &self,
         ids: &[Uuid],
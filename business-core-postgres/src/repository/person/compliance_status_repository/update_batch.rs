@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::ComplianceStatusRepositoryImpl;
 
 impl ComplianceStatusRepositoryImpl {
@@ -23,7 +24,7 @@ impl ComplianceStatusRepositoryImpl {
         }
 
         let mut updated_items = Vec::new();
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("compliance_status", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -113,20 +114,11 @@ impl ComplianceStatusRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::ComplianceStatus,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
-            
+
             // 8. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             entity_update_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
             
             updated_items.push(entity);
         }
@@ -137,6 +129,7 @@ impl ComplianceStatusRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, ComplianceStatusModel> for ComplianceStatusRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "compliance_status", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<ComplianceStatusModel>,
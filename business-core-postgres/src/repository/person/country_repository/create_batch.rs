@@ -6,6 +6,8 @@ use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountryRepositoryImpl;
 
 impl CountryRepositoryImpl {
@@ -22,7 +24,7 @@ impl CountryRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("country", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -60,11 +62,17 @@ impl CountryRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.country_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.country_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
@@ -73,6 +81,7 @@ impl CountryRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, CountryModel> for CountryRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "country", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<CountryModel>,
@@ -2,6 +2,7 @@ use std::error::Error;
 
 use business_core_db::models::person::country::CountryIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountryRepositoryImpl;
 
 impl CountryRepositoryImpl {
@@ -9,9 +10,7 @@ impl CountryRepositoryImpl {
         &self,
         iso2_hash: i64,
     ) -> Result<Vec<CountryIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.country_idx_cache.read().await;
-        let items = cache.get_by_i64_index("iso2_hash", &iso2_hash);
-        Ok(items)
+        self.find_by_i64_key("iso2_hash", iso2_hash).await
     }
 }
 
@@ -7,32 +7,66 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct CountryRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub country_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<CountryIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub country_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountryIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the country_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the country_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
 }
 
 impl CountryRepositoryImpl {
     pub fn new(
         executor: Executor,
         country_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountryIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, country_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        country_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountryIdxModel>>>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            country_idx_shared_cache: country_idx_cache.clone(),
             country_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 country_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
         }
     }
 
     pub async fn load_all_country_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<CountryIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM country_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("country", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -51,10 +85,19 @@ impl CountryRepositoryImpl {
 #[async_trait]
 impl TransactionAware for CountryRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.country_idx_cache.read().await.on_commit().await
+        self.country_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.country_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.country_idx_cache.read().await.on_rollback().await
     }
 }
@@ -74,8 +117,71 @@ impl TryFromRow<PgRow> for CountryModel {
 impl TryFromRow<PgRow> for CountryIdxModel {
     fn try_from_row(row: &PgRow) -> Result<Self, Box<dyn Error + Send + Sync>> {
         Ok(CountryIdxModel {
-            id: row.get("country_id"),
+            id: row.get("id"),
             iso2_hash: row.try_get("iso2_hash")?,
         })
     }
 }
+
+#[async_trait]
+impl FindByI64Key for CountryRepositoryImpl {
+    type Idx = CountryIdxModel;
+
+    const ENTITY: &'static str = "country";
+    const I64_KEYS: &'static [&'static str] = &["iso2_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<CountryIdxModel>> {
+        &self.country_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for CountryRepositoryImpl {
+    type Idx = CountryIdxModel;
+
+    const ENTITY: &'static str = "country";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<CountryIdxModel>>> {
+        &self.country_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<CountryIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_country_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
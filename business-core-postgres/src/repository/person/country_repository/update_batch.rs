@@ -6,6 +6,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountryRepositoryImpl;
 
 impl CountryRepositoryImpl {
@@ -22,7 +23,7 @@ impl CountryRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("country", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -48,12 +49,16 @@ impl CountryRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = self.country_idx_cache.read().await;
-            for (id, idx) in indices {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|(id, _)| *id).collect();
+            {
+                let cache = self.country_idx_cache.read().await;
+                for (id, idx) in indices {
+                    cache.remove(&id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -62,6 +67,7 @@ impl CountryRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, CountryModel> for CountryRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "country", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<CountryModel>,
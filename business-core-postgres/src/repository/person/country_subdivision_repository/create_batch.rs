@@ -5,24 +5,34 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
+use business_core_db::utils::normalize_code;
+use std::collections::HashMap;
+use crate::error::RepositoryError;
+use crate::repository::find_by_i64_key::FindByI64Key;
 
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::CountrySubdivisionRepositoryImpl;
 
 impl CountrySubdivisionRepositoryImpl {
     pub(super) async fn create_batch_impl(
         repo: &CountrySubdivisionRepositoryImpl,
-        items: Vec<CountrySubdivisionModel>,
+        mut items: Vec<CountrySubdivisionModel>,
     ) -> Result<Vec<CountrySubdivisionModel>, Box<dyn Error + Send + Sync>> {
         if items.is_empty() {
             return Ok(Vec::new());
         }
 
+        for item in &mut items {
+            item.normalize()?;
+        }
+        repo.check_unique_codes(&items).await?;
+
         let mut saved_items = Vec::new();
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("country_subdivision", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -62,19 +72,62 @@ impl CountrySubdivisionRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.country_subdivision_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.country_subdivision_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
     }
+
+    /// Reject items whose code is already used in their country, by a stored country subdivision
+    /// or by an earlier item of the batch
+    ///
+    /// Codes are expected normalized. Candidates are found by code hash and their stored
+    /// code compared, so a hash collision is not reported as a duplicate.
+    pub(super) async fn check_unique_codes(
+        &self,
+        items: &[CountrySubdivisionModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut seen: HashMap<(Uuid, &str), Uuid> = HashMap::new();
+        for item in items {
+            let existing_id = match seen.insert((item.country_id, item.code.as_str()), item.id) {
+                Some(earlier_id) => Some(earlier_id),
+                None => self
+                    .find_matching_by_i64_key(
+                        "code_hash",
+                        item.to_index().code_hash,
+                        |idx| idx.country_id == item.country_id,
+                        |stored: &CountrySubdivisionModel| normalize_code(&stored.code) == item.code.as_str(),
+                    )
+                    .await?
+                    .first()
+                    .map(|stored| stored.id),
+            };
+            if let Some(existing_id) = existing_id {
+                return Err(RepositoryError::CodeTaken {
+                    entity: "country_subdivision".to_string(),
+                    code: item.code.to_string(),
+                    existing_id,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl CreateBatch<Postgres, CountrySubdivisionModel> for CountrySubdivisionRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "country_subdivision", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<CountrySubdivisionModel>,
@@ -125,6 +178,46 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_create_batch_rejects_code_in_other_case() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+
+        let country = create_test_country("CA", "Canada");
+        let other_country = create_test_country("ES", "Spain");
+        let (country_id, other_country_id) = (country.id, other_country.id);
+        country_repo.create_batch(vec![country, other_country], None).await?;
+
+        let saved = country_subdivision_repo
+            .create_batch(vec![create_test_country_subdivision(country_id, "QC", "Quebec")], None)
+            .await?;
+
+        let duplicate = create_test_country_subdivision(country_id, " qc", "Quebec Again");
+        let error = country_subdivision_repo
+            .create_batch(vec![duplicate], None)
+            .await
+            .expect_err("A code differing only in case must be rejected");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::CodeTaken { entity, code, existing_id }) => {
+                assert_eq!(entity, "country_subdivision");
+                assert_eq!(code, "QC");
+                assert_eq!(*existing_id, saved[0].id);
+            }
+            other => panic!("Expected CodeTaken, got {other:?}"),
+        }
+
+        // Codes are unique per country only
+        let elsewhere = country_subdivision_repo
+            .create_batch(vec![create_test_country_subdivision(other_country_id, "qc", "Elsewhere")], None)
+            .await?;
+        assert_eq!(elsewhere[0].code.as_str(), "QC");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_batch_empty() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -4,6 +4,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountrySubdivisionRepositoryImpl;
 
 impl CountrySubdivisionRepositoryImpl {
@@ -11,12 +12,7 @@ impl CountrySubdivisionRepositoryImpl {
         repo: &CountrySubdivisionRepositoryImpl,
         ids: &[Uuid],
     ) -> Result<Vec<(Uuid, bool)>, Box<dyn Error + Send + Sync>> {
-        let mut result = Vec::new();
-        let cache = repo.country_subdivision_idx_cache.read().await;
-        for &id in ids {
-            result.push((id, cache.contains_primary(&id)));
-        }
-        Ok(result)
+        repo.exist_idx_by_ids(ids).await
     }
 }
 
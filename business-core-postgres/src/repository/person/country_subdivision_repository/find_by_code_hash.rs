@@ -1,7 +1,9 @@
 use std::error::Error;
+use business_core_db::utils::{hash_as_i64, normalize_code};
 
 use business_core_db::models::person::country_subdivision::CountrySubdivisionIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountrySubdivisionRepositoryImpl;
 
 impl CountrySubdivisionRepositoryImpl {
@@ -9,9 +11,15 @@ impl CountrySubdivisionRepositoryImpl {
         &self,
         code_hash: i64,
     ) -> Result<Vec<CountrySubdivisionIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.country_subdivision_idx_cache.read().await;
-        let items = cache.get_by_i64_index("code_hash", &code_hash);
-        Ok(items)
+        self.find_by_i64_key("code_hash", code_hash).await
+    }
+
+    /// Find by code, given in any case and with surrounding whitespace
+    pub async fn find_by_code(
+        &self,
+        code: &str,
+    ) -> Result<Vec<CountrySubdivisionIdxModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_code_hash(hash_as_i64(&normalize_code(code).as_str())?).await
     }
 }
 
@@ -53,6 +61,28 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_find_by_code_in_other_case() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+
+        let country = create_test_country("NL", "Netherlands");
+        let country_id = country.id;
+        country_repo.create_batch(vec![country], None).await?;
+
+        let saved = country_subdivision_repo
+            .create_batch(vec![create_test_country_subdivision(country_id, "NH", "North Holland")], None)
+            .await?;
+
+        let found = country_subdivision_repo.find_by_code(" nh ").await?;
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_find_by_code_hash_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -3,13 +3,19 @@ use uuid::Uuid;
 
 use business_core_db::models::person::country_subdivision::CountrySubdivisionIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::CountrySubdivisionRepositoryImpl;
 
 impl CountrySubdivisionRepositoryImpl {
+    /// Subdivisions of a country, from the index cache or, when the repository does
+    /// not serve finders from its cache, from country_subdivision_idx
     pub async fn find_by_country_id(
         &self,
         country_id: Uuid,
     ) -> Result<Vec<CountrySubdivisionIdxModel>, Box<dyn Error + Send + Sync>> {
+        if !self.serves_key_from_cache("country_id", country_id.into()) {
+            return self.find_idx_by_column("country_id", country_id).await;
+        }
         let cache = self.country_subdivision_idx_cache.read().await;
         let items = cache.get_by_uuid_index("country_id", &country_id);
         Ok(items)
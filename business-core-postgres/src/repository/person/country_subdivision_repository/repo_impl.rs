@@ -7,32 +7,66 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct CountrySubdivisionRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub country_subdivision_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<CountrySubdivisionIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub country_subdivision_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountrySubdivisionIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the country_subdivision_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the country_subdivision_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
 }
 
 impl CountrySubdivisionRepositoryImpl {
     pub fn new(
         executor: Executor,
         country_subdivision_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountrySubdivisionIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, country_subdivision_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        country_subdivision_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<CountrySubdivisionIdxModel>>>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            country_subdivision_idx_shared_cache: country_subdivision_idx_cache.clone(),
             country_subdivision_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 country_subdivision_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
         }
     }
 
     pub async fn load_all_country_subdivision_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<CountrySubdivisionIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM country_subdivision_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("country_subdivision", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -51,10 +85,19 @@ impl CountrySubdivisionRepositoryImpl {
 #[async_trait]
 impl TransactionAware for CountrySubdivisionRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.country_subdivision_idx_cache.read().await.on_commit().await
+        self.country_subdivision_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.country_subdivision_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.country_subdivision_idx_cache.read().await.on_rollback().await
     }
 }
@@ -80,4 +123,67 @@ impl TryFromRow<PgRow> for CountrySubdivisionIdxModel {
             code_hash: row.try_get("code_hash")?,
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for CountrySubdivisionRepositoryImpl {
+    type Idx = CountrySubdivisionIdxModel;
+
+    const ENTITY: &'static str = "country_subdivision";
+    const I64_KEYS: &'static [&'static str] = &["code_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<CountrySubdivisionIdxModel>> {
+        &self.country_subdivision_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for CountrySubdivisionRepositoryImpl {
+    type Idx = CountrySubdivisionIdxModel;
+
+    const ENTITY: &'static str = "country_subdivision";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<CountrySubdivisionIdxModel>>> {
+        &self.country_subdivision_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<CountrySubdivisionIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_country_subdivision_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
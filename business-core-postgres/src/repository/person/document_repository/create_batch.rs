@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::DocumentRepositoryImpl;
 
 impl DocumentRepositoryImpl {
@@ -23,7 +24,7 @@ impl DocumentRepositoryImpl {
         }
 
         let mut saved_items = Vec::new();
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("document", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -43,12 +44,13 @@ impl DocumentRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_document_audit
-                (id, person_id, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                (id, owner_id, owner_type, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 "#,
             )
             .bind(entity.id)
-            .bind(entity.person_id)
+            .bind(entity.owner_id)
+            .bind(entity.owner_type)
             .bind(entity.document_type.as_str())
             .bind(entity.document_path.as_deref())
             .bind(entity.status)
@@ -64,12 +66,13 @@ impl DocumentRepositoryImpl {
             let entity_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_document
-                (id, person_id, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                (id, owner_id, owner_type, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 "#,
             )
             .bind(entity.id)
-            .bind(entity.person_id)
+            .bind(entity.owner_id)
+            .bind(entity.owner_type)
             .bind(entity.document_type.as_str())
             .bind(entity.document_path.as_deref())
             .bind(entity.status)
@@ -87,20 +90,11 @@ impl DocumentRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::Document,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
 
             // 7. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             entity_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
             saved_items.push(entity);
         }
@@ -111,6 +105,7 @@ impl DocumentRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, DocumentModel> for DocumentRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "document", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<DocumentModel>,
@@ -158,7 +153,7 @@ mod tests {
         for entity in &saved {
             assert!(entity.hash != 0, "Hash should be computed");
             assert_eq!(entity.audit_log_id, Some(audit_log.id));
-            assert_eq!(entity.person_id, person_id);
+            assert_eq!(entity.owner_id, person_id);
         }
 
         Ok(())
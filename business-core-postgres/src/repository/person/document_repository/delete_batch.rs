@@ -2,11 +2,13 @@ use business_core_db::models::audit::{audit_link::AuditLinkModel, entity_type::E
 use async_trait::async_trait;
 use business_core_db::repository::load_batch::LoadBatch;
 use business_core_db::repository::delete_batch::DeleteBatch;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
 use business_core_db::utils::hash_as_i64;
 use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::DocumentRepositoryImpl;
 
 impl DocumentRepositoryImpl {
@@ -14,17 +16,17 @@ impl DocumentRepositoryImpl {
         repo: &DocumentRepositoryImpl,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         let audit_log_id = audit_log_id.ok_or("audit_log_id is required for DocumentModel")?;
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         // 1. Load the full entities to be deleted
         let entities_to_delete = repo.load_batch(ids).await?;
         
-        let mut deleted_count = 0;
-        let mut tx = repo.executor.tx.lock().await;
+        let mut deleted = Vec::new();
+        let mut tx = repo.operation_timeout.lock("document", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for entity_opt in entities_to_delete {
@@ -48,12 +50,13 @@ impl DocumentRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_document_audit
-                (id, person_id, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                (id, owner_id, owner_type, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 "#,
             )
             .bind(final_audit_entity.id)
-            .bind(final_audit_entity.person_id)
+            .bind(final_audit_entity.owner_id)
+            .bind(final_audit_entity.owner_type)
             .bind(final_audit_entity.document_type.as_str())
             .bind(final_audit_entity.document_path.as_deref())
             .bind(final_audit_entity.status)
@@ -79,35 +82,41 @@ impl DocumentRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::Document,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
-            
+
             // 6. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             let result = entity_delete_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
             
-            deleted_count += result.rows_affected() as usize;
+            if result.rows_affected() > 0 {
+                deleted.push(entity.id);
+            }
         }
 
-        Ok(deleted_count)
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for DocumentRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "document", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids, audit_log_id).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for DocumentRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids, audit_log_id).await
     }
 }
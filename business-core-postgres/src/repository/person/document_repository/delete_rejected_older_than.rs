@@ -0,0 +1,181 @@
+use chrono::NaiveDate;
+use sqlx::Row;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::DocumentRepositoryImpl;
+
+impl DocumentRepositoryImpl {
+    /// Delete all documents that have been in `Rejected` status since before `cutoff`
+    ///
+    /// `DocumentModel` carries no timestamp, so the moment a document became `Rejected`
+    /// is derived from the audit trail: it is the `updated_at` of the earliest
+    /// `Rejected` audit row recorded after the last non-`Rejected` audit row of the document.
+    /// This way a document that was rejected, re-uploaded and rejected again is aged from
+    /// its latest rejection.
+    ///
+    /// ```sql
+    /// SELECT d.id
+    /// FROM person_document d
+    /// WHERE d.status = 'Rejected'
+    ///   AND (
+    ///     SELECT MIN(al.updated_at)
+    ///     FROM person_document_audit a
+    ///     JOIN audit_log al ON al.id = a.audit_log_id
+    ///     WHERE a.id = d.id
+    ///       AND a.status = 'Rejected'
+    ///       AND al.updated_at > COALESCE((
+    ///           SELECT MAX(al2.updated_at)
+    ///           FROM person_document_audit a2
+    ///           JOIN audit_log al2 ON al2.id = a2.audit_log_id
+    ///           WHERE a2.id = d.id AND a2.status <> 'Rejected'
+    ///       ), '-infinity'::timestamptz)
+    ///   ) < $1::date
+    /// ```
+    ///
+    /// The matching documents are removed through `delete_batch`, so each deletion
+    /// gets its final audit record and audit link under `audit_log_id`.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - The number of documents deleted
+    pub async fn delete_rejected_older_than(
+        &self,
+        cutoff: NaiveDate,
+        audit_log_id: Uuid,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let query = r#"
+            SELECT d.id
+            FROM person_document d
+            WHERE d.status = 'Rejected'
+              AND (
+                SELECT MIN(al.updated_at)
+                FROM person_document_audit a
+                JOIN audit_log al ON al.id = a.audit_log_id
+                WHERE a.id = d.id
+                  AND a.status = 'Rejected'
+                  AND al.updated_at > COALESCE((
+                      SELECT MAX(al2.updated_at)
+                      FROM person_document_audit a2
+                      JOIN audit_log al2 ON al2.id = a2.audit_log_id
+                      WHERE a2.id = d.id AND a2.status <> 'Rejected'
+                  ), '-infinity'::timestamptz)
+              ) < $1::date
+        "#;
+
+        let rows = {
+            let mut tx = self.operation_timeout.lock("document", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(query).bind(cutoff).fetch_all(&mut **transaction).await?
+        };
+
+        let ids: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        Self::delete_batch_impl(self, &ids, Some(audit_log_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::document_repository::test_utils::create_test_document_with_status;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::document::DocumentStatus;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_delete_rejected_older_than() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let person_id = Uuid::new_v4();
+
+        // Old document: uploaded 60 days ago, rejected 45 days ago
+        let mut old_upload_audit_log = create_test_audit_log();
+        old_upload_audit_log.updated_at = Utc::now() - Duration::days(60);
+        audit_log_repo.create(&old_upload_audit_log).await?;
+        let old_document = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved_old = document_repo.create_batch(vec![old_document], Some(old_upload_audit_log.id)).await?;
+
+        let mut old_reject_audit_log = create_test_audit_log();
+        old_reject_audit_log.updated_at = Utc::now() - Duration::days(45);
+        audit_log_repo.create(&old_reject_audit_log).await?;
+        let mut rejected_old = saved_old[0].clone();
+        rejected_old.status = DocumentStatus::Rejected;
+        let rejected_old = document_repo.update_batch(vec![rejected_old], Some(old_reject_audit_log.id)).await?;
+
+        // Recent document: uploaded 60 days ago, rejected today
+        let recent_document = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved_recent = document_repo.create_batch(vec![recent_document], Some(old_upload_audit_log.id)).await?;
+
+        let recent_reject_audit_log = create_test_audit_log();
+        audit_log_repo.create(&recent_reject_audit_log).await?;
+        let mut rejected_recent = saved_recent[0].clone();
+        rejected_recent.status = DocumentStatus::Rejected;
+        let rejected_recent = document_repo.update_batch(vec![rejected_recent], Some(recent_reject_audit_log.id)).await?;
+
+        // Old document that is still uploaded must not be affected
+        let uploaded_old = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved_uploaded = document_repo.create_batch(vec![uploaded_old], Some(old_upload_audit_log.id)).await?;
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        let cutoff = (Utc::now() - Duration::days(30)).date_naive();
+        let deleted_count = document_repo.delete_rejected_older_than(cutoff, delete_audit_log.id).await?;
+
+        assert_eq!(deleted_count, 1, "Only the old rejected document should be deleted");
+
+        let loaded = document_repo
+            .load_batch(&[rejected_old[0].id, rejected_recent[0].id, saved_uploaded[0].id])
+            .await?;
+        assert!(loaded[0].is_none(), "Old rejected document should be deleted");
+        assert!(loaded[1].is_some(), "Recently rejected document should be kept");
+        assert!(loaded[2].is_some(), "Uploaded document should be kept");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_rejected_older_than_uses_latest_rejection() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        // Rejected 50 days ago, re-uploaded 40 days ago, rejected again today
+        let mut first_reject_audit_log = create_test_audit_log();
+        first_reject_audit_log.updated_at = Utc::now() - Duration::days(50);
+        audit_log_repo.create(&first_reject_audit_log).await?;
+        let document = create_test_document_with_status(Uuid::new_v4(), DocumentStatus::Rejected);
+        let saved = document_repo.create_batch(vec![document], Some(first_reject_audit_log.id)).await?;
+
+        let mut reupload_audit_log = create_test_audit_log();
+        reupload_audit_log.updated_at = Utc::now() - Duration::days(40);
+        audit_log_repo.create(&reupload_audit_log).await?;
+        let mut reuploaded = saved[0].clone();
+        reuploaded.status = DocumentStatus::Uploaded;
+        let reuploaded = document_repo.update_batch(vec![reuploaded], Some(reupload_audit_log.id)).await?;
+
+        let second_reject_audit_log = create_test_audit_log();
+        audit_log_repo.create(&second_reject_audit_log).await?;
+        let mut rejected_again = reuploaded[0].clone();
+        rejected_again.status = DocumentStatus::Rejected;
+        document_repo.update_batch(vec![rejected_again], Some(second_reject_audit_log.id)).await?;
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        let cutoff = (Utc::now() - Duration::days(30)).date_naive();
+        document_repo.delete_rejected_older_than(cutoff, delete_audit_log.id).await?;
+
+        let loaded = document_repo.load_batch(&[saved[0].id]).await?;
+        assert!(loaded[0].is_some(), "Document rejected again recently should be kept");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use business_core_db::models::person::document::{DocumentModel, DocumentOwnerType};
+use crate::utils::TryFromRow;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::DocumentRepositoryImpl;
+
+impl DocumentRepositoryImpl {
+    /// Documents attached to the owner of kind `owner_type` with id `owner_id`
+    pub async fn find_by_owner(
+        &self,
+        owner_type: DocumentOwnerType,
+        owner_id: Uuid,
+    ) -> Result<Vec<DocumentModel>, Box<dyn Error + Send + Sync>> {
+        let query = r#"SELECT * FROM person_document WHERE owner_type = $1 AND owner_id = $2"#;
+        let rows = {
+            let mut tx = self.operation_timeout.lock("document", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(query)
+                .bind(owner_type)
+                .bind(owner_id)
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(DocumentModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+
+    /// Documents attached to the person with id `person_id`
+    pub async fn find_by_person_id(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<DocumentModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_owner(DocumentOwnerType::Person, person_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::document_repository::test_utils::{
+        create_test_document, create_test_document_for_owner,
+    };
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::document::DocumentOwnerType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_find_by_owner_isolates_owners() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person_id = Uuid::new_v4();
+        let product_id = Uuid::new_v4();
+        let person_document = create_test_document(person_id);
+        let terms = create_test_document_for_owner(DocumentOwnerType::Product, product_id);
+        // Same id under another owner type, as ids of different tables may collide
+        let contract = create_test_document_for_owner(DocumentOwnerType::EntityReference, product_id);
+        document_repo
+            .create_batch(
+                vec![person_document.clone(), terms.clone(), contract.clone()],
+                Some(audit_log.id),
+            )
+            .await?;
+
+        let found = document_repo.find_by_owner(DocumentOwnerType::Product, product_id).await?;
+        assert_eq!(found.iter().map(|document| document.id).collect::<Vec<_>>(), vec![terms.id]);
+        assert_eq!(found[0].owner_type, DocumentOwnerType::Product);
+
+        let found = document_repo
+            .find_by_owner(DocumentOwnerType::EntityReference, product_id)
+            .await?;
+        assert_eq!(found.iter().map(|document| document.id).collect::<Vec<_>>(), vec![contract.id]);
+
+        let found = document_repo.find_by_person_id(person_id).await?;
+        assert_eq!(found.iter().map(|document| document.id).collect::<Vec<_>>(), vec![person_document.id]);
+
+        // A product id is not a person id
+        assert!(document_repo.find_by_person_id(product_id).await?.is_empty());
+        assert!(document_repo.find_by_owner(DocumentOwnerType::Product, person_id).await?.is_empty());
+
+        Ok(())
+    }
+}
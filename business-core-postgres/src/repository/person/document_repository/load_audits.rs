@@ -18,7 +18,7 @@ impl DocumentRepositoryImpl {
         // First, get the total count of audit records for this entity
         let count_query = r#"SELECT COUNT(*) as count FROM person_document_audit WHERE id = $1"#;
         let total: i64 = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("document", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query_scalar(count_query)
                     .bind(id)
@@ -38,7 +38,7 @@ impl DocumentRepositoryImpl {
         "#;
         
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("document", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query(query)
                     .bind(id)
@@ -77,6 +77,10 @@ mod tests {
     use business_core_db::repository::update_batch::UpdateBatch;
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
     use crate::repository::person::document_repository::test_utils::create_test_document;
+    use business_core_db::models::audit_chained::{order_chain, verify_chain_from};
+    use business_core_db::models::person::document::DocumentStatus;
+    use business_core_db::utils::hash_as_i64;
+    use uuid::Uuid;
 
     #[tokio::test]
     async fn test_load_audits() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -146,4 +150,96 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// `DocumentModel` as serialized before migration 027
+    #[derive(serde::Serialize)]
+    struct PreOwnerDocument {
+        id: Uuid,
+        person_id: Uuid,
+        document_type: String,
+        document_path: Option<String>,
+        status: &'static str,
+        predecessor_1: Option<Uuid>,
+        predecessor_2: Option<Uuid>,
+        predecessor_3: Option<Uuid>,
+        antecedent_hash: i64,
+        antecedent_audit_log_id: Uuid,
+        hash: i64,
+        audit_log_id: Option<Uuid>,
+    }
+
+    #[tokio::test]
+    async fn test_pre_owner_documents_verify_after_migration() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let person = create_test_person("Legacy Document Owner");
+        let person_id = person.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // A document hashed by the pre-027 code, stored as migration 027 leaves it
+        let mut document = create_test_document(person_id);
+        document.audit_log_id = Some(audit_log.id);
+        document.hash = hash_as_i64(&PreOwnerDocument {
+            id: document.id,
+            person_id,
+            document_type: document.document_type.to_string(),
+            document_path: document.document_path.as_ref().map(|path| path.to_string()),
+            status: "Uploaded",
+            predecessor_1: None,
+            predecessor_2: None,
+            predecessor_3: None,
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: Some(audit_log.id),
+        })?;
+        let mut current_layout = document.clone();
+        current_layout.hash = 0;
+        assert_ne!(hash_as_i64(&current_layout)?, document.hash);
+        {
+            let mut tx = document_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            for table in ["person_document", "person_document_audit"] {
+                sqlx::query(&format!(
+                    "INSERT INTO {table} (id, owner_id, owner_type, document_type, document_path, status, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id) \
+                     VALUES ($1, $2, 'Person', $3, $4, 'Uploaded', 0, $5, $6, $7)"
+                ))
+                .bind(document.id)
+                .bind(person_id)
+                .bind(document.document_type.as_str())
+                .bind(document.document_path.as_deref())
+                .bind(Uuid::nil())
+                .bind(document.hash)
+                .bind(audit_log.id)
+                .execute(&mut **transaction)
+                .await?;
+            }
+        }
+
+        // Saving it unchanged writes no version
+        let unchanged_audit_log = create_test_audit_log();
+        audit_log_repo.create(&unchanged_audit_log).await?;
+        let stored = document_repo.load(document.id).await?.ok_or("Document not found")?;
+        let saved = document_repo.update_batch(vec![stored], Some(unchanged_audit_log.id)).await?;
+        assert_eq!(saved[0].hash, document.hash);
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        let mut verified = saved[0].clone();
+        verified.status = DocumentStatus::Verified;
+        document_repo.update_batch(vec![verified], Some(update_audit_log.id)).await?;
+
+        let audits = document_repo.load_audits(document.id, PageRequest::new(10, 0)).await?.items;
+        assert_eq!(audits.len(), 2);
+        let audits = order_chain(audits);
+        assert_eq!(audits[0].hash, document.hash);
+        assert_eq!(verify_chain_from(&audits, None), Ok(()));
+
+        Ok(())
+    }
+}
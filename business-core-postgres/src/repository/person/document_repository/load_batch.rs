@@ -19,7 +19,7 @@ impl DocumentRepositoryImpl {
         
         let query = r#"SELECT * FROM person_document WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("document", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
             } else {
@@ -39,10 +39,16 @@ impl DocumentRepositoryImpl {
         }
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<DocumentModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, DocumentModel> for DocumentRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "document", count = ids.len())))]
     async fn load_batch(&self, ids: &[Uuid]) -> Result<Vec<Option<DocumentModel>>, Box<dyn Error + Send + Sync>> {
         Self::load_batch_impl(self, ids).await
     }
@@ -89,7 +95,7 @@ mod tests {
         for item in loaded {
             assert!(item.is_some());
             let document = item.unwrap();
-            assert_eq!(document.person_id, person_id);
+            assert_eq!(document.owner_id, person_id);
         }
 
         Ok(())
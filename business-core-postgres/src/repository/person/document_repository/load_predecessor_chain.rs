@@ -0,0 +1,253 @@
+use business_core_db::models::person::document::{DocumentHistoryNode, DocumentModel};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::DocumentRepositoryImpl;
+
+/// Maximum number of predecessor levels followed when resolving document history
+pub const MAX_PREDECESSOR_DEPTH: usize = 32;
+
+impl DocumentRepositoryImpl {
+    /// Load the linear history of a document by following `predecessor_1`
+    ///
+    /// The first element is the document itself, followed by the document it superseded,
+    /// and so on. Resolution stops at a missing predecessor, at a document already seen
+    /// in the chain (cycle), or after `MAX_PREDECESSOR_DEPTH` predecessors.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DocumentModel>)` - The chain, empty if the document does not exist
+    pub async fn load_predecessor_chain(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<DocumentModel>, Box<dyn Error + Send + Sync>> {
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut next = Some(id);
+
+        while let Some(current_id) = next {
+            if chain.len() > MAX_PREDECESSOR_DEPTH || !visited.insert(current_id) {
+                break;
+            }
+            let document = match Self::load_batch_impl(self, &[current_id]).await?.pop().flatten() {
+                Some(document) => document,
+                None => break,
+            };
+            next = document.predecessor_1;
+            chain.push(document);
+        }
+
+        Ok(chain)
+    }
+
+    /// Load the full history of a document as a tree following all three predecessor slots
+    ///
+    /// Documents are loaded one level at a time. A document reachable through several
+    /// branches is expanded only once, under the first branch that reaches it, which also
+    /// protects against cycles. Levels deeper than `MAX_PREDECESSOR_DEPTH` are not loaded.
+    ///
+    /// # Returns
+    /// * `Ok(Some(DocumentHistoryNode))` - The history tree rooted at the document
+    /// * `Ok(None)` - The document does not exist
+    pub async fn load_predecessor_tree(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<DocumentHistoryNode>, Box<dyn Error + Send + Sync>> {
+        let mut documents: HashMap<Uuid, DocumentModel> = HashMap::new();
+        let mut level = vec![id];
+        let mut depth = 0;
+
+        while !level.is_empty() && depth <= MAX_PREDECESSOR_DEPTH {
+            let loaded = Self::load_batch_impl(self, &level).await?;
+            let mut next_level = Vec::new();
+            for document in loaded.into_iter().flatten() {
+                for predecessor_id in document.predecessors() {
+                    if !documents.contains_key(&predecessor_id)
+                        && predecessor_id != document.id
+                        && !next_level.contains(&predecessor_id)
+                    {
+                        next_level.push(predecessor_id);
+                    }
+                }
+                documents.insert(document.id, document);
+            }
+            next_level.retain(|predecessor_id| !documents.contains_key(predecessor_id));
+            level = next_level;
+            depth += 1;
+        }
+
+        let mut placed = HashSet::new();
+        Ok(build_history_node(id, &documents, &mut placed))
+    }
+}
+
+fn build_history_node(
+    id: Uuid,
+    documents: &HashMap<Uuid, DocumentModel>,
+    placed: &mut HashSet<Uuid>,
+) -> Option<DocumentHistoryNode> {
+    let document = documents.get(&id)?;
+    if !placed.insert(id) {
+        return None;
+    }
+
+    let predecessors = document
+        .predecessors()
+        .into_iter()
+        .filter_map(|predecessor_id| build_history_node(predecessor_id, documents, placed))
+        .collect();
+
+    Some(DocumentHistoryNode {
+        document: document.clone(),
+        predecessors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MAX_PREDECESSOR_DEPTH;
+    use crate::repository::person::document_repository::test_utils::create_test_document;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_load_predecessor_chain_linear() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person_id = Uuid::new_v4();
+        let first = create_test_document(person_id);
+        let mut second = create_test_document(person_id);
+        second.predecessor_1 = Some(first.id);
+        let mut third = create_test_document(person_id);
+        third.predecessor_1 = Some(second.id);
+
+        let saved = document_repo
+            .create_batch(vec![first, second, third], Some(audit_log.id))
+            .await?;
+
+        let chain = document_repo.load_predecessor_chain(saved[2].id).await?;
+        let chain_ids: Vec<Uuid> = chain.iter().map(|d| d.id).collect();
+        assert_eq!(chain_ids, vec![saved[2].id, saved[1].id, saved[0].id]);
+
+        let missing = document_repo.load_predecessor_chain(Uuid::new_v4()).await?;
+        assert!(missing.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_predecessor_chain_stops_on_cycle() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person_id = Uuid::new_v4();
+        let mut first = create_test_document(person_id);
+        let mut second = create_test_document(person_id);
+        first.predecessor_1 = Some(second.id);
+        second.predecessor_1 = Some(first.id);
+
+        let saved = document_repo
+            .create_batch(vec![first, second], Some(audit_log.id))
+            .await?;
+
+        let chain = document_repo.load_predecessor_chain(saved[0].id).await?;
+        assert_eq!(chain.len(), 2);
+
+        let tree = document_repo.load_predecessor_tree(saved[0].id).await?.unwrap();
+        assert_eq!(tree.predecessors.len(), 1);
+        assert!(tree.predecessors[0].predecessors.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_predecessor_tree_branching() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // front and back scans merged into one document, the front scan replacing an older one
+        let person_id = Uuid::new_v4();
+        let old_front = create_test_document(person_id);
+        let mut front = create_test_document(person_id);
+        front.predecessor_1 = Some(old_front.id);
+        let back = create_test_document(person_id);
+        let mut merged = create_test_document(person_id);
+        merged.predecessor_1 = Some(front.id);
+        merged.predecessor_2 = Some(back.id);
+
+        let saved = document_repo
+            .create_batch(vec![old_front, front, back, merged], Some(audit_log.id))
+            .await?;
+
+        let tree = document_repo.load_predecessor_tree(saved[3].id).await?.unwrap();
+        assert_eq!(tree.document.id, saved[3].id);
+        assert_eq!(tree.predecessors.len(), 2);
+        assert_eq!(tree.predecessors[0].document.id, saved[1].id);
+        assert_eq!(tree.predecessors[1].document.id, saved[2].id);
+        assert_eq!(tree.predecessors[0].predecessors.len(), 1);
+        assert_eq!(tree.predecessors[0].predecessors[0].document.id, saved[0].id);
+        assert!(tree.predecessors[1].predecessors.is_empty());
+
+        // The linear chain only follows predecessor_1
+        let chain = document_repo.load_predecessor_chain(saved[3].id).await?;
+        let chain_ids: Vec<Uuid> = chain.iter().map(|d| d.id).collect();
+        assert_eq!(chain_ids, vec![saved[3].id, saved[1].id, saved[0].id]);
+
+        assert!(document_repo.load_predecessor_tree(Uuid::new_v4()).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_predecessor_chain_stops_at_max_depth() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // Each document supersedes the previous one, 8 levels more than followed
+        let person_id = Uuid::new_v4();
+        let mut documents = vec![create_test_document(person_id)];
+        for _ in 0..MAX_PREDECESSOR_DEPTH + 8 {
+            let mut document = create_test_document(person_id);
+            document.predecessor_1 = Some(documents[documents.len() - 1].id);
+            documents.push(document);
+        }
+        let saved = document_repo.create_batch(documents, Some(audit_log.id)).await?;
+        let newest = &saved[saved.len() - 1];
+
+        let chain = document_repo.load_predecessor_chain(newest.id).await?;
+        assert_eq!(chain.len(), MAX_PREDECESSOR_DEPTH + 1);
+        assert_eq!(chain[0].id, newest.id);
+        assert_eq!(chain[MAX_PREDECESSOR_DEPTH].id, saved[saved.len() - 1 - MAX_PREDECESSOR_DEPTH].id);
+
+        let mut node = document_repo.load_predecessor_tree(newest.id).await?.unwrap();
+        let mut levels = 1;
+        while let Some(predecessor) = node.predecessors.pop() {
+            assert!(node.predecessors.is_empty());
+            node = predecessor;
+            levels += 1;
+        }
+        assert_eq!(levels, MAX_PREDECESSOR_DEPTH + 1);
+        assert_eq!(node.document.id, saved[saved.len() - 1 - MAX_PREDECESSOR_DEPTH].id);
+
+        Ok(())
+    }
+}
@@ -5,6 +5,9 @@ pub mod load_audits;
 pub mod update_batch;
 pub mod delete_batch;
 pub mod exist_by_ids;
+pub mod delete_rejected_older_than;
+pub mod load_predecessor_chain;
+pub mod find_by_owner;
 #[cfg(test)]
 pub mod test_utils;
 
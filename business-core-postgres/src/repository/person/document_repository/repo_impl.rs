@@ -4,14 +4,20 @@ use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
 use async_trait::async_trait;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct DocumentRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
 }
 
 impl DocumentRepositoryImpl {
     pub fn new(executor: Executor) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 }
 
@@ -19,7 +25,8 @@ impl TryFromRow<PgRow> for DocumentModel {
     fn try_from_row(row: &PgRow) -> Result<Self, Box<dyn Error + Send + Sync>> {
         Ok(DocumentModel {
             id: row.get("id"),
-            person_id: row.get("person_id"),
+            owner_id: row.get("owner_id"),
+            owner_type: row.get("owner_type"),
             document_type: get_optional_heapless_string(row, "document_type")?.ok_or("document_type is required")?,
             document_path: get_optional_heapless_string(row, "document_path")?,
             status: row.get("status"),
@@ -1,11 +1,12 @@
-use business_core_db::models::person::document::{DocumentModel, DocumentStatus};
+use business_core_db::models::person::document::{DocumentModel, DocumentOwnerType, DocumentStatus};
 use heapless::String as HeaplessString;
 use uuid::Uuid;
 
 pub fn create_test_document(person_id: Uuid) -> DocumentModel {
     DocumentModel {
         id: Uuid::new_v4(),
-        person_id,
+        owner_id: person_id,
+        owner_type: DocumentOwnerType::Person,
         document_type: HeaplessString::try_from("Passport").unwrap(),
         document_path: Some(HeaplessString::try_from("/documents/passport.pdf").unwrap()),
         status: DocumentStatus::Uploaded,
@@ -25,7 +26,8 @@ pub fn create_test_document_with_type(
 ) -> DocumentModel {
     DocumentModel {
         id: Uuid::new_v4(),
-        person_id,
+        owner_id: person_id,
+        owner_type: DocumentOwnerType::Person,
         document_type: HeaplessString::try_from(document_type).unwrap(),
         document_path: Some(HeaplessString::try_from("/documents/document.pdf").unwrap()),
         status: DocumentStatus::Uploaded,
@@ -45,7 +47,8 @@ pub fn create_test_document_with_status(
 ) -> DocumentModel {
     DocumentModel {
         id: Uuid::new_v4(),
-        person_id,
+        owner_id: person_id,
+        owner_type: DocumentOwnerType::Person,
         document_type: HeaplessString::try_from("ID Card").unwrap(),
         document_path: Some(HeaplessString::try_from("/documents/id.pdf").unwrap()),
         status,
@@ -57,4 +60,14 @@ pub fn create_test_document_with_status(
         hash: 0,
         audit_log_id: None,
     }
-}
\ No newline at end of file
+}
+
+pub fn create_test_document_for_owner(
+    owner_type: DocumentOwnerType,
+    owner_id: Uuid,
+) -> DocumentModel {
+    DocumentModel {
+        owner_type,
+        ..create_test_document(owner_id)
+    }
+}
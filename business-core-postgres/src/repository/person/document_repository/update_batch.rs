@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use business_core_db::models::{
     audit::{audit_link::AuditLinkModel, entity_type::EntityType},
+    audit_chained::AuditChained,
     person::document::DocumentModel,
 };
 use business_core_db::repository::update_batch::UpdateBatch;
@@ -9,6 +10,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::DocumentRepositoryImpl;
 
 impl DocumentRepositoryImpl {
@@ -23,7 +25,7 @@ impl DocumentRepositoryImpl {
         }
 
         let mut updated_items = Vec::new();
-        let mut tx = self.executor.tx.lock().await;
+        let mut tx = self.operation_timeout.lock("document", &self.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -38,8 +40,9 @@ impl DocumentRepositoryImpl {
             
             let computed_hash = hash_as_i64(&entity_for_hashing)?;
             
-            // 3. Only proceed with update if entity has changed
-            if computed_hash == previous_hash {
+            // 3. Only proceed with update if entity has changed, a document stored
+            //    before migration 027 may still carry its pre-027 hash
+            if computed_hash == previous_hash || entity_for_hashing.legacy_hashes().contains(&previous_hash) {
                 updated_items.push(entity);
                 continue;
             }
@@ -57,12 +60,13 @@ impl DocumentRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO person_document_audit
-                (id, person_id, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                (id, owner_id, owner_type, document_type, document_path, status, predecessor_1, predecessor_2, predecessor_3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 "#,
             )
             .bind(entity.id)
-            .bind(entity.person_id)
+            .bind(entity.owner_id)
+            .bind(entity.owner_type)
             .bind(entity.document_type.as_str())
             .bind(entity.document_path.as_deref())
             .bind(entity.status)
@@ -78,24 +82,26 @@ impl DocumentRepositoryImpl {
             let rows_affected = sqlx::query(
                 r#"
                 UPDATE person_document SET
-                    person_id = $2,
-                    document_type = $3,
-                    document_path = $4,
-                    status = $5,
-                    predecessor_1 = $6,
-                    predecessor_2 = $7,
-                    predecessor_3 = $8,
-                    antecedent_hash = $9,
-                    antecedent_audit_log_id = $10,
-                    hash = $11,
-                    audit_log_id = $12
+                    owner_id = $2,
+                    owner_type = $3,
+                    document_type = $4,
+                    document_path = $5,
+                    status = $6,
+                    predecessor_1 = $7,
+                    predecessor_2 = $8,
+                    predecessor_3 = $9,
+                    antecedent_hash = $10,
+                    antecedent_audit_log_id = $11,
+                    hash = $12,
+                    audit_log_id = $13
                 WHERE id = $1
-                  AND hash = $13
-                  AND audit_log_id = $14
+                  AND hash = $14
+                  AND audit_log_id = $15
                 "#,
             )
             .bind(entity.id)
-            .bind(entity.person_id)
+            .bind(entity.owner_id)
+            .bind(entity.owner_type)
             .bind(entity.document_type.as_str())
             .bind(entity.document_path.as_deref())
             .bind(entity.status)
@@ -113,6 +119,8 @@ impl DocumentRepositoryImpl {
             .rows_affected();
 
             if rows_affected == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(entity = "document", id = %entity.id, "Concurrent update detected");
                 return Err("Concurrent update detected".into());
             }
             
@@ -122,19 +130,10 @@ impl DocumentRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::Document,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
-            
+
             // 8. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
             
             updated_items.push(entity);
         }
@@ -145,6 +144,7 @@ impl DocumentRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, DocumentModel> for DocumentRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "document", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<DocumentModel>,
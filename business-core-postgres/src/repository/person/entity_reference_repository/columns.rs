@@ -0,0 +1,137 @@
+use business_core_db::models::person::entity_reference::{EntityReferenceIdxModel, EntityReferenceModel};
+use std::sync::LazyLock;
+
+use crate::repository::column_list::{AuditedTableSql, ColumnList, PgQuery};
+
+/// Columns of entity_reference and entity_reference_audit
+pub(crate) const ENTITY_REFERENCE_COLUMNS: ColumnList<EntityReferenceModel> = ColumnList::new(
+    &[
+        "id",
+        "person_id",
+        "entity_role",
+        "reference_external_id",
+        "reference_details_l1",
+        "reference_details_l2",
+        "reference_details_l3",
+        "related_person_id",
+        "start_date",
+        "end_date",
+        "status",
+        "antecedent_hash",
+        "antecedent_audit_log_id",
+        "hash",
+        "audit_log_id",
+    ],
+    bind_entity_reference,
+);
+
+/// Columns of entity_reference_idx
+pub(crate) const ENTITY_REFERENCE_IDX_COLUMNS: ColumnList<EntityReferenceIdxModel> = ColumnList::new(
+    &["id", "person_id", "reference_external_id_hash", "entity_role_hash"],
+    bind_entity_reference_idx,
+);
+
+/// Write statements of entity_reference, generated from the column lists on first use
+pub(crate) static ENTITY_REFERENCE_SQL: LazyLock<AuditedTableSql> = LazyLock::new(|| {
+    AuditedTableSql::new("entity_reference", &ENTITY_REFERENCE_COLUMNS, &ENTITY_REFERENCE_IDX_COLUMNS)
+});
+
+/// `ENTITY_REFERENCE_SQL.update_idx` returning the version the trigger advanced the row to
+pub(crate) static ENTITY_REFERENCE_IDX_VERSIONED_UPDATE: LazyLock<String> =
+    LazyLock::new(|| format!("{} RETURNING version", ENTITY_REFERENCE_SQL.update_idx));
+
+fn bind_entity_reference<'q>(query: PgQuery<'q>, item: &'q EntityReferenceModel) -> PgQuery<'q> {
+    query
+        .bind(item.id)
+        .bind(item.person_id)
+        .bind(item.entity_role)
+        .bind(item.reference_external_id.as_str())
+        .bind(item.reference_details_l1.as_deref())
+        .bind(item.reference_details_l2.as_deref())
+        .bind(item.reference_details_l3.as_deref())
+        .bind(item.related_person_id)
+        .bind(item.start_date)
+        .bind(item.end_date)
+        .bind(item.status)
+        .bind(item.antecedent_hash)
+        .bind(item.antecedent_audit_log_id)
+        .bind(item.hash)
+        .bind(item.audit_log_id)
+}
+
+fn bind_entity_reference_idx<'q>(query: PgQuery<'q>, idx: &'q EntityReferenceIdxModel) -> PgQuery<'q> {
+    query
+        .bind(idx.id)
+        .bind(idx.person_id)
+        .bind(idx.reference_external_id_hash)
+        .bind(idx.entity_role_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_entity_reference, create_test_person,
+    };
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::models::person::entity_reference::RelationshipStatus;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use chrono::{TimeZone, Utc};
+    use heapless::String as HeaplessString;
+
+    #[tokio::test]
+    async fn test_every_column_round_trips() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Reference Owner");
+        let related = create_test_person("Related Person");
+        let (person_id, related_id) = (person.id, related.id);
+        person_repo.create_batch(vec![person, related], Some(audit_log.id)).await?;
+
+        // Every optional field set, timestamps in whole seconds to survive the round trip
+        let mut entity_reference = create_test_entity_reference(person_id, &format!("REF-{}", random(8)));
+        entity_reference.reference_details_l1 = Some(HeaplessString::try_from("Details one").unwrap());
+        entity_reference.reference_details_l2 = Some(HeaplessString::try_from("Details two").unwrap());
+        entity_reference.reference_details_l3 = Some(HeaplessString::try_from("Details three").unwrap());
+        entity_reference.related_person_id = Some(related_id);
+        entity_reference.start_date = Some(Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap());
+        entity_reference.end_date = Some(Utc.with_ymd_and_hms(2025, 12, 31, 17, 30, 0).unwrap());
+        entity_reference.status = Some(RelationshipStatus::Active);
+
+        let created = entity_reference_repo
+            .create_batch(vec![entity_reference], Some(audit_log.id))
+            .await?
+            .remove(0);
+        let loaded = entity_reference_repo
+            .load_batch(&[created.id])
+            .await?
+            .remove(0)
+            .ok_or("Entity reference not found")?;
+        assert_eq!(serde_json::to_value(&loaded)?, serde_json::to_value(&created)?);
+
+        let mut changed = loaded;
+        changed.reference_details_l2 = None;
+        changed.status = Some(RelationshipStatus::Terminated);
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        let updated = entity_reference_repo
+            .update_batch(vec![changed], Some(update_audit_log.id))
+            .await?
+            .remove(0);
+        let loaded = entity_reference_repo
+            .load_batch(&[updated.id])
+            .await?
+            .remove(0)
+            .ok_or("Entity reference not found")?;
+        assert_eq!(serde_json::to_value(&loaded)?, serde_json::to_value(&updated)?);
+        assert_eq!(loaded.antecedent_audit_log_id, audit_log.id);
+
+        Ok(())
+    }
+}
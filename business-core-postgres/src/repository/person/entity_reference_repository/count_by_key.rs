@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use business_core_db::repository::count_by_key::CountByKey;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::count_by_key::check_index_key;
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::EntityReferenceRepositoryImpl;
+
+/// Uuid keys of `EntityReferenceIdxModel`
+const UUID_KEYS: &[&str] = &["person_id"];
+
+/// Answered from the cache, or from entity_reference_idx when the repository does not
+/// serve finders from its cache
+#[async_trait]
+impl CountByKey<Postgres> for EntityReferenceRepositoryImpl {
+    async fn count_by_uuid_key(&self, key_name: &str, value: Uuid) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("entity_reference", UUID_KEYS, key_name)?;
+        if !self.serves_key_from_cache(key_name, value.into()) {
+            return Ok(self.find_idx_by_column(key_name, value).await?.len());
+        }
+        let cache = self.entity_reference_idx_cache.read().await;
+        Ok(cache.get_by_uuid_index(key_name, &value).len())
+    }
+
+    async fn count_by_i64_key(&self, key_name: &str, value: i64) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("entity_reference", <Self as FindByI64Key>::I64_KEYS, key_name)?;
+        Ok(self.find_by_i64_key(key_name, value).await?.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::count_by_key::count_idx_rows;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_entity_reference, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::count_by_key::CountByKey;
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    #[tokio::test]
+    async fn test_count_by_person_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Referenced Person");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let references = (0..2)
+            .map(|i| create_test_entity_reference(person_id, &format!("COUNT-REF-{i}")))
+            .collect();
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let cached = entity_reference_repo.count_by_uuid_key("person_id", person_id).await?;
+        let sql = count_idx_rows(&entity_reference_repo.executor, &entity_reference_repo.operation_timeout, "entity_reference_idx", "person_id", person_id).await?;
+        assert_eq!(cached, 2);
+        assert_eq!(sql, cached);
+
+        Ok(())
+    }
+}
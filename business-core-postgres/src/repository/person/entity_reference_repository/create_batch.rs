@@ -5,11 +5,17 @@ use business_core_db::models::{
 };
 use business_core_db::repository::create_batch::CreateBatch;
 use sqlx::Postgres;
+use std::collections::HashMap;
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::columns::{ENTITY_REFERENCE_COLUMNS, ENTITY_REFERENCE_IDX_COLUMNS, ENTITY_REFERENCE_SQL};
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::EntityReferenceRepositoryImpl;
 
 impl EntityReferenceRepositoryImpl {
@@ -28,7 +34,7 @@ impl EntityReferenceRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("entity_reference", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for mut item in items {
@@ -45,70 +51,26 @@ impl EntityReferenceRepositoryImpl {
                 item.audit_log_id = Some(audit_log_id);
 
                 // Execute audit insert
-                sqlx::query(
-                    r#"
-                    INSERT INTO entity_reference_audit
-                    (id, person_id, entity_role, reference_external_id, reference_details_l1, reference_details_l2, reference_details_l3, related_person_id, start_date, end_date, status, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_id)
-                .bind(item.entity_role)
-                .bind(item.reference_external_id.as_str())
-                .bind(item.reference_details_l1.as_deref())
-                .bind(item.reference_details_l2.as_deref())
-                .bind(item.reference_details_l3.as_deref())
-                .bind(item.related_person_id)
-                .bind(item.start_date)
-                .bind(item.end_date)
-                .bind(item.status)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
+                ENTITY_REFERENCE_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.insert_audit), &item)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
 
                 // Execute main insert
-                sqlx::query(
-                    r#"
-                    INSERT INTO entity_reference
-                    (id, person_id, entity_role, reference_external_id, reference_details_l1, reference_details_l2, reference_details_l3, related_person_id, start_date, end_date, status, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_id)
-                .bind(item.entity_role)
-                .bind(item.reference_external_id.as_str())
-                .bind(item.reference_details_l1.as_deref())
-                .bind(item.reference_details_l2.as_deref())
-                .bind(item.reference_details_l3.as_deref())
-                .bind(item.related_person_id)
-                .bind(item.start_date)
-                .bind(item.end_date)
-                .bind(item.status)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
+                ENTITY_REFERENCE_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.insert), &item)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
 
                 // Insert into index table
                 let idx = item.to_index();
-                sqlx::query(
-                    r#"
-                    INSERT INTO entity_reference_idx (id, person_id, reference_external_id_hash)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(idx.id)
-                .bind(idx.person_id)
-                .bind(idx.reference_external_id_hash)
-                .execute(&mut **transaction)
-                .await?;
+                ENTITY_REFERENCE_IDX_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.insert_idx), &idx)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -116,29 +78,31 @@ impl EntityReferenceRepositoryImpl {
                     entity_id: item.id,
                     entity_type: EntityType::EntityReference,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
                 indices.push(idx);
                 saved_items.push(item);
             }
+
+            let mut deltas: HashMap<Uuid, i32> = HashMap::new();
+            for item in &saved_items {
+                *deltas.entry(item.person_id).or_insert(0) += 1;
+            }
+            Self::adjust_entity_reference_counts(&mut **transaction, &deltas, audit_log_id).await?;
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.entity_reference_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.entity_reference_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
@@ -147,6 +111,7 @@ impl EntityReferenceRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, EntityReferenceModel> for EntityReferenceRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "entity_reference", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<EntityReferenceModel>,
@@ -162,7 +127,10 @@ mod tests {
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
     use crate::test_helper::{random, setup_test_context, setup_test_context_and_listen};
     use business_core_db::{
-        models::{index_aware::IndexAware, person::entity_reference::EntityReferenceModel},
+        models::{
+            index_aware::IndexAware,
+            person::entity_reference::{entity_role_hash, EntityReferenceModel, RelationshipRole},
+        },
         repository::create_batch::CreateBatch,
     };
     use tokio::time::{sleep, Duration};
@@ -233,7 +201,8 @@ mod tests {
         let person_id = test_person.id;
 
         // Create a test entity reference
-        let test_entity_reference = create_test_entity_reference(person_id, &format!("REF-{}", random(10)));
+        let mut test_entity_reference = create_test_entity_reference(person_id, &format!("REF-{}", random(10)));
+        test_entity_reference.entity_role = RelationshipRole::UltimateBeneficialOwner;
         let entity_reference_idx = test_entity_reference.to_index();
 
         // Give listener more time to start and establish connection
@@ -332,10 +301,11 @@ mod tests {
         .expect("Failed to insert entity_reference");
 
         // Then insert the entity_reference index directly into the database using raw SQL
-        sqlx::query("INSERT INTO entity_reference_idx (id, person_id, reference_external_id_hash) VALUES ($1, $2, $3)")
+        sqlx::query("INSERT INTO entity_reference_idx (id, person_id, reference_external_id_hash, entity_role_hash) VALUES ($1, $2, $3, $4)")
             .bind(entity_reference_idx.id)
             .bind(entity_reference_idx.person_id)
             .bind(entity_reference_idx.reference_external_id_hash)
+            .bind(entity_reference_idx.entity_role_hash)
             .execute(&**pool)
             .await
             .expect("Failed to insert entity_reference index");
@@ -363,6 +333,10 @@ mod tests {
         assert_eq!(cached_entity_reference.id, entity_reference_idx.id);
         assert_eq!(cached_entity_reference.person_id, entity_reference_idx.person_id);
         assert_eq!(cached_entity_reference.reference_external_id_hash, entity_reference_idx.reference_external_id_hash);
+        assert_eq!(
+            cached_entity_reference.entity_role_hash,
+            entity_role_hash(RelationshipRole::UltimateBeneficialOwner)
+        );
 
         // Drop the read lock before proceeding to allow notification handler to process
         drop(cache);
@@ -398,4 +372,67 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_batch_maps_foreign_key_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        // The audit log is never persisted
+        let audit_log = create_test_audit_log();
+        let entity_reference = create_test_entity_reference(uuid::Uuid::new_v4(), "REF-ORPHAN");
+
+        let error = entity_reference_repo
+            .create_batch(vec![entity_reference], Some(audit_log.id))
+            .await
+            .expect_err("Creating an entity reference with an unknown audit log must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::ForeignKeyViolation { entity, referenced_table, .. }) => {
+                assert_eq!(entity, "entity_reference");
+                assert_eq!(referenced_table, "audit_log");
+            }
+            other => panic!("Expected ForeignKeyViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_maps_unique_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Duplicated Reference Owner");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let entity_reference = create_test_entity_reference(person_id, "REF-DUPLICATE");
+        entity_reference_repo
+            .create_batch(vec![entity_reference.clone()], Some(audit_log.id))
+            .await?;
+
+        let error = entity_reference_repo
+            .create_batch(vec![entity_reference], Some(audit_log.id))
+            .await
+            .expect_err("Creating the same entity reference twice must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::UniqueViolation { entity, constraint }) => {
+                assert_eq!(entity, "entity_reference");
+                assert!(!constraint.is_empty());
+            }
+            other => panic!("Expected UniqueViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -2,11 +2,17 @@ use business_core_db::models::audit::{AuditLinkModel, EntityType};
 use async_trait::async_trait;
 use business_core_db::repository::load_batch::LoadBatch;
 use business_core_db::repository::delete_batch::DeleteBatch;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
 use sqlx::Postgres;
+use std::collections::HashMap;
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use super::columns::{ENTITY_REFERENCE_COLUMNS, ENTITY_REFERENCE_SQL};
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::EntityReferenceRepositoryImpl;
 
 impl EntityReferenceRepositoryImpl {
@@ -14,17 +20,18 @@ impl EntityReferenceRepositoryImpl {
         repo: &EntityReferenceRepositoryImpl,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         let audit_log_id = audit_log_id.ok_or("audit_log_id is required for EntityReferenceModel")?;
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         let entities_to_delete = repo.load_batch(ids).await?;
-        let mut deleted_count = 0;
+        let mut deleted = Vec::new();
+        let mut deltas: HashMap<Uuid, i32> = HashMap::new();
 
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("entity_reference", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
 
             for entity in entities_to_delete.into_iter().flatten() {
@@ -37,31 +44,17 @@ impl EntityReferenceRepositoryImpl {
                 let final_hash = hash_as_i64(&final_audit_entity)?;
                 final_audit_entity.hash = final_hash;
 
-                sqlx::query(
-                    r#"
-                    INSERT INTO entity_reference_audit
-                    (id, person_id, entity_role, reference_external_id, reference_details_l1, reference_details_l2, reference_details_l3, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-                    "#,
-                )
-                .bind(final_audit_entity.id)
-                .bind(final_audit_entity.person_id)
-                .bind(final_audit_entity.entity_role)
-                .bind(final_audit_entity.reference_external_id.as_str())
-                .bind(final_audit_entity.reference_details_l1.as_deref())
-                .bind(final_audit_entity.reference_details_l2.as_deref())
-                .bind(final_audit_entity.reference_details_l3.as_deref())
-                .bind(final_audit_entity.antecedent_hash)
-                .bind(final_audit_entity.antecedent_audit_log_id)
-                .bind(final_audit_entity.hash)
-                .bind(final_audit_entity.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
+                ENTITY_REFERENCE_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.insert_audit), &final_audit_entity)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
 
                 let result = sqlx::query(r#"DELETE FROM entity_reference WHERE id = $1"#)
                     .bind(entity.id)
                     .execute(&mut **transaction)
-                    .await?;
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -69,40 +62,53 @@ impl EntityReferenceRepositoryImpl {
                     entity_id: entity.id,
                     entity_type: EntityType::EntityReference,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
                 
-                deleted_count += result.rows_affected() as usize;
+                if result.rows_affected() > 0 {
+                    *deltas.entry(entity.person_id).or_insert(0) -= 1;
+                    deleted.push(entity.id);
+                }
             }
+
+            Self::adjust_entity_reference_counts(&mut **transaction, &deltas, audit_log_id).await?;
         }
         
-        {
+        if repo.cache_policy.maintains_cache() {
             let cache = repo.entity_reference_idx_cache.read().await;
-            for id in ids {
+            for id in &deleted {
+                // No replayed notification of the row brings it back
+                repo.cache_versions.admit(*id, CacheVersions::DELETED);
                 cache.remove(id);
             }
+            if let Some(bound) = &repo.cache_bound {
+                bound.record_removal(&deleted);
+            }
         }
         
-        Ok(deleted_count)
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for EntityReferenceRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "entity_reference", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids, audit_log_id).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for EntityReferenceRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids, audit_log_id).await
     }
 }
@@ -110,10 +116,15 @@ impl DeleteBatch<Postgres> for EntityReferenceRepositoryImpl {
 #[cfg(test)]
 mod tests {
     use crate::repository::person::entity_reference_repository::test_utils::create_test_entity_reference;
+    use crate::error::RepositoryError;
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
     use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit_chained::verify_chain_from;
+    use business_core_db::models::person::entity_reference::RelationshipStatus;
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::repository::delete_batch::DeleteBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use chrono::DateTime;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -175,4 +186,170 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_deleted_version_keeps_every_column() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let person = create_test_person("Frank Moore");
+        let person_id = person.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut entity_reference = create_test_entity_reference(person_id, "DELETE-COLUMNS");
+        entity_reference.related_person_id = Some(Uuid::new_v4());
+        entity_reference.start_date = DateTime::from_timestamp(1_704_067_200, 0);
+        entity_reference.status = Some(RelationshipStatus::Active);
+        let saved = entity_reference_repo.create_batch(vec![entity_reference], Some(audit_log.id)).await?.remove(0);
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        entity_reference_repo.delete_batch(&[saved.id], Some(delete_audit_log.id)).await?;
+
+        let versions = entity_reference_repo.load_audit_versions(saved.id).await?;
+        assert_eq!(versions.len(), 2);
+        let deleted = &versions[1].item;
+        assert_eq!(deleted.audit_log_id, Some(delete_audit_log.id));
+        assert_eq!(
+            (deleted.related_person_id, deleted.start_date, deleted.status),
+            (saved.related_person_id, saved.start_date, saved.status)
+        );
+        assert_eq!(verify_chain_from(&versions, None), Ok(()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entity_reference_count_follows_create_and_delete() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let person = create_test_person("Frank Moore");
+        let person_id = person.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut entity_references = Vec::new();
+        for i in 0..3 {
+            entity_references.push(create_test_entity_reference(person_id, &format!("COUNT-{i}")));
+        }
+        let create_audit_log = create_test_audit_log();
+        audit_log_repo.create(&create_audit_log).await?;
+        let saved = entity_reference_repo.create_batch(entity_references, Some(create_audit_log.id)).await?;
+
+        let loaded = person_repo.load_batch(&[person_id]).await?;
+        let person = loaded[0].as_ref().ok_or("Person not found")?;
+        assert_eq!(person.entity_reference_count, 3);
+        assert_eq!(person.audit_log_id, Some(create_audit_log.id));
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        entity_reference_repo.delete_batch(&[saved[0].id], Some(delete_audit_log.id)).await?;
+
+        let loaded = person_repo.load_batch(&[person_id]).await?;
+        let person = loaded[0].as_ref().ok_or("Person not found")?;
+        assert_eq!(person.entity_reference_count, 2);
+        assert_eq!(person.audit_log_id, Some(delete_audit_log.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entity_reference_count_in_running_audit_log() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let person = create_test_person("Grace Hall");
+        let person_id = person.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let entity_references = vec![
+            create_test_entity_reference(person_id, "SAME-LOG-0"),
+            create_test_entity_reference(person_id, "SAME-LOG-1"),
+        ];
+        entity_reference_repo.create_batch(entity_references, Some(audit_log.id)).await?;
+
+        // The count is a new version under a follow-up audit log, the first one is kept
+        let loaded = person_repo.load_batch(&[person_id]).await?;
+        let person = loaded[0].as_ref().ok_or("Person not found")?;
+        assert_eq!(person.entity_reference_count, 2);
+        assert_eq!(person.antecedent_audit_log_id, audit_log.id);
+        let follow_up_audit_log_id = person.audit_log_id.ok_or("Person must have an audit log")?;
+        assert_ne!(follow_up_audit_log_id, audit_log.id);
+
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let counts: Vec<(Uuid, i32)> = sqlx::query_as(
+            "SELECT audit_log_id, entity_reference_count FROM person_audit WHERE id = $1 ORDER BY entity_reference_count",
+        )
+        .bind(person_id)
+        .fetch_all(&mut **transaction)
+        .await?;
+        assert_eq!(counts, vec![(audit_log.id, 0), (follow_up_audit_log_id, 2)]);
+        let (updated_at, updated_by): (chrono::DateTime<chrono::Utc>, Uuid) =
+            sqlx::query_as("SELECT updated_at, updated_by_person_id FROM audit_log WHERE id = $1")
+                .bind(follow_up_audit_log_id)
+                .fetch_one(&mut **transaction)
+                .await?;
+        assert_eq!(updated_at.timestamp_micros(), audit_log.updated_at.timestamp_micros());
+        assert_eq!(updated_by, audit_log.updated_by_person_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entity_reference_count_underflow() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let person = create_test_person("Henry Price");
+        let person_id = person.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let create_audit_log = create_test_audit_log();
+        audit_log_repo.create(&create_audit_log).await?;
+        let saved = entity_reference_repo
+            .create_batch(vec![create_test_entity_reference(person_id, "UNDERFLOW")], Some(create_audit_log.id))
+            .await?;
+
+        // Lose the count the reference was booked with
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person SET entity_reference_count = 0 WHERE id = $1")
+                .bind(person_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        let error = entity_reference_repo
+            .delete_batch(&[saved[0].id], Some(delete_audit_log.id))
+            .await
+            .expect_err("A count below zero must be rejected");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::EntityReferenceCountUnderflow { person_id: id, count, delta }) => {
+                assert_eq!(*id, person_id);
+                assert_eq!(*count, 0);
+                assert_eq!(*delta, -1);
+            }
+            other => panic!("Expected EntityReferenceCountUnderflow, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
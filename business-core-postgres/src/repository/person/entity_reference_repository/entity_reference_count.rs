@@ -0,0 +1,107 @@
+use business_core_db::models::person::person::PersonModel;
+use business_core_db::utils::HashVersion;
+use crate::repository::person::person_repository::PersonRepositoryImpl;
+use crate::utils::TryFromRow;
+use sqlx::{PgConnection, Row};
+use std::collections::HashMap;
+use std::error::Error;
+use crate::error::{map_db_error, RepositoryError};
+use uuid::Uuid;
+
+use super::repo_impl::EntityReferenceRepositoryImpl;
+
+impl EntityReferenceRepositoryImpl {
+    /// Apply `entity_reference_count` deltas to the owning persons
+    ///
+    /// Runs on the connection of the calling batch so the person audit chain advances
+    /// in the same transaction as the entity references. The persons are locked with
+    /// `FOR UPDATE` and persons that no longer exist are skipped. A delta taking a count
+    /// below zero fails with `RepositoryError::EntityReferenceCountUnderflow`.
+    ///
+    /// Each changed count is written as a new person version. person_audit holds one
+    /// version per audit log, so a person already written under `audit_log_id` gets its
+    /// version under a follow-up audit log copying the author and time of `audit_log_id`.
+    pub(super) async fn adjust_entity_reference_counts(
+        conn: &mut PgConnection,
+        deltas: &HashMap<Uuid, i32>,
+        audit_log_id: Uuid,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if deltas.is_empty() {
+            return Ok(());
+        }
+
+        let person_ids: Vec<Uuid> = deltas.keys().copied().collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT person.*, person_idx.hash_version
+            FROM person JOIN person_idx ON person_idx.id = person.id
+            WHERE person.id = ANY($1)
+            FOR UPDATE OF person
+            "#,
+        )
+        .bind(&person_ids)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| map_db_error("person", e))?;
+
+        // The person_idx rows are rewritten on update, keep their current hash version
+        let mut persons_to_update: HashMap<HashVersion, Vec<PersonModel>> = HashMap::new();
+        let mut persons_to_follow_up: HashMap<HashVersion, Vec<PersonModel>> = HashMap::new();
+        for row in rows {
+            let mut person = PersonModel::try_from_row(&row)?;
+            let hash_version = HashVersion::try_from(row.get::<i16, _>("hash_version"))?;
+            let delta = deltas.get(&person.id).copied().unwrap_or(0);
+            let count = person
+                .entity_reference_count
+                .checked_add(delta)
+                .filter(|count| *count >= 0)
+                .ok_or(RepositoryError::EntityReferenceCountUnderflow {
+                    person_id: person.id,
+                    count: person.entity_reference_count,
+                    delta,
+                })?;
+            if count == person.entity_reference_count {
+                continue;
+            }
+            person.entity_reference_count = count;
+
+            let persons = if person.audit_log_id == Some(audit_log_id) {
+                &mut persons_to_follow_up
+            } else {
+                &mut persons_to_update
+            };
+            persons.entry(hash_version).or_default().push(person);
+        }
+
+        for (hash_version, persons) in persons_to_update {
+            PersonRepositoryImpl::update_in_connection(&mut *conn, persons, audit_log_id, hash_version).await?;
+        }
+        if !persons_to_follow_up.is_empty() {
+            let follow_up_audit_log_id = Self::create_follow_up_audit_log(conn, audit_log_id).await?;
+            for (hash_version, persons) in persons_to_follow_up {
+                PersonRepositoryImpl::update_in_connection(&mut *conn, persons, follow_up_audit_log_id, hash_version).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert an audit log with the author and time of `audit_log_id`, returning its id
+    async fn create_follow_up_audit_log(
+        conn: &mut PgConnection,
+        audit_log_id: Uuid,
+    ) -> Result<Uuid, Box<dyn Error + Send + Sync>> {
+        let follow_up_audit_log_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (id, updated_at, updated_by_person_id)
+            SELECT $2, updated_at, updated_by_person_id FROM audit_log WHERE id = $1
+            "#,
+        )
+        .bind(audit_log_id)
+        .bind(follow_up_audit_log_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| map_db_error("audit_log", e))?;
+        Ok(follow_up_audit_log_id)
+    }
+}
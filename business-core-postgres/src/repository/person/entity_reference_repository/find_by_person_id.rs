@@ -1,29 +1,64 @@
+use std::collections::HashMap;
 use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::person::entity_reference::EntityReferenceIdxModel;
-use business_core_db::repository::pagination::{Page, PageRequest};
+use business_core_db::repository::pagination::{Page, PageRequest, SortOrder};
 
 use super::repo_impl::EntityReferenceRepositoryImpl;
 
 impl EntityReferenceRepositoryImpl {
+    /// A page of the references of a person, newest `start_date` first
     pub async fn find_by_person_id(
         &self,
         person_id: Uuid,
         page: PageRequest,
     ) -> Result<Page<EntityReferenceIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.entity_reference_idx_cache.read().await;
-        let all_items = cache.get_by_uuid_index("person_id", &person_id);
-        
+        self.find_by_person_id_ordered(person_id, page, SortOrder::Descending).await
+    }
+
+    /// A page of the references of a person, ordered by `start_date` in `order`
+    ///
+    /// References without a `start_date` come last either way, and references with the
+    /// same `start_date` are ordered by id, so pages are stable across calls.
+    pub async fn find_by_person_id_ordered(
+        &self,
+        person_id: Uuid,
+        page: PageRequest,
+        order: SortOrder,
+    ) -> Result<Page<EntityReferenceIdxModel>, Box<dyn Error + Send + Sync>> {
+        let mut all_items: HashMap<Uuid, EntityReferenceIdxModel> = self
+            .find_idx_by_person_id(person_id)
+            .await?
+            .into_iter()
+            .map(|idx| (idx.id, idx))
+            .collect();
         let total = all_items.len();
-        let start = page.offset;
-        let end = (start + page.limit).min(total);
-        
-        let items = if start < total {
-            all_items[start..end].to_vec()
-        } else {
-            Vec::new()
+        if page.offset >= total {
+            return Ok(Page::new(Vec::new(), total, page.limit, page.offset));
+        }
+
+        // The index models carry no start_date, the main table orders the page
+        let ids: Vec<Uuid> = all_items.keys().copied().collect();
+        let query = format!(
+            "SELECT id FROM entity_reference WHERE id = ANY($1) \
+             ORDER BY start_date {} NULLS LAST, id LIMIT $2 OFFSET $3",
+            order.as_sql()
+        );
+        let page_ids: Vec<Uuid> = {
+            let mut tx = self.operation_timeout.lock("entity_reference", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query_scalar(&query)
+                .bind(&ids)
+                .bind(page.limit as i64)
+                .bind(page.offset as i64)
+                .fetch_all(&mut **transaction)
+                .await?
         };
-        
+
+        let items = page_ids
+            .iter()
+            .filter_map(|id| all_items.remove(id))
+            .collect();
         Ok(Page::new(items, total, page.limit, page.offset))
     }
 }
@@ -32,7 +67,8 @@ impl EntityReferenceRepositoryImpl {
 mod tests {
     use crate::test_helper::setup_test_context;
     use business_core_db::repository::create_batch::CreateBatch;
-    use business_core_db::repository::pagination::PageRequest;
+    use business_core_db::repository::pagination::{PageRequest, SortOrder};
+    use chrono::{TimeZone, Utc};
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_person, create_test_entity_reference};
 
     #[tokio::test]
@@ -196,4 +232,62 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_find_by_person_id_newest_start_date_first() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person = create_test_person("test-person");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let start = |day: u32| Some(Utc.with_ymd_and_hms(2024, 3, day, 0, 0, 0).unwrap());
+        let mut oldest = create_test_entity_reference(person_id, "ordered-oldest");
+        oldest.start_date = start(1);
+        let mut newest = create_test_entity_reference(person_id, "ordered-newest");
+        newest.start_date = start(20);
+        let mut undated = create_test_entity_reference(person_id, "ordered-undated");
+        undated.start_date = None;
+        let mut same_day = vec![
+            create_test_entity_reference(person_id, "ordered-same-day-a"),
+            create_test_entity_reference(person_id, "ordered-same-day-b"),
+        ];
+        for reference in &mut same_day {
+            reference.start_date = start(10);
+        }
+        same_day.sort_by_key(|reference| reference.id);
+
+        let expected = vec![newest.id, same_day[0].id, same_day[1].id, oldest.id, undated.id];
+        entity_reference_repo
+            .create_batch(
+                vec![undated, oldest, same_day[1].clone(), newest, same_day[0].clone()],
+                Some(audit_log.id),
+            )
+            .await?;
+
+        let page = entity_reference_repo.find_by_person_id(person_id, PageRequest::new(10, 0)).await?;
+        assert_eq!(page.items.iter().map(|idx| idx.id).collect::<Vec<_>>(), expected);
+
+        // Pages follow the same order
+        let page_2 = entity_reference_repo.find_by_person_id(person_id, PageRequest::new(2, 2)).await?;
+        assert_eq!(page_2.total, 5);
+        assert_eq!(page_2.items.iter().map(|idx| idx.id).collect::<Vec<_>>(), expected[2..4].to_vec());
+
+        // Oldest first on request, still without start date last and ties by id
+        let page = entity_reference_repo
+            .find_by_person_id_ordered(person_id, PageRequest::new(10, 0), SortOrder::Ascending)
+            .await?;
+        assert_eq!(
+            page.items.iter().map(|idx| idx.id).collect::<Vec<_>>(),
+            vec![expected[3], expected[1], expected[2], expected[0], expected[4]]
+        );
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use std::error::Error;
 use business_core_db::models::person::entity_reference::EntityReferenceIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::EntityReferenceRepositoryImpl;
 
 impl EntityReferenceRepositoryImpl {
@@ -8,9 +9,7 @@ impl EntityReferenceRepositoryImpl {
         &self,
         reference_external_id_hash: i64,
     ) -> Result<Vec<EntityReferenceIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.entity_reference_idx_cache.read().await;
-        let items = cache.get_by_i64_index("reference_external_id_hash", &reference_external_id_hash);
-        Ok(items)
+        self.find_by_i64_key("reference_external_id_hash", reference_external_id_hash).await
     }
 }
 
@@ -0,0 +1,343 @@
+use business_core_db::models::person::entity_reference::{entity_role_hash, EntityReferenceModel, RelationshipRole};
+use crate::utils::TryFromRow;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::EntityReferenceRepositoryImpl;
+
+impl EntityReferenceRepositoryImpl {
+    /// References with the given role, e.g. all guarantors
+    ///
+    /// The full references are read from the main table, which also filters on
+    /// `end_date`. With `active_only`, references ending on or before the current date
+    /// of the repository clock are left out; the day of `end_date` is taken in UTC.
+    pub async fn find_by_role(
+        &self,
+        role: RelationshipRole,
+        active_only: bool,
+    ) -> Result<Vec<EntityReferenceModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_role_impl(None, role, active_only).await
+    }
+
+    /// References of a person with the given role
+    ///
+    /// The candidates are the index models of the person with the role's
+    /// entity_role_hash, found as by `find_idx_by_person_id`, so the other references of
+    /// the person are not read. The main table then filters them on the role and, with
+    /// `active_only`, on the current date of the repository clock, see `find_by_role`.
+    pub async fn find_by_person_and_role(
+        &self,
+        person_id: Uuid,
+        role: RelationshipRole,
+        active_only: bool,
+    ) -> Result<Vec<EntityReferenceModel>, Box<dyn Error + Send + Sync>> {
+        let candidate_ids = self.ids_by_person_and_role(person_id, role).await?;
+        if candidate_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.find_by_role_impl(Some(candidate_ids), role, active_only).await
+    }
+
+    async fn ids_by_person_and_role(
+        &self,
+        person_id: Uuid,
+        role: RelationshipRole,
+    ) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
+        let role_hash = entity_role_hash(role);
+        Ok(self
+            .find_idx_by_person_id(person_id)
+            .await?
+            .into_iter()
+            .filter(|idx| idx.entity_role_hash == role_hash)
+            .map(|idx| idx.id)
+            .collect())
+    }
+
+    async fn find_by_role_impl(
+        &self,
+        ids: Option<Vec<Uuid>>,
+        role: RelationshipRole,
+        active_only: bool,
+    ) -> Result<Vec<EntityReferenceModel>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.operation_timeout.lock("entity_reference", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT * FROM entity_reference
+                WHERE entity_role = $1
+                AND ($2::UUID[] IS NULL OR id = ANY($2))
+                AND (NOT $3 OR end_date IS NULL OR (end_date AT TIME ZONE 'UTC')::DATE > $4)
+                ORDER BY id
+                "#,
+            )
+            .bind(role)
+            .bind(ids)
+            .bind(active_only)
+            .bind(self.clock.today())
+            .fetch_all(&mut **transaction)
+            .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(EntityReferenceModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::cache_versions::PendingVersions;
+    use crate::repository::person::entity_reference_repository::test_utils::create_test_entity_reference;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::person::EntityReferenceRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::entity_reference::{entity_role_hash, RelationshipRole};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::FixedClock;
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_find_by_role() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let borrower = create_test_person("Borrower");
+        let other_borrower = create_test_person("Other Borrower");
+        let (borrower_id, other_borrower_id) = (borrower.id, other_borrower.id);
+        person_repo.create_batch(vec![borrower, other_borrower], Some(audit_log.id)).await?;
+
+        let mut guarantor = create_test_entity_reference(borrower_id, "ROLE-GUARANTOR-1");
+        guarantor.entity_role = RelationshipRole::Guarantor;
+        let mut other_guarantor = create_test_entity_reference(other_borrower_id, "ROLE-GUARANTOR-2");
+        other_guarantor.entity_role = RelationshipRole::Guarantor;
+        let mut beneficiary = create_test_entity_reference(borrower_id, "ROLE-BENEFICIARY");
+        beneficiary.entity_role = RelationshipRole::Beneficiary;
+        let (guarantor_id, other_guarantor_id, beneficiary_id) = (guarantor.id, other_guarantor.id, beneficiary.id);
+        entity_reference_repo
+            .create_batch(vec![guarantor, other_guarantor, beneficiary], Some(audit_log.id))
+            .await?;
+
+        let guarantors: HashSet<_> = entity_reference_repo
+            .find_by_role(RelationshipRole::Guarantor, false)
+            .await?
+            .into_iter()
+            .map(|reference| reference.id)
+            .collect();
+        assert!(guarantors.contains(&guarantor_id));
+        assert!(guarantors.contains(&other_guarantor_id));
+        assert!(!guarantors.contains(&beneficiary_id));
+
+        let of_borrower = entity_reference_repo
+            .find_by_person_and_role(borrower_id, RelationshipRole::Guarantor, false)
+            .await?;
+        assert_eq!(of_borrower.len(), 1);
+        assert_eq!(of_borrower[0].id, guarantor_id);
+
+        let beneficiaries = entity_reference_repo
+            .find_by_person_and_role(borrower_id, RelationshipRole::Beneficiary, false)
+            .await?;
+        assert_eq!(beneficiaries.len(), 1);
+        assert_eq!(beneficiaries[0].id, beneficiary_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_role_active_only() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let borrower = create_test_person("Borrower With History");
+        let borrower_id = borrower.id;
+        person_repo.create_batch(vec![borrower], Some(audit_log.id)).await?;
+
+        let mut ended = create_test_entity_reference(borrower_id, "ACTIVE-ENDED");
+        ended.end_date = Some(Utc::now() - Duration::days(30));
+        let mut ending_later = create_test_entity_reference(borrower_id, "ACTIVE-LATER");
+        ending_later.end_date = Some(Utc::now() + Duration::days(30));
+        let open_ended = create_test_entity_reference(borrower_id, "ACTIVE-OPEN");
+        let mut references = vec![ended, ending_later, open_ended];
+        for reference in &mut references {
+            reference.entity_role = RelationshipRole::Guarantor;
+        }
+        let ids: Vec<_> = references.iter().map(|reference| reference.id).collect();
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let all: HashSet<_> = entity_reference_repo
+            .find_by_person_and_role(borrower_id, RelationshipRole::Guarantor, false)
+            .await?
+            .into_iter()
+            .map(|reference| reference.id)
+            .collect();
+        assert_eq!(all, ids.iter().copied().collect());
+
+        let active: HashSet<_> = entity_reference_repo
+            .find_by_person_and_role(borrower_id, RelationshipRole::Guarantor, true)
+            .await?
+            .into_iter()
+            .map(|reference| reference.id)
+            .collect();
+        assert_eq!(active, HashSet::from([ids[1], ids[2]]));
+
+        let active_guarantors: HashSet<_> = entity_reference_repo
+            .find_by_role(RelationshipRole::Guarantor, true)
+            .await?
+            .into_iter()
+            .map(|reference| reference.id)
+            .collect();
+        assert!(!active_guarantors.contains(&ids[0]));
+        assert!(active_guarantors.contains(&ids[2]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_role_active_on_clock_date() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let borrower = create_test_person("Borrower On The Clock");
+        let borrower_id = borrower.id;
+        person_repo.create_batch(vec![borrower], Some(audit_log.id)).await?;
+
+        let mut ending_on_leap_day = create_test_entity_reference(borrower_id, "CLOCK-LEAP-DAY");
+        ending_on_leap_day.end_date = Some(Utc.with_ymd_and_hms(2096, 2, 29, 9, 0, 0).unwrap());
+        let mut ending_next_year = create_test_entity_reference(borrower_id, "CLOCK-NEXT-YEAR");
+        ending_next_year.end_date = Some(Utc.with_ymd_and_hms(2097, 1, 1, 0, 0, 0).unwrap());
+        let mut references = vec![ending_on_leap_day, ending_next_year];
+        for reference in &mut references {
+            reference.entity_role = RelationshipRole::Guarantor;
+        }
+        let ids: Vec<_> = references.iter().map(|reference| reference.id).collect();
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let active_on = |y: i32, m: u32, d: u32| {
+            // Shares the session cache, which holds the uncommitted references
+            let repo = EntityReferenceRepositoryImpl {
+                executor: entity_reference_repo.executor.clone(),
+                entity_reference_idx_cache: entity_reference_repo.entity_reference_idx_cache.clone(),
+                entity_reference_idx_shared_cache: entity_reference_repo.entity_reference_idx_shared_cache.clone(),
+                cache_policy: entity_reference_repo.cache_policy,
+                operation_timeout: entity_reference_repo.operation_timeout,
+                cache_versions: PendingVersions::new(entity_reference_repo.cache_versions.shared().clone()),
+                cache_bound: None,
+                clock: Arc::new(FixedClock::new(NaiveDate::from_ymd_opt(y, m, d).unwrap())),
+            };
+            async move {
+                repo.find_by_person_and_role(borrower_id, RelationshipRole::Guarantor, true)
+                    .await
+                    .map(|found| found.into_iter().map(|reference| reference.id).collect::<HashSet<_>>())
+            }
+        };
+
+        // A reference no longer applies on its end date
+        assert_eq!(active_on(2096, 2, 28).await?, HashSet::from([ids[0], ids[1]]));
+        assert_eq!(active_on(2096, 2, 29).await?, HashSet::from([ids[1]]));
+        assert_eq!(active_on(2096, 12, 31).await?, HashSet::from([ids[1]]));
+        assert!(active_on(2097, 1, 1).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_person_and_role_narrows_by_cache() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let company = create_test_person("Company With Owners");
+        let other_company = create_test_person("Other Company");
+        let (company_id, other_company_id) = (company.id, other_company.id);
+        person_repo.create_batch(vec![company, other_company], Some(audit_log.id)).await?;
+
+        let mut references = Vec::new();
+        for (person_id, external_id, role) in [
+            (company_id, "IDX-ROLE-UBO", RelationshipRole::UltimateBeneficialOwner),
+            (company_id, "IDX-ROLE-DIRECTOR", RelationshipRole::Director),
+            (company_id, "IDX-ROLE-SIGNATORY", RelationshipRole::AuthorizedSignatory),
+            (other_company_id, "IDX-ROLE-OTHER-UBO", RelationshipRole::UltimateBeneficialOwner),
+        ] {
+            let mut reference = create_test_entity_reference(person_id, external_id);
+            reference.entity_role = role;
+            references.push(reference);
+        }
+        let owner_id = references[0].id;
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let owners = entity_reference_repo
+            .find_by_person_and_role(company_id, RelationshipRole::UltimateBeneficialOwner, false)
+            .await?;
+        assert_eq!(owners.len(), 1);
+        assert_eq!(owners[0].id, owner_id);
+        assert_eq!(owners[0].entity_role, RelationshipRole::UltimateBeneficialOwner);
+
+        let directors = entity_reference_repo
+            .find_by_person_and_role(company_id, RelationshipRole::Director, false)
+            .await?;
+        assert_eq!(directors.len(), 1);
+        assert!(entity_reference_repo
+            .find_by_person_and_role(company_id, RelationshipRole::Guarantor, false)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entity_role_hash_matches_migration() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+
+        // Every role, the migration hashes the rows written before it in SQL
+        let roles = [
+            RelationshipRole::Customer,
+            RelationshipRole::Employee,
+            RelationshipRole::Shareholder,
+            RelationshipRole::Director,
+            RelationshipRole::UltimateBeneficialOwner,
+            RelationshipRole::Agent,
+            RelationshipRole::Vendor,
+            RelationshipRole::Partner,
+            RelationshipRole::RegulatoryContact,
+            RelationshipRole::EmergencyContact,
+            RelationshipRole::SystemAdmin,
+            RelationshipRole::Guarantor,
+            RelationshipRole::LegalGuardian,
+            RelationshipRole::PowerOfAttorney,
+            RelationshipRole::Beneficiary,
+            RelationshipRole::AuthorizedSignatory,
+            RelationshipRole::ControllingPerson,
+            RelationshipRole::Delegate,
+            RelationshipRole::Administrator,
+            RelationshipRole::Other,
+        ];
+        for role in roles {
+            let in_sql: i64 = sqlx::query_scalar("SELECT entity_role_hash($1)")
+                .bind(role)
+                .fetch_one(&**ctx.pool())
+                .await?;
+            assert_eq!(in_sql, entity_role_hash(role), "{role:?}");
+        }
+
+        Ok(())
+    }
+}
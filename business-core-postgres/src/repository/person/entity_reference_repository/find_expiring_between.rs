@@ -0,0 +1,159 @@
+use business_core_db::models::person::entity_reference::{EntityReferenceModel, RelationshipStatus};
+use crate::utils::TryFromRow;
+use chrono::{Days, NaiveDate};
+use std::error::Error;
+
+use super::repo_impl::EntityReferenceRepositoryImpl;
+
+impl EntityReferenceRepositoryImpl {
+    /// Active references ending between `start` and `end`, both days included
+    ///
+    /// For the review of relationships about to expire. The day of `end_date` is taken
+    /// in UTC, as `EffectiveDated` does; references without an `end_date` never expire
+    /// and are left out. Ordered by `end_date`, then id.
+    pub async fn find_expiring_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<EntityReferenceModel>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.operation_timeout.lock("entity_reference", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT * FROM entity_reference
+                WHERE status = $1
+                AND (end_date AT TIME ZONE 'UTC')::DATE BETWEEN $2 AND $3
+                ORDER BY end_date, id
+                "#,
+            )
+            .bind(RelationshipStatus::Active)
+            .bind(start)
+            .bind(end)
+            .fetch_all(&mut **transaction)
+            .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(EntityReferenceModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+
+    /// Active references ending within `days` days of the current date of the
+    /// repository clock, today included, see `find_expiring_between`
+    pub async fn find_expiring_within(
+        &self,
+        days: u64,
+    ) -> Result<Vec<EntityReferenceModel>, Box<dyn Error + Send + Sync>> {
+        let today = self.clock.today();
+        let end = today
+            .checked_add_days(Days::new(days))
+            .ok_or_else(|| format!("{days} days after {today} is out of range"))?;
+        self.find_expiring_between(today, end).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::entity_reference_repository::test_utils::create_test_entity_reference;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::person::EntityReferenceRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::entity_reference::RelationshipStatus;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::FixedClock;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_find_expiring_between() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Relationship Under Review");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // Far ahead, so references of other tests do not fall in the window
+        let mut inside = create_test_entity_reference(person_id, "EXPIRING-INSIDE");
+        inside.end_date = Some(Utc.with_ymd_and_hms(2091, 3, 31, 23, 0, 0).unwrap());
+        let mut outside = create_test_entity_reference(person_id, "EXPIRING-OUTSIDE");
+        outside.end_date = Some(Utc.with_ymd_and_hms(2091, 4, 1, 0, 0, 0).unwrap());
+        let open_ended = create_test_entity_reference(person_id, "EXPIRING-OPEN");
+        let mut inactive = create_test_entity_reference(person_id, "EXPIRING-INACTIVE");
+        inactive.end_date = Some(Utc.with_ymd_and_hms(2091, 3, 15, 0, 0, 0).unwrap());
+        inactive.status = Some(RelationshipStatus::Terminated);
+        let mut references = vec![inside, outside, open_ended];
+        for reference in &mut references {
+            reference.status = Some(RelationshipStatus::Active);
+        }
+        let inside_id = references[0].id;
+        references.push(inactive);
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let expiring = entity_reference_repo
+            .find_expiring_between(
+                NaiveDate::from_ymd_opt(2091, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2091, 3, 31).unwrap(),
+            )
+            .await?;
+
+        let ids: Vec<_> = expiring.iter().map(|reference| reference.id).collect();
+        assert_eq!(ids, vec![inside_id]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_expiring_within_on_leap_day_and_year_end() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let entity_reference_repo = &ctx.person_repos().entity_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Relationship On The Clock");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let ending = |external_id: &str, y: i32, m: u32, d: u32| {
+            let mut reference = create_test_entity_reference(person_id, external_id);
+            reference.end_date = Some(Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap());
+            reference.status = Some(RelationshipStatus::Active);
+            reference
+        };
+        let references = vec![
+            ending("CLOCK-MARCH-1ST", 2092, 3, 1),
+            ending("CLOCK-MARCH-2ND", 2092, 3, 2),
+            ending("CLOCK-NEW-YEAR", 2093, 1, 1),
+        ];
+        let ids: Vec<_> = references.iter().map(|reference| reference.id).collect();
+        entity_reference_repo.create_batch(references, Some(audit_log.id)).await?;
+
+        let on = |y: i32, m: u32, d: u32| {
+            EntityReferenceRepositoryImpl::new(
+                entity_reference_repo.executor.clone(),
+                entity_reference_repo.entity_reference_idx_shared_cache.clone(),
+                Arc::new(FixedClock::new(NaiveDate::from_ymd_opt(y, m, d).unwrap())),
+            )
+        };
+
+        // 2092 is a leap year: one day after February 29th is March 1st
+        let expiring = on(2092, 2, 29).find_expiring_within(1).await?;
+        assert_eq!(expiring.iter().map(|reference| reference.id).collect::<Vec<_>>(), vec![ids[0]]);
+
+        let expiring = on(2092, 12, 31).find_expiring_within(1).await?;
+        assert_eq!(expiring.iter().map(|reference| reference.id).collect::<Vec<_>>(), vec![ids[2]]);
+
+        assert!(on(2092, 12, 31).find_expiring_within(0).await?.is_empty());
+
+        Ok(())
+    }
+}
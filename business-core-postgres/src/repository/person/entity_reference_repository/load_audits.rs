@@ -1,9 +1,11 @@
 use async_trait::async_trait;
 use business_core_db::models::person::entity_reference::EntityReferenceModel;
+use business_core_db::models::audit_chained::{order_chain, AuditVersion};
 use business_core_db::repository::load_audits::LoadAudits;
 use business_core_db::repository::pagination::{Page, PageRequest};
+use crate::error::map_db_error;
 use crate::utils::TryFromRow;
-use sqlx::Postgres;
+use sqlx::{Postgres, Row};
 use std::error::Error;
 use uuid::Uuid;
 
@@ -18,7 +20,7 @@ impl EntityReferenceRepositoryImpl {
         // First, get the total count of audit records for this entity
         let count_query = r#"SELECT COUNT(*) as count FROM entity_reference_audit WHERE id = $1"#;
         let total: i64 = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("entity_reference", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query_scalar(count_query)
                     .bind(id)
@@ -38,7 +40,7 @@ impl EntityReferenceRepositoryImpl {
         "#;
         
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("entity_reference", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query(query)
                     .bind(id)
@@ -59,6 +61,31 @@ impl EntityReferenceRepositoryImpl {
 
         Ok(Page::new(items, total as usize, page.limit, page.offset))
     }
+
+    /// All stored versions of entity reference `id` along their chain, with the `redacted` flag of each
+    ///
+    /// Versions scrubbed by `PersonPrivacyService::anonymize` are flagged, so the result
+    /// verifies with `verify_chain_from`.
+    pub async fn load_audit_versions(&self, id: Uuid) -> Result<Vec<AuditVersion<EntityReferenceModel>>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.operation_timeout.lock("entity_reference", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("SELECT * FROM entity_reference_audit WHERE id = $1")
+                .bind(id)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("entity_reference", e))?
+        };
+
+        let mut versions = Vec::with_capacity(rows.len());
+        for row in rows {
+            versions.push(AuditVersion {
+                item: EntityReferenceModel::try_from_row(&row)?,
+                redacted: row.get("redacted"),
+            });
+        }
+        Ok(order_chain(versions))
+    }
 }
 
 #[async_trait]
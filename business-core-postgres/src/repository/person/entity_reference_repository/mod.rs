@@ -1,4 +1,5 @@
 pub mod repo_impl;
+pub mod columns;
 pub mod create_batch;
 pub mod load_batch;
 pub mod load_audits;
@@ -6,7 +7,11 @@ pub mod update_batch;
 pub mod delete_batch;
 pub mod exist_by_ids;
 pub mod find_by_person_id;
+pub mod find_by_role;
+pub mod find_expiring_between;
 pub mod find_by_reference_external_id_hash;
+pub mod entity_reference_count;
+pub mod count_by_key;
 #[cfg(test)]
 pub mod test_utils;
 
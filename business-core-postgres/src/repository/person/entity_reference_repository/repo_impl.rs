@@ -1,4 +1,5 @@
 use business_core_db::models::person::entity_reference::{EntityReferenceIdxModel, EntityReferenceModel};
+use business_core_db::utils::Clock;
 use crate::utils::{get_heapless_string, get_optional_heapless_string, TryFromRow};
 use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use postgres_index_cache::TransactionAwareIdxModelCache;
@@ -7,33 +8,71 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
 use uuid::Uuid;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct EntityReferenceRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub entity_reference_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<EntityReferenceIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub entity_reference_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<EntityReferenceIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the entity_reference_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the entity_reference_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
+    /// Current date of `find_expiring_within`
+    pub clock: Arc<dyn Clock>,
 }
 
 impl EntityReferenceRepositoryImpl {
     pub fn new(
         executor: Executor,
         entity_reference_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<EntityReferenceIdxModel>>>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, entity_reference_idx_cache, clock, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        entity_reference_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<EntityReferenceIdxModel>>>,
+        clock: Arc<dyn Clock>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            clock,
+            entity_reference_idx_shared_cache: entity_reference_idx_cache.clone(),
             entity_reference_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 entity_reference_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
         }
     }
 
     pub async fn load_all_entity_reference_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<EntityReferenceIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM entity_reference_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("entity_reference", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -48,12 +87,24 @@ impl EntityReferenceRepositoryImpl {
         Ok(idx_models)
     }
 
+    /// Index models of the references of a person, from the index cache or, when the
+    /// repository does not serve finders from its cache, from entity_reference_idx
+    pub async fn find_idx_by_person_id(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<EntityReferenceIdxModel>, Box<dyn Error + Send + Sync>> {
+        if !self.serves_key_from_cache("person_id", person_id.into()) {
+            return self.find_idx_by_column("person_id", person_id).await;
+        }
+        let cache = self.entity_reference_idx_cache.read().await;
+        Ok(cache.get_by_uuid_index("person_id", &person_id))
+    }
+
     pub async fn find_ids_by_person_id(
         &self,
         person_id: Uuid,
     ) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
-        let cache = self.entity_reference_idx_cache.read().await;
-        let items = cache.get_by_uuid_index("person_id", &person_id);
+        let items = self.find_idx_by_person_id(person_id).await?;
         let result = items.into_iter().map(|item| item.id).collect();
         Ok(result)
     }
@@ -62,8 +113,7 @@ impl EntityReferenceRepositoryImpl {
         &self,
         reference_external_id_hash: i64,
     ) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
-        let cache = self.entity_reference_idx_cache.read().await;
-        let items = cache.get_by_i64_index("reference_external_id_hash", &reference_external_id_hash);
+        let items = self.find_by_i64_key("reference_external_id_hash", reference_external_id_hash).await?;
         let result = items.into_iter().map(|item| item.id).collect();
         Ok(result)
     }
@@ -97,6 +147,7 @@ impl TryFromRow<PgRow> for EntityReferenceIdxModel {
             id: row.get("id"),
             person_id: row.get("person_id"),
             reference_external_id_hash: row.get("reference_external_id_hash"),
+            entity_role_hash: row.get("entity_role_hash"),
         })
     }
 }
@@ -104,10 +155,82 @@ impl TryFromRow<PgRow> for EntityReferenceIdxModel {
 #[async_trait]
 impl TransactionAware for EntityReferenceRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.entity_reference_idx_cache.read().await.on_commit().await
+        self.entity_reference_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.entity_reference_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.entity_reference_idx_cache.read().await.on_rollback().await
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for EntityReferenceRepositoryImpl {
+    type Idx = EntityReferenceIdxModel;
+
+    const ENTITY: &'static str = "entity_reference";
+    const I64_KEYS: &'static [&'static str] = &["reference_external_id_hash", "entity_role_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<EntityReferenceIdxModel>> {
+        &self.entity_reference_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for EntityReferenceRepositoryImpl {
+    type Idx = EntityReferenceIdxModel;
+
+    const ENTITY: &'static str = "entity_reference";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<EntityReferenceIdxModel>>> {
+        &self.entity_reference_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<EntityReferenceIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_entity_reference_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
@@ -5,11 +5,17 @@ use business_core_db::models::{
 };
 use business_core_db::models::index_aware::IndexAware;
 use business_core_db::repository::update_batch::UpdateBatch;
-use sqlx::Postgres;
+use sqlx::{Postgres, Row};
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::columns::{
+    ENTITY_REFERENCE_COLUMNS, ENTITY_REFERENCE_IDX_COLUMNS, ENTITY_REFERENCE_IDX_VERSIONED_UPDATE, ENTITY_REFERENCE_SQL,
+};
 use super::repo_impl::EntityReferenceRepositoryImpl;
 
 impl EntityReferenceRepositoryImpl {
@@ -27,7 +33,7 @@ impl EntityReferenceRepositoryImpl {
         let mut indices_to_update = Vec::new();
         
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("entity_reference", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for mut item in items {
@@ -51,77 +57,34 @@ impl EntityReferenceRepositoryImpl {
                 let new_computed_hash = hash_as_i64(&item)?;
                 item.hash = new_computed_hash;
 
-                sqlx::query(
-                    r#"
-                    INSERT INTO entity_reference_audit
-                    (id, person_id, entity_role, reference_external_id, reference_details_l1, reference_details_l2, reference_details_l3, related_person_id, start_date, end_date, status, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_id)
-                .bind(item.entity_role)
-                .bind(item.reference_external_id.as_str())
-                .bind(item.reference_details_l1.as_deref())
-                .bind(item.reference_details_l2.as_deref())
-                .bind(item.reference_details_l3.as_deref())
-                .bind(item.related_person_id)
-                .bind(item.start_date)
-                .bind(item.end_date)
-                .bind(item.status)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
-
-                let rows_affected = sqlx::query(
-                    r#"
-                    UPDATE entity_reference SET
-                    person_id = $2, entity_role = $3, reference_external_id = $4,
-                    reference_details_l1 = $5, reference_details_l2 = $6, reference_details_l3 = $7,
-                    related_person_id = $8, start_date = $9, end_date = $10, status = $11,
-                    antecedent_hash = $12, antecedent_audit_log_id = $13, hash = $14, audit_log_id = $15
-                    WHERE id = $1 AND hash = $16 AND audit_log_id = $17
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_id)
-                .bind(item.entity_role)
-                .bind(item.reference_external_id.as_str())
-                .bind(item.reference_details_l1.as_deref())
-                .bind(item.reference_details_l2.as_deref())
-                .bind(item.reference_details_l3.as_deref())
-                .bind(item.related_person_id)
-                .bind(item.start_date)
-                .bind(item.end_date)
-                .bind(item.status)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .bind(previous_hash)
-                .bind(previous_audit_log_id)
-                .execute(&mut **transaction)
-                .await?
-                .rows_affected();
+                ENTITY_REFERENCE_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.insert_audit), &item)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
+
+                let rows_affected = ENTITY_REFERENCE_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.update), &item)
+                    .bind(previous_hash)
+                    .bind(previous_audit_log_id)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?
+                    .rows_affected();
 
                 if rows_affected == 0 {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(entity = "entity_reference", id = %item.id, "Concurrent update detected");
                     return Err("Concurrent update detected".into());
                 }
 
                 let idx = item.to_index();
-                sqlx::query(
-                    r#"
-                    UPDATE entity_reference_idx SET person_id = $2, reference_external_id_hash = $3 WHERE id = $1
-                    "#,
-                )
-                .bind(idx.id)
-                .bind(idx.person_id)
-                .bind(idx.reference_external_id_hash)
-                .execute(&mut **transaction)
-                .await?;
+                let version: i64 = ENTITY_REFERENCE_IDX_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_IDX_VERSIONED_UPDATE), &idx)
+                    .fetch_one(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?
+                    .get("version");
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -129,29 +92,27 @@ impl EntityReferenceRepositoryImpl {
                     entity_id: item.id,
                     entity_type: EntityType::EntityReference,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
-
-                indices_to_update.push((item.id, idx));
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
+
+                indices_to_update.push((idx, version as u64));
                 updated_items.push(item);
             }
         }
         
-        {
-            let cache = self.entity_reference_idx_cache.read().await;
-            for (id, idx) in indices_to_update {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices_to_update.iter().map(|(idx, _)| idx.id).collect();
+            {
+                let cache = self.entity_reference_idx_cache.read().await;
+                for (idx, version) in indices_to_update {
+                    // A notification already brought a newer version of the row
+                    if !self.cache_versions.admit(idx.id, version) {
+                        continue;
+                    }
+                    cache.remove(&idx.id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -160,6 +121,7 @@ impl EntityReferenceRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, EntityReferenceModel> for EntityReferenceRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "entity_reference", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<EntityReferenceModel>,
@@ -1,7 +1,8 @@
 use std::sync::Arc;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
 use parking_lot::RwLock as ParkingRwLock;
 use postgres_unit_of_work::UnitOfWorkSession;
-use postgres_index_cache::{CacheNotificationListener, IndexCacheHandler};
 use business_core_db::models::person::{
     country::CountryIdxModel,
     country_subdivision::CountrySubdivisionIdxModel,
@@ -11,8 +12,91 @@ use business_core_db::models::person::{
     entity_reference::EntityReferenceIdxModel,
     risk_summary::RiskSummaryIdxModel,
 };
+use business_core_db::utils::{Clock, HashVersion, SystemClock};
+use crate::repository::cache_auditor::{CacheAuditConfig, CacheAuditor, CachedIds};
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::{CacheBound, CacheCapacity};
+use crate::repository::cache_health::CacheHealth;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::idx_notification_listener::IdxNotificationListener;
+use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+use crate::repository::operation_timeout::OperationTimeout;
 use super::{CountryRepositoryImpl, CountrySubdivisionRepositoryImpl, LocalityRepositoryImpl, LocationRepositoryImpl, PersonRepositoryImpl, EntityReferenceRepositoryImpl, RiskSummaryRepositoryImpl, ActivityLogRepositoryImpl, PortfolioRepositoryImpl, ComplianceStatusRepositoryImpl, DocumentRepositoryImpl};
 
+/// Settings of a `PersonRepoFactory`
+///
+/// Build it with struct update syntax over the defaults, e.g.
+/// `PersonRepoConfig { person_cache_capacity: Some(10_000), ..PersonRepoConfig::default() }`.
+#[derive(Debug, Clone)]
+pub struct PersonRepoConfig {
+    /// Cache policy of the country repository
+    pub country_cache_policy: CachePolicy,
+    /// Cache policy of the country subdivision repository
+    pub country_subdivision_cache_policy: CachePolicy,
+    /// Cache policy of the locality repository
+    pub locality_cache_policy: CachePolicy,
+    /// Cache policy of the location repository
+    pub location_cache_policy: CachePolicy,
+    /// Cache policy of the entity reference repository
+    pub entity_reference_cache_policy: CachePolicy,
+    /// Cache policy of the risk summary repository
+    pub risk_summary_cache_policy: CachePolicy,
+    /// Cache policy of the person repository
+    pub person_cache_policy: CachePolicy,
+    /// Hash version the person repository writes person_idx rows with
+    pub person_hash_version: HashVersion,
+    /// Bounds on the transaction lock wait of every repository of the factory, and on
+    /// the SQL of the person batch operations
+    pub operation_timeout: OperationTimeout,
+    /// Number of entries the person_idx cache keeps, `None` leaves it unbounded
+    ///
+    /// With a capacity, the least recently used person_idx entries are evicted and the
+    /// person finders go to SQL unless all rows of the key value are cached, so an
+    /// evicted person is still found. The other capacities bound their cache alike.
+    pub person_cache_capacity: Option<usize>,
+    /// Number of entries the country_idx cache keeps, `None` leaves it unbounded
+    pub country_cache_capacity: Option<usize>,
+    /// Number of entries the country_subdivision_idx cache keeps, `None` leaves it unbounded
+    pub country_subdivision_cache_capacity: Option<usize>,
+    /// Number of entries the locality_idx cache keeps, `None` leaves it unbounded
+    pub locality_cache_capacity: Option<usize>,
+    /// Number of entries the location_idx cache keeps, `None` leaves it unbounded
+    pub location_cache_capacity: Option<usize>,
+    /// Number of entries the entity_reference_idx cache keeps, `None` leaves it unbounded
+    pub entity_reference_cache_capacity: Option<usize>,
+    /// Number of entries the risk_summary_idx cache keeps, `None` leaves it unbounded
+    pub risk_summary_cache_capacity: Option<usize>,
+    /// Clock telling the repositories the current date
+    pub clock: Arc<dyn Clock>,
+    /// When the coalescers registered with the listener apply the notifications
+    pub coalescing: CoalescingConfig,
+}
+
+impl Default for PersonRepoConfig {
+    fn default() -> Self {
+        Self {
+            country_cache_policy: CachePolicy::default(),
+            country_subdivision_cache_policy: CachePolicy::default(),
+            locality_cache_policy: CachePolicy::default(),
+            location_cache_policy: CachePolicy::default(),
+            entity_reference_cache_policy: CachePolicy::default(),
+            risk_summary_cache_policy: CachePolicy::default(),
+            person_cache_policy: CachePolicy::default(),
+            person_hash_version: HashVersion::default(),
+            operation_timeout: OperationTimeout::default(),
+            person_cache_capacity: None,
+            country_cache_capacity: None,
+            country_subdivision_cache_capacity: None,
+            locality_cache_capacity: None,
+            location_cache_capacity: None,
+            entity_reference_cache_capacity: None,
+            risk_summary_cache_capacity: None,
+            clock: Arc::new(SystemClock),
+            coalescing: CoalescingConfig::default(),
+        }
+    }
+}
+
 /// Factory for creating person module repositories
 ///
 /// This factory holds all caches for the person module and provides
@@ -26,13 +110,71 @@ pub struct PersonRepoFactory {
     person_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>>,
     entity_reference_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<EntityReferenceIdxModel>>>,
     risk_summary_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<RiskSummaryIdxModel>>>,
+    country_cache_policy: CachePolicy,
+    country_subdivision_cache_policy: CachePolicy,
+    locality_cache_policy: CachePolicy,
+    location_cache_policy: CachePolicy,
+    entity_reference_cache_policy: CachePolicy,
+    risk_summary_cache_policy: CachePolicy,
+    person_cache_policy: CachePolicy,
+    person_hash_version: HashVersion,
+    operation_timeout: OperationTimeout,
+    person_cache_capacity: Option<Arc<CacheCapacity>>,
+    country_cache_capacity: Option<Arc<CacheCapacity>>,
+    country_subdivision_cache_capacity: Option<Arc<CacheCapacity>>,
+    locality_cache_capacity: Option<Arc<CacheCapacity>>,
+    location_cache_capacity: Option<Arc<CacheCapacity>>,
+    entity_reference_cache_capacity: Option<Arc<CacheCapacity>>,
+    risk_summary_cache_capacity: Option<Arc<CacheCapacity>>,
+    cache_health: CacheHealth,
+    country_cache_versions: Arc<CacheVersions>,
+    country_subdivision_cache_versions: Arc<CacheVersions>,
+    locality_cache_versions: Arc<CacheVersions>,
+    location_cache_versions: Arc<CacheVersions>,
+    person_cache_versions: Arc<CacheVersions>,
+    entity_reference_cache_versions: Arc<CacheVersions>,
+    risk_summary_cache_versions: Arc<CacheVersions>,
+    /// Ids of the person_idx entries, `None` when neither a coalescer nor a capacity
+    /// tracks them
+    person_cached_ids: Option<Arc<dyn CachedIds>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl PersonRepoFactory {
-    /// Create a new PersonRepoFactory singleton
+    /// Create a new PersonRepoFactory singleton with the default `PersonRepoConfig`
     ///
     /// Optionally register cache handlers with a notification listener
-    pub fn new(listener: Option<&mut CacheNotificationListener>) -> Arc<Self> {
+    pub fn new(listener: Option<&mut IdxNotificationListener>) -> Arc<Self> {
+        Self::new_with_config(listener, PersonRepoConfig::default())
+    }
+
+    /// Create a new PersonRepoFactory singleton with the given settings
+    ///
+    /// Optionally register cache handlers with a notification listener, a
+    /// `NotificationCoalescer` per idx table applying its notifications in batches. The
+    /// handler of an idx table is only registered when the cache policy of its
+    /// repository is `CachePolicy::Enabled`.
+    pub fn new_with_config(listener: Option<&mut IdxNotificationListener>, config: PersonRepoConfig) -> Arc<Self> {
+        let PersonRepoConfig {
+            country_cache_policy,
+            country_subdivision_cache_policy,
+            locality_cache_policy,
+            location_cache_policy,
+            entity_reference_cache_policy,
+            risk_summary_cache_policy,
+            person_cache_policy,
+            person_hash_version,
+            operation_timeout,
+            person_cache_capacity,
+            country_cache_capacity,
+            country_subdivision_cache_capacity,
+            locality_cache_capacity,
+            location_cache_capacity,
+            entity_reference_cache_capacity,
+            risk_summary_cache_capacity,
+            clock,
+            coalescing,
+        } = config;
         let country_idx_cache = Arc::new(ParkingRwLock::new(
             business_core_db::IdxModelCache::new(vec![]).unwrap()
         ));
@@ -61,51 +203,97 @@ impl PersonRepoFactory {
             business_core_db::IdxModelCache::new(vec![]).unwrap()
         ));
         
+        // Versions of each cache, shared by its repositories and its coalescer so a
+        // delayed notification does not overwrite a newer local write
+        let country_cache_versions = Arc::new(CacheVersions::new());
+        let country_subdivision_cache_versions = Arc::new(CacheVersions::new());
+        let locality_cache_versions = Arc::new(CacheVersions::new());
+        let location_cache_versions = Arc::new(CacheVersions::new());
+        let person_cache_versions = Arc::new(CacheVersions::new());
+        let entity_reference_cache_versions = Arc::new(CacheVersions::new());
+        let risk_summary_cache_versions = Arc::new(CacheVersions::new());
+
+        // Capacity of each bounded cache, shared by its repositories and its coalescer
+        let country_cache_capacity = CacheCapacity::for_policy(country_cache_policy, country_cache_capacity);
+        let country_subdivision_cache_capacity = CacheCapacity::for_policy(country_subdivision_cache_policy, country_subdivision_cache_capacity);
+        let locality_cache_capacity = CacheCapacity::for_policy(locality_cache_policy, locality_cache_capacity);
+        let location_cache_capacity = CacheCapacity::for_policy(location_cache_policy, location_cache_capacity);
+        let entity_reference_cache_capacity = CacheCapacity::for_policy(entity_reference_cache_policy, entity_reference_cache_capacity);
+        let risk_summary_cache_capacity = CacheCapacity::for_policy(risk_summary_cache_policy, risk_summary_cache_capacity);
+        let person_cache_capacity = CacheCapacity::for_policy(person_cache_policy, person_cache_capacity);
+
+        // The repositories share the health of the listener feeding their caches
+        let cache_health = listener
+            .as_deref()
+            .map_or_else(CacheHealth::default, IdxNotificationListener::cache_health);
+
+        // Ids of the person_idx entries, for the auditor: listed by the coalescer when
+        // the cache is fed by the listener, else by its capacity
+        let mut person_cached_ids: Option<Arc<dyn CachedIds>> = person_cache_capacity
+            .clone()
+            .map(|capacity| capacity as Arc<dyn CachedIds>);
+
         // Register handlers with listener if provided
         if let Some(listener) = listener {
-            let handler = Arc::new(IndexCacheHandler::new(
-                "country_idx".to_string(),
-                country_idx_cache.clone(),
-            ));
-            listener.register_handler(handler);
-            
-            let subdivision_handler = Arc::new(IndexCacheHandler::new(
-                "country_subdivision_idx".to_string(),
-                country_subdivision_idx_cache.clone(),
-            ));
-            listener.register_handler(subdivision_handler);
-            
-            let locality_handler = Arc::new(IndexCacheHandler::new(
-                "locality_idx".to_string(),
-                locality_idx_cache.clone(),
-            ));
-            listener.register_handler(locality_handler);
-
-            let location_handler = Arc::new(IndexCacheHandler::new(
-                "location_idx".to_string(),
-                location_idx_cache.clone(),
-            ));
-            listener.register_handler(location_handler);
-
-            let person_handler = Arc::new(IndexCacheHandler::new(
-                "person_idx".to_string(),
-                person_idx_cache.clone(),
-            ));
-            listener.register_handler(person_handler);
-
-            let entity_reference_handler = Arc::new(IndexCacheHandler::new(
-                "entity_reference_idx".to_string(),
-                entity_reference_idx_cache.clone(),
-            ));
-            listener.register_handler(entity_reference_handler);
-
-            let risk_summary_handler = Arc::new(IndexCacheHandler::new(
-                "risk_summary_idx".to_string(),
-                risk_summary_idx_cache.clone(),
-            ));
-            listener.register_handler(risk_summary_handler);
+            if country_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("country_idx", country_idx_cache.clone(), coalescing)
+                        .with_versions(country_cache_versions.clone())
+                        .with_capacity(country_cache_capacity.clone()),
+                ));
+            }
+
+            if country_subdivision_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("country_subdivision_idx", country_subdivision_idx_cache.clone(), coalescing)
+                        .with_versions(country_subdivision_cache_versions.clone())
+                        .with_capacity(country_subdivision_cache_capacity.clone()),
+                ));
+            }
+
+            if locality_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("locality_idx", locality_idx_cache.clone(), coalescing)
+                        .with_versions(locality_cache_versions.clone())
+                        .with_capacity(locality_cache_capacity.clone()),
+                ));
+            }
+
+            if location_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("location_idx", location_idx_cache.clone(), coalescing)
+                        .with_versions(location_cache_versions.clone())
+                        .with_capacity(location_cache_capacity.clone()),
+                ));
+            }
+
+            if person_cache_policy.registers_notifications() {
+                let coalescer = Arc::new(
+                    NotificationCoalescer::new("person_idx", person_idx_cache.clone(), coalescing)
+                        .with_versions(person_cache_versions.clone())
+                        .with_capacity(person_cache_capacity.clone()),
+                );
+                person_cached_ids = Some(coalescer.clone());
+                listener.register_handler(coalescer);
+            }
+
+            if entity_reference_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("entity_reference_idx", entity_reference_idx_cache.clone(), coalescing)
+                        .with_versions(entity_reference_cache_versions.clone())
+                        .with_capacity(entity_reference_cache_capacity.clone()),
+                ));
+            }
+
+            if risk_summary_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("risk_summary_idx", risk_summary_idx_cache.clone(), coalescing)
+                        .with_versions(risk_summary_cache_versions.clone())
+                        .with_capacity(risk_summary_cache_capacity.clone()),
+                ));
+            }
         }
-        
+
         Arc::new(Self {
             country_idx_cache,
             country_subdivision_idx_cache,
@@ -114,111 +302,227 @@ impl PersonRepoFactory {
             person_idx_cache,
             entity_reference_idx_cache,
             risk_summary_idx_cache,
+            country_cache_policy,
+            country_subdivision_cache_policy,
+            locality_cache_policy,
+            location_cache_policy,
+            entity_reference_cache_policy,
+            risk_summary_cache_policy,
+            person_cache_policy,
+            person_hash_version,
+            operation_timeout,
+            person_cache_capacity,
+            country_cache_capacity,
+            country_subdivision_cache_capacity,
+            locality_cache_capacity,
+            location_cache_capacity,
+            entity_reference_cache_capacity,
+            risk_summary_cache_capacity,
+            cache_health,
+            country_cache_versions,
+            country_subdivision_cache_versions,
+            locality_cache_versions,
+            location_cache_versions,
+            person_cache_versions,
+            entity_reference_cache_versions,
+            risk_summary_cache_versions,
+            person_cached_ids,
+            clock,
         })
     }
 
+    /// Health of the notifications feeding the caches of this factory
+    ///
+    /// The health of the listener given to the factory, shared with the repositories it
+    /// builds; degraded while `IdxNotificationListener::supervise` reconnects. Without a
+    /// listener it is never degraded. `CacheHealth::report` gives the entry count and
+    /// last applied notification of each cache fed by the listener.
+    pub fn cache_health(&self) -> CacheHealth {
+        self.cache_health.clone()
+    }
+
+    /// Clock the repositories of this factory take the current date from
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    /// Auditor comparing the person index cache with person_idx, on connections of `pool`
+    ///
+    /// `None` when the cache is neither bounded nor fed by a listener, its ids are then
+    /// not tracked.
+    pub fn person_cache_auditor(&self, pool: PgPool) -> Option<Arc<CacheAuditor<PersonIdxModel>>> {
+        let ids = self.person_cached_ids.clone()?;
+        Some(Arc::new(
+            CacheAuditor::new("person", self.person_idx_cache.clone(), ids, pool)
+                .with_versions(self.person_cache_versions.clone()),
+        ))
+    }
+
+    /// Audit the person index cache every `config.interval`
+    ///
+    /// Abort the returned task to stop auditing; the reports are kept by the auditor.
+    /// `None` when `person_cache_auditor` is.
+    pub fn spawn_person_cache_auditor(
+        &self,
+        pool: PgPool,
+        config: CacheAuditConfig,
+    ) -> Option<(Arc<CacheAuditor<PersonIdxModel>>, JoinHandle<()>)> {
+        let auditor = self.person_cache_auditor(pool)?;
+        let handle = auditor.spawn(config);
+        Some((auditor, handle))
+    }
+
     /// Build a CountryRepository with the given executor
     pub fn build_country_repo(&self, session: &impl UnitOfWorkSession) -> Arc<CountryRepositoryImpl> {
-        let repo = Arc::new(CountryRepositoryImpl::new(
-            session.executor().clone(),
-            self.country_idx_cache.clone(),
-        ));
+        let repo = Arc::new(CountryRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.country_cache_versions.clone()),
+            cache_bound: self.country_cache_capacity.clone().map(CacheBound::new),
+            ..CountryRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.country_idx_cache.clone(),
+                self.country_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a CountrySubdivisionRepository with the given executor
     pub fn build_country_subdivision_repo(&self, session: &impl UnitOfWorkSession) -> Arc<CountrySubdivisionRepositoryImpl> {
-        let repo = Arc::new(CountrySubdivisionRepositoryImpl::new(
-            session.executor().clone(),
-            self.country_subdivision_idx_cache.clone(),
-        ));
+        let repo = Arc::new(CountrySubdivisionRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.country_subdivision_cache_versions.clone()),
+            cache_bound: self.country_subdivision_cache_capacity.clone().map(CacheBound::new),
+            ..CountrySubdivisionRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.country_subdivision_idx_cache.clone(),
+                self.country_subdivision_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a LocalityRepository with the given executor
     pub fn build_locality_repo(&self, session: &impl UnitOfWorkSession) -> Arc<LocalityRepositoryImpl> {
-        let repo = Arc::new(LocalityRepositoryImpl::new(
-            session.executor().clone(),
-            self.locality_idx_cache.clone(),
-        ));
+        let repo = Arc::new(LocalityRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.locality_cache_versions.clone()),
+            cache_bound: self.locality_cache_capacity.clone().map(CacheBound::new),
+            ..LocalityRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.locality_idx_cache.clone(),
+                self.locality_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a LocationRepository with the given executor
     pub fn build_location_repo(&self, session: &impl UnitOfWorkSession) -> Arc<LocationRepositoryImpl> {
-        let repo = Arc::new(LocationRepositoryImpl::new(
-            session.executor().clone(),
-            self.location_idx_cache.clone(),
-        ));
+        let repo = Arc::new(LocationRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.location_cache_versions.clone()),
+            cache_bound: self.location_cache_capacity.clone().map(CacheBound::new),
+            ..LocationRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.location_idx_cache.clone(),
+                self.location_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a PersonRepository with the given executor
     pub fn build_person_repo(&self, session: &impl UnitOfWorkSession) -> Arc<PersonRepositoryImpl> {
-        let repo = Arc::new(PersonRepositoryImpl::new(
-            session.executor().clone(),
-            self.person_idx_cache.clone(),
-        ));
+        let repo = Arc::new(PersonRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_bound: self.person_cache_capacity.clone().map(CacheBound::new),
+            cache_health: self.cache_health.clone(),
+            cache_versions: PendingVersions::new(self.person_cache_versions.clone()),
+            ..PersonRepositoryImpl::new_with_hash_version(
+                session.executor().clone(),
+                self.person_idx_cache.clone(),
+                self.person_cache_policy,
+                self.person_hash_version,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build an EntityReferenceRepository with the given executor
     pub fn build_entity_reference_repo(&self, session: &impl UnitOfWorkSession) -> Arc<EntityReferenceRepositoryImpl> {
-        let repo = Arc::new(EntityReferenceRepositoryImpl::new(
-            session.executor().clone(),
-            self.entity_reference_idx_cache.clone(),
-        ));
+        let repo = Arc::new(EntityReferenceRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.entity_reference_cache_versions.clone()),
+            cache_bound: self.entity_reference_cache_capacity.clone().map(CacheBound::new),
+            ..EntityReferenceRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.entity_reference_idx_cache.clone(),
+                self.clock.clone(),
+                self.entity_reference_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a RiskSummaryRepository with the given executor
     pub fn build_risk_summary_repo(&self, session: &impl UnitOfWorkSession) -> Arc<RiskSummaryRepositoryImpl> {
-        let repo = Arc::new(RiskSummaryRepositoryImpl::new(
-            session.executor().clone(),
-            self.risk_summary_idx_cache.clone(),
-        ));
+        let repo = Arc::new(RiskSummaryRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.risk_summary_cache_versions.clone()),
+            cache_bound: self.risk_summary_cache_capacity.clone().map(CacheBound::new),
+            ..RiskSummaryRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.risk_summary_idx_cache.clone(),
+                self.risk_summary_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build an ActivityLogRepository with the given executor
     pub fn build_activity_log_repo(&self, session: &impl UnitOfWorkSession) -> Arc<ActivityLogRepositoryImpl> {
-        let repo = Arc::new(ActivityLogRepositoryImpl::new(
-            session.executor().clone(),
-        ));
+        let repo = Arc::new(ActivityLogRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..ActivityLogRepositoryImpl::new(session.executor().clone())
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a PortfolioRepository with the given executor
     pub fn build_portfolio_repo(&self, session: &impl UnitOfWorkSession) -> Arc<PortfolioRepositoryImpl> {
-        let repo = Arc::new(PortfolioRepositoryImpl::new(
-            session.executor().clone(),
-        ));
+        let repo = Arc::new(PortfolioRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..PortfolioRepositoryImpl::new(session.executor().clone())
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a ComplianceStatusRepository with the given executor
     pub fn build_compliance_status_repo(&self, session: &impl UnitOfWorkSession) -> Arc<ComplianceStatusRepositoryImpl> {
-        let repo = Arc::new(ComplianceStatusRepositoryImpl::new(
-            session.executor().clone(),
-        ));
+        let repo = Arc::new(ComplianceStatusRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..ComplianceStatusRepositoryImpl::new(session.executor().clone())
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a DocumentRepository with the given executor
     pub fn build_document_repo(&self, session: &impl UnitOfWorkSession) -> Arc<DocumentRepositoryImpl> {
-        let repo = Arc::new(DocumentRepositoryImpl::new(
-            session.executor().clone(),
-        ));
+        let repo = Arc::new(DocumentRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..DocumentRepositoryImpl::new(session.executor().clone())
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
@@ -5,24 +5,34 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
+use business_core_db::utils::normalize_code;
+use std::collections::HashMap;
+use crate::error::RepositoryError;
+use crate::repository::find_by_i64_key::FindByI64Key;
 
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::LocalityRepositoryImpl;
 
 impl LocalityRepositoryImpl {
     pub(super) async fn create_batch_impl(
         repo: &LocalityRepositoryImpl,
-        items: Vec<LocalityModel>,
+        mut items: Vec<LocalityModel>,
     ) -> Result<Vec<LocalityModel>, Box<dyn Error + Send + Sync>> {
         if items.is_empty() {
             return Ok(Vec::new());
         }
 
+        for item in &mut items {
+            item.normalize()?;
+        }
+        repo.check_unique_codes(&items).await?;
+
         let mut saved_items = Vec::new();
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("locality", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -62,19 +72,62 @@ impl LocalityRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.locality_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.locality_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
     }
+
+    /// Reject items whose code is already used in their subdivision, by a stored locality
+    /// or by an earlier item of the batch
+    ///
+    /// Codes are expected normalized. Candidates are found by code hash and their stored
+    /// code compared, so a hash collision is not reported as a duplicate.
+    pub(super) async fn check_unique_codes(
+        &self,
+        items: &[LocalityModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut seen: HashMap<(Uuid, &str), Uuid> = HashMap::new();
+        for item in items {
+            let existing_id = match seen.insert((item.country_subdivision_id, item.code.as_str()), item.id) {
+                Some(earlier_id) => Some(earlier_id),
+                None => self
+                    .find_matching_by_i64_key(
+                        "code_hash",
+                        item.to_index().code_hash,
+                        |idx| idx.country_subdivision_id == item.country_subdivision_id,
+                        |stored: &LocalityModel| normalize_code(&stored.code) == item.code.as_str(),
+                    )
+                    .await?
+                    .first()
+                    .map(|stored| stored.id),
+            };
+            if let Some(existing_id) = existing_id {
+                return Err(RepositoryError::CodeTaken {
+                    entity: "locality".to_string(),
+                    code: item.code.to_string(),
+                    existing_id,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl CreateBatch<Postgres, LocalityModel> for LocalityRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "locality", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<LocalityModel>,
@@ -131,6 +184,64 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_create_batch_rejects_code_in_other_case() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        let country = create_test_country("CH", "Switzerland");
+        let country_id = country.id;
+        country_repo.create_batch(vec![country], None).await?;
+
+        let zurich = create_test_country_subdivision(country_id, "ZH", "Zurich");
+        let bern = create_test_country_subdivision(country_id, "BE", "Bern");
+        let (zurich_id, bern_id) = (zurich.id, bern.id);
+        country_subdivision_repo.create_batch(vec![zurich, bern], None).await?;
+
+        // Within one batch
+        let batch = vec![
+            create_test_locality(zurich_id, "WIN", "Winterthur"),
+            create_test_locality(zurich_id, "win ", "Winterthur Again"),
+        ];
+        let first_id = batch[0].id;
+        let error = locality_repo
+            .create_batch(batch, None)
+            .await
+            .expect_err("A code differing only in case must be rejected");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::CodeTaken { entity, code, existing_id }) => {
+                assert_eq!(entity, "locality");
+                assert_eq!(code, "WIN");
+                assert_eq!(*existing_id, first_id);
+            }
+            other => panic!("Expected CodeTaken, got {other:?}"),
+        }
+
+        // Against a stored locality
+        let saved = locality_repo
+            .create_batch(vec![create_test_locality(zurich_id, "WIN", "Winterthur")], None)
+            .await?;
+        let error = locality_repo
+            .create_batch(vec![create_test_locality(zurich_id, "Win", "Winterthur Again")], None)
+            .await
+            .expect_err("A code differing only in case must be rejected");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::CodeTaken { existing_id, .. }) => assert_eq!(*existing_id, saved[0].id),
+            other => panic!("Expected CodeTaken, got {other:?}"),
+        }
+
+        // Codes are unique per subdivision only
+        locality_repo
+            .create_batch(vec![create_test_locality(bern_id, "win", "Elsewhere")], None)
+            .await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_batch_empty() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
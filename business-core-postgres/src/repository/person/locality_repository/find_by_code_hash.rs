@@ -1,7 +1,9 @@
 use std::error::Error;
+use business_core_db::utils::{hash_as_i64, normalize_code};
 
 use business_core_db::models::person::locality::LocalityIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocalityRepositoryImpl;
 
 impl LocalityRepositoryImpl {
@@ -9,9 +11,15 @@ impl LocalityRepositoryImpl {
         &self,
         code_hash: i64,
     ) -> Result<Vec<LocalityIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.locality_idx_cache.read().await;
-        let items = cache.get_by_i64_index("code_hash", &code_hash);
-        Ok(items)
+        self.find_by_i64_key("code_hash", code_hash).await
+    }
+
+    /// Find by code, given in any case and with surrounding whitespace
+    pub async fn find_by_code(
+        &self,
+        code: &str,
+    ) -> Result<Vec<LocalityIdxModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_code_hash(hash_as_i64(&normalize_code(code).as_str())?).await
     }
 }
 
@@ -59,6 +67,33 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_find_by_code_in_other_case() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        let country = create_test_country("NL", "Netherlands");
+        let country_id = country.id;
+        country_repo.create_batch(vec![country], None).await?;
+
+        let subdivision = create_test_country_subdivision(country_id, "NH", "North Holland");
+        let subdivision_id = subdivision.id;
+        country_subdivision_repo.create_batch(vec![subdivision], None).await?;
+
+        let saved = locality_repo
+            .create_batch(vec![create_test_locality(subdivision_id, "HAA", "Haarlem")], None)
+            .await?;
+
+        let found = locality_repo.find_by_code("haa").await?;
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_find_by_code_hash_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -1,20 +1,43 @@
 use std::error::Error;
 use uuid::Uuid;
 
-use business_core_db::models::person::locality::LocalityIdxModel;
+use business_core_db::models::person::locality::{LocalityIdxModel, LocalityModel};
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocalityRepositoryImpl;
 
 impl LocalityRepositoryImpl {
+    /// Localities of a country subdivision, from the index cache or, when the
+    /// repository does not serve finders from its cache, from locality_idx
     pub async fn find_by_country_subdivision_id(
         &self,
         country_subdivision_id: Uuid,
     ) -> Result<Vec<LocalityIdxModel>, Box<dyn Error + Send + Sync>> {
+        if !self.serves_key_from_cache("country_subdivision_id", country_subdivision_id.into()) {
+            return self.find_idx_by_column("country_subdivision_id", country_subdivision_id).await;
+        }
         let cache = self.locality_idx_cache.read().await;
         let items = cache.get_by_uuid_index("country_subdivision_id", &country_subdivision_id);
         let result = items.to_vec();
         Ok(result)
     }
+
+    /// Full localities of a country subdivision, loaded in one query
+    ///
+    /// The ids are found as by `find_by_country_subdivision_id`.
+    pub async fn load_by_country_subdivision_id(
+        &self,
+        country_subdivision_id: Uuid,
+    ) -> Result<Vec<LocalityModel>, Box<dyn Error + Send + Sync>> {
+        let ids: Vec<Uuid> = self
+            .find_by_country_subdivision_id(country_subdivision_id)
+            .await?
+            .iter()
+            .map(|idx| idx.id)
+            .collect();
+        let localities = Self::load_batch_impl(self, &ids).await?;
+        Ok(localities.into_iter().flatten().collect())
+    }
 }
 
 #[cfg(test)]
@@ -63,6 +86,43 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_load_by_country_subdivision_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        let country = create_test_country("AT", "Austria");
+        let country_id = country.id;
+        country_repo.create_batch(vec![country], None).await?;
+
+        let vienna = create_test_country_subdivision(country_id, "WI", "Vienna");
+        let tyrol = create_test_country_subdivision(country_id, "TI", "Tyrol");
+        let (vienna_id, tyrol_id) = (vienna.id, tyrol.id);
+        country_subdivision_repo.create_batch(vec![vienna, tyrol], None).await?;
+
+        let localities = vec![
+            create_test_locality(vienna_id, "WI1", "Innere Stadt"),
+            create_test_locality(vienna_id, "WI2", "Leopoldstadt"),
+            create_test_locality(tyrol_id, "TI1", "Innsbruck"),
+        ];
+        locality_repo.create_batch(localities, None).await?;
+
+        let in_vienna = locality_repo.load_by_country_subdivision_id(vienna_id).await?;
+        assert_eq!(in_vienna.len(), 2);
+        assert!(in_vienna.iter().all(|l| l.country_subdivision_id == vienna_id));
+        assert!(in_vienna.iter().any(|l| l.name_l1.as_str() == "Leopoldstadt"));
+
+        let in_tyrol = locality_repo.load_by_country_subdivision_id(tyrol_id).await?;
+        assert_eq!(in_tyrol.len(), 1);
+        assert_eq!(in_tyrol[0].name_l1.as_str(), "Innsbruck");
+
+        assert!(locality_repo.load_by_country_subdivision_id(Uuid::new_v4()).await?.is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_find_by_country_subdivision_id_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -19,7 +19,7 @@ impl LocalityRepositoryImpl {
         
         let query = r#"SELECT * FROM locality WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("locality", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
                 sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
             } else {
@@ -39,10 +39,16 @@ impl LocalityRepositoryImpl {
         }
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<LocalityModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, LocalityModel> for LocalityRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "locality", count = ids.len())))]
     async fn load_batch(&self, ids: &[Uuid]) -> Result<Vec<Option<LocalityModel>>, Box<dyn Error + Send + Sync>> {
         Self::load_batch_impl(self, ids).await
     }
@@ -6,6 +6,7 @@ pub mod load_batch;
 pub mod update_batch;
 pub mod find_by_code_hash;
 pub mod find_by_country_subdivision_id;
+pub mod search_by_name;
 
 pub use repo_impl::LocalityRepositoryImpl;
 
@@ -0,0 +1,164 @@
+use business_core_db::models::person::locality::LocalityModel;
+use crate::error::RepositoryError;
+use crate::utils::TryFromRow;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::LocalityRepositoryImpl;
+
+/// Shortest search text accepted by `search_by_name`
+pub const MIN_SEARCH_CHARS: usize = 2;
+
+impl LocalityRepositoryImpl {
+    /// Localities whose name, in any of the three languages, contains `query`
+    ///
+    /// The match ignores case and surrounding whitespace of `query`. Results are ranked
+    /// exact matches first, then names starting with `query`, then the other matches,
+    /// each group ordered by `name_l1`. A `query` shorter than `MIN_SEARCH_CHARS`
+    /// characters fails with `RepositoryError::SearchTooShort`, as it would match most
+    /// of the table.
+    pub async fn search_by_name(
+        &self,
+        country_subdivision_id: Option<Uuid>,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<LocalityModel>, Box<dyn Error + Send + Sync>> {
+        let query = query.trim();
+        if query.chars().count() < MIN_SEARCH_CHARS {
+            return Err(RepositoryError::SearchTooShort {
+                entity: "locality".to_string(),
+                min_chars: MIN_SEARCH_CHARS,
+            }
+            .into());
+        }
+        let escaped = escape_like(query);
+
+        let rows = {
+            let mut tx = self.operation_timeout.lock("locality", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT * FROM (
+                    SELECT *,
+                        CASE
+                            WHEN LOWER(name_l1) = LOWER($2) OR LOWER(name_l2) = LOWER($2) OR LOWER(name_l3) = LOWER($2) THEN 0
+                            WHEN name_l1 ILIKE $3 || '%' OR name_l2 ILIKE $3 || '%' OR name_l3 ILIKE $3 || '%' THEN 1
+                            ELSE 2
+                        END AS rank
+                    FROM locality
+                    WHERE ($1::UUID IS NULL OR country_subdivision_id = $1)
+                    AND (name_l1 ILIKE '%' || $3 || '%' OR name_l2 ILIKE '%' || $3 || '%' OR name_l3 ILIKE '%' || $3 || '%')
+                ) matches
+                ORDER BY rank, name_l1, id
+                LIMIT $4
+                "#,
+            )
+            .bind(country_subdivision_id)
+            .bind(query)
+            .bind(escaped)
+            .bind(i64::from(limit))
+            .fetch_all(&mut **transaction)
+            .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(LocalityModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+}
+
+/// Escape the LIKE wildcards of `text`, so it is matched literally
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_like;
+    use crate::error::RepositoryError;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use heapless::String as HeaplessString;
+    use crate::repository::person::test_utils::{create_test_country, create_test_country_subdivision, create_test_locality};
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("San Francisco"), "San Francisco");
+        assert_eq!(escape_like("100%_a\\b"), "100\\%\\_a\\\\b");
+    }
+
+    #[tokio::test]
+    async fn test_search_by_name() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        let country = create_test_country("XB", "Search Country");
+        let country_id = country.id;
+        country_repo.create_batch(vec![country], None).await?;
+        let sao_paulo_state = create_test_country_subdivision(country_id, "SPS", "São Paulo State");
+        let parana = create_test_country_subdivision(country_id, "PRS", "Paraná");
+        let (state_id, parana_id) = (sao_paulo_state.id, parana.id);
+        country_subdivision_repo.create_batch(vec![sao_paulo_state, parana], None).await?;
+
+        let sao_paulo = create_test_locality(state_id, "SPSEARCH", "São Paulo");
+        let mut sao_jose = create_test_locality(state_id, "SJSEARCH", "São José dos Campos");
+        sao_jose.name_l2 = Some(HeaplessString::try_from("Saint Joseph").unwrap());
+        let near_sao_paulo = create_test_locality(state_id, "NSPSEARCH", "Grande São Paulo");
+        let sao_paulo_parana = create_test_locality(parana_id, "SPPSEARCH", "São Paulo do Paraná");
+        let ids = [sao_paulo.id, sao_jose.id, near_sao_paulo.id, sao_paulo_parana.id];
+        locality_repo
+            .create_batch(vec![sao_paulo, sao_jose, near_sao_paulo, sao_paulo_parana], None)
+            .await?;
+
+        // Exact match first, then prefix matches, then the other matches
+        let found = locality_repo.search_by_name(Some(state_id), " são paulo ", 10).await?;
+        assert_eq!(found.iter().map(|locality| locality.id).collect::<Vec<_>>(), vec![ids[0], ids[2]]);
+
+        // Without the subdivision filter the other subdivision's locality is found too
+        let found = locality_repo.search_by_name(None, "SãO PAULO DO", 10).await?;
+        assert_eq!(found.iter().map(|locality| locality.id).collect::<Vec<_>>(), vec![ids[3]]);
+
+        // Names in other languages are searched
+        let found = locality_repo.search_by_name(Some(state_id), "saint jo", 10).await?;
+        assert_eq!(found.iter().map(|locality| locality.id).collect::<Vec<_>>(), vec![ids[1]]);
+
+        // The limit applies after ranking, prefix matches by name before the others
+        let found = locality_repo.search_by_name(Some(state_id), "são", 2).await?;
+        assert_eq!(found.iter().map(|locality| locality.id).collect::<Vec<_>>(), vec![ids[1], ids[0]]);
+
+        // Wildcards are matched literally
+        assert!(locality_repo.search_by_name(Some(state_id), "s%o", 10).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_by_name_rejects_short_query() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let locality_repo = &ctx.person_repos().locality_repository;
+
+        for query in ["", "S", " ã "] {
+            let error = locality_repo
+                .search_by_name(None, query, 10)
+                .await
+                .expect_err("A one character search is refused");
+            assert!(matches!(
+                error.downcast_ref::<RepositoryError>(),
+                Some(RepositoryError::SearchTooShort { min_chars: 2, .. })
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -6,23 +6,28 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocalityRepositoryImpl;
 
 impl LocalityRepositoryImpl {
     pub(super) async fn update_batch_impl(
         &self,
-        items: Vec<LocalityModel>,
+        mut items: Vec<LocalityModel>,
     ) -> Result<Vec<LocalityModel>, Box<dyn Error + Send + Sync>> {
         if items.is_empty() {
             return Ok(Vec::new());
         }
 
+        for item in &mut items {
+            item.normalize()?;
+        }
+
         let mut updated_items = Vec::new();
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("locality", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -49,12 +54,16 @@ impl LocalityRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = self.locality_idx_cache.read().await;
-            for (id, idx) in indices {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|(id, _)| *id).collect();
+            {
+                let cache = self.locality_idx_cache.read().await;
+                for (id, idx) in indices {
+                    cache.remove(&id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -63,6 +72,7 @@ impl LocalityRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, LocalityModel> for LocalityRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "locality", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<LocalityModel>,
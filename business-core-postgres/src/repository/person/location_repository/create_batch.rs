@@ -6,10 +6,14 @@ use business_core_db::models::{
 use business_core_db::repository::create_batch::CreateBatch;
 use sqlx::Postgres;
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocationRepositoryImpl;
 
 impl LocationRepositoryImpl {
@@ -28,7 +32,7 @@ impl LocationRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("location", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for mut item in items {
@@ -68,7 +72,8 @@ impl LocationRepositoryImpl {
                 .bind(item.hash)
                 .bind(item.audit_log_id)
                 .execute(&mut **transaction)
-                .await?;
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 // Execute main insert
                 sqlx::query(
@@ -94,7 +99,8 @@ impl LocationRepositoryImpl {
                 .bind(item.hash)
                 .bind(item.audit_log_id)
                 .execute(&mut **transaction)
-                .await?;
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 // Insert into index table
                 let idx = item.to_index();
@@ -107,7 +113,8 @@ impl LocationRepositoryImpl {
                 .bind(idx.id)
                 .bind(idx.locality_id)
                 .execute(&mut **transaction)
-                .await?;
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -115,17 +122,7 @@ impl LocationRepositoryImpl {
                     entity_id: item.id,
                     entity_type: EntityType::Location,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
                 indices.push(idx);
                 saved_items.push(item);
@@ -133,11 +130,17 @@ impl LocationRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.location_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.location_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
@@ -146,6 +149,7 @@ impl LocationRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, LocationModel> for LocationRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "location", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<LocationModel>,
@@ -429,4 +433,61 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_batch_maps_foreign_key_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let location_repo = &ctx.person_repos().location_repository;
+
+        // The audit log is never persisted
+        let audit_log = create_test_audit_log();
+        let location = create_test_location(uuid::Uuid::new_v4(), "1 Orphan St");
+
+        let error = location_repo
+            .create_batch(vec![location], Some(audit_log.id))
+            .await
+            .expect_err("Creating a location with an unknown audit log must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::ForeignKeyViolation { entity, referenced_table, .. }) => {
+                assert_eq!(entity, "location");
+                assert_eq!(referenced_table, "audit_log");
+            }
+            other => panic!("Expected ForeignKeyViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_maps_unique_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let location_repo = &ctx.person_repos().location_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let location = create_test_location(uuid::Uuid::new_v4(), "1 Duplicated St");
+        location_repo.create_batch(vec![location.clone()], Some(audit_log.id)).await?;
+
+        let error = location_repo
+            .create_batch(vec![location], Some(audit_log.id))
+            .await
+            .expect_err("Creating the same location twice must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::UniqueViolation { entity, constraint }) => {
+                assert_eq!(entity, "location");
+                assert!(!constraint.is_empty());
+            }
+            other => panic!("Expected UniqueViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
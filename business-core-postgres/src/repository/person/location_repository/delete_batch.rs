@@ -2,11 +2,15 @@ use business_core_db::models::audit::{AuditLinkModel, EntityType};
 use async_trait::async_trait;
 use business_core_db::repository::load_batch::LoadBatch;
 use business_core_db::repository::delete_batch::DeleteBatch;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
 use sqlx::Postgres;
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::LocationRepositoryImpl;
 
 impl LocationRepositoryImpl {
@@ -14,17 +18,17 @@ impl LocationRepositoryImpl {
         repo: &LocationRepositoryImpl,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         let audit_log_id = audit_log_id.ok_or("audit_log_id is required for LocationModel")?;
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         let entities_to_delete = repo.load_batch(ids).await?;
-        let mut deleted_count = 0;
+        let mut deleted = Vec::new();
 
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("location", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
 
             for entity in entities_to_delete.into_iter().flatten() {
@@ -60,12 +64,14 @@ impl LocationRepositoryImpl {
                 .bind(final_audit_entity.hash)
                 .bind(final_audit_entity.audit_log_id)
                 .execute(&mut **transaction)
-                .await?;
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 let result = sqlx::query(r#"DELETE FROM location WHERE id = $1"#)
                     .bind(entity.id)
                     .execute(&mut **transaction)
-                    .await?;
+                    .await
+                    .map_err(|e| map_db_error("location", e))?;
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -73,40 +79,50 @@ impl LocationRepositoryImpl {
                     entity_id: entity.id,
                     entity_type: EntityType::Location,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
                 
-                deleted_count += result.rows_affected() as usize;
+                if result.rows_affected() > 0 {
+                    deleted.push(entity.id);
+                }
             }
         }
         
-        {
+        if repo.cache_policy.maintains_cache() {
             let cache = repo.location_idx_cache.read().await;
-            for id in ids {
+            for id in &deleted {
+                // No replayed notification of the row brings it back
+                repo.cache_versions.admit(*id, CacheVersions::DELETED);
                 cache.remove(id);
             }
+            if let Some(bound) = &repo.cache_bound {
+                bound.record_removal(&deleted);
+            }
         }
         
-        Ok(deleted_count)
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for LocationRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "location", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids, audit_log_id).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for LocationRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids, audit_log_id).await
     }
 }
@@ -116,6 +132,7 @@ mod tests {
     use crate::test_helper::setup_test_context;
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::repository::delete_batch::DeleteBatch;
+    use business_core_db::repository::delete_batch_detailed::DeleteBatchDetailed;
     use uuid::Uuid;
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_country, create_test_country_subdivision, create_test_locality, create_test_location};
 
@@ -214,4 +231,40 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_delete_batch_detailed() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let country_repo = &ctx.person_repos().country_repository;
+        let country_subdivision_repo = &ctx.person_repos().country_subdivision_repository;
+        let locality_repo = &ctx.person_repos().locality_repository;
+        let location_repo = &ctx.person_repos().location_repository;
+
+        let country = create_test_country("DL", "Detailed Location Country");
+        let country_id = country.id;
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        country_repo.create_batch(vec![country], Some(audit_log.id)).await?;
+        let subdivision = create_test_country_subdivision(country_id, "DL", "Detailed Subdivision");
+        let subdivision_id = subdivision.id;
+        country_subdivision_repo.create_batch(vec![subdivision], Some(audit_log.id)).await?;
+        let locality = create_test_locality(subdivision_id, "DL", "Detailed Locality");
+        let locality_id = locality.id;
+        locality_repo.create_batch(vec![locality], Some(audit_log.id)).await?;
+        let saved = location_repo
+            .create_batch(vec![create_test_location(locality_id, "1 Detailed Road")], Some(audit_log.id))
+            .await?;
+        let missing_id = Uuid::new_v4();
+
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        let outcome = location_repo
+            .delete_batch_detailed(&[missing_id, saved[0].id], Some(delete_audit_log.id))
+            .await?;
+        assert_eq!(outcome.deleted, vec![saved[0].id]);
+        assert_eq!(outcome.not_found, vec![missing_id]);
+
+        Ok(())
+    }
+}
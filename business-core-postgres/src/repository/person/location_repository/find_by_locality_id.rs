@@ -3,16 +3,23 @@ use uuid::Uuid;
 use business_core_db::models::person::location::LocationIdxModel;
 use business_core_db::repository::pagination::{Page, PageRequest};
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocationRepositoryImpl;
 
 impl LocationRepositoryImpl {
+    /// A page of the locations of a locality, from the index cache or, when the
+    /// repository does not serve finders from its cache, from location_idx
     pub async fn find_by_locality_id(
         &self,
         locality_id: Uuid,
         page: PageRequest,
     ) -> Result<Page<LocationIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.location_idx_cache.read().await;
-        let all_items = cache.get_by_uuid_index("locality_id", &locality_id);
+        let all_items = if self.serves_key_from_cache("locality_id", locality_id.into()) {
+            let cache = self.location_idx_cache.read().await;
+            cache.get_by_uuid_index("locality_id", &locality_id)
+        } else {
+            self.find_idx_by_column("locality_id", locality_id).await?
+        };
         
         let total = all_items.len();
         let start = page.offset;
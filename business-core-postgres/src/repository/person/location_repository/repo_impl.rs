@@ -7,32 +7,66 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct LocationRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub location_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<LocationIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub location_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<LocationIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the location_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the location_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
 }
 
 impl LocationRepositoryImpl {
     pub fn new(
         executor: Executor,
         location_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<LocationIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, location_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        location_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<LocationIdxModel>>>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            location_idx_shared_cache: location_idx_cache.clone(),
             location_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 location_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
         }
     }
 
     pub async fn load_all_location_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<LocationIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM location_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("location", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -73,7 +107,7 @@ impl TryFromRow<PgRow> for LocationModel {
 impl TryFromRow<PgRow> for LocationIdxModel {
     fn try_from_row(row: &PgRow) -> Result<Self, Box<dyn Error + Send + Sync>> {
         Ok(LocationIdxModel {
-            id: row.get("location_id"),
+            id: row.get("id"),
             locality_id: row.get("locality_id"),
         })
     }
@@ -82,10 +116,83 @@ impl TryFromRow<PgRow> for LocationIdxModel {
 #[async_trait]
 impl TransactionAware for LocationRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.location_idx_cache.read().await.on_commit().await
+        self.location_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.location_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.location_idx_cache.read().await.on_rollback().await
     }
-}
\ No newline at end of file
+}
+
+/// No i64 keys, implemented for the idx table reads shared with the other cached repositories
+#[async_trait]
+impl FindByI64Key for LocationRepositoryImpl {
+    type Idx = LocationIdxModel;
+
+    const ENTITY: &'static str = "location";
+    const I64_KEYS: &'static [&'static str] = &[];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<LocationIdxModel>> {
+        &self.location_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for LocationRepositoryImpl {
+    type Idx = LocationIdxModel;
+
+    const ENTITY: &'static str = "location";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<LocationIdxModel>>> {
+        &self.location_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<LocationIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_location_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
@@ -7,9 +7,12 @@ use business_core_db::models::index_aware::IndexAware;
 use business_core_db::repository::update_batch::UpdateBatch;
 use sqlx::Postgres;
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::LocationRepositoryImpl;
 
 impl LocationRepositoryImpl {
@@ -27,7 +30,7 @@ impl LocationRepositoryImpl {
         let mut indices_to_update = Vec::new();
         
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("location", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for mut item in items {
@@ -74,7 +77,8 @@ impl LocationRepositoryImpl {
                 .bind(item.hash)
                 .bind(item.audit_log_id)
                 .execute(&mut **transaction)
-                .await?;
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 let rows_affected = sqlx::query(
                     r#"
@@ -104,23 +108,27 @@ impl LocationRepositoryImpl {
                 .bind(previous_hash)
                 .bind(previous_audit_log_id)
                 .execute(&mut **transaction)
-                .await?
+                .await
+                .map_err(|e| map_db_error("location", e))?
                 .rows_affected();
 
                 if rows_affected == 0 {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(entity = "location", id = %item.id, "Concurrent update detected");
                     return Err("Concurrent update detected".into());
                 }
 
                 let idx = item.to_index();
-                sqlx::query(
+                let version: i64 = sqlx::query_scalar(
                     r#"
-                    UPDATE location_idx SET locality_id = $2 WHERE id = $1
+                    UPDATE location_idx SET locality_id = $2 WHERE id = $1 RETURNING version
                     "#,
                 )
                 .bind(idx.id)
                 .bind(idx.locality_id)
-                .execute(&mut **transaction)
-                .await?;
+                .fetch_one(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("location", e))?;
 
                 // Create audit link
                 let audit_link = AuditLinkModel {
@@ -128,29 +136,27 @@ impl LocationRepositoryImpl {
                     entity_id: item.id,
                     entity_type: EntityType::Location,
                 };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
+                AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
-                indices_to_update.push((item.id, idx));
+                indices_to_update.push((idx, version as u64));
                 updated_items.push(item);
             }
         }
         
-        {
-            let cache = self.location_idx_cache.read().await;
-            for (id, idx) in indices_to_update {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices_to_update.iter().map(|(idx, _)| idx.id).collect();
+            {
+                let cache = self.location_idx_cache.read().await;
+                for (idx, version) in indices_to_update {
+                    // A notification already brought a newer version of the row
+                    if !self.cache_versions.admit(idx.id, version) {
+                        continue;
+                    }
+                    cache.remove(&idx.id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -159,6 +165,7 @@ impl LocationRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, LocationModel> for LocationRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "location", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<LocationModel>,
@@ -234,6 +241,74 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_batch_advances_cache_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::repository::cache_versions::CacheVersions;
+        use crate::repository::idx_notification_listener::IdxNotificationListener;
+        use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+        use business_core_db::repository::delete_batch::DeleteBatch;
+        use postgres_unit_of_work::TransactionAware;
+        use std::sync::Arc;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+        let location_repo = &person_repos.location_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let country = create_test_country("QV", "Versioned Country");
+        let subdivision = create_test_country_subdivision(country.id, "QV-1", "Versioned Subdivision");
+        let first_locality = create_test_locality(subdivision.id, "QV-1-1", "First Locality");
+        let second_locality = create_test_locality(subdivision.id, "QV-1-2", "Second Locality");
+        let (first_locality_id, second_locality_id) = (first_locality.id, second_locality.id);
+        person_repos.country_repository.create_batch(vec![country], Some(audit_log.id)).await?;
+        person_repos.country_subdivision_repository.create_batch(vec![subdivision], Some(audit_log.id)).await?;
+        person_repos.locality_repository.create_batch(vec![first_locality, second_locality], Some(audit_log.id)).await?;
+
+        let mut location = location_repo
+            .create_batch(vec![create_test_location(first_locality_id, "1 Versioned Street")], Some(audit_log.id))
+            .await?
+            .remove(0);
+        assert_eq!(location_repo.cache_versions.version(location.id), Some(CacheVersions::INITIAL));
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        location.locality_id = second_locality_id;
+        location_repo.update_batch(vec![location.clone()], Some(update_audit_log.id)).await?;
+        assert_eq!(location_repo.cache_versions.version(location.id), Some(2));
+
+        // Once the session committed, the notification of the insert arriving after the
+        // update is dropped
+        location_repo.on_commit().await?;
+        let coalescer = Arc::new(
+            NotificationCoalescer::new("location_idx", location_repo.location_idx_shared_cache.clone(), CoalescingConfig::default())
+                .with_versions(location_repo.cache_versions.shared().clone()),
+        );
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(coalescer.clone());
+        let insert = serde_json::json!({
+            "table": "location_idx",
+            "op": "INSERT",
+            "row": { "id": location.id, "locality_id": first_locality_id, "version": CacheVersions::INITIAL },
+        });
+        listener.dispatch(&insert.to_string())?;
+        listener.flush();
+        assert_eq!(coalescer.counters().stale(), 1);
+
+        // Nor does it bring back a deleted location
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        location_repo.delete_batch(&[location.id], Some(delete_audit_log.id)).await?;
+        location_repo.on_commit().await?;
+        listener.dispatch(&insert.to_string())?;
+        listener.flush();
+        assert_eq!(coalescer.counters().stale(), 2);
+        assert!(!location_repo.location_idx_shared_cache.read().contains_primary(&location.id));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_update_batch_empty() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -10,6 +10,7 @@ pub mod portfolio_repository;
 pub mod compliance_status_repository;
 pub mod document_repository;
 pub mod factory;
+pub mod repo_builder;
 
 pub use country_repository::CountryRepositoryImpl;
 pub use country_subdivision_repository::CountrySubdivisionRepositoryImpl;
@@ -22,7 +23,8 @@ pub use activity_log_repository::ActivityLogRepositoryImpl;
 pub use portfolio_repository::PortfolioRepositoryImpl;
 pub use compliance_status_repository::ComplianceStatusRepositoryImpl;
 pub use document_repository::DocumentRepositoryImpl;
-pub use factory::{PersonRepoFactory, PersonRepositories};
+pub use factory::{PersonRepoConfig, PersonRepoFactory, PersonRepositories};
+pub use repo_builder::{PersonRepoBuilder, SelectedPersonRepositories};
 
 #[cfg(test)]
 pub mod test_utils;
\ No newline at end of file
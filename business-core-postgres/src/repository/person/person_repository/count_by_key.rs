@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use business_core_db::repository::count_by_key::CountByKey;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::count_by_key::{check_index_key, count_idx_rows};
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::PersonRepositoryImpl;
+
+/// Uuid keys of `PersonIdxModel`
+const UUID_KEYS: &[&str] = &["organization_person_id", "duplicate_of_person_id"];
+
+#[async_trait]
+impl CountByKey<Postgres> for PersonRepositoryImpl {
+    async fn count_by_uuid_key(&self, key_name: &str, value: Uuid) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("person", UUID_KEYS, key_name)?;
+        if !self.serves_key_from_cache(key_name, value.into()) {
+            return count_idx_rows(&self.executor, &self.operation_timeout, "person_idx", key_name, value).await;
+        }
+        let cache = self.person_idx_cache.read().await;
+        Ok(cache.get_by_uuid_index(key_name, &value).len())
+    }
+
+    async fn count_by_i64_key(&self, key_name: &str, value: i64) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("person", <Self as FindByI64Key>::I64_KEYS, key_name)?;
+        if !self.serves_key_from_cache(key_name, value.into()) {
+            return count_idx_rows(&self.executor, &self.operation_timeout, "person_idx", key_name, value).await;
+        }
+        let cache = self.person_idx_cache.read().await;
+        Ok(cache.get_by_i64_index(key_name, &value).len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::RepositoryError;
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::count_by_key::CountByKey;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::sync::Arc;
+    use uuid::Uuid;
+    use super::super::test_utils::{create_test_audit_log, create_test_person};
+
+    #[tokio::test]
+    async fn test_count_by_organization_person_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let sql_repo = PersonRepositoryImpl::new_with_cache_policy(
+            person_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let organization = create_test_person("Counted Organization");
+        let organization_id = organization.id;
+        let mut persons = vec![organization];
+        for i in 0..3 {
+            let mut member = create_test_person(&format!("Counted Member {i}"));
+            member.organization_person_id = Some(organization_id);
+            persons.push(member);
+        }
+        person_repo.create_batch(persons, Some(audit_log.id)).await?;
+
+        assert_eq!(person_repo.count_by_uuid_key("organization_person_id", organization_id).await?, 3);
+        assert_eq!(sql_repo.count_by_uuid_key("organization_person_id", organization_id).await?, 3);
+        assert_eq!(person_repo.count_by_uuid_key("organization_person_id", Uuid::new_v4()).await?, 0);
+        assert_eq!(sql_repo.count_by_uuid_key("organization_person_id", Uuid::new_v4()).await?, 0);
+
+        let error = sql_repo
+            .count_by_uuid_key("id_number_hash", organization_id)
+            .await
+            .expect_err("id_number_hash is not a uuid key");
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::UnknownIndexKey { .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
 use async_trait::async_trait;
 use business_core_db::models::{
     audit::{AuditLinkModel, EntityType},
-    person::person::PersonModel,
+    person::person::{PersonIdxModel, PersonModel},
 };
 use business_core_db::repository::create_batch::CreateBatch;
-use sqlx::Postgres;
+use sqlx::{PgConnection, Postgres};
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
-use business_core_db::models::index_aware::IndexAware;
-use business_core_db::utils::hash_as_i64;
+use business_core_db::utils::{hash_as_i64, HashVersion};
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::columns::{PERSON_COLUMNS, PERSON_IDX_COLUMNS, PERSON_SQL};
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -23,154 +27,98 @@ impl PersonRepositoryImpl {
             return Ok(Vec::new());
         }
 
-        let mut saved_items = Vec::new();
-        let mut indices = Vec::new();
-        
-        // Acquire lock once and do all database operations
-        {
-            let mut tx = repo.executor.tx.lock().await;
+        let (saved_items, indices) = {
+            let mut tx = repo.operation_timeout.lock("person", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
-            
-            for mut item in items {
-                // 1. Create a copy of entity for hashing
-                let mut entity_for_hashing = item.clone();
-                entity_for_hashing.hash = 0;  // Must be 0 before hashing
-                entity_for_hashing.audit_log_id = Some(audit_log_id); // Set ID before hashing
-
-                // 2. Compute hash
-                let computed_hash = hash_as_i64(&entity_for_hashing)?;
-
-                // 3. Update original entity with computed hash and new audit_log_id
-                item.hash = computed_hash;
-                item.audit_log_id = Some(audit_log_id);
-
-                // Execute audit insert
-                sqlx::query(
-                    r#"
-                    INSERT INTO person_audit
-                    (id, person_type, risk_rating, status, display_name, external_identifier, id_type, id_number, entity_reference_count, organization_person_id, messaging_info1, messaging_info2, messaging_info3, messaging_info4, messaging_info5, department, location_id, duplicate_of_person_id, last_activity_log, last_compliance_status, last_document, last_portfolio, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_type)
-                .bind(item.risk_rating)
-                .bind(item.status)
-                .bind(item.display_name.as_str())
-                .bind(item.external_identifier.as_deref())
-                .bind(item.id_type)
-                .bind(item.id_number.as_str())
-                .bind(item.entity_reference_count)
-                .bind(item.organization_person_id)
-                .bind(item.messaging_info1.as_deref())
-                .bind(item.messaging_info2.as_deref())
-                .bind(item.messaging_info3.as_deref())
-                .bind(item.messaging_info4.as_deref())
-                .bind(item.messaging_info5.as_deref())
-                .bind(item.department.as_deref())
-                .bind(item.location_id)
-                .bind(item.duplicate_of_person_id)
-                .bind(item.last_activity_log)
-                .bind(item.last_compliance_status)
-                .bind(item.last_document)
-                .bind(item.last_portfolio)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
-
-                // Execute main insert
-                sqlx::query(
-                    r#"
-                    INSERT INTO person
-                    (id, person_type, risk_rating, status, display_name, external_identifier, id_type, id_number, entity_reference_count, organization_person_id, messaging_info1, messaging_info2, messaging_info3, messaging_info4, messaging_info5, department, location_id, duplicate_of_person_id, last_activity_log, last_compliance_status, last_document, last_portfolio, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_type)
-                .bind(item.risk_rating)
-                .bind(item.status)
-                .bind(item.display_name.as_str())
-                .bind(item.external_identifier.as_deref())
-                .bind(item.id_type)
-                .bind(item.id_number.as_str())
-                .bind(item.entity_reference_count)
-                .bind(item.organization_person_id)
-                .bind(item.messaging_info1.as_deref())
-                .bind(item.messaging_info2.as_deref())
-                .bind(item.messaging_info3.as_deref())
-                .bind(item.messaging_info4.as_deref())
-                .bind(item.messaging_info5.as_deref())
-                .bind(item.department.as_deref())
-                .bind(item.location_id)
-                .bind(item.duplicate_of_person_id)
-                .bind(item.last_activity_log)
-                .bind(item.last_compliance_status)
-                .bind(item.last_document)
-                .bind(item.last_portfolio)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
-
-                // Insert into index table
-                let idx = item.to_index();
-                sqlx::query(
-                    r#"
-                    INSERT INTO person_idx (id, external_identifier_hash, organization_person_id, duplicate_of_person_id, id_number_hash)
-                    VALUES ($1, $2, $3, $4, $5)
-                    "#,
-                )
-                .bind(idx.id)
-                .bind(idx.external_identifier_hash)
-                .bind(idx.organization_person_id)
-                .bind(idx.duplicate_of_person_id)
-                .bind(idx.id_number_hash)
-                .execute(&mut **transaction)
-                .await?;
-
-                // Create audit link
-                let audit_link = AuditLinkModel {
-                    audit_log_id,
-                    entity_id: item.id,
-                    entity_type: EntityType::Person,
-                };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
-
-                indices.push(idx);
-                saved_items.push(item);
-            }
-        } // Transaction lock released here
+            repo.operation_timeout
+                .query("person", Self::insert_in_connection(&mut **transaction, items, audit_log_id, repo.hash_version))
+                .await??
+        };
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.person_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.person_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
     }
+
+    /// Write the person inserts on an already locked connection
+    ///
+    /// Returns the persisted items and their index models; the caller decides whether
+    /// the index models go to the person_idx cache.
+    pub(super) async fn insert_in_connection(
+        conn: &mut PgConnection,
+        items: Vec<PersonModel>,
+        audit_log_id: Uuid,
+        hash_version: HashVersion,
+    ) -> Result<(Vec<PersonModel>, Vec<PersonIdxModel>), Box<dyn Error + Send + Sync>> {
+        let mut saved_items = Vec::new();
+        let mut indices = Vec::new();
+
+        for mut item in items {
+            // 1. Create a copy of entity for hashing
+            let mut entity_for_hashing = item.clone();
+            entity_for_hashing.hash = 0;  // Must be 0 before hashing
+            entity_for_hashing.audit_log_id = Some(audit_log_id); // Set ID before hashing
+
+            // 2. Compute hash
+            let computed_hash = hash_as_i64(&entity_for_hashing)?;
+
+            // 3. Update original entity with computed hash and new audit_log_id
+            item.hash = computed_hash;
+            item.audit_log_id = Some(audit_log_id);
+
+            // Execute audit insert
+            PERSON_COLUMNS
+                .bind(sqlx::query(&PERSON_SQL.insert_audit), &item)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?;
+
+            // Execute main insert
+            PERSON_COLUMNS
+                .bind(sqlx::query(&PERSON_SQL.insert), &item)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?;
+
+            // Insert into index table
+            let idx = item.to_index_with_hash_version(hash_version);
+            PERSON_IDX_COLUMNS
+                .bind(sqlx::query(&PERSON_SQL.insert_idx), &idx)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?;
+
+            // Create audit link
+            let audit_link = AuditLinkModel {
+                audit_log_id,
+                entity_id: item.id,
+                entity_type: EntityType::Person,
+            };
+            AuditLinkRepositoryImpl::insert_in_connection(&mut *conn, &[audit_link]).await?;
+
+            indices.push(idx);
+            saved_items.push(item);
+        }
+
+        Ok((saved_items, indices))
+    }
 }
 
 #[async_trait]
 impl CreateBatch<Postgres, PersonModel> for PersonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "person", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<PersonModel>,
@@ -191,6 +139,8 @@ mod tests {
     use crate::repository::person::person_repository::test_utils::{
         create_test_person, create_test_person_with_external_id,
     };
+    use business_core_db::fixtures::PersonFixture;
+    use business_core_db::models::person::common_enums::RiskRating;
     use tokio::time::{sleep, Duration};
 
     #[tokio::test]
@@ -278,6 +228,89 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_create_batch_persists_fixture() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let saved = PersonFixture::builder()
+            .display_name("High Risk Legal Person")
+            .person_type(PersonType::Legal)
+            .risk_rating(RiskRating::High)
+            .department("Treasury")
+            .persist(person_repo.as_ref(), Some(audit_log.id))
+            .await?;
+
+        let loaded = person_repo.load(saved.id).await?.unwrap();
+        assert_eq!(loaded.risk_rating, RiskRating::High);
+        assert_eq!(loaded.person_type, PersonType::Legal);
+        assert_eq!(loaded.department.as_deref(), Some("Treasury"));
+        assert_eq!(loaded.hash, saved.hash);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_maps_foreign_key_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        // The audit log is never persisted
+        let audit_log = create_test_audit_log();
+        let person = create_test_person("Person Without Audit Log", PersonType::Natural);
+
+        let error = person_repo
+            .create_batch(vec![person], Some(audit_log.id))
+            .await
+            .expect_err("Creating a person with an unknown audit log must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::ForeignKeyViolation { entity, referenced_table, .. }) => {
+                assert_eq!(entity, "person");
+                assert_eq!(referenced_table, "audit_log");
+            }
+            other => panic!("Expected ForeignKeyViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_maps_unique_violation() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person = create_test_person("Duplicated Person", PersonType::Natural);
+        person_repo.create_batch(vec![person.clone()], Some(audit_log.id)).await?;
+
+        let error = person_repo
+            .create_batch(vec![person], Some(audit_log.id))
+            .await
+            .expect_err("Creating the same person twice must fail");
+
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::UniqueViolation { entity, constraint }) => {
+                assert_eq!(entity, "person");
+                assert!(!constraint.is_empty());
+            }
+            other => panic!("Expected UniqueViolation, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_person_insert_triggers_cache_notification(
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -418,4 +451,70 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_create_batch_records_span() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id};
+        use tracing::Subscriber;
+        use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+        /// Records the name, entity and count of every span created
+        #[derive(Clone, Default)]
+        struct SpanCapture(Arc<Mutex<Vec<(&'static str, String, u64)>>>);
+
+        #[derive(Default)]
+        struct FieldVisitor {
+            entity: String,
+            count: u64,
+        }
+
+        impl Visit for FieldVisitor {
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                if field.name() == "count" {
+                    self.count = value;
+                }
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "entity" {
+                    self.entity = value.to_string();
+                }
+            }
+
+            fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+        }
+
+        impl<S: Subscriber> Layer<S> for SpanCapture {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+                let mut visitor = FieldVisitor::default();
+                attrs.record(&mut visitor);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((attrs.metadata().name(), visitor.entity, visitor.count));
+            }
+        }
+
+        let capture = SpanCapture::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let persons = (0..3)
+            .map(|i| create_test_person(&format!("Traced Person {i}"), PersonType::Natural))
+            .collect();
+        person_repo.create_batch(persons, Some(audit_log.id)).await?;
+
+        let spans = capture.0.lock().unwrap();
+        assert!(spans.contains(&("create_batch", "person".to_string(), 3)), "{:?}", *spans);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use business_core_db::models::person::person::PersonModel;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::dry_run::{begin_dry_run, end_dry_run};
+
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Validate a `create_batch` without persisting it
+    ///
+    /// Runs every step of `create_batch`, including the SQL inserts, inside a savepoint
+    /// that is rolled back whatever the outcome. The person_idx cache is not touched.
+    /// Returns the models as `create_batch` would persist them.
+    pub async fn create_batch_dry_run(
+        &self,
+        items: Vec<PersonModel>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<Vec<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let audit_log_id = audit_log_id.ok_or("audit_log_id is required for PersonModel")?;
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self.operation_timeout.lock("person", &self.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        begin_dry_run(&mut **transaction).await?;
+        let result = Self::insert_in_connection(&mut **transaction, items, audit_log_id, self.hash_version).await;
+        end_dry_run(&mut **transaction).await?;
+
+        let (saved_items, _indices) = result?;
+        Ok(saved_items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_create_batch_dry_run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let persons: Vec<_> = (0..50)
+            .map(|i| create_test_person(&format!("Dry Run Person {i}")))
+            .collect();
+        let ids: Vec<Uuid> = persons.iter().map(|p| p.id).collect();
+
+        let validated = person_repo.create_batch_dry_run(persons.clone(), Some(audit_log.id)).await?;
+
+        assert_eq!(validated.len(), 50);
+        assert!(validated.iter().all(|p| p.hash != 0 && p.audit_log_id == Some(audit_log.id)));
+
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM person WHERE id = ANY($1)")
+                .bind(&ids)
+                .fetch_one(&mut **transaction)
+                .await?;
+            assert_eq!(count, 0);
+        }
+        {
+            let cache = person_repo.person_idx_cache.read().await;
+            assert!(ids.iter().all(|id| !cache.contains_primary(id)));
+        }
+
+        // The transaction stays usable and a real create persists the same models
+        let saved = person_repo.create_batch(persons, Some(audit_log.id)).await?;
+        for (saved, validated) in saved.iter().zip(&validated) {
+            assert_eq!(saved.hash, validated.hash);
+        }
+
+        Ok(())
+    }
+}
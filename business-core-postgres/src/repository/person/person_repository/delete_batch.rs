@@ -2,11 +2,16 @@ use business_core_db::models::audit::{AuditLinkModel, EntityType};
 use async_trait::async_trait;
 use business_core_db::repository::load_batch::LoadBatch;
 use business_core_db::repository::delete_batch::DeleteBatch;
-use sqlx::Postgres;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
+use sqlx::{PgConnection, Postgres, Row};
 use std::error::Error;
+use crate::error::map_db_error;
 use uuid::Uuid;
 use business_core_db::utils::hash_as_i64;
 
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use super::columns::{PERSON_COLUMNS, PERSON_SQL};
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -14,110 +19,92 @@ impl PersonRepositoryImpl {
         repo: &PersonRepositoryImpl,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         let audit_log_id = audit_log_id.ok_or("audit_log_id is required for PersonModel")?;
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         let entities_to_delete = repo.load_batch(ids).await?;
-        let mut deleted_count = 0;
 
-        {
-            let mut tx = repo.executor.tx.lock().await;
+        let deleted: Vec<Uuid> = {
+            let mut tx = repo.operation_timeout.lock("person", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
-
-            for entity in entities_to_delete.into_iter().flatten() {
-                let mut final_audit_entity = entity.clone();
-                final_audit_entity.antecedent_hash = entity.hash;
-                final_audit_entity.antecedent_audit_log_id = entity.audit_log_id.ok_or("Entity must have audit_log_id for deletion")?;
-                final_audit_entity.audit_log_id = Some(audit_log_id);
-                final_audit_entity.hash = 0;
-
-                let final_hash = hash_as_i64(&final_audit_entity)?;
-                final_audit_entity.hash = final_hash;
-
-                sqlx::query(
-                    r#"
-                    INSERT INTO person_audit
-                    (id, person_type, risk_rating, status, display_name, external_identifier, id_type, id_number, entity_reference_count, organization_person_id, messaging_info1, messaging_info2, messaging_info3, messaging_info4, messaging_info5, department, location_id, duplicate_of_person_id, last_activity_log, last_compliance_status, last_document, last_portfolio, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
-                    "#,
-                )
-                .bind(final_audit_entity.id)
-                .bind(final_audit_entity.person_type)
-                .bind(final_audit_entity.risk_rating)
-                .bind(final_audit_entity.status)
-                .bind(final_audit_entity.display_name.as_str())
-                .bind(final_audit_entity.external_identifier.as_deref())
-                .bind(final_audit_entity.id_type)
-                .bind(final_audit_entity.id_number.as_str())
-                .bind(final_audit_entity.entity_reference_count)
-                .bind(final_audit_entity.organization_person_id)
-                .bind(final_audit_entity.messaging_info1.as_deref())
-                .bind(final_audit_entity.messaging_info2.as_deref())
-                .bind(final_audit_entity.messaging_info3.as_deref())
-                .bind(final_audit_entity.messaging_info4.as_deref())
-                .bind(final_audit_entity.messaging_info5.as_deref())
-                .bind(final_audit_entity.department.as_deref())
-                .bind(final_audit_entity.location_id)
-                .bind(final_audit_entity.duplicate_of_person_id)
-                .bind(final_audit_entity.last_activity_log)
-                .bind(final_audit_entity.last_compliance_status)
-                .bind(final_audit_entity.last_document)
-                .bind(final_audit_entity.last_portfolio)
-                .bind(final_audit_entity.antecedent_hash)
-                .bind(final_audit_entity.antecedent_audit_log_id)
-                .bind(final_audit_entity.hash)
-                .bind(final_audit_entity.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
-
-                let result = sqlx::query(r#"DELETE FROM person WHERE id = $1"#)
-                    .bind(entity.id)
-                    .execute(&mut **transaction)
-                    .await?;
-
-                // Create audit link
-                let audit_link = AuditLinkModel {
-                    audit_log_id,
-                    entity_id: entity.id,
-                    entity_type: EntityType::Person,
-                };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
-                
-                deleted_count += result.rows_affected() as usize;
-            }
-        }
-        
-        {
+            let conn: &mut PgConnection = &mut **transaction;
+
+            repo.operation_timeout
+                .query("person", async {
+                    for entity in entities_to_delete.into_iter().flatten() {
+                        let mut final_audit_entity = entity.clone();
+                        final_audit_entity.antecedent_hash = entity.hash;
+                        final_audit_entity.antecedent_audit_log_id = entity.audit_log_id.ok_or("Entity must have audit_log_id for deletion")?;
+                        final_audit_entity.audit_log_id = Some(audit_log_id);
+                        final_audit_entity.hash = 0;
+
+                        let final_hash = hash_as_i64(&final_audit_entity)?;
+                        final_audit_entity.hash = final_hash;
+
+                        PERSON_COLUMNS
+                            .bind(sqlx::query(&PERSON_SQL.insert_audit), &final_audit_entity)
+                            .execute(&mut *conn)
+                            .await
+                            .map_err(|e| map_db_error("person", e))?;
+
+                        // Create audit link
+                        let audit_link = AuditLinkModel {
+                            audit_log_id,
+                            entity_id: entity.id,
+                            entity_type: EntityType::Person,
+                        };
+                        AuditLinkRepositoryImpl::insert_in_connection(&mut *conn, &[audit_link]).await?;
+                    }
+
+                    let rows = sqlx::query(r#"DELETE FROM person WHERE id = ANY($1) RETURNING id"#)
+                        .bind(ids)
+                        .fetch_all(&mut *conn)
+                        .await
+                        .map_err(|e| map_db_error("person", e))?;
+                    Ok::<Vec<Uuid>, Box<dyn Error + Send + Sync>>(rows.iter().map(|row| row.get("id")).collect())
+                })
+                .await??
+        };
+
+        if repo.cache_policy.maintains_cache() {
             let cache = repo.person_idx_cache.read().await;
-            for id in ids {
+            for id in &deleted {
+                // No replayed notification of the row brings it back
+                repo.cache_versions.admit(*id, CacheVersions::DELETED);
                 cache.remove(id);
             }
+            if let Some(bound) = &repo.cache_bound {
+                bound.record_removal(&deleted);
+            }
         }
-        
-        Ok(deleted_count)
+
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for PersonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "person", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids, audit_log_id).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for PersonRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids, audit_log_id).await
     }
 }
@@ -128,6 +115,7 @@ mod tests {
     use crate::test_helper::setup_test_context;
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::repository::delete_batch::DeleteBatch;
+    use business_core_db::repository::delete_batch_detailed::DeleteBatchDetailed;
     use uuid::Uuid;
     use business_core_db::models::person::person::PersonType;
     use crate::repository::person::person_repository::test_utils::create_test_person;
@@ -193,4 +181,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_batch_detailed_with_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let persons = vec![
+            create_test_person("Detailed Delete 1", PersonType::Natural),
+            create_test_person("Detailed Delete 2", PersonType::Natural),
+        ];
+        let saved = person_repo.create_batch(persons, Some(audit_log.id)).await?;
+
+        let missing_id = Uuid::new_v4();
+        let ids = vec![saved[0].id, missing_id, saved[1].id];
+
+        // # Attention, we are deleting in the same transaction. This will not happen in a real scenario
+        // in order to prevent duplicate key, we will create a new audit log for the delete.
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        let outcome = person_repo.delete_batch_detailed(&ids, Some(delete_audit_log.id)).await?;
+
+        assert_eq!(outcome.deleted.len(), 2);
+        assert!(outcome.deleted.contains(&saved[0].id));
+        assert!(outcome.deleted.contains(&saved[1].id));
+        assert_eq!(outcome.not_found, vec![missing_id]);
+
+        let cache = person_repo.person_idx_cache.read().await;
+        for person in &saved {
+            assert!(!cache.contains_primary(&person.id), "Deleted person should be removed from cache");
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::person::person::PersonIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -9,6 +10,9 @@ impl PersonRepositoryImpl {
         &self,
         duplicate_of_person_id: Uuid,
     ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        if !self.serves_key_from_cache("duplicate_of_person_id", duplicate_of_person_id.into()) {
+            return self.find_idx_by_column("duplicate_of_person_id", duplicate_of_person_id).await;
+        }
         let cache = self.person_idx_cache.read().await;
         let items = cache.get_by_uuid_index("duplicate_of_person_id", &duplicate_of_person_id);
         Ok(items)
@@ -0,0 +1,164 @@
+use std::error::Error;
+use business_core_db::models::person::person::PersonModel;
+
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Find the person with the given external identifier
+    ///
+    /// Candidates are looked up by `external_identifier_hash` and then checked against the
+    /// stored identifier, so a hash collision never returns the wrong person.
+    /// The repository's hash version is tried first, then its fallback for rows not yet
+    /// migrated; only index rows of the version used are considered.
+    /// External identifiers are unique; more than one true match is an error.
+    pub async fn find_by_external_identifier(
+        &self,
+        external_id: &str,
+    ) -> Result<Option<PersonModel>, Box<dyn Error + Send + Sync>> {
+        for hash_version in [self.hash_version, self.hash_version.fallback()] {
+            let external_identifier_hash = hash_version.hash(&external_id)?;
+            let mut matches = self
+                .find_matching_by_i64_key(
+                    "external_identifier_hash",
+                    external_identifier_hash,
+                    |idx| idx.hash_version == hash_version,
+                    |person: &PersonModel| person.external_identifier.as_deref() == Some(external_id),
+                )
+                .await?;
+            if matches.len() > 1 {
+                return Err(format!("Multiple persons found with external identifier {external_id}").into());
+            }
+            if let Some(found) = matches.pop() {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::models::index_aware::IndexAware;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::{hash_as_i64, HashVersion};
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+
+    #[tokio::test]
+    async fn test_find_by_external_identifier() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let external_id = format!("EXT-{}", random(8));
+        let mut person = create_test_person("Grace Hopper");
+        person.external_identifier = Some(heapless::String::try_from(external_id.as_str()).unwrap());
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let found = person_repo.find_by_external_identifier(&external_id).await?;
+
+        let found = found.ok_or("Person not found")?;
+        assert_eq!(found.id, saved[0].id);
+        assert_eq!(found.external_identifier.as_deref(), Some(external_id.as_str()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_external_identifier_non_existing() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let found = person_repo.find_by_external_identifier(&format!("EXT-{}", random(8))).await?;
+
+        assert!(found.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_external_identifier_ignores_hash_collision() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let external_id = format!("EXT-{}", random(8));
+        let other_external_id = format!("EXT-{}", random(8));
+        let mut person = create_test_person("Colliding Person");
+        person.external_identifier = Some(heapless::String::try_from(other_external_id.as_str()).unwrap());
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // Give the other person's index entry the hash of the searched identifier
+        let mut colliding_idx = saved[0].to_index();
+        colliding_idx.external_identifier_hash = Some(hash_as_i64(&external_id.as_str())?);
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET external_identifier_hash = $2 WHERE id = $1")
+                .bind(colliding_idx.id)
+                .bind(colliding_idx.external_identifier_hash)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        {
+            let cache = person_repo.person_idx_cache.read().await;
+            cache.remove(&colliding_idx.id);
+            cache.add(colliding_idx);
+        }
+
+        let found = person_repo.find_by_external_identifier(&external_id).await?;
+
+        assert!(found.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_external_identifier_falls_back_to_other_hash_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        assert_eq!(person_repo.hash_version, HashVersion::V1);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let external_id = format!("EXT-{}", random(8));
+        let mut person = create_test_person("Migrated Person");
+        person.external_identifier = Some(heapless::String::try_from(external_id.as_str()).unwrap());
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // Rewrite the index row as a V2 row, as a migration would
+        let migrated_idx = saved[0].to_index_with_hash_version(HashVersion::V2);
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET external_identifier_hash = $2, id_number_hash = $3, hash_version = $4 WHERE id = $1")
+                .bind(migrated_idx.id)
+                .bind(migrated_idx.external_identifier_hash)
+                .bind(migrated_idx.id_number_hash)
+                .bind(migrated_idx.hash_version)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        {
+            let cache = person_repo.person_idx_cache.read().await;
+            cache.remove(&migrated_idx.id);
+            cache.add(migrated_idx);
+        }
+
+        let found = person_repo.find_by_external_identifier(&external_id).await?;
+
+        assert_eq!(found.ok_or("Person not found")?.id, saved[0].id);
+
+        Ok(())
+    }
+}
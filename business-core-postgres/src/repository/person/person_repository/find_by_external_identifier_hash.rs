@@ -1,6 +1,8 @@
 use std::error::Error;
 use business_core_db::models::person::person::PersonIdxModel;
 
+use crate::repository::cache_health::Freshness;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -8,9 +10,18 @@ impl PersonRepositoryImpl {
         &self,
         external_identifier_hash: i64,
     ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.person_idx_cache.read().await;
-        let items = cache.get_by_i64_index("external_identifier_hash", &external_identifier_hash);
-        Ok(items)
+        self.find_by_external_identifier_hash_with_freshness(external_identifier_hash, Freshness::CacheOk)
+            .await
+    }
+
+    /// Same as `find_by_external_identifier_hash`, as fresh as `freshness` asks
+    pub async fn find_by_external_identifier_hash_with_freshness(
+        &self,
+        external_identifier_hash: i64,
+        freshness: Freshness,
+    ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_i64_key_with_freshness("external_identifier_hash", external_identifier_hash, freshness)
+            .await
     }
 }
 
@@ -19,6 +30,7 @@ mod tests {
     use crate::test_helper::{random, setup_test_context};
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::utils::hash_as_i64;
+    use crate::repository::cache_health::Freshness;
     use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
 
     #[tokio::test]
@@ -153,4 +165,62 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_find_by_external_identifier_hash_freshness_when_degraded() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let external_id = format!("EMP-{}", random(5));
+        let cached_hash = hash_as_i64(&external_id).unwrap();
+        let mut person = create_test_person("Freshness");
+        person.external_identifier = Some(heapless::String::try_from(external_id.as_str()).unwrap());
+        let person_id = person_repo.create_batch(vec![person], Some(audit_log.id)).await?[0].id;
+
+        // A change the cache never heard of, as when its notification is lost
+        let fresh_hash = hash_as_i64(&format!("EMP-{}", random(5))).unwrap();
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET external_identifier_hash = $1 WHERE id = $2")
+                .bind(fresh_hash)
+                .bind(person_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+
+        // Healthy, a fresh answer is still served from the cache
+        let found = person_repo
+            .find_by_external_identifier_hash_with_freshness(cached_hash, Freshness::RequireFresh)
+            .await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![person_id]);
+
+        person_repo.cache_health.mark_degraded("listener connection lost");
+
+        // CacheOk serves the stale value
+        let found = person_repo.find_by_external_identifier_hash(cached_hash).await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![person_id]);
+
+        // RequireFresh hits SQL
+        let found = person_repo
+            .find_by_external_identifier_hash_with_freshness(cached_hash, Freshness::RequireFresh)
+            .await?;
+        assert!(found.is_empty());
+        let found = person_repo
+            .find_by_external_identifier_hash_with_freshness(fresh_hash, Freshness::RequireFresh)
+            .await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![person_id]);
+
+        // CacheOnly never does
+        let found = person_repo
+            .find_by_external_identifier_hash_with_freshness(fresh_hash, Freshness::CacheOnly)
+            .await?;
+        assert!(found.is_empty());
+
+        Ok(())
+    }
+}
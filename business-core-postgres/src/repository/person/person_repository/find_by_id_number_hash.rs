@@ -0,0 +1,46 @@
+use std::error::Error;
+use business_core_db::models::person::person::PersonIdxModel;
+
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    pub async fn find_by_id_number_hash(
+        &self,
+        id_number_hash: i64,
+    ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_i64_key("id_number_hash", id_number_hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+
+    #[tokio::test]
+    async fn test_find_by_id_number_hash() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let id_number = format!("ID-{}", random(8));
+        let expected_hash = hash_as_i64(&id_number.as_str()).unwrap();
+        let mut person = create_test_person("Id Number Person");
+        person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let found = person_repo.find_by_id_number_hash(expected_hash).await?;
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+        assert!(person_repo.find_by_id_number_hash(expected_hash.wrapping_add(1)).await?.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::error::Error;
+use business_core_db::models::person::person::{IdentityType, PersonModel};
+
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Find the person holding the identity document `id_type` / `id_number`
+    ///
+    /// Candidates are looked up by `id_number_hash` and then checked against the stored
+    /// type and number, so the same number under another identity type, or a hash
+    /// collision, never matches. Hash versions are tried as in
+    /// `find_by_external_identifier`. A number is unique per identity type; more than
+    /// one match is an error.
+    pub async fn find_by_id_type_and_number(
+        &self,
+        id_type: IdentityType,
+        id_number: &str,
+    ) -> Result<Option<PersonModel>, Box<dyn Error + Send + Sync>> {
+        for hash_version in [self.hash_version, self.hash_version.fallback()] {
+            let id_number_hash = hash_version.hash(&id_number)?;
+            let mut matches = self
+                .find_matching_by_i64_key(
+                    "id_number_hash",
+                    id_number_hash,
+                    |idx| idx.hash_version == hash_version,
+                    |person: &PersonModel| person.id_type == id_type && person.id_number.as_str() == id_number,
+                )
+                .await?;
+            if matches.len() > 1 {
+                return Err(format!("Multiple persons found with {id_type} {id_number}").into());
+            }
+            if let Some(found) = matches.pop() {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::models::person::person::IdentityType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+
+    #[tokio::test]
+    async fn test_find_by_id_type_and_number() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let id_number = format!("P{}", random(8));
+        let mut person = create_test_person("Passport Holder");
+        person.id_type = IdentityType::Passport;
+        person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let found = person_repo
+            .find_by_id_type_and_number(IdentityType::Passport, &id_number)
+            .await?
+            .ok_or("Person not found")?;
+        assert_eq!(found.id, saved[0].id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_type_and_number_other_type() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let id_number = format!("N{}", random(8));
+        let mut person = create_test_person("National Id Holder");
+        person.id_type = IdentityType::NationalId;
+        person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        // Same number, different identity type
+        let found = person_repo
+            .find_by_id_type_and_number(IdentityType::Passport, &id_number)
+            .await?;
+        assert!(found.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_by_id_type_and_number_rejects_duplicates() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let id_number = format!("P{}", random(8));
+        let persons = (0..2)
+            .map(|i| {
+                let mut person = create_test_person(&format!("Passport Holder {i}"));
+                person.id_type = IdentityType::Passport;
+                person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+                person
+            })
+            .collect();
+        person_repo.create_batch(persons, Some(audit_log.id)).await?;
+
+        let result = person_repo
+            .find_by_id_type_and_number(IdentityType::Passport, &id_number)
+            .await;
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
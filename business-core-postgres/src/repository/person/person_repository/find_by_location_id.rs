@@ -0,0 +1,89 @@
+use business_core_db::models::person::person::PersonModel;
+use business_core_db::repository::load_batch::LoadBatch;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Persons at the given location, for correcting or merging the location
+    ///
+    /// Ids come from the `location_id` key of the index cache, or from person_idx
+    /// when the repository does not serve finders from its cache. Persons without a
+    /// location are never returned.
+    pub async fn find_by_location_id(
+        &self,
+        location_id: Uuid,
+    ) -> Result<Vec<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let items = if self.serves_key_from_cache("location_id", location_id.into()) {
+            let cache = self.person_idx_cache.read().await;
+            cache.get_by_uuid_index("location_id", &location_id)
+        } else {
+            self.find_idx_by_column("location_id", location_id).await?
+        };
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        Ok(self.load_batch(&ids).await?.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_find_by_location_id() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let sql_repo = PersonRepositoryImpl::new_with_cache_policy(
+            person_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let location_id = Uuid::new_v4();
+        let mut first = create_test_person("Resident One");
+        first.location_id = Some(location_id);
+        let mut second = create_test_person("Resident Two");
+        second.location_id = Some(location_id);
+        let mut elsewhere = create_test_person("Resident Elsewhere");
+        elsewhere.location_id = Some(Uuid::new_v4());
+        let mut homeless = create_test_person("No Location");
+        homeless.location_id = None;
+        let saved = person_repo
+            .create_batch(vec![first, second, elsewhere, homeless], Some(audit_log.id))
+            .await?;
+
+        let expected: HashSet<Uuid> = saved[..2].iter().map(|person| person.id).collect();
+        for repo in [person_repo.as_ref(), &sql_repo] {
+            let found = repo.find_by_location_id(location_id).await?;
+            assert_eq!(found.iter().map(|person| person.id).collect::<HashSet<_>>(), expected);
+            assert!(found.iter().all(|person| person.location_id == Some(location_id)));
+        }
+
+        // Moving a person away updates the index
+        let mut moved = saved[1].clone();
+        moved.location_id = None;
+        person_repo.update_batch(vec![moved], Some(audit_log.id)).await?;
+        for repo in [person_repo.as_ref(), &sql_repo] {
+            let found = repo.find_by_location_id(location_id).await?;
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].id, saved[0].id);
+        }
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use uuid::Uuid;
 use business_core_db::models::person::person::PersonIdxModel;
 use business_core_db::repository::pagination::{Page, PageRequest};
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -11,8 +12,12 @@ impl PersonRepositoryImpl {
         organization_person_id: Uuid,
         page: PageRequest,
     ) -> Result<Page<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.person_idx_cache.read().await;
-        let all_items = cache.get_by_uuid_index("organization_person_id", &organization_person_id);
+        let all_items = if self.serves_key_from_cache("organization_person_id", organization_person_id.into()) {
+            let cache = self.person_idx_cache.read().await;
+            cache.get_by_uuid_index("organization_person_id", &organization_person_id)
+        } else {
+            self.find_idx_by_column("organization_person_id", organization_person_id).await?
+        };
         let total = all_items.len();
         
         // Apply pagination
@@ -0,0 +1,100 @@
+use std::error::Error;
+use uuid::Uuid;
+use business_core_db::models::person::person::PersonIdxModel;
+use crate::repository::cache_capacity::{CacheBound, KeyValue};
+use crate::repository::find_by_i64_key::FindByI64Key;
+use crate::utils::TryFromRow;
+
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Find the persons belonging to any of the given organizations
+    ///
+    /// Unpaged batch variant of `find_by_organization_person_id`, used to walk
+    /// organization hierarchies one level at a time. A bounded cache answers when all
+    /// organizations are complete in it, see `CacheCapacity`.
+    pub async fn find_by_organization_person_ids(
+        &self,
+        organization_person_ids: &[Uuid],
+    ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        if organization_person_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let from_cache = organization_person_ids
+            .iter()
+            .all(|id| self.serves_key_from_cache("organization_person_id", KeyValue::Uuid(*id)));
+        if from_cache {
+            let cache = self.person_idx_cache.read().await;
+            let mut items = Vec::new();
+            for organization_person_id in organization_person_ids {
+                items.extend(cache.get_by_uuid_index("organization_person_id", organization_person_id));
+            }
+            return Ok(items);
+        }
+
+        let epoch = self.cache_bound.as_ref().map(CacheBound::epoch);
+        let rows = {
+            let mut tx = self.operation_timeout.lock("person", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("SELECT * FROM person_idx WHERE organization_person_id = ANY($1)")
+                .bind(organization_person_ids)
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(PersonIdxModel::try_from_row(&row)?);
+        }
+        self.populate_cache(&items).await;
+        if let (true, Some(bound), Some(epoch)) = (self.cache_policy.maintains_cache(), &self.cache_bound, epoch) {
+            for id in organization_person_ids {
+                bound.record_complete("organization_person_id", KeyValue::Uuid(*id), epoch);
+            }
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+
+    #[tokio::test]
+    async fn test_find_by_organization_person_ids() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let org_1 = create_test_person("organization-1");
+        let org_2 = create_test_person("organization-2");
+        let org_ids = vec![org_1.id, org_2.id];
+        person_repo.create_batch(vec![org_1, org_2], Some(audit_log.id)).await?;
+
+        let mut employees = Vec::new();
+        for (i, org_id) in org_ids.iter().enumerate() {
+            let mut person = create_test_person(&format!("employee-{i}"));
+            person.organization_person_id = Some(*org_id);
+            employees.push(person);
+        }
+        let saved = person_repo.create_batch(employees, Some(audit_log.id)).await?;
+
+        let found = person_repo.find_by_organization_person_ids(&org_ids).await?;
+        assert_eq!(found.len(), 2);
+        for saved_person in &saved {
+            assert!(found.iter().any(|idx| idx.id == saved_person.id));
+        }
+
+        let found = person_repo.find_by_organization_person_ids(&org_ids[..1]).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+
+        Ok(())
+    }
+}
@@ -19,9 +19,11 @@ impl PersonRepositoryImpl {
         
         let query = r#"SELECT * FROM person WHERE id = ANY($1)"#;
         let rows = {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("person", &repo.executor.tx).await?;
             if let Some(transaction) = tx.as_mut() {
-                sqlx::query(query).bind(ids).fetch_all(&mut **transaction).await?
+                repo.operation_timeout
+                    .query("person", sqlx::query(query).bind(ids).fetch_all(&mut **transaction))
+                    .await??
             } else {
                 return Err("Transaction has been consumed".into());
             }
@@ -39,10 +41,16 @@ impl PersonRepositoryImpl {
         }
         Ok(result)
     }
+
+    /// Load a single item, `None` when no item has this id
+    pub async fn load(&self, id: Uuid) -> Result<Option<PersonModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_batch_impl(self, &[id]).await?.into_iter().next().flatten())
+    }
 }
 
 #[async_trait]
 impl LoadBatch<Postgres, PersonModel> for PersonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "person", count = ids.len())))]
     async fn load_batch(&self, ids: &[Uuid]) -> Result<Vec<Option<PersonModel>>, Box<dyn Error + Send + Sync>> {
         Self::load_batch_impl(self, ids).await
     }
@@ -119,4 +127,24 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_load() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person = create_test_person("Single Person", PersonType::Natural);
+        let saved = person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let loaded = person_repo.load(saved[0].id).await?;
+        assert_eq!(loaded.map(|person| person.id), Some(saved[0].id));
+
+        assert!(person_repo.load(Uuid::new_v4()).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,13 +1,22 @@
 pub mod repo_impl;
+pub mod columns;
 pub mod create_batch;
+pub mod create_batch_dry_run;
+pub mod try_create_batch;
 pub mod load_batch;
 pub mod load_audits;
 pub mod update_batch;
 pub mod delete_batch;
 pub mod exist_by_ids;
+pub mod find_by_external_identifier;
 pub mod find_by_external_identifier_hash;
+pub mod find_by_id_number_hash;
+pub mod find_by_id_type_and_number;
 pub mod find_by_organization_person_id;
+pub mod find_by_organization_person_ids;
 pub mod find_by_duplicate_of_person_id;
+pub mod find_by_location_id;
+pub mod count_by_key;
 #[cfg(test)]
 pub mod test_utils;
 
@@ -1,4 +1,10 @@
 use business_core_db::models::person::person::{PersonIdxModel, PersonModel};
+use business_core_db::utils::HashVersion;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_health::CacheHealth;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::operation_timeout::OperationTimeout;
 use crate::utils::{get_heapless_string, get_optional_heapless_string, TryFromRow};
 use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use postgres_index_cache::TransactionAwareIdxModelCache;
@@ -7,32 +13,85 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
 
 pub struct PersonRepositoryImpl {
     pub executor: Executor,
     pub person_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<PersonIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub person_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Hash version used to write person_idx rows and tried first by the finders
+    pub hash_version: HashVersion,
+    /// Bounds on the transaction lock wait and the SQL of the batch operations
+    pub operation_timeout: OperationTimeout,
+    /// Bound on the person_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
+    /// Health of the person_idx notifications, consulted by `Freshness::RequireFresh`
+    pub cache_health: CacheHealth,
+    /// Versions written to the person_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
 }
 
 impl PersonRepositoryImpl {
     pub fn new(
         executor: Executor,
         person_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, person_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        person_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>>,
+        cache_policy: CachePolicy,
+    ) -> Self {
+        Self::new_with_hash_version(executor, person_idx_cache, cache_policy, HashVersion::default())
+    }
+
+    pub fn new_with_hash_version(
+        executor: Executor,
+        person_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>>,
+        cache_policy: CachePolicy,
+        hash_version: HashVersion,
     ) -> Self {
         Self {
             executor,
+            person_idx_shared_cache: person_idx_cache.clone(),
             person_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 person_idx_cache,
             ))),
+            cache_policy,
+            hash_version,
+            operation_timeout: OperationTimeout::default(),
+            cache_bound: None,
+            cache_health: CacheHealth::default(),
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
         }
     }
 
+    /// Whether finders can be answered from the cache alone
+    ///
+    /// A bounded cache may have evicted matching entries, so its finders go to SQL
+    /// unless `serves_key_from_cache` knows the key value complete.
+    pub fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
     pub async fn load_all_person_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<PersonIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM person_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("person", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -89,6 +148,8 @@ impl TryFromRow<PgRow> for PersonIdxModel {
             organization_person_id: row.try_get("organization_person_id").ok(),
             duplicate_of_person_id: row.try_get("duplicate_of_person_id").ok(),
             id_number_hash: row.try_get("id_number_hash").ok(),
+            location_id: row.try_get("location_id").ok(),
+            hash_version: HashVersion::try_from(row.get::<i16, _>("hash_version"))?,
         })
     }
 }
@@ -96,10 +157,86 @@ impl TryFromRow<PgRow> for PersonIdxModel {
 #[async_trait]
 impl TransactionAware for PersonRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.person_idx_cache.read().await.on_commit().await
+        self.person_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.person_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.person_idx_cache.read().await.on_rollback().await
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for PersonRepositoryImpl {
+    type Idx = PersonIdxModel;
+
+    const ENTITY: &'static str = "person";
+    const I64_KEYS: &'static [&'static str] = &["external_identifier_hash", "id_number_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<PersonIdxModel>> {
+        &self.person_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        PersonRepositoryImpl::serves_from_cache(self)
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_health(&self) -> Option<&CacheHealth> {
+        Some(&self.cache_health)
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for PersonRepositoryImpl {
+    type Idx = PersonIdxModel;
+
+    const ENTITY: &'static str = "person";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<PersonIdxModel>>> {
+        &self.person_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_person_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
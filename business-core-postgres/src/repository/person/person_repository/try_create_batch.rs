@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use business_core_db::models::person::person::PersonModel;
+use business_core_db::repository::try_create_batch::{BatchResult, ItemOutcome, TryCreateBatch};
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::{map_db_error, recoverable_outcome};
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::PersonRepositoryImpl;
+
+impl PersonRepositoryImpl {
+    /// Insert the items one by one, each behind a savepoint
+    ///
+    /// An item failing with a recoverable error is rolled back to its savepoint, which
+    /// keeps the session transaction usable for the next items.
+    pub(super) async fn try_create_batch_impl(
+        &self,
+        items: Vec<PersonModel>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<BatchResult<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let audit_log_id = audit_log_id.ok_or("audit_log_id is required for PersonModel")?;
+        let mut outcomes = Vec::with_capacity(items.len());
+        let mut indices = Vec::new();
+
+        {
+            let mut tx = self.operation_timeout.lock("person", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            for item in items {
+                sqlx::query("SAVEPOINT try_create_person")
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("person", e))?;
+                let inserted = self
+                    .operation_timeout
+                    .query(
+                        "person",
+                        Self::insert_in_connection(&mut **transaction, vec![item], audit_log_id, self.hash_version),
+                    )
+                    .await?;
+                match inserted {
+                    Ok((mut saved, idx)) => {
+                        sqlx::query("RELEASE SAVEPOINT try_create_person")
+                            .execute(&mut **transaction)
+                            .await
+                            .map_err(|e| map_db_error("person", e))?;
+                        indices.extend(idx);
+                        outcomes.push(ItemOutcome::Created(saved.remove(0)));
+                    }
+                    Err(error) => {
+                        let outcome = recoverable_outcome(error.as_ref()).ok_or(error)?;
+                        sqlx::query("ROLLBACK TO SAVEPOINT try_create_person")
+                            .execute(&mut **transaction)
+                            .await
+                            .map_err(|e| map_db_error("person", e))?;
+                        outcomes.push(outcome);
+                    }
+                }
+            }
+        }
+
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = self.person_idx_cache.read().await;
+                for idx in indices {
+                    if self.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
+            }
+            self.record_cache_use(&ids);
+        }
+
+        Ok(BatchResult { outcomes })
+    }
+}
+
+#[async_trait]
+impl TryCreateBatch<Postgres, PersonModel> for PersonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "person", count = items.len())))]
+    async fn try_create_batch(
+        &self,
+        items: Vec<PersonModel>,
+        audit_log_id: Option<Uuid>,
+    ) -> Result<BatchResult<PersonModel>, Box<dyn Error + Send + Sync>> {
+        self.try_create_batch_impl(items, audit_log_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use business_core_db::repository::try_create_batch::{ItemOutcome, TryCreateBatch};
+
+    #[tokio::test]
+    async fn test_try_create_batch_skips_duplicate() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let existing = create_test_person("Already Imported");
+        let existing_id = existing.id;
+        person_repo.create_batch(vec![existing.clone()], Some(audit_log.id)).await?;
+
+        let import_audit_log = create_test_audit_log();
+        audit_log_repo.create(&import_audit_log).await?;
+        let fresh = create_test_person("Newly Imported");
+        let fresh_id = fresh.id;
+        let result = person_repo
+            .try_create_batch(vec![existing, fresh], Some(import_audit_log.id))
+            .await?;
+
+        assert_eq!(result.outcomes.len(), 2);
+        assert!(matches!(result.outcomes[0], ItemOutcome::SkippedDuplicate));
+        assert!(matches!(&result.outcomes[1], ItemOutcome::Created(person) if person.id == fresh_id));
+        assert_eq!(result.skipped_count(), 1);
+        assert!(result.failures().is_empty());
+        assert!(!result.is_complete());
+        assert_eq!(result.created().map(|person| person.id).collect::<Vec<_>>(), vec![fresh_id]);
+
+        // The transaction is still usable, the duplicate kept its row and left no audit row
+        let loaded = person_repo.load_batch(&[existing_id, fresh_id]).await?;
+        assert_eq!(loaded[0].as_ref().and_then(|person| person.audit_log_id), Some(audit_log.id));
+        assert_eq!(loaded[1].as_ref().and_then(|person| person.audit_log_id), Some(import_audit_log.id));
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let skipped_audits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM person_audit WHERE id = $1 AND audit_log_id = $2")
+                .bind(existing_id)
+                .bind(import_audit_log.id)
+                .fetch_one(&mut **transaction)
+                .await?;
+        assert_eq!(skipped_audits, 0);
+
+        Ok(())
+    }
+}
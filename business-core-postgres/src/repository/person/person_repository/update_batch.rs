@@ -1,15 +1,22 @@
 use async_trait::async_trait;
 use business_core_db::models::{
     audit::{AuditLinkModel, EntityType},
-    person::person::PersonModel,
+    person::{
+        common_enums::PersonStatus,
+        person::{PersonIdxModel, PersonModel},
+    },
 };
-use business_core_db::models::index_aware::IndexAware;
 use business_core_db::repository::update_batch::UpdateBatch;
-use sqlx::Postgres;
+use sqlx::{PgConnection, Postgres, Row};
 use std::error::Error;
+use crate::error::{map_db_error, RepositoryError};
+use std::collections::HashMap;
 use uuid::Uuid;
-use business_core_db::utils::hash_as_i64;
+use business_core_db::utils::{hash_as_i64, HashVersion};
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
+use crate::repository::find_by_i64_key::FindByI64Key;
+use super::columns::{PERSON_COLUMNS, PERSON_IDX_COLUMNS, PERSON_IDX_VERSIONED_UPDATE, PERSON_SQL};
 use super::repo_impl::PersonRepositoryImpl;
 
 impl PersonRepositoryImpl {
@@ -23,179 +30,181 @@ impl PersonRepositoryImpl {
             return Ok(Vec::new());
         }
 
-        let mut updated_items = Vec::new();
-        let mut indices_to_update = Vec::new();
-        
-        {
-            let mut tx = self.executor.tx.lock().await;
+        let (updated_items, indices_to_update) = {
+            let mut tx = self.operation_timeout.lock("person", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
-            
-            for mut item in items {
-                let previous_hash = item.hash;
-                let previous_audit_log_id = item.audit_log_id.ok_or("Entity must have audit_log_id for update")?;
-
-                let mut entity_for_hashing = item.clone();
-                entity_for_hashing.hash = 0;
-                let computed_hash = hash_as_i64(&entity_for_hashing)?;
-
-                if computed_hash == previous_hash {
-                    updated_items.push(item);
-                    continue;
+            self.operation_timeout
+                .query("person", Self::check_status_transitions(&mut **transaction, &items))
+                .await??;
+            self.operation_timeout
+                .query("person", Self::update_in_connection(&mut **transaction, items, audit_log_id, self.hash_version))
+                .await??
+        };
+        
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices_to_update.iter().map(|(idx, _)| idx.id).collect();
+            {
+                let cache = self.person_idx_cache.read().await;
+                for (idx, version) in indices_to_update {
+                    // A notification already brought a newer version of the row
+                    if !self.cache_versions.admit(idx.id, version) {
+                        continue;
+                    }
+                    cache.remove(&idx.id);
+                    cache.add(idx);
                 }
+            }
+            self.record_cache_use(&ids);
+        }
 
-                item.antecedent_hash = previous_hash;
-                item.antecedent_audit_log_id = previous_audit_log_id;
-                item.audit_log_id = Some(audit_log_id);
-                item.hash = 0;
-
-                let new_computed_hash = hash_as_i64(&item)?;
-                item.hash = new_computed_hash;
-
-                sqlx::query(
-                    r#"
-                    INSERT INTO person_audit
-                    (id, person_type, risk_rating, status, display_name, external_identifier, id_type, id_number, entity_reference_count, organization_person_id, messaging_info1, messaging_info2, messaging_info3, messaging_info4, messaging_info5, department, location_id, duplicate_of_person_id, last_activity_log, last_compliance_status, last_document, last_portfolio, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_type)
-                .bind(item.risk_rating)
-                .bind(item.status)
-                .bind(item.display_name.as_str())
-                .bind(item.external_identifier.as_deref())
-                .bind(item.id_type)
-                .bind(item.id_number.as_str())
-                .bind(item.entity_reference_count)
-                .bind(item.organization_person_id)
-                .bind(item.messaging_info1.as_deref())
-                .bind(item.messaging_info2.as_deref())
-                .bind(item.messaging_info3.as_deref())
-                .bind(item.messaging_info4.as_deref())
-                .bind(item.messaging_info5.as_deref())
-                .bind(item.department.as_deref())
-                .bind(item.location_id)
-                .bind(item.duplicate_of_person_id)
-                .bind(item.last_activity_log)
-                .bind(item.last_compliance_status)
-                .bind(item.last_document)
-                .bind(item.last_portfolio)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .execute(&mut **transaction)
-                .await?;
-
-                let rows_affected = sqlx::query(
-                    r#"
-                    UPDATE person SET
-                    person_type = $2, risk_rating = $3, status = $4,
-                    display_name = $5, external_identifier = $6,
-                    id_type = $7, id_number = $8,
-                    entity_reference_count = $9, organization_person_id = $10,
-                    messaging_info1 = $11, messaging_info2 = $12, messaging_info3 = $13,
-                    messaging_info4 = $14, messaging_info5 = $15, department = $16,
-                    location_id = $17, duplicate_of_person_id = $18,
-                    last_activity_log = $19, last_compliance_status = $20,
-                    last_document = $21, last_portfolio = $22,
-                    antecedent_hash = $23, antecedent_audit_log_id = $24,
-                    hash = $25, audit_log_id = $26
-                    WHERE id = $1 AND hash = $27 AND audit_log_id = $28
-                    "#,
-                )
-                .bind(item.id)
-                .bind(item.person_type)
-                .bind(item.risk_rating)
-                .bind(item.status)
-                .bind(item.display_name.as_str())
-                .bind(item.external_identifier.as_deref())
-                .bind(item.id_type)
-                .bind(item.id_number.as_str())
-                .bind(item.entity_reference_count)
-                .bind(item.organization_person_id)
-                .bind(item.messaging_info1.as_deref())
-                .bind(item.messaging_info2.as_deref())
-                .bind(item.messaging_info3.as_deref())
-                .bind(item.messaging_info4.as_deref())
-                .bind(item.messaging_info5.as_deref())
-                .bind(item.department.as_deref())
-                .bind(item.location_id)
-                .bind(item.duplicate_of_person_id)
-                .bind(item.last_activity_log)
-                .bind(item.last_compliance_status)
-                .bind(item.last_document)
-                .bind(item.last_portfolio)
-                .bind(item.antecedent_hash)
-                .bind(item.antecedent_audit_log_id)
-                .bind(item.hash)
-                .bind(item.audit_log_id)
-                .bind(previous_hash)
-                .bind(previous_audit_log_id)
-                .execute(&mut **transaction)
+        Ok(updated_items)
+    }
+
+    /// Reject status changes that `PersonStatus::can_transition_to` does not allow
+    ///
+    /// The stored status is read on `conn`; items without a stored row are left to the update.
+    async fn check_status_transitions(
+        conn: &mut PgConnection,
+        items: &[PersonModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        let stored: HashMap<Uuid, PersonStatus> =
+            sqlx::query_as::<_, (Uuid, PersonStatus)>("SELECT id, status FROM person WHERE id = ANY($1)")
+                .bind(&ids)
+                .fetch_all(&mut *conn)
                 .await?
-                .rows_affected();
+                .into_iter()
+                .collect();
 
-                if rows_affected == 0 {
-                    return Err("Concurrent update detected".into());
+        for item in items {
+            let Some(from) = stored.get(&item.id) else {
+                continue;
+            };
+            if !from.can_transition_to(item.status) {
+                return Err(RepositoryError::InvalidTransition {
+                    entity: "person".to_string(),
+                    id: item.id,
+                    from: from.to_string(),
+                    to: item.status.to_string(),
                 }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the person updates on an already locked connection
+    ///
+    /// Shared with repositories that have to advance a person's audit chain inside
+    /// their own transaction. The person_idx rows are rewritten with `hash_version`.
+    /// Returns the updated items and the index models the caller has to apply
+    /// to the person_idx cache, each with the version its row was advanced to.
+    pub(crate) async fn update_in_connection(
+        conn: &mut PgConnection,
+        items: Vec<PersonModel>,
+        audit_log_id: Uuid,
+        hash_version: HashVersion,
+    ) -> Result<(Vec<PersonModel>, Vec<(PersonIdxModel, u64)>), Box<dyn Error + Send + Sync>> {
+        let mut updated_items = Vec::new();
+        let mut indices_to_update = Vec::new();
+
+        for mut item in items {
+            let previous_hash = item.hash;
+            let previous_audit_log_id = item.audit_log_id.ok_or("Entity must have audit_log_id for update")?;
 
-                let idx = item.to_index();
-                sqlx::query(
-                    r#"
-                    UPDATE person_idx SET
-                    external_identifier_hash = $2,
-                    organization_person_id = $3,
-                    duplicate_of_person_id = $4,
-                    id_number_hash = $5
-                    WHERE id = $1
-                    "#,
-                )
-                .bind(idx.id)
-                .bind(idx.external_identifier_hash)
-                .bind(idx.organization_person_id)
-                .bind(idx.duplicate_of_person_id)
-                .bind(idx.id_number_hash)
-                .execute(&mut **transaction)
-                .await?;
-
-                // Create audit link
-                let audit_link = AuditLinkModel {
-                    audit_log_id,
-                    entity_id: item.id,
-                    entity_type: EntityType::Person,
-                };
-                sqlx::query(
-                    r#"
-                    INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                    VALUES ($1, $2, $3)
-                    "#,
-                )
-                .bind(audit_link.audit_log_id)
-                .bind(audit_link.entity_id)
-                .bind(audit_link.entity_type)
-                .execute(&mut **transaction)
-                .await?;
-
-                indices_to_update.push((item.id, idx));
+            let mut entity_for_hashing = item.clone();
+            entity_for_hashing.hash = 0;
+            let computed_hash = hash_as_i64(&entity_for_hashing)?;
+
+            if computed_hash == previous_hash {
                 updated_items.push(item);
+                continue;
             }
-        }
-        
-        {
-            let cache = self.person_idx_cache.read().await;
-            for (id, idx) in indices_to_update {
-                cache.remove(&id);
-                cache.add(idx);
+
+            item.antecedent_hash = previous_hash;
+            item.antecedent_audit_log_id = previous_audit_log_id;
+            item.audit_log_id = Some(audit_log_id);
+            item.hash = 0;
+
+            let new_computed_hash = hash_as_i64(&item)?;
+            item.hash = new_computed_hash;
+
+            PERSON_COLUMNS
+                .bind(sqlx::query(&PERSON_SQL.insert_audit), &item)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?;
+
+            let rows_affected = PERSON_COLUMNS
+                .bind(sqlx::query(&PERSON_SQL.update), &item)
+                .bind(previous_hash)
+                .bind(previous_audit_log_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?
+                .rows_affected();
+
+            if rows_affected == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(entity = "person", id = %item.id, "Concurrent update detected");
+                return Err("Concurrent update detected".into());
             }
+
+            let idx = item.to_index_with_hash_version(hash_version);
+            let version: i64 = PERSON_IDX_COLUMNS
+                .bind(sqlx::query(&PERSON_IDX_VERSIONED_UPDATE), &idx)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(|e| map_db_error("person", e))?
+                .get("version");
+
+            #[cfg(feature = "consistency-checks")]
+            Self::check_idx_row(&mut *conn, &idx).await?;
+
+            // Create audit link
+            let audit_link = AuditLinkModel {
+                audit_log_id,
+                entity_id: item.id,
+                entity_type: EntityType::Person,
+            };
+            AuditLinkRepositoryImpl::insert_in_connection(&mut *conn, &[audit_link]).await?;
+
+            indices_to_update.push((idx, version as u64));
+            updated_items.push(item);
         }
 
-        Ok(updated_items)
+        Ok((updated_items, indices_to_update))
+    }
+}
+
+#[cfg(feature = "consistency-checks")]
+impl PersonRepositoryImpl {
+    /// Re-read the person_idx row of `idx` on `conn` and check it matches
+    ///
+    /// Fails with `RepositoryError::IndexDiverged` when the row is missing or differs.
+    pub(crate) async fn check_idx_row(conn: &mut PgConnection, idx: &PersonIdxModel) -> Result<(), Box<dyn Error + Send + Sync>> {
+        use crate::utils::TryFromRow;
+
+        let row = sqlx::query("SELECT * FROM person_idx WHERE id = $1")
+            .bind(idx.id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| map_db_error("person", e))?;
+        let stored = row.as_ref().map(PersonIdxModel::try_from_row).transpose()?;
+        if stored.as_ref() != Some(idx) {
+            return Err(RepositoryError::IndexDiverged {
+                entity: "person".to_string(),
+                id: idx.id,
+            }
+            .into());
+        }
+        Ok(())
     }
 }
 
 #[async_trait]
 impl UpdateBatch<Postgres, PersonModel> for PersonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "person", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<PersonModel>,
@@ -256,6 +265,162 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_batch_checks_status_transition() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::error::RepositoryError;
+        use business_core_db::models::person::common_enums::PersonStatus;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let mut person = create_test_person("Transitioning Person", PersonType::Natural);
+        person.status = PersonStatus::Active;
+        let mut person = person_repo.create_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+
+        for status in [PersonStatus::Blacklisted, PersonStatus::PendingVerification] {
+            let update_audit_log = create_test_audit_log();
+            audit_log_repo.create(&update_audit_log).await?;
+            person.status = status;
+            person = person_repo.update_batch(vec![person], Some(update_audit_log.id)).await?.remove(0);
+            assert_eq!(person.status, status);
+        }
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        person.status = PersonStatus::Deceased;
+        let mut person = person_repo.update_batch(vec![person], Some(update_audit_log.id)).await?.remove(0);
+
+        // Deceased is final
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        person.status = PersonStatus::Active;
+        let error = person_repo
+            .update_batch(vec![person.clone()], Some(update_audit_log.id))
+            .await
+            .expect_err("Deceased -> Active must be refused");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::InvalidTransition { entity, id, from, to }) => {
+                assert_eq!(entity, "person");
+                assert_eq!(*id, person.id);
+                assert_eq!(from, "Deceased");
+                assert_eq!(to, "Active");
+            }
+            other => panic!("Expected InvalidTransition, got {other:?}"),
+        }
+        assert_eq!(person_repo.load(person.id).await?.unwrap().status, PersonStatus::Deceased);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_batch_advances_cache_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::repository::cache_versions::CacheVersions;
+        use crate::repository::idx_notification_listener::IdxNotificationListener;
+        use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+        use postgres_unit_of_work::TransactionAware;
+        use std::sync::Arc;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Versioned Person", PersonType::Natural);
+        let mut person = person_repo.create_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+        assert_eq!(person_repo.cache_versions.version(person.id), Some(CacheVersions::INITIAL));
+        let stale_idx = person.to_index_with_hash_version(person_repo.hash_version);
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        person.display_name = HeaplessString::try_from("Versioned Person Renamed").unwrap();
+        let person = person_repo.update_batch(vec![person], Some(update_audit_log.id)).await?.remove(0);
+
+        let version: i64 = {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query_scalar("SELECT version FROM person_idx WHERE id = $1")
+                .bind(person.id)
+                .fetch_one(&mut **transaction)
+                .await?
+        };
+        assert_eq!(version, 2);
+        assert_eq!(person_repo.cache_versions.version(person.id), Some(2));
+        assert_eq!(person_repo.cache_versions.shared().version(person.id), None);
+
+        // Once the session committed, the notification of the insert arriving after the
+        // update is dropped
+        person_repo.on_commit().await?;
+        assert_eq!(person_repo.cache_versions.shared().version(person.id), Some(2));
+        let coalescer = Arc::new(
+            NotificationCoalescer::new("person_idx", person_repo.person_idx_shared_cache.clone(), CoalescingConfig::default())
+                .with_versions(person_repo.cache_versions.shared().clone()),
+        );
+        let mut listener = IdxNotificationListener::new();
+        listener.register_handler(coalescer.clone());
+        let mut row = serde_json::to_value(&stale_idx)?;
+        row["version"] = CacheVersions::INITIAL.into();
+        listener.dispatch(&serde_json::json!({ "table": "person_idx", "op": "INSERT", "row": row }).to_string())?;
+        listener.flush();
+        assert_eq!(coalescer.counters().stale(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_batch_after_rolled_back_delete() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::repository::cache_versions::CacheVersions;
+        use business_core_db::repository::delete_batch::DeleteBatch;
+        use postgres_unit_of_work::TransactionAware;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Restored Person", PersonType::Natural);
+        let mut person = person_repo.create_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+        person_repo.on_commit().await?;
+
+        // A delete rolled back, with its statements
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("SAVEPOINT rolled_back_delete").execute(&mut **transaction).await?;
+        }
+        let delete_audit_log = create_test_audit_log();
+        audit_log_repo.create(&delete_audit_log).await?;
+        person_repo.delete_batch(&[person.id], Some(delete_audit_log.id)).await?;
+        assert_eq!(person_repo.cache_versions.version(person.id), Some(CacheVersions::DELETED));
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("ROLLBACK TO SAVEPOINT rolled_back_delete").execute(&mut **transaction).await?;
+        }
+        person_repo.on_rollback().await?;
+        assert_eq!(person_repo.cache_versions.shared().version(person.id), Some(CacheVersions::INITIAL));
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        person.id_number = HeaplessString::try_from("RESTORED-2").unwrap();
+        let person = person_repo.update_batch(vec![person], Some(update_audit_log.id)).await?.remove(0);
+        person_repo.on_commit().await?;
+
+        assert_eq!(person_repo.cache_versions.shared().version(person.id), Some(2));
+        let cached = person_repo
+            .person_idx_shared_cache
+            .read()
+            .get_by_primary(&person.id)
+            .ok_or("The updated person is missing from the cache")?;
+        assert_eq!(cached.id_number_hash, person.to_index_with_hash_version(person_repo.hash_version).id_number_hash);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_update_batch_empty() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let ctx = setup_test_context().await?;
@@ -270,4 +435,42 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "consistency-checks")]
+    #[tokio::test]
+    async fn test_check_idx_row_detects_divergence() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use super::PersonRepositoryImpl;
+        use crate::error::RepositoryError;
+
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Checked Person", PersonType::Natural);
+        let person = person_repo.create_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+        let idx = person.to_index_with_hash_version(person_repo.hash_version);
+
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        PersonRepositoryImpl::check_idx_row(&mut **transaction, &idx).await?;
+
+        sqlx::query("UPDATE person_idx SET id_number_hash = 0 WHERE id = $1")
+            .bind(person.id)
+            .execute(&mut **transaction)
+            .await?;
+        let error = PersonRepositoryImpl::check_idx_row(&mut **transaction, &idx)
+            .await
+            .expect_err("The corrupted row must be reported");
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::IndexDiverged { entity, id }) => {
+                assert_eq!(entity, "person");
+                assert_eq!(*id, person.id);
+            }
+            other => panic!("Expected IndexDiverged, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
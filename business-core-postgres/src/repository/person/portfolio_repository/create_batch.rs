@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::PortfolioRepositoryImpl;
 
 impl PortfolioRepositoryImpl {
@@ -23,7 +24,7 @@ impl PortfolioRepositoryImpl {
         }
 
         let mut saved_items = Vec::new();
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("portfolio", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -99,20 +100,11 @@ impl PortfolioRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::Portfolio,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
 
             // 7. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             entity_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
             saved_items.push(entity);
         }
@@ -123,6 +115,7 @@ impl PortfolioRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, PortfolioModel> for PortfolioRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "portfolio", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<PortfolioModel>,
@@ -0,0 +1,43 @@
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::PortfolioRepositoryImpl;
+
+impl PortfolioRepositoryImpl {
+    /// List the ids of the persons holding a portfolio
+    ///
+    /// A portfolio belongs to exactly one person, so the result has one entry,
+    /// or none when the portfolio does not exist.
+    pub async fn find_members_of_portfolio(
+        &self,
+        portfolio_id: Uuid,
+    ) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
+        let query = r#"SELECT person_id FROM portfolio WHERE id = $1"#;
+        let person_ids = {
+            let mut tx = self.operation_timeout.lock("portfolio", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query_scalar::<_, Uuid>(query)
+                .bind(portfolio_id)
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+        Ok(person_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_find_members_of_missing_portfolio() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let portfolio_repo = &ctx.person_repos().portfolio_repository;
+
+        let members = portfolio_repo.find_members_of_portfolio(Uuid::new_v4()).await?;
+        assert!(members.is_empty());
+
+        Ok(())
+    }
+}
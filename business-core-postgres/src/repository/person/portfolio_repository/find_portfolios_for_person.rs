@@ -0,0 +1,82 @@
+use business_core_db::models::person::portfolio::PortfolioModel;
+use crate::utils::TryFromRow;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::repo_impl::PortfolioRepositoryImpl;
+
+impl PortfolioRepositoryImpl {
+    /// List the portfolios held by a person
+    ///
+    /// Membership is the portfolio's `person_id`; portfolios carry no status, so all
+    /// existing portfolios of the person are returned.
+    pub async fn find_portfolios_for_person(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<PortfolioModel>, Box<dyn Error + Send + Sync>> {
+        let query = r#"SELECT * FROM portfolio WHERE person_id = $1 ORDER BY id"#;
+        let rows = {
+            let mut tx = self.operation_timeout.lock("portfolio", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(query).bind(person_id).fetch_all(&mut **transaction).await?
+        };
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            items.push(PortfolioModel::try_from_row(&row)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::portfolio_repository::test_utils::create_test_portfolio;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_person_in_two_portfolios() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let portfolio_repo = &ctx.person_repos().portfolio_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let person_id = Uuid::new_v4();
+        let mut first = create_test_portfolio();
+        first.person_id = person_id;
+        let mut second = create_test_portfolio();
+        second.person_id = person_id;
+        let other = create_test_portfolio();
+        let saved = portfolio_repo
+            .create_batch(vec![first, second, other], Some(audit_log.id))
+            .await?;
+
+        let portfolios = portfolio_repo.find_portfolios_for_person(person_id).await?;
+        assert_eq!(portfolios.len(), 2);
+        assert!(portfolios.iter().any(|p| p.id == saved[0].id));
+        assert!(portfolios.iter().any(|p| p.id == saved[1].id));
+
+        for portfolio in &saved[..2] {
+            let members = portfolio_repo.find_members_of_portfolio(portfolio.id).await?;
+            assert_eq!(members, vec![person_id]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_find_portfolios_for_person_none() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let portfolio_repo = &ctx.person_repos().portfolio_repository;
+
+        let portfolios = portfolio_repo.find_portfolios_for_person(Uuid::new_v4()).await?;
+        assert!(portfolios.is_empty());
+
+        Ok(())
+    }
+}
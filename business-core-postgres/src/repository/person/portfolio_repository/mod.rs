@@ -5,6 +5,8 @@ pub mod load_audits;
 pub mod update_batch;
 pub mod delete_batch;
 pub mod exist_by_ids;
+pub mod find_portfolios_for_person;
+pub mod find_members_of_portfolio;
 #[cfg(test)]
 pub mod test_utils;
 
@@ -4,14 +4,20 @@ use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
 use async_trait::async_trait;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct PortfolioRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
 }
 
 impl PortfolioRepositoryImpl {
     pub fn new(executor: Executor) -> Self {
-        Self { executor }
+        Self {
+            executor,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 }
 
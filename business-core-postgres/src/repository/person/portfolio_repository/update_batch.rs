@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::PortfolioRepositoryImpl;
 
 impl PortfolioRepositoryImpl {
@@ -23,7 +24,7 @@ impl PortfolioRepositoryImpl {
         }
 
         let mut updated_items = Vec::new();
-        let mut tx = self.executor.tx.lock().await;
+        let mut tx = self.operation_timeout.lock("portfolio", &self.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -125,6 +126,8 @@ impl PortfolioRepositoryImpl {
             .rows_affected();
 
             if rows_affected == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(entity = "portfolio", id = %entity.id, "Concurrent update detected");
                 return Err("Concurrent update detected".into());
             }
             
@@ -134,19 +137,10 @@ impl PortfolioRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::Portfolio,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
-            
+
             // 8. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
             
             updated_items.push(entity);
         }
@@ -157,6 +151,7 @@ impl PortfolioRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, PortfolioModel> for PortfolioRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "portfolio", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<PortfolioModel>,
@@ -0,0 +1,189 @@
+use std::marker::PhantomData;
+use postgres_unit_of_work::UnitOfWorkSession;
+use crate::repository::repo_selection::{RepoSelection, Selected, Unselected};
+use super::factory::PersonRepoFactory;
+use super::{CountryRepositoryImpl, CountrySubdivisionRepositoryImpl, LocalityRepositoryImpl, LocationRepositoryImpl, PersonRepositoryImpl, EntityReferenceRepositoryImpl, RiskSummaryRepositoryImpl, ActivityLogRepositoryImpl, PortfolioRepositoryImpl, ComplianceStatusRepositoryImpl, DocumentRepositoryImpl};
+
+/// Builder constructing only the selected person module repositories
+///
+/// Obtained from `PersonRepoFactory::builder`. Each `with_*` call selects one repository;
+/// `build` returns a `SelectedPersonRepositories` whose unselected fields are `()`.
+pub struct PersonRepoBuilder<'a, Country = Unselected, CountrySubdivision = Unselected, Locality = Unselected, Location = Unselected, Person = Unselected, EntityReference = Unselected, RiskSummary = Unselected, ActivityLog = Unselected, Portfolio = Unselected, ComplianceStatus = Unselected, Document = Unselected> {
+    factory: &'a PersonRepoFactory,
+    selection: PhantomData<(Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document)>,
+}
+
+impl PersonRepoFactory {
+    /// Start a builder that constructs only the selected repositories
+    pub fn builder(&self) -> PersonRepoBuilder<'_> {
+        PersonRepoBuilder {
+            factory: self,
+            selection: PhantomData,
+        }
+    }
+}
+
+impl<'a, Country: RepoSelection, CountrySubdivision: RepoSelection, Locality: RepoSelection, Location: RepoSelection, Person: RepoSelection, EntityReference: RepoSelection, RiskSummary: RepoSelection, ActivityLog: RepoSelection, Portfolio: RepoSelection, ComplianceStatus: RepoSelection, Document: RepoSelection> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+    /// Select the CountryRepository
+    pub fn with_country(self) -> PersonRepoBuilder<'a, Selected, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the CountrySubdivisionRepository
+    pub fn with_country_subdivision(self) -> PersonRepoBuilder<'a, Country, Selected, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the LocalityRepository
+    pub fn with_locality(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Selected, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the LocationRepository
+    pub fn with_location(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Selected, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the PersonRepository
+    pub fn with_person(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Selected, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the EntityReferenceRepository
+    pub fn with_entity_reference(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, Selected, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the RiskSummaryRepository
+    pub fn with_risk_summary(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, Selected, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the ActivityLogRepository
+    pub fn with_activity_log(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, Selected, Portfolio, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the PortfolioRepository
+    pub fn with_portfolio(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Selected, ComplianceStatus, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the ComplianceStatusRepository
+    pub fn with_compliance_status(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, Selected, Document> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Select the DocumentRepository
+    pub fn with_document(self) -> PersonRepoBuilder<'a, Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Selected> {
+        PersonRepoBuilder {
+            factory: self.factory,
+            selection: PhantomData,
+        }
+    }
+
+    /// Build the selected repositories with the given executor
+    pub fn build(self, session: &impl UnitOfWorkSession) -> SelectedPersonRepositories<Country, CountrySubdivision, Locality, Location, Person, EntityReference, RiskSummary, ActivityLog, Portfolio, ComplianceStatus, Document> {
+        SelectedPersonRepositories {
+            country_repository: Country::build(|| self.factory.build_country_repo(session)),
+            country_subdivision_repository: CountrySubdivision::build(|| self.factory.build_country_subdivision_repo(session)),
+            locality_repository: Locality::build(|| self.factory.build_locality_repo(session)),
+            location_repository: Location::build(|| self.factory.build_location_repo(session)),
+            person_repository: Person::build(|| self.factory.build_person_repo(session)),
+            entity_reference_repository: EntityReference::build(|| self.factory.build_entity_reference_repo(session)),
+            risk_summary_repository: RiskSummary::build(|| self.factory.build_risk_summary_repo(session)),
+            activity_log_repository: ActivityLog::build(|| self.factory.build_activity_log_repo(session)),
+            portfolio_repository: Portfolio::build(|| self.factory.build_portfolio_repo(session)),
+            compliance_status_repository: ComplianceStatus::build(|| self.factory.build_compliance_status_repo(session)),
+            document_repository: Document::build(|| self.factory.build_document_repo(session)),
+        }
+    }
+}
+
+/// Container for the person module repositories selected on a `PersonRepoBuilder`
+pub struct SelectedPersonRepositories<Country: RepoSelection, CountrySubdivision: RepoSelection, Locality: RepoSelection, Location: RepoSelection, Person: RepoSelection, EntityReference: RepoSelection, RiskSummary: RepoSelection, ActivityLog: RepoSelection, Portfolio: RepoSelection, ComplianceStatus: RepoSelection, Document: RepoSelection> {
+    pub country_repository: Country::Repo<CountryRepositoryImpl>,
+    pub country_subdivision_repository: CountrySubdivision::Repo<CountrySubdivisionRepositoryImpl>,
+    pub locality_repository: Locality::Repo<LocalityRepositoryImpl>,
+    pub location_repository: Location::Repo<LocationRepositoryImpl>,
+    pub person_repository: Person::Repo<PersonRepositoryImpl>,
+    pub entity_reference_repository: EntityReference::Repo<EntityReferenceRepositoryImpl>,
+    pub risk_summary_repository: RiskSummary::Repo<RiskSummaryRepositoryImpl>,
+    pub activity_log_repository: ActivityLog::Repo<ActivityLogRepositoryImpl>,
+    pub portfolio_repository: Portfolio::Repo<PortfolioRepositoryImpl>,
+    pub compliance_status_repository: ComplianceStatus::Repo<ComplianceStatusRepositoryImpl>,
+    pub document_repository: Document::Repo<DocumentRepositoryImpl>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::audit::AuditRepoFactory;
+    use crate::repository::person::PersonRepoFactory;
+    use crate::repository::person::document_repository::test_utils::create_test_document_with_status;
+    use crate::repository::person::person_repository::test_utils::create_test_person;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::document::DocumentStatus;
+    use business_core_db::models::person::person::PersonType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use postgres_unit_of_work::{PostgresUnitOfWork, UnitOfWork};
+
+    #[tokio::test]
+    async fn test_builder_builds_selected_repositories() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+
+        let uow = PostgresUnitOfWork::new(ctx.pool().clone());
+        let session = uow.begin().await?;
+        let audit_log_repo = AuditRepoFactory::new().build_audit_log_repo(&session);
+        let factory = PersonRepoFactory::new(None);
+        let repos = factory.builder().with_person().with_document().build(&session);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Builder Person", PersonType::Natural);
+        let person_id = person.id;
+        repos.person_repository.create_batch(vec![person], Some(audit_log.id)).await?;
+        let document = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved = repos.document_repository.create_batch(vec![document], Some(audit_log.id)).await?;
+
+        let loaded = repos.document_repository.load_batch(&[saved[0].id]).await?;
+        assert_eq!(loaded[0].as_ref().map(|d| d.person_id), Some(person_id));
+
+        // Unselected repositories are `()`, using them does not compile
+        let _: () = repos.country_repository;
+        let _: () = repos.portfolio_repository;
+
+        Ok(())
+    }
+}
@@ -6,6 +6,8 @@ use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::RiskSummaryRepositoryImpl;
 
 impl RiskSummaryRepositoryImpl {
@@ -21,7 +23,7 @@ impl RiskSummaryRepositoryImpl {
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("risk_summary", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for item in items {
@@ -63,11 +65,17 @@ impl RiskSummaryRepositoryImpl {
         drop(tx);
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.risk_summary_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.risk_summary_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
@@ -76,6 +84,7 @@ impl RiskSummaryRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, RiskSummaryModel> for RiskSummaryRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "risk_summary", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<RiskSummaryModel>,
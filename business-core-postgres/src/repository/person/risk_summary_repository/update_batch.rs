@@ -6,6 +6,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::RiskSummaryRepositoryImpl;
 
 impl RiskSummaryRepositoryImpl {
@@ -21,7 +22,7 @@ impl RiskSummaryRepositoryImpl {
         let mut indices = Vec::new();
         
         // Acquire lock once and do all database operations
-        let mut tx = self.executor.tx.lock().await;
+        let mut tx = self.operation_timeout.lock("risk_summary", &self.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for item in items {
@@ -55,12 +56,16 @@ impl RiskSummaryRepositoryImpl {
         drop(tx);
         
         // Update cache after releasing transaction lock
-        {
-            let cache = self.risk_summary_idx_cache.read().await;
-            for (id, idx) in indices {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|(id, _)| *id).collect();
+            {
+                let cache = self.risk_summary_idx_cache.read().await;
+                for (id, idx) in indices {
+                    cache.remove(&id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -69,6 +74,7 @@ impl RiskSummaryRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, RiskSummaryModel> for RiskSummaryRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "risk_summary", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<RiskSummaryModel>,
@@ -6,6 +6,8 @@ use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 
+use crate::repository::cache_versions::CacheVersions;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ComplianceMetadataRepositoryImpl;
 
 impl ComplianceMetadataRepositoryImpl {
@@ -22,7 +24,7 @@ impl ComplianceMetadataRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = repo.executor.tx.lock().await;
+            let mut tx = repo.operation_timeout.lock("compliance_metadata", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -73,11 +75,17 @@ impl ComplianceMetadataRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = repo.compliance_metadata_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.compliance_metadata_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
             }
+            repo.record_cache_use(&ids);
         }
 
         Ok(saved_items)
@@ -86,6 +94,7 @@ impl ComplianceMetadataRepositoryImpl {
 
 #[async_trait]
 impl CreateBatch<Postgres, ComplianceMetadataModel> for ComplianceMetadataRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "compliance_metadata", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<ComplianceMetadataModel>,
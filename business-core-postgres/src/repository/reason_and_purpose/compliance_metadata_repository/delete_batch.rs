@@ -1,57 +1,77 @@
 use async_trait::async_trait;
 use business_core_db::repository::delete_batch::DeleteBatch;
-use sqlx::Postgres;
+use business_core_db::repository::delete_batch_detailed::{DeleteBatchDetailed, DeleteOutcome};
+use sqlx::{Postgres, Row};
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::ComplianceMetadataRepositoryImpl;
 
 impl ComplianceMetadataRepositoryImpl {
     pub(super) async fn delete_batch_impl(
         repo: &ComplianceMetadataRepositoryImpl,
         ids: &[Uuid],
-    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         if ids.is_empty() {
-            return Ok(0);
+            return Ok(DeleteOutcome::default());
         }
 
         // Delete from index table first
         let delete_idx_query = r#"DELETE FROM compliance_metadata_idx WHERE id = ANY($1)"#;
-        let delete_query = r#"DELETE FROM compliance_metadata WHERE id = ANY($1)"#;
+        let delete_query = r#"DELETE FROM compliance_metadata WHERE id = ANY($1) RETURNING id"#;
 
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("compliance_metadata", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         sqlx::query(delete_idx_query)
             .bind(ids)
             .execute(&mut **transaction)
             .await?;
-        let result = sqlx::query(delete_query)
+        let rows = sqlx::query(delete_query)
             .bind(ids)
-            .execute(&mut **transaction)
+            .fetch_all(&mut **transaction)
             .await?;
-        let rows_affected = result.rows_affected() as usize;
+        let deleted: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
         
         // Update cache after releasing transaction lock
         drop(tx);
-        {
+        if repo.cache_policy.maintains_cache() {
             let cache = repo.compliance_metadata_idx_cache.read().await;
-            for id in ids {
+            for id in &deleted {
+                // No replayed notification of the row brings it back
+                repo.cache_versions.admit(*id, CacheVersions::DELETED);
                 cache.remove(id);
             }
+            if let Some(bound) = &repo.cache_bound {
+                bound.record_removal(&deleted);
+            }
         }
         
-        Ok(rows_affected)
+        Ok(DeleteOutcome::from_returned(ids, deleted))
     }
 }
 
 #[async_trait]
 impl DeleteBatch<Postgres> for ComplianceMetadataRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "compliance_metadata", count = ids.len())))]
     async fn delete_batch(
         &self,
         ids: &[Uuid],
         _audit_log_id: Option<Uuid>,
     ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let outcome = Self::delete_batch_impl(self, ids).await?;
+        Ok(outcome.deleted.len())
+    }
+}
+
+#[async_trait]
+impl DeleteBatchDetailed<Postgres> for ComplianceMetadataRepositoryImpl {
+    async fn delete_batch_detailed(
+        &self,
+        ids: &[Uuid],
+        _audit_log_id: Option<Uuid>,
+    ) -> Result<DeleteOutcome, Box<dyn Error + Send + Sync>> {
         Self::delete_batch_impl(self, ids).await
     }
 }
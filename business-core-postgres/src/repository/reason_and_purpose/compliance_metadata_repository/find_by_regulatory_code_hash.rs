@@ -1,6 +1,7 @@
 use std::error::Error;
 use business_core_db::models::reason_and_purpose::compliance_metadata::ComplianceMetadataIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ComplianceMetadataRepositoryImpl;
 
 impl ComplianceMetadataRepositoryImpl {
@@ -8,9 +9,7 @@ impl ComplianceMetadataRepositoryImpl {
         &self,
         regulatory_code_hash: i64,
     ) -> Result<Vec<ComplianceMetadataIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.compliance_metadata_idx_cache.read().await;
-        let items = cache.get_by_i64_index("regulatory_code_hash", &regulatory_code_hash);
-        Ok(items)
+        self.find_by_i64_key("regulatory_code_hash", regulatory_code_hash).await
     }
 }
 
@@ -1,4 +1,7 @@
 use business_core_db::models::reason_and_purpose::compliance_metadata::{ComplianceMetadataIdxModel, ComplianceMetadataModel};
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
 use crate::utils::{get_heapless_string, get_optional_heapless_string, TryFromRow};
 use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use postgres_index_cache::TransactionAwareIdxModelCache;
@@ -7,32 +10,63 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct ComplianceMetadataRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub compliance_metadata_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<ComplianceMetadataIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub compliance_metadata_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ComplianceMetadataIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the compliance_metadata_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the compliance_metadata_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
 }
 
 impl ComplianceMetadataRepositoryImpl {
     pub fn new(
         executor: Executor,
         compliance_metadata_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ComplianceMetadataIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, compliance_metadata_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        compliance_metadata_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ComplianceMetadataIdxModel>>>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            compliance_metadata_idx_shared_cache: compliance_metadata_idx_cache.clone(),
             compliance_metadata_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 compliance_metadata_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
         }
     }
 
     pub async fn load_all_compliance_metadata_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<ComplianceMetadataIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM compliance_metadata_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("compliance_metadata", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -51,10 +85,19 @@ impl ComplianceMetadataRepositoryImpl {
 #[async_trait]
 impl TransactionAware for ComplianceMetadataRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.compliance_metadata_idx_cache.read().await.on_commit().await
+        self.compliance_metadata_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.compliance_metadata_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.compliance_metadata_idx_cache.read().await.on_rollback().await
     }
 }
@@ -83,8 +126,71 @@ impl TryFromRow<PgRow> for ComplianceMetadataModel {
 impl TryFromRow<PgRow> for ComplianceMetadataIdxModel {
     fn try_from_row(row: &PgRow) -> Result<Self, Box<dyn Error + Send + Sync>> {
         Ok(ComplianceMetadataIdxModel {
-            id: row.get("compliance_metadata_id"),
+            id: row.get("id"),
             regulatory_code_hash: row.try_get("regulatory_code_hash").ok(),
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for ComplianceMetadataRepositoryImpl {
+    type Idx = ComplianceMetadataIdxModel;
+
+    const ENTITY: &'static str = "compliance_metadata";
+    const I64_KEYS: &'static [&'static str] = &["regulatory_code_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<ComplianceMetadataIdxModel>> {
+        &self.compliance_metadata_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for ComplianceMetadataRepositoryImpl {
+    type Idx = ComplianceMetadataIdxModel;
+
+    const ENTITY: &'static str = "compliance_metadata";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<ComplianceMetadataIdxModel>>> {
+        &self.compliance_metadata_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<ComplianceMetadataIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_compliance_metadata_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
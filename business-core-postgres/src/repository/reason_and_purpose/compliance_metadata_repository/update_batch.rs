@@ -6,6 +6,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ComplianceMetadataRepositoryImpl;
 
 impl ComplianceMetadataRepositoryImpl {
@@ -22,7 +23,7 @@ impl ComplianceMetadataRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("compliance_metadata", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -59,12 +60,16 @@ impl ComplianceMetadataRepositoryImpl {
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = self.compliance_metadata_idx_cache.read().await;
-            for (id, idx) in indices {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|(id, _)| *id).collect();
+            {
+                let cache = self.compliance_metadata_idx_cache.read().await;
+                for (id, idx) in indices {
+                    cache.remove(&id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -73,6 +78,7 @@ impl ComplianceMetadataRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, ComplianceMetadataModel> for ComplianceMetadataRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "compliance_metadata", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<ComplianceMetadataModel>,
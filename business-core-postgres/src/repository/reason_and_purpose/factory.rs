@@ -1,11 +1,17 @@
 use std::sync::Arc;
 use parking_lot::RwLock as ParkingRwLock;
 use postgres_unit_of_work::UnitOfWorkSession;
-use postgres_index_cache::{CacheNotificationListener, IndexCacheHandler};
 use business_core_db::models::reason_and_purpose::{
     compliance_metadata::ComplianceMetadataIdxModel,
     reason::ReasonIdxModel,
 };
+use crate::repository::cache_capacity::{CacheBound, CacheCapacity};
+use crate::repository::cache_health::CacheHealth;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
+use crate::repository::idx_notification_listener::IdxNotificationListener;
+use crate::repository::notification_coalescer::{CoalescingConfig, NotificationCoalescer};
+use crate::repository::operation_timeout::OperationTimeout;
 use super::{ComplianceMetadataRepositoryImpl, ReasonRepositoryImpl, ReasonReferenceRepositoryImpl};
 
 /// Factory for creating reason_and_purpose module repositories
@@ -16,13 +22,72 @@ use super::{ComplianceMetadataRepositoryImpl, ReasonRepositoryImpl, ReasonRefere
 pub struct ReasonAndPurposeRepoFactory {
     compliance_metadata_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ComplianceMetadataIdxModel>>>,
     reason_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ReasonIdxModel>>>,
+    compliance_metadata_cache_policy: CachePolicy,
+    reason_cache_policy: CachePolicy,
+    operation_timeout: OperationTimeout,
+    compliance_metadata_cache_capacity: Option<Arc<CacheCapacity>>,
+    reason_cache_capacity: Option<Arc<CacheCapacity>>,
+    cache_health: CacheHealth,
+    compliance_metadata_cache_versions: Arc<CacheVersions>,
+    reason_cache_versions: Arc<CacheVersions>,
 }
 
 impl ReasonAndPurposeRepoFactory {
     /// Create a new ReasonAndPurposeRepoFactory singleton
     ///
     /// Optionally register cache handlers with a notification listener
-    pub fn new(listener: Option<&mut CacheNotificationListener>) -> Arc<Self> {
+    pub fn new(listener: Option<&mut IdxNotificationListener>) -> Arc<Self> {
+        Self::new_with_cache_policies(listener, CachePolicy::Enabled, CachePolicy::Enabled)
+    }
+
+    /// Create a new ReasonAndPurposeRepoFactory singleton with a cache policy per indexed repository
+    ///
+    /// Notification handlers, a `NotificationCoalescer` per idx table, are only registered
+    /// for caches whose policy is `CachePolicy::Enabled`
+    pub fn new_with_cache_policies(
+        listener: Option<&mut IdxNotificationListener>,
+        compliance_metadata_cache_policy: CachePolicy,
+        reason_cache_policy: CachePolicy,
+    ) -> Arc<Self> {
+        Self::new_with_operation_timeout(
+            listener,
+            compliance_metadata_cache_policy,
+            reason_cache_policy,
+            OperationTimeout::default(),
+        )
+    }
+
+    /// Create a new ReasonAndPurposeRepoFactory singleton whose repositories bound their
+    /// wait for the transaction lock with `operation_timeout`
+    pub fn new_with_operation_timeout(
+        listener: Option<&mut IdxNotificationListener>,
+        compliance_metadata_cache_policy: CachePolicy,
+        reason_cache_policy: CachePolicy,
+        operation_timeout: OperationTimeout,
+    ) -> Arc<Self> {
+        Self::new_with_cache_capacities(
+            listener,
+            compliance_metadata_cache_policy,
+            reason_cache_policy,
+            operation_timeout,
+            None,
+            None,
+        )
+    }
+
+    /// Create a new ReasonAndPurposeRepoFactory singleton whose compliance_metadata_idx
+    /// and reason_idx caches keep at most the given number of entries
+    ///
+    /// A `None` capacity leaves its cache unbounded. A bounded cache evicts its least
+    /// recently used entries, see `CacheCapacity`.
+    pub fn new_with_cache_capacities(
+        listener: Option<&mut IdxNotificationListener>,
+        compliance_metadata_cache_policy: CachePolicy,
+        reason_cache_policy: CachePolicy,
+        operation_timeout: OperationTimeout,
+        compliance_metadata_cache_capacity: Option<usize>,
+        reason_cache_capacity: Option<usize>,
+    ) -> Arc<Self> {
         let compliance_metadata_idx_cache = Arc::new(ParkingRwLock::new(
             business_core_db::IdxModelCache::new(vec![]).unwrap()
         ));
@@ -31,62 +96,125 @@ impl ReasonAndPurposeRepoFactory {
             business_core_db::IdxModelCache::new(vec![]).unwrap()
         ));
         
+        // Versions of each cache, shared by its repositories and its coalescer so a
+        // delayed notification does not overwrite a newer local write
+        let compliance_metadata_cache_versions = Arc::new(CacheVersions::new());
+        let reason_cache_versions = Arc::new(CacheVersions::new());
+
+        // Capacity of each bounded cache, shared by its repositories and its coalescer
+        let compliance_metadata_cache_capacity =
+            CacheCapacity::for_policy(compliance_metadata_cache_policy, compliance_metadata_cache_capacity);
+        let reason_cache_capacity = CacheCapacity::for_policy(reason_cache_policy, reason_cache_capacity);
+
+        // The repositories share the health of the listener feeding their caches
+        let cache_health = listener
+            .as_deref()
+            .map_or_else(CacheHealth::default, IdxNotificationListener::cache_health);
+
         // Register handlers with listener if provided
         if let Some(listener) = listener {
-            let handler = Arc::new(IndexCacheHandler::new(
-                "compliance_metadata_idx".to_string(),
-                compliance_metadata_idx_cache.clone(),
-            ));
-            listener.register_handler(handler);
-            
-            let reason_handler = Arc::new(IndexCacheHandler::new(
-                "reason_idx".to_string(),
-                reason_idx_cache.clone(),
-            ));
-            listener.register_handler(reason_handler);
+            if compliance_metadata_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("compliance_metadata_idx", compliance_metadata_idx_cache.clone(), CoalescingConfig::default())
+                        .with_versions(compliance_metadata_cache_versions.clone())
+                        .with_capacity(compliance_metadata_cache_capacity.clone()),
+                ));
+            }
+
+            if reason_cache_policy.registers_notifications() {
+                listener.register_handler(Arc::new(
+                    NotificationCoalescer::new("reason_idx", reason_idx_cache.clone(), CoalescingConfig::default())
+                        .with_versions(reason_cache_versions.clone())
+                        .with_capacity(reason_cache_capacity.clone()),
+                ));
+            }
         }
-        
+
         Arc::new(Self {
             compliance_metadata_idx_cache,
             reason_idx_cache,
+            compliance_metadata_cache_policy,
+            reason_cache_policy,
+            operation_timeout,
+            compliance_metadata_cache_capacity,
+            reason_cache_capacity,
+            cache_health,
+            compliance_metadata_cache_versions,
+            reason_cache_versions,
         })
     }
 
+    /// Health of the notifications feeding the caches of this factory
+    ///
+    /// The health of the listener given to the factory, shared with the repositories it
+    /// builds; degraded while `IdxNotificationListener::supervise` reconnects. Without a
+    /// listener it is never degraded. `CacheHealth::report` gives the entry count and
+    /// last applied notification of each cache fed by the listener.
+    pub fn cache_health(&self) -> CacheHealth {
+        self.cache_health.clone()
+    }
+
     /// Build a ComplianceMetadataRepository with the given executor
     pub fn build_compliance_metadata_repo(&self, session: &impl UnitOfWorkSession) -> Arc<ComplianceMetadataRepositoryImpl> {
-        let repo = Arc::new(ComplianceMetadataRepositoryImpl::new(
-            session.executor().clone(),
-            self.compliance_metadata_idx_cache.clone(),
-        ));
+        let repo = Arc::new(ComplianceMetadataRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.compliance_metadata_cache_versions.clone()),
+            cache_bound: self.compliance_metadata_cache_capacity.clone().map(CacheBound::new),
+            ..ComplianceMetadataRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.compliance_metadata_idx_cache.clone(),
+                self.compliance_metadata_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a ReasonRepository with the given executor
     pub fn build_reason_repo(&self, session: &impl UnitOfWorkSession) -> Arc<ReasonRepositoryImpl> {
-        let repo = Arc::new(ReasonRepositoryImpl::new(
-            session.executor().clone(),
-            self.reason_idx_cache.clone(),
-        ));
+        let repo = Arc::new(ReasonRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            cache_versions: PendingVersions::new(self.reason_cache_versions.clone()),
+            cache_bound: self.reason_cache_capacity.clone().map(CacheBound::new),
+            cache_health: self.cache_health.clone(),
+            ..ReasonRepositoryImpl::new_with_cache_policy(
+                session.executor().clone(),
+                self.reason_idx_cache.clone(),
+                self.reason_cache_policy,
+            )
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build a ReasonReferenceRepository with the given executor
+    ///
+    /// The repository looks up reasons through its own ReasonRepository built on the same session
     pub fn build_reason_reference_repo(&self, session: &impl UnitOfWorkSession) -> Arc<ReasonReferenceRepositoryImpl> {
-        let repo = Arc::new(ReasonReferenceRepositoryImpl::new(
-            session.executor().clone(),
-        ));
+        let reason_repository = self.build_reason_repo(session);
+        self.build_reason_reference_repo_with(session, reason_repository)
+    }
+
+    fn build_reason_reference_repo_with(
+        &self,
+        session: &impl UnitOfWorkSession,
+        reason_repository: Arc<ReasonRepositoryImpl>,
+    ) -> Arc<ReasonReferenceRepositoryImpl> {
+        let repo = Arc::new(ReasonReferenceRepositoryImpl {
+            operation_timeout: self.operation_timeout,
+            ..ReasonReferenceRepositoryImpl::new(session.executor().clone(), reason_repository)
+        });
         session.register_transaction_aware(repo.clone());
         repo
     }
 
     /// Build all reason_and_purpose repositories with the given executor
     pub fn build_all_repos(&self, session: &impl UnitOfWorkSession) -> ReasonAndPurposeRepositories {
+        let reason_repository = self.build_reason_repo(session);
         ReasonAndPurposeRepositories {
             compliance_metadata_repository: self.build_compliance_metadata_repo(session),
-            reason_repository: self.build_reason_repo(session),
-            reason_reference_repository: self.build_reason_reference_repo(session),
+            reason_reference_repository: self.build_reason_reference_repo_with(session, reason_repository.clone()),
+            reason_repository,
         }
     }
 }
@@ -4,11 +4,14 @@ use business_core_db::models::{
     reason_and_purpose::reason_reference::ReasonReferenceModel,
 };
 use business_core_db::repository::create_batch::CreateBatch;
+use business_core_db::repository::load_batch::LoadBatch;
 use business_core_db::utils::hash_as_i64;
 use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::error::RepositoryError;
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::ReasonReferenceRepositoryImpl;
 
 impl ReasonReferenceRepositoryImpl {
@@ -22,8 +25,10 @@ impl ReasonReferenceRepositoryImpl {
             return Ok(Vec::new());
         }
 
+        repo.check_required_details(&items).await?;
+
         let mut saved_items = Vec::new();
-        let mut tx = repo.executor.tx.lock().await;
+        let mut tx = repo.operation_timeout.lock("reason_reference", &repo.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -43,37 +48,41 @@ impl ReasonReferenceRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO reason_reference_audit
-                (id, reason_id, entity_id, additional_details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                (id, reason_id, entity_id, additional_details, details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 "#,
             )
             .bind(entity.id)
             .bind(entity.reason_id)
             .bind(entity.entity_id)
             .bind(entity.additional_details.as_deref())
+            .bind(entity.details.as_deref())
             .bind(entity.entity_type)
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
             .bind(entity.hash)
-            .bind(entity.audit_log_id);
+            .bind(entity.audit_log_id)
+            .bind(entity.created_at);
 
             // 5. Build entity insert query
             let entity_insert_query = sqlx::query(
                 r#"
                 INSERT INTO reason_reference
-                (id, reason_id, entity_id, additional_details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                (id, reason_id, entity_id, additional_details, details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 "#,
             )
             .bind(entity.id)
             .bind(entity.reason_id)
             .bind(entity.entity_id)
             .bind(entity.additional_details.as_deref())
+            .bind(entity.details.as_deref())
             .bind(entity.entity_type)
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
             .bind(entity.hash)
-            .bind(entity.audit_log_id);
+            .bind(entity.audit_log_id)
+            .bind(entity.created_at);
 
             // 6. Create audit link to track the entity modification in the transaction
             let audit_link = AuditLinkModel {
@@ -81,30 +90,61 @@ impl ReasonReferenceRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::ReasonReference,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
 
             // 7. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
             entity_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
 
             saved_items.push(entity);
         }
 
         Ok(saved_items)
     }
+
+    /// Reject the batch when a reference of a reason with `requires_details` has no details
+    ///
+    /// The referenced reasons are loaded in one batch. References to unknown reasons are
+    /// not checked here.
+    async fn check_required_details(
+        &self,
+        items: &[ReasonReferenceModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut reason_ids: Vec<Uuid> = items
+            .iter()
+            .filter(|item| item.details.is_none())
+            .map(|item| item.reason_id)
+            .collect();
+        reason_ids.sort();
+        reason_ids.dedup();
+        if reason_ids.is_empty() {
+            return Ok(());
+        }
+
+        let codes: Vec<String> = self
+            .reason_repository
+            .load_batch(&reason_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .filter(|reason| reason.requires_details)
+            .map(|reason| reason.code.to_string())
+            .collect();
+        if codes.is_empty() {
+            Ok(())
+        } else {
+            Err(RepositoryError::MissingReasonDetails {
+                entity: "reason_reference".to_string(),
+                codes,
+            }
+            .into())
+        }
+    }
 }
 
 #[async_trait]
 impl CreateBatch<Postgres, ReasonReferenceModel> for ReasonReferenceRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "reason_reference", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<ReasonReferenceModel>,
@@ -120,7 +160,9 @@ mod tests {
     use crate::test_helper::setup_test_context;
     use business_core_db::repository::create_batch::CreateBatch;
     use crate::repository::reason_and_purpose::compliance_metadata_repository::test_utils::test_utils::create_test_compliance_metadata;
-    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::{create_test_reason_with_compliance_metadata};
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::{create_test_reason, create_test_reason_with_compliance_metadata};
+    use crate::error::RepositoryError;
+    use heapless::String as HeaplessString;
 
     fn create_test_audit_log() -> business_core_db::models::audit::audit_log::AuditLogModel {
         business_core_db::models::audit::audit_log::AuditLogModel {
@@ -183,4 +225,52 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_batch_requires_details() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+        let reason_reference_repo = &ctx.reason_and_purpose_repos().reason_reference_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let mut detailed_reason = create_test_reason("OTHER_DETAILED", "Other, please specify");
+        detailed_reason.requires_details = true;
+        let plain_reason = create_test_reason("PLAIN", "Plain Reason");
+        reason_repo
+            .create_batch(vec![detailed_reason.clone(), plain_reason.clone()], Some(audit_log.id))
+            .await?;
+
+        // Reason requiring details, with details
+        let mut with_details = create_test_reason_reference(detailed_reason.id, uuid::Uuid::new_v4());
+        with_details.details = Some(HeaplessString::try_from("Customer moved abroad").unwrap());
+        // Reason not requiring details, without details
+        let without_details = create_test_reason_reference(plain_reason.id, uuid::Uuid::new_v4());
+        let saved = reason_reference_repo
+            .create_batch(vec![with_details, without_details], Some(audit_log.id))
+            .await?;
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0].details.as_deref(), Some("Customer moved abroad"));
+
+        // Reason requiring details, without details
+        let missing_details = create_test_reason_reference(detailed_reason.id, uuid::Uuid::new_v4());
+        let missing_id = missing_details.id;
+        let error = reason_reference_repo
+            .create_batch(vec![missing_details], Some(audit_log.id))
+            .await
+            .unwrap_err();
+        match error.downcast_ref::<RepositoryError>() {
+            Some(RepositoryError::MissingReasonDetails { entity, codes }) => {
+                assert_eq!(entity, "reason_reference");
+                assert_eq!(codes, &vec!["OTHER_DETAILED".to_string()]);
+            }
+            other => panic!("Expected MissingReasonDetails, got {other:?}"),
+        }
+        assert!(error.to_string().contains("OTHER_DETAILED"));
+        assert!(reason_reference_repo.load(missing_id).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,17 +1,28 @@
 use business_core_db::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
+use crate::repository::reason_and_purpose::reason_repository::ReasonRepositoryImpl;
 use crate::utils::{get_optional_heapless_string, TryFromRow};
 use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use std::sync::Arc;
 use async_trait::async_trait;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct ReasonReferenceRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
+    /// Reason repository of the same unit of work session, used to check `requires_details`
+    pub reason_repository: Arc<ReasonRepositoryImpl>,
 }
 
 impl ReasonReferenceRepositoryImpl {
-    pub fn new(executor: Executor) -> Self {
-        Self { executor }
+    pub fn new(executor: Executor, reason_repository: Arc<ReasonRepositoryImpl>) -> Self {
+        Self {
+            executor,
+            reason_repository,
+            operation_timeout: OperationTimeout::default(),
+        }
     }
 }
 
@@ -22,7 +33,9 @@ impl TryFromRow<PgRow> for ReasonReferenceModel {
             reason_id: row.get("reason_id"),
             entity_id: row.get("entity_id"),
             additional_details: get_optional_heapless_string(row, "additional_details")?,
+            details: get_optional_heapless_string(row, "details")?,
             entity_type: row.get("entity_type"),
+            created_at: row.get("created_at"),
             antecedent_hash: row.get("antecedent_hash"),
             antecedent_audit_log_id: row.get("antecedent_audit_log_id"),
             hash: row.get("hash"),
@@ -1,5 +1,6 @@
 use business_core_db::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
 use business_core_db::models::audit::entity_type::EntityType;
+use chrono::Utc;
 use heapless::String as HeaplessString;
 use uuid::Uuid;
 
@@ -9,7 +10,9 @@ pub fn create_test_reason_reference(reason_id: Uuid, entity_id: Uuid) -> ReasonR
         reason_id,
         entity_id,
         additional_details: None,
+        details: None,
         entity_type: EntityType::Person,
+        created_at: Utc::now(),
         antecedent_hash: 0,
         antecedent_audit_log_id: Uuid::nil(),
         hash: 0,
@@ -27,7 +30,9 @@ pub fn create_test_reason_reference_with_details(
         reason_id,
         entity_id,
         additional_details: Some(HeaplessString::try_from(details).unwrap()),
+        details: None,
         entity_type: EntityType::Person,
+        created_at: Utc::now(),
         antecedent_hash: 0,
         antecedent_audit_log_id: Uuid::nil(),
         hash: 0,
@@ -45,7 +50,9 @@ pub fn create_test_reason_reference_with_entity_type(
         reason_id,
         entity_id,
         additional_details: None,
+        details: None,
         entity_type,
+        created_at: Utc::now(),
         antecedent_hash: 0,
         antecedent_audit_log_id: Uuid::nil(),
         hash: 0,
@@ -9,6 +9,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::audit::audit_link_repository::AuditLinkRepositoryImpl;
 use super::repo_impl::ReasonReferenceRepositoryImpl;
 
 impl ReasonReferenceRepositoryImpl {
@@ -23,7 +24,7 @@ impl ReasonReferenceRepositoryImpl {
         }
 
         let mut updated_items = Vec::new();
-        let mut tx = self.executor.tx.lock().await;
+        let mut tx = self.operation_timeout.lock("reason_reference", &self.executor.tx).await?;
         let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
         
         for mut entity in items {
@@ -57,19 +58,21 @@ impl ReasonReferenceRepositoryImpl {
             let audit_insert_query = sqlx::query(
                 r#"
                 INSERT INTO reason_reference_audit
-                (id, reason_id, entity_id, additional_details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                (id, reason_id, entity_id, additional_details, details, entity_type, antecedent_hash, antecedent_audit_log_id, hash, audit_log_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 "#,
             )
             .bind(entity.id)
             .bind(entity.reason_id)
             .bind(entity.entity_id)
             .bind(entity.additional_details.as_deref())
+            .bind(entity.details.as_deref())
             .bind(entity.entity_type)
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
             .bind(entity.hash)
-            .bind(entity.audit_log_id);
+            .bind(entity.audit_log_id)
+            .bind(entity.created_at);
             
             // 6. Build entity update query
             let rows_affected = sqlx::query(
@@ -78,20 +81,22 @@ impl ReasonReferenceRepositoryImpl {
                     reason_id = $2,
                     entity_id = $3,
                     additional_details = $4,
-                    entity_type = $5,
-                    antecedent_hash = $6,
-                    antecedent_audit_log_id = $7,
-                    hash = $8,
-                    audit_log_id = $9
+                    details = $5,
+                    entity_type = $6,
+                    antecedent_hash = $7,
+                    antecedent_audit_log_id = $8,
+                    hash = $9,
+                    audit_log_id = $10
                 WHERE id = $1
-                  AND hash = $10
-                  AND audit_log_id = $11
+                  AND hash = $11
+                  AND audit_log_id = $12
                 "#,
             )
             .bind(entity.id)
             .bind(entity.reason_id)
             .bind(entity.entity_id)
             .bind(entity.additional_details.as_deref())
+            .bind(entity.details.as_deref())
             .bind(entity.entity_type)
             .bind(entity.antecedent_hash)
             .bind(entity.antecedent_audit_log_id)
@@ -104,6 +109,8 @@ impl ReasonReferenceRepositoryImpl {
             .rows_affected();
 
             if rows_affected == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(entity = "reason_reference", id = %entity.id, "Concurrent update detected");
                 return Err("Concurrent update detected".into());
             }
             
@@ -113,19 +120,10 @@ impl ReasonReferenceRepositoryImpl {
                 entity_id: entity.id,
                 entity_type: EntityType::ReasonReference,
             };
-            let audit_link_query = sqlx::query(
-                r#"
-                INSERT INTO audit_link (audit_log_id, entity_id, entity_type)
-                VALUES ($1, $2, $3)
-                "#,
-            )
-            .bind(audit_link.audit_log_id)
-            .bind(audit_link.entity_id)
-            .bind(audit_link.entity_type);
-            
+
             // 8. Execute in transaction (audit first!)
             audit_insert_query.execute(&mut **transaction).await?;
-            audit_link_query.execute(&mut **transaction).await?;
+            AuditLinkRepositoryImpl::insert_in_connection(&mut **transaction, &[audit_link]).await?;
             
             updated_items.push(entity);
         }
@@ -136,6 +134,7 @@ impl ReasonReferenceRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, ReasonReferenceModel> for ReasonReferenceRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "reason_reference", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<ReasonReferenceModel>,
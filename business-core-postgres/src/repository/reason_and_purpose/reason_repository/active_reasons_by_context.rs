@@ -0,0 +1,94 @@
+use business_core_db::models::reason_and_purpose::reason::{ReasonContext, ReasonModel};
+use crate::utils::TryFromRow;
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::repo_impl::ReasonRepositoryImpl;
+
+impl ReasonRepositoryImpl {
+    /// Load all active reasons grouped by context, for populating reason dropdowns
+    ///
+    /// One query loads every active reason; each group is sorted by `display_order`,
+    /// then by `code`. Contexts without an active reason have no entry.
+    pub async fn active_reasons_by_context(
+        &self,
+    ) -> Result<HashMap<ReasonContext, Vec<ReasonModel>>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.operation_timeout.lock("reason", &self.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("SELECT * FROM reason WHERE is_active ORDER BY display_order, code")
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+
+        let mut groups: HashMap<ReasonContext, Vec<ReasonModel>> = HashMap::new();
+        for row in rows {
+            let reason = ReasonModel::try_from_row(&row)?;
+            groups.entry(reason.context).or_default().push(reason);
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::reason_and_purpose::reason::ReasonContext;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use super::super::test_utils::test_utils::create_test_reason_with_context;
+
+    #[tokio::test]
+    async fn test_active_reasons_by_context() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let seeds = [
+            ("DROPDOWN_ACCOUNT_B", ReasonContext::Account, 2, true),
+            ("DROPDOWN_ACCOUNT_A", ReasonContext::Account, 1, true),
+            ("DROPDOWN_ACCOUNT_OFF", ReasonContext::Account, 0, false),
+            ("DROPDOWN_LOAN_C", ReasonContext::Loan, 30, true),
+            ("DROPDOWN_LOAN_A", ReasonContext::Loan, 10, true),
+            ("DROPDOWN_LOAN_B", ReasonContext::Loan, 20, true),
+            ("DROPDOWN_KYC_A", ReasonContext::Kyc, 5, true),
+        ];
+        let reasons: Vec<_> = seeds
+            .iter()
+            .map(|(code, context, display_order, is_active)| {
+                let mut reason = create_test_reason_with_context(code, code, *context);
+                reason.display_order = *display_order;
+                reason.is_active = *is_active;
+                reason
+            })
+            .collect();
+        reason_repo.create_batch(reasons, None).await?;
+
+        let groups = reason_repo.active_reasons_by_context().await?;
+
+        // Other reasons of the database may share the contexts, keep the seeded ones
+        let seeded_codes = |context: ReasonContext| -> Vec<String> {
+            groups
+                .get(&context)
+                .map(|group| {
+                    group
+                        .iter()
+                        .filter(|reason| reason.code.starts_with("DROPDOWN_"))
+                        .map(|reason| reason.code.to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        assert_eq!(seeded_codes(ReasonContext::Account), vec!["DROPDOWN_ACCOUNT_A", "DROPDOWN_ACCOUNT_B"]);
+        assert_eq!(
+            seeded_codes(ReasonContext::Loan),
+            vec!["DROPDOWN_LOAN_A", "DROPDOWN_LOAN_B", "DROPDOWN_LOAN_C"]
+        );
+        assert_eq!(seeded_codes(ReasonContext::Kyc), vec!["DROPDOWN_KYC_A"]);
+
+        for (context, group) in &groups {
+            assert!(group.iter().all(|reason| reason.is_active && reason.context == *context));
+            assert!(group.windows(2).all(|pair| pair[0].display_order <= pair[1].display_order));
+        }
+
+        Ok(())
+    }
+}
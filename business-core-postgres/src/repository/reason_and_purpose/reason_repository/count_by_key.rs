@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use business_core_db::repository::count_by_key::CountByKey;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::count_by_key::{check_index_key, count_idx_rows};
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use super::repo_impl::ReasonRepositoryImpl;
+
+/// Uuid keys of `ReasonIdxModel`
+const UUID_KEYS: &[&str] = &["compliance_metadata"];
+
+#[async_trait]
+impl CountByKey<Postgres> for ReasonRepositoryImpl {
+    async fn count_by_uuid_key(&self, key_name: &str, value: Uuid) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("reason", UUID_KEYS, key_name)?;
+        if !self.serves_key_from_cache(key_name, value.into()) {
+            return count_idx_rows(&self.executor, &self.operation_timeout, "reason_idx", key_name, value).await;
+        }
+        let cache = self.reason_idx_cache.read().await;
+        Ok(cache.get_by_uuid_index(key_name, &value).len())
+    }
+
+    async fn count_by_i64_key(&self, key_name: &str, value: i64) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        check_index_key("reason", <Self as FindByI64Key>::I64_KEYS, key_name)?;
+        if !self.serves_key_from_cache(key_name, value.into()) {
+            return count_idx_rows(&self.executor, &self.operation_timeout, "reason_idx", key_name, value).await;
+        }
+        let cache = self.reason_idx_cache.read().await;
+        Ok(cache.get_by_i64_index(key_name, &value).len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::reason_and_purpose::ReasonRepositoryImpl;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::reason_and_purpose::reason::ReasonCategory;
+    use business_core_db::repository::count_by_key::CountByKey;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::sync::Arc;
+    use super::super::test_utils::test_utils::create_test_reason_with_category;
+
+    #[tokio::test]
+    async fn test_count_by_category_hash() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+        let sql_repo = ReasonRepositoryImpl::new_with_cache_policy(
+            reason_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+        let category_hash = hash_as_i64(&ReasonCategory::Compliance.to_string())?;
+
+        // Other reasons of the category may exist, count the ones added here
+        let cached_before = reason_repo.count_by_i64_key("category_hash", category_hash).await?;
+        let sql_before = sql_repo.count_by_i64_key("category_hash", category_hash).await?;
+        assert_eq!(cached_before, sql_before);
+
+        let reasons = (0..3)
+            .map(|i| {
+                create_test_reason_with_category(
+                    &format!("COUNT_CAT_{i}"),
+                    &format!("Counted Reason {i}"),
+                    ReasonCategory::Compliance,
+                )
+            })
+            .collect();
+        reason_repo.create_batch(reasons, None).await?;
+
+        let cached = reason_repo.count_by_i64_key("category_hash", category_hash).await?;
+        let sql = sql_repo.count_by_i64_key("category_hash", category_hash).await?;
+        assert_eq!(cached, cached_before + 3);
+        assert_eq!(sql, cached);
+
+        Ok(())
+    }
+}
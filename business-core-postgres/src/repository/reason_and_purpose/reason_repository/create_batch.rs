@@ -1,11 +1,16 @@
 use async_trait::async_trait;
-use business_core_db::models::reason_and_purpose::reason::ReasonModel;
+use business_core_db::models::reason_and_purpose::reason::{ReasonIdxModel, ReasonModel};
 use business_core_db::repository::create_batch::CreateBatch;
-use sqlx::Postgres;
+use sqlx::{PgConnection, Postgres};
+use std::collections::HashSet;
 use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::index_aware::IndexAware;
 
+use crate::error::RepositoryError;
+use crate::repository::find_by_i64_key::FindByI64Key;
+
+use crate::repository::cache_versions::CacheVersions;
 use super::repo_impl::ReasonRepositoryImpl;
 
 impl ReasonRepositoryImpl {
@@ -16,84 +21,163 @@ impl ReasonRepositoryImpl {
         if items.is_empty() {
             return Ok(Vec::new());
         }
+        Self::check_language_columns(&items)?;
+        repo.check_unique_codes(&items).await?;
 
-        let mut saved_items = Vec::new();
-        let mut indices = Vec::new();
-        
-        // Acquire lock once and do all database operations
-        {
-            let mut tx = repo.executor.tx.lock().await;
+        let (saved_items, indices) = {
+            let mut tx = repo.operation_timeout.lock("reason", &repo.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
-            
-            for item in items {
-                // Execute main insert
-                sqlx::query(
-                    r#"
-                    INSERT INTO reason (
-                        id, code, category, context,
-                        l1_content, l2_content, l3_content,
-                        l1_language_code, l2_language_code, l3_language_code,
-                        requires_details, is_active, severity, display_order,
-                        compliance_metadata
-                    )
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-                    "#,
+            Self::insert_in_connection(&mut **transaction, items).await?
+        };
+        
+        // Update cache after releasing transaction lock
+        if repo.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|idx| idx.id).collect();
+            {
+                let cache = repo.reason_idx_cache.read().await;
+                for idx in indices {
+                    if repo.cache_versions.admit(idx.id, CacheVersions::INITIAL) {
+                        cache.add(idx);
+                    }
+                }
+            }
+            repo.record_cache_use(&ids);
+        }
+
+        Ok(saved_items)
+    }
+
+    /// Reject the batch with `RepositoryError::UnpairedLanguageColumn` when a content
+    /// column is set without its language code, or a language code without its content
+    pub(super) fn check_language_columns(items: &[ReasonModel]) -> Result<(), RepositoryError> {
+        for item in items {
+            let levels = [
+                (1, item.l1_content.is_some(), item.l1_language_code.is_some()),
+                (2, item.l2_content.is_some(), item.l2_language_code.is_some()),
+                (3, item.l3_content.is_some(), item.l3_language_code.is_some()),
+            ];
+            for (level, has_content, has_code) in levels {
+                let (present, missing) = match (has_content, has_code) {
+                    (true, false) => ("content", "language_code"),
+                    (false, true) => ("language_code", "content"),
+                    _ => continue,
+                };
+                return Err(RepositoryError::UnpairedLanguageColumn {
+                    id: item.id,
+                    level,
+                    present,
+                    missing,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject the batch with `RepositoryError::DuplicateCode` when a code is repeated
+    /// within `items` or already taken by a stored reason
+    ///
+    /// Stored reasons are found through `find_matching_by_i64_key` and their actual code is
+    /// compared, so a hash collision is not reported as a duplicate.
+    pub(super) async fn check_unique_codes(
+        &self,
+        items: &[ReasonModel],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut codes = HashSet::new();
+        for item in items {
+            if !codes.insert(item.code.as_str()) {
+                return Err(RepositoryError::DuplicateCode(item.code.to_string()).into());
+            }
+        }
+
+        for item in items {
+            let taken = self
+                .find_matching_by_i64_key(
+                    "code_hash",
+                    item.to_index().code_hash,
+                    |idx| idx.id != item.id,
+                    |existing: &ReasonModel| existing.code == item.code,
                 )
-                .bind(item.id)
-                .bind(item.code.as_str())
-                .bind(item.category)
-                .bind(item.context)
-                .bind(item.l1_content.as_ref().map(|s| s.as_str()))
-                .bind(item.l2_content.as_ref().map(|s| s.as_str()))
-                .bind(item.l3_content.as_ref().map(|s| s.as_str()))
-                .bind(item.l1_language_code.as_ref().map(|s| s.as_str()))
-                .bind(item.l2_language_code.as_ref().map(|s| s.as_str()))
-                .bind(item.l3_language_code.as_ref().map(|s| s.as_str()))
-                .bind(item.requires_details)
-                .bind(item.is_active)
-                .bind(item.severity)
-                .bind(item.display_order)
-                .bind(item.compliance_metadata)
-                .execute(&mut **transaction)
                 .await?;
+            if !taken.is_empty() {
+                return Err(RepositoryError::DuplicateCode(item.code.to_string()).into());
+            }
+        }
+
+        Ok(())
+    }
 
-                // Insert into index table
-                let idx = item.to_index();
-                sqlx::query(
-                    r#"
-                    INSERT INTO reason_idx (
-                        id, code_hash, category_hash, context_hash, compliance_metadata
-                    )
-                    VALUES ($1, $2, $3, $4, $5)
-                    "#,
+    /// Write the reason inserts on an already locked connection
+    ///
+    /// Returns the persisted items and their index models; the caller decides whether
+    /// the index models go to the reason_idx cache.
+    pub(super) async fn insert_in_connection(
+        conn: &mut PgConnection,
+        items: Vec<ReasonModel>,
+    ) -> Result<(Vec<ReasonModel>, Vec<ReasonIdxModel>), Box<dyn Error + Send + Sync>> {
+        let mut saved_items = Vec::new();
+        let mut indices = Vec::new();
+
+        for item in items {
+            // Execute main insert
+            sqlx::query(
+                r#"
+                INSERT INTO reason (
+                    id, code, category, context,
+                    l1_content, l2_content, l3_content,
+                    l1_language_code, l2_language_code, l3_language_code,
+                    requires_details, is_active, severity, display_order,
+                    compliance_metadata
                 )
-                .bind(idx.id)
-                .bind(idx.code_hash)
-                .bind(idx.category_hash)
-                .bind(idx.context_hash)
-                .bind(idx.compliance_metadata)
-                .execute(&mut **transaction)
-                .await?;
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                "#,
+            )
+            .bind(item.id)
+            .bind(item.code.as_str())
+            .bind(item.category)
+            .bind(item.context)
+            .bind(item.l1_content.as_ref().map(|s| s.as_str()))
+            .bind(item.l2_content.as_ref().map(|s| s.as_str()))
+            .bind(item.l3_content.as_ref().map(|s| s.as_str()))
+            .bind(item.l1_language_code.as_ref().map(|s| s.as_str()))
+            .bind(item.l2_language_code.as_ref().map(|s| s.as_str()))
+            .bind(item.l3_language_code.as_ref().map(|s| s.as_str()))
+            .bind(item.requires_details)
+            .bind(item.is_active)
+            .bind(item.severity)
+            .bind(item.display_order)
+            .bind(item.compliance_metadata)
+            .execute(&mut *conn)
+            .await?;
 
-                indices.push(idx);
-                saved_items.push(item);
-            }
-        } // Transaction lock released here
-        
-        // Update cache after releasing transaction lock
-        {
-            let cache = repo.reason_idx_cache.read().await;
-            for idx in indices {
-                cache.add(idx);
-            }
+            // Insert into index table
+            let idx = item.to_index();
+            sqlx::query(
+                r#"
+                INSERT INTO reason_idx (
+                    id, code_hash, category_hash, context_hash, compliance_metadata
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(idx.id)
+            .bind(idx.code_hash)
+            .bind(idx.category_hash)
+            .bind(idx.context_hash)
+            .bind(idx.compliance_metadata)
+            .execute(&mut *conn)
+            .await?;
+
+            indices.push(idx);
+            saved_items.push(item);
         }
 
-        Ok(saved_items)
+        Ok((saved_items, indices))
     }
 }
 
 #[async_trait]
 impl CreateBatch<Postgres, ReasonModel> for ReasonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "reason", count = items.len())))]
     async fn create_batch(
         &self,
         items: Vec<ReasonModel>,
@@ -108,8 +192,11 @@ mod tests {
     use crate::test_helper::{setup_test_context, setup_test_context_and_listen};
     use business_core_db::models::index_aware::IndexAware;
     use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
     use tokio::time::{sleep, Duration};
     use super::super::test_utils::test_utils::create_test_reason;
+    use crate::error::RepositoryError;
+    use heapless::String as HeaplessString;
 
     #[tokio::test]
     async fn test_create_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -253,4 +340,114 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_batch_rejects_existing_code() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        reason_repo
+            .create_batch(vec![create_test_reason("DUPLICATE_DB", "Original")], None)
+            .await?;
+
+        let duplicate = create_test_reason("DUPLICATE_DB", "Duplicate");
+        let duplicate_id = duplicate.id;
+        let error = reason_repo
+            .create_batch(vec![create_test_reason("DUPLICATE_DB_OTHER", "Other"), duplicate], None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::DuplicateCode(code)) if code == "DUPLICATE_DB"
+        ));
+        // Nothing of the batch was written
+        assert!(reason_repo.load(duplicate_id).await?.is_none());
+        assert!(reason_repo
+            .find_by_code_hash(create_test_reason("DUPLICATE_DB_OTHER", "").to_index().code_hash)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_rejects_code_repeated_in_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let reasons = vec![
+            create_test_reason("DUPLICATE_BATCH", "First"),
+            create_test_reason("DUPLICATE_BATCH_OTHER", "Other"),
+            create_test_reason("DUPLICATE_BATCH", "Second"),
+        ];
+        let ids: Vec<_> = reasons.iter().map(|reason| reason.id).collect();
+        let error = reason_repo.create_batch(reasons, None).await.unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::DuplicateCode(code)) if code == "DUPLICATE_BATCH"
+        ));
+        assert!(reason_repo.load_batch(&ids).await?.iter().all(Option::is_none));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_rejects_content_without_language_code() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let mut reason = create_test_reason("UNPAIRED_CONTENT", "English");
+        reason.l2_content = Some(HeaplessString::try_from("Français").unwrap());
+        let reason_id = reason.id;
+        let error = reason_repo.create_batch(vec![reason], None).await.unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::UnpairedLanguageColumn { id, level: 2, present: "content", missing: "language_code" })
+                if *id == reason_id
+        ));
+        assert!(reason_repo.load(reason_id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_rejects_language_code_without_content() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let mut reason = create_test_reason("UNPAIRED_CODE", "English");
+        reason.l3_language_code = Some(HeaplessString::try_from("fra").unwrap());
+        let error = reason_repo
+            .create_batch(vec![create_test_reason("UNPAIRED_CODE_OTHER", "Other"), reason], None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::UnpairedLanguageColumn { level: 3, present: "language_code", missing: "content", .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_accepts_paired_language_columns() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let mut reason = create_test_reason("PAIRED_LANGUAGES", "English");
+        reason.l2_content = Some(HeaplessString::try_from("Français").unwrap());
+        reason.l2_language_code = Some(HeaplessString::try_from("fra").unwrap());
+        reason.l3_content = Some(HeaplessString::try_from("Deutsch").unwrap());
+        reason.l3_language_code = Some(HeaplessString::try_from("deu").unwrap());
+        let saved = reason_repo.create_batch(vec![reason], None).await?;
+
+        assert_eq!(saved[0].get_content(b"fra"), Some("Français"));
+        assert_eq!(saved[0].get_content(b"deu"), Some("Deutsch"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use business_core_db::models::reason_and_purpose::reason::ReasonModel;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::dry_run::{begin_dry_run, end_dry_run};
+
+use super::repo_impl::ReasonRepositoryImpl;
+
+impl ReasonRepositoryImpl {
+    /// Validate a `create_batch` without persisting it
+    ///
+    /// Runs every step of `create_batch`, including the language and code uniqueness checks and the SQL
+    /// inserts, inside a savepoint
+    /// that is rolled back whatever the outcome. The reason_idx cache is not touched.
+    /// Returns the models as `create_batch` would persist them.
+    pub async fn create_batch_dry_run(
+        &self,
+        items: Vec<ReasonModel>,
+        _audit_log_id: Option<Uuid>,
+    ) -> Result<Vec<ReasonModel>, Box<dyn Error + Send + Sync>> {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+        Self::check_language_columns(&items)?;
+        self.check_unique_codes(&items).await?;
+
+        let mut tx = self.operation_timeout.lock("reason", &self.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        begin_dry_run(&mut **transaction).await?;
+        let result = Self::insert_in_connection(&mut **transaction, items).await;
+        end_dry_run(&mut **transaction).await?;
+
+        let (saved_items, _indices) = result?;
+        Ok(saved_items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_helper::{random, setup_test_context};
+    use uuid::Uuid;
+    use super::super::test_utils::test_utils::create_test_reason;
+
+    #[tokio::test]
+    async fn test_create_batch_dry_run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let prefix = random(6);
+        let reasons: Vec<_> = (0..50)
+            .map(|i| create_test_reason(&format!("DRY_{prefix}_{i}"), &format!("Dry run reason {i}")))
+            .collect();
+        let ids: Vec<Uuid> = reasons.iter().map(|r| r.id).collect();
+
+        let validated = reason_repo.create_batch_dry_run(reasons, None).await?;
+        assert_eq!(validated.len(), 50);
+
+        {
+            let mut tx = reason_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reason WHERE id = ANY($1)")
+                .bind(&ids)
+                .fetch_one(&mut **transaction)
+                .await?;
+            assert_eq!(count, 0);
+        }
+        {
+            let cache = reason_repo.reason_idx_cache.read().await;
+            assert!(ids.iter().all(|id| !cache.contains_primary(id)));
+        }
+
+        Ok(())
+    }
+}
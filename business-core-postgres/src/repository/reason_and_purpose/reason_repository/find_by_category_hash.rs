@@ -1,6 +1,7 @@
 use std::error::Error;
 use business_core_db::models::reason_and_purpose::reason::ReasonIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ReasonRepositoryImpl;
 
 impl ReasonRepositoryImpl {
@@ -8,9 +9,7 @@ impl ReasonRepositoryImpl {
         &self,
         category_hash: i64,
     ) -> Result<Vec<ReasonIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.reason_idx_cache.read().await;
-        let items = cache.get_by_i64_index("category_hash", &category_hash);
-        Ok(items)
+        self.find_by_i64_key("category_hash", category_hash).await
     }
 }
 
@@ -1,6 +1,8 @@
 use std::error::Error;
 use business_core_db::models::reason_and_purpose::reason::ReasonIdxModel;
 
+use crate::repository::cache_health::Freshness;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ReasonRepositoryImpl;
 
 impl ReasonRepositoryImpl {
@@ -8,9 +10,16 @@ impl ReasonRepositoryImpl {
         &self,
         code_hash: i64,
     ) -> Result<Vec<ReasonIdxModel>, Box<dyn Error + Send + Sync>> {
-        let cache = self.reason_idx_cache.read().await;
-        let items = cache.get_by_i64_index("code_hash", &code_hash);
-        Ok(items)
+        self.find_by_code_hash_with_freshness(code_hash, Freshness::CacheOk).await
+    }
+
+    /// Same as `find_by_code_hash`, as fresh as `freshness` asks
+    pub async fn find_by_code_hash_with_freshness(
+        &self,
+        code_hash: i64,
+        freshness: Freshness,
+    ) -> Result<Vec<ReasonIdxModel>, Box<dyn Error + Send + Sync>> {
+        self.find_by_i64_key_with_freshness("code_hash", code_hash, freshness).await
     }
 }
 
@@ -19,6 +28,7 @@ mod tests {
     use crate::test_helper::setup_test_context;
     use business_core_db::repository::create_batch::CreateBatch;
     use business_core_db::utils::hash_as_i64;
+    use crate::repository::cache_health::Freshness;
     use super::super::test_utils::test_utils::create_test_reason;
 
     #[tokio::test]
@@ -29,20 +39,19 @@ mod tests {
         let test_code = "FIND_BY_CODE_TEST";
         let expected_hash = hash_as_i64(&test_code).unwrap();
         
-        let mut reasons = Vec::new();
-        for i in 0..3 {
-            reasons.push(create_test_reason(test_code, &format!("Test Reason {i}")));
+        // Codes are unique, only the first reason carries the searched code
+        let mut reasons = vec![create_test_reason(test_code, "Test Reason 0")];
+        for i in 1..3 {
+            reasons.push(create_test_reason(&format!("{test_code}_{i}"), &format!("Test Reason {i}")));
         }
 
         let saved = reason_repo.create_batch(reasons, None).await?;
 
         let found = reason_repo.find_by_code_hash(expected_hash).await?;
         
-        assert_eq!(found.len(), 3);
-        for saved_reason in &saved {
-            assert!(found.iter().any(|idx| idx.id == saved_reason.id));
-            assert!(found.iter().all(|idx| idx.code_hash == expected_hash));
-        }
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+        assert_eq!(found[0].code_hash, expected_hash);
 
         Ok(())
     }
@@ -59,4 +68,53 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_find_by_code_hash_freshness_when_degraded() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let cached_hash = hash_as_i64(&"FRESHNESS_CACHED").unwrap();
+        let fresh_hash = hash_as_i64(&"FRESHNESS_FRESH").unwrap();
+        let reason_id = reason_repo
+            .create_batch(vec![create_test_reason("FRESHNESS_CACHED", "Freshness")], None)
+            .await?[0]
+            .id;
+
+        // A change the cache never heard of, as when its notification is lost
+        {
+            let mut tx = reason_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE reason_idx SET code_hash = $1 WHERE id = $2")
+                .bind(fresh_hash)
+                .bind(reason_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+
+        reason_repo.cache_health.mark_degraded("listener connection lost");
+
+        // CacheOk serves the stale value
+        let found = reason_repo.find_by_code_hash(cached_hash).await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![reason_id]);
+
+        // RequireFresh hits SQL
+        assert!(reason_repo
+            .find_by_code_hash_with_freshness(cached_hash, Freshness::RequireFresh)
+            .await?
+            .is_empty());
+        let found = reason_repo
+            .find_by_code_hash_with_freshness(fresh_hash, Freshness::RequireFresh)
+            .await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![reason_id]);
+
+        // Once healthy again, RequireFresh trusts the cache
+        reason_repo.cache_health.mark_healthy();
+        let found = reason_repo
+            .find_by_code_hash_with_freshness(cached_hash, Freshness::RequireFresh)
+            .await?;
+        assert_eq!(found.iter().map(|idx| idx.id).collect::<Vec<_>>(), vec![reason_id]);
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use std::error::Error;
 use uuid::Uuid;
 use business_core_db::models::reason_and_purpose::reason::ReasonIdxModel;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ReasonRepositoryImpl;
 
 impl ReasonRepositoryImpl {
@@ -9,6 +10,9 @@ impl ReasonRepositoryImpl {
         &self,
         compliance_metadata: Uuid,
     ) -> Result<Vec<ReasonIdxModel>, Box<dyn Error + Send + Sync>> {
+        if !self.serves_key_from_cache("compliance_metadata", compliance_metadata.into()) {
+            return self.find_idx_by_column("compliance_metadata", compliance_metadata).await;
+        }
         let cache = self.reason_idx_cache.read().await;
         let items = cache.get_by_uuid_index("compliance_metadata", &compliance_metadata);
         Ok(items)
@@ -2,6 +2,7 @@ pub mod repo_impl;
 pub use repo_impl::ReasonRepositoryImpl;
 
 pub mod create_batch;
+pub mod create_batch_dry_run;
 pub mod load_batch;
 pub mod update_batch;
 pub mod delete_batch;
@@ -10,4 +11,6 @@ pub mod find_by_code_hash;
 pub mod find_by_category_hash;
 pub mod find_by_context_hash;
 pub mod find_by_compliance_metadata;
+pub mod active_reasons_by_context;
+pub mod count_by_key;
 pub mod test_utils;
\ No newline at end of file
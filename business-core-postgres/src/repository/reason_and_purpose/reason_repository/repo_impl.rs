@@ -1,4 +1,8 @@
 use business_core_db::models::reason_and_purpose::reason::{ReasonIdxModel, ReasonModel};
+use crate::repository::cache_health::CacheHealth;
+use crate::repository::cache_policy::CachePolicy;
+use crate::repository::cache_capacity::CacheBound;
+use crate::repository::cache_versions::{CacheVersions, PendingVersions};
 use crate::utils::{get_heapless_string, get_optional_heapless_string, TryFromRow};
 use postgres_unit_of_work::{Executor, TransactionAware, TransactionResult};
 use postgres_index_cache::TransactionAwareIdxModelCache;
@@ -7,32 +11,66 @@ use tokio::sync::RwLock;
 use std::sync::Arc;
 use sqlx::{postgres::PgRow, Row};
 use std::error::Error;
+use uuid::Uuid;
+use crate::repository::find_by_i64_key::FindByI64Key;
 use async_trait::async_trait;
+use crate::repository::refresh_idx_cache::RefreshIdxCache;
+use crate::repository::operation_timeout::OperationTimeout;
 
 pub struct ReasonRepositoryImpl {
     pub executor: Executor,
+    /// Bounds on the transaction lock wait, see `OperationTimeout`
+    pub operation_timeout: OperationTimeout,
     pub reason_idx_cache: Arc<RwLock<TransactionAwareIdxModelCache<ReasonIdxModel>>>,
+    /// Cache shared by the repositories of the factory, see `RefreshIdxCache`
+    pub reason_idx_shared_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ReasonIdxModel>>>,
+    pub cache_policy: CachePolicy,
+    /// Versions written to the reason_idx cache by the session, recorded on commit in the versions
+    /// shared with the coalescer of its notifications
+    pub cache_versions: PendingVersions,
+    /// Bound on the reason_idx cache, `None` leaves it unbounded
+    pub cache_bound: Option<CacheBound>,
+    /// Health of the reason_idx notifications, consulted by `Freshness::RequireFresh`
+    pub cache_health: CacheHealth,
 }
 
 impl ReasonRepositoryImpl {
     pub fn new(
         executor: Executor,
         reason_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ReasonIdxModel>>>,
+    ) -> Self {
+        Self::new_with_cache_policy(executor, reason_idx_cache, CachePolicy::Enabled)
+    }
+
+    pub fn new_with_cache_policy(
+        executor: Executor,
+        reason_idx_cache: Arc<ParkingRwLock<business_core_db::IdxModelCache<ReasonIdxModel>>>,
+        cache_policy: CachePolicy,
     ) -> Self {
         Self {
             executor,
+            operation_timeout: OperationTimeout::default(),
+            reason_idx_shared_cache: reason_idx_cache.clone(),
             reason_idx_cache: Arc::new(RwLock::new(TransactionAwareIdxModelCache::new(
                 reason_idx_cache,
             ))),
+            cache_policy,
+            cache_versions: PendingVersions::new(Arc::new(CacheVersions::new())),
+            cache_bound: None,
+            cache_health: CacheHealth::default(),
         }
     }
 
     pub async fn load_all_reason_idx(
         executor: &Executor,
+        operation_timeout: &OperationTimeout,
     ) -> Result<Vec<ReasonIdxModel>, sqlx::Error> {
         let query = sqlx::query("SELECT * FROM reason_idx");
         let rows = {
-            let mut tx = executor.tx.lock().await;
+            let mut tx = operation_timeout
+                .lock("reason", &executor.tx)
+                .await
+                .map_err(|e| sqlx::Error::Configuration(e.into()))?;
             if let Some(transaction) = tx.as_mut() {
                 query.fetch_all(&mut **transaction).await?
             } else {
@@ -51,10 +89,19 @@ impl ReasonRepositoryImpl {
 #[async_trait]
 impl TransactionAware for ReasonRepositoryImpl {
     async fn on_commit(&self) -> TransactionResult<()> {
-        self.reason_idx_cache.read().await.on_commit().await
+        self.reason_idx_cache.read().await.on_commit().await?;
+        self.cache_versions.on_commit();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_commit(&self.reason_idx_shared_cache, self.cache_versions.shared());
+        }
+        Ok(())
     }
 
     async fn on_rollback(&self) -> TransactionResult<()> {
+        self.cache_versions.on_rollback();
+        if let Some(bound) = &self.cache_bound {
+            bound.on_rollback();
+        }
         self.reason_idx_cache.read().await.on_rollback().await
     }
 }
@@ -84,11 +131,78 @@ impl TryFromRow<PgRow> for ReasonModel {
 impl TryFromRow<PgRow> for ReasonIdxModel {
     fn try_from_row(row: &PgRow) -> Result<Self, Box<dyn Error + Send + Sync>> {
         Ok(ReasonIdxModel {
-            id: row.get("reason_id"),
+            id: row.get("id"),
             code_hash: row.try_get("code_hash")?,
             category_hash: row.try_get("category_hash")?,
             context_hash: row.try_get("context_hash")?,
             compliance_metadata: row.get("compliance_metadata"),
         })
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl FindByI64Key for ReasonRepositoryImpl {
+    type Idx = ReasonIdxModel;
+
+    const ENTITY: &'static str = "reason";
+    const I64_KEYS: &'static [&'static str] = &["code_hash", "category_hash", "context_hash"];
+
+    fn executor(&self) -> &Executor {
+        &self.executor
+    }
+
+    fn operation_timeout(&self) -> &OperationTimeout {
+        &self.operation_timeout
+    }
+
+    fn idx_cache(&self) -> &RwLock<TransactionAwareIdxModelCache<ReasonIdxModel>> {
+        &self.reason_idx_cache
+    }
+
+    fn serves_from_cache(&self) -> bool {
+        self.cache_policy.serves_from_cache() && self.cache_bound.is_none()
+    }
+
+    fn maintains_cache(&self) -> bool {
+        self.cache_policy.maintains_cache()
+    }
+
+    fn cache_health(&self) -> Option<&CacheHealth> {
+        Some(&self.cache_health)
+    }
+
+    fn cache_bound(&self) -> Option<&CacheBound> {
+        self.cache_bound.as_ref()
+    }
+}
+
+#[async_trait]
+impl RefreshIdxCache for ReasonRepositoryImpl {
+    type Idx = ReasonIdxModel;
+
+    const ENTITY: &'static str = "reason";
+
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<business_core_db::IdxModelCache<ReasonIdxModel>>> {
+        &self.reason_idx_shared_cache
+    }
+
+    async fn load_all_idx(&self) -> Result<Vec<ReasonIdxModel>, Box<dyn Error + Send + Sync>> {
+        Ok(Self::load_all_reason_idx(&self.executor, &self.operation_timeout).await?)
+    }
+
+    fn warms_on_refresh(&self) -> bool {
+        self.cache_policy.maintains_cache() && self.cache_bound.is_none()
+    }
+
+    fn on_invalidated(&self, ids: Option<&[Uuid]>) {
+        if let Some(bound) = &self.cache_bound {
+            match ids {
+                Some(ids) => {
+                    bound.capacity().forget(ids);
+                    bound.capacity().invalidate_all_keys();
+                }
+                None => bound.capacity().clear(),
+            }
+        }
+    }
+}
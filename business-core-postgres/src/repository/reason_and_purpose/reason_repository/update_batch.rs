@@ -6,6 +6,7 @@ use sqlx::Postgres;
 use std::error::Error;
 use uuid::Uuid;
 
+use crate::repository::find_by_i64_key::FindByI64Key;
 use super::repo_impl::ReasonRepositoryImpl;
 
 impl ReasonRepositoryImpl {
@@ -22,7 +23,7 @@ impl ReasonRepositoryImpl {
         
         // Acquire lock once and do all database operations
         {
-            let mut tx = self.executor.tx.lock().await;
+            let mut tx = self.operation_timeout.lock("reason", &self.executor.tx).await?;
             let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
             
             for item in items {
@@ -58,11 +59,12 @@ impl ReasonRepositoryImpl {
 
                 // Update index table
                 let idx = item.to_index();
-                sqlx::query(
+                let version: Option<i64> = sqlx::query_scalar(
                     r#"
                     UPDATE reason_idx
                     SET code_hash = $2, category_hash = $3, context_hash = $4, compliance_metadata = $5
                     WHERE id = $1
+                    RETURNING version
                     "#,
                 )
                 .bind(idx.id)
@@ -70,21 +72,32 @@ impl ReasonRepositoryImpl {
                 .bind(idx.category_hash)
                 .bind(idx.context_hash)
                 .bind(idx.compliance_metadata)
-                .execute(&mut **transaction)
+                .fetch_optional(&mut **transaction)
                 .await?;
 
-                indices.push((item.id, idx));
+                // Without a reason_idx row there is nothing to cache
+                if let Some(version) = version {
+                    indices.push((idx, version as u64));
+                }
                 updated_items.push(item);
             }
         } // Transaction lock released here
         
         // Update cache after releasing transaction lock
-        {
-            let cache = self.reason_idx_cache.read().await;
-            for (id, idx) in indices {
-                cache.remove(&id);
-                cache.add(idx);
+        if self.cache_policy.maintains_cache() {
+            let ids: Vec<Uuid> = indices.iter().map(|(idx, _)| idx.id).collect();
+            {
+                let cache = self.reason_idx_cache.read().await;
+                for (idx, version) in indices {
+                    // A notification already brought a newer version of the row
+                    if !self.cache_versions.admit(idx.id, version) {
+                        continue;
+                    }
+                    cache.remove(&idx.id);
+                    cache.add(idx);
+                }
             }
+            self.record_cache_use(&ids);
         }
 
         Ok(updated_items)
@@ -93,6 +106,7 @@ impl ReasonRepositoryImpl {
 
 #[async_trait]
 impl UpdateBatch<Postgres, ReasonModel> for ReasonRepositoryImpl {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(entity = "reason", count = items.len())))]
     async fn update_batch(
         &self,
         items: Vec<ReasonModel>,
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use business_core_db::{HasPrimaryKey, IdxModelCache, Indexable};
+use parking_lot::RwLock as ParkingRwLock;
+use std::error::Error;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Recovery of an index cache gone stale after writes bypassing the repositories
+///
+/// Both operations act on the cache shared by all repositories of the factory, so
+/// they take effect at once, without waiting for the session to commit.
+#[async_trait]
+pub trait RefreshIdxCache: Send + Sync {
+    type Idx: HasPrimaryKey + Indexable + Clone + Send + Sync + 'static;
+
+    /// Entity name used in errors
+    const ENTITY: &'static str;
+
+    /// The cache shared by the repositories of the factory
+    fn shared_idx_cache(&self) -> &Arc<ParkingRwLock<IdxModelCache<Self::Idx>>>;
+
+    /// Every row of the idx table
+    async fn load_all_idx(&self) -> Result<Vec<Self::Idx>, Box<dyn Error + Send + Sync>>;
+
+    /// Whether `invalidate_and_refresh` reloads the cache after clearing it
+    ///
+    /// Repositories whose cache is disabled or bounded only clear it.
+    fn warms_on_refresh(&self) -> bool {
+        true
+    }
+
+    /// Called after entries were evicted: `Some(ids)` by `invalidate`, `None` when
+    /// `invalidate_and_refresh` cleared the whole cache
+    fn on_invalidated(&self, _ids: Option<&[Uuid]>) {}
+
+    /// Evict the entries of `ids`, later lookups miss them until they are added again
+    async fn invalidate(&self, ids: &[Uuid]) {
+        {
+            let mut cache = self.shared_idx_cache().write();
+            for id in ids {
+                cache.remove(id);
+            }
+        }
+        self.on_invalidated(Some(ids));
+    }
+
+    /// Clear the cache and reload it from the idx table
+    ///
+    /// Returns the number of entries loaded, 0 when `warms_on_refresh` is false.
+    async fn invalidate_and_refresh(&self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let items = if self.warms_on_refresh() {
+            self.load_all_idx().await?
+        } else {
+            Vec::new()
+        };
+        let loaded = items.len();
+        let cache = IdxModelCache::new(items)
+            .map_err(|e| format!("{}: index cache rebuild failed: {e:?}", Self::ENTITY))?;
+        *self.shared_idx_cache().write() = cache;
+        self.on_invalidated(None);
+        Ok(loaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RefreshIdxCache;
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::sync::Arc;
+
+    fn empty_cache() -> Arc<ParkingRwLock<business_core_db::IdxModelCache<business_core_db::models::person::person::PersonIdxModel>>> {
+        Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_refresh_after_out_of_band_writes() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let writer_repo = &ctx.person_repos().person_repository;
+        // A repository on its own cache, which the writes below bypass
+        let repo = PersonRepositoryImpl::new(writer_repo.executor.clone(), empty_cache());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let id_number = format!("ID-{}", random(8));
+        let id_number_hash = hash_as_i64(&id_number.as_str()).unwrap();
+        let mut person = create_test_person("Bulk Loaded Person");
+        person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+        let person_id = writer_repo.create_batch(vec![person], Some(audit_log.id)).await?[0].id;
+
+        // The cache does not know the row yet
+        assert!(repo.find_by_id_number_hash(id_number_hash).await?.is_empty());
+
+        let loaded = repo.invalidate_and_refresh().await?;
+        assert!(loaded >= 1);
+        let found = repo.find_by_id_number_hash(id_number_hash).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, person_id);
+
+        // A direct update leaves the refreshed cache stale again
+        let new_hash = id_number_hash.wrapping_add(1);
+        {
+            let mut tx = repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET id_number_hash = $2 WHERE id = $1")
+                .bind(person_id)
+                .bind(new_hash)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        assert_eq!(repo.find_by_id_number_hash(id_number_hash).await?.len(), 1);
+        assert!(repo.find_by_id_number_hash(new_hash).await?.is_empty());
+
+        repo.invalidate_and_refresh().await?;
+        assert!(repo.find_by_id_number_hash(id_number_hash).await?.is_empty());
+        assert_eq!(repo.find_by_id_number_hash(new_hash).await?.len(), 1);
+
+        // Targeted eviction
+        repo.invalidate(&[person_id]).await;
+        assert!(!repo.person_idx_cache.read().await.contains_primary(&person_id));
+        assert!(repo.find_by_id_number_hash(new_hash).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refresh_of_disabled_cache_only_clears() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let executor = ctx.person_repos().person_repository.executor.clone();
+        let repo = PersonRepositoryImpl::new_with_cache_policy(executor, empty_cache(), CachePolicy::Disabled);
+
+        assert_eq!(repo.invalidate_and_refresh().await?, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+/// Type-level marker for whether a factory builder constructs a repository
+///
+/// Used by the repository factory builders: the field of a selected repository is an
+/// `Arc` of the repository, the field of an unselected one is `()`, so using a
+/// repository that was not selected fails to compile.
+pub trait RepoSelection {
+    type Repo<R>;
+
+    fn build<R>(build: impl FnOnce() -> Arc<R>) -> Self::Repo<R>;
+}
+
+/// The repository is constructed
+pub struct Selected;
+
+/// The repository is not constructed
+pub struct Unselected;
+
+impl RepoSelection for Selected {
+    type Repo<R> = Arc<R>;
+
+    fn build<R>(build: impl FnOnce() -> Arc<R>) -> Arc<R> {
+        build()
+    }
+}
+
+impl RepoSelection for Unselected {
+    type Repo<R> = ();
+
+    fn build<R>(_build: impl FnOnce() -> Arc<R>) {}
+}
@@ -0,0 +1,4 @@
+pub mod service_impl;
+pub mod resolve_address;
+
+pub use service_impl::{AddressError, AddressService, AddressView};
@@ -0,0 +1,181 @@
+use sqlx::Row;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::utils::{get_heapless_string, get_optional_heapless_string};
+
+use super::service_impl::{AddressError, AddressService, AddressView};
+
+impl AddressService {
+    /// Resolve a location to its full address in one query
+    ///
+    /// Yields `AddressError::LocationNotFound` for an unknown location and
+    /// `AddressError::IncompleteAddress` when a link of the
+    /// location → locality → country_subdivision → country chain points to a missing row.
+    pub async fn resolve_address(&self, location_id: Uuid) -> Result<AddressView, Box<dyn Error + Send + Sync>> {
+        let row = {
+            let mut tx = self.location_repository.operation_timeout.lock("location", &self.location_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT
+                    l.id, l.street_line1, l.street_line2, l.street_line3, l.street_line4,
+                    l.postal_code, l.locality_id,
+                    lo.id AS locality_found, lo.code AS locality_code,
+                    lo.name_l1 AS locality_name_l1, lo.name_l2 AS locality_name_l2,
+                    lo.name_l3 AS locality_name_l3, lo.country_subdivision_id,
+                    cs.id AS country_subdivision_found, cs.code AS country_subdivision_code,
+                    cs.name_l1 AS country_subdivision_name_l1, cs.name_l2 AS country_subdivision_name_l2,
+                    cs.name_l3 AS country_subdivision_name_l3, cs.country_id,
+                    c.id AS country_found, c.iso2 AS country_iso2,
+                    c.name_l1 AS country_name_l1, c.name_l2 AS country_name_l2,
+                    c.name_l3 AS country_name_l3
+                FROM location l
+                LEFT JOIN locality lo ON lo.id = l.locality_id
+                LEFT JOIN country_subdivision cs ON cs.id = lo.country_subdivision_id
+                LEFT JOIN country c ON c.id = cs.country_id
+                WHERE l.id = $1
+                "#,
+            )
+            .bind(location_id)
+            .fetch_optional(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("location", e))?
+        };
+        let row = row.ok_or(AddressError::LocationNotFound(location_id))?;
+
+        let locality_id: Uuid = row.try_get("locality_id")?;
+        if row.try_get::<Option<Uuid>, _>("locality_found")?.is_none() {
+            return Err(AddressError::IncompleteAddress {
+                location_id,
+                missing: "locality",
+                missing_id: locality_id,
+            }
+            .into());
+        }
+        let country_subdivision_id: Uuid = row.try_get("country_subdivision_id")?;
+        if row.try_get::<Option<Uuid>, _>("country_subdivision_found")?.is_none() {
+            return Err(AddressError::IncompleteAddress {
+                location_id,
+                missing: "country_subdivision",
+                missing_id: country_subdivision_id,
+            }
+            .into());
+        }
+        let country_id: Uuid = row.try_get("country_id")?;
+        if row.try_get::<Option<Uuid>, _>("country_found")?.is_none() {
+            return Err(AddressError::IncompleteAddress {
+                location_id,
+                missing: "country",
+                missing_id: country_id,
+            }
+            .into());
+        }
+
+        Ok(AddressView {
+            location_id,
+            street_line1: get_heapless_string(&row, "street_line1")?,
+            street_line2: get_optional_heapless_string(&row, "street_line2")?,
+            street_line3: get_optional_heapless_string(&row, "street_line3")?,
+            street_line4: get_optional_heapless_string(&row, "street_line4")?,
+            postal_code: get_optional_heapless_string(&row, "postal_code")?,
+            locality_id,
+            locality_code: get_heapless_string(&row, "locality_code")?,
+            locality_name_l1: get_heapless_string(&row, "locality_name_l1")?,
+            locality_name_l2: get_optional_heapless_string(&row, "locality_name_l2")?,
+            locality_name_l3: get_optional_heapless_string(&row, "locality_name_l3")?,
+            country_subdivision_id,
+            country_subdivision_code: get_heapless_string(&row, "country_subdivision_code")?,
+            country_subdivision_name_l1: get_heapless_string(&row, "country_subdivision_name_l1")?,
+            country_subdivision_name_l2: get_optional_heapless_string(&row, "country_subdivision_name_l2")?,
+            country_subdivision_name_l3: get_optional_heapless_string(&row, "country_subdivision_name_l3")?,
+            country_id,
+            country_iso2: get_heapless_string(&row, "country_iso2")?,
+            country_name_l1: get_heapless_string(&row, "country_name_l1")?,
+            country_name_l2: get_optional_heapless_string(&row, "country_name_l2")?,
+            country_name_l3: get_optional_heapless_string(&row, "country_name_l3")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_country, create_test_country_subdivision,
+        create_test_locality, create_test_location,
+    };
+    use crate::service::address_service::{AddressError, AddressService};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_resolve_address() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let country = create_test_country("CM", "Cameroon");
+        let country_id = country.id;
+        person_repos.country_repository.create_batch(vec![country], Some(audit_log.id)).await?;
+        let subdivision = create_test_country_subdivision(country_id, "LT", "Littoral");
+        let subdivision_id = subdivision.id;
+        person_repos.country_subdivision_repository.create_batch(vec![subdivision], Some(audit_log.id)).await?;
+        let locality = create_test_locality(subdivision_id, "DLA", "Douala");
+        let locality_id = locality.id;
+        person_repos.locality_repository.create_batch(vec![locality], Some(audit_log.id)).await?;
+        let location = create_test_location(locality_id, "12 Rue de la Joie");
+        let location_id = location.id;
+        person_repos.location_repository.create_batch(vec![location], Some(audit_log.id)).await?;
+
+        let service = AddressService::new(person_repos);
+        let address = service.resolve_address(location_id).await?;
+
+        assert_eq!(address.street_line1.as_str(), "12 Rue de la Joie");
+        assert_eq!(address.locality_id, locality_id);
+        assert_eq!(address.locality_name_l1.as_str(), "Douala");
+        assert_eq!(address.country_subdivision_id, subdivision_id);
+        assert_eq!(address.country_subdivision_name_l1.as_str(), "Littoral");
+        assert_eq!(address.country_id, country_id);
+        assert_eq!(address.country_iso2.as_str(), "CM");
+        assert_eq!(address.country_name_l1.as_str(), "Cameroon");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_address_with_missing_locality() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // location.locality_id has no foreign key, the locality can dangle
+        let dangling_locality_id = Uuid::new_v4();
+        let location = create_test_location(dangling_locality_id, "1 Nowhere Street");
+        let location_id = location.id;
+        person_repos.location_repository.create_batch(vec![location], Some(audit_log.id)).await?;
+
+        let service = AddressService::new(person_repos);
+        let error = service.resolve_address(location_id).await.unwrap_err();
+        match error.downcast_ref::<AddressError>() {
+            Some(AddressError::IncompleteAddress { location_id: id, missing, missing_id }) => {
+                assert_eq!(*id, location_id);
+                assert_eq!(*missing, "locality");
+                assert_eq!(*missing_id, dangling_locality_id);
+            }
+            other => panic!("Expected IncompleteAddress, got {other:?}"),
+        }
+
+        let error = service.resolve_address(Uuid::new_v4()).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<AddressError>(), Some(AddressError::LocationNotFound(_))));
+
+        Ok(())
+    }
+}
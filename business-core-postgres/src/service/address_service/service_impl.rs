@@ -0,0 +1,62 @@
+use heapless::String as HeaplessString;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::repository::person::{LocationRepositoryImpl, PersonRepositories};
+
+/// Typed error of address resolution
+#[derive(Debug, Error)]
+pub enum AddressError {
+    #[error("Location {0} not found")]
+    LocationNotFound(Uuid),
+
+    #[error("Address of location {location_id} is incomplete: {missing} {missing_id} not found")]
+    IncompleteAddress {
+        location_id: Uuid,
+        missing: &'static str,
+        missing_id: Uuid,
+    },
+}
+
+/// A location with its locality, country subdivision and country, flattened
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressView {
+    pub location_id: Uuid,
+    pub street_line1: HeaplessString<50>,
+    pub street_line2: Option<HeaplessString<50>>,
+    pub street_line3: Option<HeaplessString<50>>,
+    pub street_line4: Option<HeaplessString<50>>,
+    pub postal_code: Option<HeaplessString<20>>,
+
+    pub locality_id: Uuid,
+    pub locality_code: HeaplessString<50>,
+    pub locality_name_l1: HeaplessString<50>,
+    pub locality_name_l2: Option<HeaplessString<50>>,
+    pub locality_name_l3: Option<HeaplessString<50>>,
+
+    pub country_subdivision_id: Uuid,
+    pub country_subdivision_code: HeaplessString<10>,
+    pub country_subdivision_name_l1: HeaplessString<100>,
+    pub country_subdivision_name_l2: Option<HeaplessString<100>>,
+    pub country_subdivision_name_l3: Option<HeaplessString<100>>,
+
+    pub country_id: Uuid,
+    pub country_iso2: HeaplessString<2>,
+    pub country_name_l1: HeaplessString<100>,
+    pub country_name_l2: Option<HeaplessString<100>>,
+    pub country_name_l3: Option<HeaplessString<100>>,
+}
+
+/// Service assembling full addresses from the location hierarchy
+pub struct AddressService {
+    pub location_repository: Arc<LocationRepositoryImpl>,
+}
+
+impl AddressService {
+    pub fn new(repos: &PersonRepositories) -> Self {
+        Self {
+            location_repository: repos.location_repository.clone(),
+        }
+    }
+}
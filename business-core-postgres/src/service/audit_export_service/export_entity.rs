@@ -0,0 +1,173 @@
+use business_core_db::models::audit::entity_type::EntityType;
+use business_core_db::models::audit::evidence_bundle::{verify_bundle, BundleVerification, EvidenceBundle};
+use business_core_db::models::audit_chained::order_chain;
+use business_core_db::models::person::person::PersonModel;
+use business_core_db::repository::load_audits::LoadAudits;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::repository::pagination::PageRequest;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::{AuditExportService, AuditExportServiceError};
+
+/// Page size used to read the audit rows of an entity
+const AUDIT_PAGE_SIZE: usize = 100;
+
+/// Evidence bundle of one entity, by entity type
+#[derive(Debug, Clone)]
+pub enum EntityEvidence {
+    Person(EvidenceBundle<PersonModel>),
+}
+
+impl EntityEvidence {
+    /// Verify the bundle offline, see `verify_bundle`
+    pub fn verify(&self) -> Result<BundleVerification, String> {
+        match self {
+            EntityEvidence::Person(bundle) => verify_bundle(bundle),
+        }
+    }
+}
+
+impl AuditExportService {
+    /// Export every audit row of an entity with its audit logs as an evidence bundle
+    ///
+    /// Only persons are supported, other entity types fail with
+    /// `AuditExportServiceError::UnsupportedEntityType`.
+    pub async fn export_entity(
+        &self,
+        entity_type: EntityType,
+        entity_id: Uuid,
+    ) -> Result<EntityEvidence, Box<dyn Error + Send + Sync>> {
+        match entity_type {
+            EntityType::Person => Ok(EntityEvidence::Person(self.export_person(entity_id).await?)),
+            other => Err(AuditExportServiceError::UnsupportedEntityType(other).into()),
+        }
+    }
+
+    /// Export the person_audit rows of a person, oldest first, with their audit logs
+    pub async fn export_person(
+        &self,
+        person_id: Uuid,
+    ) -> Result<EvidenceBundle<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let mut audits = Vec::new();
+        loop {
+            let page = self
+                .person_repository
+                .load_audits(person_id, PageRequest::new(AUDIT_PAGE_SIZE, audits.len()))
+                .await?;
+            let done = page.items.is_empty() || audits.len() + page.items.len() >= page.total;
+            audits.extend(page.items);
+            if done {
+                break;
+            }
+        }
+        if audits.is_empty() {
+            return Err(AuditExportServiceError::NotAudited {
+                entity_type: EntityType::Person,
+                entity_id: person_id,
+            }
+            .into());
+        }
+        let audits = order_chain(audits);
+
+        let mut audit_log_ids: Vec<Uuid> = audits.iter().filter_map(|audit| audit.audit_log_id).collect();
+        audit_log_ids.sort();
+        audit_log_ids.dedup();
+        let audit_logs = self
+            .audit_log_repository
+            .load_batch(&audit_log_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(EvidenceBundle::new(EntityType::Person, person_id, audits, audit_logs)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{AuditExportService, AuditExportServiceError};
+    use super::EntityEvidence;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::entity_type::EntityType;
+    use business_core_db::models::audit::evidence_bundle::{verify_bundle, EvidenceBundle};
+    use business_core_db::models::person::person::PersonModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use heapless::String as HeaplessString;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_export_person_detects_tampered_row() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = AuditExportService::new(ctx.audit_repos(), ctx.person_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let mut person = person_repo
+            .create_batch(vec![create_test_person("Audited Person")], Some(audit_log.id))
+            .await?
+            .remove(0);
+        for display_name in ["Audited Person Renamed", "Audited Person Renamed Again"] {
+            let audit_log = create_test_audit_log();
+            audit_log_repo.create(&audit_log).await?;
+            person.display_name = HeaplessString::try_from(display_name).unwrap();
+            person = person_repo.update_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+        }
+
+        let bundle = match service.export_entity(EntityType::Person, person.id).await? {
+            EntityEvidence::Person(bundle) => bundle,
+        };
+        assert_eq!(bundle.audit_rows.len(), 3);
+        assert_eq!(bundle.audit_logs.len(), 3);
+        assert!(bundle.links.iter().all(|link| link.is_valid()));
+
+        // The serialized bundle verifies on its own
+        let mut json = serde_json::to_value(&bundle)?;
+        let exported: EvidenceBundle<PersonModel> = serde_json::from_value(json.clone())?;
+        assert!(verify_bundle(&exported)?.is_valid());
+
+        // Edit the middle version after export
+        json["audit_rows"][1]["display_name"] = serde_json::json!("Someone Else");
+        let tampered: EvidenceBundle<PersonModel> = serde_json::from_value(json)?;
+        let verification = verify_bundle(&tampered)?;
+        assert!(!verification.digest_matches);
+        assert_eq!(verification.failed_links.len(), 1);
+        let (index, link) = verification.failed_links[0];
+        assert_eq!(index, 1);
+        assert!(!link.hash_matches);
+        assert!(verification.missing_audit_logs.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_entity_errors() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let service = AuditExportService::new(ctx.audit_repos(), ctx.person_repos());
+
+        let error = service
+            .export_entity(EntityType::Person, Uuid::new_v4())
+            .await
+            .expect_err("A person without audit rows cannot be exported");
+        assert!(matches!(
+            error.downcast_ref::<AuditExportServiceError>(),
+            Some(AuditExportServiceError::NotAudited { entity_type: EntityType::Person, .. })
+        ));
+
+        let error = service
+            .export_entity(EntityType::Document, Uuid::new_v4())
+            .await
+            .expect_err("Documents are not exported");
+        assert!(matches!(
+            error.downcast_ref::<AuditExportServiceError>(),
+            Some(AuditExportServiceError::UnsupportedEntityType(EntityType::Document))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod export_entity;
+
+pub use service_impl::{AuditExportService, AuditExportServiceError};
+pub use export_entity::EntityEvidence;
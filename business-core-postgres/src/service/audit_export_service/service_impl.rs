@@ -0,0 +1,36 @@
+use business_core_db::models::audit::entity_type::EntityType;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::repository::audit::audit_log_repository::AuditLogRepositoryImpl;
+use crate::repository::audit::AuditRepositories;
+use crate::repository::person::{PersonRepositories, PersonRepositoryImpl};
+
+/// Typed error of the audit export service
+#[derive(Debug, Error)]
+pub enum AuditExportServiceError {
+    #[error("Audit export is not supported for entity type {0:?}")]
+    UnsupportedEntityType(EntityType),
+
+    #[error("{entity_type:?} {entity_id} has no audit rows")]
+    NotAudited { entity_type: EntityType, entity_id: Uuid },
+}
+
+/// Service exporting the audit trail of an entity as an evidence bundle
+///
+/// The audit rows and audit logs of a bundle are read in one transaction, so the
+/// bundle verifies as a whole.
+pub struct AuditExportService {
+    pub audit_log_repository: Arc<AuditLogRepositoryImpl>,
+    pub person_repository: Arc<PersonRepositoryImpl>,
+}
+
+impl AuditExportService {
+    pub fn new(audit_repos: &AuditRepositories, person_repos: &PersonRepositories) -> Self {
+        Self {
+            audit_log_repository: audit_repos.audit_log_repository.clone(),
+            person_repository: person_repos.person_repository.clone(),
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use business_core_db::models::audit_chained::ArchivedAntecedent;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+
+use super::audit_sink::{AuditChunk, AuditSink};
+use super::service_impl::{AuditRetentionError, AuditRetentionService, AUDITED_TABLES};
+
+/// Rows read from a table per chunk
+const ARCHIVE_CHUNK_SIZE: i64 = 500;
+
+/// Rows archived by `archive_before`, per table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveReport {
+    pub archived: BTreeMap<&'static str, usize>,
+}
+
+impl ArchiveReport {
+    /// Rows archived from `table`
+    pub fn archived(&self, table: &str) -> usize {
+        self.archived.get(table).copied().unwrap_or(0)
+    }
+
+    /// Rows archived from all tables
+    pub fn total(&self) -> usize {
+        self.archived.values().sum()
+    }
+}
+
+impl AuditRetentionService {
+    /// Archive the audit rows older than `cutoff` into `sink`, then delete them
+    ///
+    /// Each table is read in chunks of `ARCHIVE_CHUNK_SIZE` rows, keyset-paginated on its
+    /// primary key. A chunk is deleted only after `sink` confirms it; when the sink fails
+    /// the chunk stays and the error is `AuditRetentionError::SinkFailed`.
+    ///
+    /// A version is older than `cutoff` when its audit log is. The newest version of each
+    /// entity is never archived, whatever its age, nor the version the entity row points
+    /// to, so the live `audit_log_id` and hash keep resolving. For each entity whose
+    /// oldest remaining version now links to an archived one, that version is recorded
+    /// in audit_archive_marker, see `archived_antecedent`.
+    ///
+    /// The audit_link and audit_log rows older than `cutoff` that no remaining audit or
+    /// entity row references are archived last, the same way.
+    pub async fn archive_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        sink: &mut impl AuditSink,
+    ) -> Result<ArchiveReport, Box<dyn Error + Send + Sync>> {
+        let mut report = ArchiveReport::default();
+
+        for (audit_table, entity_table) in AUDITED_TABLES {
+            let select = format!(
+                r#"
+                SELECT to_jsonb(t) AS row, t.id AS key_a, t.audit_log_id AS key_b
+                FROM {audit_table} t
+                JOIN audit_log l ON l.id = t.audit_log_id
+                WHERE l.updated_at < $1
+                AND (t.id, t.audit_log_id) > ($2, $3)
+                AND EXISTS (
+                    SELECT 1 FROM {audit_table} newer
+                    JOIN audit_log newer_log ON newer_log.id = newer.audit_log_id
+                    WHERE newer.id = t.id AND newer_log.updated_at > l.updated_at
+                )
+                AND NOT EXISTS (
+                    SELECT 1 FROM {entity_table} e WHERE e.id = t.id AND e.audit_log_id = t.audit_log_id
+                )
+                ORDER BY t.id, t.audit_log_id
+                LIMIT $4
+                "#
+            );
+            // The marker is the deleted version the oldest remaining version links to
+            let delete = format!(
+                r#"
+                WITH archived AS (
+                    DELETE FROM {audit_table} t
+                    USING UNNEST($1::UUID[], $2::UUID[]) AS k(id, audit_log_id)
+                    WHERE t.id = k.id AND t.audit_log_id = k.audit_log_id
+                    RETURNING t.id, t.audit_log_id, t.hash
+                )
+                INSERT INTO audit_archive_marker (table_name, entity_id, audit_log_id, hash, archived_at)
+                SELECT '{audit_table}', a.id, a.audit_log_id, a.hash, NOW()
+                FROM archived a
+                WHERE EXISTS (
+                    SELECT 1 FROM {audit_table} remaining
+                    WHERE remaining.id = a.id AND remaining.antecedent_audit_log_id = a.audit_log_id
+                    AND NOT EXISTS (
+                        SELECT 1 FROM archived gone
+                        WHERE gone.id = remaining.id AND gone.audit_log_id = remaining.audit_log_id
+                    )
+                )
+                ON CONFLICT (table_name, entity_id) DO UPDATE SET
+                audit_log_id = EXCLUDED.audit_log_id, hash = EXCLUDED.hash, archived_at = EXCLUDED.archived_at
+                "#
+            );
+            let archived = self.archive_chunks(audit_table, &select, &delete, cutoff, sink).await?;
+            report.archived.insert(audit_table, archived);
+        }
+
+        let unreferenced = |column: &str| {
+            AUDITED_TABLES
+                .iter()
+                .flat_map(|(audit_table, entity_table)| [audit_table, entity_table])
+                .map(|table| format!("NOT EXISTS (SELECT 1 FROM {table} r WHERE r.audit_log_id = {column})"))
+                .collect::<Vec<_>>()
+                .join("\nAND ")
+        };
+
+        let select = format!(
+            r#"
+            SELECT to_jsonb(k) AS row, k.audit_log_id AS key_a, k.entity_id AS key_b
+            FROM audit_link k
+            JOIN audit_log l ON l.id = k.audit_log_id
+            WHERE l.updated_at < $1
+            AND (k.audit_log_id, k.entity_id) > ($2, $3)
+            AND {}
+            ORDER BY k.audit_log_id, k.entity_id
+            LIMIT $4
+            "#,
+            unreferenced("k.audit_log_id")
+        );
+        let delete = r#"
+            DELETE FROM audit_link k
+            USING UNNEST($1::UUID[], $2::UUID[]) AS d(audit_log_id, entity_id)
+            WHERE k.audit_log_id = d.audit_log_id AND k.entity_id = d.entity_id
+            "#;
+        let archived = self.archive_chunks("audit_link", &select, delete, cutoff, sink).await?;
+        report.archived.insert("audit_link", archived);
+
+        // audit_log has a single key column, it stands for both keys
+        let select = format!(
+            r#"
+            SELECT to_jsonb(l) AS row, l.id AS key_a, l.id AS key_b
+            FROM audit_log l
+            WHERE l.updated_at < $1
+            AND (l.id, l.id) > ($2, $3)
+            AND NOT EXISTS (SELECT 1 FROM audit_link k WHERE k.audit_log_id = l.id)
+            AND {}
+            ORDER BY l.id
+            LIMIT $4
+            "#,
+            unreferenced("l.id")
+        );
+        let delete = r#"
+            DELETE FROM audit_log l
+            USING UNNEST($1::UUID[], $2::UUID[]) AS d(id, same_id)
+            WHERE l.id = d.id
+            "#;
+        let archived = self.archive_chunks("audit_log", &select, delete, cutoff, sink).await?;
+        report.archived.insert("audit_log", archived);
+
+        Ok(report)
+    }
+
+    /// The newest archived version of an entity, recorded by `archive_before`
+    ///
+    /// `None` when no version of the entity was archived from `audit_table`: its chain
+    /// is complete. Pass it to `verify_chain_from` with the remaining versions.
+    pub async fn archived_antecedent(
+        &self,
+        audit_table: &str,
+        entity_id: Uuid,
+    ) -> Result<Option<ArchivedAntecedent>, Box<dyn Error + Send + Sync>> {
+        let mut tx = self.audit_log_repository.operation_timeout.lock("audit_log", &self.audit_log_repository.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+
+        let marker: Option<(Uuid, i64)> = sqlx::query_as(
+            "SELECT audit_log_id, hash FROM audit_archive_marker WHERE table_name = $1 AND entity_id = $2",
+        )
+        .bind(audit_table)
+        .bind(entity_id)
+        .fetch_optional(&mut **transaction)
+        .await
+        .map_err(|e| map_db_error("audit_archive_marker", e))?;
+
+        Ok(marker.map(|(audit_log_id, hash)| ArchivedAntecedent { audit_log_id, hash }))
+    }
+
+    /// Move the rows of `table` selected by `select` to `sink`, chunk by chunk
+    ///
+    /// `select` binds the cutoff, the two key columns of the last row of the previous
+    /// chunk and the chunk size, and returns `row`, `key_a` and `key_b`. `delete` binds
+    /// the arrays of both keys of a chunk.
+    async fn archive_chunks(
+        &self,
+        table: &'static str,
+        select: &str,
+        delete: &str,
+        cutoff: DateTime<Utc>,
+        sink: &mut impl AuditSink,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut after = (Uuid::nil(), Uuid::nil());
+        let mut archived = 0;
+        loop {
+            let rows: Vec<(serde_json::Value, Uuid, Uuid)> = {
+                let mut tx = self.audit_log_repository.operation_timeout.lock("audit_log", &self.audit_log_repository.executor.tx).await?;
+                let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+                sqlx::query_as(select)
+                    .bind(cutoff)
+                    .bind(after.0)
+                    .bind(after.1)
+                    .bind(ARCHIVE_CHUNK_SIZE)
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error(table, e))?
+            };
+            let Some(&(_, key_a, key_b)) = rows.last() else {
+                return Ok(archived);
+            };
+            after = (key_a, key_b);
+
+            let (keys_a, keys_b): (Vec<Uuid>, Vec<Uuid>) = rows.iter().map(|(_, key_a, key_b)| (*key_a, *key_b)).unzip();
+            let chunk = AuditChunk {
+                table,
+                rows: rows.into_iter().map(|(row, _, _)| row).collect(),
+            };
+            sink.write_chunk(&chunk).await.map_err(|e| AuditRetentionError::SinkFailed {
+                table,
+                rows: chunk.rows.len(),
+                message: e.to_string(),
+            })?;
+
+            let mut tx = self.audit_log_repository.operation_timeout.lock("audit_log", &self.audit_log_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(delete)
+                .bind(&keys_a)
+                .bind(&keys_b)
+                .execute(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error(table, e))?;
+            archived += chunk.rows.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{AuditChunk, AuditRetentionError, AuditRetentionService, AuditSink};
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::test_helper::setup_test_context;
+    use async_trait::async_trait;
+    use business_core_db::models::audit::AuditLogModel;
+    use business_core_db::models::audit_chained::{order_chain, verify_chain_from, ChainError};
+    use business_core_db::models::person::person::PersonModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_audits::LoadAudits;
+    use business_core_db::repository::pagination::PageRequest;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use heapless::String as HeaplessString;
+    use serde_json::json;
+    use std::error::Error;
+    use uuid::Uuid;
+
+    /// Sink keeping the chunks in memory, or refusing them
+    #[derive(Default)]
+    struct MemorySink {
+        chunks: Vec<AuditChunk>,
+        refuse: bool,
+    }
+
+    impl MemorySink {
+        fn rows(&self, table: &str) -> Vec<&serde_json::Value> {
+            self.chunks
+                .iter()
+                .filter(|chunk| chunk.table == table)
+                .flat_map(|chunk| &chunk.rows)
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl AuditSink for MemorySink {
+        async fn write_chunk(&mut self, chunk: &AuditChunk) -> Result<(), Box<dyn Error + Send + Sync>> {
+            if self.refuse {
+                return Err("Sink is full".into());
+            }
+            self.chunks.push(chunk.clone());
+            Ok(())
+        }
+    }
+
+    /// Start of the test history, far before the audit logs of other tests
+    fn epoch() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn audit_log_at(hours: i64) -> AuditLogModel {
+        AuditLogModel {
+            updated_at: epoch() + Duration::hours(hours),
+            ..create_test_audit_log()
+        }
+    }
+
+    async fn load_chain(
+        person_repo: &PersonRepositoryImpl,
+        person_id: Uuid,
+    ) -> Result<Vec<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let page = person_repo.load_audits(person_id, PageRequest::new(10, 0)).await?;
+        Ok(order_chain(page.items))
+    }
+
+    #[tokio::test]
+    async fn test_archive_before_keeps_newest_version() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = AuditRetentionService::new(ctx.audit_repos());
+
+        // Three revisions, one hour apart
+        let audit_logs = [audit_log_at(0), audit_log_at(1), audit_log_at(2)];
+        for audit_log in &audit_logs {
+            audit_log_repo.create(audit_log).await?;
+        }
+        let mut person = person_repo
+            .create_batch(vec![create_test_person("Archived Person")], Some(audit_logs[0].id))
+            .await?
+            .remove(0);
+        for (audit_log, display_name) in audit_logs[1..].iter().zip(["Archived Person 2", "Archived Person 3"]) {
+            person.display_name = HeaplessString::try_from(display_name).unwrap();
+            person = person_repo.update_batch(vec![person], Some(audit_log.id)).await?.remove(0);
+        }
+        let first_hash = load_chain(person_repo, person.id).await?[0].hash;
+
+        // Cutoff between the first and the second revision
+        let mut sink = MemorySink::default();
+        let report = service.archive_before(epoch() + Duration::minutes(30), &mut sink).await?;
+        assert_eq!(report.archived("person_audit"), 1);
+        assert_eq!(report.archived("audit_log"), 1);
+        assert_eq!(report.total(), sink.chunks.iter().map(|chunk| chunk.rows.len()).sum::<usize>());
+        assert_eq!(sink.rows("person_audit")[0]["audit_log_id"], json!(audit_logs[0].id));
+        assert_eq!(sink.rows("person_audit")[0]["display_name"], json!("Archived Person"));
+        assert_eq!(sink.rows("audit_log")[0]["id"], json!(audit_logs[0].id));
+
+        // The remaining versions verify from the archived antecedent
+        let remaining = load_chain(person_repo, person.id).await?;
+        assert_eq!(remaining.len(), 2);
+        let archived = service
+            .archived_antecedent("person_audit", person.id)
+            .await?
+            .expect("The archived version is recorded");
+        assert_eq!(archived.audit_log_id, audit_logs[0].id);
+        assert_eq!(archived.hash, first_hash);
+        assert_eq!(verify_chain_from(&remaining, Some(&archived)), Ok(()));
+        assert_eq!(verify_chain_from(&remaining, None), Err(ChainError::BrokenLink { index: 0 }));
+
+        // Past every revision, the newest one stays and still resolves
+        let report = service.archive_before(epoch() + Duration::hours(3), &mut sink).await?;
+        assert_eq!(report.archived("person_audit"), 1);
+        assert_eq!(report.archived("audit_log"), 1);
+        let remaining = load_chain(person_repo, person.id).await?;
+        assert_eq!(remaining.len(), 1);
+        let live = person_repo.load(person.id).await?.expect("The person is still there");
+        assert_eq!(remaining[0].audit_log_id, live.audit_log_id);
+        assert_eq!(remaining[0].hash, live.hash);
+        let archived = service.archived_antecedent("person_audit", person.id).await?.unwrap();
+        assert_eq!(archived.audit_log_id, audit_logs[1].id);
+        assert_eq!(verify_chain_from(&remaining, Some(&archived)), Ok(()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_archive_before_keeps_rows_refused_by_sink() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = AuditRetentionService::new(ctx.audit_repos());
+
+        let audit_logs = [audit_log_at(0), audit_log_at(1)];
+        for audit_log in &audit_logs {
+            audit_log_repo.create(audit_log).await?;
+        }
+        let mut person = person_repo
+            .create_batch(vec![create_test_person("Kept Person")], Some(audit_logs[0].id))
+            .await?
+            .remove(0);
+        person.display_name = HeaplessString::try_from("Kept Person 2").unwrap();
+        person_repo.update_batch(vec![person.clone()], Some(audit_logs[1].id)).await?;
+
+        let mut sink = MemorySink {
+            refuse: true,
+            ..MemorySink::default()
+        };
+        let error = service
+            .archive_before(epoch() + Duration::hours(3), &mut sink)
+            .await
+            .expect_err("The sink refuses every chunk");
+        assert!(matches!(
+            error.downcast_ref::<AuditRetentionError>(),
+            Some(AuditRetentionError::SinkFailed { table: "person_audit", rows: 1, .. })
+        ));
+
+        let page = person_repo.load_audits(person.id, PageRequest::new(10, 0)).await?;
+        assert_eq!(page.total, 2);
+        assert!(service.archived_antecedent("person_audit", person.id).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use std::error::Error;
+use std::path::Path;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+/// Rows of one table handed to an `AuditSink` before they are deleted
+#[derive(Debug, Clone)]
+pub struct AuditChunk {
+    /// Table the rows come from, an audit table, `audit_link` or `audit_log`
+    pub table: &'static str,
+    /// The rows as JSON objects keyed by column name
+    pub rows: Vec<serde_json::Value>,
+}
+
+/// Destination of the rows archived by `AuditRetentionService::archive_before`
+///
+/// Returning `Ok` from `write_chunk` confirms the rows are stored: they are deleted
+/// right after. On error the rows stay in the database.
+#[async_trait]
+pub trait AuditSink: Send {
+    async fn write_chunk(&mut self, chunk: &AuditChunk) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// One line of a JSON lines archive
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    table: &'a str,
+    row: &'a serde_json::Value,
+}
+
+/// `AuditSink` appending the rows to a file, one JSON object per line
+///
+/// Each line holds the table and the row: `{"table":"person_audit","row":{...}}`.
+/// A chunk is confirmed once the file is flushed and synced to disk.
+pub struct JsonLinesFileSink {
+    file: BufWriter<File>,
+}
+
+impl JsonLinesFileSink {
+    /// Open `path` for appending, creating the file if needed
+    pub async fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path).await?;
+        Ok(Self { file: BufWriter::new(file) })
+    }
+}
+
+#[async_trait]
+impl AuditSink for JsonLinesFileSink {
+    async fn write_chunk(&mut self, chunk: &AuditChunk) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for row in &chunk.rows {
+            let mut line = serde_json::to_vec(&JsonLine { table: chunk.table, row })?;
+            line.push(b'\n');
+            self.file.write_all(&line).await?;
+        }
+        self.file.flush().await?;
+        self.file.get_ref().sync_data().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditChunk, AuditSink, JsonLinesFileSink};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_json_lines_file_sink_appends_rows() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let path = std::env::temp_dir().join(format!("audit-archive-{}.jsonl", Uuid::new_v4()));
+
+        let mut sink = JsonLinesFileSink::open(&path).await?;
+        sink.write_chunk(&AuditChunk {
+            table: "person_audit",
+            rows: vec![json!({"id": 1}), json!({"id": 2})],
+        })
+        .await?;
+        drop(sink);
+
+        // Reopening appends
+        let mut sink = JsonLinesFileSink::open(&path).await?;
+        sink.write_chunk(&AuditChunk {
+            table: "audit_log",
+            rows: vec![json!({"id": 3})],
+        })
+        .await?;
+
+        let content = tokio::fs::read_to_string(&path).await?;
+        tokio::fs::remove_file(&path).await?;
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            lines,
+            vec![
+                json!({"table": "person_audit", "row": {"id": 1}}),
+                json!({"table": "person_audit", "row": {"id": 2}}),
+                json!({"table": "audit_log", "row": {"id": 3}}),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod service_impl;
+pub mod audit_sink;
+pub mod archive_before;
+
+pub use service_impl::{AuditRetentionError, AuditRetentionService};
+pub use audit_sink::{AuditChunk, AuditSink, JsonLinesFileSink};
+pub use archive_before::ArchiveReport;
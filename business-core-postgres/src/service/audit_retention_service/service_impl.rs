@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::audit::audit_log_repository::AuditLogRepositoryImpl;
+use crate::repository::audit::AuditRepositories;
+
+/// Typed error of the audit retention service
+#[derive(Debug, Error)]
+pub enum AuditRetentionError {
+    #[error("Audit sink did not store {rows} rows of {table}: {message}")]
+    SinkFailed { table: &'static str, rows: usize, message: String },
+}
+
+/// Audit tables, with the entity table whose versions each one stores
+pub(crate) const AUDITED_TABLES: [(&str, &str); 8] = [
+    ("person_audit", "person"),
+    ("location_audit", "location"),
+    ("entity_reference_audit", "entity_reference"),
+    ("reason_reference_audit", "reason_reference"),
+    ("person_activity_log_audit", "person_activity_log"),
+    ("portfolio_audit", "portfolio"),
+    ("person_compliance_status_audit", "person_compliance_status"),
+    ("person_document_audit", "person_document"),
+];
+
+/// Service moving old audit rows out of the database
+///
+/// The archived rows are deleted in the session's transaction and only go away for
+/// good when the caller commits it.
+pub struct AuditRetentionService {
+    pub audit_log_repository: Arc<AuditLogRepositoryImpl>,
+}
+
+impl AuditRetentionService {
+    pub fn new(audit_repos: &AuditRepositories) -> Self {
+        Self {
+            audit_log_repository: audit_repos.audit_log_repository.clone(),
+        }
+    }
+}
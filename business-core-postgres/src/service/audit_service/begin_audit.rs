@@ -0,0 +1,89 @@
+use business_core_db::models::audit::{AuditContext, AuditLogModel};
+use chrono::Utc;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::AuditService;
+
+impl AuditService {
+    /// Create the audit_log row of a unit of work and return its context
+    ///
+    /// Every write of the unit of work is then made under the returned context, so they
+    /// all share the one audit log.
+    pub async fn begin_audit(
+        &self,
+        updated_by_person_id: Uuid,
+    ) -> Result<AuditContext, Box<dyn Error + Send + Sync>> {
+        let audit_log = AuditLogModel {
+            id: Uuid::new_v4(),
+            updated_at: Utc::now(),
+            updated_by_person_id,
+        };
+        let audit_log = self.audit_log_repository.create(&audit_log).await?;
+        Ok(AuditContext::from(&audit_log))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AuditService;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason_with_context;
+    use crate::service::PersonService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::{current_audit_context, with_audit_context};
+    use business_core_db::models::person::common_enums::PersonStatus;
+    use business_core_db::models::reason_and_purpose::reason::ReasonContext;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load::Load;
+
+    #[tokio::test]
+    async fn test_begin_audit_backs_several_writes() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Audit Context");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let reason = create_test_reason_with_context("AUDIT_CTX_REVIEW", "Verification review", ReasonContext::Customer);
+        let reason_id = reason.id;
+        reason_repo.create_batch(vec![reason], None).await?;
+
+        let audit_service = AuditService::new(ctx.audit_repos());
+        let audit = audit_service.begin_audit(audit_log.updated_by_person_id).await?;
+        let row = audit_log_repo.load(audit.audit_log_id).await?;
+        assert_eq!(row.updated_by_person_id, audit.updated_by_person_id);
+
+        let person_service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+        let updated = with_audit_context(audit, async {
+            let audit = current_audit_context().ok_or("No audit context")?;
+            person_service
+                .change_status(person_id, PersonStatus::PendingVerification, reason_id, None, &audit)
+                .await
+        })
+        .await?;
+        assert_eq!(updated.audit_log_id, Some(audit.audit_log_id));
+
+        // The person update and the reason reference share the one audit_log row
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let reference_audit_log_ids: Vec<Option<uuid::Uuid>> =
+            sqlx::query_scalar("SELECT audit_log_id FROM reason_reference WHERE entity_id = $1 AND reason_id = $2")
+                .bind(person_id)
+                .bind(reason_id)
+                .fetch_all(&mut **transaction)
+                .await?;
+        assert_eq!(reference_audit_log_ids, vec![Some(audit.audit_log_id)]);
+        let audit_logs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_log WHERE id = $1")
+            .bind(audit.audit_log_id)
+            .fetch_one(&mut **transaction)
+            .await?;
+        assert_eq!(audit_logs, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod service_impl;
+pub mod begin_audit;
+
+pub use service_impl::AuditService;
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use crate::repository::audit::audit_log_repository::AuditLogRepositoryImpl;
+use crate::repository::audit::AuditRepositories;
+
+/// Service opening the audit log of a unit of work
+///
+/// The audit log is created in the session's transaction, so it is committed or
+/// rolled back with the writes made under it.
+pub struct AuditService {
+    pub audit_log_repository: Arc<AuditLogRepositoryImpl>,
+}
+
+impl AuditService {
+    pub fn new(repos: &AuditRepositories) -> Self {
+        Self {
+            audit_log_repository: repos.audit_log_repository.clone(),
+        }
+    }
+}
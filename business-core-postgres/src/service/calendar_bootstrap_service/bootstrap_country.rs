@@ -0,0 +1,310 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::models::calendar::business_day::{BusinessDayModel, DayScope};
+use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+use business_core_db::models::calendar::date_calculation_rules::{
+    DateCalculationRulesModel, DateRulePurpose, DateShiftRule,
+};
+use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+use business_core_db::models::effective_dated::EffectiveDated;
+use business_core_db::repository::create_batch::CreateBatch;
+use business_core_db::repository::load_batch::LoadBatch;
+use chrono::{Datelike, NaiveDate};
+use heapless::String as HeaplessString;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::{CalendarBootstrapError, CalendarBootstrapService};
+
+/// A public holiday of the bootstrapped year
+#[derive(Debug, Clone)]
+pub struct PublicHoliday {
+    pub date: NaiveDate,
+    pub name: HeaplessString<50>,
+}
+
+/// What `bootstrap_country` sets up for one year of a country
+#[derive(Debug, Clone)]
+pub struct CountryCalendarSpec {
+    pub year: i32,
+    pub weekend_days: Vec<CalendarWeekday>,
+    pub public_holidays: Vec<PublicHoliday>,
+    /// Shift rule of the rules created for `purposes`
+    pub default_shift_rule: DateShiftRule,
+    /// One country-wide rule is created per purpose
+    pub purposes: Vec<DateRulePurpose>,
+}
+
+/// Rows `bootstrap_country` created, and the ones it found already present
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BootstrapSummary {
+    pub weekend_days_created: usize,
+    pub weekend_days_present: usize,
+    pub rules_created: usize,
+    pub rules_present: usize,
+    pub business_days_created: usize,
+    pub business_days_present: usize,
+}
+
+impl CalendarBootstrapService {
+    /// Set up the calendar of `country_id` for `spec.year`
+    ///
+    /// Creates, in this order: the country-wide weekend days, a country-wide rule per
+    /// purpose using them, and a business day row for every date of the year, marking
+    /// the weekend and the public holidays. Country-wide weekend days or active rules
+    /// already effective on January 1st are kept, as are existing business day rows,
+    /// so bootstrapping the same country and year again creates nothing.
+    ///
+    /// All rows are written in the transaction of the repositories; the calendar
+    /// tables are not audited, the audit log of `audit` is passed along regardless.
+    pub async fn bootstrap_country(
+        &self,
+        country_id: Uuid,
+        spec: CountryCalendarSpec,
+        audit: &AuditContext,
+    ) -> Result<BootstrapSummary, Box<dyn Error + Send + Sync>> {
+        let year = spec.year;
+        let (Some(first), Some(last)) = (NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31))
+        else {
+            return Err(CalendarBootstrapError::InvalidYear(year).into());
+        };
+        if let Some(holiday) = spec.public_holidays.iter().find(|holiday| holiday.date.year() != year) {
+            return Err(CalendarBootstrapError::HolidayOutsideYear { date: holiday.date, year }.into());
+        }
+        let mut summary = BootstrapSummary::default();
+
+        let weekend = match self.find_country_weekend(country_id, first).await? {
+            Some(weekend) => {
+                summary.weekend_days_present += 1;
+                weekend
+            }
+            None => {
+                let mut weekend =
+                    WeekendDaysModel::from_days(&spec.weekend_days).map_err(CalendarBootstrapError::InvalidWeekendDays)?;
+                weekend.country_id = Some(country_id);
+                weekend.effective_date = first;
+                summary.weekend_days_created += 1;
+                self.weekend_days_repository
+                    .create_batch(vec![weekend], Some(audit.audit_log_id))
+                    .await?
+                    .remove(0)
+            }
+        };
+
+        let mut rules = Vec::new();
+        for purpose in distinct(&spec.purposes) {
+            let present = self
+                .date_calculation_rules_repository
+                .find_by_purpose(purpose, country_id)
+                .await?
+                .iter()
+                .any(|rule| rule.is_active && rule.country_subdivision_id.is_none() && rule.is_effective_on(first));
+            if present {
+                summary.rules_present += 1;
+                continue;
+            }
+            rules.push(DateCalculationRulesModel {
+                id: Uuid::new_v4(),
+                country_id,
+                country_subdivision_id: None,
+                rule_name: HeaplessString::try_from(format!("Default {purpose:?}").as_str())
+                    .map_err(|_| "Rule name too long")?,
+                rule_purpose: purpose,
+                default_shift_rule: spec.default_shift_rule,
+                weekend_days_id: Some(weekend.id),
+                priority: 0,
+                is_active: true,
+                effective_date: first,
+                expiry_date: None,
+            });
+        }
+        summary.rules_created = rules.len();
+        if !rules.is_empty() {
+            self.date_calculation_rules_repository
+                .create_batch(rules, Some(audit.audit_log_id))
+                .await?;
+        }
+
+        let present: HashSet<NaiveDate> = self
+            .business_day_repository
+            .find_by_country_and_range(country_id, None, first, last)
+            .await?
+            .into_iter()
+            .map(|day| day.date)
+            .collect();
+        let holidays: BTreeMap<NaiveDate, &PublicHoliday> =
+            spec.public_holidays.iter().map(|holiday| (holiday.date, holiday)).collect();
+        let days: Vec<BusinessDayModel> = first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .filter(|date| !present.contains(date))
+            .map(|date| {
+                let holiday = holidays.get(&date);
+                let is_weekend = weekend.is_weekend(date);
+                BusinessDayModel {
+                    id: Uuid::new_v4(),
+                    country_id: Some(country_id),
+                    country_subdivision_id: None,
+                    date,
+                    weekday: CalendarWeekday::from(date.weekday()),
+                    is_business_day: !is_weekend && holiday.is_none(),
+                    is_weekend,
+                    weekend_day_01: Some(weekend.id),
+                    is_holiday: holiday.is_some(),
+                    holiday_name: holiday.map(|holiday| holiday.name.clone()),
+                    day_scope: DayScope::National,
+                }
+            })
+            .collect();
+        summary.business_days_present = present.len();
+        summary.business_days_created = days.len();
+        if !days.is_empty() {
+            self.business_day_repository
+                .create_batch(days, Some(audit.audit_log_id))
+                .await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Country-wide weekend days of `country_id` effective on `date`
+    async fn find_country_weekend(
+        &self,
+        country_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<WeekendDaysModel>, Box<dyn Error + Send + Sync>> {
+        let ids: Vec<Uuid> = self
+            .weekend_days_repository
+            .find_by_country_id(country_id)
+            .await?
+            .into_iter()
+            .filter(|idx| idx.country_subdivision_id.is_none())
+            .map(|idx| idx.id)
+            .collect();
+        if ids.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(self
+            .weekend_days_repository
+            .load_batch(&ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .find(|weekend| weekend.is_effective_on(date)))
+    }
+}
+
+/// `purposes` without repetitions, in their order
+fn distinct(purposes: &[DateRulePurpose]) -> Vec<DateRulePurpose> {
+    let mut distinct = Vec::with_capacity(purposes.len());
+    for purpose in purposes {
+        if !distinct.contains(purpose) {
+            distinct.push(*purpose);
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BootstrapSummary, CountryCalendarSpec, PublicHoliday};
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::service::calendar_bootstrap_service::CalendarBootstrapService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+    use business_core_db::models::calendar::date_calculation_rules::{DateRulePurpose, DateShiftRule};
+    use chrono::NaiveDate;
+    use heapless::String as HeaplessString;
+    use uuid::Uuid;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn spec() -> CountryCalendarSpec {
+        CountryCalendarSpec {
+            year: 2025,
+            weekend_days: vec![CalendarWeekday::Saturday, CalendarWeekday::Sunday],
+            public_holidays: vec![
+                PublicHoliday {
+                    date: date(2025, 1, 1),
+                    name: HeaplessString::try_from("New Year").unwrap(),
+                },
+                PublicHoliday {
+                    date: date(2025, 5, 20),
+                    name: HeaplessString::try_from("National Day").unwrap(),
+                },
+            ],
+            default_shift_rule: DateShiftRule::NextBusinessDay,
+            purposes: vec![DateRulePurpose::PaymentDue, DateRulePurpose::MaturityCalculation],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_country_twice() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let calendar_repos = ctx.calendar_repos();
+        let service = CalendarBootstrapService::new(calendar_repos);
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let audit = AuditContext::from(&audit_log);
+        let country_id = Uuid::new_v4();
+
+        let first = service.bootstrap_country(country_id, spec(), &audit).await?;
+        assert_eq!(
+            first,
+            BootstrapSummary {
+                weekend_days_created: 1,
+                weekend_days_present: 0,
+                rules_created: 2,
+                rules_present: 0,
+                business_days_created: 365,
+                business_days_present: 0,
+            }
+        );
+
+        let second = service.bootstrap_country(country_id, spec(), &audit).await?;
+        assert_eq!(
+            second,
+            BootstrapSummary {
+                weekend_days_created: 0,
+                weekend_days_present: 1,
+                rules_created: 0,
+                rules_present: 2,
+                business_days_created: 0,
+                business_days_present: 365,
+            }
+        );
+
+        // No duplicates
+        assert_eq!(calendar_repos.weekend_days_repository.find_by_country_id(country_id).await?.len(), 1);
+        assert_eq!(
+            calendar_repos
+                .date_calculation_rules_repository
+                .find_by_purpose(DateRulePurpose::PaymentDue, country_id)
+                .await?
+                .len(),
+            1
+        );
+        let days = calendar_repos
+            .business_day_repository
+            .find_by_country_and_range(country_id, None, date(2025, 1, 1), date(2025, 12, 31))
+            .await?;
+        assert_eq!(days.len(), 365);
+
+        // Tuesday holiday, Saturday weekend, Monday business day
+        let day = |d: NaiveDate| days.iter().find(|day| day.date == d).unwrap();
+        assert!(day(date(2025, 5, 20)).is_holiday);
+        assert!(!day(date(2025, 5, 20)).is_business_day);
+        assert!(day(date(2025, 5, 24)).is_weekend);
+        assert!(!day(date(2025, 5, 24)).is_business_day);
+        assert!(day(date(2025, 5, 26)).is_business_day);
+        assert_eq!(days.iter().filter(|day| day.is_business_day).count(), 261 - 2);
+
+        Ok(())
+    }
+}
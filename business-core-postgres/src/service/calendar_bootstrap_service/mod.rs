@@ -0,0 +1,5 @@
+pub mod bootstrap_country;
+pub mod service_impl;
+
+pub use bootstrap_country::{BootstrapSummary, CountryCalendarSpec, PublicHoliday};
+pub use service_impl::{CalendarBootstrapError, CalendarBootstrapService};
@@ -0,0 +1,38 @@
+use business_core_db::models::calendar::weekend_days::WeekendDaysError;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::calendar::{
+    BusinessDayRepositoryImpl, CalendarRepositories, DateCalculationRulesRepositoryImpl,
+    WeekendDaysRepositoryImpl,
+};
+
+/// Typed error of the calendar bootstrap service
+#[derive(Debug, Error)]
+pub enum CalendarBootstrapError {
+    #[error("Year {0} is out of the calendar range")]
+    InvalidYear(i32),
+
+    #[error("Invalid weekend days: {0}")]
+    InvalidWeekendDays(WeekendDaysError),
+
+    #[error("Public holiday {date} is outside of year {year}")]
+    HolidayOutsideYear { date: chrono::NaiveDate, year: i32 },
+}
+
+/// Service setting up the calendar of a new country
+pub struct CalendarBootstrapService {
+    pub weekend_days_repository: Arc<WeekendDaysRepositoryImpl>,
+    pub business_day_repository: Arc<BusinessDayRepositoryImpl>,
+    pub date_calculation_rules_repository: Arc<DateCalculationRulesRepositoryImpl>,
+}
+
+impl CalendarBootstrapService {
+    pub fn new(repos: &CalendarRepositories) -> Self {
+        Self {
+            weekend_days_repository: repos.weekend_days_repository.clone(),
+            business_day_repository: repos.business_day_repository.clone(),
+            date_calculation_rules_repository: repos.date_calculation_rules_repository.clone(),
+        }
+    }
+}
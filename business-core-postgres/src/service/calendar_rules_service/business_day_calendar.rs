@@ -0,0 +1,166 @@
+use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+use business_core_db::models::product::posting_schedule::BusinessDayProvider;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::CalendarRulesService;
+use super::simulate::resolve_weekend;
+
+/// Business days of a country, and optionally one of its subdivisions, read from the
+/// business_day table
+///
+/// Explicit business day rows win, so holidays are not business days. Dates without a
+/// row are business days unless they fall on the weekend days effective on them, the
+/// subdivision's before the country's. The rows are loaded for the window given to
+/// `CalendarRulesService::business_day_calendar`; outside of it only the weekend days apply.
+#[derive(Debug, Clone)]
+pub struct BusinessDayCalendar {
+    country_id: Uuid,
+    country_subdivision_id: Option<Uuid>,
+    business_days: BTreeMap<NaiveDate, bool>,
+    weekends: Vec<WeekendDaysModel>,
+}
+
+impl BusinessDayProvider for BusinessDayCalendar {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        match self.business_days.get(&date) {
+            Some(is_business_day) => *is_business_day,
+            None => resolve_weekend(&self.weekends, self.country_id, self.country_subdivision_id, date)
+                .is_none_or(|weekend| !weekend.is_weekend(date)),
+        }
+    }
+}
+
+impl CalendarRulesService {
+    /// Calendar of the country and subdivision between `from` and `to`, for the date
+    /// calculations taking a `BusinessDayProvider` such as `posting_dates`
+    pub async fn business_day_calendar(
+        &self,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<BusinessDayCalendar, Box<dyn Error + Send + Sync>> {
+        let business_days = self
+            .load_business_days(country_id, country_subdivision_id, from, to)
+            .await?;
+        let weekends = self.load_weekends(country_id, country_subdivision_id, &[]).await?;
+        Ok(BusinessDayCalendar {
+            country_id,
+            country_subdivision_id,
+            business_days,
+            weekends,
+        })
+    }
+
+    /// Whether each date between `from` and `to` with a business_day row is a business day
+    pub(super) async fn load_business_days(
+        &self,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, bool>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .business_day_repository
+            .find_by_country_and_range(country_id, country_subdivision_id, from, to)
+            .await?
+            .into_iter()
+            .map(|day| (day.date, day.is_business_day))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::calendar_rules_service::CalendarRulesService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::calendar::business_day::{BusinessDayModel, DayScope};
+    use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+    use business_core_db::models::calendar::date_calculation_rules::DateShiftRule;
+    use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+    use business_core_db::models::product::posting_schedule::{shift, BusinessDayProvider};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use chrono::NaiveDate;
+    use heapless::String as HeaplessString;
+    use uuid::Uuid;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_business_day_calendar_follows_holiday_rows() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let calendar_repos = ctx.calendar_repos();
+
+        let country_id = Uuid::new_v4();
+        calendar_repos
+            .weekend_days_repository
+            .create_batch(
+                vec![WeekendDaysModel {
+                    id: Uuid::new_v4(),
+                    country_id: Some(country_id),
+                    country_subdivision_id: None,
+                    weekend_day_01: Some(CalendarWeekday::Saturday),
+                    weekend_day_02: Some(CalendarWeekday::Sunday),
+                    weekend_day_03: None,
+                    weekend_day_04: None,
+                    weekend_day_05: None,
+                    weekend_day_06: None,
+                    weekend_day_07: None,
+                    effective_date: date(2024, 1, 1),
+                    expiry_date: None,
+                }],
+                None,
+            )
+            .await?;
+        // Whit Monday is a holiday, the Saturday before is a banking day
+        let row = |day: NaiveDate, weekday: CalendarWeekday, is_business_day: bool| BusinessDayModel {
+            id: Uuid::new_v4(),
+            country_id: Some(country_id),
+            country_subdivision_id: None,
+            date: day,
+            weekday,
+            is_business_day,
+            is_weekend: weekday == CalendarWeekday::Saturday,
+            weekend_day_01: None,
+            is_holiday: !is_business_day,
+            holiday_name: (!is_business_day).then(|| HeaplessString::try_from("Whit Monday").unwrap()),
+            day_scope: if is_business_day { DayScope::Banking } else { DayScope::National },
+        };
+        calendar_repos
+            .business_day_repository
+            .create_batch(
+                vec![
+                    row(date(2024, 5, 18), CalendarWeekday::Saturday, true),
+                    row(date(2024, 5, 20), CalendarWeekday::Monday, false),
+                ],
+                None,
+            )
+            .await?;
+
+        let service = CalendarRulesService::new(calendar_repos);
+        let calendar = service
+            .business_day_calendar(country_id, None, date(2024, 5, 1), date(2024, 5, 31))
+            .await?;
+
+        assert!(calendar.is_business_day(date(2024, 5, 17)));
+        assert!(calendar.is_business_day(date(2024, 5, 18)));
+        assert!(!calendar.is_business_day(date(2024, 5, 19)));
+        assert!(!calendar.is_business_day(date(2024, 5, 20)));
+        assert!(calendar.is_business_day(date(2024, 5, 21)));
+        assert_eq!(
+            shift(date(2024, 5, 19), &calendar, DateShiftRule::NextBusinessDay),
+            Some(date(2024, 5, 21))
+        );
+
+        // Outside the window only the weekend days apply
+        assert!(calendar.is_business_day(date(2024, 6, 3)));
+        assert!(!calendar.is_business_day(date(2024, 6, 1)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod service_impl;
+pub mod simulate;
+pub mod business_day_calendar;
+
+pub use service_impl::{CalendarRulesError, CalendarRulesService};
+pub use business_day_calendar::BusinessDayCalendar;
@@ -0,0 +1,40 @@
+use business_core_db::models::calendar::date_calculation_rules::DateShiftRule;
+use business_core_db::utils::Clock;
+use chrono::NaiveDate;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::calendar::{
+    BusinessDayRepositoryImpl, CalendarRepositories, DateCalculationRulesRepositoryImpl,
+    WeekendDaysRepositoryImpl,
+};
+
+/// Typed error of the calendar rules service
+#[derive(Debug, Error)]
+pub enum CalendarRulesError {
+    #[error("No business day found shifting {date} by {shift_rule:?}")]
+    NoBusinessDay {
+        date: NaiveDate,
+        shift_rule: DateShiftRule,
+    },
+}
+
+/// Service applying date calculation rules against the calendar
+pub struct CalendarRulesService {
+    pub weekend_days_repository: Arc<WeekendDaysRepositoryImpl>,
+    pub business_day_repository: Arc<BusinessDayRepositoryImpl>,
+    pub date_calculation_rules_repository: Arc<DateCalculationRulesRepositoryImpl>,
+    /// Current date of `shift_today`
+    pub clock: Arc<dyn Clock>,
+}
+
+impl CalendarRulesService {
+    pub fn new(repos: &CalendarRepositories) -> Self {
+        Self {
+            weekend_days_repository: repos.weekend_days_repository.clone(),
+            business_day_repository: repos.business_day_repository.clone(),
+            date_calculation_rules_repository: repos.date_calculation_rules_repository.clone(),
+            clock: repos.clock.clone(),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use business_core_db::models::calendar::date_calculation_rules::{DateCalculationRulesModel, DateRulePurpose};
+use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+use business_core_db::models::effective_dated::EffectiveDated;
+use business_core_db::models::product::posting_schedule::{shift, BusinessDayProvider};
+use business_core_db::repository::load_batch::LoadBatch;
+use chrono::{Days, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::{CalendarRulesError, CalendarRulesService};
+
+/// Days of business day rows loaded on each side of the simulated dates, enough
+/// for the longest shift
+const SHIFT_MARGIN_DAYS: u64 = 32;
+
+/// Business days of one country or subdivision as seen by a rule
+///
+/// Explicit business day rows win; dates without a row are business days unless
+/// they fall on the weekend.
+struct RuleCalendar<'a> {
+    business_days: &'a BTreeMap<NaiveDate, bool>,
+    weekend: Option<&'a WeekendDaysModel>,
+}
+
+impl BusinessDayProvider for RuleCalendar<'_> {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        match self.business_days.get(&date) {
+            Some(is_business_day) => *is_business_day,
+            None => self.weekend.is_none_or(|weekend| !weekend.is_weekend(date)),
+        }
+    }
+}
+
+impl CalendarRulesService {
+    /// Where each of `dates` lands under the rules of `purpose`, as `(date, shifted)` pairs
+    ///
+    /// For every date the effective rule is resolved: active and effective on the date,
+    /// a rule of `country_subdivision_id` before a country-wide one, then the highest
+    /// priority. Its `default_shift_rule` is applied against the rule's weekend days,
+    /// or the weekend days of the subdivision or else the country, and the business day
+    /// rows of the country and subdivision. A date no rule applies to is left as is.
+    ///
+    /// Yields `CalendarRulesError::NoBusinessDay` when a shift finds no business day.
+    pub async fn simulate(
+        &self,
+        purpose: DateRulePurpose,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+        dates: &[NaiveDate],
+    ) -> Result<Vec<(NaiveDate, NaiveDate)>, Box<dyn Error + Send + Sync>> {
+        let (Some(first), Some(last)) = (dates.iter().min(), dates.iter().max()) else {
+            return Ok(Vec::new());
+        };
+
+        let rules: Vec<DateCalculationRulesModel> = self
+            .date_calculation_rules_repository
+            .find_by_purpose(purpose, country_id)
+            .await?
+            .into_iter()
+            .filter(|rule| rule.is_active)
+            .filter(|rule| rule.country_subdivision_id.is_none() || rule.country_subdivision_id == country_subdivision_id)
+            .collect();
+
+        let weekends = self.load_weekends(country_id, country_subdivision_id, &rules).await?;
+
+        let from = first.checked_sub_days(Days::new(SHIFT_MARGIN_DAYS)).unwrap_or(*first);
+        let to = last.checked_add_days(Days::new(SHIFT_MARGIN_DAYS)).unwrap_or(*last);
+        let business_days = self
+            .load_business_days(country_id, country_subdivision_id, from, to)
+            .await?;
+
+        let mut shifted = Vec::with_capacity(dates.len());
+        for date in dates.iter().copied() {
+            let Some(rule) = resolve_rule(&rules, country_subdivision_id, date) else {
+                shifted.push((date, date));
+                continue;
+            };
+
+            let weekend = match rule.weekend_days_id {
+                Some(weekend_days_id) => weekends.iter().find(|weekend| weekend.id == weekend_days_id),
+                None => resolve_weekend(&weekends, country_id, country_subdivision_id, date),
+            };
+            let calendar = RuleCalendar {
+                business_days: &business_days,
+                weekend,
+            };
+            let target = shift(date, &calendar, rule.default_shift_rule).ok_or(CalendarRulesError::NoBusinessDay {
+                date,
+                shift_rule: rule.default_shift_rule,
+            })?;
+            shifted.push((date, target));
+        }
+        Ok(shifted)
+    }
+
+    /// Where the current date of the service clock lands under the rules of `purpose`,
+    /// see `simulate`
+    pub async fn shift_today(
+        &self,
+        purpose: DateRulePurpose,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+    ) -> Result<NaiveDate, Box<dyn Error + Send + Sync>> {
+        let today = self.clock.today();
+        let shifted = self.simulate(purpose, country_id, country_subdivision_id, &[today]).await?;
+        Ok(shifted.first().map_or(today, |(_, target)| *target))
+    }
+
+    /// Weekend days of the country and subdivision, and the ones named by `rules`
+    pub(super) async fn load_weekends(
+        &self,
+        country_id: Uuid,
+        country_subdivision_id: Option<Uuid>,
+        rules: &[DateCalculationRulesModel],
+    ) -> Result<Vec<WeekendDaysModel>, Box<dyn Error + Send + Sync>> {
+        let mut ids: BTreeSet<Uuid> = rules.iter().filter_map(|rule| rule.weekend_days_id).collect();
+        for idx in self.weekend_days_repository.find_by_country_id(country_id).await? {
+            ids.insert(idx.id);
+        }
+        if let Some(country_subdivision_id) = country_subdivision_id {
+            for idx in self
+                .weekend_days_repository
+                .find_by_country_subdivision_id(country_subdivision_id)
+                .await?
+            {
+                ids.insert(idx.id);
+            }
+        }
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Uuid> = ids.into_iter().collect();
+        Ok(self
+            .weekend_days_repository
+            .load_batch(&ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
+
+/// The rule applying on `date`: subdivision rules first, then highest priority
+fn resolve_rule(
+    rules: &[DateCalculationRulesModel],
+    country_subdivision_id: Option<Uuid>,
+    date: NaiveDate,
+) -> Option<&DateCalculationRulesModel> {
+    rules
+        .iter()
+        .filter(|rule| rule.is_effective_on(date))
+        .max_by_key(|rule| {
+            let subdivision_specific = country_subdivision_id.is_some() && rule.country_subdivision_id == country_subdivision_id;
+            (subdivision_specific, rule.priority)
+        })
+}
+
+/// Weekend days effective on `date`, the subdivision's before the country's
+pub(super) fn resolve_weekend(
+    weekends: &[WeekendDaysModel],
+    country_id: Uuid,
+    country_subdivision_id: Option<Uuid>,
+    date: NaiveDate,
+) -> Option<&WeekendDaysModel> {
+    let effective: Vec<&WeekendDaysModel> = weekends.iter().filter(|weekend| weekend.is_effective_on(date)).collect();
+    let subdivision = country_subdivision_id
+        .and_then(|id| effective.iter().find(|weekend| weekend.country_subdivision_id == Some(id)));
+    subdivision
+        .or_else(|| {
+            effective
+                .iter()
+                .find(|weekend| weekend.country_id == Some(country_id) && weekend.country_subdivision_id.is_none())
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::calendar_rules_service::CalendarRulesService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::fixtures::DateCalculationRulesFixture;
+    use business_core_db::models::calendar::business_day::{BusinessDayModel, DayScope};
+    use business_core_db::models::calendar::calendar_weekday::CalendarWeekday;
+    use business_core_db::models::calendar::date_calculation_rules::{DateRulePurpose, DateShiftRule};
+    use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::FixedClock;
+    use chrono::NaiveDate;
+    use heapless::String as HeaplessString;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn saturday_sunday(country_id: Uuid) -> WeekendDaysModel {
+        WeekendDaysModel {
+            id: Uuid::new_v4(),
+            country_id: Some(country_id),
+            country_subdivision_id: None,
+            weekend_day_01: Some(CalendarWeekday::Saturday),
+            weekend_day_02: Some(CalendarWeekday::Sunday),
+            weekend_day_03: None,
+            weekend_day_04: None,
+            weekend_day_05: None,
+            weekend_day_06: None,
+            weekend_day_07: None,
+            effective_date: date(2024, 1, 1),
+            expiry_date: None,
+        }
+    }
+
+    fn holiday(country_id: Uuid, day: NaiveDate, weekday: CalendarWeekday) -> BusinessDayModel {
+        BusinessDayModel {
+            id: Uuid::new_v4(),
+            country_id: Some(country_id),
+            country_subdivision_id: None,
+            date: day,
+            weekday,
+            is_business_day: false,
+            is_weekend: false,
+            weekend_day_01: None,
+            is_holiday: true,
+            holiday_name: Some(HeaplessString::try_from("Holiday").unwrap()),
+            day_scope: DayScope::National,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_subdivision_rule_changes_outcome() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let calendar_repos = ctx.calendar_repos();
+
+        let country_id = Uuid::new_v4();
+        let subdivision_id = Uuid::new_v4();
+        calendar_repos
+            .weekend_days_repository
+            .create_batch(vec![saturday_sunday(country_id)], None)
+            .await?;
+        calendar_repos
+            .business_day_repository
+            .create_batch(vec![holiday(country_id, date(2024, 5, 31), CalendarWeekday::Friday)], None)
+            .await?;
+        calendar_repos
+            .date_calculation_rules_repository
+            .create_batch(
+                vec![
+                    DateCalculationRulesFixture::builder(country_id)
+                        .rule_name("CountryPaymentDue")
+                        .rule_purpose(DateRulePurpose::PaymentDue)
+                        .default_shift_rule(DateShiftRule::NextBusinessDay)
+                        .priority(5)
+                        .build(),
+                    DateCalculationRulesFixture::builder(country_id)
+                        .rule_name("SubdivisionPaymentDue")
+                        .country_subdivision_id(subdivision_id)
+                        .rule_purpose(DateRulePurpose::PaymentDue)
+                        .default_shift_rule(DateShiftRule::PreviousBusinessDay)
+                        .priority(1)
+                        .build(),
+                    DateCalculationRulesFixture::builder(country_id)
+                        .rule_name("CountryDateShift")
+                        .rule_purpose(DateRulePurpose::DateShift)
+                        .default_shift_rule(DateShiftRule::PreviousBusinessDay)
+                        .build(),
+                ],
+                None,
+            )
+            .await?;
+
+        let service = CalendarRulesService::new(calendar_repos);
+        let saturday = date(2024, 6, 1);
+        let tuesday = date(2024, 6, 4);
+
+        // The country rule rolls the Saturday forward to Monday
+        let country = service
+            .simulate(DateRulePurpose::PaymentDue, country_id, None, &[saturday, tuesday])
+            .await?;
+        assert_eq!(country, vec![(saturday, date(2024, 6, 3)), (tuesday, tuesday)]);
+
+        // The subdivision rule wins despite its lower priority and rolls back,
+        // over the country holiday on Friday, to Thursday
+        let subdivision = service
+            .simulate(DateRulePurpose::PaymentDue, country_id, Some(subdivision_id), &[saturday, tuesday])
+            .await?;
+        assert_eq!(subdivision, vec![(saturday, date(2024, 5, 30)), (tuesday, tuesday)]);
+
+        // Another subdivision only sees the country rule
+        let other = service
+            .simulate(DateRulePurpose::PaymentDue, country_id, Some(Uuid::new_v4()), &[saturday])
+            .await?;
+        assert_eq!(other, vec![(saturday, date(2024, 6, 3))]);
+
+        // Dates before the rules take effect, and purposes without rules, are left as is
+        let early = date(2023, 12, 30);
+        let unruled = service
+            .simulate(DateRulePurpose::PaymentDue, country_id, None, &[early])
+            .await?;
+        assert_eq!(unruled, vec![(early, early)]);
+        let maturity = service
+            .simulate(DateRulePurpose::MaturityCalculation, country_id, Some(subdivision_id), &[saturday])
+            .await?;
+        assert_eq!(maturity, vec![(saturday, saturday)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shift_today_on_leap_day_and_year_end() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let calendar_repos = ctx.calendar_repos();
+
+        let country_id = Uuid::new_v4();
+        calendar_repos
+            .weekend_days_repository
+            .create_batch(vec![saturday_sunday(country_id)], None)
+            .await?;
+        calendar_repos
+            .business_day_repository
+            .create_batch(
+                vec![
+                    holiday(country_id, date(2028, 2, 29), CalendarWeekday::Tuesday),
+                    holiday(country_id, date(2027, 12, 31), CalendarWeekday::Friday),
+                ],
+                None,
+            )
+            .await?;
+        calendar_repos
+            .date_calculation_rules_repository
+            .create_batch(
+                vec![DateCalculationRulesFixture::builder(country_id)
+                    .rule_name("ClockPaymentDue")
+                    .rule_purpose(DateRulePurpose::PaymentDue)
+                    .default_shift_rule(DateShiftRule::NextBusinessDay)
+                    .effective(date(2024, 1, 1), None)
+                    .build()],
+                None,
+            )
+            .await?;
+
+        let on = |day: NaiveDate| CalendarRulesService {
+            clock: Arc::new(FixedClock::new(day)),
+            ..CalendarRulesService::new(calendar_repos)
+        };
+
+        // Holiday on February 29th moves to Wednesday March 1st
+        let shifted = on(date(2028, 2, 29)).shift_today(DateRulePurpose::PaymentDue, country_id, None).await?;
+        assert_eq!(shifted, date(2028, 3, 1));
+
+        // Holiday on December 31st moves past the weekend into the next year
+        let shifted = on(date(2027, 12, 31)).shift_today(DateRulePurpose::PaymentDue, country_id, None).await?;
+        assert_eq!(shifted, date(2028, 1, 3));
+
+        // A business day stays
+        let shifted = on(date(2028, 3, 1)).shift_today(DateRulePurpose::PaymentDue, country_id, None).await?;
+        assert_eq!(shifted, date(2028, 3, 1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod verify;
+pub mod reject;
+
+pub use service_impl::{DocumentVerificationError, DocumentVerificationService};
@@ -0,0 +1,102 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::models::audit::entity_type::EntityType;
+use business_core_db::models::person::document::{DocumentModel, DocumentStatus};
+use business_core_db::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
+use business_core_db::repository::create_batch::CreateBatch;
+use chrono::Utc;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::DocumentVerificationService;
+
+impl DocumentVerificationService {
+    /// Mark an Uploaded document as Rejected and link the rejection reason to it
+    ///
+    /// Returns the updated document and the created reason reference.
+    pub async fn reject(
+        &self,
+        document_id: Uuid,
+        reason_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<(DocumentModel, ReasonReferenceModel), Box<dyn Error + Send + Sync>> {
+        let document = self
+            .transition(document_id, DocumentStatus::Rejected, audit)
+            .await?;
+
+        let reason_reference = ReasonReferenceModel {
+            id: Uuid::new_v4(),
+            reason_id,
+            entity_id: document_id,
+            additional_details: None,
+            details: None,
+            entity_type: EntityType::Document,
+            created_at: Utc::now(),
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: None,
+        };
+        let saved = self
+            .reason_reference_repository
+            .create_batch(vec![reason_reference], Some(audit.audit_log_id))
+            .await?;
+        let reason_reference = saved
+            .into_iter()
+            .next()
+            .ok_or("Reason reference was not created")?;
+
+        Ok((document, reason_reference))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::document_repository::test_utils::create_test_document_with_status;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason;
+    use crate::service::document_verification_service::DocumentVerificationService;
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::models::audit::entity_type::EntityType;
+    use business_core_db::models::person::document::DocumentStatus;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::load_batch::LoadBatch;
+
+    #[tokio::test]
+    async fn test_reject_links_reason() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+        let reason_reference_repo = &ctx.reason_and_purpose_repos().reason_reference_repository;
+        let service = DocumentVerificationService::new(ctx.person_repos(), ctx.reason_and_purpose_repos(), ctx.audit_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Grace Brewster");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let document = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved = document_repo.create_batch(vec![document], Some(audit_log.id)).await?;
+        let reason = create_test_reason(&format!("DOC_REJ_{}", random(6)), "Document unreadable");
+        let saved_reasons = reason_repo.create_batch(vec![reason], None).await?;
+
+        let reject_audit_log = create_test_audit_log();
+        audit_log_repo.create(&reject_audit_log).await?;
+        let (rejected, reason_reference) = service
+            .reject(saved[0].id, saved_reasons[0].id, &AuditContext::from(&reject_audit_log))
+            .await?;
+
+        assert_eq!(rejected.status, DocumentStatus::Rejected);
+
+        let loaded = reason_reference_repo.load_batch(&[reason_reference.id]).await?;
+        let loaded = loaded[0].as_ref().ok_or("Reason reference not found")?;
+        assert_eq!(loaded.reason_id, saved_reasons[0].id);
+        assert_eq!(loaded.entity_id, saved[0].id);
+        assert_eq!(loaded.entity_type, EntityType::Document);
+        assert_eq!(loaded.audit_log_id, Some(reject_audit_log.id));
+
+        Ok(())
+    }
+}
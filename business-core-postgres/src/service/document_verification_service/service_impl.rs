@@ -0,0 +1,114 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::models::person::document::{DocumentModel, DocumentStatus};
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::repository::update_batch::UpdateBatch;
+use std::error::Error;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::repository::audit::audit_log_repository::AuditLogRepositoryImpl;
+use crate::repository::audit::AuditRepositories;
+use crate::repository::person::{DocumentRepositoryImpl, PersonRepositories};
+use crate::repository::reason_and_purpose::{ReasonAndPurposeRepositories, ReasonReferenceRepositoryImpl};
+
+/// Typed error of the document verification workflow
+#[derive(Debug, Error)]
+pub enum DocumentVerificationError {
+    #[error("Document {0} not found")]
+    DocumentNotFound(Uuid),
+
+    #[error("Document {document_id} cannot move from {from} to {to}")]
+    IllegalTransition {
+        document_id: Uuid,
+        from: DocumentStatus,
+        to: DocumentStatus,
+    },
+
+    #[error("Audit log {0} not found")]
+    AuditLogNotFound(Uuid),
+
+    #[error("Audit log {audit_log_id} is not recorded for verifier {verifier_person_id}")]
+    VerifierMismatch {
+        audit_log_id: Uuid,
+        verifier_person_id: Uuid,
+    },
+}
+
+/// Service moving uploaded documents to Verified or Rejected
+///
+/// The verifier is the `updated_by_person_id` of the audit context of the status
+/// change, which must match its recorded audit log. A rejection additionally links the
+/// rejection reason to the document with a reason reference.
+pub struct DocumentVerificationService {
+    pub document_repository: Arc<DocumentRepositoryImpl>,
+    pub reason_reference_repository: Arc<ReasonReferenceRepositoryImpl>,
+    pub audit_log_repository: Arc<AuditLogRepositoryImpl>,
+}
+
+impl DocumentVerificationService {
+    pub fn new(
+        person_repos: &PersonRepositories,
+        reason_and_purpose_repos: &ReasonAndPurposeRepositories,
+        audit_repos: &AuditRepositories,
+    ) -> Self {
+        Self {
+            document_repository: person_repos.document_repository.clone(),
+            reason_reference_repository: reason_and_purpose_repos.reason_reference_repository.clone(),
+            audit_log_repository: audit_repos.audit_log_repository.clone(),
+        }
+    }
+
+    /// Move an Uploaded document to `target` and return the updated document
+    pub(super) async fn transition(
+        &self,
+        document_id: Uuid,
+        target: DocumentStatus,
+        audit: &AuditContext,
+    ) -> Result<DocumentModel, Box<dyn Error + Send + Sync>> {
+        let AuditContext {
+            audit_log_id,
+            updated_by_person_id: verifier_person_id,
+            ..
+        } = *audit;
+        let audit_log = self
+            .audit_log_repository
+            .load_batch(&[audit_log_id])
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or(DocumentVerificationError::AuditLogNotFound(audit_log_id))?;
+        if audit_log.updated_by_person_id != verifier_person_id {
+            return Err(DocumentVerificationError::VerifierMismatch {
+                audit_log_id,
+                verifier_person_id,
+            }
+            .into());
+        }
+
+        let mut document = self
+            .document_repository
+            .load(document_id)
+            .await?
+            .ok_or(DocumentVerificationError::DocumentNotFound(document_id))?;
+        if document.status != DocumentStatus::Uploaded {
+            return Err(DocumentVerificationError::IllegalTransition {
+                document_id,
+                from: document.status,
+                to: target,
+            }
+            .into());
+        }
+
+        document.status = target;
+        let updated = self
+            .document_repository
+            .update_batch(vec![document], Some(audit_log_id))
+            .await?;
+        updated
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Document {document_id} was not updated").into())
+    }
+}
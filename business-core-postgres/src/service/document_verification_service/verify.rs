@@ -0,0 +1,94 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::models::person::document::{DocumentModel, DocumentStatus};
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::DocumentVerificationService;
+
+impl DocumentVerificationService {
+    /// Mark an Uploaded document as Verified
+    ///
+    /// The verifier is the person of `audit`, whose audit log must be recorded for them.
+    /// Any other current status yields `DocumentVerificationError::IllegalTransition`.
+    pub async fn verify(
+        &self,
+        document_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<DocumentModel, Box<dyn Error + Send + Sync>> {
+        self.transition(document_id, DocumentStatus::Verified, audit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::document_repository::test_utils::create_test_document_with_status;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::service::document_verification_service::{DocumentVerificationError, DocumentVerificationService};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::models::person::document::DocumentStatus;
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    #[tokio::test]
+    async fn test_verify() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+        let service = DocumentVerificationService::new(ctx.person_repos(), ctx.reason_and_purpose_repos(), ctx.audit_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Ada Lovelace");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let document = create_test_document_with_status(person_id, DocumentStatus::Uploaded);
+        let saved = document_repo.create_batch(vec![document], Some(audit_log.id)).await?;
+
+        let verify_audit_log = create_test_audit_log();
+        audit_log_repo.create(&verify_audit_log).await?;
+        let verified = service
+            .verify(saved[0].id, &AuditContext::from(&verify_audit_log))
+            .await?;
+
+        assert_eq!(verified.status, DocumentStatus::Verified);
+        assert_eq!(verified.audit_log_id, Some(verify_audit_log.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_expired_is_illegal() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let document_repo = &ctx.person_repos().document_repository;
+        let service = DocumentVerificationService::new(ctx.person_repos(), ctx.reason_and_purpose_repos(), ctx.audit_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Alan Turing");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+        let document = create_test_document_with_status(person_id, DocumentStatus::Expired);
+        let saved = document_repo.create_batch(vec![document], Some(audit_log.id)).await?;
+
+        let verify_audit_log = create_test_audit_log();
+        audit_log_repo.create(&verify_audit_log).await?;
+        let error = service
+            .verify(saved[0].id, &AuditContext::from(&verify_audit_log))
+            .await
+            .expect_err("Expired document must not be verified");
+
+        match error.downcast_ref::<DocumentVerificationError>() {
+            Some(DocumentVerificationError::IllegalTransition { document_id, from, to }) => {
+                assert_eq!(*document_id, saved[0].id);
+                assert_eq!(*from, DocumentStatus::Expired);
+                assert_eq!(*to, DocumentStatus::Verified);
+            }
+            other => panic!("Expected IllegalTransition, got {other:?}"),
+        }
+
+        Ok(())
+    }
+}
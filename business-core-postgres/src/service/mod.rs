@@ -0,0 +1,38 @@
+//! Services combining the repositories of one or more modules
+//!
+//! A service is built from repository sets of a single unit of work session, so all
+//! its reads and writes go through the session's transaction and are committed or
+//! rolled back together by the caller.
+//!
+//! Service errors are typed enums returned boxed in `Box<dyn Error + Send + Sync>`,
+//! like `RepositoryError`; callers recover them with `downcast_ref`.
+
+pub mod address_service;
+pub mod audit_export_service;
+pub mod audit_retention_service;
+pub mod audit_service;
+pub mod calendar_bootstrap_service;
+pub mod calendar_rules_service;
+pub mod document_verification_service;
+pub mod person_export_service;
+pub mod person_privacy_service;
+pub mod person_service;
+pub mod reason_analytics_service;
+pub mod reason_and_purpose_service;
+pub mod reference_data_service;
+pub mod reindex_service;
+
+pub use address_service::AddressService;
+pub use audit_export_service::AuditExportService;
+pub use audit_retention_service::AuditRetentionService;
+pub use audit_service::AuditService;
+pub use calendar_bootstrap_service::CalendarBootstrapService;
+pub use calendar_rules_service::CalendarRulesService;
+pub use document_verification_service::DocumentVerificationService;
+pub use person_export_service::PersonExportService;
+pub use person_privacy_service::PersonPrivacyService;
+pub use person_service::PersonService;
+pub use reason_analytics_service::ReasonAnalyticsService;
+pub use reason_and_purpose_service::ReasonAndPurposeService;
+pub use reference_data_service::ReferenceDataService;
+pub use reindex_service::ReindexService;
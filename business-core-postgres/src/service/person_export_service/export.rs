@@ -0,0 +1,359 @@
+use business_core_db::models::person::person::PersonModel;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+use std::ops::ControlFlow;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::utils::TryFromRow;
+
+use super::service_impl::{PersonExportError, PersonExportService};
+
+/// Position of an export run, to resume it where it stopped
+///
+/// `max_id` is the highest person id when the export started. Resumed runs keep
+/// it, so persons inserted after the start are left out of every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportCursor {
+    /// Last exported person, `None` before the first batch
+    pub last_id: Option<Uuid>,
+    pub max_id: Uuid,
+}
+
+/// Outcome of one export run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportResult {
+    /// Persons written by this run
+    pub exported: usize,
+    /// Where the run stopped, `None` when there was nothing to export
+    pub cursor: Option<ExportCursor>,
+    /// Whether every person up to `max_id` has been exported
+    pub complete: bool,
+}
+
+/// One exported line: the person as its model serializes, and optionally its
+/// number of entity references
+#[derive(Serialize)]
+struct ExportLine<'a> {
+    #[serde(flatten)]
+    person: &'a PersonModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_references: Option<i64>,
+}
+
+impl PersonExportService {
+    /// Write persons to `writer` as newline-delimited JSON, ordered by id
+    ///
+    /// Each line is a `PersonModel` as serialized by its serde implementation, so
+    /// importers deserialize lines back to the model. With
+    /// `with_entity_reference_counts` a line also carries `entity_references`.
+    ///
+    /// Persons are read `batch_size` at a time, starting after `cursor.last_id`
+    /// when resuming. After each batch the writer is flushed and `on_batch`
+    /// receives the cursor to persist; `ControlFlow::Break` stops the run, which
+    /// a later call resumes from that cursor.
+    ///
+    /// Yields `PersonExportError::InvalidBatchSize` for a `batch_size` of 0.
+    pub async fn export<W: Write>(
+        &self,
+        mut writer: W,
+        cursor: Option<ExportCursor>,
+        batch_size: usize,
+        mut on_batch: impl FnMut(&ExportCursor) -> ControlFlow<()>,
+    ) -> Result<ExportResult, Box<dyn Error + Send + Sync>> {
+        if batch_size == 0 {
+            return Err(PersonExportError::InvalidBatchSize.into());
+        }
+        let limit = i64::try_from(batch_size)?;
+
+        let mut cursor = match cursor {
+            Some(cursor) => cursor,
+            None => match self.max_person_id().await? {
+                Some(max_id) => ExportCursor { last_id: None, max_id },
+                None => {
+                    return Ok(ExportResult {
+                        exported: 0,
+                        cursor: None,
+                        complete: true,
+                    })
+                }
+            },
+        };
+
+        let mut exported = 0;
+        loop {
+            let persons = self.load_batch_after(&cursor, limit).await?;
+            let Some(last) = persons.last() else {
+                return Ok(ExportResult {
+                    exported,
+                    cursor: Some(cursor),
+                    complete: true,
+                });
+            };
+            cursor.last_id = Some(last.id);
+
+            let counts = if self.include_entity_reference_counts {
+                Some(self.count_entity_references(&persons).await?)
+            } else {
+                None
+            };
+            for person in &persons {
+                let line = ExportLine {
+                    person,
+                    entity_references: counts
+                        .as_ref()
+                        .map(|counts| counts.get(&person.id).copied().unwrap_or(0)),
+                };
+                serde_json::to_writer(&mut writer, &line)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+            exported += persons.len();
+
+            let complete = persons.len() < batch_size || cursor.last_id == Some(cursor.max_id);
+            if on_batch(&cursor).is_break() || complete {
+                return Ok(ExportResult {
+                    exported,
+                    cursor: Some(cursor),
+                    complete,
+                });
+            }
+        }
+    }
+
+    async fn max_person_id(&self) -> Result<Option<Uuid>, Box<dyn Error + Send + Sync>> {
+        let mut tx = self.person_repository.operation_timeout.lock("person", &self.person_repository.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let max_id = sqlx::query_scalar("SELECT id FROM person ORDER BY id DESC LIMIT 1")
+            .fetch_optional(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("person", e))?;
+        Ok(max_id)
+    }
+
+    /// Up to `limit` persons after `cursor.last_id`, up to `cursor.max_id`
+    async fn load_batch_after(
+        &self,
+        cursor: &ExportCursor,
+        limit: i64,
+    ) -> Result<Vec<PersonModel>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.person_repository.operation_timeout.lock("person", &self.person_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT * FROM person
+                WHERE ($1::uuid IS NULL OR id > $1) AND id <= $2
+                ORDER BY id
+                LIMIT $3
+                "#,
+            )
+            .bind(cursor.last_id)
+            .bind(cursor.max_id)
+            .bind(limit)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("person", e))?
+        };
+
+        let mut persons = Vec::with_capacity(rows.len());
+        for row in rows {
+            persons.push(PersonModel::try_from_row(&row)?);
+        }
+        Ok(persons)
+    }
+
+    async fn count_entity_references(
+        &self,
+        persons: &[PersonModel],
+    ) -> Result<HashMap<Uuid, i64>, Box<dyn Error + Send + Sync>> {
+        let person_ids: Vec<Uuid> = persons.iter().map(|person| person.id).collect();
+        let rows = {
+            let mut tx = self.person_repository.operation_timeout.lock("person", &self.person_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT person_id, COUNT(*) AS count FROM entity_reference
+                WHERE person_id = ANY($1)
+                GROUP BY person_id
+                "#,
+            )
+            .bind(&person_ids)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("entity_reference", e))?
+        };
+
+        let mut counts = HashMap::with_capacity(rows.len());
+        for row in rows {
+            counts.insert(row.try_get("person_id")?, row.try_get("count")?);
+        }
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ExportCursor, PersonExportService};
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_entity_reference, create_test_person,
+    };
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::person::person::PersonModel;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use std::collections::HashSet;
+    use std::ops::ControlFlow;
+    use uuid::Uuid;
+
+    /// Lines of `export` belonging to `ids`, leaving out persons of concurrent tests
+    fn lines_of<'a>(export: &'a [u8], ids: &HashSet<Uuid>) -> Vec<&'a str> {
+        std::str::from_utf8(export)
+            .unwrap()
+            .lines()
+            .filter(|line| {
+                let person: PersonModel = serde_json::from_str(line).unwrap();
+                ids.contains(&person.id)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resumed_export_matches_single_run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let persons: Vec<PersonModel> = (0..1000)
+            .map(|i| create_test_person(&format!("Export Person {i}")))
+            .collect();
+        let saved = person_repos
+            .person_repository
+            .create_batch(persons, Some(audit_log.id))
+            .await?;
+        let ids: HashSet<Uuid> = saved.iter().map(|person| person.id).collect();
+
+        let service = PersonExportService::new(person_repos);
+
+        let mut single = Vec::new();
+        let result = service
+            .export(&mut single, None, 100, |_| ControlFlow::Continue(()))
+            .await?;
+        assert!(result.complete);
+
+        // First run interrupted after three batches
+        let mut resumed = Vec::new();
+        let mut batches = 0;
+        let first = service
+            .export(&mut resumed, None, 100, |_| {
+                batches += 1;
+                if batches == 3 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .await?;
+        assert!(!first.complete);
+        assert_eq!(first.exported, 300);
+        let cursor = first.cursor.expect("Three batches were exported");
+
+        // A person inserted after the export started is left out of the resumed run.
+        // Its id is above every v4 id, whose version nibble is 4.
+        let late_id = Uuid::from_u128(u128::MAX - u128::from(Uuid::new_v4().as_u128() as u32));
+        let mut late = create_test_person("Late Person");
+        late.id = late_id;
+        person_repos
+            .person_repository
+            .create_batch(vec![late], Some(audit_log.id))
+            .await?;
+
+        // The cursor survives a round trip through storage
+        let cursor: ExportCursor = serde_json::from_str(&serde_json::to_string(&cursor)?)?;
+        let second = service
+            .export(&mut resumed, Some(cursor), 100, |_| ControlFlow::Continue(()))
+            .await?;
+        assert!(second.complete);
+        assert_eq!(second.cursor.map(|cursor| cursor.max_id), Some(cursor.max_id));
+
+        let single_lines = lines_of(&single, &ids);
+        assert_eq!(single_lines.len(), 1000);
+        assert_eq!(lines_of(&resumed, &ids), single_lines);
+        assert!(!String::from_utf8(resumed)?.contains(&late_id.to_string()));
+
+        // Lines round-trip to the persisted persons, in id order
+        let mut sorted = saved.clone();
+        sorted.sort_by_key(|person| person.id);
+        for (line, person) in single_lines.iter().zip(&sorted) {
+            assert_eq!(serde_json::from_str::<serde_json::Value>(line)?, serde_json::to_value(person)?);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_with_entity_reference_counts() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let saved = person_repos
+            .person_repository
+            .create_batch(
+                vec![create_test_person("Referenced"), create_test_person("Unreferenced")],
+                Some(audit_log.id),
+            )
+            .await?;
+        person_repos
+            .entity_reference_repository
+            .create_batch(
+                vec![
+                    create_test_entity_reference(saved[0].id, "REF-1"),
+                    create_test_entity_reference(saved[0].id, "REF-2"),
+                ],
+                Some(audit_log.id),
+            )
+            .await?;
+
+        let service = PersonExportService::new(person_repos).with_entity_reference_counts();
+        let mut export = Vec::new();
+        service
+            .export(&mut export, None, 50, |_| ControlFlow::Continue(()))
+            .await?;
+
+        let lines: Vec<serde_json::Value> = std::str::from_utf8(&export)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        let count_of = |id: Uuid| {
+            lines
+                .iter()
+                .find(|line| line["id"] == serde_json::json!(id))
+                .map(|line| line["entity_references"].clone())
+        };
+        assert_eq!(count_of(saved[0].id), Some(serde_json::json!(2)));
+        assert_eq!(count_of(saved[1].id), Some(serde_json::json!(0)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_empty_batches() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let service = PersonExportService::new(ctx.person_repos());
+
+        let error = service
+            .export(Vec::new(), None, 0, |_| ControlFlow::Continue(()))
+            .await
+            .unwrap_err();
+        assert!(error.downcast_ref::<super::super::PersonExportError>().is_some());
+
+        Ok(())
+    }
+}
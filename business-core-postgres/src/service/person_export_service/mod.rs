@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod export;
+
+pub use service_impl::{PersonExportError, PersonExportService};
+pub use export::{ExportCursor, ExportResult};
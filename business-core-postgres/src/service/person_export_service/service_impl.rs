@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::person::{PersonRepositories, PersonRepositoryImpl};
+
+/// Typed error of the person export service
+#[derive(Debug, Error)]
+pub enum PersonExportError {
+    #[error("Export batch size must be at least 1")]
+    InvalidBatchSize,
+}
+
+/// Service exporting all persons as newline-delimited JSON
+///
+/// Every batch of an export run is read in the session's transaction, so the export
+/// is one consistent snapshot.
+pub struct PersonExportService {
+    pub person_repository: Arc<PersonRepositoryImpl>,
+    /// Add the number of entity references of each person to its line
+    pub include_entity_reference_counts: bool,
+}
+
+impl PersonExportService {
+    pub fn new(repos: &PersonRepositories) -> Self {
+        Self {
+            person_repository: repos.person_repository.clone(),
+            include_entity_reference_counts: false,
+        }
+    }
+
+    /// Add an `entity_references` field to each exported line
+    pub fn with_entity_reference_counts(mut self) -> Self {
+        self.include_entity_reference_counts = true;
+        self
+    }
+}
@@ -0,0 +1,337 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::repository::pagination::PageRequest;
+use business_core_db::repository::update_batch::UpdateBatch;
+use heapless::String as HeaplessString;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+
+use super::service_impl::{PersonPrivacyError, PersonPrivacyService};
+
+/// Page size used to collect the entity references of a person
+const ENTITY_REFERENCE_PAGE_SIZE: usize = 100;
+
+impl PersonPrivacyService {
+    /// Erase the personal data of a person
+    ///
+    /// The display name and `id_number` are replaced by placeholders derived from the id,
+    /// and `external_identifier`, `messaging_info1..5` and `department` are cleared through
+    /// the regular `update_batch`, which writes the audit entry under the audit log of `audit`.
+    /// The `reference_details` of the person's entity references are cleared the same way.
+    ///
+    /// Earlier audit snapshots of the person and its entity references are scrubbed in
+    /// place and flagged `redacted`. Their hashes are left as stored so the audit chain
+    /// still links, and verification skips the hash of a redacted version. Ids and
+    /// relations are not touched.
+    pub async fn anonymize(
+        &self,
+        person_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let audit_log_id = audit.audit_log_id;
+        let mut person = self
+            .person_repository
+            .load(person_id)
+            .await?
+            .ok_or(PersonPrivacyError::PersonNotFound(person_id))?;
+
+        let pseudonym = pseudonym(person_id);
+        let id_number = redacted_id_number(person_id);
+        person.display_name = pseudonym.clone();
+        person.external_identifier = None;
+        person.id_number = id_number.clone();
+        person.messaging_info1 = None;
+        person.messaging_info2 = None;
+        person.messaging_info3 = None;
+        person.messaging_info4 = None;
+        person.messaging_info5 = None;
+        person.department = None;
+        self.person_repository
+            .update_batch(vec![person], Some(audit_log_id))
+            .await?;
+
+        let entity_reference_ids = self.entity_reference_ids(person_id).await?;
+        let entity_references = self
+            .entity_reference_repository
+            .load_batch(&entity_reference_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|mut entity_reference| {
+                entity_reference.reference_details_l1 = None;
+                entity_reference.reference_details_l2 = None;
+                entity_reference.reference_details_l3 = None;
+                entity_reference
+            })
+            .collect();
+        self.entity_reference_repository
+            .update_batch(entity_references, Some(audit_log_id))
+            .await?;
+
+        let mut tx = self.person_repository.operation_timeout.lock("person", &self.person_repository.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+
+        sqlx::query(
+            r#"
+            UPDATE person_audit SET
+            display_name = $3, external_identifier = NULL, id_number = $4,
+            messaging_info1 = NULL, messaging_info2 = NULL, messaging_info3 = NULL,
+            messaging_info4 = NULL, messaging_info5 = NULL, department = NULL,
+            redacted = TRUE
+            WHERE id = $1 AND audit_log_id <> $2
+            "#,
+        )
+        .bind(person_id)
+        .bind(audit_log_id)
+        .bind(pseudonym.as_str())
+        .bind(id_number.as_str())
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| map_db_error("person", e))?;
+
+        sqlx::query(
+            r#"
+            UPDATE entity_reference_audit SET
+            reference_details_l1 = NULL, reference_details_l2 = NULL, reference_details_l3 = NULL,
+            redacted = TRUE
+            WHERE id = ANY($1) AND audit_log_id <> $2
+            "#,
+        )
+        .bind(&entity_reference_ids)
+        .bind(audit_log_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| map_db_error("entity_reference", e))?;
+
+        Ok(())
+    }
+
+    async fn entity_reference_ids(&self, person_id: Uuid) -> Result<Vec<Uuid>, Box<dyn Error + Send + Sync>> {
+        let mut ids = Vec::new();
+        loop {
+            let page = self
+                .entity_reference_repository
+                .find_by_person_id(person_id, PageRequest::new(ENTITY_REFERENCE_PAGE_SIZE, ids.len()))
+                .await?;
+            ids.extend(page.items.iter().map(|idx| idx.id));
+            if page.items.is_empty() || ids.len() >= page.total {
+                return Ok(ids);
+            }
+        }
+    }
+}
+
+/// Pseudonymous display name, stable for a person
+fn pseudonym(person_id: Uuid) -> HeaplessString<100> {
+    let mut pseudonym = HeaplessString::new();
+    // "Anonymized " and a hyphenated uuid fit in 47 characters
+    let _ = pseudonym.push_str("Anonymized ");
+    let _ = pseudonym.push_str(&person_id.to_string());
+    pseudonym
+}
+
+/// Placeholder id number, distinct per person so anonymized persons keep distinct id_number hashes
+fn redacted_id_number(person_id: Uuid) -> HeaplessString<50> {
+    let mut id_number = HeaplessString::new();
+    // A uuid without hyphens has 32 characters
+    let _ = id_number.push_str(&person_id.simple().to_string());
+    id_number
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{PersonPrivacyError, PersonPrivacyService};
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_entity_reference, create_test_person,
+    };
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::models::audit_chained::verify_chain_from;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::update_batch::UpdateBatch;
+    use heapless::String as HeaplessString;
+    use sqlx::Row;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_anonymize_person() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+        let person_repo = &person_repos.person_repository;
+        let entity_reference_repo = &person_repos.entity_reference_repository;
+
+        let create_audit_log = create_test_audit_log();
+        audit_log_repo.create(&create_audit_log).await?;
+        let organization = create_test_person("Employer Ltd");
+        let mut person = create_test_person("Jane Doe");
+        person.external_identifier = Some(HeaplessString::try_from("EXT-JANE").unwrap());
+        person.messaging_info1 = Some(HeaplessString::try_from("jane@example.com").unwrap());
+        person.department = Some(HeaplessString::try_from("Finance").unwrap());
+        person.organization_person_id = Some(organization.id);
+        let person_id = person.id;
+        person_repo
+            .create_batch(vec![organization.clone(), person], Some(create_audit_log.id))
+            .await?;
+
+        let mut entity_reference = create_test_entity_reference(person_id, "CUST-JANE");
+        entity_reference.reference_details_l1 = Some(HeaplessString::try_from("Account manager note").unwrap());
+        let entity_reference_id = entity_reference.id;
+        entity_reference_repo
+            .create_batch(vec![entity_reference], Some(create_audit_log.id))
+            .await?;
+
+        let update_audit_log = create_test_audit_log();
+        audit_log_repo.create(&update_audit_log).await?;
+        let mut person = person_repo.load(person_id).await?.unwrap();
+        person.messaging_info2 = Some(HeaplessString::try_from("+237600000000").unwrap());
+        person_repo.update_batch(vec![person], Some(update_audit_log.id)).await?;
+
+        let hashes_before: Vec<i64> = {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query_scalar("SELECT hash FROM person_audit WHERE id = $1 ORDER BY audit_log_id")
+                .bind(person_id)
+                .fetch_all(&mut **transaction)
+                .await?
+        };
+
+        let anonymize_audit_log = create_test_audit_log();
+        audit_log_repo.create(&anonymize_audit_log).await?;
+        let service = PersonPrivacyService::new(person_repos);
+        service.anonymize(person_id, &AuditContext::from(&anonymize_audit_log)).await?;
+
+        // Main table
+        let person = person_repo.load(person_id).await?.unwrap();
+        assert_eq!(person.display_name.as_str(), format!("Anonymized {person_id}"));
+        assert!(person.external_identifier.is_none());
+        assert_eq!(person.id_number.as_str(), person_id.simple().to_string());
+        assert!(person.messaging_info1.is_none());
+        assert!(person.messaging_info2.is_none());
+        assert!(person.department.is_none());
+        assert_eq!(person.organization_person_id, Some(organization.id));
+        assert_eq!(person.audit_log_id, Some(anonymize_audit_log.id));
+
+        let entity_reference = entity_reference_repo.load(entity_reference_id).await?.unwrap();
+        assert!(entity_reference.reference_details_l1.is_none());
+        assert_eq!(entity_reference.person_id, person_id);
+        assert_eq!(entity_reference.reference_external_id.as_str(), "CUST-JANE");
+
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+
+        // Index table
+        let idx = sqlx::query("SELECT * FROM person_idx WHERE id = $1")
+            .bind(person_id)
+            .fetch_one(&mut **transaction)
+            .await?;
+        assert!(idx.get::<Option<i64>, _>("external_identifier_hash").is_none());
+        assert_eq!(idx.get::<Option<Uuid>, _>("organization_person_id"), Some(organization.id));
+
+        // Audit tables: earlier snapshots are scrubbed and keep their hash
+        let audits = sqlx::query("SELECT * FROM person_audit WHERE id = $1 ORDER BY audit_log_id")
+            .bind(person_id)
+            .fetch_all(&mut **transaction)
+            .await?;
+        // Creation, reference count, update and anonymization
+        assert_eq!(audits.len(), 4);
+        for audit in &audits {
+            let audit_log_id: Uuid = audit.get("audit_log_id");
+            assert_eq!(audit.get::<String, _>("display_name"), format!("Anonymized {person_id}"));
+            assert!(audit.get::<Option<String>, _>("external_identifier").is_none());
+            assert!(audit.get::<Option<String>, _>("messaging_info1").is_none());
+            assert!(audit.get::<Option<String>, _>("messaging_info2").is_none());
+            assert!(audit.get::<Option<String>, _>("department").is_none());
+            assert_eq!(audit.get::<String, _>("id_number"), person_id.simple().to_string());
+            assert_eq!(audit.get::<Option<Uuid>, _>("organization_person_id"), Some(organization.id));
+            assert_eq!(audit.get::<bool, _>("redacted"), audit_log_id != anonymize_audit_log.id);
+            if audit_log_id != anonymize_audit_log.id {
+                assert!(hashes_before.contains(&audit.get::<i64, _>("hash")));
+            }
+        }
+
+        let details: Vec<(Option<String>, bool)> = sqlx::query_as(
+            "SELECT reference_details_l1, redacted FROM entity_reference_audit WHERE id = $1",
+        )
+        .bind(entity_reference_id)
+        .fetch_all(&mut **transaction)
+        .await?;
+        assert_eq!(details.len(), 2);
+        assert!(details.iter().all(|(detail, _)| detail.is_none()));
+        assert!(details.iter().any(|(_, redacted)| *redacted));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_anonymized_chains_verify() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+        let person_repo = &person_repos.person_repository;
+        let entity_reference_repo = &person_repos.entity_reference_repository;
+
+        let create_audit_log = create_test_audit_log();
+        audit_log_repo.create(&create_audit_log).await?;
+        let persons = vec![create_test_person("John Roe"), create_test_person("Mary Roe")];
+        let person_ids: Vec<Uuid> = persons.iter().map(|person| person.id).collect();
+        person_repo.create_batch(persons, Some(create_audit_log.id)).await?;
+        let reference_audit_log = create_test_audit_log();
+        audit_log_repo.create(&reference_audit_log).await?;
+        let mut entity_reference = create_test_entity_reference(person_ids[0], "CUST-JOHN");
+        entity_reference.reference_details_l1 = Some(HeaplessString::try_from("Branch note").unwrap());
+        let entity_reference_id = entity_reference.id;
+        entity_reference_repo
+            .create_batch(vec![entity_reference], Some(reference_audit_log.id))
+            .await?;
+
+        let service = PersonPrivacyService::new(person_repos);
+        for person_id in &person_ids {
+            let anonymize_audit_log = create_test_audit_log();
+            audit_log_repo.create(&anonymize_audit_log).await?;
+            service.anonymize(*person_id, &AuditContext::from(&anonymize_audit_log)).await?;
+        }
+
+        let versions = person_repo.load_audit_versions(person_ids[0]).await?;
+        assert_eq!(versions.len(), 3);
+        assert!(versions[..2].iter().all(|version| version.redacted));
+        assert!(!versions[2].redacted);
+        assert_eq!(verify_chain_from(&versions, None), Ok(()));
+
+        let versions = entity_reference_repo.load_audit_versions(entity_reference_id).await?;
+        assert_eq!(versions.len(), 2);
+        assert_eq!(verify_chain_from(&versions, None), Ok(()));
+
+        // The placeholders are derived from the id, so the anonymized persons stay distinct
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let id_number_hashes: Vec<Option<i64>> = sqlx::query_scalar("SELECT id_number_hash FROM person_idx WHERE id = ANY($1)")
+            .bind(&person_ids)
+            .fetch_all(&mut **transaction)
+            .await?;
+        assert_eq!(id_number_hashes.len(), 2);
+        assert_ne!(id_number_hashes[0], id_number_hashes[1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_anonymize_unknown_person() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let service = PersonPrivacyService::new(ctx.person_repos());
+
+        let error = service.anonymize(Uuid::new_v4(), &AuditContext::from(&audit_log)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PersonPrivacyError>(),
+            Some(PersonPrivacyError::PersonNotFound(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod service_impl;
+pub mod anonymize;
+
+pub use service_impl::{PersonPrivacyError, PersonPrivacyService};
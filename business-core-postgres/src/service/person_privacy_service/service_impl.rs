@@ -0,0 +1,27 @@
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::repository::person::{EntityReferenceRepositoryImpl, PersonRepositories, PersonRepositoryImpl};
+
+/// Typed error of the person privacy operations
+#[derive(Debug, Error)]
+pub enum PersonPrivacyError {
+    #[error("Person {0} not found")]
+    PersonNotFound(Uuid),
+}
+
+/// Service for the erasure of personal data
+pub struct PersonPrivacyService {
+    pub person_repository: Arc<PersonRepositoryImpl>,
+    pub entity_reference_repository: Arc<EntityReferenceRepositoryImpl>,
+}
+
+impl PersonPrivacyService {
+    pub fn new(repos: &PersonRepositories) -> Self {
+        Self {
+            person_repository: repos.person_repository.clone(),
+            entity_reference_repository: repos.entity_reference_repository.clone(),
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::models::audit::entity_type::EntityType;
+use business_core_db::models::audit_chained::order_chain;
+use business_core_db::models::person::common_enums::PersonStatus;
+use business_core_db::models::person::person::PersonModel;
+use business_core_db::models::reason_and_purpose::reason::{ReasonContext, ReasonModel};
+use business_core_db::models::reason_and_purpose::reason_reference::ReasonReferenceModel;
+use business_core_db::repository::create_batch::CreateBatch;
+use business_core_db::repository::load_audits::LoadAudits;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::repository::pagination::PageRequest;
+use business_core_db::repository::update_batch::UpdateBatch;
+use std::collections::HashMap;
+use chrono::Utc;
+use heapless::String as HeaplessString;
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::RepositoryError;
+
+use super::service_impl::{PersonService, PersonServiceError};
+
+/// Page size used to read the audit history of a person
+const AUDIT_PAGE_SIZE: usize = 100;
+
+/// One status change of a person, as recorded in person_audit
+#[derive(Debug, Clone)]
+pub struct StatusChange {
+    /// Audit log of the update that changed the status
+    pub audit_log_id: Uuid,
+    pub from: PersonStatus,
+    pub to: PersonStatus,
+    /// Reason linked to the change, `None` for a change made outside `change_status`
+    pub reason: Option<ReasonModel>,
+}
+
+impl<P, R, RR> PersonService<P, R, RR>
+where
+    P: LoadBatch<Postgres, PersonModel> + UpdateBatch<Postgres, PersonModel>,
+    R: LoadBatch<Postgres, ReasonModel>,
+    RR: CreateBatch<Postgres, ReasonReferenceModel>,
+{
+    /// Change the status of a person and link the reason of the change to it
+    ///
+    /// The reason must have the Customer or Compliance context. The person is updated
+    /// through `update_batch` and a reason reference to the person, carrying `details`,
+    /// is created under the audit log of `audit`, so `status_change_history` can pair
+    /// them. A reason with `requires_details` and no `details` fails with
+    /// `RepositoryError::MissingReasonDetails` before the person is touched. A move that
+    /// `PersonStatus::can_transition_to` refuses fails with
+    /// `RepositoryError::InvalidTransition` from the Postgres person repository.
+    pub async fn change_status(
+        &self,
+        person_id: Uuid,
+        new_status: PersonStatus,
+        reason_id: Uuid,
+        details: Option<HeaplessString<500>>,
+        audit: &AuditContext,
+    ) -> Result<PersonModel, Box<dyn Error + Send + Sync>> {
+        let reason = self
+            .reason_repository
+            .load_batch(&[reason_id])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or(PersonServiceError::ReasonNotFound(reason_id))?;
+        if !matches!(reason.context, ReasonContext::Customer | ReasonContext::Compliance) {
+            return Err(PersonServiceError::ReasonContextMismatch {
+                reason_id,
+                context: reason.context,
+            }
+            .into());
+        }
+        if reason.requires_details && details.is_none() {
+            return Err(RepositoryError::MissingReasonDetails {
+                entity: "reason_reference".to_string(),
+                codes: vec![reason.code.to_string()],
+            }
+            .into());
+        }
+
+        let mut person = self
+            .person_repository
+            .load_batch(&[person_id])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or(PersonServiceError::PersonNotFound(person_id))?;
+        if person.status == new_status {
+            return Err(PersonServiceError::StatusUnchanged {
+                person_id,
+                status: new_status,
+            }
+            .into());
+        }
+        person.status = new_status;
+        let person = self
+            .person_repository
+            .update_batch(vec![person], Some(audit.audit_log_id))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or("Person was not updated")?;
+
+        let reason_reference = ReasonReferenceModel {
+            id: Uuid::new_v4(),
+            reason_id,
+            entity_id: person_id,
+            additional_details: None,
+            details,
+            entity_type: EntityType::Person,
+            created_at: Utc::now(),
+            antecedent_hash: 0,
+            antecedent_audit_log_id: Uuid::nil(),
+            hash: 0,
+            audit_log_id: None,
+        };
+        self.reason_reference_repository
+            .create_batch(vec![reason_reference], Some(audit.audit_log_id))
+            .await?;
+
+        Ok(person)
+    }
+}
+
+impl PersonService {
+    /// Status changes of a person, oldest first, with the reasons linked to them
+    ///
+    /// Every person_audit row whose status differs from the one of its antecedent row is
+    /// a change. Rows are paired through their hashes, so one audit log holding two
+    /// versions of the person still yields each change. The reason is the one of the person's reason reference created under
+    /// the audit log of the change.
+    pub async fn status_change_history(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<StatusChange>, Box<dyn Error + Send + Sync>> {
+        let mut audits = Vec::new();
+        loop {
+            let page = self
+                .person_repository
+                .load_audits(person_id, PageRequest::new(AUDIT_PAGE_SIZE, audits.len()))
+                .await?;
+            let done = page.items.is_empty() || audits.len() + page.items.len() >= page.total;
+            audits.extend(page.items);
+            if done {
+                break;
+            }
+        }
+
+        let audits = order_chain(audits);
+        let status_by_hash: HashMap<i64, PersonStatus> = audits.iter().map(|audit| (audit.hash, audit.status)).collect();
+        let changes: Vec<(Uuid, PersonStatus, PersonStatus)> = audits
+            .iter()
+            .filter_map(|audit| {
+                let audit_log_id = audit.audit_log_id?;
+                let from = *status_by_hash.get(&audit.antecedent_hash)?;
+                (from != audit.status).then_some((audit_log_id, from, audit.status))
+            })
+            .collect();
+
+        let change_audit_log_ids: Vec<Uuid> = changes.iter().map(|change| change.0).collect();
+        let reason_ids = self.reason_ids_by_audit_log(person_id, &change_audit_log_ids).await?;
+        let mut unique_reason_ids: Vec<Uuid> = reason_ids.values().copied().collect();
+        unique_reason_ids.sort();
+        unique_reason_ids.dedup();
+        let reasons: HashMap<Uuid, ReasonModel> = self
+            .reason_repository
+            .load_batch(&unique_reason_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|reason| (reason.id, reason))
+            .collect();
+
+        Ok(changes
+            .into_iter()
+            .map(|(audit_log_id, from, to)| StatusChange {
+                audit_log_id,
+                from,
+                to,
+                reason: reason_ids
+                    .get(&audit_log_id)
+                    .and_then(|reason_id| reasons.get(reason_id).cloned()),
+            })
+            .collect())
+    }
+
+    /// Reason of the person's reason reference created under each of `audit_log_ids`
+    async fn reason_ids_by_audit_log(
+        &self,
+        person_id: Uuid,
+        audit_log_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Uuid>, Box<dyn Error + Send + Sync>> {
+        if audit_log_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut tx = self.reason_reference_repository.operation_timeout.lock("reason_reference", &self.reason_reference_repository.executor.tx).await?;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            SELECT audit_log_id, reason_id FROM reason_reference_audit
+            WHERE entity_id = $1 AND entity_type = $2 AND audit_log_id = ANY($3)
+            AND antecedent_audit_log_id = '00000000-0000-0000-0000-000000000000'
+            "#,
+        )
+        .bind(person_id)
+        .bind(EntityType::Person)
+        .bind(audit_log_ids)
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{PersonService, PersonServiceError};
+    use crate::error::RepositoryError;
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason_with_context;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::models::person::common_enums::PersonStatus;
+    use business_core_db::models::reason_and_purpose::reason::{ReasonCategory, ReasonContext};
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::repository::in_memory::setup_mock_context;
+    use business_core_db::repository::load_batch::LoadBatch;
+    use heapless::String as HeaplessString;
+
+    #[tokio::test]
+    async fn test_change_status_with_aml_reason() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Status Change");
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut reason = create_test_reason_with_context("STATUS_AML_BLOCK", "AML alert confirmed", ReasonContext::Compliance);
+        reason.category = ReasonCategory::AmlAlert;
+        let reason_id = reason.id;
+        reason_repo.create_batch(vec![reason], None).await?;
+
+        let change_audit_log = create_test_audit_log();
+        audit_log_repo.create(&change_audit_log).await?;
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+        let updated = service
+            .change_status(person_id, PersonStatus::Blacklisted, reason_id, None, &AuditContext::from(&change_audit_log))
+            .await?;
+
+        assert_eq!(updated.status, PersonStatus::Blacklisted);
+        assert_eq!(person_repo.load(person_id).await?.unwrap().status, PersonStatus::Blacklisted);
+
+        let history = service.status_change_history(person_id).await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].audit_log_id, change_audit_log.id);
+        assert_eq!(history[0].to, PersonStatus::Blacklisted);
+        assert_eq!(history[0].reason.as_ref().map(|reason| reason.id), Some(reason_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_change_status_with_required_details() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Required Details");
+        let person_id = person.id;
+        let status_before = person.status;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut reason = create_test_reason_with_context("STATUS_SANCTIONS", "Sanctions list match", ReasonContext::Compliance);
+        reason.category = ReasonCategory::AmlAlert;
+        reason.requires_details = true;
+        let reason_id = reason.id;
+        reason_repo.create_batch(vec![reason], None).await?;
+
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+        let change_audit_log = create_test_audit_log();
+        audit_log_repo.create(&change_audit_log).await?;
+        let error = service
+            .change_status(person_id, PersonStatus::Blacklisted, reason_id, None, &AuditContext::from(&change_audit_log))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::MissingReasonDetails { codes, .. }) if codes == &vec!["STATUS_SANCTIONS".to_string()]
+        ));
+        assert_eq!(person_repo.load(person_id).await?.unwrap().status, status_before);
+
+        let details = HeaplessString::try_from("Matched on the consolidated sanctions list").unwrap();
+        service
+            .change_status(
+                person_id,
+                PersonStatus::Blacklisted,
+                reason_id,
+                Some(details.clone()),
+                &AuditContext::from(&change_audit_log),
+            )
+            .await?;
+        assert_eq!(person_repo.load(person_id).await?.unwrap().status, PersonStatus::Blacklisted);
+
+        let mut tx = person_repo.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let stored: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT details FROM reason_reference WHERE entity_id = $1 AND reason_id = $2")
+                .bind(person_id)
+                .bind(reason_id)
+                .fetch_all(&mut **transaction)
+                .await?;
+        assert_eq!(stored, vec![Some(details.to_string())]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_change_status_rejects_loan_reason() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = create_test_person("Loan Reason");
+        let person_id = person.id;
+        let status_before = person.status;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let reason = create_test_reason_with_context("STATUS_LOAN", "Loan rejected", ReasonContext::Loan);
+        let reason_id = reason.id;
+        reason_repo.create_batch(vec![reason], None).await?;
+
+        let change_audit_log = create_test_audit_log();
+        audit_log_repo.create(&change_audit_log).await?;
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+        let error = service
+            .change_status(person_id, PersonStatus::Blacklisted, reason_id, None, &AuditContext::from(&change_audit_log))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<PersonServiceError>(),
+            Some(PersonServiceError::ReasonContextMismatch { context: ReasonContext::Loan, .. })
+        ));
+        assert_eq!(person_repo.load(person_id).await?.unwrap().status, status_before);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_status_change_history() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let reason_repo = &ctx.reason_and_purpose_repos().reason_repository;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let mut person = create_test_person("Status History");
+        person.status = PersonStatus::Active;
+        let person_id = person.id;
+        person_repo.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let reasons = vec![
+            create_test_reason_with_context("HISTORY_REVIEW", "Verification review", ReasonContext::Customer),
+            create_test_reason_with_context("HISTORY_BLOCK", "Sanctions hit", ReasonContext::Compliance),
+            create_test_reason_with_context("HISTORY_CLEARED", "Sanctions hit cleared", ReasonContext::Compliance),
+        ];
+        let reason_ids: Vec<_> = reasons.iter().map(|reason| reason.id).collect();
+        reason_repo.create_batch(reasons, None).await?;
+
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+        let statuses = [
+            PersonStatus::PendingVerification,
+            PersonStatus::Blacklisted,
+            PersonStatus::PendingVerification,
+        ];
+        let mut change_audit_log_ids = Vec::new();
+        for (status, reason_id) in statuses.iter().zip(&reason_ids) {
+            let change_audit_log = create_test_audit_log();
+            audit_log_repo.create(&change_audit_log).await?;
+            service
+                .change_status(person_id, *status, *reason_id, None, &AuditContext::from(&change_audit_log))
+                .await?;
+            change_audit_log_ids.push(change_audit_log.id);
+        }
+
+        let history = service.status_change_history(person_id).await?;
+
+        assert_eq!(history.len(), 3);
+        let transitions: Vec<_> = history.iter().map(|change| (change.from, change.to)).collect();
+        assert_eq!(
+            transitions,
+            vec![
+                (PersonStatus::Active, PersonStatus::PendingVerification),
+                (PersonStatus::PendingVerification, PersonStatus::Blacklisted),
+                (PersonStatus::Blacklisted, PersonStatus::PendingVerification),
+            ]
+        );
+        for ((change, audit_log_id), reason_id) in history.iter().zip(&change_audit_log_ids).zip(&reason_ids) {
+            assert_eq!(change.audit_log_id, *audit_log_id);
+            assert_eq!(change.reason.as_ref().map(|reason| reason.id), Some(*reason_id));
+        }
+
+        Ok(())
+    }
+
+    // Ported from test_change_status_with_aml_reason, on the in-memory repositories
+    #[tokio::test]
+    async fn test_change_status_in_memory() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_mock_context();
+        let service = PersonService::with_repositories(
+            ctx.person_repository.clone(),
+            ctx.reason_repository.clone(),
+            ctx.reason_reference_repository.clone(),
+        );
+
+        let audit_log = create_test_audit_log();
+        let person = create_test_person("In Memory Status Change");
+        let person_id = person.id;
+        ctx.person_repository.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut reason = create_test_reason_with_context("STATUS_AML_BLOCK", "AML alert confirmed", ReasonContext::Compliance);
+        reason.category = ReasonCategory::AmlAlert;
+        let reason_id = reason.id;
+        ctx.reason_repository.create_batch(vec![reason], None).await?;
+
+        let change_audit_log = create_test_audit_log();
+        let updated = service
+            .change_status(person_id, PersonStatus::Blacklisted, reason_id, None, &AuditContext::from(&change_audit_log))
+            .await?;
+
+        assert_eq!(updated.status, PersonStatus::Blacklisted);
+        assert_eq!(updated.audit_log_id, Some(change_audit_log.id));
+        let loaded = ctx.person_repository.load_batch(&[person_id]).await?;
+        assert_eq!(loaded[0].as_ref().unwrap().status, PersonStatus::Blacklisted);
+        assert_eq!(ctx.reason_reference_repository.len(), 1);
+
+        Ok(())
+    }
+
+    // Ported from test_change_status_with_required_details and
+    // test_change_status_rejects_loan_reason, on the in-memory repositories
+    #[tokio::test]
+    async fn test_change_status_in_memory_rejects_invalid_reason() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_mock_context();
+        let service = PersonService::with_repositories(
+            ctx.person_repository.clone(),
+            ctx.reason_repository.clone(),
+            ctx.reason_reference_repository.clone(),
+        );
+
+        let audit_log = create_test_audit_log();
+        let person = create_test_person("In Memory Invalid Reason");
+        let person_id = person.id;
+        let status_before = person.status;
+        ctx.person_repository.create_batch(vec![person], Some(audit_log.id)).await?;
+
+        let mut sanctions = create_test_reason_with_context("STATUS_SANCTIONS", "Sanctions list match", ReasonContext::Compliance);
+        sanctions.requires_details = true;
+        let loan = create_test_reason_with_context("STATUS_LOAN", "Loan rejected", ReasonContext::Loan);
+        let (sanctions_id, loan_id) = (sanctions.id, loan.id);
+        ctx.reason_repository.create_batch(vec![sanctions, loan], None).await?;
+
+        let change_audit_log = create_test_audit_log();
+        let audit = AuditContext::from(&change_audit_log);
+        let error = service
+            .change_status(person_id, PersonStatus::Blacklisted, sanctions_id, None, &audit)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RepositoryError>(),
+            Some(RepositoryError::MissingReasonDetails { codes, .. }) if codes == &vec!["STATUS_SANCTIONS".to_string()]
+        ));
+
+        let error = service
+            .change_status(person_id, PersonStatus::Blacklisted, loan_id, None, &audit)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<PersonServiceError>(),
+            Some(PersonServiceError::ReasonContextMismatch { context: ReasonContext::Loan, .. })
+        ));
+
+        let loaded = ctx.person_repository.load_batch(&[person_id]).await?;
+        assert_eq!(loaded[0].as_ref().unwrap().status, status_before);
+        assert!(ctx.reason_reference_repository.is_empty());
+
+        // Once the details are given, the change goes through
+        let details = HeaplessString::try_from("Matched on the consolidated sanctions list").unwrap();
+        service
+            .change_status(person_id, PersonStatus::Blacklisted, sanctions_id, Some(details), &audit)
+            .await?;
+        assert_eq!(ctx.reason_reference_repository.len(), 1);
+
+        Ok(())
+    }
+}
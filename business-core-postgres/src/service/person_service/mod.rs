@@ -0,0 +1,7 @@
+pub mod service_impl;
+pub mod organization_tree;
+pub mod change_status;
+
+pub use service_impl::{PersonService, PersonServiceError};
+pub use organization_tree::OrgNode;
+pub use change_status::StatusChange;
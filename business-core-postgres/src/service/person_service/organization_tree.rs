@@ -0,0 +1,227 @@
+use business_core_db::models::person::person::PersonIdxModel;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::PersonService;
+
+/// Node of an organization hierarchy
+#[derive(Debug, Clone)]
+pub struct OrgNode {
+    pub person: PersonIdxModel,
+    /// Persons whose `organization_person_id` points to this person
+    pub children: Vec<OrgNode>,
+}
+
+impl PersonService {
+    /// Build the organization tree below `root_person_id`
+    ///
+    /// Members are resolved breadth-first, one `find_by_organization_person_ids` call per level,
+    /// which is served from the person_idx cache when the cache policy allows it.
+    /// A person already placed in the tree is not visited again, which breaks cycles.
+    /// At most `max_depth` levels below the root are resolved.
+    pub async fn organization_tree(
+        &self,
+        root_person_id: Uuid,
+        max_depth: usize,
+    ) -> Result<OrgNode, Box<dyn Error + Send + Sync>> {
+        let root = self
+            .load_person_idx(root_person_id)
+            .await?
+            .ok_or_else(|| format!("Person {root_person_id} not found"))?;
+
+        let mut visited = HashSet::from([root_person_id]);
+        let mut children_by_parent: HashMap<Uuid, Vec<PersonIdxModel>> = HashMap::new();
+        let mut level = vec![root_person_id];
+
+        for _ in 0..max_depth {
+            if level.is_empty() {
+                break;
+            }
+            let members = self
+                .person_repository
+                .find_by_organization_person_ids(&level)
+                .await?;
+
+            let mut next_level = Vec::new();
+            for member in members {
+                let parent_id = match member.organization_person_id {
+                    Some(parent_id) => parent_id,
+                    None => continue,
+                };
+                if !visited.insert(member.id) {
+                    continue;
+                }
+                next_level.push(member.id);
+                children_by_parent.entry(parent_id).or_default().push(member);
+            }
+            level = next_level;
+        }
+
+        Ok(build_org_node(root, &mut children_by_parent))
+    }
+
+    /// List the organizations above `person_id`, nearest first
+    ///
+    /// Follows `organization_person_id` upwards until a person without organization,
+    /// a missing person, or a person already seen (cycle).
+    pub async fn organization_ancestors(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Vec<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        let mut ancestors = Vec::new();
+        let mut visited = HashSet::from([person_id]);
+        let mut current = self.load_person_idx(person_id).await?;
+
+        while let Some(parent_id) = current.as_ref().and_then(|person| person.organization_person_id) {
+            if !visited.insert(parent_id) {
+                break;
+            }
+            current = self.load_person_idx(parent_id).await?;
+            if let Some(parent) = &current {
+                ancestors.push(parent.clone());
+            }
+        }
+
+        Ok(ancestors)
+    }
+
+    async fn load_person_idx(
+        &self,
+        person_id: Uuid,
+    ) -> Result<Option<PersonIdxModel>, Box<dyn Error + Send + Sync>> {
+        if self.person_repository.serves_from_cache() {
+            let cache = self.person_repository.person_idx_cache.read().await;
+            return Ok(cache.get_by_primary(&person_id));
+        }
+        let hash_version = self.person_repository.hash_version;
+        Ok(self
+            .person_repository
+            .load(person_id)
+            .await?
+            .map(|person| person.to_index_with_hash_version(hash_version)))
+    }
+}
+
+fn build_org_node(
+    person: PersonIdxModel,
+    children_by_parent: &mut HashMap<Uuid, Vec<PersonIdxModel>>,
+) -> OrgNode {
+    let children = children_by_parent
+        .remove(&person.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_org_node(child, children_by_parent))
+        .collect();
+    OrgNode { person, children }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::{create_test_audit_log, create_test_person};
+    use crate::service::person_service::PersonService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    #[tokio::test]
+    async fn test_organization_tree_three_levels() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let holding = create_test_person("holding");
+        let mut subsidiary_a = create_test_person("subsidiary-a");
+        subsidiary_a.organization_person_id = Some(holding.id);
+        let mut subsidiary_b = create_test_person("subsidiary-b");
+        subsidiary_b.organization_person_id = Some(holding.id);
+        let mut department = create_test_person("department");
+        department.organization_person_id = Some(subsidiary_a.id);
+
+        let (holding_id, subsidiary_a_id, department_id) = (holding.id, subsidiary_a.id, department.id);
+        person_repo
+            .create_batch(vec![holding, subsidiary_a, subsidiary_b, department], Some(audit_log.id))
+            .await?;
+
+        let tree = service.organization_tree(holding_id, 5).await?;
+        assert_eq!(tree.person.id, holding_id);
+        assert_eq!(tree.children.len(), 2);
+        let branch_a = tree
+            .children
+            .iter()
+            .find(|child| child.person.id == subsidiary_a_id)
+            .expect("subsidiary-a should be in the tree");
+        assert_eq!(branch_a.children.len(), 1);
+        assert_eq!(branch_a.children[0].person.id, department_id);
+
+        let ancestors = service.organization_ancestors(department_id).await?;
+        let ancestor_ids: Vec<_> = ancestors.iter().map(|person| person.id).collect();
+        assert_eq!(ancestor_ids, vec![subsidiary_a_id, holding_id]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_organization_tree_cyclic_pair() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        let mut first = create_test_person("cyclic-first");
+        let mut second = create_test_person("cyclic-second");
+        first.organization_person_id = Some(second.id);
+        second.organization_person_id = Some(first.id);
+        let (first_id, second_id) = (first.id, second.id);
+        person_repo.create_batch(vec![first, second], Some(audit_log.id)).await?;
+
+        let tree = service.organization_tree(first_id, 10).await?;
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].person.id, second_id);
+        assert!(tree.children[0].children.is_empty());
+
+        let ancestors = service.organization_ancestors(first_id).await?;
+        assert_eq!(ancestors.len(), 1);
+        assert_eq!(ancestors[0].id, second_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_organization_tree_depth_cap() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repo = &ctx.person_repos().person_repository;
+        let service = PersonService::new(ctx.person_repos(), ctx.reason_and_purpose_repos());
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+
+        // Chain of 4 levels: root <- level1 <- level2 <- level3
+        let root = create_test_person("depth-root");
+        let root_id = root.id;
+        let mut persons = vec![root];
+        for i in 1..4 {
+            let mut person = create_test_person(&format!("depth-level-{i}"));
+            person.organization_person_id = Some(persons[i - 1].id);
+            persons.push(person);
+        }
+        person_repo.create_batch(persons, Some(audit_log.id)).await?;
+
+        let tree = service.organization_tree(root_id, 2).await?;
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].children.len(), 1);
+        assert!(tree.children[0].children[0].children.is_empty());
+
+        let unbounded = service.organization_tree(root_id, 10).await?;
+        assert_eq!(unbounded.children[0].children[0].children.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use business_core_db::models::person::common_enums::PersonStatus;
+use business_core_db::models::reason_and_purpose::reason::ReasonContext;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::repository::person::{PersonRepositories, PersonRepositoryImpl};
+use crate::repository::reason_and_purpose::{
+    ReasonAndPurposeRepositories, ReasonReferenceRepositoryImpl, ReasonRepositoryImpl,
+};
+
+/// Typed error of the person service
+#[derive(Debug, Error)]
+pub enum PersonServiceError {
+    #[error("Person {0} not found")]
+    PersonNotFound(Uuid),
+
+    #[error("Reason {0} not found")]
+    ReasonNotFound(Uuid),
+
+    #[error("Reason {reason_id} has context {context}, a status change needs Customer or Compliance")]
+    ReasonContextMismatch {
+        reason_id: Uuid,
+        context: ReasonContext,
+    },
+
+    #[error("Person {person_id} already has status {status:?}")]
+    StatusUnchanged { person_id: Uuid, status: PersonStatus },
+}
+
+/// Service for cross-entity operations of the person module
+///
+/// Generic over its repositories: `change_status` only needs the repository traits and
+/// runs on the in-memory repositories of `business_core_db` as well. The operations
+/// reading the audit and idx tables need the Postgres repositories, the defaults.
+pub struct PersonService<
+    P = PersonRepositoryImpl,
+    R = ReasonRepositoryImpl,
+    RR = ReasonReferenceRepositoryImpl,
+> {
+    pub person_repository: Arc<P>,
+    pub reason_repository: Arc<R>,
+    pub reason_reference_repository: Arc<RR>,
+}
+
+impl PersonService {
+    pub fn new(
+        person_repos: &PersonRepositories,
+        reason_and_purpose_repos: &ReasonAndPurposeRepositories,
+    ) -> Self {
+        Self {
+            person_repository: person_repos.person_repository.clone(),
+            reason_repository: reason_and_purpose_repos.reason_repository.clone(),
+            reason_reference_repository: reason_and_purpose_repos.reason_reference_repository.clone(),
+        }
+    }
+}
+
+impl<P, R, RR> PersonService<P, R, RR> {
+    /// Service over the given repositories, e.g. in-memory ones
+    pub fn with_repositories(
+        person_repository: Arc<P>,
+        reason_repository: Arc<R>,
+        reason_reference_repository: Arc<RR>,
+    ) -> Self {
+        Self {
+            person_repository,
+            reason_repository,
+            reason_reference_repository,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod usage_counts;
+
+pub use service_impl::ReasonAnalyticsService;
+pub use usage_counts::ReasonUsage;
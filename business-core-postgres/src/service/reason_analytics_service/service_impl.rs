@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use crate::repository::reason_and_purpose::{ReasonAndPurposeRepositories, ReasonRepositoryImpl};
+
+/// Service reporting how the reasons of the catalog are used
+pub struct ReasonAnalyticsService {
+    pub reason_repository: Arc<ReasonRepositoryImpl>,
+}
+
+impl ReasonAnalyticsService {
+    pub fn new(repos: &ReasonAndPurposeRepositories) -> Self {
+        Self {
+            reason_repository: repos.reason_repository.clone(),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use business_core_db::models::reason_and_purpose::reason::ReasonContext;
+use chrono::{DateTime, Utc};
+use heapless::String as HeaplessString;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::utils::get_heapless_string;
+
+use super::service_impl::ReasonAnalyticsService;
+
+/// How often a reason has been applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReasonUsage {
+    pub reason_id: Uuid,
+    pub code: HeaplessString<50>,
+    /// Number of reason_reference rows of the reason
+    pub reference_count: i64,
+    /// Creation time of the latest reference, `None` when never applied
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ReasonAnalyticsService {
+    /// Usage of every reason, optionally of one context, most used first
+    ///
+    /// Reasons never applied are included with a count of 0. Ties are ordered by code.
+    pub async fn usage_counts(
+        &self,
+        context: Option<ReasonContext>,
+    ) -> Result<Vec<ReasonUsage>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.reason_repository.operation_timeout.lock("reason", &self.reason_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT r.id, r.code, COUNT(rr.id) AS reference_count, MAX(rr.created_at) AS last_used_at
+                FROM reason r
+                LEFT JOIN reason_reference rr ON rr.reason_id = r.id
+                WHERE $1::reason_context IS NULL OR r.context = $1
+                GROUP BY r.id, r.code
+                ORDER BY reference_count DESC, r.code, r.id
+                "#,
+            )
+            .bind(context)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("reason_reference", e))?
+        };
+
+        rows.iter().map(reason_usage_from_row).collect()
+    }
+
+    /// Active reasons not applied since `cutoff`, most used first
+    ///
+    /// Candidates for pruning: their `reference_count` and `last_used_at` cover
+    /// references older than the cutoff.
+    pub async fn unused_since(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<ReasonUsage>, Box<dyn Error + Send + Sync>> {
+        let rows = {
+            let mut tx = self.reason_repository.operation_timeout.lock("reason", &self.reason_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(
+                r#"
+                SELECT r.id, r.code, COUNT(rr.id) AS reference_count, MAX(rr.created_at) AS last_used_at
+                FROM reason r
+                LEFT JOIN reason_reference rr ON rr.reason_id = r.id
+                WHERE r.is_active
+                GROUP BY r.id, r.code
+                HAVING COUNT(rr.id) FILTER (WHERE rr.created_at >= $1) = 0
+                ORDER BY reference_count DESC, r.code, r.id
+                "#,
+            )
+            .bind(cutoff)
+            .fetch_all(&mut **transaction)
+            .await
+            .map_err(|e| map_db_error("reason_reference", e))?
+        };
+
+        rows.iter().map(reason_usage_from_row).collect()
+    }
+}
+
+fn reason_usage_from_row(row: &PgRow) -> Result<ReasonUsage, Box<dyn Error + Send + Sync>> {
+    Ok(ReasonUsage {
+        reason_id: row.try_get("id")?,
+        code: get_heapless_string(row, "code")?,
+        reference_count: row.try_get("reference_count")?,
+        last_used_at: row.try_get("last_used_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ReasonAnalyticsService;
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::repository::reason_and_purpose::reason_reference_repository::test_utils::create_test_reason_reference;
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason_with_context;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::reason_and_purpose::reason::ReasonContext;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use chrono::{Duration, Utc};
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn unique_code(prefix: &str) -> String {
+        format!("{prefix}_{}", &Uuid::new_v4().simple().to_string()[..8])
+    }
+
+    #[tokio::test]
+    async fn test_usage_counts_and_unused_report() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let repos = ctx.reason_and_purpose_repos();
+
+        let reasons = vec![
+            create_test_reason_with_context(&unique_code("USED_TWICE"), "Used twice", ReasonContext::Customer),
+            create_test_reason_with_context(&unique_code("USED_ONCE"), "Used once", ReasonContext::Customer),
+            create_test_reason_with_context(&unique_code("UNUSED"), "Unused", ReasonContext::Customer),
+        ];
+        let ids: Vec<Uuid> = reasons.iter().map(|reason| reason.id).collect();
+        let mut retired = create_test_reason_with_context(&unique_code("RETIRED"), "Retired", ReasonContext::Customer);
+        retired.is_active = false;
+        let retired_id = retired.id;
+        repos
+            .reason_repository
+            .create_batch(reasons.into_iter().chain([retired]).collect(), None)
+            .await?;
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let mut old = create_test_reason_reference(ids[1], Uuid::new_v4());
+        old.created_at = Utc::now() - Duration::days(400);
+        let first = create_test_reason_reference(ids[0], Uuid::new_v4());
+        let latest = create_test_reason_reference(ids[0], Uuid::new_v4());
+        let latest_created_at = latest.created_at;
+        repos
+            .reason_reference_repository
+            .create_batch(
+                vec![first, latest, old],
+                Some(audit_log.id),
+            )
+            .await?;
+
+        let service = ReasonAnalyticsService::new(repos);
+        let ours: HashSet<Uuid> = ids.iter().copied().chain([retired_id]).collect();
+
+        let usage: Vec<_> = service
+            .usage_counts(Some(ReasonContext::Customer))
+            .await?
+            .into_iter()
+            .filter(|usage| ours.contains(&usage.reason_id))
+            .collect();
+        let counts: Vec<(Uuid, i64)> = usage.iter().map(|usage| (usage.reason_id, usage.reference_count)).collect();
+        assert_eq!(counts[..2], [(ids[0], 2), (ids[1], 1)]);
+        assert!(counts[2..].iter().all(|(_, count)| *count == 0));
+        assert_eq!(counts.len(), 4);
+        let last_used_at = usage[0].last_used_at.expect("Used reason has a last use");
+        assert!((last_used_at - latest_created_at).num_milliseconds().abs() < 1);
+        assert!(usage[2].last_used_at.is_none());
+
+        // Other contexts leave these reasons out
+        let transaction_usage = service.usage_counts(Some(ReasonContext::Transaction)).await?;
+        assert!(transaction_usage.iter().all(|usage| !ours.contains(&usage.reason_id)));
+
+        // Since the old reference, only the reason never applied is unused
+        let unused: Vec<Uuid> = service
+            .unused_since(Utc::now() - Duration::days(500))
+            .await?
+            .into_iter()
+            .map(|usage| usage.reason_id)
+            .filter(|id| ours.contains(id))
+            .collect();
+        assert_eq!(unused, vec![ids[2]]);
+
+        // Since yesterday, the reason applied 400 days ago is unused too, most used first
+        let unused: Vec<(Uuid, i64)> = service
+            .unused_since(Utc::now() - Duration::days(1))
+            .await?
+            .into_iter()
+            .map(|usage| (usage.reason_id, usage.reference_count))
+            .filter(|(id, _)| ours.contains(id))
+            .collect();
+        assert_eq!(unused, vec![(ids[1], 1), (ids[2], 0)]);
+
+        Ok(())
+    }
+}
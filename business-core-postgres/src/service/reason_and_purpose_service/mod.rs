@@ -0,0 +1,7 @@
+pub mod service_impl;
+pub mod verify_integrity;
+pub mod repair_integrity;
+
+pub use service_impl::ReasonAndPurposeService;
+pub use verify_integrity::{DanglingComplianceMetadataReference, IntegrityReport};
+pub use repair_integrity::RepairMode;
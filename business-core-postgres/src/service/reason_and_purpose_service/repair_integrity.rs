@@ -0,0 +1,129 @@
+use business_core_db::models::audit::audit_context::AuditContext;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::repository::update_batch::UpdateBatch;
+use std::error::Error;
+
+use super::service_impl::ReasonAndPurposeService;
+use super::verify_integrity::IntegrityReport;
+
+/// How `repair` handles dangling compliance_metadata references
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairMode {
+    /// Set the dangling references to null
+    Clear,
+    /// Return an error listing the dangling references, without modifying anything
+    Fail,
+}
+
+impl ReasonAndPurposeService {
+    /// Run `verify_integrity` and handle dangling references according to `mode`
+    ///
+    /// With `RepairMode::Clear` the affected reasons are updated with `compliance_metadata`
+    /// set to null under the audit log of `audit`.
+    ///
+    /// # Returns
+    /// * `Ok(IntegrityReport)` - The report of the dangling references found (and cleared)
+    /// * `Err` - With `RepairMode::Fail` when dangling references exist, or on database errors
+    pub async fn repair(
+        &self,
+        mode: RepairMode,
+        audit: &AuditContext,
+    ) -> Result<IntegrityReport, Box<dyn Error + Send + Sync>> {
+        let report = self.verify_integrity().await?;
+        if report.is_clean() {
+            return Ok(report);
+        }
+
+        if mode == RepairMode::Fail {
+            let codes: Vec<&str> = report
+                .dangling
+                .iter()
+                .flat_map(|dangling| dangling.reason_codes.iter().map(String::as_str))
+                .collect();
+            return Err(format!(
+                "Dangling compliance_metadata references on reasons: {}",
+                codes.join(", ")
+            )
+            .into());
+        }
+
+        let reasons: Vec<_> = self
+            .reason_repository
+            .load_batch(&report.affected_reason_ids())
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|mut reason| {
+                reason.compliance_metadata = None;
+                reason
+            })
+            .collect();
+        self.reason_repository
+            .update_batch(reasons, Some(audit.audit_log_id))
+            .await?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::person::test_utils::create_test_audit_log;
+    use crate::repository::reason_and_purpose::compliance_metadata_repository::test_utils::test_utils::create_test_compliance_metadata;
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason_with_compliance_metadata;
+    use crate::service::reason_and_purpose_service::{ReasonAndPurposeService, RepairMode};
+    use crate::test_helper::setup_test_context;
+    use business_core_db::models::audit::audit_context::AuditContext;
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    #[tokio::test]
+    async fn test_repair_fail_and_clear() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let repos = ctx.reason_and_purpose_repos();
+        let service = ReasonAndPurposeService::new(repos);
+
+        let metadata = create_test_compliance_metadata(Some("REG-REPAIR"), true, false);
+        let saved_metadata = repos.compliance_metadata_repository.create_batch(vec![metadata], None).await?;
+        let removed_id = saved_metadata[0].id;
+
+        let reason = create_test_reason_with_compliance_metadata("REPAIR_DANGLING", "Dangling reference", Some(removed_id));
+        let saved_reasons = repos.reason_repository.create_batch(vec![reason], None).await?;
+        let reason_id = saved_reasons[0].id;
+
+        {
+            let mut tx = repos.compliance_metadata_repository.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("DELETE FROM compliance_metadata WHERE id = $1")
+                .bind(removed_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        // No listener runs in this context, apply the cache notification by hand
+        repos
+            .compliance_metadata_repository
+            .compliance_metadata_idx_cache
+            .read()
+            .await
+            .remove(&removed_id);
+
+        let audit_log = create_test_audit_log();
+        ctx.audit_repos().audit_log_repository.create(&audit_log).await?;
+        let audit = AuditContext::from(&audit_log);
+
+        let result = service.repair(RepairMode::Fail, &audit).await;
+        let error = result.expect_err("Fail mode should report dangling references");
+        assert!(error.to_string().contains("REPAIR_DANGLING"));
+
+        let report = service.repair(RepairMode::Clear, &audit).await?;
+        assert!(report.affected_reason_ids().contains(&reason_id));
+
+        let report = service.verify_integrity().await?;
+        assert!(report.dangling.iter().all(|d| d.compliance_metadata_id != removed_id));
+
+        let cache = repos.reason_repository.reason_idx_cache.read().await;
+        let idx = cache.get_by_primary(&reason_id).expect("Reason should stay in cache");
+        assert!(idx.compliance_metadata.is_none());
+
+        Ok(())
+    }
+}
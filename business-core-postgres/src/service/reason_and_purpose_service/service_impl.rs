@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use crate::repository::reason_and_purpose::{
+    ComplianceMetadataRepositoryImpl, ReasonAndPurposeRepositories, ReasonRepositoryImpl,
+};
+
+/// Service for cross-repository operations of the reason_and_purpose module
+pub struct ReasonAndPurposeService {
+    pub reason_repository: Arc<ReasonRepositoryImpl>,
+    pub compliance_metadata_repository: Arc<ComplianceMetadataRepositoryImpl>,
+}
+
+impl ReasonAndPurposeService {
+    pub fn new(repos: &ReasonAndPurposeRepositories) -> Self {
+        Self {
+            reason_repository: repos.reason_repository.clone(),
+            compliance_metadata_repository: repos.compliance_metadata_repository.clone(),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use business_core_db::repository::exist_by_ids::ExistByIds;
+use sqlx::Row;
+use std::collections::BTreeMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use super::service_impl::ReasonAndPurposeService;
+
+/// A compliance_metadata id referenced by reasons but no longer existing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingComplianceMetadataReference {
+    pub compliance_metadata_id: Uuid,
+    /// Ids of the reasons holding the reference
+    pub reason_ids: Vec<Uuid>,
+    /// Codes of the reasons holding the reference, in the order of `reason_ids`
+    pub reason_codes: Vec<String>,
+}
+
+/// Result of a reason to compliance_metadata integrity check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Number of reasons with a non-null compliance_metadata reference
+    pub checked_reasons: usize,
+    /// Dangling references, one entry per missing compliance_metadata id
+    pub dangling: Vec<DanglingComplianceMetadataReference>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.dangling.is_empty()
+    }
+
+    /// Ids of all reasons holding a dangling reference
+    pub fn affected_reason_ids(&self) -> Vec<Uuid> {
+        self.dangling
+            .iter()
+            .flat_map(|dangling| dangling.reason_ids.iter().copied())
+            .collect()
+    }
+}
+
+impl ReasonAndPurposeService {
+    /// Check that every compliance_metadata referenced from reason_idx still exists
+    ///
+    /// Referenced ids are collected from reason_idx and bulk-checked with
+    /// `ComplianceMetadataRepositoryImpl::exist_by_ids`.
+    pub async fn verify_integrity(&self) -> Result<IntegrityReport, Box<dyn Error + Send + Sync>> {
+        let query = r#"
+            SELECT ri.id, ri.compliance_metadata, r.code
+            FROM reason_idx ri
+            JOIN reason r ON r.id = ri.id
+            WHERE ri.compliance_metadata IS NOT NULL
+            ORDER BY r.code
+        "#;
+
+        let rows = {
+            let mut tx = self.reason_repository.operation_timeout.lock("reason", &self.reason_repository.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query(query).fetch_all(&mut **transaction).await?
+        };
+
+        let mut references: BTreeMap<Uuid, Vec<(Uuid, String)>> = BTreeMap::new();
+        for row in &rows {
+            let reason_id: Uuid = row.get("id");
+            let compliance_metadata_id: Uuid = row.get("compliance_metadata");
+            let code: String = row.get("code");
+            references
+                .entry(compliance_metadata_id)
+                .or_default()
+                .push((reason_id, code));
+        }
+
+        let referenced_ids: Vec<Uuid> = references.keys().copied().collect();
+        let existence = self
+            .compliance_metadata_repository
+            .exist_by_ids(&referenced_ids)
+            .await?;
+
+        let mut dangling = Vec::new();
+        for (compliance_metadata_id, exists) in existence {
+            if exists {
+                continue;
+            }
+            let holders = references.remove(&compliance_metadata_id).unwrap_or_default();
+            let (reason_ids, reason_codes) = holders.into_iter().unzip();
+            dangling.push(DanglingComplianceMetadataReference {
+                compliance_metadata_id,
+                reason_ids,
+                reason_codes,
+            });
+        }
+
+        Ok(IntegrityReport {
+            checked_reasons: rows.len(),
+            dangling,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repository::reason_and_purpose::compliance_metadata_repository::test_utils::test_utils::create_test_compliance_metadata;
+    use crate::repository::reason_and_purpose::reason_repository::test_utils::test_utils::create_test_reason_with_compliance_metadata;
+    use crate::service::reason_and_purpose_service::ReasonAndPurposeService;
+    use crate::test_helper::setup_test_context;
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    #[tokio::test]
+    async fn test_verify_integrity_detects_dangling_reference() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let repos = ctx.reason_and_purpose_repos();
+        let service = ReasonAndPurposeService::new(repos);
+
+        let kept = create_test_compliance_metadata(Some("REG-KEPT"), true, false);
+        let removed = create_test_compliance_metadata(Some("REG-REMOVED"), true, false);
+        let saved_metadata = repos
+            .compliance_metadata_repository
+            .create_batch(vec![kept, removed], None)
+            .await?;
+        let kept_id = saved_metadata[0].id;
+        let removed_id = saved_metadata[1].id;
+
+        let reasons = vec![
+            create_test_reason_with_compliance_metadata("INTEGRITY_OK", "Valid reference", Some(kept_id)),
+            create_test_reason_with_compliance_metadata("INTEGRITY_DANGLING", "Dangling reference", Some(removed_id)),
+        ];
+        let saved_reasons = repos.reason_repository.create_batch(reasons, None).await?;
+
+        let report = service.verify_integrity().await?;
+        assert!(report.dangling.iter().all(|d| d.compliance_metadata_id != removed_id));
+
+        // Remove the compliance metadata behind the repository's back
+        {
+            let mut tx = repos.compliance_metadata_repository.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("DELETE FROM compliance_metadata WHERE id = $1")
+                .bind(removed_id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        // No listener runs in this context, apply the cache notification by hand
+        repos
+            .compliance_metadata_repository
+            .compliance_metadata_idx_cache
+            .read()
+            .await
+            .remove(&removed_id);
+
+        let report = service.verify_integrity().await?;
+        assert!(!report.is_clean());
+        let dangling = report
+            .dangling
+            .iter()
+            .find(|d| d.compliance_metadata_id == removed_id)
+            .expect("Dangling reference should be reported");
+        assert_eq!(dangling.reason_ids, vec![saved_reasons[1].id]);
+        assert_eq!(dangling.reason_codes, vec!["INTEGRITY_DANGLING".to_string()]);
+        assert!(report.dangling.iter().all(|d| d.compliance_metadata_id != kept_id));
+
+        Ok(())
+    }
+}
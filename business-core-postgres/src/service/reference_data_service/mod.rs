@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod reference_snapshot;
+
+pub use service_impl::{ReferenceDataError, ReferenceDataService};
+pub use reference_snapshot::{ReferenceSnapshot, REFERENCE_SNAPSHOT_VERSION};
@@ -0,0 +1,195 @@
+use business_core_db::models::calendar::business_day::BusinessDayModel;
+use business_core_db::models::calendar::weekend_days::WeekendDaysModel;
+use business_core_db::models::person::country::CountryModel;
+use business_core_db::models::person::country_subdivision::CountrySubdivisionModel;
+use business_core_db::models::person::locality::LocalityModel;
+use business_core_db::repository::create_batch::CreateBatch;
+use business_core_db::repository::load_batch::LoadBatch;
+use business_core_db::HasPrimaryKey;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Postgres;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::repository::RefreshIdxCache;
+
+use super::service_impl::{ReferenceDataError, ReferenceDataService};
+
+/// Format version of `ReferenceSnapshot`, raised on any incompatible change of its layout
+pub const REFERENCE_SNAPSHOT_VERSION: u32 = 1;
+
+/// The whole reference dataset, for disaster recovery and environment cloning
+///
+/// Each list is ordered by id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceSnapshot {
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub countries: Vec<CountryModel>,
+    pub country_subdivisions: Vec<CountrySubdivisionModel>,
+    pub localities: Vec<LocalityModel>,
+    pub weekend_days: Vec<WeekendDaysModel>,
+    pub business_days: Vec<BusinessDayModel>,
+}
+
+impl ReferenceDataService {
+    /// Read every row of the reference dataset into one snapshot
+    pub async fn export_reference_snapshot(&self) -> Result<ReferenceSnapshot, Box<dyn Error + Send + Sync>> {
+        Ok(ReferenceSnapshot {
+            schema_version: REFERENCE_SNAPSHOT_VERSION,
+            exported_at: Utc::now(),
+            countries: load_all(self.country_repository.as_ref(), |item: &CountryModel| item.id).await?,
+            country_subdivisions: load_all(self.country_subdivision_repository.as_ref(), |item: &CountrySubdivisionModel| item.id).await?,
+            localities: load_all(self.locality_repository.as_ref(), |item: &LocalityModel| item.id).await?,
+            weekend_days: load_all(self.weekend_days_repository.as_ref(), |item: &WeekendDaysModel| item.id).await?,
+            business_days: load_all(self.business_day_repository.as_ref(), |item: &BusinessDayModel| item.id).await?,
+        })
+    }
+
+    /// Restore a snapshot made by `export_reference_snapshot`
+    ///
+    /// The rows are created in dependency order through the regular `create_batch`, in the
+    /// transaction of the session. Rows of the snapshot must not exist yet, the target is
+    /// expected to hold no reference data. On error nothing is applied once the session
+    /// is rolled back. A snapshot of another format version yields
+    /// `ReferenceDataError::UnsupportedSnapshotVersion`.
+    pub async fn import_reference_snapshot(
+        &self,
+        snapshot: ReferenceSnapshot,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if snapshot.schema_version != REFERENCE_SNAPSHOT_VERSION {
+            return Err(ReferenceDataError::UnsupportedSnapshotVersion {
+                found: snapshot.schema_version,
+                expected: REFERENCE_SNAPSHOT_VERSION,
+            }
+            .into());
+        }
+
+        if !snapshot.countries.is_empty() {
+            self.country_repository.create_batch(snapshot.countries, None).await?;
+        }
+        if !snapshot.country_subdivisions.is_empty() {
+            self.country_subdivision_repository
+                .create_batch(snapshot.country_subdivisions, None)
+                .await?;
+        }
+        if !snapshot.localities.is_empty() {
+            self.locality_repository.create_batch(snapshot.localities, None).await?;
+        }
+        if !snapshot.weekend_days.is_empty() {
+            self.weekend_days_repository.create_batch(snapshot.weekend_days, None).await?;
+        }
+        if !snapshot.business_days.is_empty() {
+            self.business_day_repository.create_batch(snapshot.business_days, None).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every row of the table of `repo`, ordered by id
+///
+/// The ids are read from the idx table, which holds one row per main row.
+async fn load_all<R, T>(repo: &R, id_of: impl Fn(&T) -> Uuid) -> Result<Vec<T>, Box<dyn Error + Send + Sync>>
+where
+    R: RefreshIdxCache + LoadBatch<Postgres, T>,
+{
+    let ids: Vec<Uuid> = repo
+        .load_all_idx()
+        .await?
+        .iter()
+        .map(HasPrimaryKey::primary_key)
+        .collect();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut items: Vec<T> = repo.load_batch(&ids).await?.into_iter().flatten().collect();
+    items.sort_by_key(|item| id_of(item));
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReferenceSnapshot, REFERENCE_SNAPSHOT_VERSION};
+    use crate::repository::calendar::business_day_repository::test_utils::test_utils::create_test_business_day_holiday;
+    use crate::repository::calendar::weekend_days_repository::test_utils::test_utils::create_test_weekend_days;
+    use crate::repository::person::test_utils::{
+        create_test_country, create_test_country_subdivision, create_test_locality,
+    };
+    use crate::service::reference_data_service::{ReferenceDataError, ReferenceDataService};
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::repository::create_batch::CreateBatch;
+
+    /// The snapshot without its export time, for comparison
+    fn data_of(snapshot: &ReferenceSnapshot) -> serde_json::Value {
+        let mut value = serde_json::to_value(snapshot).unwrap();
+        value.as_object_mut().unwrap().remove("exported_at");
+        value
+    }
+
+    #[tokio::test]
+    async fn test_reference_snapshot_round_trip() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let exported = {
+            let ctx = setup_test_context().await?;
+            let person_repos = ctx.person_repos();
+            let calendar_repos = ctx.calendar_repos();
+
+            let country = create_test_country(&random(2).to_uppercase(), "Snapshot Country");
+            let country_id = country.id;
+            person_repos.country_repository.create_batch(vec![country], None).await?;
+            let subdivision = create_test_country_subdivision(country_id, &random(6), "Snapshot Region");
+            let subdivision_id = subdivision.id;
+            person_repos
+                .country_subdivision_repository
+                .create_batch(vec![subdivision], None)
+                .await?;
+            let locality = create_test_locality(subdivision_id, &random(8), "Snapshot Town");
+            person_repos.locality_repository.create_batch(vec![locality], None).await?;
+            let weekend_days = create_test_weekend_days(Some(country_id), None);
+            let weekend_days_id = weekend_days.id;
+            calendar_repos.weekend_days_repository.create_batch(vec![weekend_days], None).await?;
+            let mut holiday = create_test_business_day_holiday(Some(country_id), "Snapshot Day");
+            holiday.weekend_day_01 = Some(weekend_days_id);
+            calendar_repos.business_day_repository.create_batch(vec![holiday], None).await?;
+
+            let service = ReferenceDataService::new(person_repos, calendar_repos);
+            let snapshot = service.export_reference_snapshot().await?;
+            assert_eq!(snapshot.schema_version, REFERENCE_SNAPSHOT_VERSION);
+            assert!(snapshot.countries.iter().any(|country| country.id == country_id));
+            assert!(snapshot.weekend_days.iter().any(|weekend_days| weekend_days.id == weekend_days_id));
+            snapshot
+            // The session is rolled back here
+        };
+
+        // Through the serialized form, as a snapshot is stored
+        let bytes = serde_json::to_vec(&exported)?;
+        let snapshot: ReferenceSnapshot = serde_json::from_slice(&bytes)?;
+
+        let ctx = setup_test_context().await?;
+        let service = ReferenceDataService::new(ctx.person_repos(), ctx.calendar_repos());
+        service.import_reference_snapshot(snapshot).await?;
+
+        let restored = service.export_reference_snapshot().await?;
+        assert_eq!(data_of(&restored), data_of(&exported));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_other_version() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let service = ReferenceDataService::new(ctx.person_repos(), ctx.calendar_repos());
+
+        let mut snapshot = service.export_reference_snapshot().await?;
+        snapshot.schema_version = REFERENCE_SNAPSHOT_VERSION + 1;
+        let error = service.import_reference_snapshot(snapshot).await.unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ReferenceDataError>(),
+            Some(ReferenceDataError::UnsupportedSnapshotVersion { .. })
+        ));
+
+        Ok(())
+    }
+}
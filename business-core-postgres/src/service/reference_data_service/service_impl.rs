@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::calendar::{BusinessDayRepositoryImpl, CalendarRepositories, WeekendDaysRepositoryImpl};
+use crate::repository::person::{
+    CountryRepositoryImpl, CountrySubdivisionRepositoryImpl, LocalityRepositoryImpl, PersonRepositories,
+};
+
+/// Typed error of the reference data service
+#[derive(Debug, Error)]
+pub enum ReferenceDataError {
+    #[error("Reference snapshot version {found} is not supported, expected {expected}")]
+    UnsupportedSnapshotVersion { found: u32, expected: u32 },
+}
+
+/// Service exporting and restoring the reference dataset as a whole
+///
+/// The reference dataset is made of the countries, country subdivisions, localities,
+/// weekend days and business days. The service works on repositories built for the
+/// same unit of work session, so an import is committed or rolled back as one.
+pub struct ReferenceDataService {
+    pub country_repository: Arc<CountryRepositoryImpl>,
+    pub country_subdivision_repository: Arc<CountrySubdivisionRepositoryImpl>,
+    pub locality_repository: Arc<LocalityRepositoryImpl>,
+    pub weekend_days_repository: Arc<WeekendDaysRepositoryImpl>,
+    pub business_day_repository: Arc<BusinessDayRepositoryImpl>,
+}
+
+impl ReferenceDataService {
+    pub fn new(person_repos: &PersonRepositories, calendar_repos: &CalendarRepositories) -> Self {
+        Self {
+            country_repository: person_repos.country_repository.clone(),
+            country_subdivision_repository: person_repos.country_subdivision_repository.clone(),
+            locality_repository: person_repos.locality_repository.clone(),
+            weekend_days_repository: calendar_repos.weekend_days_repository.clone(),
+            business_day_repository: calendar_repos.business_day_repository.clone(),
+        }
+    }
+}
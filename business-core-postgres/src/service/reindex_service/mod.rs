@@ -0,0 +1,5 @@
+pub mod service_impl;
+pub mod rebuild_idx;
+
+pub use service_impl::{ReindexError, ReindexService};
+pub use rebuild_idx::ReindexReport;
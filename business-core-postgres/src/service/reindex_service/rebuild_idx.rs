@@ -0,0 +1,306 @@
+use business_core_db::models::audit::EntityType;
+use business_core_db::models::index_aware::IndexAware;
+use business_core_db::models::person::entity_reference::{EntityReferenceIdxModel, EntityReferenceModel};
+use business_core_db::models::person::person::{PersonIdxModel, PersonModel};
+use std::collections::HashMap;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::error::map_db_error;
+use crate::repository::person::entity_reference_repository::columns::{ENTITY_REFERENCE_IDX_COLUMNS, ENTITY_REFERENCE_SQL};
+use crate::repository::person::person_repository::columns::{PERSON_IDX_COLUMNS, PERSON_SQL};
+use crate::utils::TryFromRow;
+
+use super::service_impl::{ReindexError, ReindexService};
+
+/// Outcome of an index rebuild
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReindexReport {
+    /// Ids whose index row was missing or differed from the main row, now rewritten
+    pub repaired: Vec<Uuid>,
+    /// Number of index rows already matching their main row
+    pub unchanged: usize,
+    /// Requested ids without a main row, left untouched
+    pub missing: Vec<Uuid>,
+}
+
+impl ReindexService {
+    /// Recompute the index rows of `ids` from their main rows and rewrite the ones that differ
+    ///
+    /// Supports `EntityType::Person` and `EntityType::EntityReference`; other
+    /// entity types yield `ReindexError::UnsupportedEntityType`. Person index rows
+    /// are computed with the repository's hash version. Rewritten rows replace
+    /// their cache entries, so finders answered from the cache see the repair too.
+    pub async fn rebuild_idx(
+        &self,
+        entity_type: EntityType,
+        ids: &[Uuid],
+    ) -> Result<ReindexReport, Box<dyn Error + Send + Sync>> {
+        match entity_type {
+            EntityType::Person => self.rebuild_person_idx(ids).await,
+            EntityType::EntityReference => self.rebuild_entity_reference_idx(ids).await,
+            other => Err(ReindexError::UnsupportedEntityType(other).into()),
+        }
+    }
+
+    async fn rebuild_person_idx(&self, ids: &[Uuid]) -> Result<ReindexReport, Box<dyn Error + Send + Sync>> {
+        let repo = &self.person_repository;
+        let mut report = ReindexReport::default();
+        if ids.is_empty() {
+            return Ok(report);
+        }
+
+        let mut repaired = Vec::new();
+        {
+            let mut tx = repo.operation_timeout.lock("person", &repo.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            let persons = sqlx::query("SELECT * FROM person WHERE id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("person", e))?
+                .iter()
+                .map(PersonModel::try_from_row)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut stored: HashMap<Uuid, PersonIdxModel> = HashMap::new();
+            for row in sqlx::query("SELECT * FROM person_idx WHERE id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("person", e))?
+            {
+                let idx = PersonIdxModel::try_from_row(&row)?;
+                stored.insert(idx.id, idx);
+            }
+
+            let found: HashMap<Uuid, PersonModel> = persons.into_iter().map(|person| (person.id, person)).collect();
+            for id in ids {
+                let Some(person) = found.get(id) else {
+                    report.missing.push(*id);
+                    continue;
+                };
+                let idx = person.to_index_with_hash_version(repo.hash_version);
+                if stored.get(id) == Some(&idx) {
+                    report.unchanged += 1;
+                    continue;
+                }
+                PERSON_IDX_COLUMNS
+                    .bind(sqlx::query(&PERSON_SQL.upsert_idx), &idx)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("person", e))?;
+                report.repaired.push(idx.id);
+                repaired.push(idx);
+            }
+        }
+
+        if repo.cache_policy.maintains_cache() {
+            let cache = repo.person_idx_cache.read().await;
+            for idx in repaired {
+                cache.remove(&idx.id);
+                cache.add(idx);
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn rebuild_entity_reference_idx(&self, ids: &[Uuid]) -> Result<ReindexReport, Box<dyn Error + Send + Sync>> {
+        let repo = &self.entity_reference_repository;
+        let mut report = ReindexReport::default();
+        if ids.is_empty() {
+            return Ok(report);
+        }
+
+        let mut repaired = Vec::new();
+        {
+            let mut tx = repo.operation_timeout.lock("entity_reference", &repo.executor.tx).await?;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            let references = sqlx::query("SELECT * FROM entity_reference WHERE id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("entity_reference", e))?
+                .iter()
+                .map(EntityReferenceModel::try_from_row)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut stored: HashMap<Uuid, EntityReferenceIdxModel> = HashMap::new();
+            for row in sqlx::query("SELECT * FROM entity_reference_idx WHERE id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&mut **transaction)
+                .await
+                .map_err(|e| map_db_error("entity_reference", e))?
+            {
+                let idx = EntityReferenceIdxModel::try_from_row(&row)?;
+                stored.insert(idx.id, idx);
+            }
+
+            let found: HashMap<Uuid, EntityReferenceModel> =
+                references.into_iter().map(|reference| (reference.id, reference)).collect();
+            for id in ids {
+                let Some(reference) = found.get(id) else {
+                    report.missing.push(*id);
+                    continue;
+                };
+                let idx = reference.to_index();
+                if stored.get(id) == Some(&idx) {
+                    report.unchanged += 1;
+                    continue;
+                }
+                ENTITY_REFERENCE_IDX_COLUMNS
+                    .bind(sqlx::query(&ENTITY_REFERENCE_SQL.upsert_idx), &idx)
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| map_db_error("entity_reference", e))?;
+                report.repaired.push(idx.id);
+                repaired.push(idx);
+            }
+        }
+
+        if repo.cache_policy.maintains_cache() {
+            let cache = repo.entity_reference_idx_cache.read().await;
+            for idx in repaired {
+                cache.remove(&idx.id);
+                cache.add(idx);
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ReindexError, ReindexReport, ReindexService};
+    use crate::repository::cache_policy::CachePolicy;
+    use crate::repository::person::test_utils::{
+        create_test_audit_log, create_test_entity_reference, create_test_person,
+    };
+    use crate::repository::person::PersonRepositoryImpl;
+    use crate::test_helper::{random, setup_test_context};
+    use business_core_db::models::audit::EntityType;
+    use business_core_db::repository::create_batch::CreateBatch;
+    use business_core_db::utils::hash_as_i64;
+    use parking_lot::RwLock as ParkingRwLock;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_rebuild_repairs_corrupted_person_idx() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+        let person_repo = &person_repos.person_repository;
+        // Finders of this repository read person_idx, so they see the corruption
+        let sql_repo = PersonRepositoryImpl::new_with_cache_policy(
+            person_repo.executor.clone(),
+            Arc::new(ParkingRwLock::new(business_core_db::IdxModelCache::new(vec![]).unwrap())),
+            CachePolicy::Disabled,
+        );
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let id_number = format!("ID-{}", random(8));
+        let id_number_hash = hash_as_i64(&id_number.as_str()).unwrap();
+        let mut person = create_test_person("Reindexed Person");
+        person.id_number = heapless::String::try_from(id_number.as_str()).unwrap();
+        let intact = create_test_person("Intact Person");
+        let saved = person_repo.create_batch(vec![person, intact], Some(audit_log.id)).await?;
+
+        {
+            let mut tx = person_repo.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("UPDATE person_idx SET id_number_hash = 0 WHERE id = $1")
+                .bind(saved[0].id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+        assert!(sql_repo.find_by_id_number_hash(id_number_hash).await?.is_empty());
+
+        let service = ReindexService::new(person_repos);
+        let unknown = Uuid::new_v4();
+        let report = service
+            .rebuild_idx(EntityType::Person, &[saved[0].id, saved[1].id, unknown])
+            .await?;
+        assert_eq!(
+            report,
+            ReindexReport {
+                repaired: vec![saved[0].id],
+                unchanged: 1,
+                missing: vec![unknown],
+            }
+        );
+
+        let found = sql_repo.find_by_id_number_hash(id_number_hash).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, saved[0].id);
+        assert_eq!(person_repo.find_by_id_number_hash(id_number_hash).await?.len(), 1);
+
+        // Running again finds nothing to repair
+        let report = service.rebuild_idx(EntityType::Person, &[saved[0].id]).await?;
+        assert!(report.repaired.is_empty());
+        assert_eq!(report.unchanged, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_restores_deleted_entity_reference_idx() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let audit_log_repo = &ctx.audit_repos().audit_log_repository;
+        let person_repos = ctx.person_repos();
+
+        let audit_log = create_test_audit_log();
+        audit_log_repo.create(&audit_log).await?;
+        let person = person_repos
+            .person_repository
+            .create_batch(vec![create_test_person("Referenced Person")], Some(audit_log.id))
+            .await?
+            .remove(0);
+        let reference = person_repos
+            .entity_reference_repository
+            .create_batch(vec![create_test_entity_reference(person.id, "REINDEX-1")], Some(audit_log.id))
+            .await?
+            .remove(0);
+
+        {
+            let mut tx = person_repos.entity_reference_repository.executor.tx.lock().await;
+            let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+            sqlx::query("DELETE FROM entity_reference_idx WHERE id = $1")
+                .bind(reference.id)
+                .execute(&mut **transaction)
+                .await?;
+        }
+
+        let service = ReindexService::new(person_repos);
+        let report = service.rebuild_idx(EntityType::EntityReference, &[reference.id]).await?;
+        assert_eq!(report.repaired, vec![reference.id]);
+
+        let mut tx = person_repos.entity_reference_repository.executor.tx.lock().await;
+        let transaction = tx.as_mut().ok_or("Transaction has been consumed")?;
+        let person_id: Uuid = sqlx::query_scalar("SELECT person_id FROM entity_reference_idx WHERE id = $1")
+            .bind(reference.id)
+            .fetch_one(&mut **transaction)
+            .await?;
+        assert_eq!(person_id, person.id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_rejects_unsupported_entity() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ctx = setup_test_context().await?;
+        let service = ReindexService::new(ctx.person_repos());
+
+        let error = service
+            .rebuild_idx(EntityType::Document, &[Uuid::new_v4()])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ReindexError>(),
+            Some(ReindexError::UnsupportedEntityType(EntityType::Document))
+        ));
+
+        Ok(())
+    }
+}
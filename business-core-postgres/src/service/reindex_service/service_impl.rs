@@ -0,0 +1,30 @@
+use business_core_db::models::audit::EntityType;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::repository::person::{EntityReferenceRepositoryImpl, PersonRepositories, PersonRepositoryImpl};
+
+/// Typed error of the reindex service
+#[derive(Debug, Error)]
+pub enum ReindexError {
+    #[error("Index rebuild is not supported for {0:?}")]
+    UnsupportedEntityType(EntityType),
+}
+
+/// Service repairing index rows from their main rows
+///
+/// Main rows are read and index rows rewritten in the same transaction, so a rebuild
+/// never mixes rows of two states.
+pub struct ReindexService {
+    pub person_repository: Arc<PersonRepositoryImpl>,
+    pub entity_reference_repository: Arc<EntityReferenceRepositoryImpl>,
+}
+
+impl ReindexService {
+    pub fn new(repos: &PersonRepositories) -> Self {
+        Self {
+            person_repository: repos.person_repository.clone(),
+            entity_reference_repository: repos.entity_reference_repository.clone(),
+        }
+    }
+}